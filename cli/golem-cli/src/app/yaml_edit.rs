@@ -18,10 +18,15 @@ use crate::model::app::{
     AppComponentName, Application, BinaryComponentSource, DependencyType, HttpApiDefinitionName,
 };
 use anyhow::{anyhow, Context};
-use nondestructive::yaml::{Document, Id, MappingMut, Separator, SequenceMut, Value, ValueMut};
+use nondestructive::yaml::{
+    Block, Chomp, Document, Id, MappingMut, Separator, SequenceMut, Value, ValueMut,
+};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
+// Fields of an HTTP API definition route binding that hold Rib scripts
+const RIB_SCRIPT_FIELDS: [&str; 3] = ["response", "idempotencyKey", "invocationContext"];
+
 pub struct AppYamlEditor<'a> {
     application: &'a Application,
     documents: HashMap<PathBuf, Document>,
@@ -235,6 +240,84 @@ impl<'a> AppYamlEditor<'a> {
         Ok(())
     }
 
+    /// Formats the Rib scripts of the route bindings of an HTTP API definition,
+    /// returns the number of scripts that were changed
+    pub fn format_api_definition_rib_scripts(
+        &mut self,
+        api_definition_name: &HttpApiDefinitionName,
+    ) -> anyhow::Result<usize> {
+        let path = self.document_path_for_api_definition(api_definition_name);
+
+        let document = self.document_mut(&path)?;
+
+        let mut formatted_scripts = Vec::<(Id, String)>::new();
+        {
+            let Some(routes) = document
+                .as_ref()
+                .as_mapping()
+                .and_then(|root| root.get("httpApi"))
+                .and_then(|http_api| http_api.as_mapping())
+                .and_then(|http_api| http_api.get("definitions"))
+                .and_then(|definitions| definitions.as_mapping())
+                .and_then(|definitions| definitions.get(api_definition_name.as_str()))
+                .and_then(|api_definition| api_definition.as_mapping())
+                .and_then(|api_definition| api_definition.get("routes"))
+                .and_then(|routes| routes.as_sequence())
+            else {
+                return Ok(0);
+            };
+
+            for route in routes.iter() {
+                let Some(binding) = route
+                    .as_mapping()
+                    .and_then(|route| route.get("binding"))
+                    .and_then(|binding| binding.as_mapping())
+                else {
+                    continue;
+                };
+
+                for field in RIB_SCRIPT_FIELDS {
+                    let Some(value) = binding.get(field) else {
+                        continue;
+                    };
+                    let Some(script) = value.as_str() else {
+                        continue;
+                    };
+
+                    let formatted = rib::format(script).map_err(|err| {
+                        anyhow!(
+                            "failed to format the Rib script of {} for HTTP API definition {} in {}:\n{}",
+                            field,
+                            api_definition_name.as_str(),
+                            path.display(),
+                            err
+                        )
+                    })?;
+
+                    if formatted.trim_end() != script.trim_end() {
+                        formatted_scripts.push((value.id(), formatted));
+                    }
+                }
+            }
+        }
+
+        let count = formatted_scripts.len();
+
+        for (id, formatted) in formatted_scripts {
+            let formatted = formatted.trim_end();
+            let mut value = document.value_mut(id);
+
+            // Multi-line scripts are kept as literal blocks, so they remain readable
+            if formatted.contains('\n') {
+                value.set_block(formatted.lines(), Block::Literal(Chomp::Clip));
+            } else {
+                value.set_string(formatted);
+            }
+        }
+
+        Ok(count)
+    }
+
     fn document_mut(&mut self, path: &Path) -> anyhow::Result<&mut Document> {
         if !self.documents.contains_key(path) {
             self.documents.insert(
@@ -12,12 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::command::admin::AdminSubcommand;
 use crate::command::api::ApiSubcommand;
 use crate::command::app::AppSubcommand;
 use crate::command::cloud::CloudSubcommand;
 use crate::command::component::ComponentSubcommand;
 use crate::command::plugin::PluginSubcommand;
 use crate::command::profile::ProfileSubcommand;
+use crate::command::rib::RibSubcommand;
 use crate::command::worker::AgentSubcommand;
 use crate::config::{BuildProfileName, ProfileName};
 use crate::log::LogColorize;
@@ -579,6 +581,16 @@ pub enum GolemCliSubcommand {
         #[clap(subcommand)]
         subcommand: CloudSubcommand,
     },
+    /// Operate the services of a self-hosted Golem cluster
+    Admin {
+        #[clap(subcommand)]
+        subcommand: AdminSubcommand,
+    },
+    /// Format Rib scripts
+    Rib {
+        #[clap(subcommand)]
+        subcommand: RibSubcommand,
+    },
     /// Start Rib REPL for a selected component
     Repl {
         #[command(flatten)]
@@ -587,12 +599,19 @@ pub enum GolemCliSubcommand {
         version: Option<u64>,
         #[command(flatten)]
         deploy_args: Option<DeployArgs>,
-        /// Optional script to run, when defined the repl will execute the script and exit
+        /// Optional script to run, when defined the repl will execute the script and exit.
+        /// The exit code is 2 if the script fails to compile, and 3 if it fails at runtime
         #[clap(long, short, conflicts_with_all = ["script_file"])]
         script: Option<String>,
-        /// Optional script_file to run, when defined the repl will execute the script and exit
+        /// Optional script_file to run, when defined the repl will execute the script and exit.
+        /// The exit code is 2 if the script fails to compile, and 3 if it fails at runtime
         #[clap(long, conflicts_with_all = ["script"])]
         script_file: Option<PathBuf>,
+        /// Optional values of the global variables (such as `request`) used in the script, as a JSON object
+        /// with the variable names as keys. Example: --input '{"request": {"path": {"user-id": "1"}}}'.
+        /// The exit code is 4 if a value doesn't match the type of the variable in the script
+        #[clap(long)]
+        input: Option<String>,
         /// Do not stream logs from the invoked agents. Can be also controlled with the :logs command in the REPL.
         #[clap(long)]
         disable_stream: bool,
@@ -955,6 +974,9 @@ pub mod component {
             force_build: ForceBuildArg,
             #[command(flatten)]
             deploy_args: DeployArgs,
+            /// Wait until the deployed component versions are compiled, so the first invocations do not have to compile them
+            #[clap(long)]
+            wait_for_compilation: bool,
         },
         /// Clean component(s) based on the current directory or by selection
         Clean {
@@ -1570,6 +1592,103 @@ pub mod plugin {
     }
 }
 
+pub mod admin {
+    use crate::command::admin::shards::ShardsSubcommand;
+    use clap::{Args, Subcommand};
+    use url::Url;
+
+    #[derive(Debug, Subcommand)]
+    pub enum AdminSubcommand {
+        /// Inspect and override the shard placement of the shard manager
+        Shards {
+            #[command(flatten)]
+            shard_manager: ShardManagerArgs,
+            #[clap(subcommand)]
+            subcommand: ShardsSubcommand,
+        },
+    }
+
+    #[derive(Debug, Args)]
+    pub struct ShardManagerArgs {
+        /// URL of the HTTP endpoint of the shard manager
+        #[arg(long, global = true, default_value = "http://localhost:8081")]
+        pub shard_manager_url: Url,
+    }
+
+    #[derive(Debug, Args)]
+    pub struct AuditArgs {
+        /// Reason of the change, recorded in the audit log of the shard manager
+        #[arg(long)]
+        pub reason: Option<String>,
+    }
+
+    pub mod shards {
+        use crate::command::admin::AuditArgs;
+        use crate::model::shard_manager::ShardManagerPodSelector;
+        use clap::Subcommand;
+
+        #[derive(Debug, Subcommand)]
+        pub enum ShardsSubcommand {
+            /// Show the current routing table, the pinned shards and whether rebalancing is frozen
+            Show,
+            /// Show the pending rebalance plan
+            Plan,
+            /// Show the audit log of the admin changes
+            AuditLog,
+            /// Pin a shard to a pod, keeping it there whenever the pod is available
+            Pin {
+                /// The shard to pin
+                shard: i64,
+                /// The pod, selected by its pod name or by <HOST>:<PORT>
+                pod: ShardManagerPodSelector,
+                #[command(flatten)]
+                audit: AuditArgs,
+            },
+            /// Remove the pin of a shard, letting the shard manager balance it again
+            Unpin {
+                /// The pinned shard
+                shard: i64,
+                #[command(flatten)]
+                audit: AuditArgs,
+            },
+            /// Freeze rebalancing, so no shard is moved between pods until unfrozen
+            ///
+            /// Shards of removed pods still get assigned, but draining pods keep their shards.
+            Freeze {
+                #[command(flatten)]
+                audit: AuditArgs,
+            },
+            /// Unfreeze rebalancing
+            Unfreeze {
+                #[command(flatten)]
+                audit: AuditArgs,
+            },
+            /// Trigger an immediate rebalance
+            Rebalance {
+                #[command(flatten)]
+                audit: AuditArgs,
+            },
+        }
+    }
+}
+
+pub mod rib {
+    use clap::Subcommand;
+    use std::path::PathBuf;
+
+    #[derive(Debug, Subcommand)]
+    pub enum RibSubcommand {
+        /// Format Rib scripts in place, by default the Rib scripts of the HTTP API definitions of the application
+        Fmt {
+            /// Rib script files to format, instead of the HTTP API definitions of the application
+            files: Vec<PathBuf>,
+            /// Only check if the Rib scripts are formatted, fails if any of them would change
+            #[clap(long)]
+            check: bool,
+        },
+    }
+}
+
 pub mod profile {
     use crate::command::profile::config::ProfileConfigSubcommand;
     use crate::config::ProfileName;
@@ -0,0 +1,206 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::command::admin::shards::ShardsSubcommand;
+use crate::command::admin::{AdminSubcommand, AuditArgs};
+use crate::command_handler::Handlers;
+use crate::context::Context;
+use crate::log::{log_action, LogColorize};
+use crate::model::shard_manager::{
+    PinShardRequest, ShardAdminRequest, ShardAuditLogEntry, ShardOverrides, ShardRebalancePlan,
+    ShardRoutingTable, UnpinShardRequest,
+};
+use crate::model::text::shard_manager::{
+    ShardAuditLogView, ShardOverridesView, ShardRebalancePlanView, ShardRoutingTableView,
+};
+use anyhow::{anyhow, bail, Context as AnyhowContext};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::sync::Arc;
+use url::Url;
+
+pub struct AdminCommandHandler {
+    ctx: Arc<Context>,
+}
+
+impl AdminCommandHandler {
+    pub fn new(ctx: Arc<Context>) -> Self {
+        Self { ctx }
+    }
+
+    pub async fn handle_command(&self, subcommand: AdminSubcommand) -> anyhow::Result<()> {
+        match subcommand {
+            AdminSubcommand::Shards {
+                shard_manager,
+                subcommand,
+            } => {
+                let client = ShardManagerAdminClient::new(
+                    self.ctx.admin_http_client()?,
+                    shard_manager.shard_manager_url,
+                );
+                self.handle_shards_command(&client, subcommand).await
+            }
+        }
+    }
+
+    async fn handle_shards_command(
+        &self,
+        client: &ShardManagerAdminClient,
+        subcommand: ShardsSubcommand,
+    ) -> anyhow::Result<()> {
+        match subcommand {
+            ShardsSubcommand::Show => {
+                let routing_table: ShardRoutingTable = client.get("v1/shards").await?;
+                self.ctx
+                    .log_handler()
+                    .log_view(&ShardRoutingTableView(routing_table));
+            }
+            ShardsSubcommand::Plan => {
+                let plan: ShardRebalancePlan = client.get("v1/shards/plan").await?;
+                self.ctx
+                    .log_handler()
+                    .log_view(&ShardRebalancePlanView(plan));
+            }
+            ShardsSubcommand::AuditLog => {
+                let entries: Vec<ShardAuditLogEntry> = client.get("v1/shards/audit-log").await?;
+                self.ctx.log_handler().log_view(&ShardAuditLogView(entries));
+            }
+            ShardsSubcommand::Pin { shard, pod, audit } => {
+                log_action(
+                    "Pinning",
+                    format!(
+                        "shard {} to pod {}",
+                        shard.to_string().log_color_highlight(),
+                        pod.to_string().log_color_highlight()
+                    ),
+                );
+                let request = PinShardRequest {
+                    shard,
+                    pod,
+                    admin: admin_request(audit),
+                };
+                self.log_overrides(client.post("v1/shards/pin", &request).await?);
+            }
+            ShardsSubcommand::Unpin { shard, audit } => {
+                log_action(
+                    "Unpinning",
+                    format!("shard {}", shard.to_string().log_color_highlight()),
+                );
+                let request = UnpinShardRequest {
+                    shard,
+                    admin: admin_request(audit),
+                };
+                self.log_overrides(client.post("v1/shards/unpin", &request).await?);
+            }
+            ShardsSubcommand::Freeze { audit } => {
+                log_action("Freezing", "rebalancing");
+                self.log_overrides(
+                    client
+                        .post("v1/rebalancing/freeze", &admin_request(audit))
+                        .await?,
+                );
+            }
+            ShardsSubcommand::Unfreeze { audit } => {
+                log_action("Unfreezing", "rebalancing");
+                self.log_overrides(
+                    client
+                        .post("v1/rebalancing/unfreeze", &admin_request(audit))
+                        .await?,
+                );
+            }
+            ShardsSubcommand::Rebalance { audit } => {
+                log_action("Triggering", "rebalance");
+                self.log_overrides(
+                    client
+                        .post("v1/rebalancing/trigger", &admin_request(audit))
+                        .await?,
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    fn log_overrides(&self, overrides: ShardOverrides) {
+        self.ctx
+            .log_handler()
+            .log_view(&ShardOverridesView(overrides));
+    }
+}
+
+/// The admin request recording the local user and the given reason in the audit log
+fn admin_request(audit: AuditArgs) -> ShardAdminRequest {
+    ShardAdminRequest {
+        requested_by: std::env::var("USER")
+            .or_else(|_| std::env::var("USERNAME"))
+            .ok(),
+        reason: audit.reason,
+    }
+}
+
+struct ShardManagerAdminClient {
+    client: reqwest::Client,
+    base_url: Url,
+}
+
+impl ShardManagerAdminClient {
+    fn new(client: reqwest::Client, base_url: Url) -> Self {
+        Self { client, base_url }
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> anyhow::Result<T> {
+        let url = self.url(path)?;
+        let response = self
+            .client
+            .get(url.clone())
+            .send()
+            .await
+            .with_context(|| anyhow!("Failed to reach the shard manager at {url}"))?;
+        Self::parse_response(response).await
+    }
+
+    async fn post<B: Serialize, T: DeserializeOwned>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> anyhow::Result<T> {
+        let url = self.url(path)?;
+        let response = self
+            .client
+            .post(url.clone())
+            .json(body)
+            .send()
+            .await
+            .with_context(|| anyhow!("Failed to reach the shard manager at {url}"))?;
+        Self::parse_response(response).await
+    }
+
+    fn url(&self, path: &str) -> anyhow::Result<Url> {
+        self.base_url
+            .join(path)
+            .with_context(|| anyhow!("Invalid shard manager URL: {}", self.base_url))
+    }
+
+    async fn parse_response<T: DeserializeOwned>(response: reqwest::Response) -> anyhow::Result<T> {
+        let status = response.status();
+        if !status.is_success() {
+            let message = response.text().await.unwrap_or_default();
+            bail!("Shard manager request failed with {status}: {message}");
+        }
+        response
+            .json()
+            .await
+            .context("Failed to parse the response of the shard manager")
+    }
+}
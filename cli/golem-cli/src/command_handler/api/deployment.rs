@@ -598,6 +598,7 @@ impl ApiDeploymentCommandHandler {
                     .map(|(name, version)| ApiDefinitionInfoCloud {
                         id: name.to_string(),
                         version: version.to_string(),
+                        traffic_split: None,
                     })
                     .collect::<Vec<_>>(),
                 site: ApiSiteCloud {
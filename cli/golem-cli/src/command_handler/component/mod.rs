@@ -46,6 +46,7 @@ use crate::model::{
 use crate::validation::ValidationBuilder;
 use anyhow::{anyhow, bail, Context as AnyhowContext};
 use golem_client::api::ComponentClient;
+use golem_client::model::ComponentCompilationState;
 use golem_client::model::ComponentQuery;
 use golem_client::model::ComponentSearch as ComponentSearchCloud;
 use golem_client::model::ComponentSearchParameters as ComponentSearchParametersCloud;
@@ -62,6 +63,7 @@ use itertools::Itertools;
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::fs::File;
 use tracing::debug;
 use url::Url;
@@ -97,9 +99,15 @@ impl ComponentCommandHandler {
                 component_name,
                 force_build,
                 deploy_args,
+                wait_for_compilation,
             } => {
-                self.cmd_deploy(component_name, force_build, deploy_args)
-                    .await
+                self.cmd_deploy(
+                    component_name,
+                    force_build,
+                    deploy_args,
+                    wait_for_compilation,
+                )
+                .await
             }
             ComponentSubcommand::Clean { component_name } => self.cmd_clean(component_name).await,
             ComponentSubcommand::AddDependency {
@@ -279,20 +287,90 @@ impl ComponentCommandHandler {
         component_name: ComponentOptionalComponentNames,
         force_build: ForceBuildArg,
         deploy_args: DeployArgs,
+        wait_for_compilation: bool,
     ) -> anyhow::Result<()> {
-        self.deploy(
-            self.ctx
-                .cloud_project_handler()
-                .opt_select_project(None)
-                .await?
-                .as_ref(),
-            component_name.component_name,
-            false,
-            Some(force_build),
-            &ApplicationComponentSelectMode::CurrentDir,
-            &deploy_args,
-        )
-        .await?;
+        let components = self
+            .deploy(
+                self.ctx
+                    .cloud_project_handler()
+                    .opt_select_project(None)
+                    .await?
+                    .as_ref(),
+                component_name.component_name,
+                false,
+                Some(force_build),
+                &ApplicationComponentSelectMode::CurrentDir,
+                &deploy_args,
+            )
+            .await?;
+
+        if wait_for_compilation {
+            self.wait_for_compilation(&components).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn wait_for_compilation(&self, components: &[Component]) -> anyhow::Result<()> {
+        if components.is_empty() {
+            return Ok(());
+        }
+
+        log_action("Waiting", "for the compilation of the deployed components");
+        let _indent = LogIndent::new();
+
+        let clients = self.ctx.golem_clients().await?;
+        for component in components {
+            let component_label = format!(
+                "{} version {}",
+                component.component_name.0.log_color_highlight(),
+                component
+                    .versioned_component_id
+                    .version
+                    .to_string()
+                    .log_color_highlight()
+            );
+
+            loop {
+                let status = clients
+                    .component
+                    .get_component_compilation_status(
+                        &component.versioned_component_id.component_id,
+                        &component.versioned_component_id.version.to_string(),
+                    )
+                    .await
+                    .map_service_error()?;
+
+                match status.state {
+                    ComponentCompilationState::Queued | ComponentCompilationState::Compiling => {
+                        tokio::time::sleep(Duration::from_secs(1)).await;
+                    }
+                    ComponentCompilationState::Done => {
+                        log_action("Compiled", component_label);
+                        break;
+                    }
+                    ComponentCompilationState::Failed => {
+                        log_warn_action(
+                            "Failed",
+                            format!(
+                                "to compile {component_label}, it is going to be compiled on first use: {}",
+                                status.error.unwrap_or_default()
+                            ),
+                        );
+                        break;
+                    }
+                    ComponentCompilationState::Unknown => {
+                        log_warn_action(
+                            "Skipped",
+                            format!(
+                                "waiting for {component_label}, its compilation status is not available"
+                            ),
+                        );
+                        break;
+                    }
+                }
+            }
+        }
 
         Ok(())
     }
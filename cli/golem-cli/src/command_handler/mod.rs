@@ -19,6 +19,7 @@ use crate::command::{
     GolemCliCommand, GolemCliCommandParseResult, GolemCliFallbackCommand, GolemCliGlobalFlags,
     GolemCliSubcommand,
 };
+use crate::command_handler::admin::AdminCommandHandler;
 use crate::command_handler::api::cloud::certificate::ApiCloudCertificateCommandHandler;
 use crate::command_handler::api::cloud::domain::ApiCloudDomainCommandHandler;
 use crate::command_handler::api::cloud::ApiCloudCommandHandler;
@@ -43,10 +44,11 @@ use crate::command_handler::partial_match::ErrorHandler;
 use crate::command_handler::plugin::PluginCommandHandler;
 use crate::command_handler::profile::config::ProfileConfigCommandHandler;
 use crate::command_handler::profile::ProfileCommandHandler;
+use crate::command_handler::rib::RibCommandHandler;
 use crate::command_handler::rib_repl::RibReplHandler;
 use crate::command_handler::worker::WorkerCommandHandler;
 use crate::context::Context;
-use crate::error::{ContextInitHintError, HintError, NonSuccessfulExit};
+use crate::error::{ContextInitHintError, HintError, NonSuccessfulExit, NonSuccessfulExitWithCode};
 use crate::log::{logln, set_log_output, Output};
 use crate::model::text::fmt::log_error;
 use crate::{command_name, init_tracing};
@@ -62,6 +64,7 @@ use std::process::ExitCode;
 use std::sync::Arc;
 use tracing::{debug, Level};
 
+mod admin;
 mod api;
 mod app;
 mod cloud;
@@ -71,6 +74,7 @@ mod log;
 mod partial_match;
 mod plugin;
 mod profile;
+mod rib;
 mod rib_repl;
 mod worker;
 
@@ -290,6 +294,10 @@ impl<Hooks: CommandHandlerHooks + 'static> CommandHandler<Hooks> {
         };
 
         result.unwrap_or_else(|error| {
+            if let Some(NonSuccessfulExitWithCode(exit_code)) = error.downcast_ref() {
+                return ExitCode::from(*exit_code);
+            }
+
             if error.downcast_ref::<NonSuccessfulExit>().is_some() {
                 // NOP
             } else if error
@@ -342,12 +350,19 @@ impl<Hooks: CommandHandlerHooks + 'static> CommandHandler<Hooks> {
             GolemCliSubcommand::Cloud { subcommand } => {
                 self.ctx.cloud_handler().handle_command(subcommand).await
             }
+            GolemCliSubcommand::Admin { subcommand } => {
+                self.ctx.admin_handler().handle_command(subcommand).await
+            }
+            GolemCliSubcommand::Rib { subcommand } => {
+                self.ctx.rib_handler().handle_command(subcommand).await
+            }
             GolemCliSubcommand::Repl {
                 component_name,
                 version,
                 deploy_args,
                 script,
                 script_file,
+                input,
                 disable_stream,
             } => {
                 self.ctx
@@ -358,6 +373,7 @@ impl<Hooks: CommandHandlerHooks + 'static> CommandHandler<Hooks> {
                         deploy_args.as_ref(),
                         script,
                         script_file,
+                        input,
                         !disable_stream,
                     )
                     .await
@@ -379,6 +395,7 @@ impl<Hooks: CommandHandlerHooks + 'static> CommandHandler<Hooks> {
 //       by moving these simple factory methods into the specific handlers on demand,
 //       if the need ever arises
 pub trait Handlers {
+    fn admin_handler(&self) -> AdminCommandHandler;
     fn api_cloud_certificate_handler(&self) -> ApiCloudCertificateCommandHandler;
     fn api_cloud_domain_handler(&self) -> ApiCloudDomainCommandHandler;
     fn api_cloud_handler(&self) -> ApiCloudCommandHandler;
@@ -403,11 +420,16 @@ pub trait Handlers {
     fn plugin_handler(&self) -> PluginCommandHandler;
     fn profile_config_handler(&self) -> ProfileConfigCommandHandler;
     fn profile_handler(&self) -> ProfileCommandHandler;
+    fn rib_handler(&self) -> RibCommandHandler;
     fn rib_repl_handler(&self) -> RibReplHandler;
     fn worker_handler(&self) -> WorkerCommandHandler;
 }
 
 impl Handlers for Arc<Context> {
+    fn admin_handler(&self) -> AdminCommandHandler {
+        AdminCommandHandler::new(self.clone())
+    }
+
     fn api_cloud_certificate_handler(&self) -> ApiCloudCertificateCommandHandler {
         ApiCloudCertificateCommandHandler::new(self.clone())
     }
@@ -504,6 +526,10 @@ impl Handlers for Arc<Context> {
         ProfileCommandHandler::new(self.clone())
     }
 
+    fn rib_handler(&self) -> RibCommandHandler {
+        RibCommandHandler::new(self.clone())
+    }
+
     fn rib_repl_handler(&self) -> RibReplHandler {
         RibReplHandler::new(self.clone())
     }
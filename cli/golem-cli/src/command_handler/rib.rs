@@ -0,0 +1,127 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::app::yaml_edit::AppYamlEditor;
+use crate::command::rib::RibSubcommand;
+use crate::command_name;
+use crate::context::Context;
+use crate::error::NonSuccessfulExit;
+use crate::fs;
+use crate::log::{log_action, log_warn_action, LogColorize};
+use crate::model::text::fmt::log_error;
+use anyhow::{anyhow, bail};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+pub struct RibCommandHandler {
+    ctx: Arc<Context>,
+}
+
+impl RibCommandHandler {
+    pub fn new(ctx: Arc<Context>) -> Self {
+        Self { ctx }
+    }
+
+    pub async fn handle_command(&self, subcommand: RibSubcommand) -> anyhow::Result<()> {
+        match subcommand {
+            RibSubcommand::Fmt { files, check } => {
+                if files.is_empty() {
+                    self.cmd_fmt_api_definitions(check).await
+                } else {
+                    self.cmd_fmt_files(files, check)
+                }
+            }
+        }
+    }
+
+    fn cmd_fmt_files(&self, files: Vec<PathBuf>, check: bool) -> anyhow::Result<()> {
+        let mut unformatted_count = 0;
+
+        for file in &files {
+            let source = fs::read_to_string(file)?;
+            let formatted = rib::format(&source)
+                .map_err(|err| anyhow!("Failed to format {}:\n{}", file.display(), err))?;
+
+            if formatted == source {
+                continue;
+            }
+
+            unformatted_count += 1;
+
+            if check {
+                log_warn_action(
+                    "Unformatted",
+                    file.as_path().log_color_highlight().to_string(),
+                );
+            } else {
+                log_warn_action(
+                    "Formatting",
+                    file.as_path().log_color_highlight().to_string(),
+                );
+                fs::write_str(file, formatted)?;
+            }
+        }
+
+        self.log_fmt_result(check, unformatted_count)
+    }
+
+    async fn cmd_fmt_api_definitions(&self, check: bool) -> anyhow::Result<()> {
+        let app_ctx = self.ctx.app_context_lock().await;
+        let app_ctx = app_ctx.some_or_err()?;
+
+        let mut editor = AppYamlEditor::new(&app_ctx.application);
+        let mut unformatted_count = 0;
+
+        for api_definition_name in app_ctx.application.http_api_definitions().keys() {
+            let count = editor.format_api_definition_rib_scripts(api_definition_name)?;
+
+            if count > 0 {
+                unformatted_count += count;
+
+                log_warn_action(
+                    if check { "Unformatted" } else { "Formatting" },
+                    format!(
+                        "{} Rib script(s) of HTTP API definition {}",
+                        count,
+                        api_definition_name.as_str().log_color_highlight()
+                    ),
+                );
+            }
+        }
+
+        if !check && unformatted_count > 0 {
+            editor.update_documents()?;
+        }
+
+        self.log_fmt_result(check, unformatted_count)
+    }
+
+    fn log_fmt_result(&self, check: bool, unformatted_count: usize) -> anyhow::Result<()> {
+        if unformatted_count == 0 {
+            log_action("Checked", "Rib scripts, all of them are formatted");
+            Ok(())
+        } else if check {
+            let fmt_command = format!("{} rib fmt", command_name());
+            log_error(format!(
+                "{} Rib script(s) are not formatted, use '{}' to format them",
+                unformatted_count,
+                fmt_command.log_color_highlight()
+            ));
+            bail!(NonSuccessfulExit)
+        } else {
+            log_action("Formatted", format!("{unformatted_count} Rib script(s)"));
+            Ok(())
+        }
+    }
+}
@@ -15,7 +15,7 @@
 use crate::command::shared_args::{DeployArgs, StreamArgs};
 use crate::command_handler::Handlers;
 use crate::context::{Context, RibReplState};
-use crate::error::NonSuccessfulExit;
+use crate::error::{NonSuccessfulExit, NonSuccessfulExitWithCode};
 use crate::fs;
 use crate::log::{logln, set_log_output, Output};
 use crate::model::component::ComponentView;
@@ -31,17 +31,24 @@ use colored::Colorize;
 use golem_common::model::agent::AgentId;
 use golem_rib_repl::{
     Command, CommandRegistry, ReplComponentDependencies, ReplContext, RibDependencyManager,
-    RibRepl, RibReplConfig, WorkerFunctionInvoke,
+    RibExecutionError, RibRepl, RibReplConfig, WorkerFunctionInvoke,
 };
 use golem_wasm::analysis::AnalysedType;
 use golem_wasm::json::OptionallyValueAndTypeJson;
 use golem_wasm::ValueAndType;
 use rib::{ComponentDependency, ComponentDependencyKey};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 use uuid::Uuid;
 
+// Exit codes of running a script, such that a smoke test can tell
+// a broken script from a failure in the invoked components
+const SCRIPT_COMPILATION_ERROR_EXIT_CODE: u8 = 2;
+const SCRIPT_RUNTIME_ERROR_EXIT_CODE: u8 = 3;
+const SCRIPT_INVALID_INPUT_EXIT_CODE: u8 = 4;
+
 #[derive(Clone)]
 pub struct RibReplHandler {
     ctx: Arc<Context>,
@@ -63,6 +70,7 @@ impl RibReplHandler {
         deploy_args: Option<&DeployArgs>,
         script: Option<String>,
         script_file: Option<PathBuf>,
+        input: Option<String>,
         stream_logs: bool,
     ) -> anyhow::Result<()> {
         self.stream_logs
@@ -78,6 +86,13 @@ impl RibReplHandler {
             }
         };
 
+        let rib_input: HashMap<String, serde_json::Value> = match input {
+            Some(input) => serde_json::from_str(&input).map_err(|err| {
+                anyhow!("The input must be a JSON object with the names of the variables as keys: {err}")
+            })?,
+            None => HashMap::new(),
+        };
+
         let selected_components = self
             .ctx
             .component_handler()
@@ -175,9 +190,12 @@ impl RibReplHandler {
             component_source: None,
             prompt: None,
             command_registry: Some(command_registry),
+            lint_config: None,
         })
         .await?;
 
+        repl.set_rib_input(rib_input);
+
         if script_input.is_none() {
             logln("");
             self.ctx.log_handler().log_view(&ComponentReplStartedView(
@@ -199,10 +217,14 @@ impl RibReplHandler {
                             repl.print_execute_result(&result);
                         }
                     },
-                    Err(_) => {
+                    Err(err) => {
                         set_log_output(Output::Stderr);
                         repl.print_execute_result(&result);
-                        bail!(NonSuccessfulExit);
+
+                        match script_error_exit_code(err) {
+                            Some(exit_code) => bail!(NonSuccessfulExitWithCode(exit_code)),
+                            None => bail!(NonSuccessfulExit),
+                        }
                     }
                 }
             }
@@ -213,6 +235,15 @@ impl RibReplHandler {
     }
 }
 
+fn script_error_exit_code(err: &RibExecutionError) -> Option<u8> {
+    match err {
+        RibExecutionError::RibCompilationError(_) => Some(SCRIPT_COMPILATION_ERROR_EXIT_CODE),
+        RibExecutionError::RibRuntimeError(_) => Some(SCRIPT_RUNTIME_ERROR_EXIT_CODE),
+        RibExecutionError::InvalidRibInput(_) => Some(SCRIPT_INVALID_INPUT_EXIT_CODE),
+        RibExecutionError::Custom(_) => None,
+    }
+}
+
 pub struct Logs {
     stream_logs: Arc<AtomicBool>,
 }
@@ -379,3 +410,35 @@ impl WorkerFunctionInvoke for RibReplHandler {
         Ok(result.result)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::command_handler::rib_repl::script_error_exit_code;
+    use assert2::assert;
+    use golem_rib_repl::RibExecutionError;
+    use rib::{RibCompilationError, RibRuntimeError};
+    use test_r::test;
+
+    #[test]
+    fn script_errors_have_distinct_exit_codes() {
+        assert!(
+            script_error_exit_code(&RibExecutionError::RibCompilationError(
+                RibCompilationError::InvalidSyntax("unexpected token".to_string())
+            )) == Some(2)
+        );
+        assert!(
+            script_error_exit_code(&RibExecutionError::RibRuntimeError(
+                RibRuntimeError::ExhaustedIterator
+            )) == Some(3)
+        );
+        assert!(
+            script_error_exit_code(&RibExecutionError::InvalidRibInput(
+                "invalid value for input `request`".to_string()
+            )) == Some(4)
+        );
+        assert!(
+            script_error_exit_code(&RibExecutionError::Custom("unknown command".to_string()))
+                .is_none()
+        );
+    }
+}
@@ -389,6 +389,12 @@ impl Context {
         &self.file_download_client
     }
 
+    /// Creates an HTTP client for the admin endpoints of the internal services, such as the
+    /// shard manager, which are not covered by the generated clients
+    pub fn admin_http_client(&self) -> anyhow::Result<reqwest::Client> {
+        new_reqwest_client(&self.client_config.service_http_client_config)
+    }
+
     pub fn worker_service_url(&self) -> &Url {
         &self.client_config.worker_url
     }
@@ -18,6 +18,20 @@ impl Display for NonSuccessfulExit {
 
 impl Error for NonSuccessfulExit {}
 
+// NonSuccessfulExitWithCode is the same as NonSuccessfulExit, but with a specific exit code
+// for the process, for the commands where the exit code tells the kind of the failure.
+#[derive(Debug)]
+pub struct NonSuccessfulExitWithCode(pub u8);
+
+impl Display for NonSuccessfulExitWithCode {
+    fn fmt(&self, _f: &mut Formatter<'_>) -> std::fmt::Result {
+        //NOP
+        Ok(())
+    }
+}
+
+impl Error for NonSuccessfulExitWithCode {}
+
 #[derive(Clone, Copy, Debug, Display, EnumIter)]
 pub enum ShowClapHelpTarget {
     AppNew,
@@ -2497,6 +2497,30 @@ mod app_builder {
                                         }
                                     };
 
+                                    let check_response_stream_not_allowed = |validation: &mut ValidationBuilder| {
+                                        if route.binding.response_stream.is_some() {
+                                            validation.add_error(
+                                                format!(
+                                                    "Property {} is not allowed with binding type {}",
+                                                    "response_stream".log_color_highlight(),
+                                                    binding_type_as_string.log_color_highlight(),
+                                                )
+                                            );
+                                        }
+                                    };
+
+                                    let check_ingress_protocol_not_allowed = |validation: &mut ValidationBuilder| {
+                                        if route.binding.ingress_protocol.is_some() {
+                                            validation.add_error(
+                                                format!(
+                                                    "Property {} is not allowed with binding type {}",
+                                                    "ingress_protocol".log_color_highlight(),
+                                                    binding_type_as_string.log_color_highlight(),
+                                                )
+                                            );
+                                        }
+                                    };
+
                                     let check_component_name_and_version = |validation: &mut ValidationBuilder|
                                         {
                                             match route.binding.component_name.as_deref() {
@@ -2560,30 +2584,47 @@ mod app_builder {
                                             check_rib(validation, "idempotency_key", &route.binding.idempotency_key, false);
                                             check_rib(validation, "invocation_context", &route.binding.invocation_context, false);
                                             check_rib(validation, "response", &route.binding.response, true);
+                                            if route.binding.ingress_protocol.is_some() && route.binding.response_stream.is_some() {
+                                                validation.add_error(
+                                                    format!(
+                                                        "Properties {} and {} cannot be used together",
+                                                        "ingress_protocol".log_color_highlight(),
+                                                        "response_stream".log_color_highlight(),
+                                                    )
+                                                );
+                                            }
                                         }
                                         app_raw::HttpApiDefinitionBindingType::CorsPreflight => {
                                             check_not_allowed(validation, "component_name", &route.binding.component_name);
                                             check_not_allowed(validation, "idempotency_key", &route.binding.idempotency_key);
                                             check_not_allowed(validation, "invocation_context", &route.binding.invocation_context);
                                             check_rib(validation, "response", &route.binding.response, false);
+                                            check_response_stream_not_allowed(validation);
+                                            check_ingress_protocol_not_allowed(validation);
                                         }
                                         app_raw::HttpApiDefinitionBindingType::FileServer => {
                                             check_component_name_and_version(validation);
                                             check_rib(validation, "idempotency_key", &route.binding.idempotency_key, false);
                                             check_rib(validation, "invocation_context", &route.binding.invocation_context, false);
                                             check_rib(validation, "response", &route.binding.response, true);
+                                            check_response_stream_not_allowed(validation);
+                                            check_ingress_protocol_not_allowed(validation);
                                         }
                                         app_raw::HttpApiDefinitionBindingType::HttpHandler => {
                                             check_component_name_and_version(validation);
                                             check_not_allowed(validation, "idempotency_key", &route.binding.idempotency_key);
                                             check_not_allowed(validation, "invocation_context", &route.binding.invocation_context);
                                             check_not_allowed(validation, "response", &route.binding.response);
+                                            check_response_stream_not_allowed(validation);
+                                            check_ingress_protocol_not_allowed(validation);
                                         }
                                         app_raw::HttpApiDefinitionBindingType::SwaggerUi => {
                                             check_not_allowed(validation, "component_name", &route.binding.component_name);
                                             check_not_allowed(validation, "idempotency_key", &route.binding.idempotency_key);
                                             check_not_allowed(validation, "invocation_context", &route.binding.invocation_context);
                                             check_not_allowed(validation, "response", &route.binding.response);
+                                            check_response_stream_not_allowed(validation);
+                                            check_ingress_protocol_not_allowed(validation);
                                         }
                                     }
                                 },
@@ -6,7 +6,7 @@ use crate::model::Format;
 use anyhow::{anyhow, Context};
 use golem_common::model::{ComponentFilePath, ComponentFilePermissions};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 use url::Url;
 
@@ -161,6 +161,21 @@ pub struct HttpApiDefinition {
     pub project: Option<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub routes: Vec<HttpApiDefinitionRoute>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rib_execution_limits: Option<HttpApiDefinitionRibExecutionLimits>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct HttpApiDefinitionRibExecutionLimits {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_instructions: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_stack_depth: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_list_size: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_worker_calls: Option<u64>,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -171,6 +186,8 @@ pub struct HttpApiDefinitionRoute {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub security: Option<String>,
     pub binding: HttpApiDefinitionBinding,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transformation: Option<HttpApiDefinitionTransformation>,
 }
 
 #[derive(Clone, Copy, Default, Debug, PartialEq, Serialize, Deserialize)]
@@ -199,6 +216,57 @@ pub struct HttpApiDefinitionBinding {
     pub invocation_context: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub response: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_stream: Option<HttpApiDefinitionResponseStreamType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ingress_protocol: Option<HttpApiDefinitionIngressProtocolType>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum HttpApiDefinitionResponseStreamType {
+    Sse,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum HttpApiDefinitionIngressProtocolType {
+    Grpc,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct HttpApiDefinitionTransformation {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_headers: Option<HttpApiDefinitionHeaderTransformation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_headers: Option<HttpApiDefinitionHeaderTransformation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path_rewrite: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id_header: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub propagate_trace_context: Option<bool>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub compression: Vec<HttpApiDefinitionCompressionAlgorithm>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_request_body_size: Option<u64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct HttpApiDefinitionHeaderTransformation {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub set: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub remove: Vec<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum HttpApiDefinitionCompressionAlgorithm {
+    Gzip,
+    Br,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
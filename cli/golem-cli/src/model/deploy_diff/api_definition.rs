@@ -16,15 +16,20 @@ use crate::log::LogColorize;
 use crate::model::api::to_method_pattern;
 use crate::model::app::HttpApiDefinitionName;
 use crate::model::app_raw::{
-    HttpApiDefinition, HttpApiDefinitionBindingType, HttpApiDefinitionRoute,
+    HttpApiDefinition, HttpApiDefinitionBindingType, HttpApiDefinitionCompressionAlgorithm,
+    HttpApiDefinitionHeaderTransformation, HttpApiDefinitionIngressProtocolType,
+    HttpApiDefinitionResponseStreamType, HttpApiDefinitionRibExecutionLimits,
+    HttpApiDefinitionRoute, HttpApiDefinitionTransformation,
 };
 use crate::model::component::Component;
 use crate::model::deploy_diff::{DiffSerialize, ToYamlValueWithoutNulls};
 use crate::model::text::fmt::format_rib_source_for_error;
 use anyhow::anyhow;
 use golem_client::model::{
-    GatewayBindingComponent, GatewayBindingData, GatewayBindingType, HttpApiDefinitionRequest,
-    HttpApiDefinitionResponseData, RouteRequestData,
+    CompressionAlgorithm, GatewayBindingComponent, GatewayBindingData, GatewayBindingType,
+    HeaderTransformation, HttpApiDefinitionRequest, HttpApiDefinitionResponseData,
+    HttpTransformation, IngressProtocolType, ResponseStreamType, RibExecutionLimitsData,
+    RouteRequestData,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
@@ -56,11 +61,15 @@ impl DiffableHttpApiDefinition {
                         idempotency_key: route.binding.idempotency_key,
                         response: route.binding.response,
                         invocation_context: route.binding.invocation_context,
+                        response_stream: route.binding.response_stream,
+                        ingress_protocol: route.binding.ingress_protocol,
                     },
                     security: route.security,
+                    transformation: route.transformation,
                 })
                 .collect(),
             draft: api_definition.draft,
+            rib_execution_limits: api_definition.rib_execution_limits,
         }))
     }
 
@@ -80,6 +89,10 @@ impl DiffableHttpApiDefinition {
                 .map(|route| normalize_http_api_route(latest_component_versions, route))
                 .collect::<Result<Vec<_>, _>>()?,
             draft: true,
+            rib_execution_limits: api_definition
+                .rib_execution_limits
+                .as_ref()
+                .map(normalize_http_api_rib_execution_limits),
         });
 
         // NOTE: if the only diff is being non-draft on serverside, we hide that
@@ -147,11 +160,71 @@ fn normalize_http_api_route(
             idempotency_key: normalize_rib_property(&route.binding.idempotency_key)?,
             invocation_context: normalize_rib_property(&route.binding.invocation_context)?,
             response: normalize_rib_property(&route.binding.response)?,
+            response_stream: route.binding.response_stream.map(|response_stream| {
+                match response_stream {
+                    HttpApiDefinitionResponseStreamType::Sse => ResponseStreamType::Sse,
+                }
+            }),
+            ingress_protocol: route.binding.ingress_protocol.map(|ingress_protocol| {
+                match ingress_protocol {
+                    HttpApiDefinitionIngressProtocolType::Grpc => IngressProtocolType::Grpc,
+                }
+            }),
         },
         security: route.security.clone(),
+        transformation: route
+            .transformation
+            .as_ref()
+            .map(normalize_http_api_transformation),
     })
 }
 
+fn normalize_http_api_transformation(
+    transformation: &HttpApiDefinitionTransformation,
+) -> HttpTransformation {
+    let header_transformation =
+        |headers: &HttpApiDefinitionHeaderTransformation| HeaderTransformation {
+            set: (!headers.set.is_empty()).then(|| headers.set.clone().into_iter().collect()),
+            remove: (!headers.remove.is_empty()).then(|| headers.remove.clone()),
+        };
+
+    HttpTransformation {
+        request_headers: transformation
+            .request_headers
+            .as_ref()
+            .map(header_transformation),
+        response_headers: transformation
+            .response_headers
+            .as_ref()
+            .map(header_transformation),
+        path_rewrite: transformation.path_rewrite.clone(),
+        request_id_header: transformation.request_id_header.clone(),
+        propagate_trace_context: transformation.propagate_trace_context,
+        compression: (!transformation.compression.is_empty()).then(|| {
+            transformation
+                .compression
+                .iter()
+                .map(|algorithm| match algorithm {
+                    HttpApiDefinitionCompressionAlgorithm::Gzip => CompressionAlgorithm::Gzip,
+                    HttpApiDefinitionCompressionAlgorithm::Br => CompressionAlgorithm::Br,
+                })
+                .collect()
+        }),
+        max_request_body_size: transformation.max_request_body_size,
+    }
+}
+
+fn normalize_http_api_rib_execution_limits(
+    limits: &HttpApiDefinitionRibExecutionLimits,
+) -> RibExecutionLimitsData {
+    RibExecutionLimitsData {
+        max_instructions: limits.max_instructions,
+        max_stack_depth: limits.max_stack_depth,
+        max_list_size: limits.max_list_size,
+        max_worker_calls: limits.max_worker_calls,
+    }
+}
+
 fn normalize_rib_property(rib: &Option<String>) -> anyhow::Result<Option<String>> {
     rib.as_ref()
         .map(|r| r.as_str())
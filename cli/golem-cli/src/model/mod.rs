@@ -22,6 +22,7 @@ pub mod deploy_diff;
 pub mod invoke_result_view;
 pub mod plugin_manifest;
 pub mod project;
+pub mod shard_manager;
 pub mod template;
 pub mod text;
 pub mod wave;
@@ -0,0 +1,207 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Types of the admin HTTP API of the shard manager
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShardManagerPod {
+    pub host: String,
+    pub port: u16,
+    pub pod_name: Option<String>,
+    #[serde(default)]
+    pub zone: Option<String>,
+}
+
+impl Display for ShardManagerPod {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.pod_name {
+            Some(pod_name) => write!(f, "{pod_name} ({}:{})", self.host, self.port),
+            None => write!(f, "{}:{}", self.host, self.port),
+        }
+    }
+}
+
+/// Selects a pod by its pod name, or by <HOST>:<PORT>
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShardManagerPodSelector {
+    #[serde(default)]
+    pub host: String,
+    #[serde(default)]
+    pub port: u16,
+    pub pod_name: Option<String>,
+}
+
+impl FromStr for ShardManagerPodSelector {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err("Empty pod selector, expected <POD_NAME> or <HOST>:<PORT>".to_string());
+        }
+        match s.rsplit_once(':') {
+            Some((host, port)) => {
+                let port = port
+                    .parse()
+                    .map_err(|_| format!("Invalid port in pod selector: {port}"))?;
+                Ok(Self {
+                    host: host.to_string(),
+                    port,
+                    pod_name: None,
+                })
+            }
+            None => Ok(Self {
+                host: String::new(),
+                port: 0,
+                pod_name: Some(s.to_string()),
+            }),
+        }
+    }
+}
+
+impl Display for ShardManagerPodSelector {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.pod_name {
+            Some(pod_name) => write!(f, "{pod_name}"),
+            None => write!(f, "{}:{}", self.host, self.port),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PodShards {
+    pub pod: ShardManagerPod,
+    pub shards: Vec<i64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PinnedShard {
+    pub shard: i64,
+    pub pod: ShardManagerPodSelector,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ShardOverrides {
+    pub rebalancing_frozen: bool,
+    pub pinned_shards: Vec<PinnedShard>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ShardRoutingTable {
+    pub number_of_shards: usize,
+    pub pods: Vec<PodShards>,
+    pub unassigned_shards: Vec<i64>,
+    #[serde(flatten)]
+    pub overrides: ShardOverrides,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ShardRebalancePlan {
+    pub assignments: Vec<PodShards>,
+    pub unassignments: Vec<PodShards>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ShardAuditLogEntry {
+    pub timestamp: DateTime<Utc>,
+    pub action: String,
+    pub requested_by: Option<String>,
+    pub reason: Option<String>,
+}
+
+/// The fields common to every admin request of the shard manager, recorded in its audit log
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ShardAdminRequest {
+    pub requested_by: Option<String>,
+    pub reason: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PinShardRequest {
+    pub shard: i64,
+    pub pod: ShardManagerPodSelector,
+    #[serde(flatten)]
+    pub admin: ShardAdminRequest,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UnpinShardRequest {
+    pub shard: i64,
+    #[serde(flatten)]
+    pub admin: ShardAdminRequest,
+}
+
+/// Formats shard numbers as ranges, for example `0-3, 7, 9-10`
+pub fn format_shard_ranges(shards: &[i64]) -> String {
+    let mut shards = shards.to_vec();
+    shards.sort();
+
+    let mut ranges: Vec<(i64, i64)> = Vec::new();
+    for shard in shards {
+        match ranges.last_mut() {
+            Some((_, last)) if *last + 1 == shard => *last = shard,
+            _ => ranges.push((shard, shard)),
+        }
+    }
+
+    ranges
+        .into_iter()
+        .map(|(first, last)| {
+            if first == last {
+                first.to_string()
+            } else {
+                format!("{first}-{last}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::shard_manager::{format_shard_ranges, ShardManagerPodSelector};
+    use std::str::FromStr;
+    use test_r::test;
+
+    #[test]
+    fn shard_ranges() {
+        assert_eq!(format_shard_ranges(&[]), "");
+        assert_eq!(format_shard_ranges(&[7, 0, 1, 2, 3, 9, 10]), "0-3, 7, 9-10");
+    }
+
+    #[test]
+    fn pod_selector() {
+        assert_eq!(
+            ShardManagerPodSelector::from_str("worker-executor-0"),
+            Ok(ShardManagerPodSelector {
+                host: String::new(),
+                port: 0,
+                pod_name: Some("worker-executor-0".to_string()),
+            })
+        );
+        assert_eq!(
+            ShardManagerPodSelector::from_str("10.0.0.1:9000"),
+            Ok(ShardManagerPodSelector {
+                host: "10.0.0.1".to_string(),
+                port: 9000,
+                pod_name: None,
+            })
+        );
+        assert!(ShardManagerPodSelector::from_str("host:port").is_err());
+    }
+}
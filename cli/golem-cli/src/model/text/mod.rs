@@ -25,6 +25,7 @@ pub mod help;
 pub mod plugin;
 pub mod profile;
 pub mod project;
+pub mod shard_manager;
 pub mod template;
 pub mod token;
 pub mod worker;
@@ -0,0 +1,201 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::log::logln;
+use crate::model::shard_manager::{
+    format_shard_ranges, PodShards, ShardAuditLogEntry, ShardOverrides, ShardRebalancePlan,
+    ShardRoutingTable,
+};
+use crate::model::text::fmt::{format_warn, log_table, FieldsBuilder, MessageWithFields, TextView};
+use chrono::{DateTime, Utc};
+use cli_table::{format::Justify, Table};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Table)]
+struct PodShardsTableView {
+    #[table(title = "Pod")]
+    pub pod: String,
+    #[table(title = "Zone")]
+    pub zone: String,
+    #[table(title = "Shard count", justify = "Justify::Right")]
+    pub shard_count: usize,
+    #[table(title = "Shards")]
+    pub shards: String,
+}
+
+impl From<&PodShards> for PodShardsTableView {
+    fn from(value: &PodShards) -> Self {
+        Self {
+            pod: value.pod.to_string(),
+            zone: value.pod.zone.clone().unwrap_or_default(),
+            shard_count: value.shards.len(),
+            shards: format_shard_ranges(&value.shards),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ShardRoutingTableView(pub ShardRoutingTable);
+
+impl TextView for ShardRoutingTableView {
+    fn log(&self) {
+        if self.0.pods.is_empty() {
+            logln("No pods are registered");
+        } else {
+            log_table::<_, PodShardsTableView>(&self.0.pods);
+        }
+        logln("");
+
+        let mut fields = FieldsBuilder::new();
+        fields
+            .field("Number of shards", &self.0.number_of_shards)
+            .fmt_field("Unassigned shards", &self.0.unassigned_shards, |shards| {
+                if shards.is_empty() {
+                    "none".to_string()
+                } else {
+                    format_warn(&format_shard_ranges(shards))
+                }
+            });
+        for (name, value) in fields
+            .build()
+            .into_iter()
+            .chain(overrides_fields(&self.0.overrides))
+        {
+            logln(format!("{name}: {value}"));
+        }
+    }
+}
+
+#[derive(Table)]
+struct RebalancePlanTableView {
+    #[table(title = "Pod")]
+    pub pod: String,
+    #[table(title = "Assigned shards")]
+    pub assigned: String,
+    #[table(title = "Revoked shards")]
+    pub revoked: String,
+}
+
+impl From<&(String, (Vec<i64>, Vec<i64>))> for RebalancePlanTableView {
+    fn from((pod, (assigned, revoked)): &(String, (Vec<i64>, Vec<i64>))) -> Self {
+        Self {
+            pod: pod.clone(),
+            assigned: format_shard_ranges(assigned),
+            revoked: format_shard_ranges(revoked),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ShardRebalancePlanView(pub ShardRebalancePlan);
+
+impl TextView for ShardRebalancePlanView {
+    fn log(&self) {
+        let mut rows: BTreeMap<String, (Vec<i64>, Vec<i64>)> = BTreeMap::new();
+        for pod_shards in &self.0.assignments {
+            rows.entry(pod_shards.pod.to_string())
+                .or_default()
+                .0
+                .extend(&pod_shards.shards);
+        }
+        for pod_shards in &self.0.unassignments {
+            rows.entry(pod_shards.pod.to_string())
+                .or_default()
+                .1
+                .extend(&pod_shards.shards);
+        }
+
+        if rows.is_empty() {
+            logln("No pending rebalance");
+        } else {
+            log_table::<_, RebalancePlanTableView>(&rows.into_iter().collect::<Vec<_>>());
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ShardOverridesView(pub ShardOverrides);
+
+impl MessageWithFields for ShardOverridesView {
+    fn message(&self) -> String {
+        "Shard placement overrides".to_string()
+    }
+
+    fn fields(&self) -> Vec<(String, String)> {
+        overrides_fields(&self.0)
+    }
+}
+
+fn overrides_fields(overrides: &ShardOverrides) -> Vec<(String, String)> {
+    let mut fields = FieldsBuilder::new();
+
+    fields
+        .fmt_field("Rebalancing", &overrides.rebalancing_frozen, |frozen| {
+            if *frozen {
+                format_warn("frozen")
+            } else {
+                "active".to_string()
+            }
+        })
+        .fmt_field("Pinned shards", &overrides.pinned_shards, |pinned_shards| {
+            if pinned_shards.is_empty() {
+                "none".to_string()
+            } else {
+                pinned_shards
+                    .iter()
+                    .map(|pinned_shard| format!("{} -> {}", pinned_shard.shard, pinned_shard.pod))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            }
+        });
+
+    fields.build()
+}
+
+#[derive(Table)]
+struct AuditLogEntryTableView {
+    #[table(title = "Time")]
+    pub timestamp: DateTime<Utc>,
+    #[table(title = "Action")]
+    pub action: String,
+    #[table(title = "Requested by")]
+    pub requested_by: String,
+    #[table(title = "Reason")]
+    pub reason: String,
+}
+
+impl From<&ShardAuditLogEntry> for AuditLogEntryTableView {
+    fn from(value: &ShardAuditLogEntry) -> Self {
+        Self {
+            timestamp: value.timestamp,
+            action: value.action.clone(),
+            requested_by: value.requested_by.clone().unwrap_or_default(),
+            reason: value.reason.clone().unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ShardAuditLogView(pub Vec<ShardAuditLogEntry>);
+
+impl TextView for ShardAuditLogView {
+    fn log(&self) {
+        if self.0.is_empty() {
+            logln("The audit log is empty");
+        } else {
+            log_table::<_, AuditLogEntryTableView>(&self.0);
+        }
+    }
+}
@@ -50,6 +50,10 @@ fn generate(yaml_path: PathBuf, out_dir: OsString) {
                 "ComponentMetadata",
                 "golem_common::model::component_metadata::ComponentMetadata",
             ),
+            (
+                "ComponentCompilationOptions",
+                "golem_common::model::component_metadata::ComponentCompilationOptions",
+            ),
             (
                 "ComponentFilePathWithPermissionsList",
                 "golem_common::model::ComponentFilePathWithPermissionsList",
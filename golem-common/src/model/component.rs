@@ -70,8 +70,65 @@ impl Display for VersionedComponentId {
     }
 }
 
+/// Priority of a compilation request. Interactive requests, such as the ones caused by deploying
+/// a component, are compiled before any queued bulk requests like recompilations.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ComponentCompilationPriority {
+    #[default]
+    Interactive,
+    Bulk,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, poem_openapi::Enum)]
+pub enum ComponentCompilationState {
+    /// The compilation service has no information about the component version, either because it
+    /// was never requested to compile it, or because its status has expired
+    Unknown,
+    Queued,
+    Compiling,
+    Done,
+    Failed,
+}
+
+/// Status of the compilation of a component version
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[derive(poem_openapi::Object)]
+#[oai(rename_all = "camelCase")]
+pub struct ComponentCompilationStatus {
+    pub state: ComponentCompilationState,
+    /// Number of started compilation attempts
+    pub attempts: u32,
+    /// The error of the last failed attempt
+    pub error: Option<String>,
+}
+
+impl ComponentCompilationStatus {
+    pub fn unknown() -> Self {
+        Self {
+            state: ComponentCompilationState::Unknown,
+            attempts: 0,
+            error: None,
+        }
+    }
+
+    /// Returns true if the compilation service is not going to make further attempts
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self.state,
+            ComponentCompilationState::Unknown
+                | ComponentCompilationState::Done
+                | ComponentCompilationState::Failed
+        )
+    }
+}
+
 mod protobuf {
-    use crate::model::component::VersionedComponentId;
+    use crate::model::component::{
+        ComponentCompilationPriority, ComponentCompilationState, ComponentCompilationStatus,
+        VersionedComponentId,
+    };
+    use golem_api_grpc::proto::golem::componentcompilation::v1 as grpc_compilation;
 
     impl TryFrom<golem_api_grpc::proto::golem::component::VersionedComponentId>
         for VersionedComponentId
@@ -99,4 +156,66 @@ mod protobuf {
             }
         }
     }
+
+    impl From<grpc_compilation::ComponentCompilationPriority> for ComponentCompilationPriority {
+        fn from(value: grpc_compilation::ComponentCompilationPriority) -> Self {
+            match value {
+                grpc_compilation::ComponentCompilationPriority::Interactive => Self::Interactive,
+                grpc_compilation::ComponentCompilationPriority::Bulk => Self::Bulk,
+            }
+        }
+    }
+
+    impl From<ComponentCompilationPriority> for grpc_compilation::ComponentCompilationPriority {
+        fn from(value: ComponentCompilationPriority) -> Self {
+            match value {
+                ComponentCompilationPriority::Interactive => Self::Interactive,
+                ComponentCompilationPriority::Bulk => Self::Bulk,
+            }
+        }
+    }
+
+    impl From<grpc_compilation::ComponentCompilationState> for ComponentCompilationState {
+        fn from(value: grpc_compilation::ComponentCompilationState) -> Self {
+            match value {
+                grpc_compilation::ComponentCompilationState::Unknown => Self::Unknown,
+                grpc_compilation::ComponentCompilationState::Queued => Self::Queued,
+                grpc_compilation::ComponentCompilationState::Compiling => Self::Compiling,
+                grpc_compilation::ComponentCompilationState::Done => Self::Done,
+                grpc_compilation::ComponentCompilationState::Failed => Self::Failed,
+            }
+        }
+    }
+
+    impl From<ComponentCompilationState> for grpc_compilation::ComponentCompilationState {
+        fn from(value: ComponentCompilationState) -> Self {
+            match value {
+                ComponentCompilationState::Unknown => Self::Unknown,
+                ComponentCompilationState::Queued => Self::Queued,
+                ComponentCompilationState::Compiling => Self::Compiling,
+                ComponentCompilationState::Done => Self::Done,
+                ComponentCompilationState::Failed => Self::Failed,
+            }
+        }
+    }
+
+    impl From<grpc_compilation::ComponentCompilationStatus> for ComponentCompilationStatus {
+        fn from(value: grpc_compilation::ComponentCompilationStatus) -> Self {
+            Self {
+                state: value.state().into(),
+                attempts: value.attempts,
+                error: value.error,
+            }
+        }
+    }
+
+    impl From<ComponentCompilationStatus> for grpc_compilation::ComponentCompilationStatus {
+        fn from(value: ComponentCompilationStatus) -> Self {
+            Self {
+                state: grpc_compilation::ComponentCompilationState::from(value.state) as i32,
+                attempts: value.attempts,
+                error: value.error,
+            }
+        }
+    }
 }
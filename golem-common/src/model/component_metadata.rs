@@ -70,6 +70,21 @@ impl ComponentMetadata {
                 root_package_version,
                 dynamic_linking,
                 agent_types,
+                compilation_options: ComponentCompilationOptions::default(),
+            }),
+            cache: Arc::default(),
+        }
+    }
+
+    /// Returns a copy of the metadata with the given compilation options
+    pub fn with_compilation_options(
+        &self,
+        compilation_options: ComponentCompilationOptions,
+    ) -> Self {
+        Self {
+            data: Arc::new(ComponentMetadataInnerData {
+                compilation_options,
+                ..self.data.as_ref().clone()
             }),
             cache: Arc::default(),
         }
@@ -111,6 +126,10 @@ impl ComponentMetadata {
         !self.data.agent_types.is_empty()
     }
 
+    pub fn compilation_options(&self) -> &ComponentCompilationOptions {
+        &self.data.compilation_options
+    }
+
     pub fn load_snapshot(&self) -> Result<Option<InvokableFunction>, String> {
         self.cache.lock().unwrap().load_snapshot(&self.data)
     }
@@ -172,6 +191,7 @@ impl Debug for ComponentMetadata {
             .field("root_package_version", &self.data.root_package_version)
             .field("dynamic_linking", &self.data.dynamic_linking)
             .field("agent_types", &self.data.agent_types)
+            .field("compilation_options", &self.data.compilation_options)
             .finish()
     }
 }
@@ -308,6 +328,10 @@ pub struct ComponentMetadataInnerData {
 
     #[serde(default)]
     pub agent_types: Vec<AgentType>,
+
+    #[serde(default)]
+    #[oai(default)]
+    pub compilation_options: ComponentCompilationOptions,
 }
 
 impl ComponentMetadataInnerData {
@@ -833,6 +857,145 @@ impl From<wasmparser::MemoryType> for LinearMemory {
     }
 }
 
+/// Options for compiling a component to native code and for the engine running its instances.
+///
+/// The defaults match how components were compiled before these options existed, so components
+/// with default options share the same compiled artifacts.
+#[derive(
+    Debug,
+    Clone,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    BinaryCodec,
+    poem_openapi::Object,
+)]
+#[desert(evolution())]
+#[oai(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentCompilationOptions {
+    #[serde(default)]
+    #[oai(default)]
+    pub optimization_level: CompilationOptimizationLevel,
+    #[serde(default)]
+    #[oai(default)]
+    pub interruption: CompilationInterruption,
+    /// Limits of the instances of the component, following the limits of wasmtime's pooling allocator
+    pub pooling_limits: Option<ComponentPoolingLimits>,
+}
+
+impl ComponentCompilationOptions {
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Identifies the compiled artifacts of the options, for example `speed_and_size-epoch`.
+    /// Returns `None` for the default optimization level and interruption.
+    ///
+    /// The pooling limits are not part of the fingerprint, as they do not affect the generated
+    /// native code but are enforced when instantiating the component.
+    pub fn fingerprint(&self) -> Option<String> {
+        if self.optimization_level == CompilationOptimizationLevel::default()
+            && self.interruption == CompilationInterruption::default()
+        {
+            None
+        } else {
+            Some(format!("{}-{}", self.optimization_level, self.interruption))
+        }
+    }
+}
+
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    BinaryCodec,
+    poem_openapi::Enum,
+)]
+#[desert(evolution())]
+pub enum CompilationOptimizationLevel {
+    /// Optimizes for the speed of the generated code
+    #[default]
+    Speed,
+    /// Disables optimizations, making the compilation faster
+    None,
+    /// Optimizes for both the speed and the size of the generated code
+    SpeedAndSize,
+}
+
+impl Display for CompilationOptimizationLevel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            CompilationOptimizationLevel::Speed => "speed",
+            CompilationOptimizationLevel::None => "none",
+            CompilationOptimizationLevel::SpeedAndSize => "speed_and_size",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// How long-running executions of the component are interrupted
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    BinaryCodec,
+    poem_openapi::Enum,
+)]
+#[desert(evolution())]
+pub enum CompilationInterruption {
+    /// Fuel metering in addition to epoch based interruption. Fuel is used for accounting the
+    /// consumed resources, at the cost of slower execution of CPU-heavy code.
+    #[default]
+    Fuel,
+    /// Epoch based interruption only, without fuel metering. Instead of the executed
+    /// instructions, the component is charged a fixed amount of fuel for every epoch deadline
+    /// it reaches, so it is still subject to the fuel limits. The accounting is only as precise
+    /// as the epoch interval of the executor: short invocations might not get charged, and the
+    /// charged amount does not depend on what the component executed.
+    Epoch,
+}
+
+impl Display for CompilationInterruption {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            CompilationInterruption::Fuel => "fuel",
+            CompilationInterruption::Epoch => "epoch",
+        };
+        write!(f, "{s}")
+    }
+}
+
+#[derive(
+    Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, BinaryCodec, poem_openapi::Object,
+)]
+#[desert(evolution())]
+#[oai(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentPoolingLimits {
+    /// Maximum size of a linear memory of an instance in bytes
+    pub max_memory_size: u64,
+    /// Maximum number of elements of a table of an instance
+    pub table_elements: u32,
+    /// Maximum number of core instances created by an instance of the component
+    pub max_core_instances: u32,
+}
+
 /// Metadata of Component in terms of golem_wasm_ast types
 #[derive(Default)]
 pub struct RawComponentMetadata {
@@ -911,6 +1074,7 @@ impl RawComponentMetadata {
             root_package_name: self.root_package_name,
             root_package_version: self.root_package_version,
             agent_types,
+            compilation_options: ComponentCompilationOptions::default(),
         }
     }
 }
@@ -1121,8 +1285,10 @@ fn add_virtual_exports(exports: &mut Vec<AnalysedExport>) {
 mod protobuf {
     use crate::model::base64::Base64;
     use crate::model::component_metadata::{
-        ComponentMetadata, ComponentMetadataInnerData, DynamicLinkedInstance, DynamicLinkedWasmRpc,
-        LinearMemory, ProducerField, Producers, VersionedName, WasmRpcTarget,
+        CompilationInterruption, CompilationOptimizationLevel, ComponentCompilationOptions,
+        ComponentMetadata, ComponentMetadataInnerData, ComponentPoolingLimits,
+        DynamicLinkedInstance, DynamicLinkedWasmRpc, LinearMemory, ProducerField, Producers,
+        VersionedName, WasmRpcTarget,
     };
     use std::collections::HashMap;
     use std::sync::Arc;
@@ -1248,6 +1414,10 @@ mod protobuf {
                     .into_iter()
                     .map(|at| at.try_into())
                     .collect::<Result<_, _>>()?,
+                compilation_options: value
+                    .compilation_options
+                    .map(|options| options.into())
+                    .unwrap_or_default(),
             })
         }
     }
@@ -1288,6 +1458,124 @@ mod protobuf {
                 root_package_name: value.root_package_name,
                 root_package_version: value.root_package_version,
                 agent_types: value.agent_types.into_iter().map(|at| at.into()).collect(),
+                compilation_options: if value.compilation_options.is_default() {
+                    None
+                } else {
+                    Some(value.compilation_options.into())
+                },
+            }
+        }
+    }
+
+    impl From<golem_api_grpc::proto::golem::component::ComponentCompilationOptions>
+        for ComponentCompilationOptions
+    {
+        fn from(
+            value: golem_api_grpc::proto::golem::component::ComponentCompilationOptions,
+        ) -> Self {
+            Self {
+                optimization_level: value.optimization_level().into(),
+                interruption: value.interruption().into(),
+                pooling_limits: value.pooling_limits.map(|limits| limits.into()),
+            }
+        }
+    }
+
+    impl From<ComponentCompilationOptions>
+        for golem_api_grpc::proto::golem::component::ComponentCompilationOptions
+    {
+        fn from(value: ComponentCompilationOptions) -> Self {
+            Self {
+                optimization_level:
+                    golem_api_grpc::proto::golem::component::CompilationOptimizationLevel::from(
+                        value.optimization_level,
+                    ) as i32,
+                interruption: golem_api_grpc::proto::golem::component::CompilationInterruption::from(
+                    value.interruption,
+                ) as i32,
+                pooling_limits: value.pooling_limits.map(|limits| limits.into()),
+            }
+        }
+    }
+
+    impl From<golem_api_grpc::proto::golem::component::CompilationOptimizationLevel>
+        for CompilationOptimizationLevel
+    {
+        fn from(
+            value: golem_api_grpc::proto::golem::component::CompilationOptimizationLevel,
+        ) -> Self {
+            match value {
+                golem_api_grpc::proto::golem::component::CompilationOptimizationLevel::Speed => {
+                    CompilationOptimizationLevel::Speed
+                }
+                golem_api_grpc::proto::golem::component::CompilationOptimizationLevel::None => {
+                    CompilationOptimizationLevel::None
+                }
+                golem_api_grpc::proto::golem::component::CompilationOptimizationLevel::SpeedAndSize => {
+                    CompilationOptimizationLevel::SpeedAndSize
+                }
+            }
+        }
+    }
+
+    impl From<CompilationOptimizationLevel>
+        for golem_api_grpc::proto::golem::component::CompilationOptimizationLevel
+    {
+        fn from(value: CompilationOptimizationLevel) -> Self {
+            match value {
+                CompilationOptimizationLevel::Speed => Self::Speed,
+                CompilationOptimizationLevel::None => Self::None,
+                CompilationOptimizationLevel::SpeedAndSize => Self::SpeedAndSize,
+            }
+        }
+    }
+
+    impl From<golem_api_grpc::proto::golem::component::CompilationInterruption>
+        for CompilationInterruption
+    {
+        fn from(value: golem_api_grpc::proto::golem::component::CompilationInterruption) -> Self {
+            match value {
+                golem_api_grpc::proto::golem::component::CompilationInterruption::Fuel => {
+                    CompilationInterruption::Fuel
+                }
+                golem_api_grpc::proto::golem::component::CompilationInterruption::Epoch => {
+                    CompilationInterruption::Epoch
+                }
+            }
+        }
+    }
+
+    impl From<CompilationInterruption>
+        for golem_api_grpc::proto::golem::component::CompilationInterruption
+    {
+        fn from(value: CompilationInterruption) -> Self {
+            match value {
+                CompilationInterruption::Fuel => Self::Fuel,
+                CompilationInterruption::Epoch => Self::Epoch,
+            }
+        }
+    }
+
+    impl From<golem_api_grpc::proto::golem::component::ComponentPoolingLimits>
+        for ComponentPoolingLimits
+    {
+        fn from(value: golem_api_grpc::proto::golem::component::ComponentPoolingLimits) -> Self {
+            Self {
+                max_memory_size: value.max_memory_size,
+                table_elements: value.table_elements,
+                max_core_instances: value.max_core_instances,
+            }
+        }
+    }
+
+    impl From<ComponentPoolingLimits>
+        for golem_api_grpc::proto::golem::component::ComponentPoolingLimits
+    {
+        fn from(value: ComponentPoolingLimits) -> Self {
+            Self {
+                max_memory_size: value.max_memory_size,
+                table_elements: value.table_elements,
+                max_core_instances: value.max_core_instances,
             }
         }
     }
@@ -423,6 +423,12 @@ impl ShardAssignment {
         }
     }
 
+    /// Switches to a different number of shards, replacing all the assigned shards
+    pub fn reshard(&mut self, number_of_shards: usize, shard_ids: &HashSet<ShardId>) {
+        self.number_of_shards = number_of_shards;
+        self.shard_ids = shard_ids.clone();
+    }
+
     pub fn revoke_shards(&mut self, shard_ids: &HashSet<ShardId>) {
         for shard_id in shard_ids {
             self.shard_ids.remove(shard_id);
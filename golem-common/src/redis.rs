@@ -208,6 +208,31 @@ impl RedisLabelledApi<'_> {
         )
     }
 
+    pub async fn eval<R, S, K, V>(&self, script: S, keys: Vec<K>, args: V) -> RedisResult<R>
+    where
+        R: FromValue,
+        S: AsRef<str>,
+        K: AsRef<str>,
+        V: TryInto<MultipleValues> + Send,
+        V::Error: Into<RedisError> + Send,
+    {
+        self.ensure_connected().await?;
+        let start = Instant::now();
+        self.record(
+            start,
+            "EVAL",
+            self.pool
+                .eval(
+                    script.as_ref().to_string(),
+                    keys.iter()
+                        .map(|k| self.prefixed_key(k))
+                        .collect::<Vec<_>>(),
+                    args,
+                )
+                .await,
+        )
+    }
+
     pub async fn expire<R, K>(&self, key: K, seconds: i64) -> RedisResult<R>
     where
         R: FromValue,
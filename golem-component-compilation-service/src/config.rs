@@ -39,6 +39,7 @@ pub struct ServerConfig {
     pub blob_storage: BlobStorageConfig,
 
     // Workers
+    pub compile_queue: CompileQueueConfig,
     pub compile_worker: CompileWorkerConfig,
 
     // GRPC
@@ -73,6 +74,12 @@ impl SafeDisplay for ServerConfig {
             "{}",
             self.blob_storage.to_safe_string_indented()
         );
+        let _ = writeln!(&mut result, "compile queue:");
+        let _ = writeln!(
+            &mut result,
+            "{}",
+            self.compile_queue.to_safe_string_indented()
+        );
         let _ = writeln!(&mut result, "compile worker:");
         let _ = writeln!(
             &mut result,
@@ -168,6 +175,24 @@ impl SafeDisplay for DynamicComponentServiceConfig {
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CompileQueueConfig {
+    /// Retries of failed compilations
+    pub retries: RetryConfig,
+    /// How long the status of finished compilations is kept
+    pub status_retention: Duration,
+}
+
+impl SafeDisplay for CompileQueueConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        let _ = writeln!(&mut result, "status retention: {:?}", self.status_retention);
+        let _ = writeln!(&mut result, "retries:");
+        let _ = writeln!(&mut result, "{}", self.retries.to_safe_string_indented());
+        result
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct CompileWorkerConfig {
     pub retries: RetryConfig,
@@ -197,6 +222,7 @@ impl Default for ServerConfig {
             component_service: Default::default(),
             compiled_component_service: Default::default(),
             blob_storage: BlobStorageConfig::default_local_file_system(),
+            compile_queue: Default::default(),
             compile_worker: Default::default(),
             grpc_host: "0.0.0.0".to_string(),
             grpc_port: 9091,
@@ -245,6 +271,21 @@ impl Default for ComponentServiceConfig {
     }
 }
 
+impl Default for CompileQueueConfig {
+    fn default() -> Self {
+        Self {
+            retries: RetryConfig {
+                max_attempts: 5,
+                min_delay: Duration::from_secs(1),
+                max_delay: Duration::from_secs(60),
+                multiplier: 3.0,
+                max_jitter_factor: Some(0.15),
+            },
+            status_retention: Duration::from_secs(60 * 60),
+        }
+    }
+}
+
 impl Default for CompileWorkerConfig {
     fn default() -> Self {
         Self {
@@ -24,11 +24,15 @@ use golem_api_grpc::proto::golem::common::{Empty, ErrorBody, ErrorsBody};
 use golem_api_grpc::proto::golem::component;
 use golem_api_grpc::proto::golem::componentcompilation::v1::component_compilation_service_server::ComponentCompilationService as GrpcCompilationServer;
 use golem_api_grpc::proto::golem::componentcompilation::v1::{
-    component_compilation_error, component_compilation_response, ComponentCompilationError,
-    ComponentCompilationRequest, ComponentCompilationResponse,
+    component_compilation_error, component_compilation_response,
+    component_compilation_status_response, ComponentCompilationError, ComponentCompilationRequest,
+    ComponentCompilationResponse, ComponentCompilationStatusRequest,
+    ComponentCompilationStatusResponse,
 };
 use golem_common::grpc::proto_component_id_string;
 use golem_common::metrics::api::TraceErrorKind;
+use golem_common::model::component::{ComponentCompilationPriority, ComponentCompilationStatus};
+use golem_common::model::component_metadata::ComponentCompilationOptions;
 use golem_common::model::ComponentId;
 use golem_common::recorded_grpc_api_request;
 use tonic::{Request, Response, Status};
@@ -98,6 +102,35 @@ impl GrpcCompilationServer for CompileGrpcService {
             result: Some(response),
         }))
     }
+
+    async fn get_compilation_status(
+        &self,
+        request: Request<ComponentCompilationStatusRequest>,
+    ) -> Result<Response<ComponentCompilationStatusResponse>, Status> {
+        let request = request.into_inner();
+        let record = recorded_grpc_api_request!(
+            "get_compilation_status",
+            component_id = proto_component_id_string(&request.component_id),
+        );
+
+        let response = match self
+            .get_compilation_status_impl(request)
+            .instrument(record.span.clone())
+            .await
+        {
+            Ok(status) => record.succeed(component_compilation_status_response::Result::Success(
+                status.into(),
+            )),
+            Err(error) => record.fail(
+                component_compilation_status_response::Result::Failure(error.clone()),
+                &ComponentCompilationTraceErrorKind(&error),
+            ),
+        };
+
+        Ok(Response::new(ComponentCompilationStatusResponse {
+            result: Some(response),
+        }))
+    }
 }
 
 impl CompileGrpcService {
@@ -109,11 +142,36 @@ impl CompileGrpcService {
         let component_id = make_component_id(request.component_id)?;
         let component_version = request.component_version;
         let project_id = make_project_id(request.project_id)?;
+        let compilation_options = request
+            .compilation_options
+            .map(ComponentCompilationOptions::from)
+            .unwrap_or_default();
+        let priority = ComponentCompilationPriority::from(request.priority());
         self.service
-            .enqueue_compilation(component_id, component_version, project_id, sender)
+            .enqueue_compilation(
+                component_id,
+                component_version,
+                project_id,
+                compilation_options,
+                request.reusable_version,
+                priority,
+                sender,
+            )
             .await?;
         Ok(())
     }
+
+    async fn get_compilation_status_impl(
+        &self,
+        request: ComponentCompilationStatusRequest,
+    ) -> Result<ComponentCompilationStatus, ComponentCompilationError> {
+        let component_id = make_component_id(request.component_id)?;
+        let status = self
+            .service
+            .get_compilation_status(&component_id, request.component_version)
+            .await?;
+        Ok(status)
+    }
 }
 
 impl From<crate::model::CompilationError> for ComponentCompilationError {
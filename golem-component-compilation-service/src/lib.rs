@@ -21,10 +21,12 @@ use golem_service_base::db::sqlite::SqlitePool;
 use golem_service_base::storage::blob::s3::S3BlobStorage;
 use golem_service_base::storage::blob::sqlite::SqliteBlobStorage;
 use golem_service_base::storage::blob::BlobStorage;
+use golem_worker_executor::engines::EngineProfile;
 use golem_worker_executor::services::compiled_component;
 use grpc::CompileGrpcService;
 use prometheus::Registry;
 use service::CompilationService;
+use std::collections::HashMap;
 use std::{
     net::{Ipv4Addr, SocketAddr},
     sync::Arc,
@@ -89,7 +91,7 @@ pub async fn run(
     let compiled_component =
         compiled_component::configured(&config.compiled_component_service, blob_storage.clone());
 
-    let engine = wasmtime::Engine::new(&create_wasmtime_config()).expect("Failed to create engine");
+    let engines = create_engines().expect("Failed to create engines");
 
     // Start metrics and healthcheck server.
     let address = config.http_addr().expect("Invalid HTTP address");
@@ -102,9 +104,10 @@ pub async fn run(
     .await?;
 
     let compilation_service = ComponentCompilationServiceImpl::new(
+        config.compile_queue,
         config.compile_worker,
         config.component_service.clone(),
-        engine,
+        engines,
         compiled_component,
     )
     .await;
@@ -171,6 +174,19 @@ async fn start_grpc_server(
     Ok(grpc_port)
 }
 
+/// Creates an engine for every engine profile, as the compilation options of the components
+/// select the optimization level and the interruption mode of the generated code
+fn create_engines() -> anyhow::Result<HashMap<EngineProfile, wasmtime::Engine>> {
+    EngineProfile::all()
+        .into_iter()
+        .map(|profile| {
+            let mut config = create_wasmtime_config();
+            profile.configure(&mut config);
+            Ok((profile, wasmtime::Engine::new(&config)?))
+        })
+        .collect()
+}
+
 fn create_wasmtime_config() -> wasmtime::Config {
     let mut config = wasmtime::Config::default();
 
@@ -15,6 +15,8 @@
 use std::fmt::Display;
 
 use crate::config::StaticComponentServiceConfig;
+use golem_common::model::component::ComponentCompilationPriority;
+use golem_common::model::component_metadata::ComponentCompilationOptions;
 use golem_common::model::{ComponentId, ProjectId};
 use tokio::sync::mpsc;
 use wasmtime::component::Component;
@@ -31,16 +33,20 @@ impl Display for ComponentWithVersion {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct CompilationRequest {
     pub component: ComponentWithVersion,
     pub project_id: ProjectId,
+    pub compilation_options: ComponentCompilationOptions,
+    /// Previous version with the same binary and engine profile, whose compiled component is
+    /// reused if it exists
+    pub reusable_version: Option<u64>,
+    pub priority: ComponentCompilationPriority,
     pub sender: Option<StaticComponentServiceConfig>,
 }
 
 pub struct CompiledComponent {
-    pub component_and_version: ComponentWithVersion,
-    pub project_id: ProjectId,
+    pub request: CompilationRequest,
     pub component: Component,
 }
 
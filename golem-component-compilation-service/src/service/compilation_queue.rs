@@ -0,0 +1,507 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::config::CompileQueueConfig;
+use crate::model::*;
+use golem_common::model::component::{
+    ComponentCompilationPriority, ComponentCompilationState, ComponentCompilationStatus,
+};
+use golem_common::model::component_metadata::ComponentCompilationOptions;
+use golem_common::model::ComponentId;
+use golem_common::retries::get_delay;
+use golem_worker_executor::engines::EngineProfile;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tokio::sync::Notify;
+use tracing::{info, warn, Instrument};
+
+// Queue of compilation requests, keeping track of the status of each requested component version.
+//
+// Interactive requests are always taken before bulk ones. Failed compilations are put back
+// to the queue after a backoff delay until the configured number of attempts is reached.
+pub struct CompilationQueue {
+    config: CompileQueueConfig,
+    state: Mutex<QueueState>,
+    notify: Notify,
+}
+
+#[derive(Default)]
+struct QueueState {
+    interactive: VecDeque<CompilationRequest>,
+    bulk: VecDeque<CompilationRequest>,
+    statuses: HashMap<(ComponentId, u64), TrackedStatus>,
+}
+
+struct TrackedStatus {
+    status: ComponentCompilationStatus,
+    priority: ComponentCompilationPriority,
+    compilation_options: ComponentCompilationOptions,
+    updated_at: Instant,
+}
+
+impl CompilationQueue {
+    pub fn new(config: CompileQueueConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(QueueState::default()),
+            notify: Notify::new(),
+        }
+    }
+
+    /// Adds a request to the queue, unless the same component version is already queued or
+    /// being compiled. An already queued bulk request gets promoted by an interactive one.
+    ///
+    /// The latest compilation options always win: an already queued request gets updated, and
+    /// a component version being compiled for a different engine profile gets queued again.
+    pub fn enqueue(&self, request: CompilationRequest) {
+        let mut guard = self.state.lock().unwrap();
+        let state = &mut *guard;
+        state.remove_expired(&self.config);
+
+        let key = key_of(&request.component);
+        if let Some(tracked) = state.statuses.get_mut(&key) {
+            match tracked.status.state {
+                ComponentCompilationState::Queued => {
+                    if request.compilation_options != tracked.compilation_options {
+                        tracked.compilation_options = request.compilation_options.clone();
+                        // Not found if the request is waiting for a retry, in which case the
+                        // retry is going to use the updated options
+                        if let Some(queued) = state
+                            .interactive
+                            .iter_mut()
+                            .chain(state.bulk.iter_mut())
+                            .find(|queued| key_of(&queued.component) == key)
+                        {
+                            queued.compilation_options = request.compilation_options.clone();
+                        }
+                    }
+                    if request.priority == ComponentCompilationPriority::Interactive
+                        && tracked.priority == ComponentCompilationPriority::Bulk
+                    {
+                        tracked.priority = ComponentCompilationPriority::Interactive;
+                        // Not found if the request is waiting for a retry, in which case the
+                        // retry is going to use the updated priority
+                        if let Some(idx) = state
+                            .bulk
+                            .iter()
+                            .position(|queued| key_of(&queued.component) == key)
+                        {
+                            let mut queued = state.bulk.remove(idx).unwrap();
+                            queued.priority = ComponentCompilationPriority::Interactive;
+                            state.interactive.push_back(queued);
+                        }
+                    }
+                    return;
+                }
+                ComponentCompilationState::Compiling
+                    if same_engine_profile(
+                        &request.compilation_options,
+                        &tracked.compilation_options,
+                    ) =>
+                {
+                    return
+                }
+                _ => {}
+            }
+        }
+
+        state.statuses.insert(
+            key,
+            TrackedStatus {
+                status: ComponentCompilationStatus {
+                    state: ComponentCompilationState::Queued,
+                    attempts: 0,
+                    error: None,
+                },
+                priority: request.priority,
+                compilation_options: request.compilation_options.clone(),
+                updated_at: Instant::now(),
+            },
+        );
+        state.push(request);
+        drop(guard);
+
+        self.notify.notify_one();
+    }
+
+    /// Waits for the next request to compile and marks it as being compiled
+    pub async fn next(&self) -> CompilationRequest {
+        loop {
+            if let Some(request) = self.try_next() {
+                return request;
+            }
+            self.notify.notified().await;
+        }
+    }
+
+    pub fn succeeded(&self, request: &CompilationRequest) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(tracked) = state.statuses.get_mut(&key_of(&request.component)) {
+            // Compiled for an outdated engine profile, the component version is queued again
+            if !same_engine_profile(&tracked.compilation_options, &request.compilation_options) {
+                return;
+            }
+            tracked.status.state = ComponentCompilationState::Done;
+            tracked.status.error = None;
+            tracked.updated_at = Instant::now();
+        }
+    }
+
+    /// Records a failed compilation attempt, and schedules the next attempt if there are any left
+    pub fn failed(self: &Arc<Self>, request: CompilationRequest, error: &CompilationError) {
+        let mut state = self.state.lock().unwrap();
+        let Some(tracked) = state.statuses.get_mut(&key_of(&request.component)) else {
+            return;
+        };
+        if !same_engine_profile(&tracked.compilation_options, &request.compilation_options) {
+            return;
+        }
+
+        tracked.status.error = Some(error.to_string());
+        tracked.updated_at = Instant::now();
+
+        match get_delay(&self.config.retries, tracked.status.attempts) {
+            Some(delay) => {
+                info!(
+                    component_id = request.component.id.to_string(),
+                    component_version = request.component.version.to_string(),
+                    attempts = tracked.status.attempts,
+                    "Retrying compilation in {delay:?}"
+                );
+                tracked.status.state = ComponentCompilationState::Queued;
+
+                let queue = self.clone();
+                tokio::spawn(
+                    async move {
+                        tokio::time::sleep(delay).await;
+                        queue.requeue(request);
+                    }
+                    .in_current_span(),
+                );
+            }
+            None => {
+                warn!(
+                    component_id = request.component.id.to_string(),
+                    component_version = request.component.version.to_string(),
+                    attempts = tracked.status.attempts,
+                    "Giving up compiling component"
+                );
+                tracked.status.state = ComponentCompilationState::Failed;
+            }
+        }
+    }
+
+    pub fn status(&self, component_id: &ComponentId, version: u64) -> ComponentCompilationStatus {
+        let state = self.state.lock().unwrap();
+        state
+            .statuses
+            .get(&(component_id.clone(), version))
+            .map(|tracked| tracked.status.clone())
+            .unwrap_or_else(ComponentCompilationStatus::unknown)
+    }
+
+    fn try_next(&self) -> Option<CompilationRequest> {
+        let mut guard = self.state.lock().unwrap();
+        let state = &mut *guard;
+
+        let request = state
+            .interactive
+            .pop_front()
+            .or_else(|| state.bulk.pop_front())?;
+        crate::metrics::decrement_queue_length();
+
+        if let Some(tracked) = state.statuses.get_mut(&key_of(&request.component)) {
+            tracked.status.state = ComponentCompilationState::Compiling;
+            tracked.status.attempts += 1;
+            tracked.updated_at = Instant::now();
+        }
+        Some(request)
+    }
+
+    fn requeue(&self, mut request: CompilationRequest) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(tracked) = state.statuses.get(&key_of(&request.component)) {
+            request.priority = tracked.priority;
+            request.compilation_options = tracked.compilation_options.clone();
+        }
+        state.push(request);
+        drop(state);
+
+        self.notify.notify_one();
+    }
+}
+
+impl QueueState {
+    fn push(&mut self, request: CompilationRequest) {
+        match request.priority {
+            ComponentCompilationPriority::Interactive => self.interactive.push_back(request),
+            ComponentCompilationPriority::Bulk => self.bulk.push_back(request),
+        }
+        crate::metrics::increment_queue_length();
+    }
+
+    fn remove_expired(&mut self, config: &CompileQueueConfig) {
+        self.statuses.retain(|_, tracked| {
+            !tracked.status.is_finished() || tracked.updated_at.elapsed() < config.status_retention
+        });
+    }
+}
+
+// The compiled artifacts only depend on the engine profile of the options, the pooling limits
+// are enforced when instantiating the component
+fn same_engine_profile(a: &ComponentCompilationOptions, b: &ComponentCompilationOptions) -> bool {
+    EngineProfile::from(a) == EngineProfile::from(b)
+}
+
+fn key_of(component: &ComponentWithVersion) -> (ComponentId, u64) {
+    (component.id.clone(), component.version)
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use crate::config::CompileQueueConfig;
+    use crate::model::{CompilationError, CompilationRequest, ComponentWithVersion};
+    use crate::service::compilation_queue::CompilationQueue;
+    use golem_common::model::component::{ComponentCompilationPriority, ComponentCompilationState};
+    use golem_common::model::component_metadata::{
+        CompilationOptimizationLevel, ComponentCompilationOptions, ComponentPoolingLimits,
+    };
+    use golem_common::model::{ComponentId, ProjectId, RetryConfig};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    fn request(
+        component_id: &ComponentId,
+        version: u64,
+        priority: ComponentCompilationPriority,
+    ) -> CompilationRequest {
+        CompilationRequest {
+            component: ComponentWithVersion {
+                id: component_id.clone(),
+                version,
+            },
+            project_id: ProjectId::new_v4(),
+            compilation_options: Default::default(),
+            reusable_version: None,
+            priority,
+            sender: None,
+        }
+    }
+
+    fn config(max_attempts: u32) -> CompileQueueConfig {
+        CompileQueueConfig {
+            retries: RetryConfig {
+                max_attempts,
+                min_delay: Duration::from_millis(1),
+                max_delay: Duration::from_millis(10),
+                multiplier: 2.0,
+                max_jitter_factor: None,
+            },
+            status_retention: Duration::from_secs(60),
+        }
+    }
+
+    #[test]
+    async fn interactive_requests_are_taken_first() {
+        let queue = CompilationQueue::new(config(3));
+        let component_id = ComponentId::new_v4();
+
+        queue.enqueue(request(
+            &component_id,
+            0,
+            ComponentCompilationPriority::Bulk,
+        ));
+        queue.enqueue(request(
+            &component_id,
+            1,
+            ComponentCompilationPriority::Bulk,
+        ));
+        queue.enqueue(request(
+            &component_id,
+            2,
+            ComponentCompilationPriority::Interactive,
+        ));
+        // Promotes the already queued bulk request
+        queue.enqueue(request(
+            &component_id,
+            1,
+            ComponentCompilationPriority::Interactive,
+        ));
+
+        let versions = [
+            queue.next().await.component.version,
+            queue.next().await.component.version,
+            queue.next().await.component.version,
+        ];
+        assert_eq!(versions, [2, 1, 0]);
+
+        let status = queue.status(&component_id, 0);
+        assert_eq!(status.state, ComponentCompilationState::Compiling);
+        assert_eq!(status.attempts, 1);
+        assert_eq!(
+            queue.status(&component_id, 3).state,
+            ComponentCompilationState::Unknown
+        );
+    }
+
+    #[test]
+    async fn failed_compilations_are_retried() {
+        let queue = Arc::new(CompilationQueue::new(config(2)));
+        let component_id = ComponentId::new_v4();
+
+        queue.enqueue(request(
+            &component_id,
+            0,
+            ComponentCompilationPriority::Interactive,
+        ));
+
+        let first = queue.next().await;
+        queue.failed(
+            first,
+            &CompilationError::CompileFailure("first".to_string()),
+        );
+        assert_eq!(
+            queue.status(&component_id, 0).state,
+            ComponentCompilationState::Queued
+        );
+
+        let second = queue.next().await;
+        queue.failed(
+            second,
+            &CompilationError::CompileFailure("second".to_string()),
+        );
+
+        let status = queue.status(&component_id, 0);
+        assert_eq!(status.state, ComponentCompilationState::Failed);
+        assert_eq!(status.attempts, 2);
+        assert_eq!(
+            status.error,
+            Some("Failed to compile component: second".to_string())
+        );
+
+        // Failed compilations can be requested again
+        queue.enqueue(request(
+            &component_id,
+            0,
+            ComponentCompilationPriority::Interactive,
+        ));
+        let third = queue.next().await;
+        queue.succeeded(&third);
+
+        let status = queue.status(&component_id, 0);
+        assert_eq!(status.state, ComponentCompilationState::Done);
+        assert_eq!(status.attempts, 1);
+    }
+
+    fn optimized_for_size() -> ComponentCompilationOptions {
+        ComponentCompilationOptions {
+            optimization_level: CompilationOptimizationLevel::SpeedAndSize,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    async fn changed_compilation_options_are_compiled_again() {
+        let queue = CompilationQueue::new(config(3));
+        let component_id = ComponentId::new_v4();
+
+        queue.enqueue(request(
+            &component_id,
+            0,
+            ComponentCompilationPriority::Interactive,
+        ));
+        let compiling = queue.next().await;
+
+        // Unchanged options do not queue the component version again
+        queue.enqueue(request(
+            &component_id,
+            0,
+            ComponentCompilationPriority::Interactive,
+        ));
+        assert!(queue.try_next().is_none());
+
+        let mut changed = request(&component_id, 0, ComponentCompilationPriority::Interactive);
+        changed.compilation_options = optimized_for_size();
+        queue.enqueue(changed);
+        assert_eq!(
+            queue.status(&component_id, 0).state,
+            ComponentCompilationState::Queued
+        );
+
+        // The outdated compilation does not finish the queued one
+        queue.succeeded(&compiling);
+        assert_eq!(
+            queue.status(&component_id, 0).state,
+            ComponentCompilationState::Queued
+        );
+
+        let recompiling = queue.next().await;
+        assert_eq!(recompiling.compilation_options, optimized_for_size());
+        queue.succeeded(&recompiling);
+        assert_eq!(
+            queue.status(&component_id, 0).state,
+            ComponentCompilationState::Done
+        );
+    }
+
+    #[test]
+    async fn queued_request_gets_the_latest_compilation_options() {
+        let queue = CompilationQueue::new(config(3));
+        let component_id = ComponentId::new_v4();
+
+        queue.enqueue(request(
+            &component_id,
+            0,
+            ComponentCompilationPriority::Bulk,
+        ));
+        let mut changed = request(&component_id, 0, ComponentCompilationPriority::Bulk);
+        changed.compilation_options = optimized_for_size();
+        queue.enqueue(changed);
+
+        let next = queue.next().await;
+        assert_eq!(next.compilation_options, optimized_for_size());
+        assert!(queue.try_next().is_none());
+    }
+
+    #[test]
+    async fn changed_pooling_limits_are_not_compiled_again() {
+        let queue = CompilationQueue::new(config(3));
+        let component_id = ComponentId::new_v4();
+
+        queue.enqueue(request(
+            &component_id,
+            0,
+            ComponentCompilationPriority::Interactive,
+        ));
+        let compiling = queue.next().await;
+
+        let mut changed = request(&component_id, 0, ComponentCompilationPriority::Interactive);
+        changed.compilation_options.pooling_limits = Some(ComponentPoolingLimits {
+            max_memory_size: 64 * 1024 * 1024,
+            table_elements: 10000,
+            max_core_instances: 20,
+        });
+        queue.enqueue(changed);
+        assert!(queue.try_next().is_none());
+
+        queue.succeeded(&compiling);
+        assert_eq!(
+            queue.status(&component_id, 0).state,
+            ComponentCompilationState::Done
+        );
+    }
+}
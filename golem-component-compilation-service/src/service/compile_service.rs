@@ -13,11 +13,18 @@
 // limitations under the License.
 
 use super::*;
-use crate::config::{CompileWorkerConfig, ComponentServiceConfig, StaticComponentServiceConfig};
+use crate::config::{
+    CompileQueueConfig, CompileWorkerConfig, ComponentServiceConfig, StaticComponentServiceConfig,
+};
 use crate::model::*;
+use crate::service::compilation_queue::CompilationQueue;
 use async_trait::async_trait;
+use golem_common::model::component::{ComponentCompilationPriority, ComponentCompilationStatus};
+use golem_common::model::component_metadata::ComponentCompilationOptions;
 use golem_common::model::{ComponentId, ProjectId};
+use golem_worker_executor::engines::EngineProfile;
 use golem_worker_executor::services::compiled_component::CompiledComponentService;
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::mpsc;
 use wasmtime::Engine;
@@ -29,40 +36,50 @@ pub trait CompilationService {
         component_id: ComponentId,
         component_version: u64,
         project_id: ProjectId,
+        compilation_options: ComponentCompilationOptions,
+        reusable_version: Option<u64>,
+        priority: ComponentCompilationPriority,
         sender: Option<StaticComponentServiceConfig>,
     ) -> Result<(), CompilationError>;
+
+    async fn get_compilation_status(
+        &self,
+        component_id: &ComponentId,
+        component_version: u64,
+    ) -> Result<ComponentCompilationStatus, CompilationError>;
 }
 
 #[derive(Clone)]
 pub struct ComponentCompilationServiceImpl {
-    queue: mpsc::Sender<CompilationRequest>,
+    queue: Arc<CompilationQueue>,
 }
 
 impl ComponentCompilationServiceImpl {
     pub async fn new(
+        compile_queue: CompileQueueConfig,
         compile_worker: CompileWorkerConfig,
         component_service: ComponentServiceConfig,
 
-        engine: Engine,
+        engines: HashMap<EngineProfile, Engine>,
 
         compiled_component_service: Arc<dyn CompiledComponentService>,
     ) -> Self {
-        let (compile_tx, compile_rx) = mpsc::channel(100);
+        let queue = Arc::new(CompilationQueue::new(compile_queue));
         let (upload_tx, upload_rx) = mpsc::channel(100);
 
         CompileWorker::start(
             component_service.static_config(),
             compile_worker,
-            engine.clone(),
+            engines,
             compiled_component_service.clone(),
             upload_tx,
-            compile_rx,
+            queue.clone(),
         )
         .await;
 
-        UploadWorker::start(compiled_component_service.clone(), upload_rx);
+        UploadWorker::start(compiled_component_service.clone(), queue.clone(), upload_rx);
 
-        Self { queue: compile_tx }
+        Self { queue }
     }
 }
 
@@ -73,11 +90,16 @@ impl CompilationService for ComponentCompilationServiceImpl {
         component_id: ComponentId,
         component_version: u64,
         project_id: ProjectId,
+        compilation_options: ComponentCompilationOptions,
+        reusable_version: Option<u64>,
+        priority: ComponentCompilationPriority,
         sender: Option<StaticComponentServiceConfig>,
     ) -> Result<(), CompilationError> {
         tracing::info!(
             component_id = component_id.to_string(),
             component_version = component_version.to_string(),
+            compilation_options = compilation_options.fingerprint(),
+            priority = format!("{priority:?}"),
             "Enqueueing compilation for component",
         );
         let request = CompilationRequest {
@@ -86,10 +108,20 @@ impl CompilationService for ComponentCompilationServiceImpl {
                 version: component_version,
             },
             project_id,
+            compilation_options,
+            reusable_version,
+            priority,
             sender,
         };
-        self.queue.send(request).await?;
-        crate::metrics::increment_queue_length();
+        self.queue.enqueue(request);
         Ok(())
     }
+
+    async fn get_compilation_status(
+        &self,
+        component_id: &ComponentId,
+        component_version: u64,
+    ) -> Result<ComponentCompilationStatus, CompilationError> {
+        Ok(self.queue.status(component_id, component_version))
+    }
 }
@@ -14,6 +14,7 @@
 
 use crate::config::{CompileWorkerConfig, StaticComponentServiceConfig};
 use crate::model::*;
+use crate::service::compilation_queue::CompilationQueue;
 use futures::TryStreamExt;
 use golem_api_grpc::proto::golem::component::v1::component_service_client::ComponentServiceClient;
 use golem_api_grpc::proto::golem::component::v1::download_component_response;
@@ -21,14 +22,17 @@ use golem_api_grpc::proto::golem::component::v1::ComponentError;
 use golem_api_grpc::proto::golem::component::v1::DownloadComponentRequest;
 use golem_common::client::{GrpcClient, GrpcClientConfig};
 use golem_common::metrics::external_calls::record_external_call_response_size_bytes;
+use golem_common::model::component_metadata::ComponentCompilationOptions;
 use golem_common::model::RetryConfig;
 use golem_common::model::{ComponentId, ProjectId};
 use golem_common::retries::with_retries;
+use golem_worker_executor::engines::EngineProfile;
 use golem_worker_executor::grpc::authorised_grpc_request;
 use golem_worker_executor::grpc::is_grpc_retriable;
 use golem_worker_executor::grpc::GrpcError;
 use golem_worker_executor::metrics::component::record_compilation_time;
 use golem_worker_executor::services::compiled_component::CompiledComponentService;
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Instant;
 use tokio::sync::{mpsc, Mutex};
@@ -48,7 +52,7 @@ pub struct CompileWorker {
     config: CompileWorkerConfig,
 
     // Resources
-    engine: Engine,
+    engines: HashMap<EngineProfile, Engine>,
     compiled_component_service: Arc<dyn CompiledComponentService>,
     client: Arc<Mutex<Option<ClientWithToken>>>,
 }
@@ -58,14 +62,14 @@ impl CompileWorker {
         component_service_config: Option<StaticComponentServiceConfig>,
         config: CompileWorkerConfig,
 
-        engine: Engine,
+        engines: HashMap<EngineProfile, Engine>,
         compiled_component_service: Arc<dyn CompiledComponentService>,
 
         sender: mpsc::Sender<CompiledComponent>,
-        mut recv: mpsc::Receiver<CompilationRequest>,
+        queue: Arc<CompilationQueue>,
     ) {
         let worker = Self {
-            engine,
+            engines,
             compiled_component_service,
             config: config.clone(),
             client: Arc::new(Mutex::new(None)),
@@ -77,17 +81,22 @@ impl CompileWorker {
 
         tokio::spawn(
             async move {
-                while let Some(request) = recv.recv().await {
-                    crate::metrics::decrement_queue_length();
+                loop {
+                    let request = queue.next().await;
 
-                    if let Some(sender) = request.sender {
+                    if let Some(sender) = &request.sender {
                         if worker.client.lock().await.is_none() {
-                            worker.set_client(sender).await;
+                            worker.set_client(sender.clone()).await;
                         }
                     }
 
                     let result = worker
-                        .compile_component(&request.component, &request.project_id)
+                        .compile_component(
+                            &request.component,
+                            &request.project_id,
+                            &request.compilation_options,
+                            request.reusable_version,
+                        )
                         .await;
                     match result {
                         Err(error) => {
@@ -97,15 +106,11 @@ impl CompileWorker {
                                 error = error.to_string(),
                                 "Failed to compile component"
                             );
+                            queue.failed(request, &error);
                         }
                         Ok(component) => {
-                            let send_result = sender
-                                .send(CompiledComponent {
-                                    component_and_version: request.component,
-                                    component,
-                                    project_id: request.project_id,
-                                })
-                                .await;
+                            let send_result =
+                                sender.send(CompiledComponent { request, component }).await;
 
                             if send_result.is_err() {
                                 tracing::error!("Failed to send compiled component");
@@ -151,8 +156,18 @@ impl CompileWorker {
         &self,
         component_with_version: &ComponentWithVersion,
         project_id: &ProjectId,
+        compilation_options: &ComponentCompilationOptions,
+        reusable_version: Option<u64>,
     ) -> Result<Component, CompilationError> {
-        let engine = self.engine.clone();
+        let engine = self
+            .engines
+            .get(&EngineProfile::from(compilation_options))
+            .cloned()
+            .ok_or_else(|| {
+                CompilationError::Unexpected(format!(
+                    "No engine for compilation options {compilation_options:?}"
+                ))
+            })?;
 
         // Ensure that the component hasn't already been compiled.
         let result = self
@@ -161,6 +176,7 @@ impl CompileWorker {
                 project_id,
                 &component_with_version.id,
                 component_with_version.version,
+                compilation_options,
                 &engine,
             )
             .await;
@@ -176,6 +192,40 @@ impl CompileWorker {
             }
         };
 
+        // The previous version has the same binary and engine profile, so its compiled component
+        // only has to be stored for this version.
+        if let Some(reusable_version) = reusable_version {
+            let result = self
+                .compiled_component_service
+                .get(
+                    project_id,
+                    &component_with_version.id,
+                    reusable_version,
+                    compilation_options,
+                    &engine,
+                )
+                .await;
+
+            match result {
+                Ok(Some(component)) => {
+                    info!(
+                        component_id = component_with_version.id.to_string(),
+                        component_version = component_with_version.version.to_string(),
+                        reusable_version = reusable_version.to_string(),
+                        "Reusing compiled component of previous version"
+                    );
+                    return Ok(component);
+                }
+                Ok(_) => (),
+                Err(err) => {
+                    warn!(
+                        "Failed to download compiled component {}@{}: {}",
+                        component_with_version.id, reusable_version, err
+                    );
+                }
+            };
+        }
+
         if let Some(client) = &*self.client.lock().await {
             let bytes = download_via_grpc(
                 &client.client,
@@ -289,3 +339,201 @@ struct ClientWithToken {
     client: GrpcClient<ComponentServiceClient<OtelGrpcService<Channel>>>,
     access_token: Uuid,
 }
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use crate::config::CompileWorkerConfig;
+    use crate::model::{CompilationError, ComponentWithVersion};
+    use crate::service::compile_worker::CompileWorker;
+    use golem_common::model::component_metadata::{
+        CompilationInterruption, CompilationOptimizationLevel, ComponentCompilationOptions,
+        ComponentPoolingLimits,
+    };
+    use golem_common::model::{ComponentId, ProjectId};
+    use golem_service_base::storage::blob::memory::InMemoryBlobStorage;
+    use golem_worker_executor::engines::EngineProfile;
+    use golem_worker_executor::services::compiled_component::{
+        CompiledComponentService, DefaultCompiledComponentService,
+    };
+    use std::sync::Arc;
+    use tokio::sync::Mutex;
+    use wasmtime::component::Component;
+
+    fn optimized_for_size() -> ComponentCompilationOptions {
+        ComponentCompilationOptions {
+            optimization_level: CompilationOptimizationLevel::SpeedAndSize,
+            interruption: CompilationInterruption::Epoch,
+            pooling_limits: None,
+        }
+    }
+
+    // Without a component service, the worker can only return already compiled components
+    fn worker(compiled_component_service: Arc<dyn CompiledComponentService>) -> CompileWorker {
+        CompileWorker {
+            config: CompileWorkerConfig::default(),
+            engines: crate::create_engines().unwrap(),
+            compiled_component_service,
+            client: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    fn compile(worker: &CompileWorker, options: &ComponentCompilationOptions) -> Component {
+        let engine = &worker.engines[&EngineProfile::from(options)];
+        Component::new(engine, "(component)").unwrap()
+    }
+
+    #[test]
+    async fn different_compilation_options_are_cached_separately() {
+        let compiled_component_service: Arc<dyn CompiledComponentService> = Arc::new(
+            DefaultCompiledComponentService::new(Arc::new(InMemoryBlobStorage::new())),
+        );
+        let worker = worker(compiled_component_service.clone());
+        let project_id = ProjectId::new_v4();
+        let component_id = ComponentId::new_v4();
+        let default_options = ComponentCompilationOptions::default();
+
+        assert_ne!(
+            default_options.fingerprint(),
+            optimized_for_size().fingerprint()
+        );
+
+        compiled_component_service
+            .put(
+                &project_id,
+                &component_id,
+                0,
+                &default_options,
+                &compile(&worker, &default_options),
+            )
+            .await
+            .unwrap();
+
+        // The component compiled with the default options is not found with the other options,
+        // so it is never loaded by an engine it was not compiled for
+        let engine = &worker.engines[&EngineProfile::from(&optimized_for_size())];
+        let cached = compiled_component_service
+            .get(&project_id, &component_id, 0, &optimized_for_size(), engine)
+            .await
+            .unwrap();
+        assert!(cached.is_none());
+
+        let engine = &worker.engines[&EngineProfile::from(&default_options)];
+        let cached = compiled_component_service
+            .get(&project_id, &component_id, 0, &default_options, engine)
+            .await
+            .unwrap();
+        assert!(cached.is_some());
+    }
+
+    #[test]
+    async fn unchanged_compilation_options_are_not_compiled_again() {
+        let compiled_component_service: Arc<dyn CompiledComponentService> = Arc::new(
+            DefaultCompiledComponentService::new(Arc::new(InMemoryBlobStorage::new())),
+        );
+        let worker = worker(compiled_component_service.clone());
+        let project_id = ProjectId::new_v4();
+        let component = ComponentWithVersion {
+            id: ComponentId::new_v4(),
+            version: 0,
+        };
+
+        compiled_component_service
+            .put(
+                &project_id,
+                &component.id,
+                component.version,
+                &optimized_for_size(),
+                &compile(&worker, &optimized_for_size()),
+            )
+            .await
+            .unwrap();
+
+        let result = worker
+            .compile_component(&component, &project_id, &optimized_for_size(), None)
+            .await;
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    async fn changed_compilation_options_are_compiled_again() {
+        let compiled_component_service: Arc<dyn CompiledComponentService> = Arc::new(
+            DefaultCompiledComponentService::new(Arc::new(InMemoryBlobStorage::new())),
+        );
+        let worker = worker(compiled_component_service.clone());
+        let project_id = ProjectId::new_v4();
+        let component = ComponentWithVersion {
+            id: ComponentId::new_v4(),
+            version: 0,
+        };
+        let default_options = ComponentCompilationOptions::default();
+
+        compiled_component_service
+            .put(
+                &project_id,
+                &component.id,
+                component.version,
+                &default_options,
+                &compile(&worker, &default_options),
+            )
+            .await
+            .unwrap();
+
+        // Missing the cache, the component has to be downloaded for compiling it again, which
+        // fails without a component service
+        let result = worker
+            .compile_component(&component, &project_id, &optimized_for_size(), None)
+            .await;
+        assert!(matches!(
+            result,
+            Err(CompilationError::Unexpected(error)) if error == "Component service is not configured"
+        ));
+    }
+
+    #[test]
+    async fn changed_pooling_limits_reuse_the_compiled_component() {
+        let compiled_component_service: Arc<dyn CompiledComponentService> = Arc::new(
+            DefaultCompiledComponentService::new(Arc::new(InMemoryBlobStorage::new())),
+        );
+        let worker = worker(compiled_component_service.clone());
+        let project_id = ProjectId::new_v4();
+        let component_id = ComponentId::new_v4();
+        let limited = ComponentCompilationOptions {
+            pooling_limits: Some(ComponentPoolingLimits {
+                max_memory_size: 64 * 1024 * 1024,
+                table_elements: 10000,
+                max_core_instances: 20,
+            }),
+            ..optimized_for_size()
+        };
+
+        assert_eq!(limited.fingerprint(), optimized_for_size().fingerprint());
+
+        compiled_component_service
+            .put(
+                &project_id,
+                &component_id,
+                0,
+                &optimized_for_size(),
+                &compile(&worker, &optimized_for_size()),
+            )
+            .await
+            .unwrap();
+
+        // The next version only changes the pooling limits, so it is not downloaded for
+        // compiling it, which would fail without a component service
+        let result = worker
+            .compile_component(
+                &ComponentWithVersion {
+                    id: component_id,
+                    version: 1,
+                },
+                &project_id,
+                &limited,
+                Some(0),
+            )
+            .await;
+        assert!(result.is_ok());
+    }
+}
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod compilation_queue;
 pub mod compile_service;
 mod compile_worker;
 mod upload_worker;
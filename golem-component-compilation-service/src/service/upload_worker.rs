@@ -19,20 +19,24 @@ use tokio::sync::mpsc;
 use tracing::Instrument;
 
 use crate::model::*;
+use crate::service::compilation_queue::CompilationQueue;
 
 // Worker that uploads compiled components to the cloud.
 #[derive(Clone)]
 pub struct UploadWorker {
     compiled_component_service: Arc<dyn CompiledComponentService>,
+    queue: Arc<CompilationQueue>,
 }
 
 impl UploadWorker {
     pub fn start(
         compiled_component_service: Arc<dyn CompiledComponentService>,
+        queue: Arc<CompilationQueue>,
         mut recv: mpsc::Receiver<CompiledComponent>,
     ) {
         let worker = Self {
             compiled_component_service,
+            queue,
         };
 
         tokio::spawn(
@@ -49,36 +53,38 @@ impl UploadWorker {
 
     // Don't need retries because they're baked into CompiledComponentService.
     async fn upload_component(&self, compiled_component: CompiledComponent) {
-        let CompiledComponent {
-            component_and_version,
-            component,
-            project_id,
-        } = compiled_component;
+        let CompiledComponent { request, component } = compiled_component;
 
         let upload_result = self
             .compiled_component_service
             .put(
-                &project_id,
-                &component_and_version.id,
-                component_and_version.version,
+                &request.project_id,
+                &request.component.id,
+                request.component.version,
+                &request.compilation_options,
                 &component,
             )
             .await
             .map_err(|err| CompilationError::ComponentUploadFailed(err.to_string()));
 
-        if let Err(ref err) = upload_result {
-            tracing::warn!(
-                component_id = component_and_version.id.to_string(),
-                component_version = component_and_version.version.to_string(),
-                error = err.to_string(),
-                "Failed to upload compiled component"
-            );
-        } else {
-            tracing::info!(
-                component_id = component_and_version.id.to_string(),
-                component_version = component_and_version.version.to_string(),
-                "Successfully uploaded compiled component"
-            );
+        match upload_result {
+            Err(err) => {
+                tracing::warn!(
+                    component_id = request.component.id.to_string(),
+                    component_version = request.component.version.to_string(),
+                    error = err.to_string(),
+                    "Failed to upload compiled component"
+                );
+                self.queue.failed(request, &err);
+            }
+            Ok(()) => {
+                tracing::info!(
+                    component_id = request.component.id.to_string(),
+                    component_version = request.component.version.to_string(),
+                    "Successfully uploaded compiled component"
+                );
+                self.queue.succeeded(&request);
+            }
         }
     }
 }
@@ -23,7 +23,8 @@ use crate::model::{ComponentQuery, ComponentSearch};
 use futures::{stream, StreamExt, TryStreamExt};
 use golem_common::model::agent::AgentTypes;
 use golem_common::model::auth::AuthCtx;
-use golem_common::model::component::VersionedComponentId;
+use golem_common::model::component::{ComponentCompilationStatus, VersionedComponentId};
+use golem_common::model::component_metadata::ComponentCompilationOptions;
 use golem_common::model::error::{ErrorBody, ErrorsBody};
 use golem_common::model::plugin::{PluginInstallationCreation, PluginInstallationUpdate};
 use golem_common::model::ComponentId;
@@ -745,6 +746,101 @@ impl ComponentApi {
         Ok(Json(Empty {}))
     }
 
+    /// Update the compilation options of a component
+    ///
+    /// Creates a new version of the component with the given compilation options, and triggers its compilation.
+    /// The compilation options select the optimization level, whether the component is interrupted by consumed fuel
+    /// or by epochs only, and the pooling limits of its instances.
+    #[oai(
+        path = "/:component_id/compilation-options",
+        method = "put",
+        operation_id = "update_component_compilation_options"
+    )]
+    async fn update_compilation_options(
+        &self,
+        component_id: Path<ComponentId>,
+        compilation_options: Json<ComponentCompilationOptions>,
+        token: GolemSecurityScheme,
+    ) -> Result<Json<dto::Component>> {
+        let auth = AuthCtx::new(token.secret());
+
+        let record = recorded_http_api_request!(
+            "update_component_compilation_options",
+            component_id = component_id.0.to_string(),
+        );
+
+        let response = self
+            .update_compilation_options_internal(component_id.0, compilation_options.0, auth)
+            .instrument(record.span.clone())
+            .await;
+        record.result(response)
+    }
+
+    async fn update_compilation_options_internal(
+        &self,
+        component_id: ComponentId,
+        compilation_options: ComponentCompilationOptions,
+        auth: AuthCtx,
+    ) -> Result<Json<dto::Component>> {
+        let component = self
+            .component_service
+            .update_compilation_options(&auth, &component_id, compilation_options)
+            .await?;
+        let converted = self.api_mapper.convert_component(component).await?;
+        Ok(Json(converted))
+    }
+
+    /// Get the compilation status of a component version
+    ///
+    /// Returns whether the given version of the component is queued for compilation, being compiled, compiled,
+    /// or failed to compile. The status is `Unknown` when the compilation service has no information about the
+    /// version, for example because it was compiled a long time ago.
+    #[oai(
+        path = "/:component_id/versions/:version/compilation-status",
+        method = "get",
+        operation_id = "get_component_compilation_status"
+    )]
+    async fn get_compilation_status(
+        &self,
+        component_id: Path<ComponentId>,
+        version: Path<String>,
+        token: GolemSecurityScheme,
+    ) -> Result<Json<ComponentCompilationStatus>> {
+        let auth = AuthCtx::new(token.secret());
+
+        let record = recorded_http_api_request!(
+            "get_component_compilation_status",
+            component_id = component_id.0.to_string(),
+            version = version.0,
+        );
+
+        let response = self
+            .get_compilation_status_internal(component_id.0, version.0, auth)
+            .instrument(record.span.clone())
+            .await;
+        record.result(response)
+    }
+
+    async fn get_compilation_status_internal(
+        &self,
+        component_id: ComponentId,
+        version: String,
+        auth: AuthCtx,
+    ) -> Result<Json<ComponentCompilationStatus>> {
+        let version_int = Self::parse_version_path_segment(&version)?;
+
+        let versioned_component_id = VersionedComponentId {
+            component_id,
+            version: version_int,
+        };
+
+        let status = self
+            .component_service
+            .get_compilation_status(&versioned_component_id, &auth)
+            .await?;
+        Ok(Json(status))
+    }
+
     /// Download file in a Component
     #[oai(
         path = "/:component_id/versions/:version/file-contents/:file_path",
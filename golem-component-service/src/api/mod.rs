@@ -155,6 +155,7 @@ impl From<DomainComponentError> for ComponentError {
             | DomainComponentError::TransformationFailed(_)
             | DomainComponentError::PluginApplicationFailed(_)
             | DomainComponentError::FailedToDownloadFile
+            | DomainComponentError::CompilationServiceError(_)
             | DomainComponentError::BlobStorageError(_) => {
                 ComponentError::InternalError(Json(ErrorBody {
                     error: value.to_safe_string(),
@@ -24,8 +24,9 @@ use futures::stream::BoxStream;
 use golem_common::model::agent::AgentType;
 use golem_common::model::auth::AuthCtx;
 use golem_common::model::auth::ProjectAction;
-use golem_common::model::component::VersionedComponentId;
+use golem_common::model::component::{ComponentCompilationStatus, VersionedComponentId};
 use golem_common::model::component_constraint::FunctionConstraints;
+use golem_common::model::component_metadata::ComponentCompilationOptions;
 use golem_common::model::component_metadata::DynamicLinkedInstance;
 use golem_common::model::plugin::{
     PluginInstallation, PluginInstallationAction, PluginInstallationCreation,
@@ -546,6 +547,45 @@ impl AuthedComponentService {
             .await
     }
 
+    pub async fn update_compilation_options(
+        &self,
+        auth: &AuthCtx,
+        component_id: &ComponentId,
+        compilation_options: ComponentCompilationOptions,
+    ) -> Result<Component, ComponentError> {
+        let owner = is_authorized_by_component(
+            &self.auth_service,
+            &self.component_service,
+            auth,
+            component_id,
+            &ProjectAction::UpdateComponent,
+        )
+        .await?;
+
+        self.component_service
+            .update_compilation_options(&owner, component_id, compilation_options)
+            .await
+    }
+
+    pub async fn get_compilation_status(
+        &self,
+        component_id: &VersionedComponentId,
+        auth: &AuthCtx,
+    ) -> Result<ComponentCompilationStatus, ComponentError> {
+        let owner = is_authorized_by_component(
+            &self.auth_service,
+            &self.component_service,
+            auth,
+            &component_id.component_id,
+            &ProjectAction::ViewComponent,
+        )
+        .await?;
+
+        self.component_service
+            .get_compilation_status(component_id, &owner)
+            .await
+    }
+
     pub async fn get_file_contents(
         &self,
         auth: &AuthCtx,
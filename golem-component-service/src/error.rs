@@ -108,6 +108,8 @@ pub enum ComponentError {
     PluginInstallationNotFound {
         installation_id: PluginInstallationId,
     },
+    #[error("Component compilation service error: {0}")]
+    CompilationServiceError(String),
 }
 
 impl ComponentError {
@@ -195,6 +197,7 @@ impl SafeDisplay for ComponentError {
             Self::InvalidPluginScope { .. } => self.to_string(),
             Self::ConcurrentUpdate { .. } => self.to_string(),
             Self::PluginInstallationNotFound { .. } => self.to_string(),
+            Self::CompilationServiceError(_) => self.to_string(),
         }
     }
 }
@@ -253,6 +256,7 @@ impl From<ComponentError> for golem_api_grpc::proto::golem::component::v1::Compo
             | ComponentError::ComponentStoreError { .. }
             | ComponentError::InternalConversionError { .. }
             | ComponentError::InternalRepoError(_)
+            | ComponentError::CompilationServiceError(_)
             | ComponentError::BlobStorageError(_) => {
                 component_error::Error::InternalError(ErrorBody {
                     error: value.to_safe_string(),
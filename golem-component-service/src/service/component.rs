@@ -33,8 +33,10 @@ use futures::TryStreamExt;
 use golem_common::model::agent::AgentType;
 use golem_common::model::component::ComponentOwner;
 use golem_common::model::component::VersionedComponentId;
+use golem_common::model::component::{ComponentCompilationPriority, ComponentCompilationStatus};
 use golem_common::model::component_constraint::FunctionConstraints;
 use golem_common::model::component_constraint::FunctionSignature;
+use golem_common::model::component_metadata::ComponentCompilationOptions;
 use golem_common::model::component_metadata::ComponentMetadata;
 use golem_common::model::component_metadata::DynamicLinkedInstance;
 use golem_common::model::plugin::PluginOwner;
@@ -253,6 +255,22 @@ pub trait ComponentService: Debug + Send + Sync {
         component_id: &ComponentId,
         actions: &[PluginInstallationAction],
     ) -> Result<Vec<Option<PluginInstallation>>, ComponentError>;
+
+    /// Creates a new version of the component with the given compilation options, and enqueues
+    /// its compilation
+    async fn update_compilation_options(
+        &self,
+        owner: &ComponentOwner,
+        component_id: &ComponentId,
+        compilation_options: ComponentCompilationOptions,
+    ) -> Result<Component, ComponentError>;
+
+    /// Gets the status of the compilation of the given component version
+    async fn get_compilation_status(
+        &self,
+        component_id: &VersionedComponentId,
+        owner: &ComponentOwner,
+    ) -> Result<ComponentCompilationStatus, ComponentError>;
 }
 
 #[derive(Debug)]
@@ -615,6 +633,31 @@ impl ComponentService for LazyComponentService {
             .batch_update_plugin_installations_for_component(owner, component_id, actions)
             .await
     }
+
+    async fn update_compilation_options(
+        &self,
+        owner: &ComponentOwner,
+        component_id: &ComponentId,
+        compilation_options: ComponentCompilationOptions,
+    ) -> Result<Component, ComponentError> {
+        let lock = self.0.read().await;
+        lock.as_ref()
+            .unwrap()
+            .update_compilation_options(owner, component_id, compilation_options)
+            .await
+    }
+
+    async fn get_compilation_status(
+        &self,
+        component_id: &VersionedComponentId,
+        owner: &ComponentOwner,
+    ) -> Result<ComponentCompilationStatus, ComponentError> {
+        let lock = self.0.read().await;
+        lock.as_ref()
+            .unwrap()
+            .get_compilation_status(component_id, owner)
+            .await
+    }
 }
 
 pub struct ComponentServiceDefault {
@@ -907,6 +950,9 @@ impl ComponentServiceDefault {
                 &owner.project_id,
                 component_id,
                 component.versioned_component_id.version,
+                component.metadata.compilation_options(),
+                None,
+                ComponentCompilationPriority::Interactive,
             )
             .await;
 
@@ -966,7 +1012,8 @@ impl ComponentServiceDefault {
         component.regenerate_object_store_key();
         component.regenerate_transformed_object_store_key();
         component.files = files.unwrap_or(component.files);
-        component.metadata = metadata;
+        component.metadata =
+            metadata.with_compilation_options(component.metadata.compilation_options().clone());
         component.env = env;
 
         // reset transformations so that plugins see original data of the component
@@ -998,6 +1045,9 @@ impl ComponentServiceDefault {
                 &owner.project_id,
                 component_id,
                 component.versioned_component_id.version,
+                component.metadata.compilation_options(),
+                None,
+                ComponentCompilationPriority::Interactive,
             )
             .await;
 
@@ -1107,7 +1157,8 @@ impl ComponentServiceDefault {
             component.metadata.dynamic_linking().clone(),
             component.metadata.agent_types().to_vec(),
         )
-        .map_err(ComponentError::ComponentProcessingError)?;
+        .map_err(ComponentError::ComponentProcessingError)?
+        .with_compilation_options(component.metadata.compilation_options().clone());
 
         Ok((component, data))
     }
@@ -2007,6 +2058,84 @@ impl ComponentService for ComponentServiceDefault {
 
         Ok(result)
     }
+
+    async fn update_compilation_options(
+        &self,
+        owner: &ComponentOwner,
+        component_id: &ComponentId,
+        compilation_options: ComponentCompilationOptions,
+    ) -> Result<Component, ComponentError> {
+        let mut component: Component = self
+            .get_latest_version(component_id, owner)
+            .await?
+            .ok_or(ComponentError::UnknownComponentId(component_id.clone()))?;
+
+        info!(
+            owner = %owner,
+            component_id = %component_id,
+            compilation_options = ?compilation_options,
+            "Updating compilation options",
+        );
+
+        // the component binaries do not change, so the object store keys of the previous
+        // version are reused, and so is its compiled component if only the pooling limits
+        // change
+        let previous_version = component.versioned_component_id.version;
+        let reusable_version = (component.metadata.compilation_options().fingerprint()
+            == compilation_options.fingerprint())
+        .then_some(previous_version);
+        component.bump_version();
+        component.metadata = component
+            .metadata
+            .with_compilation_options(compilation_options);
+
+        let record = ComponentRecord::try_from_model(component.clone())
+            .map_err(|e| ComponentError::conversion_error("record", e))?;
+
+        let create_result = self.component_repo.create(&record).await;
+        match create_result {
+            Err(RepoError::UniqueViolation(_)) => Err(ComponentError::ConcurrentUpdate {
+                component_id: component_id.clone(),
+                version: component.versioned_component_id.version,
+            })?,
+            Err(other) => Err(other)?,
+            Ok(()) => {}
+        };
+
+        self.component_compilation
+            .enqueue_compilation(
+                &owner.project_id,
+                component_id,
+                component.versioned_component_id.version,
+                component.metadata.compilation_options(),
+                reusable_version,
+                ComponentCompilationPriority::Bulk,
+            )
+            .await;
+
+        Ok(component)
+    }
+
+    async fn get_compilation_status(
+        &self,
+        component_id: &VersionedComponentId,
+        owner: &ComponentOwner,
+    ) -> Result<ComponentCompilationStatus, ComponentError> {
+        info!(
+            owner = %owner,
+            component_id = %component_id,
+            "Get component compilation status"
+        );
+
+        // Makes sure that the component version exists and belongs to the owner
+        self.get_by_version(component_id, owner).await?.ok_or(
+            ComponentError::UnknownVersionedComponentId(component_id.clone()),
+        )?;
+
+        self.component_compilation
+            .get_compilation_status(&component_id.component_id, component_id.version)
+            .await
+    }
 }
 
 struct ZipEntryStream {
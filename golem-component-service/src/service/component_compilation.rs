@@ -12,12 +12,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::error::ComponentError;
 use async_trait::async_trait;
+use golem_api_grpc::proto::golem::componentcompilation::v1::ComponentCompilationPriority as GrpcComponentCompilationPriority;
 use golem_api_grpc::proto::golem::componentcompilation::v1::{
     component_compilation_service_client::ComponentCompilationServiceClient,
-    ComponentCompilationRequest,
+    component_compilation_status_response, ComponentCompilationRequest,
+    ComponentCompilationStatusRequest,
 };
 use golem_common::client::{GrpcClient, GrpcClientConfig};
+use golem_common::model::component::{ComponentCompilationPriority, ComponentCompilationStatus};
+use golem_common::model::component_metadata::ComponentCompilationOptions;
 use golem_common::model::{ComponentId, ProjectId, RetryConfig};
 use http::Uri;
 use std::fmt::{Debug, Formatter};
@@ -29,13 +34,25 @@ use tonic_tracing_opentelemetry::middleware::client::OtelGrpcService;
 
 #[async_trait]
 pub trait ComponentCompilationService: Debug + Send + Sync {
+    /// Enqueues the compilation of a component version. The compiled component of the
+    /// `reusable_version` is reused if it exists, so it must have the same binary and be compiled
+    /// for the same engine profile.
     async fn enqueue_compilation(
         &self,
         project_id: &ProjectId,
         component_id: &ComponentId,
         component_version: u64,
+        compilation_options: &ComponentCompilationOptions,
+        reusable_version: Option<u64>,
+        priority: ComponentCompilationPriority,
     );
 
+    async fn get_compilation_status(
+        &self,
+        component_id: &ComponentId,
+        component_version: u64,
+    ) -> Result<ComponentCompilationStatus, ComponentError>;
+
     fn set_self_grpc_port(&self, grpc_port: u16);
 }
 
@@ -80,9 +97,13 @@ impl ComponentCompilationService for ComponentCompilationServiceDefault {
         project_id: &ProjectId,
         component_id: &ComponentId,
         component_version: u64,
+        compilation_options: &ComponentCompilationOptions,
+        reusable_version: Option<u64>,
+        priority: ComponentCompilationPriority,
     ) {
         let component_id_clone = component_id.clone();
         let project_id_clone = project_id.clone();
+        let compilation_options = compilation_options.clone();
 
         let component_service_port = match self.component_service_port.load(Ordering::Acquire) {
             0 => None,
@@ -94,12 +115,16 @@ impl ComponentCompilationService for ComponentCompilationServiceDefault {
             .call("enqueue-compilation", move |client| {
                 let component_id_clone = component_id_clone.clone();
                 let project_id_clone = project_id_clone.clone();
+                let compilation_options = compilation_options.clone();
                 Box::pin(async move {
                     let request = ComponentCompilationRequest {
                         component_id: Some(component_id_clone.into()),
                         component_version,
                         component_service_port,
                         project_id: Some(project_id_clone.into()),
+                        compilation_options: Some(compilation_options.into()),
+                        priority: GrpcComponentCompilationPriority::from(priority) as i32,
+                        reusable_version,
                     };
 
                     client.enqueue_compilation(request).await
@@ -120,6 +145,43 @@ impl ComponentCompilationService for ComponentCompilationServiceDefault {
         }
     }
 
+    async fn get_compilation_status(
+        &self,
+        component_id: &ComponentId,
+        component_version: u64,
+    ) -> Result<ComponentCompilationStatus, ComponentError> {
+        let component_id_clone = component_id.clone();
+
+        let response = self
+            .client
+            .call("get-compilation-status", move |client| {
+                let component_id_clone = component_id_clone.clone();
+                Box::pin(async move {
+                    let request = ComponentCompilationStatusRequest {
+                        component_id: Some(component_id_clone.into()),
+                        component_version,
+                    };
+
+                    client.get_compilation_status(request).await
+                })
+            })
+            .await
+            .map_err(|e| ComponentError::CompilationServiceError(e.to_string()))?
+            .into_inner();
+
+        match response.result {
+            None => Err(ComponentError::CompilationServiceError(
+                "Empty response".to_string(),
+            )),
+            Some(component_compilation_status_response::Result::Success(status)) => {
+                Ok(status.into())
+            }
+            Some(component_compilation_status_response::Result::Failure(error)) => Err(
+                ComponentError::CompilationServiceError(format!("{:?}", error.error)),
+            ),
+        }
+    }
+
     fn set_self_grpc_port(&self, grpc_port: u16) {
         self.component_service_port
             .store(grpc_port, Ordering::Release);
@@ -137,7 +199,24 @@ impl Debug for ComponentCompilationServiceDisabled {
 
 #[async_trait]
 impl ComponentCompilationService for ComponentCompilationServiceDisabled {
-    async fn enqueue_compilation(&self, _: &ProjectId, _: &ComponentId, _: u64) {}
+    async fn enqueue_compilation(
+        &self,
+        _: &ProjectId,
+        _: &ComponentId,
+        _: u64,
+        _: &ComponentCompilationOptions,
+        _: Option<u64>,
+        _: ComponentCompilationPriority,
+    ) {
+    }
+
+    async fn get_compilation_status(
+        &self,
+        _: &ComponentId,
+        _: u64,
+    ) -> Result<ComponentCompilationStatus, ComponentError> {
+        Ok(ComponentCompilationStatus::unknown())
+    }
 
     fn set_self_grpc_port(&self, _grpc_port: u16) {}
 }
@@ -96,6 +96,10 @@ impl DebugConfig {
             port: default_golem_config.port,
             http_address: self.http_address,
             http_port: self.http_port,
+            // unused
+            admin_http_address: default_golem_config.admin_http_address,
+            // unused
+            admin_http_port: None,
             shard_manager_service: ShardManagerServiceConfig::SingleShard(
                 ShardManagerServiceSingleShardConfig {},
             ),
@@ -102,6 +102,8 @@ impl FuelManagement for DebugContext {
     async fn return_fuel(&mut self, _current_level: i64) -> Result<i64, WorkerExecutorError> {
         Ok(0)
     }
+
+    fn charge_fuel(&mut self, _amount: i64) {}
 }
 
 #[async_trait]
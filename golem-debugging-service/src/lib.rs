@@ -34,6 +34,7 @@ use async_trait::async_trait;
 use golem_service_base::clients::auth::AuthService as BaseAuthService;
 use golem_service_base::storage::blob::BlobStorage;
 use golem_worker_executor::durable_host::DurableWorkerCtx;
+use golem_worker_executor::engines::ComponentEngines;
 use golem_worker_executor::preview2::{golem_agent, golem_api_1_x, golem_durability};
 use golem_worker_executor::services::active_workers::ActiveWorkers;
 use golem_worker_executor::services::agent_types::AgentTypesService;
@@ -89,7 +90,10 @@ impl Bootstrap<DebugContext> for ServerBootstrap {
         &self,
         golem_config: &GolemConfig,
     ) -> Arc<ActiveWorkers<DebugContext>> {
-        Arc::new(ActiveWorkers::<DebugContext>::new(&golem_config.memory))
+        Arc::new(ActiveWorkers::<DebugContext>::new(
+            &golem_config.memory,
+            &golem_config.engine,
+        ))
     }
 
     fn create_component_service(
@@ -130,8 +134,7 @@ impl Bootstrap<DebugContext> for ServerBootstrap {
     async fn create_services(
         &self,
         active_workers: Arc<ActiveWorkers<DebugContext>>,
-        engine: Arc<Engine>,
-        linker: Arc<Linker<DebugContext>>,
+        engines: Arc<ComponentEngines<DebugContext>>,
         runtime: Handle,
         component_service: Arc<dyn ComponentService>,
         shard_manager_service: Arc<dyn ShardManagerService>,
@@ -180,8 +183,7 @@ impl Bootstrap<DebugContext> for ServerBootstrap {
                 shard_service.clone(),
             )),
             active_workers.clone(),
-            engine.clone(),
-            linker.clone(),
+            engines.clone(),
             runtime.clone(),
             component_service.clone(),
             shard_manager_service.clone(),
@@ -216,8 +218,7 @@ impl Bootstrap<DebugContext> for ServerBootstrap {
                 shard_service.clone(),
             )),
             active_workers.clone(),
-            engine.clone(),
-            linker.clone(),
+            engines.clone(),
             runtime.clone(),
             component_service.clone(),
             worker_fork.clone(),
@@ -247,8 +248,7 @@ impl Bootstrap<DebugContext> for ServerBootstrap {
         Ok(All::new(
             active_workers,
             agent_types_service,
-            engine,
-            linker,
+            engines,
             runtime.clone(),
             component_service,
             shard_manager_service,
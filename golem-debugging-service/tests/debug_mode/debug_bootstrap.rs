@@ -11,6 +11,7 @@ use golem_debugging_service::oplog::debug_oplog_service::DebugOplogService;
 use golem_debugging_service::{create_debug_wasmtime_linker, run_debug_worker_executor};
 use golem_service_base::storage::blob::BlobStorage;
 use golem_test_framework::components::worker_executor::provided::ProvidedWorkerExecutor;
+use golem_worker_executor::engines::ComponentEngines;
 use golem_worker_executor::services::active_workers::ActiveWorkers;
 use golem_worker_executor::services::agent_types::AgentTypesService;
 use golem_worker_executor::services::blob_store::BlobStoreService;
@@ -68,7 +69,10 @@ impl Bootstrap<DebugContext> for TestDebuggingServerBootStrap {
         &self,
         golem_config: &GolemConfig,
     ) -> Arc<ActiveWorkers<DebugContext>> {
-        Arc::new(ActiveWorkers::<DebugContext>::new(&golem_config.memory))
+        Arc::new(ActiveWorkers::<DebugContext>::new(
+            &golem_config.memory,
+            &golem_config.engine,
+        ))
     }
 
     fn create_plugins(
@@ -109,8 +113,7 @@ impl Bootstrap<DebugContext> for TestDebuggingServerBootStrap {
     async fn create_services(
         &self,
         active_workers: Arc<ActiveWorkers<DebugContext>>,
-        engine: Arc<Engine>,
-        linker: Arc<Linker<DebugContext>>,
+        engines: Arc<ComponentEngines<DebugContext>>,
         runtime: Handle,
         component_service: Arc<dyn ComponentService>,
         shard_manager_service: Arc<dyn ShardManagerService>,
@@ -169,8 +172,7 @@ impl Bootstrap<DebugContext> for TestDebuggingServerBootStrap {
                 shard_service.clone(),
             )),
             active_workers.clone(),
-            engine.clone(),
-            linker.clone(),
+            engines.clone(),
             runtime.clone(),
             component_service.clone(),
             shard_manager_service.clone(),
@@ -203,8 +205,7 @@ impl Bootstrap<DebugContext> for TestDebuggingServerBootStrap {
                 shard_service.clone(),
             )),
             active_workers.clone(),
-            engine.clone(),
-            linker.clone(),
+            engines.clone(),
             runtime.clone(),
             component_service.clone(),
             worker_fork.clone(),
@@ -234,8 +235,7 @@ impl Bootstrap<DebugContext> for TestDebuggingServerBootStrap {
         Ok(All::new(
             active_workers,
             agent_types_service,
-            engine,
-            linker,
+            engines,
             runtime.clone(),
             component_service,
             shard_manager_service,
@@ -3,6 +3,7 @@ use crate::{get_component_cache_config, get_component_service_config};
 use async_trait::async_trait;
 use golem_service_base::storage::blob::BlobStorage;
 use golem_worker_executor::durable_host::DurableWorkerCtx;
+use golem_worker_executor::engines::ComponentEngines;
 use golem_worker_executor::preview2::{golem_api_1_x, golem_durability};
 use golem_worker_executor::services::active_workers::ActiveWorkers;
 use golem_worker_executor::services::agent_types::AgentTypesService;
@@ -47,7 +48,10 @@ impl Bootstrap<TestWorkerCtx> for RegularWorkerExecutorBootstrap {
         &self,
         golem_config: &GolemConfig,
     ) -> Arc<ActiveWorkers<TestWorkerCtx>> {
-        Arc::new(ActiveWorkers::<TestWorkerCtx>::new(&golem_config.memory))
+        Arc::new(ActiveWorkers::<TestWorkerCtx>::new(
+            &golem_config.memory,
+            &golem_config.engine,
+        ))
     }
 
     fn create_plugins(
@@ -79,8 +83,7 @@ impl Bootstrap<TestWorkerCtx> for RegularWorkerExecutorBootstrap {
     async fn create_services(
         &self,
         active_workers: Arc<ActiveWorkers<TestWorkerCtx>>,
-        engine: Arc<Engine>,
-        linker: Arc<Linker<TestWorkerCtx>>,
+        engines: Arc<ComponentEngines<TestWorkerCtx>>,
         runtime: Handle,
         component_service: Arc<dyn ComponentService>,
         shard_manager_service: Arc<dyn ShardManagerService>,
@@ -114,8 +117,7 @@ impl Bootstrap<TestWorkerCtx> for RegularWorkerExecutorBootstrap {
                 shard_service.clone(),
             )),
             active_workers.clone(),
-            engine.clone(),
-            linker.clone(),
+            engines.clone(),
             runtime.clone(),
             component_service.clone(),
             shard_manager_service.clone(),
@@ -148,8 +150,7 @@ impl Bootstrap<TestWorkerCtx> for RegularWorkerExecutorBootstrap {
                 shard_service.clone(),
             )),
             active_workers.clone(),
-            engine.clone(),
-            linker.clone(),
+            engines.clone(),
             runtime.clone(),
             component_service.clone(),
             worker_fork.clone(),
@@ -178,8 +179,7 @@ impl Bootstrap<TestWorkerCtx> for RegularWorkerExecutorBootstrap {
         Ok(All::new(
             active_workers,
             agent_types_service,
-            engine,
-            linker,
+            engines,
             runtime,
             component_service,
             shard_manager_service,
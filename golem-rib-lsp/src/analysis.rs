@@ -0,0 +1,497 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use rib::{
+    ComponentDependencies, ComponentDependency, Expr, RibCompilationError, RibCompiler,
+    RibCompilerConfig, SourcePosition, SourceSpan,
+};
+
+/// Static analysis of a single Rib script, backing the features of the language server.
+/// Positions and spans are in terms of the Rib script itself (1-based lines and columns),
+/// and it is the responsibility of the caller to map them to the enclosing document.
+pub struct RibAnalyzer {
+    compiler: RibCompiler,
+    component_dependencies: ComponentDependencies,
+}
+
+#[derive(Debug, Clone)]
+pub struct RibDiagnostic {
+    pub message: String,
+    pub source_span: SourceSpan,
+    pub severity: RibDiagnosticSeverity,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RibDiagnosticSeverity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone)]
+pub struct RibHover {
+    pub contents: String,
+    pub source_span: SourceSpan,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RibCompletionKind {
+    WorkerFunction,
+    BuiltInFunction,
+    Variable,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RibCompletion {
+    pub label: String,
+    pub detail: Option<String>,
+    pub documentation: Option<String>,
+    pub kind: RibCompletionKind,
+}
+
+impl RibAnalyzer {
+    pub fn new(component_dependencies: Vec<ComponentDependency>) -> RibAnalyzer {
+        let compiler = RibCompiler::new(RibCompilerConfig::new(
+            component_dependencies,
+            vec![],
+            vec![],
+        ));
+
+        let component_dependencies = compiler.get_component_dependencies();
+
+        RibAnalyzer {
+            compiler,
+            component_dependencies,
+        }
+    }
+
+    pub fn diagnostics(&self, rib: &str) -> Vec<RibDiagnostic> {
+        let expr = match Expr::from_text(rib) {
+            Ok(expr) => expr,
+            Err(error) => return vec![internal::parse_error_diagnostic(error)],
+        };
+
+        match self.compiler.compile(expr) {
+            Ok(compiler_output) => compiler_output
+                .lint_warnings
+                .iter()
+                .map(internal::lint_diagnostic)
+                .collect(),
+            Err(RibCompilationError::DeniedLints(lints)) => {
+                lints.iter().map(internal::lint_diagnostic).collect()
+            }
+            Err(error) => vec![internal::compilation_error_diagnostic(error)],
+        }
+    }
+
+    // Hover is only available for scripts that type check,
+    // as the types of a partially inferred script would be misleading
+    pub fn hover(&self, rib: &str, position: &SourcePosition) -> Option<RibHover> {
+        let expr = Expr::from_text(rib).ok()?;
+        let inferred_expr = self.compiler.infer_types(expr).ok()?;
+        let mut expr = inferred_expr.get_expr().clone();
+
+        let node = internal::innermost_expr_at(&mut expr, position)?;
+
+        let contents = match &node {
+            Expr::Identifier { variable_id, .. } => {
+                format!("{}: {}", variable_id, node.inferred_type().printable())
+            }
+            Expr::Let {
+                variable_id, expr, ..
+            } => format!("{}: {}", variable_id, expr.inferred_type().printable()),
+            _ => node.inferred_type().printable(),
+        };
+
+        Some(RibHover {
+            contents,
+            source_span: node.source_span(),
+        })
+    }
+
+    pub fn completions(&self, rib: &str, position: &SourcePosition) -> Vec<RibCompletion> {
+        let mut completions = internal::let_binding_completions(rib, position);
+        completions.extend(internal::worker_function_completions(
+            &self.component_dependencies,
+        ));
+        completions.extend(internal::builtin_function_completions());
+
+        completions.sort();
+        completions.dedup();
+        completions
+    }
+
+    // The span of the `let` binding that introduces the variable at the given position
+    pub fn definition(&self, rib: &str, position: &SourcePosition) -> Option<SourceSpan> {
+        let mut expr = Expr::from_text(rib).ok()?;
+        expr.bind_variables_of_let_assignment();
+
+        let Expr::Identifier { variable_id, .. } =
+            internal::innermost_expr_at(&mut expr, position)?
+        else {
+            return None;
+        };
+
+        if !variable_id.is_local() {
+            return None;
+        }
+
+        internal::let_binding_span(&mut expr, &variable_id)
+    }
+}
+
+mod internal {
+    use crate::analysis::{RibCompletion, RibCompletionKind, RibDiagnostic, RibDiagnosticSeverity};
+    use rib::{
+        BuiltInFunction, ComponentDependencies, Expr, ExprVisitor, FunctionName,
+        RibCompilationError, RibLintWarning, SourcePosition, SourceSpan, VariableId,
+    };
+
+    pub(crate) fn parse_error_diagnostic(error: String) -> RibDiagnostic {
+        match parse_error_position(&error) {
+            Some(position) => {
+                // The first line only repeats the position
+                let message = error.lines().skip(1).collect::<Vec<_>>().join("\n");
+
+                RibDiagnostic {
+                    message: if message.trim().is_empty() {
+                        error
+                    } else {
+                        message
+                    },
+                    source_span: SourceSpan::new(position.clone(), position),
+                    severity: RibDiagnosticSeverity::Error,
+                }
+            }
+            None => RibDiagnostic {
+                message: error,
+                source_span: start_of_script(),
+                severity: RibDiagnosticSeverity::Error,
+            },
+        }
+    }
+
+    // Parse errors are reported as `Parse error at line: <line>, column: <column>`
+    // followed by the details of the error
+    pub(crate) fn parse_error_position(error: &str) -> Option<SourcePosition> {
+        let location = error
+            .lines()
+            .next()?
+            .strip_prefix("Parse error at line: ")?;
+
+        let (line, column) = location.split_once(", column: ")?;
+
+        Some(SourcePosition::new(
+            line.trim().parse().ok()?,
+            column.trim().parse().ok()?,
+        ))
+    }
+
+    pub(crate) fn compilation_error_diagnostic(error: RibCompilationError) -> RibDiagnostic {
+        match error {
+            RibCompilationError::RibTypeError(type_error) => {
+                let mut message = type_error.cause.clone();
+
+                for detail in &type_error.additional_error_details {
+                    message.push('\n');
+                    message.push_str(detail);
+                }
+
+                for help_message in &type_error.help_messages {
+                    message.push_str(&format!("\nhelp: {help_message}"));
+                }
+
+                RibDiagnostic {
+                    message,
+                    source_span: type_error.source_span.clone(),
+                    severity: RibDiagnosticSeverity::Error,
+                }
+            }
+            error => RibDiagnostic {
+                message: error.to_string(),
+                source_span: start_of_script(),
+                severity: RibDiagnosticSeverity::Error,
+            },
+        }
+    }
+
+    pub(crate) fn lint_diagnostic(warning: &RibLintWarning) -> RibDiagnostic {
+        RibDiagnostic {
+            message: format!("{} ({})", warning.message, warning.lint),
+            source_span: warning.source_span.clone(),
+            severity: if warning.is_denied() {
+                RibDiagnosticSeverity::Error
+            } else {
+                RibDiagnosticSeverity::Warning
+            },
+        }
+    }
+
+    pub(crate) fn innermost_expr_at(expr: &mut Expr, position: &SourcePosition) -> Option<Expr> {
+        let mut visitor = ExprVisitor::bottom_up(expr);
+        let mut innermost: Option<Expr> = None;
+
+        while let Some(current) = visitor.pop_front() {
+            let span = current.source_span();
+
+            if !contains(&span, position) {
+                continue;
+            }
+
+            let is_narrower = match &innermost {
+                Some(existing) => {
+                    let existing_span = existing.source_span();
+                    encloses(&existing_span, &span) && !same_span(&existing_span, &span)
+                }
+                None => true,
+            };
+
+            if is_narrower {
+                innermost = Some(current.clone());
+            }
+        }
+
+        innermost
+    }
+
+    pub(crate) fn let_binding_span(
+        expr: &mut Expr,
+        variable_id: &VariableId,
+    ) -> Option<SourceSpan> {
+        let mut visitor = ExprVisitor::top_down(expr);
+
+        while let Some(current) = visitor.pop_front() {
+            if let Expr::Let {
+                variable_id: let_variable_id,
+                source_span,
+                ..
+            } = current
+            {
+                if let_variable_id == variable_id {
+                    return Some(source_span.clone());
+                }
+            }
+        }
+
+        None
+    }
+
+    // Variables bound by a `let` that ends before the cursor.
+    // This is best effort, given a script that is being edited often fails to parse
+    pub(crate) fn let_binding_completions(
+        rib: &str,
+        position: &SourcePosition,
+    ) -> Vec<RibCompletion> {
+        let Ok(mut expr) = Expr::from_text(rib) else {
+            return vec![];
+        };
+
+        let mut completions = vec![];
+        let mut visitor = ExprVisitor::top_down(&mut expr);
+
+        while let Some(current) = visitor.pop_front() {
+            if let Expr::Let {
+                variable_id,
+                type_annotation,
+                source_span,
+                ..
+            } = current
+            {
+                let end = (source_span.end_line(), source_span.end_column());
+
+                if end <= (position.line, position.column) {
+                    completions.push(RibCompletion {
+                        label: variable_id.name(),
+                        detail: type_annotation.as_ref().map(|t| t.to_string()),
+                        documentation: None,
+                        kind: RibCompletionKind::Variable,
+                    });
+                }
+            }
+        }
+
+        completions
+    }
+
+    pub(crate) fn worker_function_completions(
+        component_dependencies: &ComponentDependencies,
+    ) -> Vec<RibCompletion> {
+        let mut completions = vec![];
+
+        for function_dictionary in component_dependencies.function_dictionary() {
+            for (function_name, function_type) in &function_dictionary.name_and_types {
+                match function_name {
+                    FunctionName::Function(_)
+                    | FunctionName::ResourceConstructor(_)
+                    | FunctionName::ResourceMethod(_) => {
+                        let parameter_types = function_type
+                            .parameter_types
+                            .iter()
+                            .map(|parameter_type| parameter_type.printable())
+                            .collect::<Vec<_>>()
+                            .join(", ");
+
+                        let return_type = function_type
+                            .return_type
+                            .as_ref()
+                            .map(|return_type| return_type.printable())
+                            .unwrap_or_else(|| "()".to_string());
+
+                        completions.push(RibCompletion {
+                            label: function_name.name(),
+                            detail: Some(format!("({parameter_types}) -> {return_type}")),
+                            documentation: None,
+                            kind: RibCompletionKind::WorkerFunction,
+                        });
+                    }
+                    // Variant and enum cases are values rather than functions
+                    FunctionName::Variant(_) | FunctionName::Enum(_) => {}
+                }
+            }
+        }
+
+        completions
+    }
+
+    pub(crate) fn builtin_function_completions() -> Vec<RibCompletion> {
+        BuiltInFunction::all()
+            .iter()
+            .map(|function| RibCompletion {
+                label: function.name().to_string(),
+                detail: Some(function.signature().to_string()),
+                documentation: Some(function.description().to_string()),
+                kind: RibCompletionKind::BuiltInFunction,
+            })
+            .collect()
+    }
+
+    fn start_of_script() -> SourceSpan {
+        SourceSpan::new(SourcePosition::new(1, 1), SourcePosition::new(1, 1))
+    }
+
+    fn contains(span: &SourceSpan, position: &SourcePosition) -> bool {
+        let start = (span.start_line(), span.start_column());
+        let end = (span.end_line(), span.end_column());
+        let position = (position.line, position.column);
+
+        start <= position && position <= end
+    }
+
+    fn encloses(outer: &SourceSpan, inner: &SourceSpan) -> bool {
+        (outer.start_line(), outer.start_column()) <= (inner.start_line(), inner.start_column())
+            && (inner.end_line(), inner.end_column()) <= (outer.end_line(), outer.end_column())
+    }
+
+    fn same_span(left: &SourceSpan, right: &SourceSpan) -> bool {
+        left.start_line() == right.start_line()
+            && left.start_column() == right.start_column()
+            && left.end_line() == right.end_line()
+            && left.end_column() == right.end_column()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use crate::analysis::internal::parse_error_position;
+    use crate::analysis::{RibAnalyzer, RibCompletionKind, RibDiagnosticSeverity};
+    use rib::SourcePosition;
+
+    #[test]
+    fn test_parse_error_position() {
+        let position =
+            parse_error_position("Parse error at line: 5, column: 24\nUnexpected `=`").unwrap();
+
+        assert_eq!((position.line, position.column), (5, 24));
+        assert!(parse_error_position("unexpected `;` at the end of rib expression").is_none());
+    }
+
+    #[test]
+    fn test_diagnostics_of_valid_script() {
+        let analyzer = RibAnalyzer::new(vec![]);
+
+        let diagnostics = analyzer.diagnostics(
+            r#"
+              let x: u64 = 1;
+              x
+            "#,
+        );
+
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_diagnostics_of_type_error() {
+        let analyzer = RibAnalyzer::new(vec![]);
+
+        let diagnostics = analyzer.diagnostics("let x: u64 = \"foo\";\nx");
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].source_span.start_line(), 1);
+    }
+
+    #[test]
+    fn test_diagnostics_of_lint_warning() {
+        let analyzer = RibAnalyzer::new(vec![]);
+
+        let diagnostics = analyzer.diagnostics("let x: u64 = 1;\nlet y: u64 = 2;\ny");
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, RibDiagnosticSeverity::Warning);
+        assert_eq!(diagnostics[0].source_span.start_line(), 1);
+        assert_eq!(
+            diagnostics[0].message,
+            "variable `x` is never used (unused-let)"
+        );
+    }
+
+    #[test]
+    fn test_hover_on_identifier() {
+        let analyzer = RibAnalyzer::new(vec![]);
+
+        let hover = analyzer
+            .hover("let x: u64 = 1;\nx", &SourcePosition::new(2, 1))
+            .unwrap();
+
+        assert_eq!(hover.contents, "x: u64");
+    }
+
+    #[test]
+    fn test_definition_of_let_binding() {
+        let analyzer = RibAnalyzer::new(vec![]);
+
+        let definition = analyzer
+            .definition("let x = 1;\nlet y = x;\ny", &SourcePosition::new(2, 9))
+            .unwrap();
+
+        assert_eq!((definition.start_line(), definition.start_column()), (1, 1));
+    }
+
+    #[test]
+    fn test_completions() {
+        let analyzer = RibAnalyzer::new(vec![]);
+
+        let completions = analyzer.completions(
+            "let greeting = \"hello\";\ngreeting",
+            &SourcePosition::new(2, 1),
+        );
+
+        assert!(completions
+            .iter()
+            .any(|c| c.label == "greeting" && c.kind == RibCompletionKind::Variable));
+        assert!(completions
+            .iter()
+            .any(|c| c.label == "to-lower" && c.kind == RibCompletionKind::BuiltInFunction));
+    }
+}
@@ -0,0 +1,244 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{anyhow, Context};
+use golem_common::model::component_metadata::RawComponentMetadata;
+use rib::{ComponentDependency, ComponentDependencyKey};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use tracing::warn;
+use uuid::Uuid;
+
+/// A local WASM file of a component, whose exports are made available to the Rib scripts
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComponentSource {
+    pub component_name: String,
+    pub wasm_path: PathBuf,
+}
+
+impl FromStr for ComponentSource {
+    type Err = String;
+
+    // Parses `<component-name>=<path-to-wasm>`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (component_name, wasm_path) = s
+            .split_once('=')
+            .ok_or_else(|| format!("expected <component-name>=<path-to-wasm>, found `{s}`"))?;
+
+        if component_name.is_empty() || wasm_path.is_empty() {
+            return Err(format!(
+                "expected <component-name>=<path-to-wasm>, found `{s}`"
+            ));
+        }
+
+        Ok(ComponentSource {
+            component_name: component_name.to_string(),
+            wasm_path: PathBuf::from(wasm_path),
+        })
+    }
+}
+
+pub fn load_component_dependencies(
+    component_sources: &[ComponentSource],
+    app_manifest: Option<&Path>,
+) -> anyhow::Result<Vec<ComponentDependency>> {
+    let mut component_sources = component_sources.to_vec();
+
+    if let Some(app_manifest) = app_manifest {
+        component_sources.extend(component_sources_from_app_manifest(app_manifest)?);
+    }
+
+    component_sources.iter().map(load_component).collect()
+}
+
+pub fn load_component(component_source: &ComponentSource) -> anyhow::Result<ComponentDependency> {
+    let data = std::fs::read(&component_source.wasm_path).with_context(|| {
+        format!(
+            "failed to read component {} from {}",
+            component_source.component_name,
+            component_source.wasm_path.display()
+        )
+    })?;
+
+    let metadata = RawComponentMetadata::analyse_component(&data).map_err(|err| {
+        anyhow!(
+            "failed to analyse component {}: {}",
+            component_source.component_name,
+            err
+        )
+    })?;
+
+    // Components loaded from the local file system are not deployed,
+    // hence the component id is derived from the name to remain stable across restarts
+    let component_dependency_key = ComponentDependencyKey {
+        component_name: component_source.component_name.clone(),
+        component_id: Uuid::new_v5(
+            &Uuid::NAMESPACE_OID,
+            component_source.component_name.as_bytes(),
+        ),
+        component_version: 0,
+        root_package_name: metadata.root_package_name,
+        root_package_version: metadata.root_package_version,
+    };
+
+    Ok(ComponentDependency::new(
+        component_dependency_key,
+        metadata.exports,
+    ))
+}
+
+// Components of an application manifest (`golem.yaml`) that are already built.
+// Components that are not built yet are skipped, as they have no exports to offer.
+pub fn component_sources_from_app_manifest(
+    app_manifest: &Path,
+) -> anyhow::Result<Vec<ComponentSource>> {
+    let yaml = std::fs::read_to_string(app_manifest).with_context(|| {
+        format!(
+            "failed to read application manifest {}",
+            app_manifest.display()
+        )
+    })?;
+
+    let manifest_dir = app_manifest.parent().unwrap_or(Path::new("."));
+
+    let component_sources = internal::component_sources_from_manifest_yaml(&yaml, manifest_dir)?;
+
+    Ok(component_sources
+        .into_iter()
+        .filter(|component_source| {
+            let exists = component_source.wasm_path.exists();
+
+            if !exists {
+                warn!(
+                    "Skipping component {}, as {} does not exist. Build the application to load it",
+                    component_source.component_name,
+                    component_source.wasm_path.display()
+                );
+            }
+
+            exists
+        })
+        .collect())
+}
+
+mod internal {
+    use crate::component_loader::ComponentSource;
+    use anyhow::Context;
+    use serde::Deserialize;
+    use std::collections::HashMap;
+    use std::path::Path;
+
+    // The subset of the application manifest needed to locate the linked WASM of each component
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct AppManifest {
+        #[serde(default)]
+        temp_dir: Option<String>,
+        #[serde(default)]
+        components: HashMap<String, AppManifestComponent>,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct AppManifestComponent {
+        #[serde(default)]
+        linked_wasm: Option<String>,
+    }
+
+    pub(crate) fn component_sources_from_manifest_yaml(
+        yaml: &str,
+        manifest_dir: &Path,
+    ) -> anyhow::Result<Vec<ComponentSource>> {
+        let manifest: AppManifest =
+            serde_yaml::from_str(yaml).context("failed to parse application manifest")?;
+
+        let temp_dir = manifest_dir.join(manifest.temp_dir.as_deref().unwrap_or("golem-temp"));
+
+        let mut component_sources = manifest
+            .components
+            .into_iter()
+            .map(|(component_name, component)| {
+                // Same default as the CLI uses for the final linked WASM
+                let wasm_path = match component.linked_wasm {
+                    Some(linked_wasm) => manifest_dir.join(linked_wasm),
+                    None => temp_dir
+                        .join("final-linked-wasm")
+                        .join(format!("{component_name}.wasm")),
+                };
+
+                ComponentSource {
+                    component_name,
+                    wasm_path,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        component_sources.sort_by(|a, b| a.component_name.cmp(&b.component_name));
+
+        Ok(component_sources)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use crate::component_loader::internal::component_sources_from_manifest_yaml;
+    use crate::component_loader::ComponentSource;
+    use std::path::{Path, PathBuf};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_component_source_from_str() {
+        assert_eq!(
+            ComponentSource::from_str("shopping-cart=target/shopping_cart.wasm"),
+            Ok(ComponentSource {
+                component_name: "shopping-cart".to_string(),
+                wasm_path: PathBuf::from("target/shopping_cart.wasm"),
+            })
+        );
+
+        assert!(ComponentSource::from_str("target/shopping_cart.wasm").is_err());
+    }
+
+    #[test]
+    fn test_component_sources_from_manifest() {
+        let yaml = r#"
+components:
+  shopping-cart:
+    template: rust
+  inventory:
+    linkedWasm: build/inventory.wasm
+"#;
+
+        let component_sources =
+            component_sources_from_manifest_yaml(yaml, Path::new("/project")).unwrap();
+
+        assert_eq!(
+            component_sources,
+            vec![
+                ComponentSource {
+                    component_name: "inventory".to_string(),
+                    wasm_path: PathBuf::from("/project/build/inventory.wasm"),
+                },
+                ComponentSource {
+                    component_name: "shopping-cart".to_string(),
+                    wasm_path: PathBuf::from(
+                        "/project/golem-temp/final-linked-wasm/shopping-cart.wasm"
+                    ),
+                },
+            ]
+        );
+    }
+}
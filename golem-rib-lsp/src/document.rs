@@ -0,0 +1,252 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use rib::{SourcePosition, SourceSpan};
+use tower_lsp::lsp_types::{Position, Range, Url};
+
+// Fields of an API definition binding that hold Rib scripts
+const RIB_FIELDS: [&str; 3] = ["response", "idempotencyKey", "invocationContext"];
+
+/// A Rib script within a document opened in the editor.
+///
+/// A `.rib` file is a single script, while an API definition YAML
+/// (or an application manifest with an `httpApi` section) holds one script
+/// per Rib valued field of a route binding.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RibSnippet {
+    pub text: String,
+    line_offset: u32,
+    column_offset: u32,
+    line_count: u32,
+}
+
+impl RibSnippet {
+    fn new(text: String, line_offset: u32, column_offset: u32) -> RibSnippet {
+        let line_count = text.lines().count().max(1) as u32;
+
+        RibSnippet {
+            text,
+            line_offset,
+            column_offset,
+            line_count,
+        }
+    }
+
+    pub fn contains(&self, position: &Position) -> bool {
+        position.line >= self.line_offset
+            && position.line < self.line_offset + self.line_count
+            && position.character >= self.column_offset
+    }
+
+    pub fn to_rib_position(&self, position: &Position) -> Option<SourcePosition> {
+        if !self.contains(position) {
+            return None;
+        }
+
+        Some(SourcePosition::new(
+            (position.line - self.line_offset + 1) as i32,
+            (position.character - self.column_offset + 1) as i32,
+        ))
+    }
+
+    pub fn to_lsp_range(&self, source_span: &SourceSpan) -> Range {
+        let start = self.to_lsp_position(source_span.start_line(), source_span.start_column());
+        let mut end = self.to_lsp_position(source_span.end_line(), source_span.end_column());
+
+        // Parse errors are reported at a single position,
+        // which is widened to a character to remain visible in the editor
+        if start == end {
+            end.character += 1;
+        }
+
+        Range::new(start, end)
+    }
+
+    fn to_lsp_position(&self, line: i32, column: i32) -> Position {
+        Position::new(
+            self.line_offset + (line.max(1) - 1) as u32,
+            self.column_offset + (column.max(1) - 1) as u32,
+        )
+    }
+}
+
+pub fn rib_snippets(uri: &Url, text: &str) -> Vec<RibSnippet> {
+    let path = uri.path();
+
+    if path.ends_with(".yaml") || path.ends_with(".yml") {
+        internal::yaml_rib_snippets(text)
+    } else {
+        vec![RibSnippet::new(text.to_string(), 0, 0)]
+    }
+}
+
+mod internal {
+    use crate::document::{RibSnippet, RIB_FIELDS};
+
+    // A line based scan, rather than a YAML parser, as the positions of the scripts
+    // within the document are needed and the document is often invalid while being edited
+    pub(crate) fn yaml_rib_snippets(text: &str) -> Vec<RibSnippet> {
+        let lines = text.lines().collect::<Vec<_>>();
+        let mut snippets = vec![];
+        let mut index = 0;
+
+        while index < lines.len() {
+            let line = lines[index];
+            index += 1;
+
+            let key_indent = indentation(line);
+            let entry = line.trim_start();
+            let entry = entry.strip_prefix("- ").unwrap_or(entry);
+
+            let Some((key, value)) = entry.split_once(':') else {
+                continue;
+            };
+
+            if !RIB_FIELDS.contains(&key.trim()) {
+                continue;
+            }
+
+            let value_trimmed = value.trim();
+
+            if value_trimmed.starts_with('|') || value_trimmed.starts_with('>') {
+                let block_start = index;
+
+                while index < lines.len()
+                    && (lines[index].trim().is_empty() || indentation(lines[index]) > key_indent)
+                {
+                    index += 1;
+                }
+
+                let mut block = &lines[block_start..index];
+
+                while let Some((last, rest)) = block.split_last() {
+                    if last.trim().is_empty() {
+                        block = rest;
+                    } else {
+                        break;
+                    }
+                }
+
+                if block.is_empty() {
+                    continue;
+                }
+
+                let block_indent = block
+                    .iter()
+                    .filter(|line| !line.trim().is_empty())
+                    .map(|line| indentation(line))
+                    .min()
+                    .unwrap_or(0);
+
+                let script = block
+                    .iter()
+                    .map(|line| line.get(block_indent..).unwrap_or(""))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+
+                snippets.push(RibSnippet::new(
+                    script,
+                    block_start as u32,
+                    block_indent as u32,
+                ));
+            } else if !value_trimmed.is_empty() {
+                let value_start = line.len() - value.trim_start().len();
+
+                let (script, column) = match unquote(value_trimmed) {
+                    Some(unquoted) => (unquoted, value_start + 1),
+                    None => (value_trimmed, value_start),
+                };
+
+                snippets.push(RibSnippet::new(
+                    script.to_string(),
+                    (index - 1) as u32,
+                    column as u32,
+                ));
+            }
+        }
+
+        snippets
+    }
+
+    fn indentation(line: &str) -> usize {
+        line.len() - line.trim_start().len()
+    }
+
+    fn unquote(value: &str) -> Option<&str> {
+        ['"', '\''].iter().find_map(|quote| {
+            value
+                .strip_prefix(*quote)
+                .and_then(|value| value.strip_suffix(*quote))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use crate::document::rib_snippets;
+    use tower_lsp::lsp_types::{Position, Url};
+
+    #[test]
+    fn test_rib_file_is_a_single_snippet() {
+        let uri = Url::parse("file:///project/script.rib").unwrap();
+        let snippets = rib_snippets(&uri, "let x = 1;\nx");
+
+        assert_eq!(snippets.len(), 1);
+        assert_eq!(snippets[0].text, "let x = 1;\nx");
+    }
+
+    #[test]
+    fn test_yaml_block_snippets() {
+        let uri = Url::parse("file:///project/golem.yaml").unwrap();
+
+        let yaml = r#"httpApi:
+  definitions:
+    shopping-cart:
+      routes:
+      - method: GET
+        path: /{user}/contents
+        binding:
+          componentName: shopping-cart
+          response: |
+            let worker = instance("cart-${request.path.user}");
+            worker.get-cart-contents()
+
+      - method: POST
+        path: /{user}/contents
+        binding:
+          componentName: shopping-cart
+          idempotencyKey: "request.headers.key"
+"#;
+
+        let snippets = rib_snippets(&uri, yaml);
+
+        assert_eq!(snippets.len(), 2);
+        assert_eq!(
+            snippets[0].text,
+            "let worker = instance(\"cart-${request.path.user}\");\nworker.get-cart-contents()"
+        );
+        assert_eq!(snippets[1].text, "request.headers.key");
+
+        let position = snippets[0].to_rib_position(&Position::new(10, 12)).unwrap();
+
+        assert_eq!((position.line, position.column), (2, 1));
+        assert!(snippets[0].to_rib_position(&Position::new(7, 12)).is_none());
+
+        let position = snippets[1].to_rib_position(&Position::new(16, 27)).unwrap();
+
+        assert_eq!((position.line, position.column), (1, 1));
+    }
+}
@@ -0,0 +1,56 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use clap::Parser;
+use golem_rib_lsp::{load_component_dependencies, ComponentSource, RibAnalyzer, RibLanguageServer};
+use std::path::PathBuf;
+use tower_lsp::{LspService, Server};
+
+/// Language server for Rib, communicating over stdio
+#[derive(Parser, Debug)]
+#[command(name = "golem-rib-lsp", version)]
+struct Args {
+    /// Component whose exported functions are available to Rib scripts,
+    /// given as `<component-name>=<path-to-wasm>`. Can be repeated.
+    #[arg(long = "component")]
+    components: Vec<ComponentSource>,
+
+    /// Application manifest (golem.yaml) whose built components are loaded
+    #[arg(long)]
+    app_manifest: Option<PathBuf>,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    // stdout is reserved for the protocol
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .with_ansi(false)
+        .init();
+
+    let args = Args::parse();
+
+    let component_dependencies =
+        load_component_dependencies(&args.components, args.app_manifest.as_deref())?;
+
+    let (service, socket) = LspService::new(|client| {
+        RibLanguageServer::new(client, RibAnalyzer::new(component_dependencies))
+    });
+
+    Server::new(tokio::io::stdin(), tokio::io::stdout(), socket)
+        .serve(service)
+        .await;
+
+    Ok(())
+}
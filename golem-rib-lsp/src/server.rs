@@ -0,0 +1,211 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::analysis::{RibAnalyzer, RibCompletionKind, RibDiagnosticSeverity};
+use crate::document::{rib_snippets, RibSnippet};
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+use tower_lsp::jsonrpc::Result;
+use tower_lsp::lsp_types::{
+    CompletionItem, CompletionItemKind, CompletionOptions, CompletionParams, CompletionResponse,
+    Diagnostic, DiagnosticSeverity, DidChangeTextDocumentParams, DidCloseTextDocumentParams,
+    DidOpenTextDocumentParams, Documentation, GotoDefinitionParams, GotoDefinitionResponse, Hover,
+    HoverContents, HoverParams, HoverProviderCapability, InitializeParams, InitializeResult,
+    InitializedParams, Location, MarkupContent, MarkupKind, MessageType, OneOf, Position,
+    ServerCapabilities, ServerInfo, TextDocumentSyncCapability, TextDocumentSyncKind, Url,
+};
+use tower_lsp::{Client, LanguageServer};
+
+pub struct RibLanguageServer {
+    client: Client,
+    analyzer: RibAnalyzer,
+    documents: RwLock<HashMap<Url, String>>,
+}
+
+impl RibLanguageServer {
+    pub fn new(client: Client, analyzer: RibAnalyzer) -> Self {
+        Self {
+            client,
+            analyzer,
+            documents: RwLock::new(HashMap::new()),
+        }
+    }
+
+    async fn update_document(&self, uri: Url, text: String) {
+        let diagnostics = rib_snippets(&uri, &text)
+            .iter()
+            .flat_map(|snippet| {
+                self.analyzer
+                    .diagnostics(&snippet.text)
+                    .into_iter()
+                    .map(|diagnostic| Diagnostic {
+                        range: snippet.to_lsp_range(&diagnostic.source_span),
+                        severity: Some(match diagnostic.severity {
+                            RibDiagnosticSeverity::Error => DiagnosticSeverity::ERROR,
+                            RibDiagnosticSeverity::Warning => DiagnosticSeverity::WARNING,
+                        }),
+                        source: Some("rib".to_string()),
+                        message: diagnostic.message,
+                        ..Default::default()
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        self.documents.write().await.insert(uri.clone(), text);
+
+        self.client
+            .publish_diagnostics(uri, diagnostics, None)
+            .await;
+    }
+
+    async fn snippet_at(&self, uri: &Url, position: &Position) -> Option<RibSnippet> {
+        let documents = self.documents.read().await;
+        let text = documents.get(uri)?;
+
+        rib_snippets(uri, text)
+            .into_iter()
+            .find(|snippet| snippet.contains(position))
+    }
+}
+
+#[tower_lsp::async_trait]
+impl LanguageServer for RibLanguageServer {
+    async fn initialize(&self, _: InitializeParams) -> Result<InitializeResult> {
+        Ok(InitializeResult {
+            capabilities: ServerCapabilities {
+                text_document_sync: Some(TextDocumentSyncCapability::Kind(
+                    TextDocumentSyncKind::FULL,
+                )),
+                hover_provider: Some(HoverProviderCapability::Simple(true)),
+                completion_provider: Some(CompletionOptions {
+                    trigger_characters: Some(vec![".".to_string()]),
+                    ..Default::default()
+                }),
+                definition_provider: Some(OneOf::Left(true)),
+                ..Default::default()
+            },
+            server_info: Some(ServerInfo {
+                name: "golem-rib-lsp".to_string(),
+                version: None,
+            }),
+        })
+    }
+
+    async fn initialized(&self, _: InitializedParams) {
+        self.client
+            .log_message(MessageType::INFO, "Rib language server initialized")
+            .await;
+    }
+
+    async fn shutdown(&self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn did_open(&self, params: DidOpenTextDocumentParams) {
+        self.update_document(params.text_document.uri, params.text_document.text)
+            .await;
+    }
+
+    async fn did_change(&self, mut params: DidChangeTextDocumentParams) {
+        // With full synchronization the last change holds the whole document
+        if let Some(change) = params.content_changes.pop() {
+            self.update_document(params.text_document.uri, change.text)
+                .await;
+        }
+    }
+
+    async fn did_close(&self, params: DidCloseTextDocumentParams) {
+        let uri = params.text_document.uri;
+
+        self.documents.write().await.remove(&uri);
+        self.client.publish_diagnostics(uri, vec![], None).await;
+    }
+
+    async fn hover(&self, params: HoverParams) -> Result<Option<Hover>> {
+        let uri = params.text_document_position_params.text_document.uri;
+        let position = params.text_document_position_params.position;
+
+        let Some(snippet) = self.snippet_at(&uri, &position).await else {
+            return Ok(None);
+        };
+
+        let hover = snippet
+            .to_rib_position(&position)
+            .and_then(|rib_position| self.analyzer.hover(&snippet.text, &rib_position));
+
+        Ok(hover.map(|hover| Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: format!("```rib\n{}\n```", hover.contents),
+            }),
+            range: Some(snippet.to_lsp_range(&hover.source_span)),
+        }))
+    }
+
+    async fn completion(&self, params: CompletionParams) -> Result<Option<CompletionResponse>> {
+        let uri = params.text_document_position.text_document.uri;
+        let position = params.text_document_position.position;
+
+        let Some(snippet) = self.snippet_at(&uri, &position).await else {
+            return Ok(None);
+        };
+
+        let Some(rib_position) = snippet.to_rib_position(&position) else {
+            return Ok(None);
+        };
+
+        let items = self
+            .analyzer
+            .completions(&snippet.text, &rib_position)
+            .into_iter()
+            .map(|completion| CompletionItem {
+                label: completion.label,
+                kind: Some(match completion.kind {
+                    RibCompletionKind::WorkerFunction => CompletionItemKind::METHOD,
+                    RibCompletionKind::BuiltInFunction => CompletionItemKind::FUNCTION,
+                    RibCompletionKind::Variable => CompletionItemKind::VARIABLE,
+                }),
+                detail: completion.detail,
+                documentation: completion.documentation.map(Documentation::String),
+                ..Default::default()
+            })
+            .collect();
+
+        Ok(Some(CompletionResponse::Array(items)))
+    }
+
+    async fn goto_definition(
+        &self,
+        params: GotoDefinitionParams,
+    ) -> Result<Option<GotoDefinitionResponse>> {
+        let uri = params.text_document_position_params.text_document.uri;
+        let position = params.text_document_position_params.position;
+
+        let Some(snippet) = self.snippet_at(&uri, &position).await else {
+            return Ok(None);
+        };
+
+        let definition = snippet
+            .to_rib_position(&position)
+            .and_then(|rib_position| self.analyzer.definition(&snippet.text, &rib_position));
+
+        Ok(definition.map(|source_span| {
+            GotoDefinitionResponse::Scalar(Location::new(
+                uri.clone(),
+                snippet.to_lsp_range(&source_span),
+            ))
+        }))
+    }
+}
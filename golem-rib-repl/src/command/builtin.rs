@@ -1,4 +1,7 @@
-use crate::{parse_with_clap, Command, FunctionSignaturePrintConfig, ReplContext};
+use crate::{
+    parse_with_clap, Command, FunctionSignaturePrintConfig, ReplContext, ReplSession,
+    RibExecutionError,
+};
 use clap::{CommandFactory, Parser};
 use crossterm::cursor::MoveTo;
 use crossterm::{
@@ -6,8 +9,9 @@ use crossterm::{
     terminal::{Clear as TermClear, ClearType},
 };
 use golem_wasm::analysis::AnalysedType;
-use rib::{CompilerOutput, ComponentDependencies, Expr, RibCompilationError};
+use rib::{BuiltInFunction, CompilerOutput, ComponentDependencies, Expr, RibCompilationError};
 use std::io::stdout;
+use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
 #[command(about = "Display type of a rib expression")]
@@ -239,3 +243,152 @@ impl Command for ExportsConcise {
             .print_rib_compilation_error(&error)
     }
 }
+
+pub struct Builtins;
+
+impl Command for Builtins {
+    type Input = ();
+    type Output = Vec<BuiltInFunction>;
+    type InputParseError = ();
+    type ExecutionError = ();
+
+    fn parse(
+        &self,
+        _input: &str,
+        _repl_context: &ReplContext,
+    ) -> Result<Self::Input, Self::InputParseError> {
+        Ok(())
+    }
+
+    fn execute(
+        &self,
+        _input: Self::Input,
+        _repl_context: &mut ReplContext,
+    ) -> Result<Self::Output, Self::ExecutionError> {
+        Ok(BuiltInFunction::all())
+    }
+
+    fn print_output(&self, output: Self::Output, repl_context: &ReplContext) {
+        let printer = repl_context.get_printer();
+        printer.print_builtin_functions(&output);
+    }
+
+    fn print_input_parse_error(&self, _error: Self::InputParseError, _repl_context: &ReplContext) {}
+
+    fn print_execution_error(&self, _error: Self::ExecutionError, _repl_context: &ReplContext) {}
+}
+
+#[derive(Parser, Debug)]
+#[command(about = "Save or load the rib statements, the bindings and the history of a session")]
+pub struct SessionFileInput {
+    /// Path to the session file
+    #[arg(required = true)]
+    pub path: PathBuf,
+}
+
+pub struct SessionFileOutput {
+    pub path: PathBuf,
+    pub session: ReplSession,
+}
+
+pub struct Save;
+
+impl Command for Save {
+    type Input = SessionFileInput;
+    type Output = SessionFileOutput;
+    type InputParseError = clap::Error;
+    type ExecutionError = String;
+
+    fn parse(
+        &self,
+        input: &str,
+        _repl_context: &ReplContext,
+    ) -> Result<Self::Input, Self::InputParseError> {
+        parse_with_clap::<SessionFileInput>(self.name().as_str(), input)
+    }
+
+    fn execute(
+        &self,
+        input: Self::Input,
+        repl_context: &mut ReplContext,
+    ) -> Result<Self::Output, Self::ExecutionError> {
+        let session = repl_context.save_session(&input.path)?;
+
+        Ok(SessionFileOutput {
+            path: input.path,
+            session,
+        })
+    }
+
+    fn print_output(&self, output: Self::Output, repl_context: &ReplContext) {
+        repl_context.get_printer().print_custom_message(&format!(
+            "saved {} rib statements to {}",
+            output.session.rib_statements.len(),
+            output.path.display()
+        ));
+    }
+
+    fn print_input_parse_error(&self, error: Self::InputParseError, repl_context: &ReplContext) {
+        repl_context.get_printer().print_clap_parse_error(&error);
+    }
+
+    fn print_execution_error(&self, error: Self::ExecutionError, repl_context: &ReplContext) {
+        repl_context.get_printer().print_custom_error(&error);
+    }
+}
+
+pub struct Load;
+
+impl Command for Load {
+    type Input = SessionFileInput;
+    type Output = SessionFileOutput;
+    type InputParseError = clap::Error;
+    type ExecutionError = RibExecutionError;
+
+    fn parse(
+        &self,
+        input: &str,
+        _repl_context: &ReplContext,
+    ) -> Result<Self::Input, Self::InputParseError> {
+        parse_with_clap::<SessionFileInput>(self.name().as_str(), input)
+    }
+
+    fn execute(
+        &self,
+        input: Self::Input,
+        repl_context: &mut ReplContext,
+    ) -> Result<Self::Output, Self::ExecutionError> {
+        let session = repl_context.load_session(&input.path)?;
+
+        Ok(SessionFileOutput {
+            path: input.path,
+            session,
+        })
+    }
+
+    fn print_output(&self, output: Self::Output, repl_context: &ReplContext) {
+        repl_context.get_printer().print_custom_message(&format!(
+            "loaded {} rib statements from {}",
+            output.session.rib_statements.len(),
+            output.path.display()
+        ));
+    }
+
+    fn print_input_parse_error(&self, error: Self::InputParseError, repl_context: &ReplContext) {
+        repl_context.get_printer().print_clap_parse_error(&error);
+    }
+
+    fn print_execution_error(&self, error: Self::ExecutionError, repl_context: &ReplContext) {
+        let printer = repl_context.get_printer();
+
+        match error {
+            RibExecutionError::RibCompilationError(error) => {
+                printer.print_rib_compilation_error(&error)
+            }
+            RibExecutionError::RibRuntimeError(error) => printer.print_rib_runtime_error(&error),
+            RibExecutionError::InvalidRibInput(error) | RibExecutionError::Custom(error) => {
+                printer.print_custom_error(&error)
+            }
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::command::builtin::{Clear, Exports, ExportsConcise, TypeInfo};
+use crate::command::builtin::{Builtins, Clear, Exports, ExportsConcise, Load, Save, TypeInfo};
 use crate::UntypedCommand;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -15,6 +15,9 @@ impl CommandRegistry {
         registry.register(Clear);
         registry.register(Exports);
         registry.register(ExportsConcise);
+        registry.register(Builtins);
+        registry.register(Save);
+        registry.register(Load);
         registry
     }
 
@@ -32,6 +32,8 @@ pub fn compile_rib_script(
 
     let inferred_expr = compiler.infer_types(expr)?;
 
+    let rib_input_type_info = RibInputTypeInfo::from_expr(&inferred_expr)?;
+
     let instance_variables = fetch_instance_variables(&inferred_expr);
 
     let identifiers = get_identifiers(&inferred_expr);
@@ -40,25 +42,51 @@ pub fn compile_rib_script(
 
     let enums = compiler.get_enums();
 
-    let byte_code = RibByteCode::from_expr(&inferred_expr)
+    // The script is the whole REPL session so far, and only the lints
+    // of the statement that was just entered are reported
+    let current_statement_line = repl_state.current_rib_statement_start_line();
+
+    let lint_warnings = compiler
+        .lint(&inferred_expr)
+        .into_iter()
+        .filter(|warning| warning.source_span.start_line() as usize >= current_statement_line)
+        .collect::<Vec<_>>();
+
+    let denied_lints = lint_warnings
+        .iter()
+        .filter(|warning| warning.is_denied())
+        .cloned()
+        .collect::<Vec<_>>();
+
+    if !denied_lints.is_empty() {
+        return Err(RibCompilationError::DeniedLints(denied_lints));
+    }
+
+    let (byte_code, source_map) = RibByteCode::from_expr_with_source_map(&inferred_expr)
         .map_err(|err| RibCompilationError::ByteCodeGenerationFail(Box::new(err)))?;
 
     Ok(ReplCompilerOutput {
         rib_byte_code: byte_code,
+        rib_source_map: source_map,
+        rib_input_type_info,
         instance_variables,
         identifiers,
         variants,
         enums,
+        lint_warnings,
     })
 }
 
 #[derive(Clone)]
 pub struct ReplCompilerOutput {
     pub rib_byte_code: RibByteCode,
+    pub rib_source_map: RibSourceMap,
+    pub rib_input_type_info: RibInputTypeInfo,
     pub instance_variables: InstanceVariables,
     pub identifiers: Vec<VariableId>,
     pub variants: Vec<TypeVariant>,
     pub enums: Vec<TypeEnum>,
+    pub lint_warnings: Vec<RibLintWarning>,
 }
 
 #[derive(Default, Clone)]
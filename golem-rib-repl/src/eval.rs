@@ -14,31 +14,54 @@
 
 use crate::invoke::ReplRibFunctionInvoke;
 use crate::repl_state::ReplState;
+use crate::rib_debug::ReplDebugSession;
 use crate::worker_name_gen::DynamicWorkerGen;
-use rib::{InstructionId, Interpreter, RibByteCode, RibInput, RibResult, RibRuntimeError};
+use rib::{
+    InstructionId, Interpreter, RibByteCode, RibDebugger, RibInput, RibResult, RibRuntimeError,
+    RibSourceMap,
+};
 use std::sync::Arc;
 
 pub async fn eval(
     rib_byte_code: RibByteCode,
+    rib_input: RibInput,
     repl_state: &Arc<ReplState>,
 ) -> Result<RibResult, RibRuntimeError> {
     let last_instruction = InstructionId::new(rib_byte_code.len());
 
-    let rib_result = dynamic_interpreter(repl_state).run(rib_byte_code).await?;
+    let rib_result = dynamic_interpreter(rib_input, repl_state)
+        .run(rib_byte_code)
+        .await?;
 
     repl_state.update_last_executed_instruction(last_instruction);
 
     Ok(rib_result)
 }
 
+// Instead of running the byte code, the debugger runs it under the control of the user.
+// Similar to `eval`, the worker functions invoked by the previous statements are not invoked again
+pub fn debug(
+    rib_byte_code: RibByteCode,
+    rib_source_map: RibSourceMap,
+    rib_input: RibInput,
+    repl_state: &Arc<ReplState>,
+) -> ReplDebugSession {
+    let byte_code_len = rib_byte_code.len();
+
+    ReplDebugSession {
+        debugger: RibDebugger::new(
+            dynamic_interpreter(rib_input, repl_state),
+            rib_byte_code,
+            rib_source_map,
+        ),
+        byte_code_len,
+    }
+}
+
 // A dynamic rib interpreter that is created based on the state of the repl
-fn dynamic_interpreter(repl_state: &Arc<ReplState>) -> Interpreter {
+fn dynamic_interpreter(rib_input: RibInput, repl_state: &Arc<ReplState>) -> Interpreter {
     let rib_function_invoke = Arc::new(ReplRibFunctionInvoke::new(repl_state.clone()));
     let worker_name_generator = Arc::new(DynamicWorkerGen::new(repl_state.clone()));
 
-    Interpreter::new(
-        RibInput::default(),
-        rib_function_invoke,
-        worker_name_generator,
-    )
+    Interpreter::new(rib_input, rib_function_invoke, worker_name_generator)
 }
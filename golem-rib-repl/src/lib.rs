@@ -20,6 +20,7 @@ pub use invoke::*;
 pub use raw::*;
 pub use repl_bootstrap_error::*;
 pub use repl_printer::*;
+pub use repl_session::*;
 pub use rib_context::*;
 pub use rib_execution_error::*;
 pub use rib_repl::*;
@@ -32,8 +33,10 @@ mod invoke;
 mod raw;
 mod repl_bootstrap_error;
 mod repl_printer;
+mod repl_session;
 mod repl_state;
 mod rib_context;
+mod rib_debug;
 mod rib_edit;
 mod rib_execution_error;
 mod rib_repl;
@@ -4,10 +4,29 @@ pub struct RawRibScript {
 }
 
 impl RawRibScript {
+    pub fn from_statements(rib_statements: Vec<String>) -> Self {
+        RawRibScript { rib_statements }
+    }
+
+    pub fn statements(&self) -> &[String] {
+        &self.rib_statements
+    }
+
     pub fn as_text(&self) -> String {
         self.rib_statements.join(";\n")
     }
 
+    // The line (1-based) in `as_text` where the last statement starts
+    pub fn last_statement_start_line(&self) -> usize {
+        let previous_statements = self.rib_statements.len().saturating_sub(1);
+
+        self.rib_statements[..previous_statements]
+            .iter()
+            .map(|statement| statement.lines().count().max(1))
+            .sum::<usize>()
+            + 1
+    }
+
     pub fn push(&mut self, rib_statement: &str) {
         self.rib_statements.push(rib_statement.to_string());
     }
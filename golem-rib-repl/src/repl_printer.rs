@@ -83,10 +83,30 @@ pub trait ReplPrinter {
         }
     }
 
+    fn print_builtin_functions(&self, builtin_functions: &[BuiltInFunction]) {
+        println!("{}", "🔧 Built-in functions:".bold().bright_yellow());
+
+        let mut indent = Indent::new();
+        indent.add();
+
+        for builtin_function in builtin_functions {
+            println!(
+                "{} {} {}",
+                indent,
+                builtin_function.signature().bright_green(),
+                format!("// {}", builtin_function.description()).truecolor(140, 140, 140)
+            );
+        }
+    }
+
     fn print_rib_compilation_error(&self, error: &RibCompilationError) {
         print_rib_compilation_error(error);
     }
 
+    fn print_rib_lint_warning(&self, warning: &RibLintWarning) {
+        print_rib_lint_warning(warning);
+    }
+
     fn print_rib_result(&self, result: &RibResult) {
         match result {
             RibResult::Unit => {
@@ -101,6 +121,24 @@ pub trait ReplPrinter {
         }
     }
 
+    fn print_rib_debug_location(
+        &self,
+        line: i32,
+        source_line: &str,
+        instruction: Option<&RibIR>,
+        hit_breakpoint: bool,
+    ) {
+        print_rib_debug_location(line, source_line, instruction, hit_breakpoint);
+    }
+
+    fn print_rib_debug_stack(&self, stack: &InterpreterStack) {
+        print_rib_debug_stack(stack);
+    }
+
+    fn print_rib_debug_env(&self, env: &InterpreterEnv) {
+        print_rib_debug_env(env);
+    }
+
     fn print_rib_runtime_error(&self, error: &RibRuntimeError) {
         println!("{} {}", "[runtime error]".red(), error.to_string().white());
     }
@@ -443,6 +481,96 @@ fn print_rib_compilation_error(error: &RibCompilationError) {
                 error.to_string().red()
             );
         }
+        RibCompilationError::DeniedLints(lints) => {
+            for lint in lints {
+                println!(
+                    "{} {} {}",
+                    "[lint error]".red(),
+                    lint.message.white(),
+                    format!("({})", lint.lint).truecolor(140, 140, 140)
+                );
+            }
+        }
+    }
+}
+
+fn print_rib_lint_warning(warning: &RibLintWarning) {
+    println!(
+        "{} {} {}",
+        "[warning]".magenta(),
+        warning.message.white(),
+        format!("({})", warning.lint).truecolor(140, 140, 140)
+    );
+}
+
+fn print_rib_debug_location(
+    line: i32,
+    source_line: &str,
+    instruction: Option<&RibIR>,
+    hit_breakpoint: bool,
+) {
+    let label = if hit_breakpoint {
+        "[breakpoint]".red()
+    } else {
+        "[debug]".magenta()
+    };
+
+    println!(
+        "{} {} {}",
+        label,
+        format!("{line:>4} |").truecolor(140, 140, 140),
+        source_line.white()
+    );
+
+    if let Some(instruction) = instruction {
+        println!(
+            "{} {}",
+            "[instruction]".magenta(),
+            format!("{instruction:?}").truecolor(140, 140, 140)
+        );
+    }
+}
+
+// The top of the stack is printed first
+fn print_rib_debug_stack(stack: &InterpreterStack) {
+    if stack.stack.is_empty() {
+        println!("{}", "stack is empty".truecolor(140, 140, 140));
+    }
+
+    for (index, value) in stack.stack.iter().rev().enumerate() {
+        let value = match value.get_val() {
+            Some(value_and_type) => display_for_value_and_type(&value_and_type),
+            None => value.to_string(),
+        };
+
+        println!(
+            "{} {}",
+            format!("{index:>4} |").truecolor(140, 140, 140),
+            value.yellow()
+        );
+    }
+}
+
+fn print_rib_debug_env(env: &InterpreterEnv) {
+    let variables = env
+        .env
+        .iter()
+        .map(|(key, value)| {
+            let value = match value.get_val() {
+                Some(value_and_type) => display_for_value_and_type(&value_and_type),
+                None => value.to_string(),
+            };
+
+            (key.variable_id.name(), value)
+        })
+        .collect::<BTreeMap<_, _>>();
+
+    if variables.is_empty() {
+        println!("{}", "no variables in scope".truecolor(140, 140, 140));
+    }
+
+    for (name, value) in variables {
+        println!("{} = {}", name.cyan(), value.yellow());
     }
 }
 
@@ -0,0 +1,61 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use golem_wasm::ValueAndType;
+use rib::InstructionId;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// A snapshot of a REPL session, that is saved to a file using `:save`,
+/// and restored using `:load`.
+///
+/// The bindings of a session are the result of running its Rib statements again.
+/// Therefore, along with the statements, the results of the worker functions invoked by them
+/// and the generated worker names are kept, such that restoring a session doesn't invoke
+/// the worker functions again, or end up calling different workers.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplSession {
+    pub rib_statements: Vec<String>,
+    pub history: Vec<String>,
+    pub invocation_results: Vec<ReplInvocationResult>,
+    pub last_executed_instruction: Option<InstructionId>,
+    pub worker_names: BTreeMap<u64, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplInvocationResult {
+    pub instruction_id: InstructionId,
+    pub result: Option<ValueAndType>,
+}
+
+impl ReplSession {
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|err| format!("failed to serialize the session: {err}"))?;
+
+        std::fs::write(path, json)
+            .map_err(|err| format!("failed to write the session to {}: {err}", path.display()))
+    }
+
+    pub fn load(path: &Path) -> Result<ReplSession, String> {
+        let json = std::fs::read_to_string(path)
+            .map_err(|err| format!("failed to read the session from {}: {err}", path.display()))?;
+
+        serde_json::from_str(&json)
+            .map_err(|err| format!("invalid session file {}: {err}", path.display()))
+    }
+}
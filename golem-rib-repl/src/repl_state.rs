@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use crate::worker_name_gen::ReplWorkerNameGen;
-use crate::{RawRibScript, WorkerFunctionInvoke};
+use crate::{RawRibScript, ReplInvocationResult, ReplSession, WorkerFunctionInvoke};
 use golem_wasm::ValueAndType;
 use rib::{InstructionId, RibCompiler};
 use std::collections::HashMap;
@@ -80,6 +80,58 @@ impl ReplState {
         *self.last_executed_instruction.write().unwrap() = None;
     }
 
+    // The history of a session is kept by the editor, and is not part of the state
+    pub fn session(&self) -> ReplSession {
+        let mut invocation_results = self
+            .invocation_results
+            .results
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(instruction_id, result)| ReplInvocationResult {
+                instruction_id: instruction_id.clone(),
+                result: result.clone(),
+            })
+            .collect::<Vec<_>>();
+
+        invocation_results.sort_by_key(|result| result.instruction_id.index);
+
+        ReplSession {
+            rib_statements: self.rib_script().statements().to_vec(),
+            history: vec![],
+            invocation_results,
+            last_executed_instruction: self.last_executed_instruction.read().unwrap().clone(),
+            worker_names: self
+                .worker_name_gen
+                .read()
+                .unwrap()
+                .worker_name_cache
+                .iter()
+                .map(|(instance_count, worker_name)| (*instance_count, worker_name.clone()))
+                .collect(),
+        }
+    }
+
+    pub fn restore_session(&self, session: &ReplSession) {
+        *self.rib_script.write().unwrap() =
+            RawRibScript::from_statements(session.rib_statements.clone());
+
+        *self.invocation_results.results.write().unwrap() = session
+            .invocation_results
+            .iter()
+            .map(|result| (result.instruction_id.clone(), result.result.clone()))
+            .collect();
+
+        *self.last_executed_instruction.write().unwrap() =
+            session.last_executed_instruction.clone();
+
+        self.worker_name_gen.write().unwrap().worker_name_cache = session
+            .worker_names
+            .iter()
+            .map(|(instance_count, worker_name)| (*instance_count, worker_name.clone()))
+            .collect();
+    }
+
     pub fn rib_script(&self) -> RwLockReadGuard<'_, RawRibScript> {
         self.rib_script.read().unwrap()
     }
@@ -92,6 +144,10 @@ impl ReplState {
         self.rib_script.read().unwrap().as_text()
     }
 
+    pub fn current_rib_statement_start_line(&self) -> usize {
+        self.rib_script.read().unwrap().last_statement_start_line()
+    }
+
     pub fn update_rib(&self, rib: &str) {
         self.rib_script.write().unwrap().push(rib);
     }
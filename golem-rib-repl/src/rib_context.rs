@@ -1,23 +1,25 @@
+use crate::compiler::compile_rib_script;
 use crate::repl_state::ReplState;
 use crate::rib_edit::RibEdit;
-use crate::{RawRibScript, ReplPrinter};
+use crate::{RawRibScript, ReplPrinter, ReplSession, RibExecutionError};
 use rib::RibCompiler;
 use rustyline::history::DefaultHistory;
 use rustyline::Editor;
-use std::sync::RwLockReadGuard;
+use std::path::Path;
+use std::sync::{Arc, RwLockReadGuard};
 
 // A projection of internal repl_state that could be useful
 // for advanced customisation of REPL commands.
 pub struct ReplContext<'a> {
     printer: &'a dyn ReplPrinter,
-    repl_state: &'a ReplState,
+    repl_state: &'a Arc<ReplState>,
     editor: &'a mut Editor<RibEdit, DefaultHistory>,
 }
 
 impl<'a> ReplContext<'a> {
     pub(crate) fn new(
         printer: &'a dyn ReplPrinter,
-        repl_state: &'a ReplState,
+        repl_state: &'a Arc<ReplState>,
         editor: &'a mut Editor<RibEdit, DefaultHistory>,
     ) -> Self {
         Self {
@@ -47,6 +49,50 @@ impl<'a> ReplContext<'a> {
         result
     }
 
+    pub fn save_session(&self, path: &Path) -> Result<ReplSession, String> {
+        let mut session = self.repl_state.session();
+        session.history = self.editor.history().iter().cloned().collect();
+        session.save(path)?;
+
+        Ok(session)
+    }
+
+    // Replaces the current session with the one saved in the file.
+    // The restored session is compiled (without running it) to make sure it is valid
+    // against the components in the REPL, or else the current session is kept as it is.
+    pub fn load_session(&mut self, path: &Path) -> Result<ReplSession, RibExecutionError> {
+        let session = ReplSession::load(path).map_err(RibExecutionError::Custom)?;
+        let current_session = self.repl_state.session();
+
+        self.repl_state.restore_session(&session);
+
+        if !session.rib_statements.is_empty() {
+            let rib_program = self.repl_state.current_rib_program();
+
+            match compile_rib_script(&rib_program, self.repl_state.clone()) {
+                Ok(compiler_output) => {
+                    self.editor
+                        .helper_mut()
+                        .unwrap()
+                        .update_progression(&compiler_output);
+                }
+                Err(err) => {
+                    self.repl_state.restore_session(&current_session);
+
+                    return Err(RibExecutionError::RibCompilationError(err));
+                }
+            }
+        }
+
+        self.editor.clear_history().unwrap();
+
+        for entry in &session.history {
+            let _ = self.editor.add_history_entry(entry.as_str());
+        }
+
+        Ok(session)
+    }
+
     pub fn get_rib_compiler(&self) -> RwLockReadGuard<'_, RibCompiler> {
         self.repl_state.rib_compiler()
     }
@@ -0,0 +1,140 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use rib::{RibDebugStatus, RibDebugger, RibRuntimeError};
+
+// Commands controlling the debugger. Unlike the commands in the `CommandRegistry`,
+// these are run asynchronously by the REPL, as running the Rib script may invoke worker functions.
+//
+// Lines are the lines of the REPL session, where the first statement
+// entered in the session is at line 1.
+pub(crate) enum DebugCommand {
+    // Adds the Rib statement to the session, and pauses at its first line instead of running it
+    Debug(String),
+    Break(i32),
+    Delete(i32),
+    Breakpoints,
+    // Runs until the next line, pausing inside the user-defined functions called from the current line
+    Step,
+    // Runs until the next line, stepping over the function calls in the current line
+    Next,
+    StepInstruction,
+    Continue,
+    Stack,
+    Env,
+    // Stops debugging, and removes the statement being debugged from the session
+    Abort,
+}
+
+impl DebugCommand {
+    pub(crate) fn names() -> Vec<String> {
+        vec![
+            "debug",
+            "break",
+            "delete",
+            "breakpoints",
+            "step",
+            "next",
+            "stepi",
+            "continue",
+            "stack",
+            "env",
+            "abort",
+        ]
+        .into_iter()
+        .map(|name| name.to_string())
+        .collect()
+    }
+
+    // Returns `None` if the command is not a debug command
+    pub(crate) fn parse(name: &str, args: &str) -> Option<Result<DebugCommand, String>> {
+        let command = match name {
+            "debug" => {
+                let rib = args.trim();
+                let rib = rib.strip_suffix(";").unwrap_or(rib).trim();
+
+                if rib.is_empty() {
+                    Err(
+                        "expecting a rib statement to debug. Example: `:debug let x = 1`"
+                            .to_string(),
+                    )
+                } else {
+                    Ok(DebugCommand::Debug(rib.to_string()))
+                }
+            }
+            "break" => internal::parse_line(args).map(DebugCommand::Break),
+            "delete" => internal::parse_line(args).map(DebugCommand::Delete),
+            "breakpoints" => Ok(DebugCommand::Breakpoints),
+            "step" => Ok(DebugCommand::Step),
+            "next" => Ok(DebugCommand::Next),
+            "stepi" => Ok(DebugCommand::StepInstruction),
+            "continue" => Ok(DebugCommand::Continue),
+            "stack" => Ok(DebugCommand::Stack),
+            "env" => Ok(DebugCommand::Env),
+            "abort" => Ok(DebugCommand::Abort),
+            _ => return None,
+        };
+
+        Some(command)
+    }
+
+    // Whether the command can only be run while a statement is being debugged
+    pub(crate) fn requires_session(&self) -> bool {
+        !matches!(
+            self,
+            DebugCommand::Debug(_)
+                | DebugCommand::Break(_)
+                | DebugCommand::Delete(_)
+                | DebugCommand::Breakpoints
+        )
+    }
+}
+
+// A Rib statement being debugged in the REPL
+pub(crate) struct ReplDebugSession {
+    pub(crate) debugger: RibDebugger,
+    // Number of instructions of the byte code, which is the last executed
+    // instruction of the session once the debugger finishes
+    pub(crate) byte_code_len: usize,
+}
+
+impl ReplDebugSession {
+    pub(crate) async fn run(&mut self, run: DebugRun) -> Result<RibDebugStatus, RibRuntimeError> {
+        match run {
+            DebugRun::ToLine(line) => self.debugger.run_to_line(line).await,
+            DebugRun::Step => self.debugger.step().await,
+            DebugRun::StepOver => self.debugger.step_over().await,
+            DebugRun::StepInstruction => self.debugger.step_instruction().await,
+            DebugRun::Resume => self.debugger.resume().await,
+        }
+    }
+}
+
+pub(crate) enum DebugRun {
+    ToLine(i32),
+    Step,
+    StepOver,
+    StepInstruction,
+    Resume,
+}
+
+mod internal {
+    pub(crate) fn parse_line(args: &str) -> Result<i32, String> {
+        args.trim()
+            .parse::<i32>()
+            .ok()
+            .filter(|line| *line > 0)
+            .ok_or_else(|| format!("expecting a line number, but found `{}`", args.trim()))
+    }
+}
@@ -13,12 +13,13 @@
 // limitations under the License.
 
 use crate::compiler::{InstanceVariables, ReplCompilerOutput};
+use crate::rib_debug::DebugCommand;
 use crate::value_generator::generate_value;
 use crate::CommandRegistry;
 use colored::Colorize;
 use golem_wasm::analysis::{TypeEnum, TypeVariant};
 use golem_wasm::ValueAndType;
-use rib::{CustomInstanceSpec, Expr, VariableId};
+use rib::{BuiltInFunction, CustomInstanceSpec, Expr, VariableId};
 use rustyline::completion::Completer;
 use rustyline::highlight::Highlighter;
 use rustyline::hint::Hinter;
@@ -72,11 +73,21 @@ impl RibEdit {
             compiler_output: None,
             key_words: vec![
                 "let", "if", "else", "match", "for", "in", "true", "false", "yield", "some",
-                "none", "ok", "err",
+                "none", "ok", "err", "parallel",
             ],
             custom_instances: vec![],
-            std_function_names: vec!["instance"],
-            repl_commands: command_registry.get_commands(),
+            std_function_names: std::iter::once("instance")
+                .chain(
+                    BuiltInFunction::all()
+                        .iter()
+                        .map(|function| function.name()),
+                )
+                .collect(),
+            repl_commands: command_registry
+                .get_commands()
+                .into_iter()
+                .chain(DebugCommand::names())
+                .collect(),
         }
     }
     pub fn update_progression(&mut self, compiler_output: &ReplCompilerOutput) {
@@ -5,6 +5,8 @@ use std::fmt::{Display, Formatter};
 pub enum RibExecutionError {
     RibCompilationError(RibCompilationError),
     RibRuntimeError(RibRuntimeError),
+    // The value given for a global variable (such as `request`) doesn't match its inferred type
+    InvalidRibInput(String),
     Custom(String),
 }
 
@@ -15,6 +17,7 @@ impl Display for RibExecutionError {
         match self {
             RibExecutionError::RibCompilationError(err) => write!(f, "{err}"),
             RibExecutionError::RibRuntimeError(err) => write!(f, "{err}"),
+            RibExecutionError::InvalidRibInput(msg) => write!(f, "{msg}"),
             RibExecutionError::Custom(msg) => write!(f, "{msg}"),
         }
     }
@@ -12,23 +12,31 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::compiler::compile_rib_script;
+use crate::compiler::{compile_rib_script, ReplCompilerOutput};
 use crate::dependency_manager::RibDependencyManager;
-use crate::eval::eval;
+use crate::eval::{debug, eval};
 use crate::invoke::WorkerFunctionInvoke;
 use crate::repl_printer::{DefaultReplResultPrinter, ReplPrinter};
 use crate::repl_state::ReplState;
 use crate::rib_context::ReplContext;
+use crate::rib_debug::{DebugCommand, DebugRun, ReplDebugSession};
 use crate::rib_edit::RibEdit;
 use crate::{
     CommandRegistry, ReplBootstrapError, ReplComponentDependencies, RibExecutionError,
     UntypedCommand,
 };
 use colored::Colorize;
-use rib::{RibCompiler, RibCompilerConfig, RibResult};
+use golem_wasm::json::ValueAndTypeJsonExtensions;
+use golem_wasm::ValueAndType;
+use rib::{
+    InstructionId, LintConfig, LintLevel, RibCompiler, RibCompilerConfig, RibDebugStatus, RibInput,
+    RibInputTypeInfo, RibLint, RibResult,
+};
 use rustyline::error::ReadlineError;
 use rustyline::history::DefaultHistory;
 use rustyline::{Config, Editor};
+use serde_json::Value as JsonValue;
+use std::collections::{BTreeSet, HashMap};
 use std::path::PathBuf;
 use std::sync::Arc;
 
@@ -45,6 +53,9 @@ use std::sync::Arc;
 ///   and source file path. If `None`, the REPL will try to load all the components using the
 ///   `dependency_manager`, otherwise, `dependency_manager` will load only the specified component.
 /// - `prompt`: optional custom prompt, defaults to `>>>` in cyan
+/// - `lint_config`: Optional level of each lint reported for the Rib statements. If `None`, all lints
+///   are reported as warnings, except unused `let` bindings, given a binding is typically used
+///   only by the statements entered after it.
 pub struct RibReplConfig {
     pub history_file: Option<PathBuf>,
     pub dependency_manager: Arc<dyn RibDependencyManager + Sync + Send>,
@@ -53,6 +64,7 @@ pub struct RibReplConfig {
     pub component_source: Option<ComponentSource>,
     pub prompt: Option<String>,
     pub command_registry: Option<CommandRegistry>,
+    pub lint_config: Option<LintConfig>,
 }
 
 /// The REPL environment for Rib, providing an interactive shell for executing Rib code.
@@ -62,6 +74,11 @@ pub struct RibRepl {
     repl_state: Arc<ReplState>,
     prompt: String,
     command_registry: CommandRegistry,
+    debug_session: Option<ReplDebugSession>,
+    // Values of the global variables (such as `request`) in the Rib statements, in JSON
+    rib_input: HashMap<String, JsonValue>,
+    // Lines of the REPL session with a breakpoint, which are kept across debug sessions
+    breakpoints: BTreeSet<i32>,
 }
 
 impl RibRepl {
@@ -126,13 +143,20 @@ impl RibRepl {
 
         // Once https://github.com/golemcloud/golem/issues/1608 is resolved,
         // component dependency will not be required in the REPL state
+        let lint_config = config
+            .lint_config
+            .unwrap_or_else(|| LintConfig::new().with_level(RibLint::UnusedLet, LintLevel::Allow));
+
         let repl_state = ReplState::new(
             config.worker_function_invoke,
-            RibCompiler::new(RibCompilerConfig::new(
-                component_dependencies.component_dependencies,
-                vec![],
-                component_dependencies.custom_instance_spec.clone(),
-            )),
+            RibCompiler::new(
+                RibCompilerConfig::new(
+                    component_dependencies.component_dependencies,
+                    vec![],
+                    component_dependencies.custom_instance_spec.clone(),
+                )
+                .with_lint_config(lint_config),
+            ),
             history_file_path.clone(),
         );
 
@@ -150,9 +174,20 @@ impl RibRepl {
                 .prompt
                 .unwrap_or_else(|| ">>> ".truecolor(192, 192, 192).to_string()),
             command_registry,
+            debug_session: None,
+            rib_input: HashMap::new(),
+            breakpoints: BTreeSet::new(),
         })
     }
 
+    /// Sets the values of the global variables (such as `request`) used in the Rib statements.
+    ///
+    /// The values are given as JSON (with no type information), and are converted to the types
+    /// the variables are inferred to, at the time of running a statement.
+    pub fn set_rib_input(&mut self, rib_input: HashMap<String, JsonValue>) {
+        self.rib_input = rib_input;
+    }
+
     /// Reads a single line of input from the REPL prompt.
     ///
     /// This method is exposed for users who want to manage their own REPL loop
@@ -182,43 +217,29 @@ impl RibRepl {
 
                 Ok(None)
             }
+            CommandOrExpr::Debug(command) => self.execute_debug_command(command).await,
             CommandOrExpr::RawExpr(script) => {
                 // If the script is empty, we do not execute it
                 if !script.is_empty() {
-                    let rib = script.strip_suffix(";").unwrap_or(script.as_str()).trim();
-
-                    self.repl_state.update_rib(rib);
-
-                    // Add every rib script into the history (in memory) and save it
-                    // regardless of whether it compiles or not
-                    // History is never used for any progressive compilation or interpretation
-                    let _ = self.editor.add_history_entry(rib);
-                    let _ = self
-                        .editor
-                        .save_history(self.repl_state.history_file_path());
-
-                    match compile_rib_script(&self.current_rib_program(), self.repl_state.clone()) {
-                        Ok(compiler_output) => {
-                            let rib_edit = self.editor.helper_mut().unwrap();
+                    if self.debug_session.is_some() {
+                        return Err(RibExecutionError::Custom(
+                            "a rib statement is being debugged. Use `:continue` to finish it or `:abort` to discard it".to_string(),
+                        ));
+                    }
 
-                            rib_edit.update_progression(&compiler_output);
+                    let compiler_output = self.compile_statement(script.as_str())?;
 
-                            let result =
-                                eval(compiler_output.rib_byte_code, &self.repl_state).await;
+                    let rib_input = self.statement_rib_input(&compiler_output)?;
 
-                            match result {
-                                Ok(result) => Ok(Some(result)),
-                                Err(err) => {
-                                    self.repl_state.remove_last_rib_expression();
+                    let result =
+                        eval(compiler_output.rib_byte_code, rib_input, &self.repl_state).await;
 
-                                    Err(RibExecutionError::RibRuntimeError(err))
-                                }
-                            }
-                        }
+                    match result {
+                        Ok(result) => Ok(Some(result)),
                         Err(err) => {
                             self.repl_state.remove_last_rib_expression();
 
-                            Err(RibExecutionError::RibCompilationError(err))
+                            Err(RibExecutionError::RibRuntimeError(err))
                         }
                     }
                 } else {
@@ -228,6 +249,230 @@ impl RibRepl {
         }
     }
 
+    // Adds the statement to the session and compiles the session,
+    // removing the statement again if it fails to compile
+    fn compile_statement(&mut self, script: &str) -> Result<ReplCompilerOutput, RibExecutionError> {
+        let rib = script.strip_suffix(";").unwrap_or(script).trim();
+
+        self.repl_state.update_rib(rib);
+
+        // Add every rib script into the history (in memory) and save it
+        // regardless of whether it compiles or not
+        // History is never used for any progressive compilation or interpretation
+        let _ = self.editor.add_history_entry(rib);
+        let _ = self
+            .editor
+            .save_history(self.repl_state.history_file_path());
+
+        match compile_rib_script(&self.current_rib_program(), self.repl_state.clone()) {
+            Ok(compiler_output) => {
+                let rib_edit = self.editor.helper_mut().unwrap();
+
+                rib_edit.update_progression(&compiler_output);
+
+                for warning in &compiler_output.lint_warnings {
+                    self.printer.print_rib_lint_warning(warning);
+                }
+
+                Ok(compiler_output)
+            }
+            Err(err) => {
+                self.repl_state.remove_last_rib_expression();
+
+                Err(RibExecutionError::RibCompilationError(err))
+            }
+        }
+    }
+
+    async fn execute_debug_command(
+        &mut self,
+        command: DebugCommand,
+    ) -> Result<Option<RibResult>, RibExecutionError> {
+        if command.requires_session() && self.debug_session.is_none() {
+            return Err(RibExecutionError::Custom(
+                "no rib statement is being debugged. Use `:debug <rib statement>` to start debugging".to_string(),
+            ));
+        }
+
+        match command {
+            DebugCommand::Debug(rib) => {
+                if self.debug_session.is_some() {
+                    return Err(RibExecutionError::Custom(
+                        "a rib statement is already being debugged. Use `:abort` to discard it"
+                            .to_string(),
+                    ));
+                }
+
+                let compiler_output = self.compile_statement(rib.as_str())?;
+
+                let rib_input = self.statement_rib_input(&compiler_output)?;
+
+                let mut debug_session = debug(
+                    compiler_output.rib_byte_code,
+                    compiler_output.rib_source_map,
+                    rib_input,
+                    &self.repl_state,
+                );
+
+                for line in &self.breakpoints {
+                    debug_session.debugger.add_breakpoint(*line);
+                }
+
+                self.debug_session = Some(debug_session);
+
+                // The previous statements of the session are run without pausing,
+                // unless there is a breakpoint in them
+                let start_line = self.repl_state.current_rib_statement_start_line() as i32;
+
+                self.run_debugger(DebugRun::ToLine(start_line)).await
+            }
+
+            DebugCommand::Break(line) => {
+                if let Some(debug_session) = &mut self.debug_session {
+                    if !debug_session.debugger.add_breakpoint(line) {
+                        return Err(RibExecutionError::Custom(format!(
+                            "there is nothing to run at line {line}"
+                        )));
+                    }
+                }
+
+                self.breakpoints.insert(line);
+                self.printer
+                    .print_custom_message(&format!("breakpoint added at line {line}"));
+
+                Ok(None)
+            }
+
+            DebugCommand::Delete(line) => {
+                if let Some(debug_session) = &mut self.debug_session {
+                    debug_session.debugger.remove_breakpoint(line);
+                }
+
+                if self.breakpoints.remove(&line) {
+                    self.printer
+                        .print_custom_message(&format!("breakpoint removed at line {line}"));
+
+                    Ok(None)
+                } else {
+                    Err(RibExecutionError::Custom(format!(
+                        "there is no breakpoint at line {line}"
+                    )))
+                }
+            }
+
+            DebugCommand::Breakpoints => {
+                let breakpoints = self
+                    .breakpoints
+                    .iter()
+                    .map(|line| line.to_string())
+                    .collect::<Vec<_>>();
+
+                if breakpoints.is_empty() {
+                    self.printer.print_custom_message("no breakpoints");
+                } else {
+                    self.printer.print_custom_message(&format!(
+                        "breakpoints at lines {}",
+                        breakpoints.join(", ")
+                    ));
+                }
+
+                Ok(None)
+            }
+
+            DebugCommand::Step => self.run_debugger(DebugRun::Step).await,
+
+            DebugCommand::Next => self.run_debugger(DebugRun::StepOver).await,
+
+            DebugCommand::StepInstruction => self.run_debugger(DebugRun::StepInstruction).await,
+
+            DebugCommand::Continue => self.run_debugger(DebugRun::Resume).await,
+
+            DebugCommand::Stack => {
+                if let Some(debug_session) = &self.debug_session {
+                    self.printer
+                        .print_rib_debug_stack(debug_session.debugger.stack());
+                }
+
+                Ok(None)
+            }
+
+            DebugCommand::Env => {
+                if let Some(debug_session) = &self.debug_session {
+                    self.printer
+                        .print_rib_debug_env(debug_session.debugger.env());
+                }
+
+                Ok(None)
+            }
+
+            DebugCommand::Abort => {
+                self.debug_session = None;
+                self.repl_state.remove_last_rib_expression();
+                self.printer
+                    .print_custom_message("stopped debugging, and discarded the rib statement");
+
+                Ok(None)
+            }
+        }
+    }
+
+    // Runs the debugger of the current session, and ends the session once the debugger
+    // finishes (or fails), in which case the statement is treated as if it is evaluated
+    async fn run_debugger(
+        &mut self,
+        run: DebugRun,
+    ) -> Result<Option<RibResult>, RibExecutionError> {
+        let Some(debug_session) = &mut self.debug_session else {
+            return Ok(None);
+        };
+
+        let print_instruction = matches!(run, DebugRun::StepInstruction);
+
+        match debug_session.run(run).await {
+            Ok(RibDebugStatus::Paused { hit_breakpoint, .. }) => {
+                let debugger = &debug_session.debugger;
+                let line = debugger.current_line().unwrap_or_default();
+                let program = self.repl_state.current_rib_program();
+                let source_line = program
+                    .lines()
+                    .nth((line as usize).saturating_sub(1))
+                    .unwrap_or_default();
+
+                let instruction = if print_instruction {
+                    debugger.current_instruction()
+                } else {
+                    None
+                };
+
+                self.printer.print_rib_debug_location(
+                    line,
+                    source_line.trim(),
+                    instruction,
+                    hit_breakpoint,
+                );
+
+                Ok(None)
+            }
+
+            Ok(RibDebugStatus::Finished(result)) => {
+                self.repl_state
+                    .update_last_executed_instruction(InstructionId::new(
+                        debug_session.byte_code_len,
+                    ));
+                self.debug_session = None;
+
+                Ok(Some(result))
+            }
+
+            Err(err) => {
+                self.debug_session = None;
+                self.repl_state.remove_last_rib_expression();
+
+                Err(RibExecutionError::RibRuntimeError(err))
+            }
+        }
+    }
+
     /// Starts the default REPL loop for executing Rib code interactively.
     ///
     /// This is a convenience method that repeatedly reads user input and executes
@@ -262,13 +507,48 @@ impl RibRepl {
                 RibExecutionError::RibCompilationError(runtime_error) => {
                     self.printer.print_rib_compilation_error(runtime_error);
                 }
-                RibExecutionError::Custom(custom_error) => {
+                RibExecutionError::InvalidRibInput(custom_error)
+                | RibExecutionError::Custom(custom_error) => {
                     self.printer.print_custom_error(custom_error);
                 }
             },
         }
     }
 
+    // The statement is not kept in the session if its input is invalid, the same as
+    // if it failed to compile
+    fn statement_rib_input(
+        &self,
+        compiler_output: &ReplCompilerOutput,
+    ) -> Result<RibInput, RibExecutionError> {
+        self.rib_input(&compiler_output.rib_input_type_info)
+            .inspect_err(|_| self.repl_state.remove_last_rib_expression())
+    }
+
+    // Only the values of the global variables used in the session are converted,
+    // and a variable without a value results in a runtime error when it is read
+    fn rib_input(
+        &self,
+        rib_input_type_info: &RibInputTypeInfo,
+    ) -> Result<RibInput, RibExecutionError> {
+        let mut rib_input = HashMap::new();
+
+        for (name, analysed_type) in &rib_input_type_info.types {
+            if let Some(json) = self.rib_input.get(name) {
+                let value = ValueAndType::parse_with_type(json, analysed_type).map_err(|errs| {
+                    RibExecutionError::InvalidRibInput(format!(
+                        "invalid value for input `{name}`: {}",
+                        errs.join(", ")
+                    ))
+                })?;
+
+                rib_input.insert(name.clone(), value);
+            }
+        }
+
+        Ok(RibInput::new(rib_input))
+    }
+
     fn current_rib_program(&self) -> String {
         self.repl_state.current_rib_program()
     }
@@ -279,6 +559,7 @@ enum CommandOrExpr {
         args: String,
         executor: Arc<dyn UntypedCommand>,
     },
+    Debug(DebugCommand),
     RawExpr(String),
 }
 
@@ -294,6 +575,10 @@ impl CommandOrExpr {
 
             let input_args = repl_input[1..].join(" ");
 
+            if let Some(debug_command) = DebugCommand::parse(command_name, input_args.as_str()) {
+                return debug_command.map(CommandOrExpr::Debug);
+            }
+
             let command = command_registry
                 .get_command(command_name)
                 .map(|command| CommandOrExpr::Command {
@@ -326,3 +611,256 @@ fn get_default_history_file() -> PathBuf {
     path.push(".rib_history");
     path
 }
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use crate::{
+        ReplComponentDependencies, ReplSession, RibDependencyManager, RibExecutionError, RibRepl,
+        RibReplConfig, WorkerFunctionInvoke,
+    };
+    use async_trait::async_trait;
+    use golem_wasm::analysis::analysed_type::u64;
+    use golem_wasm::analysis::{
+        AnalysedExport, AnalysedFunction, AnalysedFunctionParameter, AnalysedFunctionResult,
+        AnalysedType,
+    };
+    use golem_wasm::{Value, ValueAndType};
+    use rib::{ComponentDependency, ComponentDependencyKey};
+    use serde_json::json;
+    use std::collections::HashMap;
+    use std::path::{Path, PathBuf};
+    use std::sync::{Arc, Mutex};
+    use uuid::Uuid;
+
+    // A component exporting `add: func(value: u64) -> u64`
+    struct TestDependencyManager;
+
+    #[async_trait]
+    impl RibDependencyManager for TestDependencyManager {
+        async fn get_dependencies(&self) -> anyhow::Result<ReplComponentDependencies> {
+            Ok(ReplComponentDependencies {
+                component_dependencies: vec![ComponentDependency::new(
+                    ComponentDependencyKey {
+                        component_name: "test-component".to_string(),
+                        component_id: Uuid::new_v4(),
+                        component_version: 0,
+                        root_package_name: None,
+                        root_package_version: None,
+                    },
+                    vec![AnalysedExport::Function(AnalysedFunction {
+                        name: "add".to_string(),
+                        parameters: vec![AnalysedFunctionParameter {
+                            name: "value".to_string(),
+                            typ: u64(),
+                        }],
+                        result: Some(AnalysedFunctionResult { typ: u64() }),
+                    })],
+                )],
+                custom_instance_spec: vec![],
+            })
+        }
+
+        async fn add_component(
+            &self,
+            _source_path: &Path,
+            _component_name: String,
+        ) -> anyhow::Result<ComponentDependency> {
+            unimplemented!()
+        }
+    }
+
+    // Records the invocations, and returns the argument plus the given offset
+    #[derive(Default)]
+    struct TestWorkerFunctionInvoke {
+        offset: u64,
+        invocations: Mutex<Vec<(String, String)>>,
+    }
+
+    impl TestWorkerFunctionInvoke {
+        fn with_offset(offset: u64) -> Arc<Self> {
+            Arc::new(Self {
+                offset,
+                invocations: Mutex::new(vec![]),
+            })
+        }
+
+        fn invocations(&self) -> Vec<(String, String)> {
+            self.invocations.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl WorkerFunctionInvoke for TestWorkerFunctionInvoke {
+        async fn invoke(
+            &self,
+            _component_id: Uuid,
+            _component_name: &str,
+            worker_name: &str,
+            function_name: &str,
+            args: Vec<ValueAndType>,
+            _return_type: Option<AnalysedType>,
+        ) -> anyhow::Result<Option<ValueAndType>> {
+            self.invocations
+                .lock()
+                .unwrap()
+                .push((worker_name.to_string(), function_name.to_string()));
+
+            let Value::U64(value) = args[0].value else {
+                panic!("expected a u64 argument")
+            };
+
+            Ok(Some(ValueAndType::new(
+                Value::U64(value + self.offset),
+                u64(),
+            )))
+        }
+    }
+
+    fn temp_file(prefix: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("{prefix}-{}.json", Uuid::new_v4()))
+    }
+
+    async fn repl(worker_function_invoke: Arc<TestWorkerFunctionInvoke>) -> RibRepl {
+        RibRepl::bootstrap(RibReplConfig {
+            history_file: Some(temp_file("rib-repl-history")),
+            dependency_manager: Arc::new(TestDependencyManager),
+            worker_function_invoke,
+            printer: None,
+            component_source: None,
+            prompt: None,
+            command_registry: None,
+            lint_config: None,
+        })
+        .await
+        .unwrap()
+    }
+
+    async fn execute_u64(repl: &mut RibRepl, rib: &str) -> u64 {
+        let result = repl.execute(rib).await.unwrap().unwrap();
+
+        match result.get_val().map(|value| value.value) {
+            Some(Value::U64(value)) => value,
+            other => panic!("expected a u64 result, got {other:?}"),
+        }
+    }
+
+    #[test]
+    async fn saved_session_is_restored_without_invoking_workers_again() {
+        let session_file = temp_file("rib-repl-session");
+
+        let first_invoke = TestWorkerFunctionInvoke::with_offset(100);
+        let mut first_repl = repl(first_invoke.clone()).await;
+
+        first_repl.execute("let worker = instance()").await.unwrap();
+        first_repl.execute("let x = worker.add(1)").await.unwrap();
+        assert_eq!(execute_u64(&mut first_repl, "x").await, 101);
+        first_repl
+            .execute(&format!(":save {}", session_file.display()))
+            .await
+            .unwrap();
+
+        let first_invocations = first_invoke.invocations();
+        assert_eq!(first_invocations.len(), 1);
+
+        let second_invoke = TestWorkerFunctionInvoke::with_offset(200);
+        let mut second_repl = repl(second_invoke.clone()).await;
+
+        second_repl
+            .execute(&format!(":load {}", session_file.display()))
+            .await
+            .unwrap();
+
+        let saved = ReplSession::load(&session_file).unwrap();
+        let restored = second_repl.repl_state.session();
+
+        assert_eq!(restored.rib_statements, saved.rib_statements);
+        assert_eq!(restored.worker_names, saved.worker_names);
+        assert_eq!(
+            restored.last_executed_instruction,
+            saved.last_executed_instruction
+        );
+        assert_eq!(
+            restored.invocation_results.len(),
+            saved.invocation_results.len()
+        );
+
+        // The binding is the recorded result of the first session
+        assert_eq!(execute_u64(&mut second_repl, "x").await, 101);
+        assert!(second_invoke.invocations().is_empty());
+
+        // New invocations go to the same worker as in the first session
+        assert_eq!(execute_u64(&mut second_repl, "worker.add(2)").await, 202);
+        assert_eq!(
+            second_invoke.invocations(),
+            vec![first_invocations[0].clone()]
+        );
+
+        let _ = std::fs::remove_file(session_file);
+    }
+
+    #[test]
+    async fn session_failing_to_compile_is_not_loaded() {
+        let session_file = temp_file("rib-repl-session");
+
+        ReplSession {
+            rib_statements: vec![
+                "let b: u64 = 1".to_string(),
+                r#"let c: u64 = "not a number""#.to_string(),
+            ],
+            ..ReplSession::default()
+        }
+        .save(&session_file)
+        .unwrap();
+
+        let mut repl = repl(TestWorkerFunctionInvoke::with_offset(0)).await;
+
+        repl.execute("let a: u64 = 5").await.unwrap();
+        repl.execute(&format!(":load {}", session_file.display()))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            repl.repl_state.session().rib_statements,
+            vec!["let a: u64 = 5".to_string()]
+        );
+        assert_eq!(execute_u64(&mut repl, "a").await, 5);
+
+        let _ = std::fs::remove_file(session_file);
+    }
+
+    #[test]
+    async fn rib_input_is_converted_to_the_inferred_types() {
+        let mut repl = repl(TestWorkerFunctionInvoke::with_offset(0)).await;
+
+        repl.set_rib_input(HashMap::from([(
+            "request".to_string(),
+            json!({ "path": { "id": 42, "name": "foo" } }),
+        )]));
+
+        assert_eq!(
+            execute_u64(
+                &mut repl,
+                r#"let id: u64 = request.path.id; let name: string = request.path.name; if name == "foo" then id else 0"#
+            )
+            .await,
+            42
+        );
+    }
+
+    #[test]
+    async fn rib_input_of_wrong_type_is_rejected() {
+        let mut repl = repl(TestWorkerFunctionInvoke::with_offset(0)).await;
+
+        repl.set_rib_input(HashMap::from([(
+            "request".to_string(),
+            json!({ "path": { "id": "not a number" } }),
+        )]));
+
+        let result = repl.execute("let id: u64 = request.path.id; id").await;
+
+        assert!(matches!(result, Err(RibExecutionError::InvalidRibInput(_))));
+        assert!(repl.repl_state.session().rib_statements.is_empty());
+    }
+}
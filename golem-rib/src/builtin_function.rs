@@ -0,0 +1,232 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use desert_rust::BinaryCodec;
+use std::fmt::Display;
+use std::str::FromStr;
+
+// Pure functions that are part of Rib itself. They are evaluated
+// by the interpreter and never result in a worker invocation.
+// A call to a global function with one of these names (that is not a variant constructor)
+// is resolved to a built-in function during type inference.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy, Ord, PartialOrd, BinaryCodec)]
+#[desert(evolution())]
+pub enum BuiltInFunction {
+    ToLower,
+    ToUpper,
+    Trim,
+    Split,
+    Contains,
+    StartsWith,
+    EndsWith,
+    Replace,
+    Len,
+    ParseU64,
+    ParseS64,
+    ParseF64,
+    Join,
+    UnwrapOr,
+    Min,
+    Max,
+    Base64Encode,
+    Base64Decode,
+    UrlEncode,
+    UrlDecode,
+    Map,
+    Filter,
+}
+
+impl BuiltInFunction {
+    pub fn all() -> Vec<BuiltInFunction> {
+        vec![
+            BuiltInFunction::ToLower,
+            BuiltInFunction::ToUpper,
+            BuiltInFunction::Trim,
+            BuiltInFunction::Split,
+            BuiltInFunction::Contains,
+            BuiltInFunction::StartsWith,
+            BuiltInFunction::EndsWith,
+            BuiltInFunction::Replace,
+            BuiltInFunction::Len,
+            BuiltInFunction::ParseU64,
+            BuiltInFunction::ParseS64,
+            BuiltInFunction::ParseF64,
+            BuiltInFunction::Join,
+            BuiltInFunction::UnwrapOr,
+            BuiltInFunction::Min,
+            BuiltInFunction::Max,
+            BuiltInFunction::Base64Encode,
+            BuiltInFunction::Base64Decode,
+            BuiltInFunction::UrlEncode,
+            BuiltInFunction::UrlDecode,
+            BuiltInFunction::Map,
+            BuiltInFunction::Filter,
+        ]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            BuiltInFunction::ToLower => "to-lower",
+            BuiltInFunction::ToUpper => "to-upper",
+            BuiltInFunction::Trim => "trim",
+            BuiltInFunction::Split => "split",
+            BuiltInFunction::Contains => "contains",
+            BuiltInFunction::StartsWith => "starts-with",
+            BuiltInFunction::EndsWith => "ends-with",
+            BuiltInFunction::Replace => "replace",
+            BuiltInFunction::Len => "len",
+            BuiltInFunction::ParseU64 => "parse-u64",
+            BuiltInFunction::ParseS64 => "parse-s64",
+            BuiltInFunction::ParseF64 => "parse-f64",
+            BuiltInFunction::Join => "join",
+            BuiltInFunction::UnwrapOr => "unwrap-or",
+            BuiltInFunction::Min => "min",
+            BuiltInFunction::Max => "max",
+            BuiltInFunction::Base64Encode => "base64-encode",
+            BuiltInFunction::Base64Decode => "base64-decode",
+            BuiltInFunction::UrlEncode => "url-encode",
+            BuiltInFunction::UrlDecode => "url-decode",
+            BuiltInFunction::Map => "map",
+            BuiltInFunction::Filter => "filter",
+        }
+    }
+
+    pub fn arity(&self) -> usize {
+        match self {
+            BuiltInFunction::ToLower
+            | BuiltInFunction::ToUpper
+            | BuiltInFunction::Trim
+            | BuiltInFunction::Len
+            | BuiltInFunction::ParseU64
+            | BuiltInFunction::ParseS64
+            | BuiltInFunction::ParseF64
+            | BuiltInFunction::Base64Encode
+            | BuiltInFunction::Base64Decode
+            | BuiltInFunction::UrlEncode
+            | BuiltInFunction::UrlDecode => 1,
+            BuiltInFunction::Split
+            | BuiltInFunction::Contains
+            | BuiltInFunction::StartsWith
+            | BuiltInFunction::EndsWith
+            | BuiltInFunction::Join
+            | BuiltInFunction::UnwrapOr
+            | BuiltInFunction::Min
+            | BuiltInFunction::Max
+            | BuiltInFunction::Map
+            | BuiltInFunction::Filter => 2,
+            BuiltInFunction::Replace => 3,
+        }
+    }
+
+    // Human-readable signature, where `T` stands for any type
+    pub fn signature(&self) -> &'static str {
+        match self {
+            BuiltInFunction::ToLower => "to-lower(string) -> string",
+            BuiltInFunction::ToUpper => "to-upper(string) -> string",
+            BuiltInFunction::Trim => "trim(string) -> string",
+            BuiltInFunction::Split => "split(string, string) -> list<string>",
+            BuiltInFunction::Contains => "contains(string | list<T>, string | T) -> bool",
+            BuiltInFunction::StartsWith => "starts-with(string, string) -> bool",
+            BuiltInFunction::EndsWith => "ends-with(string, string) -> bool",
+            BuiltInFunction::Replace => "replace(string, string, string) -> string",
+            BuiltInFunction::Len => "len(string | list<T>) -> u64",
+            BuiltInFunction::ParseU64 => "parse-u64(string) -> option<u64>",
+            BuiltInFunction::ParseS64 => "parse-s64(string) -> option<s64>",
+            BuiltInFunction::ParseF64 => "parse-f64(string) -> option<f64>",
+            BuiltInFunction::Join => "join(list<string>, string) -> string",
+            BuiltInFunction::UnwrapOr => "unwrap-or(option<T>, T) -> T",
+            BuiltInFunction::Min => "min(T, T) -> T",
+            BuiltInFunction::Max => "max(T, T) -> T",
+            BuiltInFunction::Base64Encode => "base64-encode(string) -> string",
+            BuiltInFunction::Base64Decode => "base64-decode(string) -> option<string>",
+            BuiltInFunction::UrlEncode => "url-encode(string) -> string",
+            BuiltInFunction::UrlDecode => "url-decode(string) -> option<string>",
+            BuiltInFunction::Map => "map(list<T>, |T| -> U) -> list<U>",
+            BuiltInFunction::Filter => "filter(list<T>, |T| -> bool) -> list<T>",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            BuiltInFunction::ToLower => "converts the string to lower case",
+            BuiltInFunction::ToUpper => "converts the string to upper case",
+            BuiltInFunction::Trim => "removes leading and trailing whitespace",
+            BuiltInFunction::Split => "splits the string by the given separator",
+            BuiltInFunction::Contains => {
+                "checks if the string contains the substring, or the list contains the element"
+            }
+            BuiltInFunction::StartsWith => "checks if the string starts with the given prefix",
+            BuiltInFunction::EndsWith => "checks if the string ends with the given suffix",
+            BuiltInFunction::Replace => "replaces all occurrences of a pattern in the string",
+            BuiltInFunction::Len => "number of characters in a string, or elements in a list",
+            BuiltInFunction::ParseU64 => "parses the string as u64, returning none on failure",
+            BuiltInFunction::ParseS64 => "parses the string as s64, returning none on failure",
+            BuiltInFunction::ParseF64 => "parses the string as f64, returning none on failure",
+            BuiltInFunction::Join => "joins the list of strings with the given separator",
+            BuiltInFunction::UnwrapOr => "returns the value of the option, or the default if none",
+            BuiltInFunction::Min => "returns the smaller of the two values",
+            BuiltInFunction::Max => "returns the larger of the two values",
+            BuiltInFunction::Base64Encode => "encodes the string using standard base64",
+            BuiltInFunction::Base64Decode => {
+                "decodes a standard base64 string, returning none if it is not valid utf-8 text"
+            }
+            BuiltInFunction::UrlEncode => "percent-encodes the string for use in a url",
+            BuiltInFunction::UrlDecode => {
+                "decodes a percent-encoded string, returning none if it is not valid utf-8 text"
+            }
+            BuiltInFunction::Map => "applies the lambda to every element of the list",
+            BuiltInFunction::Filter => {
+                "keeps the elements of the list for which the lambda is true"
+            }
+        }
+    }
+}
+
+impl FromStr for BuiltInFunction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        BuiltInFunction::all()
+            .into_iter()
+            .find(|function| function.name() == s)
+            .ok_or_else(|| format!("unknown built-in function `{s}`"))
+    }
+}
+
+impl Display for BuiltInFunction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use crate::BuiltInFunction;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_builtin_function_names_round_trip() {
+        for function in BuiltInFunction::all() {
+            assert_eq!(BuiltInFunction::from_str(function.name()), Ok(function));
+            assert!(function.signature().starts_with(function.name()));
+        }
+    }
+
+    #[test]
+    fn test_unknown_builtin_function() {
+        assert!(BuiltInFunction::from_str("to-lowercase").is_err());
+    }
+}
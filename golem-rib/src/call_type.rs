@@ -12,8 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::{BuiltInFunction, FullyQualifiedResourceConstructor, VariableId};
 use crate::{ComponentDependencyKey, DynamicParsedFunctionName, Expr};
-use crate::{FullyQualifiedResourceConstructor, VariableId};
 use std::fmt::Display;
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone, Ord, PartialOrd)]
@@ -30,6 +30,8 @@ pub enum CallType {
     VariantConstructor(String),
     EnumConstructor(String),
     InstanceCreation(InstanceCreationType),
+    BuiltIn(BuiltInFunction),
+    UserDefined(String),
 }
 
 // InstanceIdentifier holds the variables that are used to identify a worker or resource instance.
@@ -156,6 +158,8 @@ impl Display for CallType {
             CallType::Function { function_name, .. } => write!(f, "{function_name}"),
             CallType::VariantConstructor(name) => write!(f, "{name}"),
             CallType::EnumConstructor(name) => write!(f, "{name}"),
+            CallType::BuiltIn(function) => write!(f, "{function}"),
+            CallType::UserDefined(name) => write!(f, "{name}"),
             CallType::InstanceCreation(instance_creation_type) => match instance_creation_type {
                 InstanceCreationType::WitWorker { .. } => {
                     write!(f, "instance")
@@ -171,8 +175,9 @@ impl Display for CallType {
 mod protobuf {
     use crate::call_type::{CallType, InstanceCreationType};
     use crate::proto::golem::rib::WorkerInstance;
-    use crate::FullyQualifiedResourceConstructor;
+    use crate::{BuiltInFunction, FullyQualifiedResourceConstructor};
     use crate::{ComponentDependencyKey, DynamicParsedFunctionName, Expr, ParsedFunctionName};
+    use std::str::FromStr;
 
     impl TryFrom<crate::proto::golem::rib::ComponentDependencyKey> for ComponentDependencyKey {
         type Error = String;
@@ -297,6 +302,14 @@ mod protobuf {
                     let instance_creation = InstanceCreationType::try_from(*instance_creation)?;
                     Ok(CallType::InstanceCreation(instance_creation))
                 }
+
+                crate::proto::golem::rib::call_type::Name::BuiltIn(name) => {
+                    Ok(CallType::BuiltIn(BuiltInFunction::from_str(&name)?))
+                }
+
+                crate::proto::golem::rib::call_type::Name::UserDefined(name) => {
+                    Ok(CallType::UserDefined(name))
+                }
             }
         }
     }
@@ -324,6 +337,16 @@ mod protobuf {
                         crate::proto::golem::rib::call_type::Name::EnumConstructor(name),
                     ),
                 },
+                CallType::BuiltIn(function) => crate::proto::golem::rib::CallType {
+                    name: Some(crate::proto::golem::rib::call_type::Name::BuiltIn(
+                        function.name().to_string(),
+                    )),
+                },
+                CallType::UserDefined(name) => crate::proto::golem::rib::CallType {
+                    name: Some(crate::proto::golem::rib::call_type::Name::UserDefined(
+                        name,
+                    )),
+                },
                 CallType::InstanceCreation(instance_creation) => {
                     match instance_creation {
                         InstanceCreationType::WitWorker { worker_name , component_info} => {
@@ -14,6 +14,7 @@
 
 use crate::compiler::byte_code::internal::ExprState;
 use crate::compiler::ir::RibIR;
+use crate::rib_source_span::SourceSpan;
 use crate::type_inference::TypeHint;
 use crate::{Expr, InferredExpr, InstructionId};
 use desert_rust::BinaryCodec;
@@ -40,6 +41,9 @@ pub enum RibByteCodeGenerationError {
     },
     UnresolvedWorkerName,
     UnresolvedResourceVariable,
+    UnresolvedUserDefinedFunction(String),
+    InvalidLambda,
+    InvalidParallelExpr,
 }
 
 impl std::error::Error for RibByteCodeGenerationError {}
@@ -71,6 +75,21 @@ impl Display for RibByteCodeGenerationError {
             RibByteCodeGenerationError::UnresolvedWorkerName => {
                 write!(f, "inline invocation of functions on a worker instance is currently not supported")
             }
+            RibByteCodeGenerationError::UnresolvedUserDefinedFunction(function) => {
+                write!(f, "Unresolved definition for function: {function}")
+            }
+            RibByteCodeGenerationError::InvalidLambda => {
+                write!(
+                    f,
+                    "a lambda with a single parameter can only be passed to a list operation"
+                )
+            }
+            RibByteCodeGenerationError::InvalidParallelExpr => {
+                write!(
+                    f,
+                    "a parallel expression can only run the elements of a tuple or the iterations of a list comprehension"
+                )
+            }
             _ => {
                 write!(f, "inline invocation of methods on resource constructor instance is currently not supported")
             }
@@ -91,33 +110,86 @@ impl RibByteCode {
     pub fn from_expr(
         inferred_expr: &InferredExpr,
     ) -> Result<RibByteCode, RibByteCodeGenerationError> {
+        Self::from_expr_with_source_map(inferred_expr).map(|(byte_code, _)| byte_code)
+    }
+
+    // Convert expression to bytecode instructions, along with the source span of each instruction.
+    // The source map is not part of the byte code, as it is required only for debugging
+    pub fn from_expr_with_source_map(
+        inferred_expr: &InferredExpr,
+    ) -> Result<(RibByteCode, RibSourceMap), RibByteCodeGenerationError> {
         let expr: &Expr = inferred_expr.get_expr();
         let mut instructions = Vec::new();
+        let mut spans = Vec::new();
         let mut stack: Vec<ExprState> = Vec::new();
+        // Span of the expression that pushed each of the states in the stack
+        let mut stack_spans: Vec<SourceSpan> = Vec::new();
         let mut instruction_id = InstructionId::init();
+        let function_labels = internal::function_labels(expr, &mut instruction_id);
         stack.push(ExprState::from_expr(expr));
+        stack_spans.push(expr.source_span());
 
         while let Some(remaining) = stack.pop() {
+            let enclosing_span = stack_spans.pop().unwrap_or_default();
+
             match remaining {
                 ExprState::Expr(expr) => {
+                    let span = internal::source_span_or(&expr, enclosing_span);
+
                     internal::process_expr(
                         &expr,
                         &mut stack,
                         &mut instructions,
                         &mut instruction_id,
+                        &function_labels,
                     )?;
+
+                    spans.resize(instructions.len(), span.clone());
+                    stack_spans.resize(stack.len(), span);
                 }
 
                 ExprState::Instruction(instruction) => {
                     instructions.push(instruction);
+                    spans.push(enclosing_span);
                 }
             }
         }
 
         // Use VecDeque to avoid reversal, but ok as well since this is compilation
-        Ok(RibByteCode {
+        let byte_code = RibByteCode {
             instructions: instructions.into_iter().rev().collect(),
-        })
+        };
+
+        let source_map = RibSourceMap {
+            spans: spans.into_iter().rev().collect(),
+        };
+
+        Ok((byte_code, source_map))
+    }
+}
+
+// The source span of each instruction in a `RibByteCode`, where an instruction
+// maps to the innermost expression it is compiled from
+#[derive(Debug, Clone, Default)]
+pub struct RibSourceMap {
+    spans: Vec<SourceSpan>,
+}
+
+impl RibSourceMap {
+    pub fn source_span(&self, instruction_id: &InstructionId) -> Option<&SourceSpan> {
+        self.spans.get(instruction_id.index)
+    }
+
+    pub fn line(&self, instruction_id: &InstructionId) -> Option<i32> {
+        self.source_span(instruction_id)
+            .map(|source_span| source_span.start_line())
+    }
+
+    // Whether any instruction starts at the given line, i.e, whether a breakpoint at the line can be hit
+    pub fn contains_line(&self, line: i32) -> bool {
+        self.spans
+            .iter()
+            .any(|source_span| source_span.start_line() == line)
     }
 }
 
@@ -156,13 +228,14 @@ mod protobuf {
 
 mod internal {
     use crate::compiler::desugar::{desugar_pattern_match, desugar_range_selection};
+    use crate::rib_source_span::SourceSpan;
     use crate::{
-        AnalysedTypeWithUnit, DynamicParsedFunctionReference, Expr, FunctionReferenceType,
-        InferredType, InstanceIdentifier, InstanceVariable, InstructionId, Range,
-        RibByteCodeGenerationError, RibIR, TypeInternal, VariableId,
+        AnalysedTypeWithUnit, BuiltInFunction, DynamicParsedFunctionReference, Expr, ExprVisitor,
+        FunctionReferenceType, InferredType, InstanceIdentifier, InstanceVariable, InstructionId,
+        Range, RibByteCodeGenerationError, RibIR, TypeInternal, VariableId,
     };
     use golem_wasm::analysis::{AnalysedType, TypeFlags};
-    use std::collections::HashSet;
+    use std::collections::{HashMap, HashSet};
 
     use crate::call_type::{CallType, InstanceCreationType};
     use crate::type_inference::{GetTypeHint, TypeHint};
@@ -170,11 +243,43 @@ mod internal {
     use golem_wasm::{IntoValueAndType, Value, ValueAndType};
     use std::ops::Deref;
 
+    // Every user-defined function is given a label upfront,
+    // such that it can be called before its definition, or recursively.
+    pub(crate) fn function_labels(
+        expr: &Expr,
+        instruction_id: &mut InstructionId,
+    ) -> HashMap<String, InstructionId> {
+        let mut function_labels = HashMap::new();
+        let mut expr = expr.clone();
+        let mut visitor = ExprVisitor::top_down(&mut expr);
+
+        while let Some(expr) = visitor.pop_front() {
+            if let Expr::FunctionDefinition { function_name, .. } = expr {
+                function_labels.insert(function_name.clone(), instruction_id.increment_mut());
+            }
+        }
+
+        function_labels
+    }
+
+    // Expressions introduced by the compiler (such as the ones from desugaring) are not
+    // part of the source, and their instructions are mapped to the enclosing expression
+    pub(crate) fn source_span_or(expr: &Expr, enclosing_span: SourceSpan) -> SourceSpan {
+        let source_span = expr.source_span();
+
+        if source_span.start_line() > 0 {
+            source_span
+        } else {
+            enclosing_span
+        }
+    }
+
     pub(crate) fn process_expr(
         expr: &Expr,
         stack: &mut Vec<ExprState>,
         instructions: &mut Vec<RibIR>,
         instruction_id: &mut InstructionId,
+        function_labels: &HashMap<String, InstructionId>,
     ) -> Result<(), RibByteCodeGenerationError> {
         match expr {
             Expr::Unwrap { expr, .. } => {
@@ -648,6 +753,58 @@ mod internal {
                             convert_to_analysed_type(expr, inferred_type)?,
                         ));
                     }
+                    CallType::BuiltIn(BuiltInFunction::Map) => {
+                        let (variable_id, body) = get_lambda(&args[1])?;
+                        let analysed_type = convert_to_analysed_type(expr, inferred_type)?;
+
+                        handle_list_comprehension(
+                            instruction_id,
+                            stack,
+                            &args[0],
+                            body,
+                            variable_id,
+                            &analysed_type,
+                        )
+                    }
+                    CallType::BuiltIn(BuiltInFunction::Filter) => {
+                        let (variable_id, body) = get_lambda(&args[1])?;
+                        let analysed_type = convert_to_analysed_type(expr, inferred_type)?;
+
+                        handle_filter(
+                            instruction_id,
+                            stack,
+                            &args[0],
+                            body,
+                            variable_id,
+                            &analysed_type,
+                        )
+                    }
+                    CallType::BuiltIn(builtin_function) => {
+                        for expr in args.iter().rev() {
+                            stack.push(ExprState::from_expr(expr));
+                        }
+
+                        instructions.push(RibIR::CallBuiltIn(
+                            *builtin_function,
+                            convert_to_analysed_type(expr, inferred_type)?,
+                        ));
+                    }
+                    CallType::UserDefined(function_name) => {
+                        let function_label = function_labels.get(function_name).ok_or(
+                            RibByteCodeGenerationError::UnresolvedUserDefinedFunction(
+                                function_name.clone(),
+                            ),
+                        )?;
+
+                        for expr in args.iter().rev() {
+                            stack.push(ExprState::from_expr(expr));
+                        }
+
+                        instructions.push(RibIR::CallFunction(
+                            function_name.clone(),
+                            function_label.clone(),
+                        ));
+                    }
                 }
             }
 
@@ -751,6 +908,69 @@ mod internal {
             // Invoke is always handled by the CallType::Function branch
             Expr::InvokeMethodLazy { .. } => {}
 
+            Expr::FunctionDefinition {
+                function_name,
+                parameters,
+                body,
+                ..
+            } => {
+                let function_label = function_labels.get(function_name).ok_or(
+                    RibByteCodeGenerationError::UnresolvedUserDefinedFunction(
+                        function_name.clone(),
+                    ),
+                )?;
+
+                let parameters = parameters
+                    .iter()
+                    .map(|parameter| parameter.variable_id.clone())
+                    .collect::<Vec<_>>();
+
+                handle_function_definition(instruction_id, stack, function_label, &parameters, body)
+            }
+
+            // Lambdas are inlined as part of the list operations
+            Expr::Lambda { .. } => return Err(RibByteCodeGenerationError::InvalidLambda),
+
+            Expr::Parallel {
+                expr: parallel_expr,
+                max_concurrency,
+                ..
+            } => match parallel_expr.deref() {
+                Expr::Tuple {
+                    exprs,
+                    inferred_type,
+                    ..
+                } => {
+                    let analysed_type = convert_to_analysed_type(parallel_expr, inferred_type)?;
+                    handle_parallel_call(
+                        instruction_id,
+                        stack,
+                        exprs,
+                        &analysed_type,
+                        *max_concurrency,
+                    )
+                }
+                Expr::ListComprehension {
+                    iterated_variable,
+                    iterable_expr,
+                    yield_expr,
+                    inferred_type,
+                    ..
+                } => {
+                    let analysed_type = convert_to_analysed_type(parallel_expr, inferred_type)?;
+                    handle_parallel_map(
+                        instruction_id,
+                        stack,
+                        iterable_expr,
+                        yield_expr,
+                        iterated_variable,
+                        &analysed_type,
+                        *max_concurrency,
+                    )
+                }
+                _ => return Err(RibByteCodeGenerationError::InvalidParallelExpr),
+            },
+
             Expr::ListReduce {
                 reduce_variable,
                 iterated_variable,
@@ -874,6 +1094,164 @@ mod internal {
         stack.push(ExprState::from_ir(RibIR::SinkToList))
     }
 
+    fn handle_filter(
+        instruction_id: &mut InstructionId,
+        stack: &mut Vec<ExprState>,
+        iterable_expr: &Expr,
+        predicate_expr: &Expr,
+        variable_id: &VariableId,
+        sink_type: &AnalysedType,
+    ) {
+        stack.push(ExprState::from_expr(iterable_expr));
+
+        stack.push(ExprState::from_ir(RibIR::ToIterator));
+
+        stack.push(ExprState::from_ir(RibIR::CreateSink(sink_type.clone())));
+
+        let loop_start_label = instruction_id.increment_mut();
+
+        stack.push(ExprState::from_ir(RibIR::Label(loop_start_label.clone())));
+
+        let exit_label = instruction_id.increment_mut();
+
+        stack.push(ExprState::from_ir(RibIR::IsEmpty));
+
+        stack.push(ExprState::from_ir(RibIR::JumpIfFalse(exit_label.clone())));
+
+        stack.push(ExprState::from_ir(RibIR::AdvanceIterator));
+
+        stack.push(ExprState::from_ir(RibIR::AssignVar(variable_id.clone())));
+
+        stack.push(ExprState::from_expr(predicate_expr));
+
+        // Elements for which the predicate is false are skipped
+        stack.push(ExprState::from_ir(RibIR::JumpIfFalse(
+            loop_start_label.clone(),
+        )));
+
+        stack.push(ExprState::from_ir(RibIR::LoadVar(variable_id.clone())));
+
+        stack.push(ExprState::from_ir(RibIR::PushToSink));
+
+        stack.push(ExprState::from_ir(RibIR::Jump(loop_start_label)));
+
+        stack.push(ExprState::from_ir(RibIR::Label(exit_label)));
+
+        stack.push(ExprState::from_ir(RibIR::SinkToList))
+    }
+
+    // The body of a function is skipped over, unless it is called.
+    // A call pushes the arguments (the first argument being on the top of the stack),
+    // and the parameters are assigned in the scope of the function before running the body.
+    fn handle_function_definition(
+        instruction_id: &mut InstructionId,
+        stack: &mut Vec<ExprState>,
+        function_label: &InstructionId,
+        parameters: &[VariableId],
+        body: &Expr,
+    ) {
+        let function_end_label = instruction_id.increment_mut();
+
+        stack.push(ExprState::from_ir(RibIR::Jump(function_end_label.clone())));
+
+        stack.push(ExprState::from_ir(RibIR::Label(function_label.clone())));
+
+        for parameter in parameters {
+            stack.push(ExprState::from_ir(RibIR::AssignVar(parameter.clone())));
+        }
+
+        stack.push(ExprState::from_expr(body));
+
+        stack.push(ExprState::from_ir(RibIR::Return));
+
+        stack.push(ExprState::from_ir(RibIR::Label(function_end_label)));
+    }
+
+    // Each branch of a parallel expression is skipped over like the body of a function, and
+    // is run by the interpreter (concurrently with the other branches) until its `Return`.
+    // The results of the branches then form the elements of the tuple
+    fn handle_parallel_call(
+        instruction_id: &mut InstructionId,
+        stack: &mut Vec<ExprState>,
+        exprs: &[Expr],
+        tuple_type: &AnalysedType,
+        max_concurrency: Option<u64>,
+    ) {
+        let parallel_end_label = instruction_id.increment_mut();
+
+        stack.push(ExprState::from_ir(RibIR::Jump(parallel_end_label.clone())));
+
+        let mut branch_labels = vec![];
+
+        for expr in exprs {
+            let branch_label = instruction_id.increment_mut();
+
+            stack.push(ExprState::from_ir(RibIR::Label(branch_label.clone())));
+
+            stack.push(ExprState::from_expr(expr));
+
+            stack.push(ExprState::from_ir(RibIR::Return));
+
+            branch_labels.push(branch_label);
+        }
+
+        stack.push(ExprState::from_ir(RibIR::Label(parallel_end_label)));
+
+        stack.push(ExprState::from_ir(RibIR::ParallelCall(
+            branch_labels,
+            max_concurrency,
+        )));
+
+        stack.push(ExprState::from_ir(RibIR::PushTuple(
+            tuple_type.clone(),
+            exprs.len(),
+        )));
+    }
+
+    // Similar to a parallel tuple, where the yield expression is the only branch,
+    // which is run for each element of the list with the element assigned to the iterated variable
+    fn handle_parallel_map(
+        instruction_id: &mut InstructionId,
+        stack: &mut Vec<ExprState>,
+        iterable_expr: &Expr,
+        yield_expr: &Expr,
+        variable_id: &VariableId,
+        list_type: &AnalysedType,
+        max_concurrency: Option<u64>,
+    ) {
+        let parallel_end_label = instruction_id.increment_mut();
+
+        stack.push(ExprState::from_ir(RibIR::Jump(parallel_end_label.clone())));
+
+        let branch_label = instruction_id.increment_mut();
+
+        stack.push(ExprState::from_ir(RibIR::Label(branch_label.clone())));
+
+        stack.push(ExprState::from_expr(yield_expr));
+
+        stack.push(ExprState::from_ir(RibIR::Return));
+
+        stack.push(ExprState::from_ir(RibIR::Label(parallel_end_label)));
+
+        stack.push(ExprState::from_expr(iterable_expr));
+
+        stack.push(ExprState::from_ir(RibIR::ParallelMap(
+            branch_label,
+            variable_id.clone(),
+            list_type.clone(),
+            max_concurrency,
+        )));
+    }
+
+    fn get_lambda(expr: &Expr) -> Result<(&VariableId, &Expr), RibByteCodeGenerationError> {
+        match expr {
+            Expr::Lambda {
+                parameters, body, ..
+            } if parameters.len() == 1 => Ok((&parameters[0], body.deref())),
+            _ => Err(RibByteCodeGenerationError::InvalidLambda),
+        }
+    }
+
     fn handle_list_reduce(
         instruction_id: &mut InstructionId,
         stack: &mut Vec<ExprState>,
@@ -1030,6 +1408,32 @@ mod compiler_tests {
         assert_eq!(instructions, expected_instructions);
     }
 
+    #[test]
+    fn test_source_map_of_instructions() {
+        let expr = Expr::from_text(
+            r#"
+            let x = "hello";
+            x"#,
+        )
+        .unwrap();
+
+        let compiler = RibCompiler::default();
+
+        let inferred_expr = compiler.infer_types(expr).unwrap();
+
+        let (byte_code, source_map) =
+            RibByteCode::from_expr_with_source_map(&inferred_expr).unwrap();
+
+        let lines = (0..byte_code.len())
+            .map(|index| source_map.line(&InstructionId::new(index)))
+            .collect::<Vec<_>>();
+
+        assert_eq!(lines, vec![Some(2), Some(2), Some(3)]);
+        assert_eq!(source_map.line(&InstructionId::new(3)), None);
+        assert!(source_map.contains_line(3));
+        assert!(!source_map.contains_line(4));
+    }
+
     #[test]
     fn test_instructions_equal_to() {
         let number_f32 = Expr::number_inferred(BigDecimal::from(1), None, InferredType::f32());
@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use crate::compiler::worker_functions_in_rib::WorkerFunctionsInRib;
-use crate::{RibByteCode, RibInputTypeInfo, RibOutputTypeInfo};
+use crate::{RibByteCode, RibInputTypeInfo, RibLintWarning, RibOutputTypeInfo};
 
 #[derive(Debug, Clone)]
 pub struct CompilerOutput {
@@ -26,6 +26,9 @@ pub struct CompilerOutput {
     // At the API level, if we have access to expr, whenever this field is optional
     // we can compile the expression again and get the output type info
     pub rib_output_type_info: Option<RibOutputTypeInfo>,
+    // Lint warnings are only reported at the time of compilation,
+    // and are not part of the persisted compiler output.
+    pub lint_warnings: Vec<RibLintWarning>,
 }
 
 mod protobuf {
@@ -58,6 +61,7 @@ mod protobuf {
                 byte_code,
                 rib_input_type_info: rib_input,
                 rib_output_type_info,
+                lint_warnings: vec![],
             })
         }
     }
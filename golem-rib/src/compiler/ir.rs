@@ -12,7 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{AnalysedTypeWithUnit, ComponentDependencyKey, ParsedFunctionSite, VariableId};
+use crate::{
+    AnalysedTypeWithUnit, BuiltInFunction, ComponentDependencyKey, ParsedFunctionSite, VariableId,
+};
 use desert_rust::BinaryCodec;
 use golem_wasm::analysis::AnalysedType;
 use golem_wasm::ValueAndType;
@@ -73,6 +75,11 @@ pub enum RibIR {
     SinkToList,
     Length,
     GenerateWorkerName(Option<VariableId>),
+    CallBuiltIn(BuiltInFunction, AnalysedType), // Number of arguments is the arity of the function
+    CallFunction(String, InstructionId), // Jumps to the label of a user-defined function, arguments are already on the stack
+    Return,
+    ParallelCall(Vec<InstructionId>, Option<u64>), // Runs the branches at the labels concurrently, and pushes their results such that the result of the first branch is on the top of the stack
+    ParallelMap(InstructionId, VariableId, AnalysedType, Option<u64>), // Runs the branch at the label concurrently for each element of the list on top of the stack, and pushes the list of results
 }
 
 #[derive(Debug, Clone, PartialEq, BinaryCodec)]
@@ -142,10 +149,11 @@ mod protobuf {
         PushTupleInstruction, RibIr as ProtoRibIR, WitResource,
     };
     use crate::{
-        AnalysedTypeWithUnit, ComponentDependencyKey, FunctionReferenceType, InstanceVariable,
-        InstructionId, ParsedFunctionSite, RibIR, VariableId,
+        AnalysedTypeWithUnit, BuiltInFunction, ComponentDependencyKey, FunctionReferenceType,
+        InstanceVariable, InstructionId, ParsedFunctionSite, RibIR, VariableId,
     };
     use golem_wasm::analysis::{AnalysedType, TypeStr};
+    use std::str::FromStr;
 
     impl TryFrom<crate::proto::golem::rib::FunctionReferenceType> for FunctionReferenceType {
         type Error = String;
@@ -230,6 +238,48 @@ mod protobuf {
 
                     Ok(RibIR::GenerateWorkerName(variable_id))
                 }
+                Instruction::CallBuiltIn(call_built_in) => {
+                    let builtin_function = BuiltInFunction::from_str(&call_built_in.function_name)?;
+
+                    let return_type = call_built_in
+                        .return_type
+                        .ok_or("Missing return_type for built-in function call".to_string())?;
+
+                    let analysed_type = (&return_type)
+                        .try_into()
+                        .map_err(|_| "Failed to convert AnalysedType".to_string())?;
+
+                    Ok(RibIR::CallBuiltIn(builtin_function, analysed_type))
+                }
+                Instruction::CallFunction(call_function) => Ok(RibIR::CallFunction(
+                    call_function.function_name,
+                    InstructionId::new(call_function.instruction_id as usize),
+                )),
+                Instruction::Return(_) => Ok(RibIR::Return),
+                Instruction::ParallelCall(parallel_call) => Ok(RibIR::ParallelCall(
+                    parallel_call
+                        .instruction_ids
+                        .into_iter()
+                        .map(|instruction_id| InstructionId::new(instruction_id as usize))
+                        .collect(),
+                    parallel_call.max_concurrency,
+                )),
+                Instruction::ParallelMap(parallel_map) => Ok(RibIR::ParallelMap(
+                    InstructionId::new(parallel_map.instruction_id as usize),
+                    parallel_map
+                        .iterated_variable
+                        .ok_or("Iterated variable not present".to_string())?
+                        .try_into()
+                        .map_err(|_| "Failed to convert ParallelMap".to_string())?,
+                    parallel_map
+                        .list_type
+                        .ok_or("List type not present".to_string())
+                        .and_then(|t| {
+                            (&t).try_into()
+                                .map_err(|_| "Failed to convert AnalysedType".to_string())
+                        })?,
+                    parallel_map.max_concurrency,
+                )),
                 Instruction::PushLit(value) => Ok(RibIR::PushLit(
                     value
                         .try_into()
@@ -484,6 +534,39 @@ mod protobuf {
                         variable_id: variable_id_proto,
                     })
                 }
+                RibIR::CallBuiltIn(builtin_function, return_type) => {
+                    Instruction::CallBuiltIn(crate::proto::golem::rib::CallBuiltInInstruction {
+                        function_name: builtin_function.name().to_string(),
+                        return_type: Some((&return_type).into()),
+                    })
+                }
+                RibIR::CallFunction(function_name, instruction_id) => {
+                    Instruction::CallFunction(crate::proto::golem::rib::CallFunctionInstruction {
+                        function_name,
+                        instruction_id: instruction_id.index as u64,
+                    })
+                }
+                RibIR::Return => Instruction::Return(crate::proto::golem::rib::Return {}),
+                RibIR::ParallelCall(instruction_ids, max_concurrency) => {
+                    Instruction::ParallelCall(crate::proto::golem::rib::ParallelCallInstruction {
+                        instruction_ids: instruction_ids
+                            .into_iter()
+                            .map(|instruction_id| instruction_id.index as u64)
+                            .collect(),
+                        max_concurrency,
+                    })
+                }
+                RibIR::ParallelMap(
+                    instruction_id,
+                    iterated_variable,
+                    list_type,
+                    max_concurrency,
+                ) => Instruction::ParallelMap(crate::proto::golem::rib::ParallelMapInstruction {
+                    instruction_id: instruction_id.index as u64,
+                    iterated_variable: Some(iterated_variable.into()),
+                    list_type: Some((&list_type).into()),
+                    max_concurrency,
+                }),
                 RibIR::PushLit(value) => Instruction::PushLit(value.into()),
                 RibIR::And => Instruction::And(And {}),
                 RibIR::IsEmpty => Instruction::IsEmpty(IsEmpty {}),
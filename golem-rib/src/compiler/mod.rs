@@ -21,7 +21,8 @@ pub use worker_functions_in_rib::*;
 use crate::rib_type_error::RibTypeError;
 use crate::{
     ComponentDependencies, ComponentDependencyKey, CustomInstanceSpec, Expr,
-    GlobalVariableTypeSpec, InferredExpr, RibInputTypeInfo, RibOutputTypeInfo,
+    GlobalVariableTypeSpec, InferredExpr, LintConfig, RibInputTypeInfo, RibLintWarning,
+    RibOutputTypeInfo,
 };
 use golem_wasm::analysis::{AnalysedExport, TypeEnum, TypeVariant};
 use std::error::Error;
@@ -34,11 +35,17 @@ mod ir;
 mod type_with_unit;
 mod worker_functions_in_rib;
 
+// The version of the byte code produced by the compiler, to be increased whenever a change in
+// the compiler or the interpreter makes previously compiled byte code invalid.
+// Persisted byte code compiled by a different version has to be compiled again.
+pub const RIB_COMPILER_VERSION: u32 = 1;
+
 #[derive(Default)]
 pub struct RibCompiler {
     component_dependency: ComponentDependencies,
     global_variable_type_spec: Vec<GlobalVariableTypeSpec>,
     custom_instance_spec: Vec<CustomInstanceSpec>,
+    lint_config: LintConfig,
 }
 
 impl RibCompiler {
@@ -58,6 +65,7 @@ impl RibCompiler {
             component_dependency: component_dependencies,
             global_variable_type_spec,
             custom_instance_spec: config.custom_instance_spec,
+            lint_config: config.lint_config,
         }
     }
 
@@ -74,6 +82,10 @@ impl RibCompiler {
         })
     }
 
+    pub fn lint(&self, inferred_expr: &InferredExpr) -> Vec<RibLintWarning> {
+        crate::lint::lint(inferred_expr, &self.lint_config)
+    }
+
     pub fn get_custom_instance_names(&self) -> Vec<String> {
         self.custom_instance_spec
             .iter()
@@ -120,6 +132,18 @@ impl RibCompiler {
             });
         }
 
+        let lint_warnings = self.lint(&inferred_expr);
+
+        let denied_lints = lint_warnings
+            .iter()
+            .filter(|warning| warning.is_denied())
+            .cloned()
+            .collect::<Vec<_>>();
+
+        if !denied_lints.is_empty() {
+            return Err(RibCompilationError::DeniedLints(denied_lints));
+        }
+
         let byte_code = RibByteCode::from_expr(&inferred_expr)?;
 
         Ok(CompilerOutput {
@@ -127,6 +151,7 @@ impl RibCompiler {
             byte_code,
             rib_input_type_info: global_input_type_info,
             rib_output_type_info: Some(output_type_info),
+            lint_warnings,
         })
     }
 
@@ -153,11 +178,14 @@ impl RibCompiler {
 ///   You can also associate specific types with known global variables using
 ///   `GlobalVariableTypeSpec`. For example, the path `request.path.*` can be enforced to always
 ///   be of type `string`. Note that not all global variables require a type specification.
+/// - `lint_config`: The level of each lint reported by the compiler. By default, every lint is
+///   reported as a warning in the compiler output. Denied lints fail the compilation.
 #[derive(Default)]
 pub struct RibCompilerConfig {
     component_dependencies: Vec<ComponentDependency>,
     input_spec: Vec<GlobalVariableTypeSpec>,
     custom_instance_spec: Vec<CustomInstanceSpec>,
+    lint_config: LintConfig,
 }
 
 impl RibCompilerConfig {
@@ -170,8 +198,14 @@ impl RibCompilerConfig {
             component_dependencies,
             input_spec,
             custom_instance_spec,
+            lint_config: LintConfig::default(),
         }
     }
+
+    pub fn with_lint_config(mut self, lint_config: LintConfig) -> RibCompilerConfig {
+        self.lint_config = lint_config;
+        self
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -236,6 +270,10 @@ pub enum RibCompilationError {
     // A typical use of static analysis in Rib is to identify all the valid worker functions.
     // If this analysis phase fails, it typically indicates a bug in the Rib compiler.
     RibStaticAnalysisError(String),
+
+    // The Rib script type checks, but violates lints that are denied
+    // in the compiler configuration, such as an unused `let` binding.
+    DeniedLints(Vec<RibLintWarning>),
 }
 
 impl From<RibByteCodeGenerationError> for RibCompilationError {
@@ -272,6 +310,14 @@ impl Display for RibCompilationError {
             RibCompilationError::ByteCodeGenerationFail(e) => {
                 write!(f, "{e}")
             }
+            RibCompilationError::DeniedLints(lints) => {
+                let lints = lints
+                    .iter()
+                    .map(|lint| lint.to_string())
+                    .collect::<Vec<_>>();
+
+                write!(f, "{}", lints.join("\n"))
+            }
         }
     }
 }
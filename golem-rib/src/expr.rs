@@ -314,6 +314,33 @@ pub enum Expr {
         source_span: SourceSpan,
         variable_id: Option<VariableId>,
     },
+    FunctionDefinition {
+        function_name: String,
+        parameters: Vec<FunctionParameter>,
+        return_type: TypeName,
+        body: Box<Expr>,
+        type_annotation: Option<TypeName>,
+        inferred_type: InferredType,
+        source_span: SourceSpan,
+    },
+    Lambda {
+        parameters: Vec<VariableId>,
+        body: Box<Expr>,
+        type_annotation: Option<TypeName>,
+        inferred_type: InferredType,
+        source_span: SourceSpan,
+    },
+    // `parallel { a.f(), b.g() }` is parsed as Expr::Parallel { Expr::Tuple { .. } }, and
+    // `parallel(4) for x in xs { yield x.f(); }` as Expr::Parallel { Expr::ListComprehension { .. } }.
+    // Each element of the tuple, or each iteration of the list comprehension, runs concurrently
+    // with the others, with at most `max_concurrency` of them running at a time.
+    Parallel {
+        expr: Box<Expr>,
+        max_concurrency: Option<u64>,
+        type_annotation: Option<TypeName>,
+        inferred_type: InferredType,
+        source_span: SourceSpan,
+    },
 }
 
 impl Expr {
@@ -873,6 +900,43 @@ impl Expr {
         )
     }
 
+    pub fn function_definition(
+        function_name: impl AsRef<str>,
+        parameters: Vec<FunctionParameter>,
+        return_type: TypeName,
+        body: Expr,
+    ) -> Self {
+        Expr::FunctionDefinition {
+            function_name: function_name.as_ref().to_string(),
+            parameters,
+            return_type,
+            body: Box::new(body),
+            type_annotation: None,
+            inferred_type: InferredType::tuple(vec![]),
+            source_span: SourceSpan::default(),
+        }
+    }
+
+    pub fn lambda(parameters: Vec<VariableId>, body: Expr) -> Self {
+        Expr::Lambda {
+            parameters,
+            body: Box::new(body),
+            type_annotation: None,
+            inferred_type: InferredType::tuple(vec![]),
+            source_span: SourceSpan::default(),
+        }
+    }
+
+    pub fn parallel(expr: Expr, max_concurrency: Option<u64>) -> Self {
+        Expr::Parallel {
+            expr: Box::new(expr),
+            max_concurrency,
+            type_annotation: None,
+            inferred_type: InferredType::unknown(),
+            source_span: SourceSpan::default(),
+        }
+    }
+
     pub fn bind_global_variable_types(&mut self, type_spec: &Vec<GlobalVariableTypeSpec>) {
         type_inference::bind_global_variable_types(self, type_spec)
     }
@@ -1087,7 +1151,10 @@ impl Expr {
             | Expr::Range { inferred_type, .. }
             | Expr::InvokeMethodLazy { inferred_type, .. }
             | Expr::Length { inferred_type, .. }
-            | Expr::GenerateWorkerName { inferred_type, .. } => &mut *inferred_type,
+            | Expr::GenerateWorkerName { inferred_type, .. }
+            | Expr::FunctionDefinition { inferred_type, .. }
+            | Expr::Lambda { inferred_type, .. }
+            | Expr::Parallel { inferred_type, .. } => &mut *inferred_type,
         }
     }
 
@@ -1131,7 +1198,10 @@ impl Expr {
             | Expr::Range { inferred_type, .. }
             | Expr::InvokeMethodLazy { inferred_type, .. }
             | Expr::Length { inferred_type, .. }
-            | Expr::GenerateWorkerName { inferred_type, .. } => inferred_type.clone(),
+            | Expr::GenerateWorkerName { inferred_type, .. }
+            | Expr::FunctionDefinition { inferred_type, .. }
+            | Expr::Lambda { inferred_type, .. }
+            | Expr::Parallel { inferred_type, .. } => inferred_type.clone(),
         }
     }
 
@@ -1173,12 +1243,15 @@ impl Expr {
         self.bind_type_annotations();
         self.bind_variables_of_list_comprehension();
         self.bind_variables_of_list_reduce();
+        self.bind_variables_of_function_parameters();
         self.bind_variables_of_pattern_match();
         self.bind_variables_of_let_assignment();
         self.identify_instance_creation(component_dependency, custom_instance_spec)?;
         self.ensure_stateful_instance();
         self.infer_variants(component_dependency);
         self.infer_enums(component_dependency);
+        self.infer_user_defined_functions()?;
+        self.identify_builtin_function_calls(component_dependency);
         Ok(())
     }
 
@@ -1243,6 +1316,12 @@ impl Expr {
         type_inference::bind_variables_of_list_reduce(self);
     }
 
+    // Parameters of function definitions and lambdas are scoped to their body,
+    // and they are given unique ids so that they never clash with let bindings
+    pub fn bind_variables_of_function_parameters(&mut self) {
+        type_inference::bind_variables_of_function_parameters(self);
+    }
+
     pub fn identify_instance_creation(
         &mut self,
         component_dependency: &ComponentDependencies,
@@ -1349,6 +1428,9 @@ impl Expr {
             | Expr::Range { inferred_type, .. }
             | Expr::Length { inferred_type, .. }
             | Expr::GenerateWorkerName { inferred_type, .. }
+            | Expr::FunctionDefinition { inferred_type, .. }
+            | Expr::Lambda { inferred_type, .. }
+            | Expr::Parallel { inferred_type, .. }
             | Expr::Call { inferred_type, .. } => {
                 if !new_inferred_type.is_unknown() {
                     *inferred_type = inferred_type.merge(new_inferred_type);
@@ -1401,7 +1483,10 @@ impl Expr {
             | Expr::Range { source_span, .. }
             | Expr::Length { source_span, .. }
             | Expr::Call { source_span, .. }
-            | Expr::GenerateWorkerName { source_span, .. } => source_span.clone(),
+            | Expr::GenerateWorkerName { source_span, .. }
+            | Expr::FunctionDefinition { source_span, .. }
+            | Expr::Lambda { source_span, .. }
+            | Expr::Parallel { source_span, .. } => source_span.clone(),
         }
     }
 
@@ -1521,6 +1606,15 @@ impl Expr {
             | Expr::GenerateWorkerName {
                 type_annotation, ..
             }
+            | Expr::FunctionDefinition {
+                type_annotation, ..
+            }
+            | Expr::Lambda {
+                type_annotation, ..
+            }
+            | Expr::Parallel {
+                type_annotation, ..
+            }
             | Expr::Call {
                 type_annotation, ..
             } => type_annotation,
@@ -1659,6 +1753,15 @@ impl Expr {
             | Expr::GenerateWorkerName {
                 type_annotation, ..
             }
+            | Expr::FunctionDefinition {
+                type_annotation, ..
+            }
+            | Expr::Lambda {
+                type_annotation, ..
+            }
+            | Expr::Parallel {
+                type_annotation, ..
+            }
             | Expr::Call {
                 type_annotation, ..
             } => {
@@ -1713,6 +1816,9 @@ impl Expr {
             | Expr::InvokeMethodLazy { source_span, .. }
             | Expr::Length { source_span, .. }
             | Expr::GenerateWorkerName { source_span, .. }
+            | Expr::FunctionDefinition { source_span, .. }
+            | Expr::Lambda { source_span, .. }
+            | Expr::Parallel { source_span, .. }
             | Expr::Call { source_span, .. } => {
                 *source_span = new_source_span;
             }
@@ -1767,6 +1873,9 @@ impl Expr {
             | Expr::Range { inferred_type, .. }
             | Expr::Length { inferred_type, .. }
             | Expr::GenerateWorkerName { inferred_type, .. }
+            | Expr::FunctionDefinition { inferred_type, .. }
+            | Expr::Lambda { inferred_type, .. }
+            | Expr::Parallel { inferred_type, .. }
             | Expr::Call { inferred_type, .. } => {
                 *inferred_type = new_inferred_type;
             }
@@ -1781,6 +1890,17 @@ impl Expr {
         type_inference::infer_variants(self, component_dependency);
     }
 
+    pub fn identify_builtin_function_calls(
+        &mut self,
+        component_dependency: &ComponentDependencies,
+    ) {
+        type_inference::identify_builtin_function_calls(self, component_dependency);
+    }
+
+    pub fn infer_user_defined_functions(&mut self) -> Result<(), RibTypeErrorInternal> {
+        type_inference::infer_user_defined_functions(self)
+    }
+
     pub fn visit_expr_nodes_lazy<'a>(&'a mut self, queue: &mut VecDeque<&'a mut Expr>) {
         type_inference::visit_expr_nodes_lazy(self, queue);
     }
@@ -1882,6 +2002,21 @@ impl Display for Number {
     }
 }
 
+#[derive(Debug, Hash, Clone, PartialEq, Eq, Ord, PartialOrd)]
+pub struct FunctionParameter {
+    pub variable_id: VariableId,
+    pub type_name: TypeName,
+}
+
+impl FunctionParameter {
+    pub fn new(variable_id: VariableId, type_name: TypeName) -> FunctionParameter {
+        FunctionParameter {
+            variable_id,
+            type_name,
+        }
+    }
+}
+
 #[derive(Debug, Hash, Clone, PartialEq, Eq, Ord, PartialOrd)]
 pub struct MatchArm {
     pub arm_pattern: ArmPattern,
@@ -2375,6 +2510,42 @@ impl TryFrom<crate::proto::golem::rib::Expr> for Expr {
                     VariableId::list_comprehension_identifier(list_comprehension.iterated_variable);
                 Expr::list_comprehension(variable_id, iterable_expr, yield_expr)
             }
+            crate::proto::golem::rib::expr::Expr::FunctionDefinition(function_definition) => {
+                let parameters = function_definition
+                    .parameters
+                    .into_iter()
+                    .map(|parameter| {
+                        let type_name = parameter.type_name.ok_or("Missing type name")?;
+                        Ok(FunctionParameter::new(
+                            VariableId::function_parameter(parameter.name, 0),
+                            TypeName::try_from(type_name)?,
+                        ))
+                    })
+                    .collect::<Result<Vec<_>, String>>()?;
+                let return_type = function_definition
+                    .return_type
+                    .ok_or("Missing return type")?;
+                let body = function_definition.body.ok_or("Missing body")?;
+                Expr::function_definition(
+                    function_definition.function_name,
+                    parameters,
+                    TypeName::try_from(return_type)?,
+                    (*body).try_into()?,
+                )
+            }
+            crate::proto::golem::rib::expr::Expr::Lambda(lambda) => {
+                let parameters = lambda
+                    .parameters
+                    .into_iter()
+                    .map(|name| VariableId::function_parameter(name, 0))
+                    .collect();
+                let body = lambda.body.ok_or("Missing body")?;
+                Expr::lambda(parameters, (*body).try_into()?)
+            }
+            crate::proto::golem::rib::expr::Expr::Parallel(parallel) => {
+                let expr = parallel.expr.ok_or("Missing expr")?;
+                Expr::parallel((*expr).try_into()?, parallel.max_concurrency)
+            }
             crate::proto::golem::rib::expr::Expr::ListReduce(list_reduce) => {
                 let init_value_expr = list_reduce.init_value_expr.ok_or("Missing initial expr")?;
                 let init_value_expr = (*init_value_expr).try_into()?;
@@ -2962,6 +3133,47 @@ mod protobuf {
                         yield_expr: Some(Box::new((*yield_expr).into())),
                     },
                 ))),
+                Expr::FunctionDefinition {
+                    function_name,
+                    parameters,
+                    return_type,
+                    body,
+                    ..
+                } => Some(crate::proto::golem::rib::expr::Expr::FunctionDefinition(
+                    Box::new(crate::proto::golem::rib::FunctionDefinitionExpr {
+                        function_name,
+                        parameters: parameters
+                            .into_iter()
+                            .map(|parameter| crate::proto::golem::rib::FunctionParameter {
+                                name: parameter.variable_id.name(),
+                                type_name: Some(parameter.type_name.into()),
+                            })
+                            .collect(),
+                        return_type: Some(return_type.into()),
+                        body: Some(Box::new((*body).into())),
+                    }),
+                )),
+                Expr::Lambda {
+                    parameters, body, ..
+                } => Some(crate::proto::golem::rib::expr::Expr::Lambda(Box::new(
+                    crate::proto::golem::rib::LambdaExpr {
+                        parameters: parameters
+                            .into_iter()
+                            .map(|variable_id| variable_id.name())
+                            .collect(),
+                        body: Some(Box::new((*body).into())),
+                    },
+                ))),
+                Expr::Parallel {
+                    expr,
+                    max_concurrency,
+                    ..
+                } => Some(crate::proto::golem::rib::expr::Expr::Parallel(Box::new(
+                    crate::proto::golem::rib::ParallelExpr {
+                        expr: Some(Box::new((*expr).into())),
+                        max_concurrency,
+                    },
+                ))),
                 Expr::InvokeMethodLazy {
                     lhs,
                     method,
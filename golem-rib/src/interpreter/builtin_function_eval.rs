@@ -0,0 +1,346 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::interpreter::literal::GetLiteralValue;
+use crate::interpreter::rib_runtime_error::{invalid_comparison, type_mismatch_with_type_hint};
+use crate::type_inference::GetTypeHint;
+use crate::{bail_corrupted_state, BuiltInFunction, RibInterpreterResult, TypeHint};
+use base64::Engine;
+use golem_wasm::analysis::AnalysedType;
+use golem_wasm::{Value, ValueAndType};
+
+// Evaluates a built-in function, given the arguments in the order they appear in the call.
+// The return type is computed by the compiler, and is used to type the result.
+pub(crate) fn evaluate_builtin_function(
+    builtin_function: &BuiltInFunction,
+    args: Vec<ValueAndType>,
+    return_type: &AnalysedType,
+) -> RibInterpreterResult<ValueAndType> {
+    if args.len() != builtin_function.arity() {
+        bail_corrupted_state!(
+            "built-in function {} expects {} arguments, found {}",
+            builtin_function,
+            builtin_function.arity(),
+            args.len()
+        );
+    }
+
+    let value = match builtin_function {
+        BuiltInFunction::ToLower => Value::String(get_string(&args[0])?.to_lowercase()),
+        BuiltInFunction::ToUpper => Value::String(get_string(&args[0])?.to_uppercase()),
+        BuiltInFunction::Trim => Value::String(get_string(&args[0])?.trim().to_string()),
+        BuiltInFunction::Split => {
+            let input = get_string(&args[0])?;
+            let separator = get_string(&args[1])?;
+
+            Value::List(
+                input
+                    .split(separator)
+                    .map(|part| Value::String(part.to_string()))
+                    .collect(),
+            )
+        }
+        BuiltInFunction::Contains => match &args[0].value {
+            Value::String(input) => Value::Bool(input.contains(get_string(&args[1])?)),
+            Value::List(items) => Value::Bool(items.contains(&args[1].value)),
+            _ => {
+                return Err(type_mismatch_with_type_hint(
+                    vec![TypeHint::Str, TypeHint::List(None)],
+                    args[0].typ.get_type_hint(),
+                ))
+            }
+        },
+        BuiltInFunction::StartsWith => {
+            Value::Bool(get_string(&args[0])?.starts_with(get_string(&args[1])?))
+        }
+        BuiltInFunction::EndsWith => {
+            Value::Bool(get_string(&args[0])?.ends_with(get_string(&args[1])?))
+        }
+        BuiltInFunction::Replace => Value::String(
+            get_string(&args[0])?.replace(get_string(&args[1])?, get_string(&args[2])?),
+        ),
+        BuiltInFunction::Len => match &args[0].value {
+            Value::String(input) => Value::U64(input.chars().count() as u64),
+            Value::List(items) => Value::U64(items.len() as u64),
+            _ => {
+                return Err(type_mismatch_with_type_hint(
+                    vec![TypeHint::Str, TypeHint::List(None)],
+                    args[0].typ.get_type_hint(),
+                ))
+            }
+        },
+        BuiltInFunction::ParseU64 => {
+            option(get_string(&args[0])?.parse::<u64>().ok().map(Value::U64))
+        }
+        BuiltInFunction::ParseS64 => {
+            option(get_string(&args[0])?.parse::<i64>().ok().map(Value::S64))
+        }
+        BuiltInFunction::ParseF64 => {
+            option(get_string(&args[0])?.parse::<f64>().ok().map(Value::F64))
+        }
+        BuiltInFunction::Join => {
+            let separator = get_string(&args[1])?;
+
+            let parts = match &args[0].value {
+                Value::List(items) => items
+                    .iter()
+                    .map(|item| match item {
+                        Value::String(part) => Ok(part.as_str()),
+                        _ => Err(type_mismatch_with_type_hint(
+                            vec![TypeHint::Str],
+                            args[0].typ.get_type_hint(),
+                        )),
+                    })
+                    .collect::<RibInterpreterResult<Vec<_>>>()?,
+                _ => {
+                    return Err(type_mismatch_with_type_hint(
+                        vec![TypeHint::List(Some(Box::new(TypeHint::Str)))],
+                        args[0].typ.get_type_hint(),
+                    ))
+                }
+            };
+
+            Value::String(parts.join(separator))
+        }
+        BuiltInFunction::UnwrapOr => match &args[0].value {
+            Value::Option(Some(value)) => value.as_ref().clone(),
+            Value::Option(None) => args[1].value.clone(),
+            _ => {
+                return Err(type_mismatch_with_type_hint(
+                    vec![TypeHint::Option(None)],
+                    args[0].typ.get_type_hint(),
+                ))
+            }
+        },
+        BuiltInFunction::Min | BuiltInFunction::Max => {
+            let (left, right) = match (args[0].get_literal(), args[1].get_literal()) {
+                (Some(left), Some(right)) => (left, right),
+                _ => {
+                    return Err(invalid_comparison(
+                        &format!("values passed to {builtin_function} cannot be compared"),
+                        Some(args[0].clone()),
+                        Some(args[1].clone()),
+                    ))
+                }
+            };
+
+            let pick_left = match builtin_function {
+                BuiltInFunction::Min => left <= right,
+                _ => left >= right,
+            };
+
+            if pick_left {
+                args[0].value.clone()
+            } else {
+                args[1].value.clone()
+            }
+        }
+        BuiltInFunction::Base64Encode => {
+            Value::String(base64::engine::general_purpose::STANDARD.encode(get_string(&args[0])?))
+        }
+        BuiltInFunction::Base64Decode => option(
+            base64::engine::general_purpose::STANDARD
+                .decode(get_string(&args[0])?)
+                .ok()
+                .and_then(|bytes| String::from_utf8(bytes).ok())
+                .map(Value::String),
+        ),
+        BuiltInFunction::UrlEncode => {
+            Value::String(urlencoding::encode(get_string(&args[0])?).into_owned())
+        }
+        BuiltInFunction::UrlDecode => option(
+            urlencoding::decode(get_string(&args[0])?)
+                .ok()
+                .map(|decoded| Value::String(decoded.into_owned())),
+        ),
+        // List operations taking a lambda are compiled into loops, and never called at runtime
+        BuiltInFunction::Map | BuiltInFunction::Filter => {
+            bail_corrupted_state!(
+                "built-in function {} is expected to be compiled inline",
+                builtin_function
+            );
+        }
+    };
+
+    Ok(ValueAndType::new(value, return_type.clone()))
+}
+
+fn get_string(value_and_type: &ValueAndType) -> RibInterpreterResult<&str> {
+    match &value_and_type.value {
+        Value::String(value) => Ok(value.as_str()),
+        _ => Err(type_mismatch_with_type_hint(
+            vec![TypeHint::Str],
+            value_and_type.typ.get_type_hint(),
+        )),
+    }
+}
+
+fn option(value: Option<Value>) -> Value {
+    Value::Option(value.map(Box::new))
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use crate::interpreter::builtin_function_eval::evaluate_builtin_function;
+    use crate::BuiltInFunction;
+    use golem_wasm::analysis::analysed_type::{bool, list, option, str, u64};
+    use golem_wasm::{IntoValueAndType, Value, ValueAndType};
+
+    fn eval(
+        builtin_function: BuiltInFunction,
+        args: Vec<ValueAndType>,
+        return_type: golem_wasm::analysis::AnalysedType,
+    ) -> Value {
+        evaluate_builtin_function(&builtin_function, args, &return_type)
+            .unwrap()
+            .value
+    }
+
+    #[test]
+    fn test_string_functions() {
+        assert_eq!(
+            eval(
+                BuiltInFunction::ToLower,
+                vec!["FoO".into_value_and_type()],
+                str()
+            ),
+            Value::String("foo".to_string())
+        );
+
+        assert_eq!(
+            eval(
+                BuiltInFunction::Split,
+                vec!["a,b".into_value_and_type(), ",".into_value_and_type()],
+                list(str())
+            ),
+            Value::List(vec![
+                Value::String("a".to_string()),
+                Value::String("b".to_string())
+            ])
+        );
+
+        assert_eq!(
+            eval(
+                BuiltInFunction::StartsWith,
+                vec!["foobar".into_value_and_type(), "foo".into_value_and_type()],
+                bool()
+            ),
+            Value::Bool(true)
+        );
+
+        assert_eq!(
+            eval(
+                BuiltInFunction::Len,
+                vec!["héllo".into_value_and_type()],
+                u64()
+            ),
+            Value::U64(5)
+        );
+    }
+
+    #[test]
+    fn test_parse_functions() {
+        assert_eq!(
+            eval(
+                BuiltInFunction::ParseU64,
+                vec!["42".into_value_and_type()],
+                option(u64())
+            ),
+            Value::Option(Some(Box::new(Value::U64(42))))
+        );
+
+        assert_eq!(
+            eval(
+                BuiltInFunction::ParseU64,
+                vec!["-42".into_value_and_type()],
+                option(u64())
+            ),
+            Value::Option(None)
+        );
+    }
+
+    #[test]
+    fn test_min_max_and_unwrap_or() {
+        assert_eq!(
+            eval(
+                BuiltInFunction::Min,
+                vec![3u64.into_value_and_type(), 7u64.into_value_and_type()],
+                u64()
+            ),
+            Value::U64(3)
+        );
+
+        assert_eq!(
+            eval(
+                BuiltInFunction::Max,
+                vec![3u64.into_value_and_type(), 7u64.into_value_and_type()],
+                u64()
+            ),
+            Value::U64(7)
+        );
+
+        assert_eq!(
+            eval(
+                BuiltInFunction::UnwrapOr,
+                vec![
+                    ValueAndType::new(Value::Option(None), option(u64())),
+                    1u64.into_value_and_type()
+                ],
+                u64()
+            ),
+            Value::U64(1)
+        );
+    }
+
+    #[test]
+    fn test_encoding_functions() {
+        assert_eq!(
+            eval(
+                BuiltInFunction::Base64Encode,
+                vec!["golem".into_value_and_type()],
+                str()
+            ),
+            Value::String("Z29sZW0=".to_string())
+        );
+
+        assert_eq!(
+            eval(
+                BuiltInFunction::Base64Decode,
+                vec!["not base64!".into_value_and_type()],
+                option(str())
+            ),
+            Value::Option(None)
+        );
+
+        assert_eq!(
+            eval(
+                BuiltInFunction::UrlEncode,
+                vec!["a b&c".into_value_and_type()],
+                str()
+            ),
+            Value::String("a%20b%26c".to_string())
+        );
+
+        assert_eq!(
+            eval(
+                BuiltInFunction::UrlDecode,
+                vec!["a%20b%26c".into_value_and_type()],
+                option(str())
+            ),
+            Value::Option(Some(Box::new(Value::String("a b&c".to_string()))))
+        );
+    }
+}
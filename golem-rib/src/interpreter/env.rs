@@ -102,6 +102,31 @@ impl InterpreterEnv {
     pub fn lookup(&self, key: &EnvironmentKey) -> Option<&RibInterpreterStackValue> {
         self.env.get(key)
     }
+
+    // A copy of the environment for a branch of a parallel expression, such that
+    // the variables assigned in one branch are not visible to the others.
+    // Iterators and sinks are never assigned to a variable, and are not copied
+    pub(crate) fn fork(&self) -> InterpreterEnv {
+        let env =
+            self.env
+                .iter()
+                .filter_map(|(key, value)| match value {
+                    RibInterpreterStackValue::Unit => {
+                        Some((key.clone(), RibInterpreterStackValue::Unit))
+                    }
+                    RibInterpreterStackValue::Val(value) => {
+                        Some((key.clone(), RibInterpreterStackValue::Val(value.clone())))
+                    }
+                    RibInterpreterStackValue::Iterator(_)
+                    | RibInterpreterStackValue::Sink(_, _) => None,
+                })
+                .collect();
+
+        InterpreterEnv {
+            env,
+            call_worker_function_async: self.call_worker_function_async.clone(),
+        }
+    }
 }
 
 #[derive(Debug, Hash, Eq, PartialEq, Clone)]
@@ -0,0 +1,147 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::interpreter::rib_runtime_error::RibRuntimeError;
+use std::fmt::{Display, Formatter};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+// Limits on the resources a single run of the interpreter can use, such that
+// a Rib script cannot use the resources of its host without bounds.
+// Nothing is limited by default, other than the maximum call depth of the interpreter.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RibExecutionLimits {
+    // Number of instructions executed, including the instructions of parallel branches
+    pub max_instructions: Option<u64>,
+    // Number of nested calls of user-defined functions
+    pub max_stack_depth: Option<usize>,
+    // Number of elements in a list built by a list comprehension,
+    // or in the range or list a parallel list comprehension iterates
+    pub max_list_size: Option<usize>,
+    // Number of calls to worker functions
+    pub max_worker_calls: Option<u64>,
+}
+
+impl RibExecutionLimits {
+    pub fn unlimited() -> Self {
+        RibExecutionLimits::default()
+    }
+
+    pub fn with_max_instructions(mut self, max_instructions: u64) -> Self {
+        self.max_instructions = Some(max_instructions);
+        self
+    }
+
+    pub fn with_max_stack_depth(mut self, max_stack_depth: usize) -> Self {
+        self.max_stack_depth = Some(max_stack_depth);
+        self
+    }
+
+    pub fn with_max_list_size(mut self, max_list_size: usize) -> Self {
+        self.max_list_size = Some(max_list_size);
+        self
+    }
+
+    pub fn with_max_worker_calls(mut self, max_worker_calls: u64) -> Self {
+        self.max_worker_calls = Some(max_worker_calls);
+        self
+    }
+
+    pub(crate) fn check_stack_depth(&self, depth: usize) -> Result<(), RibRuntimeError> {
+        match self.max_stack_depth {
+            Some(max_stack_depth) if depth >= max_stack_depth => Err(
+                RibRuntimeError::LimitExceeded(ExecutionLimit::StackDepth(max_stack_depth)),
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn check_list_size(&self, size: usize) -> Result<(), RibRuntimeError> {
+        match self.max_list_size {
+            Some(max_list_size) if size > max_list_size => Err(RibRuntimeError::LimitExceeded(
+                ExecutionLimit::ListSize(max_list_size),
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+// The limit that was exceeded, along with its configured value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExecutionLimit {
+    Instructions(u64),
+    StackDepth(usize),
+    ListSize(usize),
+    WorkerCalls(u64),
+}
+
+impl Display for ExecutionLimit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExecutionLimit::Instructions(limit) => {
+                write!(f, "more than {limit} instructions executed")
+            }
+            ExecutionLimit::StackDepth(limit) => {
+                write!(f, "function calls nested deeper than {limit}")
+            }
+            ExecutionLimit::ListSize(limit) => {
+                write!(f, "a list of more than {limit} elements")
+            }
+            ExecutionLimit::WorkerCalls(limit) => {
+                write!(f, "more than {limit} worker function calls")
+            }
+        }
+    }
+}
+
+// The resources used by a run of the interpreter so far, shared with its parallel branches
+#[derive(Debug, Default)]
+pub(crate) struct ExecutionUsage {
+    instructions: AtomicU64,
+    worker_calls: AtomicU64,
+}
+
+impl ExecutionUsage {
+    pub(crate) fn reset(&self) {
+        self.instructions.store(0, Ordering::Relaxed);
+        self.worker_calls.store(0, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_instruction(
+        &self,
+        limits: &RibExecutionLimits,
+    ) -> Result<(), RibRuntimeError> {
+        let instructions = self.instructions.fetch_add(1, Ordering::Relaxed) + 1;
+
+        match limits.max_instructions {
+            Some(max_instructions) if instructions > max_instructions => Err(
+                RibRuntimeError::LimitExceeded(ExecutionLimit::Instructions(max_instructions)),
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn record_worker_call(
+        &self,
+        limits: &RibExecutionLimits,
+    ) -> Result<(), RibRuntimeError> {
+        let worker_calls = self.worker_calls.fetch_add(1, Ordering::Relaxed) + 1;
+
+        match limits.max_worker_calls {
+            Some(max_worker_calls) if worker_calls > max_worker_calls => Err(
+                RibRuntimeError::LimitExceeded(ExecutionLimit::WorkerCalls(max_worker_calls)),
+            ),
+            _ => Ok(()),
+        }
+    }
+}
@@ -13,9 +13,12 @@
 // limitations under the License.
 
 use crate::{InstructionId, RibByteCode, RibIR};
+use std::sync::Arc;
 
+// The byte code is shared with the cursors of the branches of parallel expressions
+#[derive(Clone)]
 pub struct RibByteCodeCursor {
-    byte_code: RibByteCode,
+    byte_code: Arc<RibByteCode>,
     position: usize,
 }
 
@@ -32,11 +35,23 @@ impl RibByteCodeCursor {
 
     pub fn from_rib_byte_code(byte_code: RibByteCode) -> RibByteCodeCursor {
         RibByteCodeCursor {
-            byte_code,
+            byte_code: Arc::new(byte_code),
             position: 0,
         }
     }
 
+    // A cursor over the same byte code, positioned right after the label of a branch
+    pub fn branch(&self, label: &InstructionId) -> Option<RibByteCodeCursor> {
+        let mut cursor = self.clone();
+        cursor.move_to(label)?;
+        Some(cursor)
+    }
+
+    // The instruction that will be returned by the next `get_instruction`, without moving the cursor
+    pub fn current_instruction(&self) -> Option<&RibIR> {
+        self.byte_code.instructions.get(self.position)
+    }
+
     pub fn get_instruction(&mut self) -> Option<RibIR> {
         if self.position < self.byte_code.instructions.len() {
             let ir = self.byte_code.instructions[self.position].clone();
@@ -47,6 +62,11 @@ impl RibByteCodeCursor {
         }
     }
 
+    // Moves to an exact position, unlike `move_to` which moves to a label
+    pub fn move_to_position(&mut self, position: &InstructionId) {
+        self.position = position.index;
+    }
+
     pub fn move_to(&mut self, move_to: &InstructionId) -> Option<()> {
         for (index, current_instruction) in self.byte_code.instructions.iter().enumerate() {
             if let Some(label_id) = current_instruction.get_instruction_id() {
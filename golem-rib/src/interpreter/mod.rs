@@ -14,21 +14,26 @@
 
 pub use env::*;
 pub use eval::*;
+pub use execution_limits::*;
 pub use interpreter_input::*;
 pub use interpreter_result::*;
 pub use literal::*;
+pub use rib_debugger::*;
 pub use rib_function_invoke::*;
 pub use rib_interpreter::*;
 pub use rib_runtime_error::*;
 pub use stack::*;
 
+mod builtin_function_eval;
 mod env;
 mod eval;
+mod execution_limits;
 mod instruction_cursor;
 mod interpreter_input;
 mod interpreter_result;
 mod interpreter_stack_value;
 mod literal;
+mod rib_debugger;
 mod rib_function_invoke;
 mod rib_interpreter;
 mod rib_runtime_error;
@@ -42,12 +47,32 @@ pub async fn interpret(
     rib_input: RibInput,
     function_invoke: Arc<dyn RibComponentFunctionInvoke + Sync + Send>,
     generate_worker_name: Option<Arc<dyn GenerateWorkerName + Sync + Send>>,
+) -> Result<RibResult, RibRuntimeError> {
+    interpret_with_limits(
+        rib,
+        rib_input,
+        function_invoke,
+        generate_worker_name,
+        RibExecutionLimits::unlimited(),
+    )
+    .await
+}
+
+// Same as `interpret`, failing with `RibRuntimeError::LimitExceeded`
+// as soon as the script goes beyond any of the given limits
+pub async fn interpret_with_limits(
+    rib: RibByteCode,
+    rib_input: RibInput,
+    function_invoke: Arc<dyn RibComponentFunctionInvoke + Sync + Send>,
+    generate_worker_name: Option<Arc<dyn GenerateWorkerName + Sync + Send>>,
+    limits: RibExecutionLimits,
 ) -> Result<RibResult, RibRuntimeError> {
     let mut interpreter = Interpreter::new(
         rib_input,
         function_invoke,
         generate_worker_name.unwrap_or_else(|| Arc::new(DefaultWorkerNameGenerator)),
-    );
+    )
+    .with_limits(limits);
     interpreter.run(rib).await
 }
 
@@ -58,12 +83,28 @@ pub async fn interpret_pure(
     rib: RibByteCode,
     rib_input: RibInput,
     generate_worker_name: Option<Arc<dyn GenerateWorkerName + Sync + Send>>,
+) -> Result<RibResult, RibRuntimeError> {
+    interpret_pure_with_limits(
+        rib,
+        rib_input,
+        generate_worker_name,
+        RibExecutionLimits::unlimited(),
+    )
+    .await
+}
+
+pub async fn interpret_pure_with_limits(
+    rib: RibByteCode,
+    rib_input: RibInput,
+    generate_worker_name: Option<Arc<dyn GenerateWorkerName + Sync + Send>>,
+    limits: RibExecutionLimits,
 ) -> Result<RibResult, RibRuntimeError> {
     let mut interpreter = Interpreter::pure(
         rib_input,
         generate_worker_name.unwrap_or_else(|| Arc::new(DefaultWorkerNameGenerator)),
-    );
-    interpreter.run(rib.clone()).await
+    )
+    .with_limits(limits);
+    interpreter.run(rib).await
 }
 
 #[macro_export]
@@ -0,0 +1,347 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::interpreter::rib_interpreter::InterpreterState;
+use crate::{
+    InstructionId, Interpreter, InterpreterEnv, InterpreterStack, RibByteCode, RibIR,
+    RibInterpreterResult, RibResult, RibSourceMap, SourceSpan,
+};
+use std::collections::BTreeSet;
+
+/// Runs a `RibByteCode` under the control of the caller, one instruction
+/// or one source line at a time, pausing at the lines with a breakpoint.
+///
+/// The debugger is paused before the first instruction when created.
+/// Lines are the lines of the Rib script the byte code (and its `RibSourceMap`) is compiled from.
+pub struct RibDebugger {
+    interpreter: Interpreter,
+    state: InterpreterState,
+    source_map: RibSourceMap,
+    breakpoints: BTreeSet<i32>,
+    result: Option<RibResult>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RibDebugStatus {
+    // Paused before running the instruction at the position
+    Paused {
+        position: InstructionId,
+        hit_breakpoint: bool,
+    },
+    Finished(RibResult),
+}
+
+impl RibDebugger {
+    pub fn new(
+        interpreter: Interpreter,
+        byte_code: RibByteCode,
+        source_map: RibSourceMap,
+    ) -> RibDebugger {
+        interpreter.usage.reset();
+
+        let state = InterpreterState::new(byte_code, &interpreter.input, &interpreter.invoke);
+
+        RibDebugger {
+            interpreter,
+            state,
+            source_map,
+            breakpoints: BTreeSet::new(),
+            result: None,
+        }
+    }
+
+    // Returns false if there is no instruction at the line,
+    // in which case the breakpoint is not added as it can never be hit
+    pub fn add_breakpoint(&mut self, line: i32) -> bool {
+        if self.source_map.contains_line(line) {
+            self.breakpoints.insert(line);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn remove_breakpoint(&mut self, line: i32) -> bool {
+        self.breakpoints.remove(&line)
+    }
+
+    pub fn breakpoints(&self) -> Vec<i32> {
+        self.breakpoints.iter().copied().collect()
+    }
+
+    pub fn position(&self) -> InstructionId {
+        self.state.byte_code_cursor.position()
+    }
+
+    // The instruction that runs next, which is `None` once the debugger has finished
+    pub fn current_instruction(&self) -> Option<&RibIR> {
+        self.state.byte_code_cursor.current_instruction()
+    }
+
+    pub fn current_source_span(&self) -> Option<&SourceSpan> {
+        self.source_map.source_span(&self.position())
+    }
+
+    pub fn current_line(&self) -> Option<i32> {
+        self.source_map.line(&self.position())
+    }
+
+    // Number of user-defined functions that are being called
+    pub fn call_depth(&self) -> usize {
+        self.state.call_stack.len()
+    }
+
+    pub fn stack(&self) -> &InterpreterStack {
+        &self.state.stack
+    }
+
+    pub fn env(&self) -> &InterpreterEnv {
+        &self.state.interpreter_env
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.result.is_some()
+    }
+
+    pub async fn step_instruction(&mut self) -> RibInterpreterResult<RibDebugStatus> {
+        self.run_until(internal::StopAt::NextInstruction).await
+    }
+
+    // Runs until the execution reaches another line, including the lines
+    // in the body of a user-defined function that is called from the current line
+    pub async fn step(&mut self) -> RibInterpreterResult<RibDebugStatus> {
+        self.run_until(internal::StopAt::NextLine {
+            max_call_depth: None,
+        })
+        .await
+    }
+
+    // Runs until the execution reaches another line, without pausing inside the
+    // user-defined functions called from the current line. A worker function call
+    // is a single instruction, and is therefore always stepped over
+    pub async fn step_over(&mut self) -> RibInterpreterResult<RibDebugStatus> {
+        let max_call_depth = Some(self.call_depth());
+
+        self.run_until(internal::StopAt::NextLine { max_call_depth })
+            .await
+    }
+
+    pub async fn run_to_line(&mut self, line: i32) -> RibInterpreterResult<RibDebugStatus> {
+        if self.current_line() == Some(line) {
+            return Ok(self.status(false));
+        }
+
+        self.run_until(internal::StopAt::Line(line)).await
+    }
+
+    pub async fn resume(&mut self) -> RibInterpreterResult<RibDebugStatus> {
+        self.run_until(internal::StopAt::Breakpoint).await
+    }
+
+    async fn run_until(
+        &mut self,
+        stop_at: internal::StopAt,
+    ) -> RibInterpreterResult<RibDebugStatus> {
+        if self.result.is_some() {
+            return Ok(self.status(false));
+        }
+
+        let start_line = self.current_line();
+
+        loop {
+            let previous_line = self.current_line();
+
+            self.interpreter.step(&mut self.state).await?;
+
+            if self.current_instruction().is_none() {
+                self.result = Some(self.state.result()?);
+                return Ok(self.status(false));
+            }
+
+            let line = self.current_line();
+
+            // A breakpoint pauses the execution only when it enters the line,
+            // and not before every instruction of the line
+            let entered_line = line.is_some() && line != previous_line;
+
+            if entered_line && line.is_some_and(|line| self.breakpoints.contains(&line)) {
+                return Ok(self.status(true));
+            }
+
+            let stop = match stop_at {
+                internal::StopAt::NextInstruction => true,
+                internal::StopAt::NextLine { max_call_depth } => {
+                    entered_line
+                        && line != start_line
+                        && max_call_depth.is_none_or(|depth| self.call_depth() <= depth)
+                }
+                internal::StopAt::Line(stop_line) => entered_line && line == Some(stop_line),
+                internal::StopAt::Breakpoint => false,
+            };
+
+            if stop {
+                return Ok(self.status(false));
+            }
+        }
+    }
+
+    fn status(&self, hit_breakpoint: bool) -> RibDebugStatus {
+        match &self.result {
+            Some(result) => RibDebugStatus::Finished(result.clone()),
+            None => RibDebugStatus::Paused {
+                position: self.position(),
+                hit_breakpoint,
+            },
+        }
+    }
+}
+
+mod internal {
+    #[derive(Clone, Copy)]
+    pub(crate) enum StopAt {
+        NextInstruction,
+        NextLine { max_call_depth: Option<usize> },
+        Line(i32),
+        Breakpoint,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use crate::{
+        Expr, Interpreter, RibByteCode, RibCompiler, RibDebugStatus, RibDebugger, RibIR, RibResult,
+    };
+    use golem_wasm::IntoValueAndType;
+
+    #[test]
+    async fn test_debugger_pauses_at_breakpoint() {
+        let rib_expr = r#"
+               let x: u64 = 1;
+               let y: u64 = x + 2;
+               let z: u64 = y * 3;
+               z
+            "#;
+
+        let mut debugger = internal::debugger(rib_expr);
+
+        assert!(debugger.add_breakpoint(4));
+        assert!(!debugger.add_breakpoint(10));
+
+        let status = debugger.resume().await.unwrap();
+
+        assert!(matches!(
+            status,
+            RibDebugStatus::Paused {
+                hit_breakpoint: true,
+                ..
+            }
+        ));
+        assert_eq!(debugger.current_line(), Some(4));
+        assert_eq!(internal::variable(&debugger, "y"), Some("3".to_string()));
+        assert_eq!(internal::variable(&debugger, "z"), None);
+
+        let status = debugger.resume().await.unwrap();
+
+        assert_eq!(
+            status,
+            RibDebugStatus::Finished(RibResult::Val(9u64.into_value_and_type()))
+        );
+        assert!(debugger.is_finished());
+    }
+
+    #[test]
+    async fn test_debugger_step_instruction() {
+        let rib_expr = r#"
+               let x: u64 = 1;
+               x
+            "#;
+
+        let mut debugger = internal::debugger(rib_expr);
+
+        assert!(matches!(
+            debugger.current_instruction(),
+            Some(RibIR::PushLit(_))
+        ));
+
+        debugger.step_instruction().await.unwrap();
+
+        assert_eq!(debugger.stack().stack.len(), 1);
+        assert!(matches!(
+            debugger.current_instruction(),
+            Some(RibIR::AssignVar(_))
+        ));
+
+        debugger.step_instruction().await.unwrap();
+
+        assert!(debugger.stack().stack.is_empty());
+        assert_eq!(internal::variable(&debugger, "x"), Some("1".to_string()));
+    }
+
+    #[test]
+    async fn test_debugger_step_over_function_call() {
+        let rib_expr = r#"
+               fn double(n: u64) -> u64 {
+                 n * 2
+               };
+               let x: u64 = double(2);
+               let y: u64 = double(x);
+               y
+            "#;
+
+        let mut stepped_over = internal::debugger(rib_expr);
+        let mut stepped_into = internal::debugger(rib_expr);
+
+        stepped_over.run_to_line(5).await.unwrap();
+        stepped_into.run_to_line(5).await.unwrap();
+
+        stepped_over.step_over().await.unwrap();
+        stepped_into.step().await.unwrap();
+
+        assert_eq!(stepped_over.current_line(), Some(6));
+        assert_eq!(stepped_over.call_depth(), 0);
+
+        // Arguments are assigned to the parameters in the function signature, before the body runs
+        assert_eq!(stepped_into.current_line(), Some(2));
+        assert_eq!(stepped_into.call_depth(), 1);
+
+        stepped_into.step().await.unwrap();
+
+        assert_eq!(stepped_into.current_line(), Some(3));
+        assert_eq!(stepped_into.call_depth(), 1);
+    }
+
+    mod internal {
+        use super::*;
+
+        pub(crate) fn debugger(rib_expr: &str) -> RibDebugger {
+            let expr = Expr::from_text(rib_expr).unwrap();
+            let inferred_expr = RibCompiler::default().infer_types(expr).unwrap();
+            let (byte_code, source_map) =
+                RibByteCode::from_expr_with_source_map(&inferred_expr).unwrap();
+
+            RibDebugger::new(Interpreter::default(), byte_code, source_map)
+        }
+
+        pub(crate) fn variable(debugger: &RibDebugger, name: &str) -> Option<String> {
+            debugger
+                .env()
+                .env
+                .iter()
+                .find(|(key, _)| key.variable_id.name() == name)
+                .map(|(_, value)| value.to_string())
+        }
+    }
+}
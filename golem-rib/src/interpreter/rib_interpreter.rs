@@ -14,6 +14,7 @@
 
 use super::interpreter_stack_value::RibInterpreterStackValue;
 use crate::interpreter::env::InterpreterEnv;
+use crate::interpreter::execution_limits::{ExecutionUsage, RibExecutionLimits};
 use crate::interpreter::instruction_cursor::RibByteCodeCursor;
 use crate::interpreter::rib_runtime_error::{
     arithmetic_error, no_result, throw_error, RibRuntimeError,
@@ -23,12 +24,15 @@ use crate::{
     internal_corrupted_state, DefaultWorkerNameGenerator, GenerateWorkerName, RibByteCode,
     RibComponentFunctionInvoke, RibIR, RibInput, RibResult,
 };
+use futures::future::BoxFuture;
 use std::sync::Arc;
 
 pub struct Interpreter {
     pub input: RibInput,
     pub invoke: Arc<dyn RibComponentFunctionInvoke + Sync + Send>,
     pub generate_worker_name: Arc<dyn GenerateWorkerName + Sync + Send>,
+    pub limits: RibExecutionLimits,
+    pub(crate) usage: ExecutionUsage,
 }
 
 impl Default for Interpreter {
@@ -37,6 +41,8 @@ impl Default for Interpreter {
             input: RibInput::default(),
             invoke: Arc::new(internal::NoopRibFunctionInvoke),
             generate_worker_name: Arc::new(DefaultWorkerNameGenerator),
+            limits: RibExecutionLimits::default(),
+            usage: ExecutionUsage::default(),
         }
     }
 }
@@ -53,6 +59,8 @@ impl Interpreter {
             input: input.clone(),
             invoke,
             generate_worker_name,
+            limits: RibExecutionLimits::default(),
+            usage: ExecutionUsage::default(),
         }
     }
 
@@ -66,274 +74,402 @@ impl Interpreter {
             input,
             invoke: Arc::new(internal::NoopRibFunctionInvoke),
             generate_worker_name,
+            limits: RibExecutionLimits::default(),
+            usage: ExecutionUsage::default(),
         }
     }
 
+    pub fn with_limits(mut self, limits: RibExecutionLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
     pub fn override_rib_input(&mut self, rib_input: RibInput) {
         self.input = rib_input;
     }
 
     pub async fn run(&mut self, instructions0: RibByteCode) -> Result<RibResult, RibRuntimeError> {
-        let mut byte_code_cursor = RibByteCodeCursor::from_rib_byte_code(instructions0);
-        let mut stack = InterpreterStack::default();
-
-        let mut interpreter_env = InterpreterEnv::from(&self.input, &self.invoke);
-
-        while let Some(instruction) = byte_code_cursor.get_instruction() {
-            match instruction {
-                RibIR::GenerateWorkerName(instance_count) => {
-                    internal::run_generate_worker_name(
-                        instance_count,
-                        self,
-                        &mut stack,
-                        &mut interpreter_env,
-                    )?;
-                }
+        self.usage.reset();
 
-                RibIR::PushLit(val) => {
-                    stack.push_val(val);
-                }
+        let mut state = InterpreterState::new(instructions0, &self.input, &self.invoke);
 
-                RibIR::PushFlag(val) => {
-                    stack.push_val(val);
-                }
+        while self.step(&mut state).await? {}
 
-                RibIR::CreateAndPushRecord(analysed_type) => {
-                    internal::run_create_record_instruction(analysed_type, &mut stack)?;
-                }
+        state.result()
+    }
 
-                RibIR::UpdateRecord(field_name) => {
-                    internal::run_update_record_instruction(field_name, &mut stack)?;
-                }
+    // Runs the next instruction of the byte code, and returns false if there are no more instructions to run
+    pub(crate) async fn step(&self, state: &mut InterpreterState) -> RibInterpreterResult<bool> {
+        let Some(instruction) = state.byte_code_cursor.get_instruction() else {
+            return Ok(false);
+        };
 
-                RibIR::PushList(analysed_type, arg_size) => {
-                    internal::run_push_list_instruction(arg_size, analysed_type, &mut stack)?;
-                }
+        self.usage.record_instruction(&self.limits)?;
 
-                RibIR::EqualTo => {
-                    internal::run_compare_instruction(&mut stack, |left, right| left == right)?;
-                }
+        let InterpreterState {
+            byte_code_cursor,
+            stack,
+            interpreter_env,
+            call_stack,
+            outer_call_depth,
+        } = state;
 
-                RibIR::GreaterThan => {
-                    internal::run_compare_instruction(&mut stack, |left, right| left > right)?;
-                }
+        match instruction {
+            RibIR::GenerateWorkerName(instance_count) => {
+                internal::run_generate_worker_name(instance_count, self, stack, interpreter_env)?;
+            }
 
-                RibIR::LessThan => {
-                    internal::run_compare_instruction(&mut stack, |left, right| left < right)?;
-                }
+            RibIR::PushLit(val) => {
+                stack.push_val(val);
+            }
 
-                RibIR::GreaterThanOrEqualTo => {
-                    internal::run_compare_instruction(&mut stack, |left, right| left >= right)?;
-                }
+            RibIR::PushFlag(val) => {
+                stack.push_val(val);
+            }
 
-                RibIR::LessThanOrEqualTo => {
-                    internal::run_compare_instruction(&mut stack, |left, right| left <= right)?;
-                }
-                RibIR::Plus(analysed_type) => {
-                    internal::run_math_instruction(
-                        &mut stack,
-                        |left, right| {
-                            let result = left + right;
-                            result.map_err(|err| arithmetic_error(err.as_str()))
-                        },
-                        &analysed_type,
-                    )?;
-                }
-                RibIR::Minus(analysed_type) => {
-                    internal::run_math_instruction(
-                        &mut stack,
-                        |left, right| {
-                            let result = left - right;
-                            result.map_err(|err| arithmetic_error(err.as_str()))
-                        },
-                        &analysed_type,
-                    )?;
-                }
-                RibIR::Divide(analysed_type) => {
-                    internal::run_math_instruction(
-                        &mut stack,
-                        |left, right| {
-                            if right.is_zero() {
-                                Err(arithmetic_error(
-                                    format!("division by zero. left: {left}, right: {right}")
-                                        .as_str(),
-                                ))
-                            } else {
-                                (left / right).map_err(|err| arithmetic_error(err.as_str()))
-                            }
-                        },
-                        &analysed_type,
-                    )?;
-                }
-                RibIR::Multiply(analysed_type) => {
-                    internal::run_math_instruction(
-                        &mut stack,
-                        |left, right| {
-                            let result = left * right;
-                            result.map_err(|err| arithmetic_error(err.as_str()))
-                        },
-                        &analysed_type,
-                    )?;
-                }
+            RibIR::CreateAndPushRecord(analysed_type) => {
+                internal::run_create_record_instruction(analysed_type, stack)?;
+            }
 
-                RibIR::AssignVar(variable_id) => {
-                    internal::run_assign_var_instruction(
-                        variable_id,
-                        &mut stack,
-                        &mut interpreter_env,
-                    )?;
-                }
+            RibIR::UpdateRecord(field_name) => {
+                internal::run_update_record_instruction(field_name, stack)?;
+            }
 
-                RibIR::LoadVar(variable_id) => {
-                    internal::run_load_var_instruction(
-                        variable_id,
-                        &mut stack,
-                        &mut interpreter_env,
-                    )?;
-                }
+            RibIR::PushList(analysed_type, arg_size) => {
+                internal::run_push_list_instruction(arg_size, analysed_type, stack)?;
+            }
 
-                RibIR::IsEmpty => {
-                    internal::run_is_empty_instruction(&mut stack)?;
-                }
+            RibIR::EqualTo => {
+                internal::run_compare_instruction(stack, |left, right| left == right)?;
+            }
 
-                RibIR::JumpIfFalse(instruction_id) => {
-                    internal::run_jump_if_false_instruction(
-                        instruction_id,
-                        &mut byte_code_cursor,
-                        &mut stack,
-                    )?;
-                }
+            RibIR::GreaterThan => {
+                internal::run_compare_instruction(stack, |left, right| left > right)?;
+            }
 
-                RibIR::SelectField(field_name) => {
-                    internal::run_select_field_instruction(field_name, &mut stack)?;
-                }
+            RibIR::LessThan => {
+                internal::run_compare_instruction(stack, |left, right| left < right)?;
+            }
 
-                RibIR::SelectIndex(index) => {
-                    internal::run_select_index_instruction(&mut stack, index)?;
-                }
+            RibIR::GreaterThanOrEqualTo => {
+                internal::run_compare_instruction(stack, |left, right| left >= right)?;
+            }
 
-                RibIR::SelectIndexV1 => {
-                    internal::run_select_index_v1_instruction(&mut stack)?;
-                }
+            RibIR::LessThanOrEqualTo => {
+                internal::run_compare_instruction(stack, |left, right| left <= right)?;
+            }
+            RibIR::Plus(analysed_type) => {
+                internal::run_math_instruction(
+                    stack,
+                    |left, right| {
+                        let result = left + right;
+                        result.map_err(|err| arithmetic_error(err.as_str()))
+                    },
+                    &analysed_type,
+                )?;
+            }
+            RibIR::Minus(analysed_type) => {
+                internal::run_math_instruction(
+                    stack,
+                    |left, right| {
+                        let result = left - right;
+                        result.map_err(|err| arithmetic_error(err.as_str()))
+                    },
+                    &analysed_type,
+                )?;
+            }
+            RibIR::Divide(analysed_type) => {
+                internal::run_math_instruction(
+                    stack,
+                    |left, right| {
+                        if right.is_zero() {
+                            Err(arithmetic_error(
+                                format!("division by zero. left: {left}, right: {right}").as_str(),
+                            ))
+                        } else {
+                            (left / right).map_err(|err| arithmetic_error(err.as_str()))
+                        }
+                    },
+                    &analysed_type,
+                )?;
+            }
+            RibIR::Multiply(analysed_type) => {
+                internal::run_math_instruction(
+                    stack,
+                    |left, right| {
+                        let result = left * right;
+                        result.map_err(|err| arithmetic_error(err.as_str()))
+                    },
+                    &analysed_type,
+                )?;
+            }
 
-                RibIR::CreateFunctionName(site, function_type) => {
-                    internal::run_create_function_name_instruction(
-                        site,
-                        function_type,
-                        &mut stack,
-                    )?;
-                }
+            RibIR::AssignVar(variable_id) => {
+                internal::run_assign_var_instruction(variable_id, stack, interpreter_env)?;
+            }
+
+            RibIR::LoadVar(variable_id) => {
+                internal::run_load_var_instruction(variable_id, stack, interpreter_env)?;
+            }
+
+            RibIR::IsEmpty => {
+                internal::run_is_empty_instruction(stack)?;
+            }
+
+            RibIR::JumpIfFalse(instruction_id) => {
+                internal::run_jump_if_false_instruction(instruction_id, byte_code_cursor, stack)?;
+            }
+
+            RibIR::SelectField(field_name) => {
+                internal::run_select_field_instruction(field_name, stack)?;
+            }
+
+            RibIR::SelectIndex(index) => {
+                internal::run_select_index_instruction(stack, index)?;
+            }
+
+            RibIR::SelectIndexV1 => {
+                internal::run_select_index_v1_instruction(stack)?;
+            }
+
+            RibIR::CreateFunctionName(site, function_type) => {
+                internal::run_create_function_name_instruction(site, function_type, stack)?;
+            }
+
+            RibIR::InvokeFunction(
+                component_info,
+                instance_variable,
+                arg_size,
+                expected_result_type,
+            ) => {
+                self.usage.record_worker_call(&self.limits)?;
 
-                RibIR::InvokeFunction(
+                internal::run_invoke_function_instruction(
                     component_info,
-                    instance_variable,
+                    &byte_code_cursor.position(),
                     arg_size,
+                    instance_variable,
+                    stack,
+                    interpreter_env,
                     expected_result_type,
-                ) => {
-                    internal::run_invoke_function_instruction(
-                        component_info,
-                        &byte_code_cursor.position(),
-                        arg_size,
-                        instance_variable,
-                        &mut stack,
-                        &mut interpreter_env,
-                        expected_result_type,
-                    )
+                )
+                .await?;
+            }
+
+            RibIR::PushVariant(variant_name, analysed_type) => {
+                internal::run_variant_construction_instruction(variant_name, analysed_type, stack)
                     .await?;
-                }
+            }
+
+            RibIR::PushEnum(enum_name, analysed_type) => {
+                internal::run_push_enum_instruction(stack, enum_name, analysed_type)?;
+            }
+
+            RibIR::Throw(message) => {
+                return Err(throw_error(message.as_str()));
+            }
 
-                RibIR::PushVariant(variant_name, analysed_type) => {
-                    internal::run_variant_construction_instruction(
-                        variant_name,
-                        analysed_type,
-                        &mut stack,
+            RibIR::GetTag => {
+                internal::run_get_tag_instruction(stack)?;
+            }
+
+            RibIR::Deconstruct => {
+                internal::run_deconstruct_instruction(stack)?;
+            }
+
+            RibIR::Jump(instruction_id) => {
+                byte_code_cursor.move_to(&instruction_id).ok_or_else(|| {
+                    internal_corrupted_state!(
+                        "internal error. Failed to move to label {}",
+                        instruction_id.index
                     )
-                    .await?;
-                }
+                })?;
+            }
 
-                RibIR::PushEnum(enum_name, analysed_type) => {
-                    internal::run_push_enum_instruction(&mut stack, enum_name, analysed_type)?;
-                }
+            RibIR::PushSome(analysed_type) => {
+                internal::run_create_some_instruction(stack, analysed_type)?;
+            }
+            RibIR::PushNone(analysed_type) => {
+                internal::run_create_none_instruction(stack, analysed_type)?;
+            }
+            RibIR::PushOkResult(analysed_type) => {
+                internal::run_create_ok_instruction(stack, analysed_type)?;
+            }
+            RibIR::PushErrResult(analysed_type) => {
+                internal::run_create_err_instruction(stack, analysed_type)?;
+            }
+            RibIR::Concat(arg_size) => {
+                internal::run_concat_instruction(stack, arg_size)?;
+            }
+            RibIR::PushTuple(analysed_type, arg_size) => {
+                internal::run_push_tuple_instruction(arg_size, analysed_type, stack)?;
+            }
+            RibIR::Negate => {
+                internal::run_negate_instruction(stack)?;
+            }
 
-                RibIR::Throw(message) => {
-                    return Err(throw_error(message.as_str()));
-                }
+            RibIR::Label(_) => {}
 
-                RibIR::GetTag => {
-                    internal::run_get_tag_instruction(&mut stack)?;
-                }
+            RibIR::And => {
+                internal::run_and_instruction(stack)?;
+            }
 
-                RibIR::Deconstruct => {
-                    internal::run_deconstruct_instruction(&mut stack)?;
-                }
+            RibIR::Or => {
+                internal::run_or_instruction(stack)?;
+            }
+            RibIR::ToIterator => {
+                internal::run_to_iterator(stack)?;
+            }
+            RibIR::CreateSink(analysed_type) => {
+                internal::run_create_sink_instruction(stack, analysed_type)?
+            }
+            RibIR::AdvanceIterator => {
+                internal::run_advance_iterator_instruction(stack)?;
+            }
+            RibIR::PushToSink => {
+                internal::run_push_to_sink_instruction(stack, &self.limits)?;
+            }
 
-                RibIR::Jump(instruction_id) => {
-                    byte_code_cursor.move_to(&instruction_id).ok_or_else(|| {
-                        internal_corrupted_state!(
-                            "internal error. Failed to move to label {}",
-                            instruction_id.index
-                        )
-                    })?;
-                }
+            RibIR::SinkToList => {
+                internal::run_sink_to_list_instruction(stack)?;
+            }
 
-                RibIR::PushSome(analysed_type) => {
-                    internal::run_create_some_instruction(&mut stack, analysed_type)?;
-                }
-                RibIR::PushNone(analysed_type) => {
-                    internal::run_create_none_instruction(&mut stack, analysed_type)?;
-                }
-                RibIR::PushOkResult(analysed_type) => {
-                    internal::run_create_ok_instruction(&mut stack, analysed_type)?;
-                }
-                RibIR::PushErrResult(analysed_type) => {
-                    internal::run_create_err_instruction(&mut stack, analysed_type)?;
-                }
-                RibIR::Concat(arg_size) => {
-                    internal::run_concat_instruction(&mut stack, arg_size)?;
-                }
-                RibIR::PushTuple(analysed_type, arg_size) => {
-                    internal::run_push_tuple_instruction(arg_size, analysed_type, &mut stack)?;
-                }
-                RibIR::Negate => {
-                    internal::run_negate_instruction(&mut stack)?;
-                }
+            RibIR::Length => {
+                internal::run_length_instruction(stack)?;
+            }
 
-                RibIR::Label(_) => {}
+            RibIR::CallBuiltIn(builtin_function, return_type) => {
+                internal::run_call_builtin_instruction(builtin_function, return_type, stack)?;
+            }
 
-                RibIR::And => {
-                    internal::run_and_instruction(&mut stack)?;
-                }
+            RibIR::CallFunction(function_name, instruction_id) => {
+                self.limits
+                    .check_stack_depth(*outer_call_depth + call_stack.len())?;
+
+                internal::run_call_function_instruction(
+                    function_name,
+                    instruction_id,
+                    &self.input,
+                    byte_code_cursor,
+                    call_stack,
+                    *outer_call_depth,
+                    interpreter_env,
+                )?;
+            }
 
-                RibIR::Or => {
-                    internal::run_or_instruction(&mut stack)?;
-                }
-                RibIR::ToIterator => {
-                    internal::run_to_iterator(&mut stack)?;
-                }
-                RibIR::CreateSink(analysed_type) => {
-                    internal::run_create_sink_instruction(&mut stack, analysed_type)?
-                }
-                RibIR::AdvanceIterator => {
-                    internal::run_advance_iterator_instruction(&mut stack)?;
-                }
-                RibIR::PushToSink => {
-                    internal::run_push_to_sink_instruction(&mut stack)?;
-                }
+            RibIR::Return => {
+                internal::run_return_instruction(byte_code_cursor, call_stack, interpreter_env)?;
+            }
+
+            RibIR::ParallelCall(branch_labels, max_concurrency) => {
+                internal::run_parallel_call_instruction(
+                    self,
+                    branch_labels,
+                    max_concurrency,
+                    byte_code_cursor,
+                    *outer_call_depth + call_stack.len(),
+                    stack,
+                    interpreter_env,
+                )
+                .await?;
+            }
 
-                RibIR::SinkToList => {
-                    internal::run_sink_to_list_instruction(&mut stack)?;
+            RibIR::ParallelMap(branch_label, variable_id, list_type, max_concurrency) => {
+                internal::run_parallel_map_instruction(
+                    self,
+                    branch_label,
+                    variable_id,
+                    list_type,
+                    max_concurrency,
+                    byte_code_cursor,
+                    *outer_call_depth + call_stack.len(),
+                    stack,
+                    interpreter_env,
+                )
+                .await?;
+            }
+        }
+
+        Ok(true)
+    }
+
+    // Runs a branch of a parallel expression until the `Return` that ends it, and returns its result.
+    // The future is boxed, as a branch can have parallel expressions of its own
+    pub(crate) fn run_branch(
+        &self,
+        mut state: InterpreterState,
+    ) -> BoxFuture<'_, RibInterpreterResult<RibInterpreterStackValue>> {
+        Box::pin(async move {
+            loop {
+                let end_of_branch = state.call_stack.is_empty()
+                    && matches!(
+                        state.byte_code_cursor.current_instruction(),
+                        Some(RibIR::Return)
+                    );
+
+                if end_of_branch {
+                    return Ok(state.stack.pop().unwrap_or(RibInterpreterStackValue::Unit));
                 }
 
-                RibIR::Length => {
-                    internal::run_length_instruction(&mut stack)?;
+                if !self.step(&mut state).await? {
+                    return Err(internal_corrupted_state!(
+                        "internal error. A parallel branch ended without a return"
+                    ));
                 }
             }
+        })
+    }
+}
+
+// The state of a single run of a byte code, kept apart from the `Interpreter`
+// such that the run can be advanced one instruction at a time
+pub(crate) struct InterpreterState {
+    pub(crate) byte_code_cursor: RibByteCodeCursor,
+    pub(crate) stack: InterpreterStack,
+    pub(crate) interpreter_env: InterpreterEnv,
+    // Frames of the user-defined functions that are being called
+    pub(crate) call_stack: Vec<internal::CallFrame>,
+    // Number of user-defined functions being called when a branch of a parallel expression started,
+    // which counts towards the maximum call depth within the branch
+    pub(crate) outer_call_depth: usize,
+}
+
+impl InterpreterState {
+    pub(crate) fn new(
+        byte_code: RibByteCode,
+        input: &RibInput,
+        invoke: &Arc<dyn RibComponentFunctionInvoke + Sync + Send>,
+    ) -> Self {
+        InterpreterState {
+            byte_code_cursor: RibByteCodeCursor::from_rib_byte_code(byte_code),
+            stack: InterpreterStack::default(),
+            interpreter_env: InterpreterEnv::from(input, invoke),
+            call_stack: vec![],
+            outer_call_depth: 0,
+        }
+    }
+
+    // The state of a branch of a parallel expression, which starts with an empty stack
+    pub(crate) fn branch(
+        byte_code_cursor: RibByteCodeCursor,
+        interpreter_env: InterpreterEnv,
+        outer_call_depth: usize,
+    ) -> Self {
+        InterpreterState {
+            byte_code_cursor,
+            stack: InterpreterStack::default(),
+            interpreter_env,
+            call_stack: vec![],
+            outer_call_depth,
         }
+    }
 
-        match byte_code_cursor.last() {
+    pub(crate) fn result(&mut self) -> Result<RibResult, RibRuntimeError> {
+        match self.byte_code_cursor.last() {
             Some(RibIR::AssignVar(_)) => Ok(RibResult::Unit),
             _ => {
-                let stack_value = stack
+                let stack_value = self
+                    .stack
                     .pop()
                     .unwrap_or_else(|| RibInterpreterStackValue::Unit);
 
@@ -346,23 +482,27 @@ impl Interpreter {
 }
 
 mod internal {
+    use crate::interpreter::builtin_function_eval::evaluate_builtin_function;
     use crate::interpreter::env::{EnvironmentKey, InterpreterEnv};
+    use crate::interpreter::execution_limits::RibExecutionLimits;
     use crate::interpreter::interpreter_stack_value::RibInterpreterStackValue;
     use crate::interpreter::literal::LiteralValue;
     use crate::interpreter::stack::InterpreterStack;
     use crate::{
-        bail_corrupted_state, internal_corrupted_state, AnalysedTypeWithUnit, CoercedNumericValue,
-        ComponentDependencyKey, EvaluatedFnArgs, EvaluatedFqFn, EvaluatedWorkerName,
-        FunctionReferenceType, GetLiteralValue, InstanceVariable, InstructionId, Interpreter,
-        ParsedFunctionName, ParsedFunctionReference, ParsedFunctionSite,
-        RibComponentFunctionInvoke, RibFunctionInvokeResult, RibInterpreterResult, TypeHint,
-        VariableId,
+        bail_corrupted_state, internal_corrupted_state, AnalysedTypeWithUnit, BuiltInFunction,
+        CoercedNumericValue, ComponentDependencyKey, EvaluatedFnArgs, EvaluatedFqFn,
+        EvaluatedWorkerName, FunctionReferenceType, GetLiteralValue, InstanceVariable,
+        InstructionId, Interpreter, ParsedFunctionName, ParsedFunctionReference,
+        ParsedFunctionSite, RibComponentFunctionInvoke, RibFunctionInvokeResult, RibInput,
+        RibInterpreterResult, TypeHint, VariableId,
     };
     use golem_wasm::analysis::AnalysedType;
     use golem_wasm::analysis::TypeResult;
     use golem_wasm::{IntoValueAndType, Value, ValueAndType};
+    use std::collections::HashMap;
 
     use crate::interpreter::instruction_cursor::RibByteCodeCursor;
+    use crate::interpreter::rib_interpreter::InterpreterState;
     use crate::interpreter::rib_runtime_error::{
         cast_error_custom, empty_stack, exhausted_iterator, field_not_found, function_invoke_fail,
         index_out_of_bound, infinite_computation, input_not_found, instruction_jump_error,
@@ -371,6 +511,7 @@ mod internal {
     };
     use crate::type_inference::GetTypeHint;
     use async_trait::async_trait;
+    use futures::{stream, StreamExt, TryStreamExt};
     use golem_wasm::analysis::analysed_type::{s16, s32, s64, s8, str, u16, u32, u64, u8};
     use std::ops::Deref;
 
@@ -636,13 +777,14 @@ mod internal {
 
     pub(crate) fn run_push_to_sink_instruction(
         interpreter_stack: &mut InterpreterStack,
+        limits: &RibExecutionLimits,
     ) -> RibInterpreterResult<()> {
         let last_value = interpreter_stack.pop_val();
         match last_value {
             Some(val) => {
-                interpreter_stack.push_to_sink(val)?;
+                let sink_size = interpreter_stack.push_to_sink(val)?;
 
-                Ok(())
+                limits.check_list_size(sink_size)
             }
             None => Ok(()),
         }
@@ -684,6 +826,217 @@ mod internal {
         Ok(())
     }
 
+    pub(crate) fn run_call_builtin_instruction(
+        builtin_function: BuiltInFunction,
+        return_type: AnalysedType,
+        interpreter_stack: &mut InterpreterStack,
+    ) -> RibInterpreterResult<()> {
+        let args = interpreter_stack.try_pop_n_val(builtin_function.arity())?;
+
+        let result = evaluate_builtin_function(&builtin_function, args, &return_type)?;
+
+        interpreter_stack.push_val(result);
+        Ok(())
+    }
+
+    // A user-defined function that calls itself more than this is considered
+    // to be an infinite recursion
+    pub(crate) const MAX_CALL_DEPTH: usize = 1000;
+
+    pub(crate) struct CallFrame {
+        return_position: InstructionId,
+        caller_env: HashMap<EnvironmentKey, RibInterpreterStackValue>,
+    }
+
+    // The body of a function runs in its own scope, where only the
+    // global inputs and the arguments (assigned by the function itself) are available
+    pub(crate) fn run_call_function_instruction(
+        function_name: String,
+        instruction_id: InstructionId,
+        rib_input: &RibInput,
+        byte_code_cursor: &mut RibByteCodeCursor,
+        call_stack: &mut Vec<CallFrame>,
+        outer_call_depth: usize,
+        interpreter_env: &mut InterpreterEnv,
+    ) -> RibInterpreterResult<()> {
+        if outer_call_depth + call_stack.len() >= MAX_CALL_DEPTH {
+            return Err(infinite_computation(&format!(
+                "maximum call depth of {MAX_CALL_DEPTH} exceeded when calling `{function_name}`. make sure recursive functions terminate"
+            )));
+        }
+
+        let function_env = InterpreterEnv::from_input(rib_input).env;
+        let caller_env = std::mem::replace(&mut interpreter_env.env, function_env);
+
+        call_stack.push(CallFrame {
+            return_position: byte_code_cursor.position(),
+            caller_env,
+        });
+
+        byte_code_cursor.move_to(&instruction_id).ok_or_else(|| {
+            internal_corrupted_state!(
+                "internal error. Failed to move to the definition of function {}",
+                function_name
+            )
+        })
+    }
+
+    pub(crate) fn run_return_instruction(
+        byte_code_cursor: &mut RibByteCodeCursor,
+        call_stack: &mut Vec<CallFrame>,
+        interpreter_env: &mut InterpreterEnv,
+    ) -> RibInterpreterResult<()> {
+        let call_frame = call_stack.pop().ok_or_else(|| {
+            internal_corrupted_state!("internal error. Return instruction outside of a function")
+        })?;
+
+        interpreter_env.env = call_frame.caller_env;
+        byte_code_cursor.move_to_position(&call_frame.return_position);
+
+        Ok(())
+    }
+
+    // Each branch runs in a copy of the environment, and the results are pushed
+    // such that the result of the first branch is on the top of the stack, as for a tuple
+    pub(crate) async fn run_parallel_call_instruction(
+        interpreter: &Interpreter,
+        branch_labels: Vec<InstructionId>,
+        max_concurrency: Option<u64>,
+        byte_code_cursor: &RibByteCodeCursor,
+        call_depth: usize,
+        interpreter_stack: &mut InterpreterStack,
+        interpreter_env: &InterpreterEnv,
+    ) -> RibInterpreterResult<()> {
+        let branches = branch_labels
+            .iter()
+            .map(|branch_label| {
+                let branch_cursor = byte_code_cursor.branch(branch_label).ok_or_else(|| {
+                    internal_corrupted_state!(
+                        "internal error. Failed to move to parallel branch {}",
+                        branch_label.index
+                    )
+                })?;
+
+                Ok(InterpreterState::branch(
+                    branch_cursor,
+                    interpreter_env.fork(),
+                    call_depth,
+                ))
+            })
+            .collect::<RibInterpreterResult<Vec<_>>>()?;
+
+        let results = run_branches(interpreter, branches.into_iter(), max_concurrency).await?;
+
+        for result in results.into_iter().rev() {
+            interpreter_stack.push(result);
+        }
+
+        Ok(())
+    }
+
+    // The branch runs once for each element of the iterable on top of the stack,
+    // with the element assigned to the iterated variable in its copy of the environment
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) async fn run_parallel_map_instruction(
+        interpreter: &Interpreter,
+        branch_label: InstructionId,
+        variable_id: VariableId,
+        list_type: AnalysedType,
+        max_concurrency: Option<u64>,
+        byte_code_cursor: &RibByteCodeCursor,
+        call_depth: usize,
+        interpreter_stack: &mut InterpreterStack,
+        interpreter_env: &InterpreterEnv,
+    ) -> RibInterpreterResult<()> {
+        let item_type = match list_type {
+            AnalysedType::List(type_list) => *type_list.inner,
+            _ => {
+                bail_corrupted_state!("expecting a list type to run a parallel list comprehension")
+            }
+        };
+
+        let branch_cursor = byte_code_cursor.branch(&branch_label).ok_or_else(|| {
+            internal_corrupted_state!(
+                "internal error. Failed to move to parallel branch {}",
+                branch_label.index
+            )
+        })?;
+
+        run_to_iterator(interpreter_stack)?;
+
+        // Taking one more item than allowed is enough to know the limit is exceeded,
+        // without going through the whole of a large range
+        let items = match interpreter_stack.pop() {
+            Some(RibInterpreterStackValue::Iterator(items)) => {
+                match interpreter.limits.max_list_size {
+                    Some(max_list_size) => items.take(max_list_size + 1).collect::<Vec<_>>(),
+                    None => items.collect::<Vec<_>>(),
+                }
+            }
+            _ => bail_corrupted_state!(
+                "failed to iterate the elements of a parallel list comprehension"
+            ),
+        };
+
+        interpreter.limits.check_list_size(items.len())?;
+
+        // The state of a branch is only created once it is about to run
+        let branches = items.into_iter().map(|item| {
+            let mut branch_env = interpreter_env.fork();
+            branch_env.insert(
+                EnvironmentKey::from(variable_id.clone()),
+                RibInterpreterStackValue::Val(item),
+            );
+
+            InterpreterState::branch(branch_cursor.clone(), branch_env, call_depth)
+        });
+
+        let results = run_branches(interpreter, branches, max_concurrency).await?;
+
+        let values = results
+            .iter()
+            .map(|result| {
+                result.get_val().map(|value| value.value).ok_or_else(|| {
+                    internal_corrupted_state!(
+                        "expected a value from each iteration of a parallel list comprehension"
+                    )
+                })
+            })
+            .collect::<RibInterpreterResult<Vec<_>>>()?;
+
+        interpreter_stack.push_list(values, &item_type);
+
+        Ok(())
+    }
+
+    // Runs the branches concurrently, with at most `max_concurrency` of them running at a time.
+    // The first branch to fail cancels the branches that are still running
+    async fn run_branches(
+        interpreter: &Interpreter,
+        branches: impl ExactSizeIterator<Item = InterpreterState> + Send,
+        max_concurrency: Option<u64>,
+    ) -> RibInterpreterResult<Vec<RibInterpreterStackValue>> {
+        let max_concurrency = max_concurrency
+            .map(|max_concurrency| max_concurrency as usize)
+            .unwrap_or(branches.len())
+            .max(1);
+
+        let mut results = stream::iter(branches.enumerate())
+            .map(|(index, branch)| async move {
+                interpreter
+                    .run_branch(branch)
+                    .await
+                    .map(|result| (index, result))
+            })
+            .buffer_unordered(max_concurrency)
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        results.sort_by_key(|(index, _)| *index);
+
+        Ok(results.into_iter().map(|(_, result)| result).collect())
+    }
+
     pub(crate) fn run_assign_var_instruction(
         variable_id: VariableId,
         interpreter_stack: &mut InterpreterStack,
@@ -724,7 +1077,7 @@ mod internal {
 
     pub(crate) fn run_generate_worker_name(
         variable_id: Option<VariableId>,
-        interpreter: &mut Interpreter,
+        interpreter: &Interpreter,
         interpreter_stack: &mut InterpreterStack,
         interpreter_env: &mut InterpreterEnv,
     ) -> RibInterpreterResult<()> {
@@ -1529,8 +1882,8 @@ mod tests {
         get_analysed_type_variant, get_value_and_type, strip_spaces, RibTestDeps,
     };
     use crate::{
-        CustomInstanceSpec, Expr, GlobalVariableTypeSpec, InferredType, InstructionId,
-        InterfaceName, Path, RibCompiler, RibCompilerConfig, VariableId,
+        CustomInstanceSpec, ExecutionLimit, Expr, GlobalVariableTypeSpec, InferredType,
+        InstructionId, InterfaceName, Path, RibCompiler, RibCompilerConfig, VariableId,
     };
     use golem_wasm::analysis::analysed_type::{
         bool, case, f32, field, list, option, r#enum, record, result, result_err, result_ok, s32,
@@ -1781,6 +2134,217 @@ mod tests {
         assert_eq!(result.get_val().unwrap(), 3i32.into_value_and_type());
     }
 
+    #[test]
+    async fn test_interpreter_builtin_functions() {
+        let rib_expr = r#"
+               let csv = "  Foo,Bar,Baz  ";
+               let parts = split(trim(csv), ",");
+               let upper = for p in parts {
+                 yield to-upper(p);
+               };
+               let count = unwrap-or(parse-u64("2"), 0);
+               { joined: join(upper, "-"), count: max(count, len(parts)), found: contains(parts, "Bar") }
+            "#;
+
+        let expr = Expr::from_text(rib_expr).unwrap();
+
+        let mut interpreter = Interpreter::default();
+
+        let compiler = RibCompiler::default();
+
+        let compiled = compiler.compile(expr).unwrap();
+
+        assert!(compiled.worker_invoke_calls.is_none());
+
+        let result = interpreter.run(compiled.byte_code).await.unwrap();
+
+        let analysed_type = record(vec![
+            field("joined", str()),
+            field("count", u64()),
+            field("found", bool()),
+        ]);
+
+        let expected = get_value_and_type(
+            &analysed_type,
+            r#"{ joined: "FOO-BAR-BAZ", count: 3, found: true }"#,
+        );
+
+        assert_eq!(result.get_val().unwrap(), expected);
+    }
+
+    #[test]
+    async fn test_interpreter_user_defined_functions() {
+        let rib_expr = r#"
+               fn factorial(n: u64) -> u64 {
+                 if n == 0 then 1 else n * factorial(n - 1)
+               };
+               fn double(x: u64) -> u64 { x * 2 };
+               let x: u64 = 100;
+               { fact: factorial(5), doubled: double(factorial(3)), x: x }
+            "#;
+
+        let expr = Expr::from_text(rib_expr).unwrap();
+
+        let mut interpreter = Interpreter::default();
+
+        let compiler = RibCompiler::default();
+
+        let compiled = compiler.compile(expr).unwrap();
+
+        let result = interpreter.run(compiled.byte_code).await.unwrap();
+
+        let analysed_type = record(vec![
+            field("fact", u64()),
+            field("doubled", u64()),
+            field("x", u64()),
+        ]);
+
+        let expected = get_value_and_type(&analysed_type, r#"{ fact: 120, doubled: 12, x: 100 }"#);
+
+        assert_eq!(result.get_val().unwrap(), expected);
+    }
+
+    #[test]
+    async fn test_interpreter_user_defined_function_infinite_recursion() {
+        let rib_expr = r#"
+               fn forever(n: u64) -> u64 { forever(n + 1) };
+               forever(1)
+            "#;
+
+        let expr = Expr::from_text(rib_expr).unwrap();
+
+        let mut interpreter = Interpreter::default();
+
+        let compiler = RibCompiler::default();
+
+        let compiled = compiler.compile(expr).unwrap();
+
+        let result = interpreter.run(compiled.byte_code).await;
+
+        assert!(matches!(
+            result,
+            Err(RibRuntimeError::InfiniteComputation { message }) if message.contains("maximum call depth")
+        ));
+    }
+
+    #[test]
+    async fn test_interpreter_max_stack_depth_exceeded() {
+        let rib_expr = r#"
+               fn forever(n: u64) -> u64 { forever(n + 1) };
+               forever(1)
+            "#;
+
+        let expr = Expr::from_text(rib_expr).unwrap();
+
+        let mut interpreter = Interpreter::default()
+            .with_limits(RibExecutionLimits::default().with_max_stack_depth(10));
+
+        let compiler = RibCompiler::default();
+
+        let compiled = compiler.compile(expr).unwrap();
+
+        let result = interpreter.run(compiled.byte_code).await;
+
+        assert!(matches!(
+            result,
+            Err(RibRuntimeError::LimitExceeded(ExecutionLimit::StackDepth(
+                10
+            )))
+        ));
+    }
+
+    #[test]
+    async fn test_interpreter_max_instructions_exceeded() {
+        let rib_expr = r#"
+              let range = 1..=1000;
+              for i in range {
+                yield i;
+              }
+            "#;
+
+        let expr = Expr::from_text(rib_expr).unwrap();
+
+        let compiler = RibCompiler::default();
+
+        let compiled = compiler.compile(expr).unwrap();
+
+        let mut interpreter = Interpreter::default()
+            .with_limits(RibExecutionLimits::default().with_max_instructions(100));
+
+        let result = interpreter.run(compiled.byte_code.clone()).await;
+
+        assert!(matches!(
+            result,
+            Err(RibRuntimeError::LimitExceeded(
+                ExecutionLimit::Instructions(100)
+            ))
+        ));
+
+        // The count starts over with every run
+        let mut interpreter = Interpreter::default()
+            .with_limits(RibExecutionLimits::default().with_max_instructions(100_000));
+
+        assert!(interpreter.run(compiled.byte_code.clone()).await.is_ok());
+        assert!(interpreter.run(compiled.byte_code).await.is_ok());
+    }
+
+    #[test]
+    async fn test_interpreter_max_list_size_exceeded() {
+        let rib_expr = r#"
+              let range = 1..=10;
+              for i in range {
+                yield i;
+              }
+            "#;
+
+        let expr = Expr::from_text(rib_expr).unwrap();
+
+        let compiler = RibCompiler::default();
+
+        let compiled = compiler.compile(expr).unwrap();
+
+        let mut interpreter =
+            Interpreter::default().with_limits(RibExecutionLimits::default().with_max_list_size(5));
+
+        let result = interpreter.run(compiled.byte_code).await;
+
+        assert!(matches!(
+            result,
+            Err(RibRuntimeError::LimitExceeded(ExecutionLimit::ListSize(5)))
+        ));
+    }
+
+    #[test]
+    async fn test_interpreter_lambdas_in_list_operations() {
+        let rib_expr = r#"
+               let numbers: list<u64> = [1, 2, 3, 4];
+               let offset: u64 = 10;
+               { shifted: map(numbers, |x| x + offset), large: filter(numbers, |x| x > 2) }
+            "#;
+
+        let expr = Expr::from_text(rib_expr).unwrap();
+
+        let mut interpreter = Interpreter::default();
+
+        let compiler = RibCompiler::default();
+
+        let compiled = compiler.compile(expr).unwrap();
+
+        let result = interpreter.run(compiled.byte_code).await.unwrap();
+
+        let analysed_type = record(vec![
+            field("shifted", list(u64())),
+            field("large", list(u64())),
+        ]);
+
+        let expected = get_value_and_type(
+            &analysed_type,
+            r#"{ shifted: [11, 12, 13, 14], large: [3, 4] }"#,
+        );
+
+        assert_eq!(result.get_val().unwrap(), expected);
+    }
+
     #[test]
     async fn test_interpreter_variable_scope_1() {
         let rib_expr = r#"
@@ -3752,6 +4316,112 @@ mod tests {
         assert_eq!(result.get_val().unwrap().value, expected_val);
     }
 
+    #[test]
+    async fn test_interpreter_parallel_tuple() {
+        let expr = r#"
+                let worker1 = instance("worker-1");
+                let worker2 = instance("worker-2");
+                parallel { worker1.pass-through(1, 2), worker2.pass-through(3, 4) }
+            "#;
+        let expr = Expr::from_text(expr).unwrap();
+        let test_deps = RibTestDeps::test_deps_for_pass_through_function();
+
+        let compiler_config =
+            RibCompilerConfig::new(test_deps.component_dependencies.clone(), vec![], vec![]);
+        let compiler = RibCompiler::new(compiler_config);
+        let compiled = compiler.compile(expr).unwrap();
+
+        let mut rib_interpreter = test_deps.interpreter;
+
+        let result = rib_interpreter.run(compiled.byte_code).await.unwrap();
+
+        let expected_val = Value::Tuple(vec![
+            Value::Record(vec![
+                Value::String("worker-1".to_string()),
+                Value::String("pass-through".to_string()),
+                Value::U64(1),
+                Value::U32(2),
+            ]),
+            Value::Record(vec![
+                Value::String("worker-2".to_string()),
+                Value::String("pass-through".to_string()),
+                Value::U64(3),
+                Value::U32(4),
+            ]),
+        ]);
+
+        assert_eq!(result.get_val().unwrap().value, expected_val);
+    }
+
+    #[test]
+    async fn test_interpreter_parallel_list_comprehension() {
+        let expr = r#"
+                let tenants = ["tenant-1", "tenant-2", "tenant-3"];
+                parallel(2) for tenant in tenants {
+                  let worker = instance(tenant);
+                  yield worker.pass-through(42, 43);
+                }
+            "#;
+        let expr = Expr::from_text(expr).unwrap();
+        let test_deps = RibTestDeps::test_deps_for_pass_through_function();
+
+        let compiler_config =
+            RibCompilerConfig::new(test_deps.component_dependencies.clone(), vec![], vec![]);
+        let compiler = RibCompiler::new(compiler_config);
+        let compiled = compiler.compile(expr).unwrap();
+
+        let mut rib_interpreter = test_deps.interpreter;
+
+        let result = rib_interpreter.run(compiled.byte_code).await.unwrap();
+
+        let expected_val = Value::List(
+            ["tenant-1", "tenant-2", "tenant-3"]
+                .into_iter()
+                .map(|tenant| {
+                    Value::Record(vec![
+                        Value::String(tenant.to_string()),
+                        Value::String("pass-through".to_string()),
+                        Value::U64(42),
+                        Value::U32(43),
+                    ])
+                })
+                .collect(),
+        );
+
+        assert_eq!(result.get_val().unwrap().value, expected_val);
+    }
+
+    #[test]
+    async fn test_interpreter_max_worker_calls_exceeded() {
+        let expr = r#"
+                let tenants = ["tenant-1", "tenant-2", "tenant-3"];
+                parallel(2) for tenant in tenants {
+                  let worker = instance(tenant);
+                  yield worker.pass-through(42, 43);
+                }
+            "#;
+        let expr = Expr::from_text(expr).unwrap();
+        let test_deps = RibTestDeps::test_deps_for_pass_through_function();
+
+        let compiler_config =
+            RibCompilerConfig::new(test_deps.component_dependencies.clone(), vec![], vec![]);
+        let compiler = RibCompiler::new(compiler_config);
+        let compiled = compiler.compile(expr).unwrap();
+
+        let mut rib_interpreter = test_deps
+            .interpreter
+            .with_limits(RibExecutionLimits::default().with_max_worker_calls(2));
+
+        let result = rib_interpreter.run(compiled.byte_code).await;
+
+        assert!(matches!(
+            result,
+            Err(RibRuntimeError::LimitExceeded(ExecutionLimit::WorkerCalls(
+                2
+            )))
+        ));
+    }
+
     #[test]
     async fn test_interpreter_durable_worker_1_1() {
         let expr = r#"
@@ -5384,6 +6054,7 @@ mod tests {
                 input: input.unwrap_or_default(),
                 invoke,
                 generate_worker_name: Arc::new(DefaultWorkerNameGenerator),
+                ..Interpreter::default()
             }
         }
 
@@ -5400,6 +6071,7 @@ mod tests {
                 input: input.unwrap_or_default(),
                 invoke,
                 generate_worker_name: Arc::new(DefaultWorkerNameGenerator),
+                ..Interpreter::default()
             }
         }
 
@@ -5418,6 +6090,7 @@ mod tests {
                 input: rib_input.unwrap_or_default(),
                 invoke,
                 generate_worker_name: Arc::new(DefaultWorkerNameGenerator),
+                ..Interpreter::default()
             }
         }
 
@@ -5429,6 +6102,7 @@ mod tests {
                 input: input.unwrap_or_default(),
                 invoke,
                 generate_worker_name: Arc::new(DefaultWorkerNameGenerator),
+                ..Interpreter::default()
             }
         }
 
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::interpreter::execution_limits::ExecutionLimit;
 use crate::interpreter::interpreter_stack_value::RibInterpreterStackValue;
 use crate::{InstructionId, TypeHint};
 use golem_wasm::{Value, ValueAndType};
@@ -48,6 +49,7 @@ pub enum RibRuntimeError {
         left: Option<ValueAndType>,
         right: Option<ValueAndType>,
     },
+    LimitExceeded(ExecutionLimit),
     NoResult,
     ThrownError(String),
     TypeMismatch {
@@ -223,6 +225,9 @@ impl Display for RibRuntimeError {
                 )
             }
             RibRuntimeError::NoResult => write!(f, "No result"),
+            RibRuntimeError::LimitExceeded(limit) => {
+                write!(f, "execution limit exceeded: {limit}")
+            }
             RibRuntimeError::InfiniteComputation { message } => {
                 write!(f, "infinite computation detected: {message}")
             }
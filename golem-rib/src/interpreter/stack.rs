@@ -160,14 +160,16 @@ impl InterpreterStack {
         self.stack.push(RibInterpreterStackValue::val(element));
     }
 
-    pub fn push_to_sink(&mut self, value_and_type: ValueAndType) -> RibInterpreterResult<()> {
+    // Returns the number of values in the sink
+    pub fn push_to_sink(&mut self, value_and_type: ValueAndType) -> RibInterpreterResult<usize> {
         let (mut list, analysed_type) = self.pop_sink().ok_or(internal_corrupted_state!(
             "failed to pop a sink from the interpreter stack"
         ))?;
 
         list.push(value_and_type);
+        let size = list.len();
         self.push(RibInterpreterStackValue::Sink(list, analysed_type));
-        Ok(())
+        Ok(size)
     }
 
     pub fn push_variant(
@@ -15,6 +15,7 @@
 #![allow(clippy::large_enum_variant)]
 #![allow(clippy::result_large_err)]
 
+pub use builtin_function::*;
 pub use call_type::*;
 pub use compiler::*;
 pub use expr::*;
@@ -22,14 +23,18 @@ pub use function_name::*;
 pub use inferred_type::*;
 pub use instance_type::*;
 pub use interpreter::*;
+pub use lint::*;
 pub use parser::type_name::TypeName;
 pub use registry::*;
+pub use rib_source_span::{SourcePosition, SourceSpan};
+pub use rib_type_error::RibTypeError;
 pub use text::*;
 pub use type_checker::*;
 pub use type_inference::*;
 pub use type_parameter::*;
 pub use variable_id::*;
 
+mod builtin_function;
 mod call_type;
 
 mod compiler;
@@ -39,6 +44,7 @@ mod generic_type_parameter;
 mod inferred_type;
 mod instance_type;
 mod interpreter;
+mod lint;
 mod parser;
 mod registry;
 mod rib_source_span;
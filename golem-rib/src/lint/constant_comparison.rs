@@ -0,0 +1,111 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::lint::LintViolation;
+use crate::{Expr, ExprVisitor};
+use std::cmp::Ordering;
+
+// A comparison is constant if both of its operands are literals (`1 > 2`),
+// or if both of its operands are the same side effect free expression (`x.y >= x.y`).
+pub fn check_constant_comparison(expr: &mut Expr) -> Vec<LintViolation> {
+    let mut violations = vec![];
+
+    let mut visitor = ExprVisitor::bottom_up(expr);
+
+    while let Some(expr) = visitor.pop_front() {
+        let (lhs, rhs, source_span, expected) = match expr {
+            Expr::EqualTo {
+                lhs,
+                rhs,
+                source_span,
+                ..
+            } => (lhs, rhs, source_span, vec![Ordering::Equal]),
+            Expr::GreaterThan {
+                lhs,
+                rhs,
+                source_span,
+                ..
+            } => (lhs, rhs, source_span, vec![Ordering::Greater]),
+            Expr::GreaterThanOrEqualTo {
+                lhs,
+                rhs,
+                source_span,
+                ..
+            } => (
+                lhs,
+                rhs,
+                source_span,
+                vec![Ordering::Greater, Ordering::Equal],
+            ),
+            Expr::LessThan {
+                lhs,
+                rhs,
+                source_span,
+                ..
+            } => (lhs, rhs, source_span, vec![Ordering::Less]),
+            Expr::LessThanOrEqualTo {
+                lhs,
+                rhs,
+                source_span,
+                ..
+            } => (lhs, rhs, source_span, vec![Ordering::Less, Ordering::Equal]),
+            _ => continue,
+        };
+
+        if let Some(ordering) = internal::constant_ordering(lhs, rhs) {
+            violations.push(LintViolation::new(
+                source_span.clone(),
+                format!("comparison is always {}", expected.contains(&ordering)),
+            ));
+        }
+    }
+
+    violations
+}
+
+mod internal {
+    use crate::Expr;
+    use std::cmp::Ordering;
+
+    pub(crate) fn constant_ordering(lhs: &Expr, rhs: &Expr) -> Option<Ordering> {
+        match (lhs, rhs) {
+            (Expr::Number { number: lhs, .. }, Expr::Number { number: rhs, .. }) => {
+                Some(lhs.value.cmp(&rhs.value))
+            }
+            (Expr::Literal { value: lhs, .. }, Expr::Literal { value: rhs, .. }) => {
+                Some(lhs.cmp(rhs))
+            }
+            (Expr::Boolean { value: lhs, .. }, Expr::Boolean { value: rhs, .. }) => {
+                Some(lhs.cmp(rhs))
+            }
+            (lhs, rhs) if is_pure_path(lhs) && is_pure_path(rhs) && lhs == rhs => {
+                Some(Ordering::Equal)
+            }
+            _ => None,
+        }
+    }
+
+    // A variable, or a field or an index selected from it, which
+    // evaluates to the same value every time it is referred in a script
+    fn is_pure_path(expr: &Expr) -> bool {
+        match expr {
+            Expr::Identifier { .. } => true,
+            Expr::SelectField { expr, .. } => is_pure_path(expr),
+            Expr::SelectIndex { expr, index, .. } => {
+                is_pure_path(expr) && matches!(index.as_ref(), Expr::Number { .. })
+            }
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,476 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::lint::constant_comparison::check_constant_comparison;
+use crate::lint::shadowed_variable::check_shadowed_variable;
+use crate::lint::unbounded_worker_calls::check_unbounded_worker_calls;
+use crate::lint::unreachable_match_arm::check_unreachable_match_arm;
+use crate::lint::unused_let::check_unused_let;
+use crate::rib_source_span::SourceSpan;
+use crate::InferredExpr;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+mod constant_comparison;
+mod shadowed_variable;
+mod unbounded_worker_calls;
+mod unreachable_match_arm;
+mod unused_let;
+
+// Lints are run only on a Rib script that type checks, as they rely
+// on the variables and worker function calls identified during type inference.
+// Unlike type errors, a lint never stops the compilation unless it is denied.
+pub fn lint(inferred_expr: &InferredExpr, lint_config: &LintConfig) -> Vec<RibLintWarning> {
+    let mut warnings = vec![];
+
+    for lint in RibLint::all() {
+        let level = lint_config.level(lint);
+
+        if level == LintLevel::Allow {
+            continue;
+        }
+
+        let mut expr = inferred_expr.get_expr().clone();
+
+        let violations = match lint {
+            RibLint::UnusedLet => check_unused_let(&mut expr),
+            RibLint::UnreachableMatchArm => check_unreachable_match_arm(&mut expr),
+            RibLint::UnboundedWorkerCalls => check_unbounded_worker_calls(&mut expr),
+            RibLint::ShadowedVariable => check_shadowed_variable(&mut expr),
+            RibLint::ConstantComparison => check_constant_comparison(&mut expr),
+        };
+
+        warnings.extend(violations.into_iter().map(|violation| RibLintWarning {
+            lint,
+            level,
+            message: violation.message,
+            source_span: violation.source_span,
+        }));
+    }
+
+    warnings.sort_by_key(|warning| {
+        (
+            warning.source_span.start_line(),
+            warning.source_span.start_column(),
+        )
+    });
+
+    warnings
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum RibLint {
+    // `let x = foo();` where `x` is never used afterwards
+    UnusedLet,
+    // A match arm that can never be selected, because an earlier arm matches the same values
+    UnreachableMatchArm,
+    // A worker function invoked for every element of a list whose size is unknown
+    UnboundedWorkerCalls,
+    // A `let` that re-declares a variable bound by an earlier `let`
+    ShadowedVariable,
+    // A comparison that evaluates to the same value regardless of the input, such as `1 > 2`
+    ConstantComparison,
+}
+
+impl RibLint {
+    pub fn all() -> Vec<RibLint> {
+        vec![
+            RibLint::UnusedLet,
+            RibLint::UnreachableMatchArm,
+            RibLint::UnboundedWorkerCalls,
+            RibLint::ShadowedVariable,
+            RibLint::ConstantComparison,
+        ]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            RibLint::UnusedLet => "unused-let",
+            RibLint::UnreachableMatchArm => "unreachable-match-arm",
+            RibLint::UnboundedWorkerCalls => "unbounded-worker-calls",
+            RibLint::ShadowedVariable => "shadowed-variable",
+            RibLint::ConstantComparison => "constant-comparison",
+        }
+    }
+}
+
+impl Display for RibLint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for RibLint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        RibLint::all()
+            .into_iter()
+            .find(|lint| lint.name() == s)
+            .ok_or_else(|| {
+                format!(
+                    "unknown lint `{}`. expected one of: {}",
+                    s,
+                    RibLint::all()
+                        .iter()
+                        .map(|lint| lint.name())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum LintLevel {
+    Allow,
+    Warn,
+    Deny,
+}
+
+impl Display for LintLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LintLevel::Allow => write!(f, "allow"),
+            LintLevel::Warn => write!(f, "warn"),
+            LintLevel::Deny => write!(f, "deny"),
+        }
+    }
+}
+
+impl FromStr for LintLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "allow" => Ok(LintLevel::Allow),
+            "warn" => Ok(LintLevel::Warn),
+            "deny" => Ok(LintLevel::Deny),
+            _ => Err(format!(
+                "unknown lint level `{s}`. expected one of: allow, warn, deny"
+            )),
+        }
+    }
+}
+
+/// The level of each lint, where every lint not configured explicitly is a warning.
+/// A denied lint fails the compilation of the Rib script.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LintConfig {
+    levels: HashMap<RibLint, LintLevel>,
+}
+
+impl LintConfig {
+    pub fn new() -> LintConfig {
+        LintConfig::default()
+    }
+
+    pub fn allow_all() -> LintConfig {
+        RibLint::all()
+            .into_iter()
+            .fold(LintConfig::new(), |config, lint| {
+                config.with_level(lint, LintLevel::Allow)
+            })
+    }
+
+    pub fn with_level(mut self, lint: RibLint, level: LintLevel) -> LintConfig {
+        self.levels.insert(lint, level);
+        self
+    }
+
+    pub fn level(&self, lint: RibLint) -> LintLevel {
+        self.levels.get(&lint).copied().unwrap_or(LintLevel::Warn)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RibLintWarning {
+    pub lint: RibLint,
+    pub level: LintLevel,
+    pub message: String,
+    pub source_span: SourceSpan,
+}
+
+impl RibLintWarning {
+    pub fn is_denied(&self) -> bool {
+        self.level == LintLevel::Deny
+    }
+}
+
+impl Display for RibLintWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let severity = if self.is_denied() { "error" } else { "warning" };
+
+        write!(
+            f,
+            "{}[{}] at line {}, column {}: {}",
+            severity,
+            self.lint,
+            self.source_span.start_line(),
+            self.source_span.start_column(),
+            self.message
+        )
+    }
+}
+
+pub(crate) struct LintViolation {
+    pub(crate) source_span: SourceSpan,
+    pub(crate) message: String,
+}
+
+impl LintViolation {
+    pub(crate) fn new(source_span: SourceSpan, message: impl Into<String>) -> LintViolation {
+        LintViolation {
+            source_span,
+            message: message.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use crate::{
+        ComponentDependency, ComponentDependencyKey, Expr, LintConfig, LintLevel,
+        RibCompilationError, RibCompiler, RibCompilerConfig, RibLint, RibLintWarning,
+    };
+    use golem_wasm::analysis::analysed_type::str;
+    use golem_wasm::analysis::{
+        AnalysedExport, AnalysedFunction, AnalysedFunctionParameter, AnalysedFunctionResult,
+    };
+    use uuid::Uuid;
+
+    #[test]
+    fn test_unused_let() {
+        let rib = r#"
+          let x = 1;
+          let _y = 2;
+          let z = 3;
+          z
+        "#;
+
+        let warnings = lint_warnings(rib, vec![]);
+
+        assert_eq!(
+            summary(&warnings),
+            vec![(
+                RibLint::UnusedLet,
+                2,
+                "variable `x` is never used".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_unused_let_of_worker_instance() {
+        let rib = r#"
+          let worker = instance("my-worker");
+          let unused = instance("other-worker");
+          worker.foo("1")
+        "#;
+
+        let warnings = lint_warnings(rib, metadata());
+
+        assert_eq!(
+            summary(&warnings),
+            vec![(
+                RibLint::UnusedLet,
+                3,
+                "variable `unused` is never used".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_shadowed_variable() {
+        let rib = r#"
+          let x: u64 = 1;
+          let x: u64 = x + 1;
+          x
+        "#;
+
+        let warnings = lint_warnings(rib, vec![]);
+
+        assert_eq!(
+            summary(&warnings),
+            vec![(
+                RibLint::ShadowedVariable,
+                3,
+                "variable `x` shadows the `let` binding at line 2".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_unreachable_match_arm() {
+        let rib = r#"
+          let x: u64 = 1;
+          match some(x) {
+            some(1) => "one",
+            some(2) => "two",
+            some(1) => "uno",
+            _ => "many"
+          }
+        "#;
+
+        let warnings = lint_warnings(rib, vec![]);
+
+        assert_eq!(
+            summary(&warnings),
+            vec![(
+                RibLint::UnreachableMatchArm,
+                6,
+                "match arm `some(1)` is unreachable, as it is covered by the earlier arm `some(1)`"
+                    .to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_unbounded_worker_calls() {
+        let rib = r#"
+          let ids: list<string> = request.ids;
+          let worker = instance("my-worker");
+          let bounded = for id in ["1", "2"] { yield worker.foo(id); };
+          let unbounded = for id in ids { yield worker.foo(id); };
+          { bounded: bounded, unbounded: unbounded }
+        "#;
+
+        let warnings = lint_warnings(rib, metadata());
+
+        assert_eq!(
+            summary(&warnings),
+            vec![(
+                RibLint::UnboundedWorkerCalls,
+                5,
+                "worker function `foo` is invoked for every element of a list of unknown size"
+                    .to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_constant_comparison() {
+        let rib = r#"
+          let x: u64 = request.x;
+          let a = 1 > 2;
+          let b = x >= x;
+          let c = x > 1;
+          { a: a, b: b, c: c }
+        "#;
+
+        let warnings = lint_warnings(rib, vec![]);
+
+        assert_eq!(
+            summary(&warnings),
+            vec![
+                (
+                    RibLint::ConstantComparison,
+                    3,
+                    "comparison is always false".to_string()
+                ),
+                (
+                    RibLint::ConstantComparison,
+                    4,
+                    "comparison is always true".to_string()
+                )
+            ]
+        );
+    }
+
+    #[test]
+    fn test_allowed_lint() {
+        let rib = r#"
+          let x = 1;
+          2
+        "#;
+
+        let expr = Expr::from_text(rib).unwrap();
+        let compiler = RibCompiler::new(
+            RibCompilerConfig::new(vec![], vec![], vec![]).with_lint_config(
+                LintConfig::new().with_level(RibLint::UnusedLet, LintLevel::Allow),
+            ),
+        );
+
+        let compiler_output = compiler.compile(expr).unwrap();
+
+        assert!(compiler_output.lint_warnings.is_empty());
+    }
+
+    #[test]
+    fn test_denied_lint() {
+        let rib = r#"
+          let x = 1;
+          2
+        "#;
+
+        let expr = Expr::from_text(rib).unwrap();
+        let compiler = RibCompiler::new(
+            RibCompilerConfig::new(vec![], vec![], vec![]).with_lint_config(
+                LintConfig::new().with_level(RibLint::UnusedLet, LintLevel::Deny),
+            ),
+        );
+
+        let error = compiler.compile(expr).unwrap_err();
+
+        assert!(matches!(error, RibCompilationError::DeniedLints(_)));
+        assert_eq!(
+            error.to_string(),
+            "error[unused-let] at line 2, column 11: variable `x` is never used"
+        );
+    }
+
+    fn lint_warnings(rib: &str, metadata: Vec<ComponentDependency>) -> Vec<RibLintWarning> {
+        let expr = Expr::from_text(rib).unwrap();
+        let compiler = RibCompiler::new(RibCompilerConfig::new(metadata, vec![], vec![]));
+        compiler.compile(expr).unwrap().lint_warnings
+    }
+
+    fn summary(warnings: &[RibLintWarning]) -> Vec<(RibLint, i32, String)> {
+        warnings
+            .iter()
+            .map(|warning| {
+                (
+                    warning.lint,
+                    warning.source_span.start_line(),
+                    warning.message.clone(),
+                )
+            })
+            .collect()
+    }
+
+    fn metadata() -> Vec<ComponentDependency> {
+        let component_dependency_key = ComponentDependencyKey {
+            component_name: "foo".to_string(),
+            component_id: Uuid::new_v4(),
+            component_version: 0,
+            root_package_name: None,
+            root_package_version: None,
+        };
+
+        vec![ComponentDependency::new(
+            component_dependency_key,
+            vec![AnalysedExport::Function(AnalysedFunction {
+                name: "foo".to_string(),
+                parameters: vec![AnalysedFunctionParameter {
+                    name: "id".to_string(),
+                    typ: str(),
+                }],
+                result: Some(AnalysedFunctionResult { typ: str() }),
+            })],
+        )]
+    }
+}
@@ -0,0 +1,59 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::lint::LintViolation;
+use crate::rib_source_span::SourceSpan;
+use crate::{Expr, ExprVisitor};
+use std::collections::HashMap;
+
+// The scope of a `let` binding in Rib is the rest of the script,
+// therefore any `let` re-declaring a name shadows the earlier one.
+// The bindings are visited in the same order as they are bound during type inference.
+pub fn check_shadowed_variable(expr: &mut Expr) -> Vec<LintViolation> {
+    let mut declared: HashMap<String, SourceSpan> = HashMap::new();
+    let mut violations = vec![];
+
+    let mut visitor = ExprVisitor::bottom_up(expr);
+
+    while let Some(expr) = visitor.pop_front() {
+        if let Expr::Let {
+            variable_id,
+            source_span,
+            ..
+        } = expr
+        {
+            let name = variable_id.name();
+
+            if name.starts_with('_') {
+                continue;
+            }
+
+            match declared.get(&name) {
+                Some(previous) => violations.push(LintViolation::new(
+                    source_span.clone(),
+                    format!(
+                        "variable `{}` shadows the `let` binding at line {}",
+                        name,
+                        previous.start_line()
+                    ),
+                )),
+                None => {
+                    declared.insert(name, source_span.clone());
+                }
+            }
+        }
+    }
+
+    violations
+}
@@ -0,0 +1,94 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::call_type::CallType;
+use crate::lint::LintViolation;
+use crate::{BuiltInFunction, Expr};
+use std::collections::VecDeque;
+
+// A worker function called in the body of a list comprehension, a list reduce
+// or a lambda passed to `map` or `filter`, is invoked once per element of the list.
+// Unless the list is a literal (or a range with literal bounds), the number of
+// invocations is decided only at runtime, typically by the request.
+pub fn check_unbounded_worker_calls(expr: &mut Expr) -> Vec<LintViolation> {
+    let mut violations = vec![];
+
+    let mut queue = VecDeque::new();
+    queue.push_back((expr, false));
+
+    while let Some((expr, is_unbounded_iteration)) = queue.pop_front() {
+        if is_unbounded_iteration {
+            if let Expr::Call {
+                call_type: CallType::Function { function_name, .. },
+                source_span,
+                ..
+            } = expr
+            {
+                violations.push(LintViolation::new(
+                    source_span.clone(),
+                    format!(
+                        "worker function `{function_name}` is invoked for every element of a list of unknown size"
+                    ),
+                ));
+            }
+        }
+
+        // The iterable of a list comprehension or a list reduce (and the initial value of the latter)
+        // are evaluated only once, unlike the yield expression that follows them
+        let per_element_children = match expr {
+            Expr::ListComprehension { iterable_expr, .. }
+                if !internal::is_bounded(iterable_expr) =>
+            {
+                Some(1..2)
+            }
+            Expr::ListReduce { iterable_expr, .. } if !internal::is_bounded(iterable_expr) => {
+                Some(2..3)
+            }
+            Expr::Call {
+                call_type: CallType::BuiltIn(BuiltInFunction::Map | BuiltInFunction::Filter),
+                args,
+                ..
+            } if args.first().is_some_and(|list| !internal::is_bounded(list)) => Some(1..2),
+            _ => None,
+        };
+
+        let mut children = VecDeque::new();
+        expr.visit_expr_nodes_lazy(&mut children);
+
+        for (index, child) in children.into_iter().enumerate() {
+            let is_per_element = per_element_children
+                .as_ref()
+                .is_some_and(|children| children.contains(&index));
+
+            queue.push_back((child, is_unbounded_iteration || is_per_element));
+        }
+    }
+
+    violations
+}
+
+mod internal {
+    use crate::{Expr, Range};
+
+    pub(crate) fn is_bounded(iterable: &Expr) -> bool {
+        match iterable {
+            Expr::Sequence { .. } => true,
+            Expr::Range {
+                range: Range::Range { from, to } | Range::RangeInclusive { from, to },
+                ..
+            } => from.is_number() && to.is_number(),
+            _ => false,
+        }
+    }
+}
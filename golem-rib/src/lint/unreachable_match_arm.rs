@@ -0,0 +1,41 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::lint::LintViolation;
+use crate::type_checker::find_unreachable_arms;
+use crate::{Expr, ExprVisitor};
+
+pub fn check_unreachable_match_arm(expr: &mut Expr) -> Vec<LintViolation> {
+    let mut violations = vec![];
+
+    let mut visitor = ExprVisitor::bottom_up(expr);
+
+    while let Some(expr) = visitor.pop_front() {
+        if let Expr::PatternMatch { match_arms, .. } = expr {
+            for unreachable_arm in find_unreachable_arms(match_arms) {
+                let match_arm = &match_arms[unreachable_arm.arm_index];
+
+                violations.push(LintViolation::new(
+                    match_arm.arm_resolution_expr.source_span(),
+                    format!(
+                        "match arm `{}` is unreachable, as it is covered by the earlier arm `{}`",
+                        match_arm.arm_pattern, unreachable_arm.cause
+                    ),
+                ));
+            }
+        }
+    }
+
+    violations
+}
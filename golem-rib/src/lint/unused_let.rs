@@ -0,0 +1,87 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::call_type::{CallType, InstanceIdentifier};
+use crate::lint::LintViolation;
+use crate::{Expr, ExprVisitor, VariableId};
+use std::collections::HashSet;
+
+// Every `let` binding is given a unique variable id during type inference,
+// hence a binding is unused if no identifier refers to its variable id.
+// Once the method calls on a worker instance are resolved, the instance variable
+// is only referred from the instance identifier of the function calls.
+// Variables starting with `_` are considered to be unused on purpose.
+pub fn check_unused_let(expr: &mut Expr) -> Vec<LintViolation> {
+    let mut bindings = vec![];
+    let mut used_variables = HashSet::new();
+
+    let mut visitor = ExprVisitor::bottom_up(expr);
+
+    while let Some(expr) = visitor.pop_front() {
+        match expr {
+            Expr::Let {
+                variable_id,
+                source_span,
+                ..
+            } => {
+                bindings.push((variable_id.clone(), source_span.clone()));
+            }
+
+            Expr::Identifier { variable_id, .. } => {
+                used_variables.insert(variable_id.clone());
+            }
+
+            Expr::Call {
+                call_type:
+                    CallType::Function {
+                        instance_identifier: Some(instance_identifier),
+                        ..
+                    },
+                ..
+            } => {
+                if let Some(variable_id) = internal::instance_variable(instance_identifier) {
+                    used_variables.insert(variable_id.clone());
+                }
+            }
+
+            _ => {}
+        }
+    }
+
+    bindings
+        .into_iter()
+        .filter(|(variable_id, _)| {
+            !variable_id.name().starts_with('_') && !used_variables.contains(variable_id)
+        })
+        .map(|(variable_id, source_span)| {
+            LintViolation::new(
+                source_span,
+                format!("variable `{}` is never used", variable_id.name()),
+            )
+        })
+        .collect()
+}
+
+mod internal {
+    use super::*;
+
+    pub(crate) fn instance_variable(
+        instance_identifier: &InstanceIdentifier,
+    ) -> Option<&VariableId> {
+        match instance_identifier {
+            InstanceIdentifier::WitWorker { variable_id, .. } => variable_id.as_ref(),
+            InstanceIdentifier::WitResource { variable_id, .. } => variable_id.as_ref(),
+        }
+    }
+}
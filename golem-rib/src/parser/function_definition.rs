@@ -0,0 +1,143 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::expr::{Expr, FunctionParameter};
+use crate::parser::block::block;
+use crate::parser::errors::RibParseError;
+use crate::parser::identifier::identifier_text;
+use crate::parser::type_name::type_name;
+use crate::rib_source_span::GetSourcePosition;
+use crate::VariableId;
+use combine::parser::char::{alpha_num, char, spaces, string};
+use combine::{attempt, between, not_followed_by, sep_by, ParseError, Parser};
+
+// fn name(arg1: type1, arg2: type2) -> return-type { body }
+pub fn function_definition<Input>() -> impl Parser<Input, Output = Expr>
+where
+    Input: combine::Stream<Token = char>,
+    RibParseError: Into<
+        <Input::Error as ParseError<Input::Token, Input::Range, Input::Position>>::StreamError,
+    >,
+    Input::Position: GetSourcePosition,
+{
+    attempt(
+        string("fn").skip(not_followed_by(alpha_num().or(char('-')).or(char('_'))).skip(spaces())),
+    )
+    .with(
+        (
+            identifier_text()
+                .message("Unable to parse function name")
+                .skip(spaces()),
+            between(
+                char('(').skip(spaces()),
+                char(')').skip(spaces()),
+                sep_by(function_parameter(), char(',').skip(spaces())),
+            ),
+            string("->").skip(spaces()),
+            type_name().skip(spaces()),
+            between(char('{').skip(spaces()), char('}'), block().skip(spaces())),
+        )
+            .map(|(function_name, parameters, _, return_type, body)| {
+                Expr::function_definition(function_name, parameters, return_type, body)
+            }),
+    )
+}
+
+fn function_parameter<Input>() -> impl Parser<Input, Output = FunctionParameter>
+where
+    Input: combine::Stream<Token = char>,
+    RibParseError: Into<
+        <Input::Error as ParseError<Input::Token, Input::Range, Input::Position>>::StreamError,
+    >,
+    Input::Position: GetSourcePosition,
+{
+    (
+        identifier_text().skip(spaces()),
+        char(':').skip(spaces()),
+        type_name().skip(spaces()),
+    )
+        .map(|(name, _, type_name)| {
+            FunctionParameter::new(VariableId::function_parameter(name, 0), type_name)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use bigdecimal::BigDecimal;
+    use test_r::test;
+
+    use crate::parser::type_name::TypeName;
+    use crate::{Expr, FunctionParameter, VariableId};
+
+    #[test]
+    fn test_function_definition() {
+        let input = "fn add(x: u64, y: u64) -> u64 { x + y }";
+        let result = Expr::from_text(input).unwrap();
+
+        let expected = Expr::function_definition(
+            "add",
+            vec![
+                FunctionParameter::new(VariableId::function_parameter("x", 0), TypeName::U64),
+                FunctionParameter::new(VariableId::function_parameter("y", 0), TypeName::U64),
+            ],
+            TypeName::U64,
+            Expr::plus(
+                Expr::identifier_global("x", None),
+                Expr::identifier_global("y", None),
+            ),
+        );
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_function_definition_with_block() {
+        let input = r#"
+          fn greet(name: string) -> string {
+            let greeting = "hello";
+            "${greeting} ${name}"
+          };
+          greet("foo")
+        "#;
+
+        let result = Expr::from_text(input).unwrap();
+
+        let Expr::ExprBlock { exprs, .. } = result else {
+            panic!("expected an expression block");
+        };
+
+        assert!(matches!(
+            &exprs[0],
+            Expr::FunctionDefinition { function_name, parameters, body, .. }
+                if function_name == "greet"
+                    && parameters.len() == 1
+                    && matches!(body.as_ref(), Expr::ExprBlock { exprs, .. } if exprs.len() == 2)
+        ));
+    }
+
+    #[test]
+    fn test_function_definition_without_parameters() {
+        let input = "fn answer() -> u64 { 42 }";
+        let result = Expr::from_text(input).unwrap();
+
+        let expected = Expr::function_definition(
+            "answer",
+            vec![],
+            TypeName::U64,
+            Expr::number(BigDecimal::from(42)),
+        );
+
+        assert_eq!(result, expected);
+    }
+}
@@ -22,6 +22,7 @@ use crate::rib_source_span::GetSourcePosition;
 
 const RESERVED_KEYWORDS: &[&str] = &[
     "if", "then", "else", "match", "ok", "some", "err", "none", "let", "for", "yield", "reduce",
+    "fn", "parallel",
 ];
 
 pub fn identifier<Input>() -> impl Parser<Input, Output = Expr>
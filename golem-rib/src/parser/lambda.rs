@@ -0,0 +1,91 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::expr::Expr;
+use crate::parser::errors::RibParseError;
+use crate::parser::identifier::identifier_text;
+use crate::parser::rib_expr::rib_expr;
+use crate::rib_source_span::GetSourcePosition;
+use crate::VariableId;
+use combine::parser::char::{char, spaces};
+use combine::{between, sep_by1, ParseError, Parser};
+
+// |x| expr, |x, y| expr
+pub fn lambda<Input>() -> impl Parser<Input, Output = Expr>
+where
+    Input: combine::Stream<Token = char>,
+    RibParseError: Into<
+        <Input::Error as ParseError<Input::Token, Input::Range, Input::Position>>::StreamError,
+    >,
+    Input::Position: GetSourcePosition,
+{
+    (
+        between(
+            char('|').skip(spaces()),
+            char('|').skip(spaces()),
+            sep_by1(
+                identifier_text()
+                    .skip(spaces())
+                    .map(|name| VariableId::function_parameter(name, 0)),
+                char(',').skip(spaces()),
+            ),
+        ),
+        rib_expr(),
+    )
+        .map(|(parameters, body)| Expr::lambda(parameters, body))
+}
+
+#[cfg(test)]
+mod tests {
+    use bigdecimal::BigDecimal;
+    use test_r::test;
+
+    use crate::{Expr, VariableId};
+
+    #[test]
+    fn test_lambda() {
+        let input = "|x| x + 1";
+        let result = Expr::from_text(input).unwrap();
+
+        let expected = Expr::lambda(
+            vec![VariableId::function_parameter("x", 0)],
+            Expr::plus(
+                Expr::identifier_global("x", None),
+                Expr::number(BigDecimal::from(1)),
+            ),
+        );
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_lambda_as_argument() {
+        let input = "filter(xs, |x| x > 1)";
+        let result = Expr::from_text(input).unwrap();
+
+        let Expr::Call { args, .. } = result else {
+            panic!("expected a call");
+        };
+
+        let expected = Expr::lambda(
+            vec![VariableId::function_parameter("x", 0)],
+            Expr::greater_than(
+                Expr::identifier_global("x", None),
+                Expr::number(BigDecimal::from(1)),
+            ),
+        );
+
+        assert_eq!(args[1], expected);
+    }
+}
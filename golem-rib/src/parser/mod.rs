@@ -24,9 +24,11 @@ mod comment;
 mod cond;
 mod errors;
 mod flag;
+mod function_definition;
 mod generic_type_parameter;
 mod identifier;
 mod integer;
+mod lambda;
 mod let_binding;
 mod list_aggregation;
 mod list_comprehension;
@@ -34,6 +36,7 @@ pub(crate) mod literal;
 mod multi_line_code_block;
 mod not;
 mod optional;
+mod parallel;
 mod pattern_match;
 mod range_type;
 mod record;
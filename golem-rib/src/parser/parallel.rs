@@ -0,0 +1,120 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::expr::Expr;
+use crate::parser::errors::RibParseError;
+use crate::parser::list_comprehension::list_comprehension;
+use crate::parser::rib_expr::rib_expr;
+use crate::rib_source_span::GetSourcePosition;
+use combine::parser::char::{alpha_num, char, digit, spaces, string};
+use combine::{attempt, between, choice, many1, not_followed_by, optional, sep_by};
+use combine::{ParseError, Parser};
+
+// parallel { expr1, expr2 }, parallel(4) { expr1, expr2 }, parallel(4) for x in xs { yield expr; }
+pub fn parallel<Input>() -> impl Parser<Input, Output = Expr>
+where
+    Input: combine::Stream<Token = char>,
+    RibParseError: Into<
+        <Input::Error as ParseError<Input::Token, Input::Range, Input::Position>>::StreamError,
+    >,
+    Input::Position: GetSourcePosition,
+{
+    attempt(
+        string("parallel")
+            .skip(not_followed_by(alpha_num().or(char('-')).or(char('_'))).skip(spaces())),
+    )
+    .with(
+        (
+            optional(between(
+                char('(').skip(spaces()),
+                char(')').skip(spaces()),
+                max_concurrency().skip(spaces()),
+            )),
+            choice((
+                list_comprehension(),
+                between(
+                    char('{').skip(spaces()),
+                    char('}'),
+                    sep_by(rib_expr(), char(',').skip(spaces())),
+                )
+                .map(Expr::tuple),
+            )),
+        )
+            .map(|(max_concurrency, expr)| Expr::parallel(expr, max_concurrency)),
+    )
+}
+
+fn max_concurrency<Input>() -> impl Parser<Input, Output = u64>
+where
+    Input: combine::Stream<Token = char>,
+    RibParseError: Into<
+        <Input::Error as ParseError<Input::Token, Input::Range, Input::Position>>::StreamError,
+    >,
+    Input::Position: GetSourcePosition,
+{
+    many1(digit()).and_then(|digits: String| match digits.parse::<u64>() {
+        Ok(max_concurrency) if max_concurrency > 0 => Ok(max_concurrency),
+        _ => Err(RibParseError::Message(format!(
+            "invalid maximum concurrency {digits}, expecting a positive number"
+        ))),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use crate::{Expr, VariableId};
+
+    #[test]
+    fn test_parallel_tuple() {
+        let input = "parallel { foo, bar }";
+        let result = Expr::from_text(input).unwrap();
+
+        let expected = Expr::parallel(
+            Expr::tuple(vec![
+                Expr::identifier_global("foo", None),
+                Expr::identifier_global("bar", None),
+            ]),
+            None,
+        );
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_parallel_list_comprehension() {
+        let input = "parallel(4) for x in xs { yield x; }";
+        let result = Expr::from_text(input).unwrap();
+
+        let expected = Expr::parallel(
+            Expr::list_comprehension(
+                VariableId::list_comprehension_identifier("x"),
+                Expr::identifier_global("xs", None),
+                Expr::expr_block(vec![Expr::identifier_global("x", None)]),
+            ),
+            Some(4),
+        );
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_parallel_invalid_max_concurrency() {
+        let input = "parallel(0) { foo, bar }";
+        let result = Expr::from_text(input);
+
+        assert!(result.is_err());
+    }
+}
@@ -20,8 +20,10 @@ use crate::parser::comment::comments;
 use crate::parser::cond::conditional;
 use crate::parser::errors::RibParseError;
 use crate::parser::flag::flag;
+use crate::parser::function_definition::function_definition;
 use crate::parser::identifier::identifier;
 use crate::parser::integer::integer;
+use crate::parser::lambda::lambda;
 use crate::parser::let_binding::let_binding;
 use crate::parser::list_aggregation::list_aggregation;
 use crate::parser::list_comprehension::list_comprehension;
@@ -29,6 +31,7 @@ use crate::parser::literal::literal;
 use crate::parser::multi_line_code_block::multi_line_block;
 use crate::parser::not::not;
 use crate::parser::optional::option;
+use crate::parser::parallel::parallel;
 use crate::parser::pattern_match::pattern_match;
 use crate::parser::range_type::{range_type, RangeType};
 use crate::parser::record::record;
@@ -112,10 +115,13 @@ where
         spaces()
             .silent()
             .with(choice((
+                parallel(),
                 list_comprehension(),
                 list_aggregation(),
                 pattern_match(),
                 let_binding(),
+                function_definition(),
+                lambda(),
                 conditional(),
                 attempt(multi_line_block()),
                 flag_or_record(),
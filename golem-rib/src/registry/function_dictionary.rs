@@ -441,6 +441,8 @@ impl FunctionName {
                 Some(Self::from_dynamic_parsed_function_name(function_name))
             }
             CallType::InstanceCreation(_) => None,
+            CallType::BuiltIn(_) => None,
+            CallType::UserDefined(_) => None,
         }
     }
 
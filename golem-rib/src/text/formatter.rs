@@ -0,0 +1,1248 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::expr::Expr;
+use crate::generic_type_parameter::GenericTypeParameter;
+use crate::text::writer;
+use crate::{ArmPattern, CallType, Range};
+
+/// Layout options of the Rib formatter
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormatterConfig {
+    /// Number of spaces per indentation level
+    pub indent_width: usize,
+    /// Expressions that do not fit within this width are broken into multiple lines,
+    /// where the syntax allows it
+    pub max_width: usize,
+}
+
+impl Default for FormatterConfig {
+    fn default() -> Self {
+        FormatterConfig {
+            indent_width: 4,
+            max_width: 100,
+        }
+    }
+}
+
+// Formats a Rib program, keeping its comments.
+//
+// The parser drops comments, but the source span of every expression covers
+// the comments around it, therefore the comments are scanned from the source separately,
+// and are emitted in order whenever the layout starts a new line past them.
+pub fn format(input: &str, config: &FormatterConfig) -> Result<String, String> {
+    let expr = Expr::from_text(input)?;
+
+    let mut formatter = Formatter {
+        config,
+        source: SourceText::new(input),
+        next_comment: 0,
+    };
+
+    let output = formatter.program(&expr);
+
+    // Formatting only changes the layout, which is verified by parsing the result
+    let formatted_expr = Expr::from_text(&output)
+        .map_err(|err| format!("formatting resulted in an invalid Rib program: {err}"))?;
+
+    if formatted_expr != expr {
+        return Err("formatting changed the meaning of the Rib program".to_string());
+    }
+
+    Ok(output)
+}
+
+struct Formatter<'a> {
+    config: &'a FormatterConfig,
+    source: SourceText,
+    next_comment: usize,
+}
+
+impl Formatter<'_> {
+    fn program(&mut self, expr: &Expr) -> String {
+        let statements = self.block_statements(expr);
+        let end = self.source.len();
+
+        let lines = self.statements(&statements, 0, false, Some(end));
+
+        if lines.is_empty() {
+            String::new()
+        } else {
+            format!("{}\n", lines.join("\n"))
+        }
+    }
+
+    // The statements of a program or a function body, where a block of statements
+    // is distinguished from a single nested block (`{ a; b }`) by the position of
+    // its first statement, which for a nested block is after the opening brace
+    fn block_statements<'e>(&self, expr: &'e Expr) -> Vec<&'e Expr> {
+        match expr {
+            Expr::ExprBlock {
+                exprs, source_span, ..
+            } if exprs.first().is_none_or(|first| {
+                let first_span = first.source_span();
+                first_span.start_line() == source_span.start_line()
+                    && first_span.start_column() == source_span.start_column()
+            }) =>
+            {
+                exprs.iter().collect()
+            }
+            expr => vec![expr],
+        }
+    }
+
+    fn statements(
+        &mut self,
+        statements: &[&Expr],
+        indent: usize,
+        yield_last: bool,
+        end: Option<usize>,
+    ) -> Vec<String> {
+        let mut lines = vec![];
+        let mut previous_end = None;
+
+        for (index, statement) in statements.iter().enumerate() {
+            let is_last = index == statements.len() - 1;
+            let range = self.code_range(statement);
+
+            // A single blank line between statements is kept
+            if let (Some(previous_end), Some((start, _))) = (previous_end, range) {
+                let next = self
+                    .pending_comment()
+                    .map(|comment| comment.start)
+                    .filter(|comment_start| *comment_start < start)
+                    .unwrap_or(start);
+
+                if self.source.has_blank_line(previous_end, next) {
+                    lines.push(String::new());
+                }
+            }
+
+            lines.extend(self.comments_before(range.map(|(start, _)| start), indent));
+
+            let mut line = self.indentation(indent);
+
+            if yield_last && is_last {
+                line.push_str("yield ");
+            }
+
+            let body = self.layout(statement, indent, width(&line));
+            line.push_str(&body);
+
+            if !is_last || yield_last {
+                line.push(';');
+            }
+
+            let next = statements
+                .get(index + 1)
+                .and_then(|next| self.code_start(next))
+                .or(end);
+
+            if let Some(comment) = self.trailing_comment(range.map(|(_, end)| end), next) {
+                line.push(' ');
+                line.push_str(&comment);
+            }
+
+            lines.push(line);
+            previous_end = range.map(|(_, end)| end);
+        }
+
+        lines.extend(self.comments_before(end, indent));
+
+        lines
+    }
+
+    fn layout(&mut self, expr: &Expr, indent: usize, column: usize) -> String {
+        if let Some(flat) = self.flat(expr) {
+            if column + width(&flat) <= self.config.max_width {
+                return flat;
+            }
+        }
+
+        let mut output = self.broken(expr, indent, column);
+        push_type_annotation(expr, &mut output);
+        output
+    }
+
+    // The single line layout of an expression, unless the expression is always laid out
+    // on multiple lines, or has comments within it
+    fn flat(&self, expr: &Expr) -> Option<String> {
+        if self.has_comments(expr) {
+            return None;
+        }
+
+        let mut output = self.flat_unannotated(expr)?;
+        push_type_annotation(expr, &mut output);
+        Some(output)
+    }
+
+    fn flat_unannotated(&self, expr: &Expr) -> Option<String> {
+        if let Some((operator, lhs, rhs)) = internal::binary_operation(expr) {
+            return Some(format!(
+                "{} {operator} {}",
+                self.flat(lhs)?,
+                self.flat(rhs)?
+            ));
+        }
+
+        let output = match expr {
+            Expr::Let {
+                variable_id,
+                type_annotation,
+                expr,
+                ..
+            } => format!(
+                "let {}{} = {}",
+                variable_id.name(),
+                type_annotation
+                    .as_ref()
+                    .map(|type_name| format!(": {type_name}"))
+                    .unwrap_or_default(),
+                self.flat(expr)?
+            ),
+            Expr::SelectField { expr, field, .. } => format!("{}.{field}", self.flat(expr)?),
+            Expr::SelectIndex { expr, index, .. } => {
+                format!("{}[{}]", self.flat(expr)?, self.flat(index)?)
+            }
+            Expr::Sequence { exprs, .. } => format!("[{}]", self.flat_list(exprs)?),
+            Expr::Range { range, .. } => match range {
+                Range::Range { from, to } => format!("{}..{}", self.flat(from)?, self.flat(to)?),
+                Range::RangeInclusive { from, to } => {
+                    format!("{}..={}", self.flat(from)?, self.flat(to)?)
+                }
+                Range::RangeFrom { from } => format!("{}..", self.flat(from)?),
+            },
+            Expr::Record { exprs, .. } => {
+                let fields = exprs
+                    .iter()
+                    .map(|(key, value)| Some(format!("{key}: {}", self.flat(value)?)))
+                    .collect::<Option<Vec<_>>>()?;
+
+                format!("{{{}}}", fields.join(", "))
+            }
+            Expr::Tuple { exprs, .. } => format!("({})", self.flat_list(exprs)?),
+            Expr::Literal { value, .. } => format!("\"{value}\""),
+            Expr::Number { number, .. } => number.value.to_string(),
+            Expr::Flags { flags, .. } => format!("{{{}}}", flags.join(", ")),
+            Expr::Identifier { variable_id, .. } => variable_id.name(),
+            Expr::Boolean { value, .. } => value.to_string(),
+            Expr::Concat { exprs, .. } => {
+                let mut output = String::from("\"");
+
+                for part in exprs {
+                    match part {
+                        Expr::Literal { value, .. } => output.push_str(value),
+                        Expr::ExprBlock { exprs, .. } => {
+                            output.push_str(&format!("${{{}}}", self.flat_joined(exprs, "; ")?))
+                        }
+                        part => output.push_str(&format!("${{{}}}", self.flat(part)?)),
+                    }
+                }
+
+                output.push('"');
+                output
+            }
+            Expr::Not { expr, .. } => format!("!{}", self.flat(expr)?),
+            Expr::Cond { cond, lhs, rhs, .. } => format!(
+                "if {} then {} else {}",
+                self.flat(cond)?,
+                self.flat(lhs)?,
+                self.flat(rhs)?
+            ),
+            Expr::Option { expr, .. } => match expr {
+                Some(expr) => format!("some({})", self.flat(expr)?),
+                None => "none".to_string(),
+            },
+            Expr::Result { expr, .. } => match expr {
+                Ok(expr) => format!("ok({})", self.flat(expr)?),
+                Err(expr) => format!("err({})", self.flat(expr)?),
+            },
+            Expr::Call {
+                call_type,
+                generic_type_parameter,
+                args,
+                ..
+            } => match call_type {
+                CallType::Function { function_name, .. } => {
+                    match internal::simple_function_name(
+                        &function_name.site,
+                        &function_name.function,
+                    ) {
+                        Some(function) => format!(
+                            "{function}{}({})",
+                            generic(generic_type_parameter),
+                            self.flat_list(args)?
+                        ),
+                        None => self.verbatim(expr).filter(|text| !text.contains('\n'))?,
+                    }
+                }
+                CallType::VariantConstructor(name) if !args.is_empty() => {
+                    format!("{name}({})", self.flat_list(args)?)
+                }
+                CallType::VariantConstructor(name) | CallType::EnumConstructor(name) => {
+                    name.clone()
+                }
+                _ => writer::write_expr(expr).ok()?,
+            },
+            Expr::InvokeMethodLazy {
+                lhs,
+                method,
+                generic_type_parameter,
+                args,
+                ..
+            } => format!(
+                "{}.{method}{}({})",
+                self.flat(lhs)?,
+                generic(generic_type_parameter),
+                self.flat_list(args)?
+            ),
+            Expr::Lambda {
+                parameters, body, ..
+            } if !body.is_block() => format!(
+                "|{}| {}",
+                parameters
+                    .iter()
+                    .map(|parameter| parameter.name())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                self.flat(body)?
+            ),
+            Expr::Parallel {
+                expr,
+                max_concurrency,
+                ..
+            } => match expr.as_ref() {
+                Expr::Tuple { exprs, .. } if exprs.is_empty() => {
+                    format!("{} {{}}", internal::parallel_keyword(max_concurrency))
+                }
+                Expr::Tuple { exprs, .. } => format!(
+                    "{} {{ {} }}",
+                    internal::parallel_keyword(max_concurrency),
+                    self.flat_list(exprs)?
+                ),
+                _ => return None,
+            },
+            Expr::ExprBlock { .. }
+            | Expr::PatternMatch { .. }
+            | Expr::ListComprehension { .. }
+            | Expr::ListReduce { .. }
+            | Expr::FunctionDefinition { .. }
+            | Expr::Lambda { .. } => return None,
+            expr => writer::write_expr(expr).ok()?,
+        };
+
+        Some(output)
+    }
+
+    fn flat_list(&self, exprs: &[Expr]) -> Option<String> {
+        self.flat_joined(exprs, ", ")
+    }
+
+    fn flat_joined(&self, exprs: &[Expr], separator: &str) -> Option<String> {
+        Some(
+            exprs
+                .iter()
+                .map(|expr| self.flat(expr))
+                .collect::<Option<Vec<_>>>()?
+                .join(separator),
+        )
+    }
+
+    // The multi line layout of an expression, without its type annotation
+    fn broken(&mut self, expr: &Expr, indent: usize, column: usize) -> String {
+        if let Some((operator, lhs, rhs)) = internal::binary_operation(expr) {
+            let lhs = self.layout(lhs, indent, column);
+            let column = end_column(column, &lhs) + operator.len() + 2;
+            let rhs = self.layout(rhs, indent, column);
+            return format!("{lhs} {operator} {rhs}");
+        }
+
+        let end = self.code_end(expr);
+
+        match expr {
+            Expr::Let {
+                variable_id,
+                type_annotation,
+                expr,
+                ..
+            } => {
+                let prefix = format!(
+                    "let {}{} = ",
+                    variable_id.name(),
+                    type_annotation
+                        .as_ref()
+                        .map(|type_name| format!(": {type_name}"))
+                        .unwrap_or_default()
+                );
+                let value = self.layout(expr, indent, column + width(&prefix));
+                format!("{prefix}{value}")
+            }
+            Expr::SelectField { expr, field, .. } => {
+                format!("{}.{field}", self.layout(expr, indent, column))
+            }
+            Expr::SelectIndex { expr, index, .. } => {
+                let expr = self.layout(expr, indent, column);
+                let index = self.layout(index, indent, end_column(column, &expr) + 1);
+                format!("{expr}[{index}]")
+            }
+            Expr::Sequence { exprs, .. } => {
+                let items = exprs.iter().map(|expr| (None, expr)).collect::<Vec<_>>();
+                self.broken_list("[", "]", &items, indent, end)
+            }
+            Expr::Record { exprs, .. } => {
+                let items = exprs
+                    .iter()
+                    .map(|(key, value)| (Some(key.as_str()), value.as_ref()))
+                    .collect::<Vec<_>>();
+                self.broken_list("{", "}", &items, indent, end)
+            }
+            Expr::Tuple { exprs, .. } => {
+                let items = exprs.iter().map(|expr| (None, expr)).collect::<Vec<_>>();
+                self.broken_list("(", ")", &items, indent, end)
+            }
+            Expr::ExprBlock { exprs, .. } => {
+                let statements = exprs.iter().collect::<Vec<_>>();
+                self.braced_statements("{", &statements, indent, false, end)
+            }
+            Expr::Not { expr, .. } => format!("!{}", self.layout(expr, indent, column + 1)),
+            Expr::Cond { cond, lhs, rhs, .. } => {
+                let cond = self.layout(cond, indent, column + 3);
+                let mut lines = vec![format!("if {cond} then")];
+
+                lines.extend(self.comments_before(self.code_start(lhs), indent + 1));
+                let lhs_indentation = self.indentation(indent + 1);
+                let lhs = self.layout(lhs, indent + 1, width(&lhs_indentation));
+                lines.push(format!("{lhs_indentation}{lhs}"));
+
+                // An `else if` chain is kept at the same level of indentation
+                if rhs.is_if_else() && rhs.type_annotation().is_none() {
+                    let prefix = format!("{}else ", self.indentation(indent));
+                    let rhs = self.layout(rhs, indent, width(&prefix));
+                    lines.push(format!("{prefix}{rhs}"));
+                } else {
+                    lines.push(format!("{}else", self.indentation(indent)));
+                    lines.extend(self.comments_before(self.code_start(rhs), indent + 1));
+                    let rhs_indentation = self.indentation(indent + 1);
+                    let rhs = self.layout(rhs, indent + 1, width(&rhs_indentation));
+                    lines.push(format!("{rhs_indentation}{rhs}"));
+                }
+
+                lines.join("\n")
+            }
+            Expr::PatternMatch {
+                predicate,
+                match_arms,
+                ..
+            } => {
+                let predicate = self.layout(predicate, indent, column + 6);
+                let mut lines = vec![format!("match {predicate} {{")];
+
+                for (index, match_arm) in match_arms.iter().enumerate() {
+                    let range = self.code_range(&match_arm.arm_resolution_expr);
+                    lines.extend(self.comments_before(range.map(|(start, _)| start), indent + 1));
+
+                    let mut line = format!(
+                        "{}{} => ",
+                        self.indentation(indent + 1),
+                        self.arm_pattern(&match_arm.arm_pattern)
+                    );
+                    let arm = self.layout(&match_arm.arm_resolution_expr, indent + 1, width(&line));
+                    line.push_str(&arm);
+                    line.push(',');
+
+                    let next = match_arms
+                        .get(index + 1)
+                        .and_then(|next| self.code_start(&next.arm_resolution_expr))
+                        .or(end);
+
+                    if let Some(comment) = self.trailing_comment(range.map(|(_, end)| end), next) {
+                        line.push(' ');
+                        line.push_str(&comment);
+                    }
+
+                    lines.push(line);
+                }
+
+                lines.extend(self.comments_before(end, indent + 1));
+                lines.push(format!("{}}}", self.indentation(indent)));
+                lines.join("\n")
+            }
+            Expr::Option {
+                expr: Some(expr), ..
+            } => format!("some({})", self.layout(expr, indent, column + 5)),
+            Expr::Result { expr, .. } => match expr {
+                Ok(expr) => format!("ok({})", self.layout(expr, indent, column + 3)),
+                Err(expr) => format!("err({})", self.layout(expr, indent, column + 4)),
+            },
+            Expr::Call {
+                call_type,
+                generic_type_parameter,
+                args,
+                ..
+            } => {
+                let function = match call_type {
+                    CallType::Function { function_name, .. } => {
+                        internal::simple_function_name(&function_name.site, &function_name.function)
+                    }
+                    CallType::VariantConstructor(name) if !args.is_empty() => Some(name.clone()),
+                    _ => None,
+                };
+
+                match function {
+                    Some(function) => {
+                        let items = args.iter().map(|arg| (None, arg)).collect::<Vec<_>>();
+                        let args = self.broken_list("(", ")", &items, indent, end);
+                        format!("{function}{}{args}", generic(generic_type_parameter))
+                    }
+                    None => self.leaf(expr),
+                }
+            }
+            Expr::InvokeMethodLazy {
+                lhs,
+                method,
+                generic_type_parameter,
+                args,
+                ..
+            } => {
+                let lhs = self.layout(lhs, indent, column);
+                let items = args.iter().map(|arg| (None, arg)).collect::<Vec<_>>();
+                let args = self.broken_list("(", ")", &items, indent, end);
+                format!("{lhs}.{method}{}{args}", generic(generic_type_parameter))
+            }
+            Expr::ListComprehension {
+                iterated_variable,
+                iterable_expr,
+                yield_expr,
+                ..
+            } => {
+                let prefix = format!("for {} in ", iterated_variable.name());
+                let iterable = self.layout(iterable_expr, indent, column + width(&prefix));
+                let statements = internal::yield_statements(yield_expr);
+                self.braced_statements(
+                    &format!("{prefix}{iterable} {{"),
+                    &statements,
+                    indent,
+                    true,
+                    end,
+                )
+            }
+            Expr::ListReduce {
+                reduce_variable,
+                iterated_variable,
+                iterable_expr,
+                init_value_expr,
+                yield_expr,
+                ..
+            } => {
+                let prefix = format!(
+                    "reduce {}, {} in ",
+                    reduce_variable.name(),
+                    iterated_variable.name()
+                );
+                let iterable = self.layout(iterable_expr, indent, column + width(&prefix));
+                let header = format!("{prefix}{iterable} from ");
+                let init_value = self.layout(init_value_expr, indent, end_column(column, &header));
+                let statements = internal::yield_statements(yield_expr);
+                self.braced_statements(
+                    &format!("{header}{init_value} {{"),
+                    &statements,
+                    indent,
+                    true,
+                    end,
+                )
+            }
+            Expr::FunctionDefinition {
+                function_name,
+                parameters,
+                return_type,
+                body,
+                ..
+            } => {
+                let parameters = parameters
+                    .iter()
+                    .map(|parameter| {
+                        format!("{}: {}", parameter.variable_id.name(), parameter.type_name)
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let statements = self.block_statements(body);
+                self.braced_statements(
+                    &format!("fn {function_name}({parameters}) -> {return_type} {{"),
+                    &statements,
+                    indent,
+                    false,
+                    end,
+                )
+            }
+            Expr::Lambda {
+                parameters, body, ..
+            } => {
+                let parameters = parameters
+                    .iter()
+                    .map(|parameter| parameter.name())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                match body.as_ref() {
+                    Expr::ExprBlock { exprs, .. } => {
+                        let statements = exprs.iter().collect::<Vec<_>>();
+                        self.braced_statements(
+                            &format!("|{parameters}| {{"),
+                            &statements,
+                            indent,
+                            false,
+                            end,
+                        )
+                    }
+                    body => {
+                        let prefix = format!("|{parameters}| ");
+                        let body = self.layout(body, indent, column + width(&prefix));
+                        format!("{prefix}{body}")
+                    }
+                }
+            }
+            Expr::Parallel {
+                expr,
+                max_concurrency,
+                ..
+            } => {
+                let keyword = internal::parallel_keyword(max_concurrency);
+
+                match expr.as_ref() {
+                    Expr::Tuple { exprs, .. } => {
+                        let items = exprs.iter().map(|expr| (None, expr)).collect::<Vec<_>>();
+                        self.broken_list(&format!("{keyword} {{"), "}", &items, indent, end)
+                    }
+                    expr => {
+                        let expr = self.layout(expr, indent, column + width(&keyword) + 1);
+                        format!("{keyword} {expr}")
+                    }
+                }
+            }
+            expr => self.leaf(expr),
+        }
+    }
+
+    fn leaf(&mut self, expr: &Expr) -> String {
+        let output = match expr {
+            // Kept as written when not expressible on a single line,
+            // which also keeps the comments within string interpolations
+            Expr::Concat { .. } | Expr::Call { .. } => self
+                .consume_verbatim(expr)
+                .or_else(|| self.flat_unannotated(expr)),
+            expr => self.flat_unannotated(expr),
+        };
+
+        output.unwrap_or_else(|| writer::write_expr(expr).unwrap_or_default())
+    }
+
+    fn broken_list(
+        &mut self,
+        open: &str,
+        close: &str,
+        items: &[(Option<&str>, &Expr)],
+        indent: usize,
+        end: Option<usize>,
+    ) -> String {
+        if items.is_empty() {
+            return format!("{open}{close}");
+        }
+
+        let mut lines = vec![open.to_string()];
+
+        for (index, (key, item)) in items.iter().enumerate() {
+            let range = self.code_range(item);
+            lines.extend(self.comments_before(range.map(|(start, _)| start), indent + 1));
+
+            let mut line = self.indentation(indent + 1);
+
+            if let Some(key) = key {
+                line.push_str(key);
+                line.push_str(": ");
+            }
+
+            let value = self.layout(item, indent + 1, width(&line));
+            line.push_str(&value);
+
+            if index < items.len() - 1 {
+                line.push(',');
+            }
+
+            let next = items
+                .get(index + 1)
+                .and_then(|(_, next)| self.code_start(next))
+                .or(end);
+
+            if let Some(comment) = self.trailing_comment(range.map(|(_, end)| end), next) {
+                line.push(' ');
+                line.push_str(&comment);
+            }
+
+            lines.push(line);
+        }
+
+        lines.extend(self.comments_before(end, indent + 1));
+        lines.push(format!("{}{close}", self.indentation(indent)));
+        lines.join("\n")
+    }
+
+    fn braced_statements(
+        &mut self,
+        header: &str,
+        statements: &[&Expr],
+        indent: usize,
+        yield_last: bool,
+        end: Option<usize>,
+    ) -> String {
+        let mut lines = vec![header.to_string()];
+        lines.extend(self.statements(statements, indent + 1, yield_last, end));
+        lines.push(format!("{}}}", self.indentation(indent)));
+        lines.join("\n")
+    }
+
+    fn arm_pattern(&self, arm_pattern: &ArmPattern) -> String {
+        let patterns = |patterns: &[ArmPattern]| {
+            patterns
+                .iter()
+                .map(|pattern| self.arm_pattern(pattern))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        match arm_pattern {
+            ArmPattern::WildCard => "_".to_string(),
+            ArmPattern::As(name, pattern) => format!("{name} @ {}", self.arm_pattern(pattern)),
+            ArmPattern::Constructor(name, variables) if variables.is_empty() && name == "none" => {
+                name.clone()
+            }
+            ArmPattern::Constructor(name, variables) => {
+                format!("{name}({})", patterns(variables))
+            }
+            ArmPattern::TupleConstructor(variables) => format!("({})", patterns(variables)),
+            ArmPattern::ListConstructor(variables) => format!("[{}]", patterns(variables)),
+            ArmPattern::RecordConstructor(fields) => format!(
+                "{{{}}}",
+                fields
+                    .iter()
+                    .map(|(key, pattern)| format!("{key}: {}", self.arm_pattern(pattern)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            ArmPattern::Literal(expr) => match expr.as_ref() {
+                Expr::Identifier { variable_id, .. } => variable_id.name(),
+                expr => self
+                    .flat(expr)
+                    .unwrap_or_else(|| writer::write_expr(expr).unwrap_or_default()),
+            },
+        }
+    }
+
+    fn indentation(&self, indent: usize) -> String {
+        " ".repeat(indent * self.config.indent_width)
+    }
+
+    // The range of the code of an expression within the source, excluding the comments
+    // and whitespace covered by its source span
+    fn code_range(&self, expr: &Expr) -> Option<(usize, usize)> {
+        let source_span = expr.source_span();
+
+        let start = self
+            .source
+            .index(source_span.start_line(), source_span.start_column())?;
+        let end = self
+            .source
+            .index(source_span.end_line(), source_span.end_column())?;
+
+        if start >= end {
+            return None;
+        }
+
+        let start = self.source.skip_forward(start, end);
+        let end = self.source.skip_backward(end, start);
+
+        Some((start, end))
+    }
+
+    fn code_start(&self, expr: &Expr) -> Option<usize> {
+        self.code_range(expr).map(|(start, _)| start)
+    }
+
+    fn code_end(&self, expr: &Expr) -> Option<usize> {
+        self.code_range(expr).map(|(_, end)| end)
+    }
+
+    fn pending_comment(&self) -> Option<&Comment> {
+        self.source.comments.get(self.next_comment)
+    }
+
+    fn has_comments(&self, expr: &Expr) -> bool {
+        match (self.code_end(expr), self.pending_comment()) {
+            (Some(end), Some(comment)) => comment.start < end,
+            _ => false,
+        }
+    }
+
+    // Comments that are not emitted yet, and start before the given position,
+    // each on its own line
+    fn comments_before(&mut self, position: Option<usize>, indent: usize) -> Vec<String> {
+        let mut lines = vec![];
+
+        let Some(position) = position else {
+            return lines;
+        };
+
+        while let Some(comment) = self.pending_comment() {
+            if comment.start >= position {
+                break;
+            }
+
+            lines.push(format!("{}{}", self.indentation(indent), comment.text));
+            self.next_comment += 1;
+        }
+
+        lines
+    }
+
+    // A comment on the same line as the end of an expression, before the next expression
+    fn trailing_comment(&mut self, end: Option<usize>, next: Option<usize>) -> Option<String> {
+        let end = end?;
+        let comment = self.pending_comment()?;
+
+        let is_trailing = comment.start >= end
+            && comment.start < next.unwrap_or(usize::MAX)
+            && !comment.text.contains('\n')
+            && self.source.line_of(comment.start) == self.source.line_of(end.saturating_sub(1));
+
+        if is_trailing {
+            let text = comment.text.clone();
+            self.next_comment += 1;
+            Some(text)
+        } else {
+            None
+        }
+    }
+
+    fn verbatim(&self, expr: &Expr) -> Option<String> {
+        self.code_range(expr)
+            .map(|(start, end)| self.source.text(start, end))
+    }
+
+    // The source of an expression as written, including its comments, which are
+    // therefore not emitted again
+    fn consume_verbatim(&mut self, expr: &Expr) -> Option<String> {
+        let (start, end) = self.code_range(expr)?;
+
+        // Comments are emitted in order, hence an earlier pending comment
+        // would be emitted after the ones within the expression
+        if self
+            .pending_comment()
+            .is_some_and(|comment| comment.start < start)
+        {
+            return None;
+        }
+
+        while self
+            .pending_comment()
+            .is_some_and(|comment| comment.start < end)
+        {
+            self.next_comment += 1;
+        }
+
+        Some(self.source.text(start, end))
+    }
+}
+
+struct Comment {
+    start: usize,
+    end: usize,
+    text: String,
+}
+
+// The source of a Rib program, indexed by characters, as source spans are
+// line and column (character) based
+struct SourceText {
+    chars: Vec<char>,
+    line_starts: Vec<usize>,
+    comments: Vec<Comment>,
+}
+
+impl SourceText {
+    fn new(input: &str) -> SourceText {
+        let chars = input.chars().collect::<Vec<_>>();
+
+        let mut line_starts = vec![0];
+        line_starts.extend(
+            chars
+                .iter()
+                .enumerate()
+                .filter(|(_, char)| **char == '\n')
+                .map(|(index, _)| index + 1),
+        );
+
+        let comments = scan_comments(&chars);
+
+        SourceText {
+            chars,
+            line_starts,
+            comments,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.chars.len()
+    }
+
+    fn index(&self, line: i32, column: i32) -> Option<usize> {
+        if line < 1 || column < 1 {
+            return None;
+        }
+
+        let line_start = *self.line_starts.get(line as usize - 1)?;
+        Some((line_start + column as usize - 1).min(self.chars.len()))
+    }
+
+    fn line_of(&self, index: usize) -> usize {
+        self.line_starts.partition_point(|start| *start <= index)
+    }
+
+    fn text(&self, start: usize, end: usize) -> String {
+        self.chars[start..end].iter().collect()
+    }
+
+    fn has_blank_line(&self, start: usize, end: usize) -> bool {
+        start < end
+            && self.chars[start..end]
+                .iter()
+                .filter(|char| **char == '\n')
+                .count()
+                >= 2
+    }
+
+    fn skip_forward(&self, mut index: usize, limit: usize) -> usize {
+        while index < limit {
+            if self.chars[index].is_whitespace() {
+                index += 1;
+            } else if let Ok(comment) = self
+                .comments
+                .binary_search_by_key(&index, |comment| comment.start)
+            {
+                index = self.comments[comment].end;
+            } else {
+                break;
+            }
+        }
+
+        index.min(limit)
+    }
+
+    fn skip_backward(&self, mut index: usize, limit: usize) -> usize {
+        while index > limit {
+            if self.chars[index - 1].is_whitespace() {
+                index -= 1;
+            } else if let Ok(comment) = self
+                .comments
+                .binary_search_by_key(&index, |comment| comment.end)
+            {
+                index = self.comments[comment].start;
+            } else {
+                break;
+            }
+        }
+
+        index.max(limit)
+    }
+}
+
+fn scan_comments(chars: &[char]) -> Vec<Comment> {
+    let starts_with = |index: usize, pattern: &str| {
+        pattern
+            .chars()
+            .enumerate()
+            .all(|(offset, char)| chars.get(index + offset) == Some(&char))
+    };
+
+    let mut comments = vec![];
+
+    // The brace depth of each nested code section, where every section but the outermost
+    // one is an interpolation (`${..}`) within a string literal
+    let mut code_depths = vec![0usize];
+    let mut in_literal = false;
+    let mut index = 0;
+
+    while index < chars.len() {
+        if in_literal {
+            if starts_with(index, "${") {
+                code_depths.push(0);
+                in_literal = false;
+                index += 2;
+                continue;
+            }
+
+            if chars[index] == '"' {
+                in_literal = false;
+            }
+
+            index += 1;
+            continue;
+        }
+
+        let end = if starts_with(index, "//") {
+            Some(
+                chars[index..]
+                    .iter()
+                    .position(|char| *char == '\n')
+                    .map_or(chars.len(), |offset| index + offset),
+            )
+        } else if starts_with(index, "/*") {
+            Some(
+                (index + 2..chars.len())
+                    .find(|end| starts_with(*end, "*/"))
+                    .map_or(chars.len(), |end| end + 2),
+            )
+        } else {
+            None
+        };
+
+        if let Some(end) = end {
+            let text = chars[index..end].iter().collect::<String>();
+
+            comments.push(Comment {
+                start: index,
+                end,
+                text: text.trim_end().to_string(),
+            });
+
+            index = end;
+            continue;
+        }
+
+        match chars[index] {
+            '"' => in_literal = true,
+            '{' => {
+                if let Some(depth) = code_depths.last_mut() {
+                    *depth += 1;
+                }
+            }
+            '}' => {
+                if code_depths.len() > 1 && code_depths.last() == Some(&0) {
+                    code_depths.pop();
+                    in_literal = true;
+                } else if let Some(depth) = code_depths.last_mut() {
+                    *depth = depth.saturating_sub(1);
+                }
+            }
+            _ => {}
+        }
+
+        index += 1;
+    }
+
+    comments
+}
+
+fn push_type_annotation(expr: &Expr, output: &mut String) {
+    // The type annotation of a let binding is part of the binding
+    if let Expr::Let { .. } = expr {
+        return;
+    }
+
+    if let Some(type_name) = expr.type_annotation() {
+        output.push_str(&format!(": {type_name}"));
+    }
+}
+
+fn generic(generic_type_parameter: &Option<GenericTypeParameter>) -> String {
+    generic_type_parameter
+        .as_ref()
+        .map(|type_parameter| format!("[{}]", type_parameter.value))
+        .unwrap_or_default()
+}
+
+fn width(text: &str) -> usize {
+    text.chars().count()
+}
+
+// The column after the given text, which starts at the given column
+fn end_column(column: usize, text: &str) -> usize {
+    match text.rsplit_once('\n') {
+        Some((_, last_line)) => width(last_line),
+        None => column + width(text),
+    }
+}
+
+mod internal {
+    use crate::expr::Expr;
+    use crate::{DynamicParsedFunctionReference, ParsedFunctionSite};
+
+    pub(crate) fn binary_operation(expr: &Expr) -> Option<(&'static str, &Expr, &Expr)> {
+        match expr {
+            Expr::GreaterThan { lhs, rhs, .. } => Some((">", lhs, rhs)),
+            Expr::GreaterThanOrEqualTo { lhs, rhs, .. } => Some((">=", lhs, rhs)),
+            Expr::LessThan { lhs, rhs, .. } => Some(("<", lhs, rhs)),
+            Expr::LessThanOrEqualTo { lhs, rhs, .. } => Some(("<=", lhs, rhs)),
+            Expr::EqualTo { lhs, rhs, .. } => Some(("==", lhs, rhs)),
+            Expr::And { lhs, rhs, .. } => Some(("&&", lhs, rhs)),
+            Expr::Or { lhs, rhs, .. } => Some(("||", lhs, rhs)),
+            Expr::Plus { lhs, rhs, .. } => Some(("+", lhs, rhs)),
+            Expr::Minus { lhs, rhs, .. } => Some(("-", lhs, rhs)),
+            Expr::Multiply { lhs, rhs, .. } => Some(("*", lhs, rhs)),
+            Expr::Divide { lhs, rhs, .. } => Some(("/", lhs, rhs)),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn parallel_keyword(max_concurrency: &Option<u64>) -> String {
+        match max_concurrency {
+            Some(max_concurrency) => format!("parallel({max_concurrency})"),
+            None => "parallel".to_string(),
+        }
+    }
+
+    // Only the name of a global function can be reconstructed from the parsed function name,
+    // other (fully qualified) names are kept as written
+    pub(crate) fn simple_function_name(
+        site: &ParsedFunctionSite,
+        function: &DynamicParsedFunctionReference,
+    ) -> Option<String> {
+        match (site, function) {
+            (ParsedFunctionSite::Global, DynamicParsedFunctionReference::Function { function }) => {
+                Some(function.clone())
+            }
+            _ => None,
+        }
+    }
+
+    // The statements of the body of a list comprehension or aggregation,
+    // the last of which is yielded
+    pub(crate) fn yield_statements(yield_expr: &Expr) -> Vec<&Expr> {
+        match yield_expr {
+            Expr::ExprBlock { exprs, .. } => exprs.iter().collect(),
+            expr => vec![expr],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use crate::text::formatter::{format, FormatterConfig};
+
+    fn format_default(input: &str) -> String {
+        format(input, &FormatterConfig::default()).unwrap()
+    }
+
+    #[test]
+    fn test_format_statements() {
+        let input = r#"let x=1;   let y :u64 =  x+2;
+
+
+  {a : x, b: [y,   y]}"#;
+
+        let expected = r#"let x = 1;
+let y: u64 = x + 2;
+
+{a: x, b: [y, y]}
+"#;
+
+        assert_eq!(format_default(input), expected);
+    }
+
+    #[test]
+    fn test_format_keeps_comments() {
+        let input = r#"
+// the worker
+let worker = instance("cart"); // a trailing comment
+
+/* the result */
+worker.get-cart-contents()
+"#;
+
+        let expected = r#"// the worker
+let worker = instance("cart"); // a trailing comment
+
+/* the result */
+worker.get-cart-contents()
+"#;
+
+        assert_eq!(format_default(input), expected);
+    }
+
+    #[test]
+    fn test_format_pattern_match() {
+        let input = r#"let x = some(1); match x { some(value) => value, none => 0 }"#;
+
+        let expected = r#"let x = some(1);
+match x {
+    some(value) => value,
+    none => 0,
+}
+"#;
+
+        assert_eq!(format_default(input), expected);
+    }
+
+    #[test]
+    fn test_format_list_comprehension() {
+        let input = r#"let xs = [1, 2]; for x in xs { let y = x + 1; yield y; }"#;
+
+        let expected = r#"let xs = [1, 2];
+for x in xs {
+    let y = x + 1;
+    yield y;
+}
+"#;
+
+        assert_eq!(format_default(input), expected);
+    }
+
+    #[test]
+    fn test_format_breaks_long_expressions() {
+        let config = FormatterConfig {
+            indent_width: 2,
+            max_width: 20,
+        };
+
+        let input = r#"let x = {name: "shopping-cart", items: [1, 2, 3]}; x"#;
+
+        let expected = r#"let x = {
+  name: "shopping-cart",
+  items: [1, 2, 3]
+};
+x
+"#;
+
+        assert_eq!(format(input, &config).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_format_is_idempotent() {
+        let inputs = [
+            r#"let x = 1; if x > 1 then "a" else if x < 1 then "b" else "c""#,
+            r#"fn add(a: u64, b: u64) -> u64 { let c = a + b; c }; add(1, 2)"#,
+            r#"let xs = [1, 2, 3]; reduce acc, x in xs from 0 { yield acc + x; }"#,
+            r#"let f = |x| x + 1; let res: result<u64, string> = ok(1); res"#,
+        ];
+
+        for input in inputs {
+            let formatted = format_default(input);
+            assert_eq!(format_default(&formatted), formatted);
+        }
+    }
+
+    #[test]
+    fn test_format_invalid_program() {
+        assert!(format("let x = ", &FormatterConfig::default()).is_err());
+    }
+}
@@ -15,8 +15,10 @@
 use crate::expr::Expr;
 use crate::ArmPattern;
 
+mod formatter;
 mod writer;
 
+pub use crate::text::formatter::FormatterConfig;
 use crate::text::writer::WriterError;
 
 pub fn from_string(input: impl AsRef<str>) -> Result<Expr, String> {
@@ -34,6 +36,27 @@ pub fn from_string(input: impl AsRef<str>) -> Result<Expr, String> {
     }
 }
 
+/// Formats a Rib program with the default configuration, keeping its comments
+pub fn format(input: impl AsRef<str>) -> Result<String, String> {
+    format_with_config(input, &FormatterConfig::default())
+}
+
+pub fn format_with_config(
+    input: impl AsRef<str>,
+    config: &FormatterConfig,
+) -> Result<String, String> {
+    let trimmed = input.as_ref().trim();
+
+    // Same as in `from_string`, programs wrapped in `${..}` are unwrapped
+    match trimmed
+        .strip_prefix("${")
+        .and_then(|trimmed| trimmed.strip_suffix('}'))
+    {
+        Some(unwrapped) => formatter::format(unwrapped, config),
+        None => formatter::format(input.as_ref(), config),
+    }
+}
+
 pub fn to_string(expr: &Expr) -> Result<String, WriterError> {
     writer::write_expr(expr)
 }
@@ -373,6 +373,8 @@ impl<W: Write> Writer<W> {
                     }
                     CallType::VariantConstructor(name) => name.to_string(),
                     CallType::EnumConstructor(name) => name.to_string(),
+                    CallType::BuiltIn(function) => function.name().to_string(),
+                    CallType::UserDefined(name) => name.to_string(),
                     CallType::InstanceCreation(instance) => match instance {
                         InstanceCreationType::WitWorker { .. } => "instance".to_string(),
                         InstanceCreationType::WitResource { resource_name, .. } => {
@@ -390,7 +392,10 @@ impl<W: Write> Writer<W> {
                 }
 
                 match call_type {
-                    CallType::Function { .. } | CallType::InstanceCreation(_) => {
+                    CallType::Function { .. }
+                    | CallType::InstanceCreation(_)
+                    | CallType::BuiltIn(_)
+                    | CallType::UserDefined(_) => {
                         self.write_display("(")?;
                         for (idx, param) in args.iter().enumerate() {
                             if idx != 0 {
@@ -492,6 +497,84 @@ impl<W: Write> Writer<W> {
                 self.write_display(" } ")
             }
 
+            Expr::FunctionDefinition {
+                function_name,
+                parameters,
+                return_type,
+                body,
+                ..
+            } => {
+                self.write_str("fn ")?;
+                self.write_str(function_name)?;
+                self.write_display("(")?;
+                for (idx, parameter) in parameters.iter().enumerate() {
+                    if idx != 0 {
+                        self.write_display(",")?;
+                        self.write_display(" ")?;
+                    }
+                    self.write_str(parameter.variable_id.name())?;
+                    self.write_str(": ")?;
+                    self.write_display(&parameter.type_name)?;
+                }
+                self.write_display(")")?;
+                self.write_str(" -> ")?;
+                self.write_display(return_type)?;
+                self.write_display(" { ")?;
+                self.write_display("\n")?;
+                self.write_expr(body)?;
+                self.write_display("\n")?;
+                self.write_display(" } ")
+            }
+
+            Expr::Lambda {
+                parameters, body, ..
+            } => {
+                self.write_display("|")?;
+                for (idx, parameter) in parameters.iter().enumerate() {
+                    if idx != 0 {
+                        self.write_display(",")?;
+                        self.write_display(" ")?;
+                    }
+                    self.write_str(parameter.name())?;
+                }
+                self.write_display("|")?;
+                self.write_display(" ")?;
+                if let Expr::ExprBlock { .. } = body.as_ref() {
+                    self.write_display("{ ")?;
+                    self.write_expr(body)?;
+                    self.write_display(" }")
+                } else {
+                    self.write_expr(body)
+                }
+            }
+
+            Expr::Parallel {
+                expr,
+                max_concurrency,
+                ..
+            } => {
+                self.write_str("parallel")?;
+                if let Some(max_concurrency) = max_concurrency {
+                    self.write_display("(")?;
+                    self.write_display(max_concurrency)?;
+                    self.write_display(")")?;
+                }
+                match expr.as_ref() {
+                    Expr::Tuple { exprs, .. } => {
+                        self.write_display(" { ")?;
+                        for (idx, expr) in exprs.iter().enumerate() {
+                            if idx != 0 {
+                                self.write_display(",")?;
+                                self.write_display(" ")?;
+                            }
+                            self.write_expr(expr)?;
+                        }
+                        self.write_display(" }")
+                    }
+                    expr => self.write_expr(expr),
+                }
+            }
+
             Expr::InvokeMethodLazy {
                 lhs,
                 method,
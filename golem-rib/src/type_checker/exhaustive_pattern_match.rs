@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use crate::rib_source_span::SourceSpan;
-use crate::{ArmPattern, ComponentDependencies, Expr, ExprVisitor};
+use crate::{ArmPattern, ComponentDependencies, Expr, ExprVisitor, MatchArm};
 
 // When checking exhaustive pattern match, there is no need to ensure
 // if the pattern aligns with conditions because those checks are done
@@ -38,6 +38,36 @@ pub fn check_exhaustive_pattern_match(
     Ok(())
 }
 
+// Unlike a pattern following a wild card (which is a compilation error),
+// an arm can be unreachable because an earlier arm (that is not a wild card)
+// matches every value it matches. Example: the second `some(1)` in
+// `match x { some(1) => a, some(2) => b, some(1) => c, _ => d }`.
+// These are not compilation errors, and are reported as lints.
+pub fn find_unreachable_arms(match_arms: &[MatchArm]) -> Vec<UnreachableArm> {
+    let mut unreachable_arms = vec![];
+
+    for (arm_index, match_arm) in match_arms.iter().enumerate() {
+        let cause = match_arms[..arm_index]
+            .iter()
+            .find(|earlier| internal::covers(&earlier.arm_pattern, &match_arm.arm_pattern));
+
+        if let Some(cause) = cause {
+            unreachable_arms.push(UnreachableArm {
+                arm_index,
+                cause: cause.arm_pattern.clone(),
+            });
+        }
+    }
+
+    unreachable_arms
+}
+
+#[derive(Debug, Clone)]
+pub struct UnreachableArm {
+    pub arm_index: usize,
+    pub cause: ArmPattern,
+}
+
 #[derive(Debug, Clone)]
 pub enum ExhaustivePatternMatchError {
     MissingConstructors {
@@ -111,6 +141,94 @@ mod internal {
         Ok(())
     }
 
+    // Whether every value matched by the `later` pattern is matched by the `earlier` pattern
+    pub(crate) fn covers(earlier: &ArmPattern, later: &ArmPattern) -> bool {
+        if is_irrefutable(earlier) {
+            return true;
+        }
+
+        match (constructor_of(earlier), constructor_of(later)) {
+            (Some((earlier_name, earlier_args)), Some((later_name, later_args))) => {
+                return earlier_name == later_name
+                    && earlier_args.len() == later_args.len()
+                    && earlier_args
+                        .iter()
+                        .zip(later_args.iter())
+                        .all(|(earlier, later)| covers(earlier, later));
+            }
+            (Some(_), None) | (None, Some(_)) => return false,
+            (None, None) => {}
+        }
+
+        match (earlier, later) {
+            (ArmPattern::TupleConstructor(earlier), ArmPattern::TupleConstructor(later))
+            | (ArmPattern::ListConstructor(earlier), ArmPattern::ListConstructor(later)) => {
+                earlier.len() == later.len()
+                    && earlier
+                        .iter()
+                        .zip(later.iter())
+                        .all(|(earlier, later)| covers(earlier, later))
+            }
+            (ArmPattern::RecordConstructor(earlier), ArmPattern::RecordConstructor(later)) => {
+                earlier.len() == later.len()
+                    && earlier.iter().all(|(field, earlier)| {
+                        later
+                            .iter()
+                            .find(|(later_field, _)| later_field == field)
+                            .is_some_and(|(_, later)| covers(earlier, later))
+                    })
+            }
+            (ArmPattern::As(_, earlier), later) => covers(earlier, later),
+            (earlier, ArmPattern::As(_, later)) => covers(earlier, later),
+            // Literals such as `1` or `"foo"` cover only the same literal
+            (ArmPattern::Literal(_), ArmPattern::Literal(_)) => {
+                earlier.to_string() == later.to_string()
+            }
+            _ => false,
+        }
+    }
+
+    fn is_irrefutable(pattern: &ArmPattern) -> bool {
+        match pattern {
+            ArmPattern::WildCard => true,
+            ArmPattern::As(_, inner) => is_irrefutable(inner),
+            pattern => pattern.is_literal_identifier(),
+        }
+    }
+
+    // A constructor can be part of the pattern either as `ArmPattern::Constructor`
+    // or as a literal call expression, the same way it is handled in `check_exhaustive`
+    fn constructor_of(pattern: &ArmPattern) -> Option<(String, Vec<ArmPattern>)> {
+        match pattern {
+            ArmPattern::Constructor(name, arm_patterns) => {
+                Some((name.clone(), arm_patterns.clone()))
+            }
+            ArmPattern::As(_, inner) => constructor_of(inner),
+            ArmPattern::Literal(expr) => match expr.deref() {
+                Expr::Call {
+                    call_type, args, ..
+                } => Some((
+                    call_type.to_string(),
+                    args.iter()
+                        .map(|arg| ArmPattern::Literal(Box::new(arg.clone())))
+                        .collect(),
+                )),
+                Expr::Option {
+                    expr: Some(expr), ..
+                } => Some(("some".to_string(), vec![ArmPattern::Literal(expr.clone())])),
+                Expr::Option { expr: None, .. } => Some(("none".to_string(), vec![])),
+                Expr::Result { expr: Ok(expr), .. } => {
+                    Some(("ok".to_string(), vec![ArmPattern::Literal(expr.clone())]))
+                }
+                Expr::Result {
+                    expr: Err(expr), ..
+                } => Some(("err".to_string(), vec![ArmPattern::Literal(expr.clone())])),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct ConstructorPatterns(HashMap<String, Vec<ArmPattern>>);
 
@@ -411,6 +529,7 @@ mod internal {
 
 #[cfg(test)]
 mod pattern_match_exhaustive_tests {
+    use crate::type_checker::exhaustive_pattern_match::find_unreachable_arms;
     use crate::type_checker::exhaustive_pattern_match::pattern_match_exhaustive_tests::internal::strip_spaces;
     use crate::{Expr, RibCompiler};
     use test_r::test;
@@ -1065,6 +1184,52 @@ mod pattern_match_exhaustive_tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_unreachable_arms_duplicate_literal() {
+        let expr = r#"
+        match x {
+            1 => "one",
+            2 => "two",
+            1 => "uno",
+            _ => "many"
+        }
+        "#;
+
+        let Expr::PatternMatch { match_arms, .. } = Expr::from_text(expr).unwrap() else {
+            panic!("expected a pattern match")
+        };
+
+        let unreachable_arms = find_unreachable_arms(&match_arms)
+            .into_iter()
+            .map(|arm| (arm.arm_index, arm.cause.to_string()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(unreachable_arms, vec![(2, "1".to_string())]);
+    }
+
+    #[test]
+    fn test_unreachable_arms_covered_tuple() {
+        let expr = r#"
+        match x {
+            (1, _) => "first",
+            (1, 2) => "second",
+            (2, 1) => "third",
+            _ => "rest"
+        }
+        "#;
+
+        let Expr::PatternMatch { match_arms, .. } = Expr::from_text(expr).unwrap() else {
+            panic!("expected a pattern match")
+        };
+
+        let unreachable_arms = find_unreachable_arms(&match_arms)
+            .into_iter()
+            .map(|arm| arm.arm_index)
+            .collect::<Vec<_>>();
+
+        assert_eq!(unreachable_arms, vec![1]);
+    }
+
     mod internal {
         pub(crate) fn strip_spaces(input: &str) -> String {
             let lines = input.lines();
@@ -0,0 +1,55 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::call_type::CallType;
+use crate::{BuiltInFunction, CustomError, Expr};
+use std::collections::VecDeque;
+
+// Lambdas are not values in Rib. They can only be passed
+// to the list operations `map` and `filter`, which are compiled inline.
+pub fn check_invalid_lambdas(expr: &mut Expr) -> Result<(), CustomError> {
+    let mut queue = VecDeque::new();
+    queue.push_back((expr, false));
+
+    while let Some((expr, is_lambda_allowed)) = queue.pop_front() {
+        if let Expr::Lambda { source_span, .. } = expr {
+            if !is_lambda_allowed {
+                return Err(CustomError::new(
+                    source_span.clone(),
+                    "a lambda can only be passed to a list operation",
+                )
+                .with_help_message(
+                    "example: `map(list, |x| x + 1)` or `filter(list, |x| x > 1)`",
+                ));
+            }
+        }
+
+        let is_list_operation = matches!(
+            expr,
+            Expr::Call {
+                call_type: CallType::BuiltIn(BuiltInFunction::Map | BuiltInFunction::Filter),
+                ..
+            }
+        );
+
+        let mut children = VecDeque::new();
+        expr.visit_expr_nodes_lazy(&mut children);
+
+        for (index, child) in children.into_iter().enumerate() {
+            queue.push_back((child, is_list_operation && index == 1));
+        }
+    }
+
+    Ok(())
+}
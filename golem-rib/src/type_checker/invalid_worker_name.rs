@@ -37,6 +37,8 @@ pub fn check_invalid_worker_name(expr: &mut Expr) -> Result<(), InvalidWorkerNam
                 }
                 CallType::VariantConstructor(_) => {}
                 CallType::EnumConstructor(_) => {}
+                CallType::BuiltIn(_) => {}
+                CallType::UserDefined(_) => {}
                 CallType::InstanceCreation(InstanceCreationType::WitResource {
                     module, ..
                 }) => {
@@ -27,6 +27,14 @@ pub fn check_unresolved_types(expr: &Expr) -> Result<(), UnResolvedTypesError> {
                 queue.push_back(expr);
             }
 
+            Expr::FunctionDefinition { body, .. } => {
+                queue.push_back(body);
+            }
+
+            Expr::Lambda { body, .. } => {
+                queue.push_back(body);
+            }
+
             Expr::Range {
                 range,
                 inferred_type,
@@ -370,6 +378,12 @@ pub fn check_unresolved_types(expr: &Expr) -> Result<(), UnResolvedTypesError> {
                     CallType::EnumConstructor(name) => {
                         format!("cannot determine the type of the enum constructor `{name}`")
                     }
+                    CallType::BuiltIn(function) => {
+                        format!("cannot determine the return type of the built-in function `{function}`")
+                    }
+                    CallType::UserDefined(name) => {
+                        format!("cannot determine the return type of the function `{name}`")
+                    }
                     CallType::InstanceCreation(instance) => match instance {
                         InstanceCreationType::WitWorker { worker_name, .. } => {
                             let worker_name = worker_name
@@ -419,6 +433,18 @@ pub fn check_unresolved_types(expr: &Expr) -> Result<(), UnResolvedTypesError> {
                 }
             }
 
+            Expr::Parallel {
+                expr,
+                inferred_type,
+                ..
+            } => {
+                queue.push_back(expr);
+
+                if inferred_type.is_unknown() {
+                    return Err(UnResolvedTypesError::from(expr.source_span()));
+                }
+            }
+
             Expr::Length {
                 expr,
                 inferred_type,
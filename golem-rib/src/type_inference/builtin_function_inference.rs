@@ -0,0 +1,499 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::call_type::CallType;
+use crate::rib_source_span::SourceSpan;
+use crate::{
+    BuiltInFunction, ComponentDependencies, DynamicParsedFunctionReference, Expr, ExprVisitor,
+    FunctionCallError, FunctionName, InferredType, ParsedFunctionSite, TypeHint, TypeInternal,
+};
+use std::str::FromStr;
+
+// Initially every call type is a dynamic-parsed function name.
+// A global function call (i.e, not a method on an instance) whose name
+// is a built-in function is resolved to the built-in function.
+// This has to run after variant inference, such that a variant constructor
+// with the same name as a built-in function takes precedence.
+// Similarly, a function exported by a component with the same name as a built-in function
+// takes precedence, such that adding built-in functions never breaks existing Rib scripts.
+pub fn identify_builtin_function_calls(
+    expr: &mut Expr,
+    component_dependency: &ComponentDependencies,
+) {
+    let mut visitor = ExprVisitor::bottom_up(expr);
+
+    while let Some(expr) = visitor.pop_back() {
+        if let Expr::Call { call_type, .. } = expr {
+            if let CallType::Function {
+                function_name,
+                instance_identifier: None,
+                component_info: None,
+            } = call_type
+            {
+                if let (
+                    ParsedFunctionSite::Global,
+                    DynamicParsedFunctionReference::Function { function },
+                ) = (&function_name.site, &function_name.function)
+                {
+                    if let Ok(builtin_function) = BuiltInFunction::from_str(function) {
+                        let component_function =
+                            FunctionName::from_dynamic_parsed_function_name(function_name);
+
+                        let exported_by_component = component_dependency
+                            .function_dictionary()
+                            .iter()
+                            .any(|function_dict| function_dict.get(&component_function).is_some());
+
+                        if !exported_by_component {
+                            *call_type = CallType::BuiltIn(builtin_function);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Tags the argument types and the result type of a call to a built-in function.
+// Generic built-in functions (`unwrap-or`, `min`, `max`, `contains`) rely on the types of
+// their arguments, and therefore this is part of the inference fix point.
+pub(crate) fn infer_builtin_function_call_types(
+    source_span: &SourceSpan,
+    builtin_function: &BuiltInFunction,
+    args: &mut [Expr],
+    function_result_inferred_type: &mut InferredType,
+) -> Result<(), FunctionCallError> {
+    if args.len() != builtin_function.arity() {
+        return Err(FunctionCallError::ArgumentSizeMisMatch {
+            function_name: builtin_function.name().to_string(),
+            source_span: source_span.clone(),
+            expected: builtin_function.arity(),
+            provided: args.len(),
+        });
+    }
+
+    let result_type = match builtin_function {
+        BuiltInFunction::ToLower
+        | BuiltInFunction::ToUpper
+        | BuiltInFunction::Trim
+        | BuiltInFunction::Replace
+        | BuiltInFunction::Base64Encode
+        | BuiltInFunction::UrlEncode => {
+            internal::tag_all(builtin_function, args, InferredType::string())?;
+            InferredType::string()
+        }
+
+        BuiltInFunction::Split => {
+            internal::tag_all(builtin_function, args, InferredType::string())?;
+            InferredType::list(InferredType::string())
+        }
+
+        BuiltInFunction::StartsWith | BuiltInFunction::EndsWith => {
+            internal::tag_all(builtin_function, args, InferredType::string())?;
+            InferredType::bool()
+        }
+
+        BuiltInFunction::ParseU64 => {
+            internal::tag_all(builtin_function, args, InferredType::string())?;
+            InferredType::option(InferredType::u64())
+        }
+
+        BuiltInFunction::ParseS64 => {
+            internal::tag_all(builtin_function, args, InferredType::string())?;
+            InferredType::option(InferredType::s64())
+        }
+
+        BuiltInFunction::ParseF64 => {
+            internal::tag_all(builtin_function, args, InferredType::string())?;
+            InferredType::option(InferredType::f64())
+        }
+
+        BuiltInFunction::Base64Decode | BuiltInFunction::UrlDecode => {
+            internal::tag_all(builtin_function, args, InferredType::string())?;
+            InferredType::option(InferredType::string())
+        }
+
+        BuiltInFunction::Join => {
+            internal::tag_argument(
+                builtin_function,
+                &mut args[0],
+                InferredType::list(InferredType::string()),
+            )?;
+            internal::tag_argument(builtin_function, &mut args[1], InferredType::string())?;
+            InferredType::string()
+        }
+
+        BuiltInFunction::Len => {
+            internal::check_string_or_list(builtin_function, &args[0])?;
+            InferredType::u64()
+        }
+
+        BuiltInFunction::Contains => {
+            let element_type = match args[0].inferred_type().internal_type() {
+                TypeInternal::List(inner) => Some(inner.clone()),
+                _ => {
+                    internal::check_string_or_list(builtin_function, &args[0])?;
+
+                    if args[0].inferred_type().is_string() {
+                        Some(InferredType::string())
+                    } else {
+                        None
+                    }
+                }
+            };
+
+            if let Some(element_type) = element_type {
+                internal::tag_argument(builtin_function, &mut args[1], element_type)?;
+            }
+
+            InferredType::bool()
+        }
+
+        BuiltInFunction::UnwrapOr => {
+            let option_type = args[0].inferred_type();
+
+            match option_type.internal_type() {
+                TypeInternal::Option(inner) => {
+                    internal::tag_argument(builtin_function, &mut args[1], inner.clone())?;
+                    inner.clone()
+                }
+                TypeInternal::Unknown => {
+                    let default_type = args[1].inferred_type();
+
+                    if !default_type.is_unknown() {
+                        args[0].add_infer_type_mut(InferredType::option(default_type.clone()));
+                    }
+
+                    default_type
+                }
+                _ => {
+                    return Err(internal::type_mismatch(
+                        builtin_function,
+                        &args[0],
+                        TypeHint::Option(None),
+                    ))
+                }
+            }
+        }
+
+        BuiltInFunction::Min | BuiltInFunction::Max => {
+            let left = args[0].inferred_type();
+            let right = args[1].inferred_type();
+
+            if !left.is_unknown() {
+                internal::tag_argument(builtin_function, &mut args[1], left.clone())?;
+            }
+
+            if !right.is_unknown() {
+                internal::tag_argument(builtin_function, &mut args[0], right.clone())?;
+            }
+
+            args[0].inferred_type()
+        }
+
+        BuiltInFunction::Map => {
+            let element_type = internal::list_element_type(builtin_function, &args[0])?;
+            let body = internal::tag_lambda(builtin_function, &mut args[1], element_type)?;
+            InferredType::list(body.inferred_type())
+        }
+
+        BuiltInFunction::Filter => {
+            let element_type = internal::list_element_type(builtin_function, &args[0])?;
+            let body = internal::tag_lambda(builtin_function, &mut args[1], element_type)?;
+            body.add_infer_type_mut(InferredType::bool());
+            args[0].inferred_type()
+        }
+    };
+
+    *function_result_inferred_type = result_type;
+
+    Ok(())
+}
+
+mod internal {
+    use crate::inferred_type::TypeOrigin;
+    use crate::type_inference::GetTypeHint;
+    use crate::{
+        ActualType, BuiltInFunction, ExpectedType, Expr, ExprVisitor, FunctionCallError,
+        InferredType, TypeHint, TypeInternal, TypeMismatchError,
+    };
+
+    pub(crate) fn tag_all(
+        builtin_function: &BuiltInFunction,
+        args: &mut [Expr],
+        expected: InferredType,
+    ) -> Result<(), FunctionCallError> {
+        for arg in args.iter_mut() {
+            tag_argument(builtin_function, arg, expected.clone())?;
+        }
+
+        Ok(())
+    }
+
+    // A preliminary check of the argument, similar to worker function calls,
+    // before tagging the argument with the expected type
+    pub(crate) fn tag_argument(
+        builtin_function: &BuiltInFunction,
+        arg: &mut Expr,
+        expected: InferredType,
+    ) -> Result<(), FunctionCallError> {
+        let provided = arg.inferred_type();
+
+        let is_valid = provided.is_unknown()
+            || provided.is_all_of()
+            || expected.is_unknown()
+            || expected.is_all_of()
+            || provided.get_type_hint().get_type_kind() == expected.get_type_hint().get_type_kind();
+
+        if !is_valid {
+            return Err(type_mismatch(
+                builtin_function,
+                arg,
+                expected.get_type_hint(),
+            ));
+        }
+
+        arg.add_infer_type_mut(expected.add_origin(TypeOrigin::Declared(arg.source_span())));
+
+        Ok(())
+    }
+
+    pub(crate) fn list_element_type(
+        builtin_function: &BuiltInFunction,
+        arg: &Expr,
+    ) -> Result<Option<InferredType>, FunctionCallError> {
+        match arg.inferred_type().internal_type() {
+            TypeInternal::List(inner) => Ok(Some(inner.clone())),
+            TypeInternal::Unknown | TypeInternal::AllOf(_) => Ok(None),
+            _ => Err(type_mismatch(builtin_function, arg, TypeHint::List(None))),
+        }
+    }
+
+    // The lambda passed to a list operation takes a single parameter,
+    // which is the element of the list. Returns the body of the lambda.
+    pub(crate) fn tag_lambda<'a>(
+        builtin_function: &BuiltInFunction,
+        arg: &'a mut Expr,
+        parameter_type: Option<InferredType>,
+    ) -> Result<&'a mut Expr, FunctionCallError> {
+        let source_span = arg.source_span();
+
+        match arg {
+            Expr::Lambda {
+                parameters, body, ..
+            } if parameters.len() == 1 => {
+                if let Some(parameter_type) = parameter_type {
+                    let mut visitor = ExprVisitor::bottom_up(&mut **body);
+
+                    while let Some(expr) = visitor.pop_back() {
+                        if let Expr::Identifier {
+                            variable_id,
+                            inferred_type,
+                            ..
+                        } = expr
+                        {
+                            if variable_id == &parameters[0] {
+                                *inferred_type = inferred_type.merge(parameter_type.clone());
+                            }
+                        }
+                    }
+                }
+
+                Ok(&mut **body)
+            }
+            _ => Err(FunctionCallError::invalid_function_call(
+                builtin_function.name(),
+                source_span,
+                format!(
+                    "expected a lambda with a single parameter. expected signature: {}",
+                    builtin_function.signature()
+                ),
+            )),
+        }
+    }
+
+    pub(crate) fn check_string_or_list(
+        builtin_function: &BuiltInFunction,
+        arg: &Expr,
+    ) -> Result<(), FunctionCallError> {
+        match arg.inferred_type().internal_type() {
+            TypeInternal::Str
+            | TypeInternal::List(_)
+            | TypeInternal::Unknown
+            | TypeInternal::AllOf(_) => Ok(()),
+            _ => Err(type_mismatch(
+                builtin_function,
+                arg,
+                TypeHint::Ambiguous {
+                    possibilities: vec![TypeHint::Str, TypeHint::List(None)],
+                },
+            )),
+        }
+    }
+
+    pub(crate) fn type_mismatch(
+        builtin_function: &BuiltInFunction,
+        arg: &Expr,
+        expected: TypeHint,
+    ) -> FunctionCallError {
+        FunctionCallError::TypeMisMatch {
+            function_name: builtin_function.name().to_string(),
+            argument_source_span: arg.source_span(),
+            error: TypeMismatchError {
+                source_span: arg.source_span(),
+                expected_type: ExpectedType::Hint(expected),
+                actual_type: ActualType::Inferred(arg.inferred_type()),
+                field_path: Default::default(),
+                additional_error_detail: vec![format!(
+                    "expected signature: {}",
+                    builtin_function.signature()
+                )],
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use crate::call_type::CallType;
+    use crate::{
+        BuiltInFunction, ComponentDependencies, ComponentDependencyKey, Expr, InferredType,
+        RibCompiler,
+    };
+    use golem_wasm::analysis::{
+        AnalysedExport, AnalysedFunction, AnalysedFunctionParameter, AnalysedFunctionResult,
+        AnalysedType, TypeStr, TypeU32,
+    };
+    use uuid::Uuid;
+
+    // A component exporting a `trim` function, clashing with the built-in function
+    fn get_component_dependencies() -> ComponentDependencies {
+        let exports = vec![AnalysedExport::Function(AnalysedFunction {
+            name: "trim".to_string(),
+            parameters: vec![AnalysedFunctionParameter {
+                name: "input".to_string(),
+                typ: AnalysedType::Str(TypeStr),
+            }],
+            result: Some(AnalysedFunctionResult {
+                typ: AnalysedType::U32(TypeU32),
+            }),
+        })];
+
+        let component_info = ComponentDependencyKey {
+            component_name: "foo".to_string(),
+            component_id: Uuid::new_v4(),
+            component_version: 0,
+            root_package_name: None,
+            root_package_version: None,
+        };
+
+        ComponentDependencies::from_raw(vec![(component_info, &exports)])
+            .expect("Failed to create component dependencies")
+    }
+
+    #[test]
+    fn test_identify_builtin_function_calls() {
+        let mut expr = Expr::from_text(r#"to-lower(trim("  Foo  "))"#).unwrap();
+        expr.identify_builtin_function_calls(&ComponentDependencies::default());
+
+        let Expr::Call {
+            call_type, args, ..
+        } = &expr
+        else {
+            panic!("expected a call")
+        };
+
+        assert_eq!(call_type, &CallType::BuiltIn(BuiltInFunction::ToLower));
+
+        let Expr::Call { call_type, .. } = &args[0] else {
+            panic!("expected a call")
+        };
+
+        assert_eq!(call_type, &CallType::BuiltIn(BuiltInFunction::Trim));
+    }
+
+    #[test]
+    fn test_infer_builtin_function_types() {
+        let expr = Expr::from_text(
+            r#"
+              let x: string = "1,2,3";
+              let parts = split(x, ",");
+              let first = unwrap-or(parse-u64("42"), 0);
+              { parts: parts, first: first, size: len(parts), large: max(first, 10) }
+            "#,
+        )
+        .unwrap();
+
+        let compiler = RibCompiler::default();
+        let inferred_expr = compiler.infer_types(expr).unwrap();
+
+        assert_eq!(
+            inferred_expr.get_expr().inferred_type(),
+            InferredType::record(vec![
+                (
+                    "parts".to_string(),
+                    InferredType::list(InferredType::string())
+                ),
+                ("first".to_string(), InferredType::u64()),
+                ("size".to_string(), InferredType::u64()),
+                ("large".to_string(), InferredType::u64()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_builtin_function_argument_size_mismatch() {
+        let expr = Expr::from_text(r#"starts-with("foo")"#).unwrap();
+
+        let compiler = RibCompiler::default();
+        let error = compiler.infer_types(expr).unwrap_err();
+
+        assert!(error.to_string().contains("starts-with"));
+    }
+
+    #[test]
+    fn test_builtin_function_type_mismatch() {
+        let expr = Expr::from_text(r#"to-lower(1)"#).unwrap();
+
+        let compiler = RibCompiler::default();
+        let error = compiler.infer_types(expr).unwrap_err();
+
+        assert!(error.to_string().contains("to-lower"));
+    }
+
+    #[test]
+    fn test_component_function_takes_precedence_over_builtin_function() {
+        let component_dependencies = get_component_dependencies();
+
+        let mut expr = Expr::from_text(r#"to-lower(trim("  Foo  "))"#).unwrap();
+        expr.identify_builtin_function_calls(&component_dependencies);
+
+        let Expr::Call {
+            call_type, args, ..
+        } = &expr
+        else {
+            panic!("expected a call")
+        };
+
+        assert_eq!(call_type, &CallType::BuiltIn(BuiltInFunction::ToLower));
+
+        let Expr::Call { call_type, .. } = &args[0] else {
+            panic!("expected a call")
+        };
+
+        assert!(matches!(call_type, CallType::Function { .. }));
+    }
+}
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::call_type::CallType;
 use crate::{ComponentDependencies, CustomInstanceSpec, Expr, FunctionCallError};
 use std::collections::VecDeque;
 
@@ -45,6 +46,14 @@ pub fn infer_function_call_types(
                 inferred_type,
                 custom_instance_spec,
             )?;
+
+            // Arguments of built-in functions can themselves be calls, such as `to-lower(trim(x))`.
+            // Arguments of other calls are left as they were before built-in functions existed
+            if matches!(call_type, CallType::BuiltIn(_)) {
+                for arg in args.iter_mut() {
+                    visitor.push_back(arg);
+                }
+            }
         } else {
             expr.visit_expr_nodes_lazy(&mut visitor);
         }
@@ -57,6 +66,7 @@ mod internal {
     use crate::call_type::{CallType, InstanceCreationType};
     use crate::inferred_type::TypeOrigin;
     use crate::rib_source_span::SourceSpan;
+    use crate::type_inference::builtin_function_inference::infer_builtin_function_call_types;
     use crate::type_inference::GetTypeHint;
     use crate::{
         ActualType, ComponentDependencies, CustomInstanceSpec, DynamicParsedFunctionName,
@@ -152,6 +162,17 @@ mod internal {
                 }
             }
 
+            CallType::BuiltIn(builtin_function) => infer_builtin_function_call_types(
+                source_span,
+                builtin_function,
+                args,
+                function_result_inferred_type,
+            ),
+
+            // Arguments and the result of a user-defined function are tagged
+            // with the declared types when the call is identified
+            CallType::UserDefined(_) => Ok(()),
+
             CallType::VariantConstructor(variant_name) => {
                 let function_name = FunctionName::Variant(variant_name.clone());
                 infer_args_and_result_type(
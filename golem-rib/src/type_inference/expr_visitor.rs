@@ -249,6 +249,10 @@ fn enqueue_expr_top_down(expr: &mut Expr, queue: &mut VecDeque<&mut Expr>) {
                 stack.push_front(&mut **expr);
             }
 
+            Expr::FunctionDefinition { body, .. } => stack.push_front(&mut **body),
+            Expr::Lambda { body, .. } => stack.push_front(&mut **body),
+            Expr::Parallel { expr, .. } => stack.push_front(&mut **expr),
+
             Expr::Literal { .. } => {}
             Expr::Number { .. } => {}
             Expr::Flags { .. } => {}
@@ -432,6 +436,10 @@ fn enqueue_expr_bottom_up(expr: &mut Expr, queue: &mut VecDeque<&mut Expr>) {
                 stack.push_back(&mut **expr);
             }
 
+            Expr::FunctionDefinition { body, .. } => stack.push_back(&mut **body),
+            Expr::Lambda { body, .. } => stack.push_back(&mut **body),
+            Expr::Parallel { expr, .. } => stack.push_back(&mut **expr),
+
             Expr::Literal { .. } => {}
             Expr::Number { .. } => {}
             Expr::Flags { .. } => {}
@@ -605,6 +613,10 @@ pub fn visit_expr_nodes_lazy<'a>(expr: &'a mut Expr, queue: &mut VecDeque<&'a mu
             queue.push_back(&mut *expr);
         }
 
+        Expr::FunctionDefinition { body, .. } => queue.push_back(&mut *body),
+        Expr::Lambda { body, .. } => queue.push_back(&mut *body),
+        Expr::Parallel { expr, .. } => queue.push_back(&mut *expr),
+
         Expr::Literal { .. } => {}
         Expr::Number { .. } => {}
         Expr::Flags { .. } => {}
@@ -640,6 +652,8 @@ mod internal {
 
             CallType::VariantConstructor(_) => (None, None),
             CallType::EnumConstructor(_) => (None, None),
+            CallType::BuiltIn(_) => (None, None),
+            CallType::UserDefined(_) => (None, None),
         }
     }
 }
@@ -22,6 +22,7 @@ pub struct GlobalVariableTypeSpec {
     variable_id: VariableId,
     path: Path,
     inferred_type: InferredType,
+    exact: bool,
 }
 
 impl GlobalVariableTypeSpec {
@@ -60,6 +61,27 @@ impl GlobalVariableTypeSpec {
             variable_id: VariableId::global(variable_name.to_string()),
             path,
             inferred_type,
+            exact: false,
+        }
+    }
+
+    // Constructs a `GlobalVariableTypeSpec` where the inferred_type is applied to the element
+    // at the end of the `path` itself, rather than to its children.
+    // For example, if the path is `a.b` and the inferred type is `U64`, then the type of `a.b` will be `U64`.
+    //
+    // This is useful to refine the type of a single field that is already covered by a
+    // more general spec, such as `a.*`. Specs are applied in order, so the exact spec
+    // has to come after the general one.
+    pub fn exact(
+        variable_name: &str,
+        path: Path,
+        inferred_type: InferredType,
+    ) -> GlobalVariableTypeSpec {
+        GlobalVariableTypeSpec {
+            variable_id: VariableId::global(variable_name.to_string()),
+            path,
+            inferred_type,
+            exact: true,
         }
     }
 }
@@ -127,7 +149,14 @@ fn override_type(expr: &mut Expr, type_spec: &GlobalVariableTypeSpec) {
                             == Some(&PathElem::Field(field.to_string()))
                         {
                             current_path.progress();
-                            previous_expr_ptr = Some(expr as *const _);
+
+                            if type_spec.exact && current_path.is_empty() {
+                                *inferred_type = type_spec.inferred_type.clone();
+                                previous_expr_ptr = None;
+                                current_path = full_path.clone();
+                            } else {
+                                previous_expr_ptr = Some(expr as *const _);
+                            }
                         } else {
                             previous_expr_ptr = None;
                             current_path = full_path.clone();
@@ -167,6 +196,7 @@ mod tests {
             variable_id: VariableId::global("foo".to_string()),
             path: Path::default(),
             inferred_type: InferredType::string(),
+            exact: false,
         };
 
         expr.bind_global_variable_types(&vec![type_spec]);
@@ -190,6 +220,7 @@ mod tests {
             variable_id: VariableId::global("foo".to_string()),
             path: Path::from_elems(vec!["bar"]),
             inferred_type: InferredType::string(),
+            exact: false,
         };
 
         expr.bind_global_variable_types(&vec![type_spec]);
@@ -204,6 +235,40 @@ mod tests {
         assert_eq!(expr, expected);
     }
 
+    #[test]
+    fn test_override_types_exact() {
+        let mut expr = Expr::from_text(
+            r#"
+            foo.bar.baz
+        "#,
+        )
+        .unwrap();
+
+        let type_specs = vec![
+            GlobalVariableTypeSpec::new(
+                "foo",
+                Path::from_elems(vec!["bar"]),
+                InferredType::string(),
+            ),
+            GlobalVariableTypeSpec::exact(
+                "foo",
+                Path::from_elems(vec!["bar", "baz"]),
+                InferredType::u64(),
+            ),
+        ];
+
+        expr.bind_global_variable_types(&type_specs);
+
+        let expected = Expr::select_field(
+            Expr::select_field(Expr::identifier_global("foo", None), "bar", None),
+            "baz",
+            None,
+        )
+        .with_inferred_type(InferredType::u64());
+
+        assert_eq!(expr, expected);
+    }
+
     #[test]
     fn test_override_types_5() {
         let expr = Expr::from_text(
@@ -219,6 +284,7 @@ mod tests {
             variable_id: VariableId::global("foo".to_string()),
             path: Path::from_elems(vec!["bar"]),
             inferred_type: InferredType::string(),
+            exact: false,
         };
 
         let rib_compiler =
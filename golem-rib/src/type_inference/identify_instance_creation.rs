@@ -258,6 +258,9 @@ fn get_instance_creation_details(
                                     | Expr::Throw { .. }
                                     | Expr::GetTag { .. }
                                     | Expr::ListComprehension { .. }
+                                    | Expr::FunctionDefinition { .. }
+                                    | Expr::Lambda { .. }
+                                    | Expr::Parallel { .. }
                                     | Expr::Length { .. } => exprs.push(arg.clone()),
                                 }
 
@@ -292,6 +295,8 @@ fn get_instance_creation_details(
         }
         CallType::VariantConstructor(_) => Ok((None, None)),
         CallType::EnumConstructor(_) => Ok((None, None)),
+        CallType::BuiltIn(_) => Ok((None, None)),
+        CallType::UserDefined(_) => Ok((None, None)),
     }
 }
 
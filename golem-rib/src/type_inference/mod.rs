@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub use builtin_function_inference::*;
 pub use call_arguments_inference::*;
 pub use custom_instance_spec::*;
 pub use enum_inference::*;
@@ -34,10 +35,12 @@ pub use type_pull_up::*;
 pub use type_push_down::*;
 pub use type_reset::*;
 pub use type_unification::*;
+pub use user_defined_function_inference::*;
 pub use variable_binding::*;
 pub use variant_inference::*;
 pub use worker_function_invocation::*;
 
+mod builtin_function_inference;
 mod call_arguments_inference;
 mod custom_instance_spec;
 mod enum_inference;
@@ -60,6 +63,7 @@ mod type_pull_up;
 mod type_push_down;
 mod type_reset;
 mod type_unification;
+mod user_defined_function_inference;
 mod variable_binding;
 mod variant_inference;
 mod worker_function_invocation;
@@ -216,6 +216,15 @@ pub fn type_pull_up(
             Expr::Length { .. } => {}
             Expr::Throw { .. } => {}
             Expr::GenerateWorkerName { .. } => {}
+            Expr::FunctionDefinition { .. } => {}
+            Expr::Lambda { .. } => {}
+            Expr::Parallel {
+                expr,
+                inferred_type,
+                ..
+            } => {
+                *inferred_type = inferred_type.merge(expr.inferred_type());
+            }
             Expr::ListComprehension {
                 yield_expr,
                 inferred_type,
@@ -172,6 +172,16 @@ pub fn push_types_down(expr: &mut Expr) -> Result<(), RibTypeErrorInternal> {
                 )?;
             }
 
+            // The branches of a parallel expression are the elements of a tuple,
+            // or the iterations of a list comprehension
+            Expr::Parallel {
+                expr,
+                inferred_type,
+                ..
+            } => {
+                expr.add_infer_type_mut(inferred_type.clone());
+            }
+
             Expr::ListReduce {
                 reduce_variable,
                 iterated_variable,
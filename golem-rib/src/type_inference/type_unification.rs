@@ -26,6 +26,8 @@ pub fn unify_types(expr: &mut Expr) -> Result<(), TypeUnificationError> {
     while let Some(sub_expr) = visitor.pop_front() {
         match sub_expr {
             Expr::Let { .. } => {}
+            Expr::FunctionDefinition { .. } => {}
+            Expr::Lambda { .. } => {}
             Expr::Boolean { .. } => {}
             Expr::Concat { .. } => {}
             Expr::GreaterThan { .. } => {}
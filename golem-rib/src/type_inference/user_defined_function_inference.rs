@@ -0,0 +1,321 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::call_type::CallType;
+use crate::rib_type_error::RibTypeErrorInternal;
+use crate::{
+    CustomError, DynamicParsedFunctionReference, Expr, ExprVisitor, FunctionCallError,
+    InferredType, ParsedFunctionSite, TypeName, VariableId,
+};
+use std::collections::{HashMap, HashSet};
+
+// Functions defined in Rib (`fn name(args) -> type { ... }`) are explicitly typed,
+// and therefore the parameters, the body and every call to the function
+// can be tagged with the declared types before the inference fix point.
+// A global function call whose name is a user-defined function is resolved to it.
+// This runs before identifying built-in function calls, such that a user-defined
+// function with the same name as a built-in function takes precedence.
+pub fn infer_user_defined_functions(expr: &mut Expr) -> Result<(), RibTypeErrorInternal> {
+    let signatures = internal::collect_signatures(expr)?;
+
+    if signatures.is_empty() {
+        return Ok(());
+    }
+
+    internal::tag_function_definitions(expr)?;
+    internal::identify_user_defined_function_calls(expr, &signatures)?;
+
+    Ok(())
+}
+
+mod internal {
+    use super::*;
+
+    pub(crate) struct FunctionSignature {
+        pub(crate) parameter_types: Vec<TypeName>,
+        pub(crate) return_type: TypeName,
+    }
+
+    pub(crate) fn collect_signatures(
+        expr: &mut Expr,
+    ) -> Result<HashMap<String, FunctionSignature>, RibTypeErrorInternal> {
+        let mut signatures = HashMap::new();
+        let mut visitor = ExprVisitor::top_down(expr);
+
+        while let Some(expr) = visitor.pop_front() {
+            if let Expr::FunctionDefinition {
+                function_name,
+                parameters,
+                return_type,
+                source_span,
+                ..
+            } = expr
+            {
+                let signature = FunctionSignature {
+                    parameter_types: parameters
+                        .iter()
+                        .map(|parameter| parameter.type_name.clone())
+                        .collect(),
+                    return_type: return_type.clone(),
+                };
+
+                if signatures
+                    .insert(function_name.clone(), signature)
+                    .is_some()
+                {
+                    return Err(CustomError::new(
+                        source_span.clone(),
+                        format!("function `{function_name}` is defined more than once"),
+                    )
+                    .with_help_message("rename one of the functions")
+                    .into());
+                }
+            }
+        }
+
+        Ok(signatures)
+    }
+
+    pub(crate) fn tag_function_definitions(expr: &mut Expr) -> Result<(), RibTypeErrorInternal> {
+        let mut visitor = ExprVisitor::top_down(expr);
+
+        while let Some(expr) = visitor.pop_front() {
+            if let Expr::FunctionDefinition {
+                function_name,
+                parameters,
+                return_type,
+                body,
+                ..
+            } = expr
+            {
+                let parameter_types: HashMap<VariableId, InferredType> = parameters
+                    .iter()
+                    .map(|parameter| {
+                        (
+                            parameter.variable_id.clone(),
+                            InferredType::from(&parameter.type_name),
+                        )
+                    })
+                    .collect();
+
+                let mut bound_variables = parameter_types.keys().cloned().collect::<HashSet<_>>();
+                collect_bound_variables(body, &mut bound_variables);
+
+                let mut body_visitor = ExprVisitor::bottom_up(&mut **body);
+
+                while let Some(expr) = body_visitor.pop_front() {
+                    if let Expr::Identifier {
+                        variable_id,
+                        inferred_type,
+                        source_span,
+                        ..
+                    } = expr
+                    {
+                        if let Some(parameter_type) = parameter_types.get(variable_id) {
+                            *inferred_type = inferred_type
+                                .merge(parameter_type.declared_at(source_span.clone()));
+                        } else if (variable_id.is_local() || variable_id.is_function_parameter())
+                            && !bound_variables.contains(variable_id)
+                        {
+                            return Err(CustomError::new(
+                                source_span.clone(),
+                                format!(
+                                    "function `{function_name}` cannot refer to `{variable_id}`, which is defined outside of the function"
+                                ),
+                            )
+                            .with_help_message(format!(
+                                "pass `{variable_id}` as an argument to `{function_name}`"
+                            ))
+                            .into());
+                        }
+                    }
+                }
+
+                let body_source_span = body.source_span();
+                body.add_infer_type_mut(
+                    InferredType::from(&*return_type).declared_at(body_source_span),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    // Variables that are introduced within the body of a function,
+    // and are therefore available when the function is called
+    fn collect_bound_variables(body: &mut Expr, bound_variables: &mut HashSet<VariableId>) {
+        let mut visitor = ExprVisitor::top_down(body);
+
+        while let Some(expr) = visitor.pop_front() {
+            match expr {
+                Expr::Let { variable_id, .. } => {
+                    bound_variables.insert(variable_id.clone());
+                }
+                Expr::FunctionDefinition { parameters, .. } => {
+                    bound_variables.extend(
+                        parameters
+                            .iter()
+                            .map(|parameter| parameter.variable_id.clone()),
+                    );
+                }
+                Expr::Lambda { parameters, .. } => {
+                    bound_variables.extend(parameters.iter().cloned());
+                }
+                _ => {}
+            }
+        }
+    }
+
+    pub(crate) fn identify_user_defined_function_calls(
+        expr: &mut Expr,
+        signatures: &HashMap<String, FunctionSignature>,
+    ) -> Result<(), RibTypeErrorInternal> {
+        let mut visitor = ExprVisitor::bottom_up(expr);
+
+        while let Some(expr) = visitor.pop_back() {
+            if let Expr::Call {
+                call_type,
+                args,
+                inferred_type,
+                source_span,
+                ..
+            } = expr
+            {
+                let Some(function_name) = user_defined_function_name(call_type, signatures) else {
+                    continue;
+                };
+
+                let signature = &signatures[&function_name];
+
+                if args.len() != signature.parameter_types.len() {
+                    return Err(FunctionCallError::ArgumentSizeMisMatch {
+                        function_name,
+                        source_span: source_span.clone(),
+                        expected: signature.parameter_types.len(),
+                        provided: args.len(),
+                    }
+                    .into());
+                }
+
+                for (arg, parameter_type) in args.iter_mut().zip(&signature.parameter_types) {
+                    let arg_source_span = arg.source_span();
+                    arg.add_infer_type_mut(
+                        InferredType::from(parameter_type).declared_at(arg_source_span),
+                    );
+                }
+
+                *inferred_type = inferred_type.merge(InferredType::from(&signature.return_type));
+                *call_type = CallType::UserDefined(function_name);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn user_defined_function_name(
+        call_type: &CallType,
+        signatures: &HashMap<String, FunctionSignature>,
+    ) -> Option<String> {
+        match call_type {
+            CallType::Function {
+                function_name,
+                instance_identifier: None,
+                component_info: None,
+            } => match (&function_name.site, &function_name.function) {
+                (
+                    ParsedFunctionSite::Global,
+                    DynamicParsedFunctionReference::Function { function },
+                ) if signatures.contains_key(function) => Some(function.clone()),
+                _ => None,
+            },
+            CallType::UserDefined(name) if signatures.contains_key(name) => Some(name.clone()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use crate::call_type::CallType;
+    use crate::{Expr, RibCompiler};
+
+    #[test]
+    fn test_identify_user_defined_function_calls() {
+        let mut expr = Expr::from_text(
+            r#"
+              fn double(x: u64) -> u64 { x * 2 };
+              double(21)
+            "#,
+        )
+        .unwrap();
+
+        expr.bind_variables_of_function_parameters();
+        expr.infer_user_defined_functions().unwrap();
+
+        let Expr::ExprBlock { exprs, .. } = &expr else {
+            panic!("expected an expression block");
+        };
+
+        let Expr::Call { call_type, .. } = &exprs[1] else {
+            panic!("expected a call");
+        };
+
+        assert_eq!(call_type, &CallType::UserDefined("double".to_string()));
+    }
+
+    #[test]
+    fn test_user_defined_function_shadows_builtin() {
+        let expr = Expr::from_text(
+            r#"
+              fn trim(x: string) -> string { x };
+              trim(" foo ")
+            "#,
+        )
+        .unwrap();
+
+        assert!(RibCompiler::default().compile(expr).is_ok());
+    }
+
+    #[test]
+    fn test_user_defined_function_argument_size_mismatch() {
+        let expr = Expr::from_text(
+            r#"
+              fn add(x: u64, y: u64) -> u64 { x + y };
+              add(1)
+            "#,
+        )
+        .unwrap();
+
+        assert!(RibCompiler::default().compile(expr).is_err());
+    }
+
+    #[test]
+    fn test_user_defined_function_cannot_capture_local_variables() {
+        let expr = Expr::from_text(
+            r#"
+              let offset: u64 = 10;
+              fn add-offset(x: u64) -> u64 { x + offset };
+              add-offset(1)
+            "#,
+        )
+        .unwrap();
+
+        let error = RibCompiler::default().compile(expr).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("cannot refer to `offset`, which is defined outside of the function"));
+    }
+}
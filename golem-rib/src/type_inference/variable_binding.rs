@@ -32,7 +32,9 @@ pub fn bind_variables_of_let_assignment(expr: &mut Expr) {
                 }
             }
 
-            Expr::Identifier { variable_id, .. } if !variable_id.is_match_binding() => {
+            Expr::Identifier { variable_id, .. }
+                if !variable_id.is_match_binding() && !variable_id.is_function_parameter() =>
+            {
                 let field_name = variable_id.name();
                 if let Some(latest_variable_id) = identifier_id_state.lookup(&field_name) {
                     *variable_id = latest_variable_id.clone();
@@ -85,6 +87,41 @@ pub fn bind_variables_of_list_reduce(expr: &mut Expr) {
     }
 }
 
+// Every function definition and lambda is given a unique index, and the identifiers
+// in its body referring to a parameter are tagged with that index. Visiting top-down
+// ensures that a parameter of an inner lambda shadows the one of the enclosing function.
+pub fn bind_variables_of_function_parameters(expr: &mut Expr) {
+    let mut function_index = 0;
+    let mut visitor = ExprVisitor::top_down(expr);
+
+    while let Some(expr) = visitor.pop_front() {
+        match expr {
+            Expr::FunctionDefinition {
+                parameters, body, ..
+            } => {
+                function_index += 1;
+
+                let mut variables = parameters
+                    .iter_mut()
+                    .map(|parameter| &mut parameter.variable_id)
+                    .collect::<Vec<_>>();
+
+                process_function_body(&mut variables, function_index, body);
+            }
+            Expr::Lambda {
+                parameters, body, ..
+            } => {
+                function_index += 1;
+
+                let mut variables = parameters.iter_mut().collect::<Vec<_>>();
+
+                process_function_body(&mut variables, function_index, body);
+            }
+            _ => {}
+        }
+    }
+}
+
 pub fn bind_variables_of_pattern_match(expr: &mut Expr) {
     bind_variables_in_pattern_match_internal(expr, 0, &mut []);
 }
@@ -227,6 +264,31 @@ fn process_yield_expr_in_comprehension(variable: &mut VariableId, yield_expr: &m
     }
 }
 
+fn process_function_body(
+    parameters: &mut [&mut VariableId],
+    function_index: usize,
+    body: &mut Expr,
+) {
+    for parameter in parameters.iter_mut() {
+        **parameter = VariableId::function_parameter(parameter.name(), function_index);
+    }
+
+    let mut visitor = ExprVisitor::top_down(body);
+
+    while let Some(expr) = visitor.pop_front() {
+        if let Expr::Identifier { variable_id, .. } = expr {
+            if variable_id.is_global() || variable_id.is_function_parameter() {
+                if let Some(parameter) = parameters
+                    .iter()
+                    .find(|parameter| parameter.name() == variable_id.name())
+                {
+                    *variable_id = (**parameter).clone();
+                }
+            }
+        }
+    }
+}
+
 fn process_yield_expr_in_reduce(
     reduce_variable: &mut VariableId,
     iterated_variable_id: &mut VariableId,
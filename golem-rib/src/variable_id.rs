@@ -26,6 +26,7 @@ pub enum VariableId {
     MatchIdentifier(MatchIdentifier),
     ListComprehension(ListComprehensionIdentifier),
     ListReduce(ListAggregationIdentifier),
+    FunctionParameter(FunctionParameterIdentifier),
 }
 
 impl VariableId {
@@ -42,6 +43,7 @@ impl VariableId {
             VariableId::MatchIdentifier(m) => format!("{}-{}", m.name, m.match_arm_index),
             VariableId::ListComprehension(l) => l.name.clone(),
             VariableId::ListReduce(r) => r.name.clone(),
+            VariableId::FunctionParameter(p) => format!("{}-{}", p.name, p.function_index),
         };
 
         VariableId::global(format!("__instance_{variable_string}"))
@@ -58,6 +60,13 @@ impl VariableId {
         })
     }
 
+    pub fn function_parameter(name: impl AsRef<str>, function_index: usize) -> VariableId {
+        VariableId::FunctionParameter(FunctionParameterIdentifier {
+            name: name.as_ref().to_string(),
+            function_index,
+        })
+    }
+
     pub fn match_identifier(name: String, match_arm_index: usize) -> VariableId {
         VariableId::MatchIdentifier(MatchIdentifier {
             name,
@@ -72,6 +81,7 @@ impl VariableId {
             VariableId::MatchIdentifier(m) => m.name.clone(),
             VariableId::ListComprehension(l) => l.name.clone(),
             VariableId::ListReduce(r) => r.name.clone(),
+            VariableId::FunctionParameter(p) => p.name.clone(),
         }
     }
 
@@ -82,6 +92,7 @@ impl VariableId {
             VariableId::MatchIdentifier(_) => false,
             VariableId::ListComprehension(_) => false,
             VariableId::ListReduce(_) => false,
+            VariableId::FunctionParameter(_) => false,
         }
     }
 
@@ -92,6 +103,7 @@ impl VariableId {
             VariableId::MatchIdentifier(_) => false,
             VariableId::ListComprehension(_) => false,
             VariableId::ListReduce(_) => false,
+            VariableId::FunctionParameter(_) => false,
         }
     }
 
@@ -102,9 +114,14 @@ impl VariableId {
             VariableId::MatchIdentifier(_) => true,
             VariableId::ListComprehension(_) => false,
             VariableId::ListReduce(_) => false,
+            VariableId::FunctionParameter(_) => false,
         }
     }
 
+    pub fn is_function_parameter(&self) -> bool {
+        matches!(self, VariableId::FunctionParameter(_))
+    }
+
     // Default variable_id could global, but as soon as type inference
     // identifies them to be local it gets converted to a local with an id
     pub fn global(variable_name: String) -> VariableId {
@@ -133,6 +150,7 @@ impl VariableId {
             VariableId::MatchIdentifier(m) => VariableId::MatchIdentifier(m.clone()),
             VariableId::ListComprehension(l) => VariableId::ListComprehension(l.clone()),
             VariableId::ListReduce(l) => VariableId::ListReduce(l.clone()),
+            VariableId::FunctionParameter(p) => VariableId::FunctionParameter(p.clone()),
         }
     }
 }
@@ -153,6 +171,15 @@ pub struct ListAggregationIdentifier {
     pub name: String,
 }
 
+#[derive(
+    Hash, Eq, Debug, Clone, PartialEq, Serialize, Deserialize, BinaryCodec, Ord, PartialOrd,
+)]
+#[desert(evolution())]
+pub struct FunctionParameterIdentifier {
+    pub name: String,
+    pub function_index: usize, // Every function definition and lambda across the program is identified by a non-sharing index value
+}
+
 #[derive(
     Hash, Eq, Debug, Clone, PartialEq, Serialize, Deserialize, BinaryCodec, Ord, PartialOrd,
 )]
@@ -179,6 +206,7 @@ impl Display for VariableId {
             VariableId::MatchIdentifier(m) => write!(f, "{}", m.name),
             VariableId::ListComprehension(l) => write!(f, "{}", l.name),
             VariableId::ListReduce(r) => write!(f, "{}", r.name),
+            VariableId::FunctionParameter(p) => write!(f, "{}", p.name),
         }
     }
 }
@@ -223,6 +251,14 @@ mod protobuf {
                 ) => Ok(VariableId::ListReduce(crate::ListAggregationIdentifier {
                     name: list_aggregation.name,
                 })),
+                crate::proto::golem::rib::variable_id::VariableId::FunctionParameterIdentifier(
+                    function_parameter,
+                ) => Ok(VariableId::FunctionParameter(
+                    crate::FunctionParameterIdentifier {
+                        name: function_parameter.name,
+                        function_index: function_parameter.function_index as usize,
+                    },
+                )),
             }
         }
     }
@@ -275,6 +311,16 @@ mod protobuf {
                         ),
                     ),
                 },
+                VariableId::FunctionParameter(p) => ProtoVariableId {
+                    variable_id: Some(
+                        crate::proto::golem::rib::variable_id::VariableId::FunctionParameterIdentifier(
+                            crate::proto::golem::rib::FunctionParameterIdentifier {
+                                name: p.name,
+                                function_index: p.function_index as u32,
+                            },
+                        ),
+                    ),
+                },
             }
         }
     }
@@ -76,6 +76,17 @@ impl TraceContextHeaders {
         }
     }
 
+    /// Starts a new trace, for requests that arrive without a trace context
+    pub fn generate() -> TraceContextHeaders {
+        Self {
+            version: 0,
+            trace_id: TraceId::generate(),
+            parent_id: SpanId::generate(),
+            trace_flags: TraceFlags::empty(),
+            trace_states: Vec::new(),
+        }
+    }
+
     pub fn from_invocation_context(
         invocation_context: InvocationContextStack,
     ) -> TraceContextHeaders {
@@ -28,7 +28,25 @@ pub async fn start_health_and_metrics_server(
     body_message: &'static str,
     join_set: &mut JoinSet<Result<(), anyhow::Error>>,
 ) -> Result<u16, anyhow::Error> {
-    let app = Router::new()
+    start_health_and_metrics_server_with_routes(
+        addr,
+        registry,
+        body_message,
+        Router::new(),
+        join_set,
+    )
+    .await
+}
+
+/// Starts the health and metrics server, also serving the given service specific routes
+pub async fn start_health_and_metrics_server_with_routes(
+    addr: impl ToSocketAddrs,
+    registry: Registry,
+    body_message: &'static str,
+    routes: Router,
+    join_set: &mut JoinSet<Result<(), anyhow::Error>>,
+) -> Result<u16, anyhow::Error> {
+    let app = routes
         .route("/healthcheck", get(move || async move { body_message }))
         .route(
             "/metrics",
@@ -15,6 +15,7 @@
 use golem_api_grpc::proto::golem;
 use golem_api_grpc::proto::golem::shardmanager::v1::shard_manager_error;
 use golem_common::metrics::api::TraceErrorKind;
+use golem_common::model::ShardId;
 use golem_common::retriable_error::IsRetriableError;
 use golem_service_base::error::worker_executor::WorkerExecutorError;
 use std::fmt::Debug;
@@ -40,6 +41,14 @@ pub enum ShardManagerError {
     RedisError(#[from] golem_common::redis::RedisError),
     #[error("IO error {0}")]
     IoError(#[from] std::io::Error),
+    #[error("This shard manager replica is not the leader")]
+    NotLeader,
+    #[error("Cannot reshard from {current} to {requested} shards: the new number of shards must be a multiple of the current one")]
+    InvalidNumberOfShards { current: usize, requested: usize },
+    #[error("Unknown pod: {0}")]
+    UnknownPod(String),
+    #[error("Unknown shard: {0}")]
+    UnknownShard(ShardId),
 }
 
 impl IsRetriableError for ShardManagerError {
@@ -54,6 +63,10 @@ impl IsRetriableError for ShardManagerError {
             ShardManagerError::SerializationError(_) => false,
             ShardManagerError::RedisError(_) => false,
             ShardManagerError::IoError(_) => false,
+            ShardManagerError::NotLeader => true,
+            ShardManagerError::InvalidNumberOfShards { .. } => false,
+            ShardManagerError::UnknownPod(_) => false,
+            ShardManagerError::UnknownShard(_) => false,
         }
     }
 
@@ -101,6 +114,18 @@ impl From<ShardManagerError> for golem::shardmanager::v1::ShardManagerError {
             ShardManagerError::IoError(err) => {
                 error(shard_manager_error::Error::Unknown, err.to_string())
             }
+            ShardManagerError::NotLeader => {
+                error(shard_manager_error::Error::Unknown, "NotLeader".to_string())
+            }
+            err @ ShardManagerError::InvalidNumberOfShards { .. } => {
+                error(shard_manager_error::Error::InvalidRequest, err.to_string())
+            }
+            err @ ShardManagerError::UnknownPod(_) => {
+                error(shard_manager_error::Error::InvalidRequest, err.to_string())
+            }
+            err @ ShardManagerError::UnknownShard(_) => {
+                error(shard_manager_error::Error::InvalidRequest, err.to_string())
+            }
         }
     }
 }
@@ -13,20 +13,44 @@
 // limitations under the License.
 
 use crate::error::HealthCheckError;
-use crate::model::Pod;
+use crate::model::{Pod, ShardLoad};
 use crate::worker_executor::WorkerExecutorService;
 use async_trait::async_trait;
-use golem_common::model::RetryConfig;
+use golem_common::model::{RetryConfig, ShardId};
 use golem_common::retriable_error::IsRetriableError;
 use golem_common::retries::with_retries_customized;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
 
+/// The health of a pod, with the loads of its shards if it could report them
+pub enum PodHealth {
+    Healthy(Option<BTreeMap<ShardId, ShardLoad>>),
+    Unhealthy,
+}
+
 #[async_trait]
 pub trait HealthCheck {
     async fn health_check(&self, pod: &Pod) -> bool;
+
+    /// Checks the health of a pod and collects the loads of its shards
+    async fn health_check_with_shard_loads(
+        &self,
+        pod: &Pod,
+        worker_executors: Arc<dyn WorkerExecutorService + Send + Sync>,
+    ) -> PodHealth {
+        if self.health_check(pod).await {
+            PodHealth::Healthy(worker_executors.get_shard_loads(pod).await.ok())
+        } else {
+            PodHealth::Unhealthy
+        }
+    }
+
+    /// Discovers the availability zone of a pod which did not report it on registration
+    async fn zone(&self, _pod: &Pod) -> Option<String> {
+        None
+    }
 }
 
 /// Executes healthcheck on all the given worker executors, and returns a set of unhealthy ones
@@ -53,6 +77,50 @@ pub async fn get_unhealthy_pods(
         .collect()
 }
 
+/// Executes healthcheck on all the given worker executors, collecting their shard loads at the same
+/// time. Returns the set of unhealthy ones, and the shard loads of the healthy ones, if all of them
+/// could report them.
+pub async fn get_unhealthy_pods_with_shard_loads(
+    health_check: Arc<dyn HealthCheck + Send + Sync>,
+    worker_executors: Arc<dyn WorkerExecutorService + Send + Sync>,
+    pods: &HashSet<Pod>,
+) -> (
+    HashSet<Pod>,
+    Option<BTreeMap<Pod, BTreeMap<ShardId, ShardLoad>>>,
+) {
+    let futures: Vec<_> = pods
+        .iter()
+        .map(|pod| {
+            let health_check = health_check.clone();
+            let worker_executors = worker_executors.clone();
+            Box::pin(async move {
+                let health = health_check
+                    .health_check_with_shard_loads(pod, worker_executors)
+                    .await;
+                (pod.clone(), health)
+            })
+        })
+        .collect();
+
+    let mut unhealthy_pods = HashSet::new();
+    let mut shard_loads = Some(BTreeMap::new());
+    for (pod, health) in futures::future::join_all(futures).await {
+        match health {
+            PodHealth::Healthy(Some(pod_shard_loads)) => {
+                if let Some(shard_loads) = &mut shard_loads {
+                    shard_loads.insert(pod, pod_shard_loads);
+                }
+            }
+            // The loads of the pod's shards would be missing
+            PodHealth::Healthy(None) => shard_loads = None,
+            PodHealth::Unhealthy => {
+                unhealthy_pods.insert(pod);
+            }
+        }
+    }
+    (unhealthy_pods, shard_loads)
+}
+
 async fn health_check_with_retries<F>(
     target: &'static str,
     implementation: F,
@@ -116,16 +184,39 @@ impl HealthCheck for GrpcHealthCheck {
         )
         .await
     }
+
+    /// The shard loads report doubles as the health check, so a healthy pod gets a single request
+    async fn health_check_with_shard_loads(
+        &self,
+        pod: &Pod,
+        _worker_executors: Arc<dyn WorkerExecutorService + Send + Sync>,
+    ) -> PodHealth {
+        match self.worker_executors.get_shard_loads(pod).await {
+            Ok(shard_loads) => PodHealth::Healthy(Some(shard_loads)),
+            // The pod may still be healthy, just unable to report its loads
+            Err(_) => {
+                if self.health_check(pod).await {
+                    PodHealth::Healthy(None)
+                } else {
+                    PodHealth::Unhealthy
+                }
+            }
+        }
+    }
 }
 
 #[cfg(feature = "kubernetes")]
 pub mod kubernetes {
     use async_trait::async_trait;
-    use k8s_openapi::api::core::v1::{Pod, PodStatus};
+    use k8s_openapi::api::core::v1::{Node, Pod, PodStatus};
     use kube::{Api, Client};
+    use tracing::warn;
 
     use golem_common::model::RetryConfig;
 
+    /// The well-known node label holding the availability zone of the node
+    const ZONE_LABEL: &str = "topology.kubernetes.io/zone";
+
     use crate::healthcheck::{health_check_with_retries, HealthCheck, HealthCheckError};
 
     #[derive(Clone)]
@@ -169,6 +260,33 @@ pub mod kubernetes {
             }
         }
 
+        /// Reads the zone label of the node the pod is scheduled to. This requires the shard
+        /// manager to have permission to get nodes.
+        async fn zone_impl(&self, pod: &crate::model::Pod) -> Result<Option<String>, kube::Error> {
+            let Some(pod_name) = &pod.pod_name else {
+                return Ok(None);
+            };
+
+            let pods: Api<Pod> = Api::namespaced(self.client.clone(), &self.namespace);
+            let node_name = pods
+                .get_opt(pod_name)
+                .await?
+                .and_then(|k8s_pod| k8s_pod.spec)
+                .and_then(|spec| spec.node_name);
+
+            match node_name {
+                Some(node_name) => {
+                    let nodes: Api<Node> = Api::all(self.client.clone());
+                    Ok(nodes
+                        .get_opt(&node_name)
+                        .await?
+                        .and_then(|node| node.metadata.labels)
+                        .and_then(|labels| labels.get(ZONE_LABEL).cloned()))
+                }
+                None => Ok(None),
+            }
+        }
+
         fn is_pod_ready(pod_status: PodStatus) -> bool {
             pod_status
                 .conditions
@@ -193,5 +311,15 @@ pub mod kubernetes {
             )
             .await
         }
+
+        async fn zone(&self, pod: &crate::model::Pod) -> Option<String> {
+            match self.zone_impl(pod).await {
+                Ok(zone) => zone,
+                Err(err) => {
+                    warn!(pod = %pod, "Failed to discover the zone of the pod: {err}");
+                    None
+                }
+            }
+        }
     }
 }
@@ -0,0 +1,284 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::ShardManagerError;
+use crate::model::{AdminAction, AuditLogEntry, Pod, PodSelector, ShardOverrides};
+use crate::rebalancing::Rebalance;
+use crate::ShardManagerServiceImpl;
+use axum::extract::State;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use golem_api_grpc::proto::golem;
+use golem_common::model::{ShardId, Timestamp};
+use http::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+/// HTTP endpoints for draining pods and for the admin operations of the shard placement, served
+/// next to the health and metrics endpoints.
+///
+/// The drain endpoints are equivalent to the `Drain` and `Undrain` gRPC methods. Every change
+/// made through the admin endpoints is persisted and recorded in the audit log.
+pub fn routes(shard_manager: Arc<ShardManagerServiceImpl>) -> axum::Router {
+    Router::new()
+        .route("/v1/pods/drain", post(drain))
+        .route("/v1/pods/undrain", post(undrain))
+        .route("/v1/shards", get(get_routing_table))
+        .route("/v1/shards/plan", get(get_pending_rebalance))
+        .route("/v1/shards/audit-log", get(get_audit_log))
+        .route("/v1/shards/pin", post(pin_shard))
+        .route("/v1/shards/unpin", post(unpin_shard))
+        .route("/v1/rebalancing/freeze", post(freeze_rebalancing))
+        .route("/v1/rebalancing/unfreeze", post(unfreeze_rebalancing))
+        .route("/v1/rebalancing/trigger", post(trigger_rebalance))
+        .with_state(shard_manager)
+}
+
+#[derive(Serialize)]
+struct RoutingTableResponse {
+    number_of_shards: usize,
+    pods: Vec<PodShards>,
+    unassigned_shards: Vec<i64>,
+    #[serde(flatten)]
+    overrides: OverridesResponse,
+}
+
+#[derive(Serialize)]
+struct PodShards {
+    pod: Pod,
+    shards: Vec<i64>,
+}
+
+#[derive(Serialize)]
+struct RebalancePlanResponse {
+    assignments: Vec<PodShards>,
+    unassignments: Vec<PodShards>,
+}
+
+#[derive(Serialize)]
+struct OverridesResponse {
+    rebalancing_frozen: bool,
+    pinned_shards: Vec<PinnedShard>,
+}
+
+#[derive(Serialize)]
+struct PinnedShard {
+    shard: i64,
+    pod: PodSelector,
+}
+
+#[derive(Serialize)]
+struct AuditLogEntryResponse {
+    timestamp: Timestamp,
+    action: String,
+    requested_by: Option<String>,
+    reason: Option<String>,
+}
+
+/// The fields common to every admin request, recorded in the audit log
+#[derive(Deserialize)]
+struct AdminRequest {
+    requested_by: Option<String>,
+    reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct PinShardRequest {
+    shard: i64,
+    pod: PodSelector,
+    #[serde(flatten)]
+    admin: AdminRequest,
+}
+
+#[derive(Deserialize)]
+struct UnpinShardRequest {
+    shard: i64,
+    #[serde(flatten)]
+    admin: AdminRequest,
+}
+
+async fn drain(
+    State(shard_manager): State<Arc<ShardManagerServiceImpl>>,
+    Json(selector): Json<PodSelector>,
+) -> Response {
+    match shard_manager.drain_internal(selector).await {
+        Ok(status) => Json(status).into_response(),
+        Err(err) => error_response(err),
+    }
+}
+
+async fn undrain(
+    State(shard_manager): State<Arc<ShardManagerServiceImpl>>,
+    Json(selector): Json<PodSelector>,
+) -> Response {
+    match shard_manager.undrain_internal(selector).await {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(err) => error_response(err),
+    }
+}
+
+async fn get_routing_table(State(shard_manager): State<Arc<ShardManagerServiceImpl>>) -> Response {
+    let result = async {
+        let routing_table = shard_manager.get_routing_table_internal().await?;
+        let overrides = shard_manager.get_overrides_internal().await?;
+        Ok::<_, ShardManagerError>(RoutingTableResponse {
+            number_of_shards: routing_table.number_of_shards,
+            pods: pod_shards(&routing_table.shard_assignments),
+            unassigned_shards: shard_numbers(&routing_table.get_unassigned_shards()),
+            overrides: overrides_response(&overrides),
+        })
+    }
+    .await;
+    match result {
+        Ok(response) => Json(response).into_response(),
+        Err(err) => error_response(err),
+    }
+}
+
+async fn get_pending_rebalance(
+    State(shard_manager): State<Arc<ShardManagerServiceImpl>>,
+) -> Response {
+    match shard_manager.get_pending_rebalance_internal().await {
+        Ok(rebalance) => Json(rebalance_plan_response(&rebalance)).into_response(),
+        Err(err) => error_response(err),
+    }
+}
+
+async fn get_audit_log(State(shard_manager): State<Arc<ShardManagerServiceImpl>>) -> Response {
+    match shard_manager.get_overrides_internal().await {
+        Ok(overrides) => Json(
+            overrides
+                .audit_log
+                .iter()
+                .map(audit_log_entry_response)
+                .collect::<Vec<_>>(),
+        )
+        .into_response(),
+        Err(err) => error_response(err),
+    }
+}
+
+async fn pin_shard(
+    State(shard_manager): State<Arc<ShardManagerServiceImpl>>,
+    Json(request): Json<PinShardRequest>,
+) -> Response {
+    let action = AdminAction::PinShard {
+        shard_id: ShardId::new(request.shard),
+        pod: request.pod,
+    };
+    apply_admin_action(shard_manager, action, request.admin).await
+}
+
+async fn unpin_shard(
+    State(shard_manager): State<Arc<ShardManagerServiceImpl>>,
+    Json(request): Json<UnpinShardRequest>,
+) -> Response {
+    let action = AdminAction::UnpinShard {
+        shard_id: ShardId::new(request.shard),
+    };
+    apply_admin_action(shard_manager, action, request.admin).await
+}
+
+async fn freeze_rebalancing(
+    State(shard_manager): State<Arc<ShardManagerServiceImpl>>,
+    Json(request): Json<AdminRequest>,
+) -> Response {
+    apply_admin_action(shard_manager, AdminAction::FreezeRebalancing, request).await
+}
+
+async fn unfreeze_rebalancing(
+    State(shard_manager): State<Arc<ShardManagerServiceImpl>>,
+    Json(request): Json<AdminRequest>,
+) -> Response {
+    apply_admin_action(shard_manager, AdminAction::UnfreezeRebalancing, request).await
+}
+
+async fn trigger_rebalance(
+    State(shard_manager): State<Arc<ShardManagerServiceImpl>>,
+    Json(request): Json<AdminRequest>,
+) -> Response {
+    apply_admin_action(shard_manager, AdminAction::TriggerRebalance, request).await
+}
+
+async fn apply_admin_action(
+    shard_manager: Arc<ShardManagerServiceImpl>,
+    action: AdminAction,
+    request: AdminRequest,
+) -> Response {
+    let entry = AuditLogEntry::new(action, request.requested_by, request.reason);
+    match shard_manager.apply_admin_action_internal(entry).await {
+        Ok(overrides) => Json(overrides_response(&overrides)).into_response(),
+        Err(err) => error_response(err),
+    }
+}
+
+fn shard_number(shard_id: &ShardId) -> i64 {
+    golem::shardmanager::ShardId::from(*shard_id).value
+}
+
+fn shard_numbers(shard_ids: &BTreeSet<ShardId>) -> Vec<i64> {
+    shard_ids.iter().map(shard_number).collect()
+}
+
+fn pod_shards(shard_assignments: &BTreeMap<Pod, BTreeSet<ShardId>>) -> Vec<PodShards> {
+    shard_assignments
+        .iter()
+        .map(|(pod, shard_ids)| PodShards {
+            pod: pod.clone(),
+            shards: shard_numbers(shard_ids),
+        })
+        .collect()
+}
+
+fn rebalance_plan_response(rebalance: &Rebalance) -> RebalancePlanResponse {
+    RebalancePlanResponse {
+        assignments: pod_shards(&rebalance.get_assignments().assignments),
+        unassignments: pod_shards(&rebalance.get_unassignments().unassignments),
+    }
+}
+
+fn overrides_response(overrides: &ShardOverrides) -> OverridesResponse {
+    OverridesResponse {
+        rebalancing_frozen: overrides.rebalancing_frozen,
+        pinned_shards: overrides
+            .pinned_shards
+            .iter()
+            .map(|(shard_id, pod)| PinnedShard {
+                shard: shard_number(shard_id),
+                pod: pod.clone(),
+            })
+            .collect(),
+    }
+}
+
+fn audit_log_entry_response(entry: &AuditLogEntry) -> AuditLogEntryResponse {
+    AuditLogEntryResponse {
+        timestamp: entry.timestamp,
+        action: entry.action.to_string(),
+        requested_by: entry.requested_by.clone(),
+        reason: entry.reason.clone(),
+    }
+}
+
+fn error_response(err: ShardManagerError) -> Response {
+    let status = match &err {
+        ShardManagerError::UnknownPod(_) => StatusCode::NOT_FOUND,
+        ShardManagerError::UnknownShard(_) => StatusCode::BAD_REQUEST,
+        ShardManagerError::NotLeader => StatusCode::SERVICE_UNAVAILABLE,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, err.to_string()).into_response()
+}
@@ -0,0 +1,1234 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::{HealthCheckError, ShardManagerError};
+use crate::healthcheck::HealthCheck;
+use crate::model::{Pod, RoutingTable, ShardLoad, ShardOverrides};
+use crate::persistence::RoutingTablePersistence;
+use crate::shard_management::ShardManagement;
+use crate::shard_manager_config::{LoadBalancingConfig, ZoneAwarenessConfig};
+use crate::worker_executor::WorkerExecutorService;
+use async_trait::async_trait;
+use golem_common::model::ShardId;
+use golem_common::redis::RedisPool;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::{File, OpenOptions, TryLockError};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, RwLock};
+use tracing::{error, info, warn};
+
+/// Decides which of the shard manager replicas is the leader.
+///
+/// Only the leader owns the routing table: it registers pods, runs health checks and
+/// rebalances shards. The other replicas only serve the persisted routing table.
+#[async_trait]
+pub trait LeaderElection {
+    /// Tries to become the leader, or to stay the leader if this replica already is.
+    /// Returns whether this replica is the leader.
+    async fn try_acquire(&self) -> Result<bool, ShardManagerError>;
+
+    /// Gives up the leadership if this replica holds it
+    async fn release(&self) -> Result<(), ShardManagerError>;
+
+    /// How long an acquired lease is valid without renewing it, if it expires at all
+    fn lease_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+/// Used when there is only a single replica, which is always the leader
+pub struct NoLeaderElection;
+
+#[async_trait]
+impl LeaderElection for NoLeaderElection {
+    async fn try_acquire(&self) -> Result<bool, ShardManagerError> {
+        Ok(true)
+    }
+
+    async fn release(&self) -> Result<(), ShardManagerError> {
+        Ok(())
+    }
+}
+
+/// Leader election through a lock key in Redis that expires unless the leader renews it
+pub struct RedisLeaderElection {
+    pool: RedisPool,
+    replica_id: String,
+    lease_duration: Duration,
+}
+
+impl RedisLeaderElection {
+    /// Holds the id of the leader replica, which fences the routing table writes
+    /// (see `RoutingTableRedisPersistence::with_leader_fencing`)
+    pub(crate) const KEY: &'static str = "shard:shard_manager_leader";
+
+    // Sets the lock if it is free, or extends it if this replica holds it
+    const ACQUIRE_SCRIPT: &'static str = r#"
+        if redis.call('get', KEYS[1]) == ARGV[1] then
+            return redis.call('pexpire', KEYS[1], ARGV[2])
+        elseif redis.call('set', KEYS[1], ARGV[1], 'NX', 'PX', ARGV[2]) then
+            return 1
+        else
+            return 0
+        end
+    "#;
+
+    const RELEASE_SCRIPT: &'static str = r#"
+        if redis.call('get', KEYS[1]) == ARGV[1] then
+            return redis.call('del', KEYS[1])
+        else
+            return 0
+        end
+    "#;
+
+    pub fn new(pool: &RedisPool, replica_id: String, lease_duration: Duration) -> Self {
+        Self {
+            pool: pool.clone(),
+            replica_id,
+            lease_duration,
+        }
+    }
+}
+
+#[async_trait]
+impl LeaderElection for RedisLeaderElection {
+    async fn try_acquire(&self) -> Result<bool, ShardManagerError> {
+        let acquired: i64 = self
+            .pool
+            .with("leader_election", "try_acquire")
+            .eval(
+                Self::ACQUIRE_SCRIPT,
+                vec![Self::KEY],
+                vec![
+                    self.replica_id.clone(),
+                    self.lease_duration.as_millis().to_string(),
+                ],
+            )
+            .await
+            .map_err(ShardManagerError::RedisError)?;
+
+        Ok(acquired == 1)
+    }
+
+    async fn release(&self) -> Result<(), ShardManagerError> {
+        let _: i64 = self
+            .pool
+            .with("leader_election", "release")
+            .eval(
+                Self::RELEASE_SCRIPT,
+                vec![Self::KEY],
+                vec![self.replica_id.clone()],
+            )
+            .await
+            .map_err(ShardManagerError::RedisError)?;
+
+        Ok(())
+    }
+
+    fn lease_duration(&self) -> Option<Duration> {
+        Some(self.lease_duration)
+    }
+}
+
+/// Leader election through an exclusive lock on a file next to the persisted routing table.
+/// The lock is released by the operating system if the leader process dies.
+pub struct FileSystemLeaderElection {
+    path: PathBuf,
+    lock: Mutex<Option<File>>,
+}
+
+impl FileSystemLeaderElection {
+    pub fn new(routing_table_path: &Path) -> Self {
+        let mut path = routing_table_path.as_os_str().to_owned();
+        path.push(".leader");
+
+        Self {
+            path: PathBuf::from(path),
+            lock: Mutex::new(None),
+        }
+    }
+}
+
+#[async_trait]
+impl LeaderElection for FileSystemLeaderElection {
+    async fn try_acquire(&self) -> Result<bool, ShardManagerError> {
+        let mut lock = self.lock.lock().await;
+
+        if lock.is_some() {
+            return Ok(true);
+        }
+
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&self.path)?;
+
+        match file.try_lock() {
+            Ok(()) => {
+                *lock = Some(file);
+                Ok(true)
+            }
+            Err(TryLockError::WouldBlock) => Ok(false),
+            Err(TryLockError::Error(err)) => Err(err.into()),
+        }
+    }
+
+    async fn release(&self) -> Result<(), ShardManagerError> {
+        if let Some(file) = self.lock.lock().await.take() {
+            file.unlock()?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The leader lease of this replica, shared with its shard management.
+///
+/// The lease is only considered held until it expires, even if the renewal is late, for example
+/// because the process got paused. The shard management checks it before every routing table
+/// write and worker executor call, so a replica that lost the lease stops acting as the leader
+/// right away, not only at its next renewal.
+#[derive(Clone, Default)]
+pub struct Lease {
+    state: Arc<std::sync::Mutex<LeaseState>>,
+}
+
+#[derive(Default)]
+enum LeaseState {
+    #[default]
+    Lost,
+    Held {
+        expires_at: Option<Instant>,
+    },
+}
+
+impl Lease {
+    #[cfg(test)]
+    pub fn held() -> Self {
+        let lease = Self::default();
+        lease.renewed(None);
+        lease
+    }
+
+    fn renewed(&self, expires_at: Option<Instant>) {
+        *self.state.lock().unwrap() = LeaseState::Held { expires_at };
+    }
+
+    fn lost(&self) {
+        *self.state.lock().unwrap() = LeaseState::Lost;
+    }
+
+    pub fn is_held(&self) -> bool {
+        match *self.state.lock().unwrap() {
+            LeaseState::Lost => false,
+            LeaseState::Held { expires_at: None } => true,
+            LeaseState::Held {
+                expires_at: Some(expires_at),
+            } => Instant::now() < expires_at,
+        }
+    }
+
+    pub fn check(&self) -> Result<(), ShardManagerError> {
+        if self.is_held() {
+            Ok(())
+        } else {
+            Err(ShardManagerError::NotLeader)
+        }
+    }
+}
+
+/// Only writes the routing table and the overrides while the lease is held
+pub struct FencedRoutingTablePersistence {
+    inner: Arc<dyn RoutingTablePersistence + Send + Sync>,
+    lease: Lease,
+}
+
+impl FencedRoutingTablePersistence {
+    pub fn new(inner: Arc<dyn RoutingTablePersistence + Send + Sync>, lease: Lease) -> Self {
+        Self { inner, lease }
+    }
+}
+
+#[async_trait]
+impl RoutingTablePersistence for FencedRoutingTablePersistence {
+    async fn write(&self, routing_table: &RoutingTable) -> Result<(), ShardManagerError> {
+        self.lease.check()?;
+        self.inner.write(routing_table).await
+    }
+
+    async fn read(&self) -> Result<RoutingTable, ShardManagerError> {
+        self.inner.read().await
+    }
+
+    async fn write_overrides(&self, overrides: &ShardOverrides) -> Result<(), ShardManagerError> {
+        self.lease.check()?;
+        self.inner.write_overrides(overrides).await
+    }
+
+    async fn read_overrides(&self) -> Result<ShardOverrides, ShardManagerError> {
+        self.inner.read_overrides().await
+    }
+}
+
+/// Only assigns and revokes shards while the lease is held
+pub struct FencedWorkerExecutorService {
+    inner: Arc<dyn WorkerExecutorService + Send + Sync>,
+    lease: Lease,
+}
+
+impl FencedWorkerExecutorService {
+    pub fn new(inner: Arc<dyn WorkerExecutorService + Send + Sync>, lease: Lease) -> Self {
+        Self { inner, lease }
+    }
+}
+
+#[async_trait]
+impl WorkerExecutorService for FencedWorkerExecutorService {
+    async fn assign_shards(
+        &self,
+        pod: &Pod,
+        shard_ids: &BTreeSet<ShardId>,
+        number_of_shards: usize,
+        full_assignment: bool,
+    ) -> Result<(), ShardManagerError> {
+        self.lease.check()?;
+        self.inner
+            .assign_shards(pod, shard_ids, number_of_shards, full_assignment)
+            .await
+    }
+
+    async fn get_shard_loads(
+        &self,
+        pod: &Pod,
+    ) -> Result<BTreeMap<ShardId, ShardLoad>, ShardManagerError> {
+        self.inner.get_shard_loads(pod).await
+    }
+
+    async fn health_check(&self, pod: &Pod) -> Result<(), HealthCheckError> {
+        self.inner.health_check(pod).await
+    }
+
+    async fn revoke_shards(
+        &self,
+        pod: &Pod,
+        shard_ids: &BTreeSet<ShardId>,
+        suspension_timeout: Duration,
+    ) -> Result<(), ShardManagerError> {
+        self.lease.check()?;
+        self.inner
+            .revoke_shards(pod, shard_ids, suspension_timeout)
+            .await
+    }
+}
+
+/// The role of this replica, holding the shard management while it is the leader
+#[derive(Clone)]
+pub struct Leadership {
+    leader_election: Arc<dyn LeaderElection + Send + Sync>,
+    shard_management: Arc<RwLock<Option<ShardManagement>>>,
+    lease: Lease,
+    take_over_lock: Arc<Mutex<()>>,
+    persistence_service: Arc<dyn RoutingTablePersistence + Send + Sync>,
+    worker_executor_service: Arc<dyn WorkerExecutorService + Send + Sync>,
+    health_check: Arc<dyn HealthCheck + Send + Sync>,
+    rebalance_threshold: f64,
+    number_of_shards: usize,
+    load_balancing: LoadBalancingConfig,
+    zone_awareness: ZoneAwarenessConfig,
+    drain_suspension_timeout: Duration,
+}
+
+impl Leadership {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        leader_election: Arc<dyn LeaderElection + Send + Sync>,
+        persistence_service: Arc<dyn RoutingTablePersistence + Send + Sync>,
+        worker_executor_service: Arc<dyn WorkerExecutorService + Send + Sync>,
+        health_check: Arc<dyn HealthCheck + Send + Sync>,
+        rebalance_threshold: f64,
+        number_of_shards: usize,
+        load_balancing: LoadBalancingConfig,
+        zone_awareness: ZoneAwarenessConfig,
+        drain_suspension_timeout: Duration,
+    ) -> Self {
+        Self {
+            leader_election,
+            shard_management: Arc::new(RwLock::new(None)),
+            lease: Lease::default(),
+            take_over_lock: Arc::new(Mutex::new(())),
+            persistence_service,
+            worker_executor_service,
+            health_check,
+            rebalance_threshold,
+            number_of_shards,
+            load_balancing,
+            zone_awareness,
+            drain_suspension_timeout,
+        }
+    }
+
+    /// The shard management, if this replica is the leader
+    pub async fn shard_management(&self) -> Option<ShardManagement> {
+        self.shard_management.read().await.clone()
+    }
+
+    pub async fn is_leader(&self) -> bool {
+        self.shard_management.read().await.is_some()
+    }
+
+    /// The routing table owned by the leader, or the last one it persisted on followers
+    pub async fn routing_table(&self) -> Result<RoutingTable, ShardManagerError> {
+        match self.shard_management().await {
+            Some(shard_management) => Ok(shard_management.current_snapshot().await),
+            None => self.persistence_service.read().await,
+        }
+    }
+
+    /// Renews the lease and takes over the shard management when the leadership is acquired,
+    /// or stops it when the leadership is lost.
+    pub async fn run_election(&self) {
+        if self.renew_lease().await && !self.is_leader().await {
+            self.take_over().await;
+        }
+    }
+
+    /// Tries to acquire or renew the lease, stopping the shard management if it is lost.
+    /// Returns whether this replica holds the lease.
+    ///
+    /// Never waits for a take over in progress, so the lease can be renewed while
+    /// the shard management is being initialized.
+    pub async fn renew_lease(&self) -> bool {
+        // The lease expires counting from before the renewal request
+        let renewal_started = Instant::now();
+        let holds_lease = match self.leader_election.try_acquire().await {
+            Ok(holds_lease) => holds_lease,
+            Err(err) => {
+                // Without being able to renew the lease, another replica may take over
+                // at any time, so this one has to stop acting as the leader
+                warn!("Failed to run leader election: {err}");
+                false
+            }
+        };
+
+        if holds_lease {
+            self.lease.renewed(
+                self.leader_election
+                    .lease_duration()
+                    .map(|lease_duration| renewal_started + lease_duration),
+            );
+        } else {
+            self.lease.lost();
+        }
+
+        if !holds_lease && self.shard_management.write().await.take().is_some() {
+            warn!("Lost leadership, stopping shard management");
+        }
+
+        holds_lease
+    }
+
+    /// Initializes the shard management after the lease has been acquired. Does nothing if
+    /// another take over is in progress.
+    ///
+    /// The initialization runs a health check of all pods, so it is done without holding the
+    /// shard management lock, and the result is only used if the lease is still held.
+    pub async fn take_over(&self) {
+        let Ok(_take_over) = self.take_over_lock.try_lock() else {
+            return;
+        };
+
+        if self.is_leader().await {
+            return;
+        }
+
+        info!("Acquired leadership, taking over shard management");
+
+        let new_shard_management = match ShardManagement::new(
+            self.persistence_service.clone(),
+            self.worker_executor_service.clone(),
+            self.health_check.clone(),
+            self.rebalance_threshold,
+            self.number_of_shards,
+            self.load_balancing.clone(),
+            self.zone_awareness.clone(),
+            self.drain_suspension_timeout,
+            self.lease.clone(),
+        )
+        .await
+        {
+            Ok(new_shard_management) => new_shard_management,
+            Err(err) => {
+                error!("Failed to initialize shard management: {err}");
+                self.lease.lost();
+                if let Err(err) = self.leader_election.release().await {
+                    warn!("Failed to release leadership: {err}");
+                }
+                return;
+            }
+        };
+
+        let mut shard_management = self.shard_management.write().await;
+        if self.lease.is_held() {
+            *shard_management = Some(new_shard_management);
+        } else {
+            warn!("Lost leadership while taking over, stopping shard management");
+        }
+    }
+
+    /// Steps down, letting another replica take over without waiting for the lease to expire
+    pub async fn resign(&self) {
+        let mut shard_management = self.shard_management.write().await;
+        self.lease.lost();
+        if shard_management.take().is_some() {
+            info!("Resigning leadership");
+        }
+        if let Err(err) = self.leader_election.release().await {
+            warn!("Failed to release leadership: {err}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use crate::error::{HealthCheckError, ShardManagerError};
+    use crate::healthcheck::HealthCheck;
+    use crate::leader_election::{
+        FencedWorkerExecutorService, FileSystemLeaderElection, LeaderElection, Leadership, Lease,
+        RedisLeaderElection,
+    };
+    use crate::model::{
+        AdminAction, AuditLogEntry, DrainStatus, Pod, PodSelector, RoutingTable, ShardId,
+        ShardLoad, ShardOverrides,
+    };
+    use crate::persistence::{RoutingTablePersistence, RoutingTableRedisPersistence};
+    use crate::shard_manager_config::{LoadBalancingConfig, ZoneAwarenessConfig};
+    use crate::worker_executor::WorkerExecutorService;
+    use async_trait::async_trait;
+    use golem_common::config::RedisConfig;
+    use golem_common::redis::RedisPool;
+    use golem_test_framework::components::redis::spawned::SpawnedRedis;
+    use golem_test_framework::components::redis::Redis;
+    use std::collections::{BTreeMap, BTreeSet};
+    use std::fmt::{Debug, Formatter};
+    use std::path::Path;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use test_r::test_dep;
+    use tokio::sync::{Mutex, Notify};
+    use uuid::Uuid;
+
+    struct TestRedis(SpawnedRedis);
+
+    impl Debug for TestRedis {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            f.write_str("TestRedis")
+        }
+    }
+
+    #[test_dep]
+    fn redis() -> TestRedis {
+        TestRedis(SpawnedRedis::new_default())
+    }
+
+    // A pool with its own key prefix, so the tests do not share the leader key
+    async fn redis_pool(redis: &TestRedis) -> RedisPool {
+        RedisPool::configured(&RedisConfig {
+            host: redis.0.public_host(),
+            port: redis.0.public_port(),
+            database: 0,
+            tracing: false,
+            pool_size: 1,
+            retries: Default::default(),
+            key_prefix: Uuid::new_v4().to_string(),
+            username: None,
+            password: None,
+        })
+        .await
+        .unwrap()
+    }
+
+    struct InMemoryPersistence {
+        routing_table: Mutex<RoutingTable>,
+        overrides: Mutex<ShardOverrides>,
+    }
+
+    impl InMemoryPersistence {
+        fn new(routing_table: RoutingTable) -> Self {
+            Self {
+                routing_table: Mutex::new(routing_table),
+                overrides: Mutex::new(ShardOverrides::default()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl RoutingTablePersistence for InMemoryPersistence {
+        async fn write(&self, routing_table: &RoutingTable) -> Result<(), ShardManagerError> {
+            *self.routing_table.lock().await = routing_table.clone();
+            Ok(())
+        }
+
+        async fn read(&self) -> Result<RoutingTable, ShardManagerError> {
+            Ok(self.routing_table.lock().await.clone())
+        }
+
+        async fn write_overrides(
+            &self,
+            overrides: &ShardOverrides,
+        ) -> Result<(), ShardManagerError> {
+            *self.overrides.lock().await = overrides.clone();
+            Ok(())
+        }
+
+        async fn read_overrides(&self) -> Result<ShardOverrides, ShardManagerError> {
+            Ok(self.overrides.lock().await.clone())
+        }
+    }
+
+    // Fails reading the routing table, like a persistence whose storage is unavailable
+    struct UnreadablePersistence;
+
+    #[async_trait]
+    impl RoutingTablePersistence for UnreadablePersistence {
+        async fn write(&self, _routing_table: &RoutingTable) -> Result<(), ShardManagerError> {
+            Ok(())
+        }
+
+        async fn read(&self) -> Result<RoutingTable, ShardManagerError> {
+            Err(ShardManagerError::IoError(std::io::Error::other(
+                "storage is unavailable",
+            )))
+        }
+
+        async fn write_overrides(
+            &self,
+            _overrides: &ShardOverrides,
+        ) -> Result<(), ShardManagerError> {
+            Ok(())
+        }
+
+        async fn read_overrides(&self) -> Result<ShardOverrides, ShardManagerError> {
+            Ok(ShardOverrides::default())
+        }
+    }
+
+    struct NoopWorkerExecutors;
+
+    #[async_trait]
+    impl WorkerExecutorService for NoopWorkerExecutors {
+        async fn assign_shards(
+            &self,
+            _pod: &Pod,
+            _shard_ids: &BTreeSet<ShardId>,
+            _number_of_shards: usize,
+            _full_assignment: bool,
+        ) -> Result<(), ShardManagerError> {
+            Ok(())
+        }
+
+        async fn get_shard_loads(
+            &self,
+            _pod: &Pod,
+        ) -> Result<BTreeMap<ShardId, ShardLoad>, ShardManagerError> {
+            Ok(BTreeMap::new())
+        }
+
+        async fn health_check(&self, _pod: &Pod) -> Result<(), HealthCheckError> {
+            Ok(())
+        }
+
+        async fn revoke_shards(
+            &self,
+            _pod: &Pod,
+            _shard_ids: &BTreeSet<ShardId>,
+            _suspension_timeout: Duration,
+        ) -> Result<(), ShardManagerError> {
+            Ok(())
+        }
+    }
+
+    struct AlwaysHealthy;
+
+    #[async_trait]
+    impl HealthCheck for AlwaysHealthy {
+        async fn health_check(&self, _pod: &Pod) -> bool {
+            true
+        }
+    }
+
+    // Blocks the health checks until it is opened
+    struct GatedHealthCheck {
+        opened: Notify,
+    }
+
+    #[async_trait]
+    impl HealthCheck for GatedHealthCheck {
+        async fn health_check(&self, _pod: &Pod) -> bool {
+            self.opened.notified().await;
+            self.opened.notify_one();
+            true
+        }
+    }
+
+    fn replica(
+        lock_path: &Path,
+        persistence: Arc<dyn RoutingTablePersistence + Send + Sync>,
+    ) -> Leadership {
+        replica_with_shards(lock_path, persistence, 16)
+    }
+
+    fn replica_with_shards(
+        lock_path: &Path,
+        persistence: Arc<dyn RoutingTablePersistence + Send + Sync>,
+        number_of_shards: usize,
+    ) -> Leadership {
+        Leadership::new(
+            Arc::new(FileSystemLeaderElection::new(lock_path)),
+            persistence,
+            Arc::new(NoopWorkerExecutors),
+            Arc::new(AlwaysHealthy),
+            0.1,
+            number_of_shards,
+            LoadBalancingConfig::default(),
+            ZoneAwarenessConfig::default(),
+            Duration::ZERO,
+        )
+    }
+
+    fn replica_with_health_check(
+        lock_path: &Path,
+        persistence: Arc<InMemoryPersistence>,
+        health_check: Arc<dyn HealthCheck + Send + Sync>,
+    ) -> Leadership {
+        Leadership::new(
+            Arc::new(FileSystemLeaderElection::new(lock_path)),
+            persistence,
+            Arc::new(NoopWorkerExecutors),
+            health_check,
+            0.1,
+            16,
+            LoadBalancingConfig::default(),
+            ZoneAwarenessConfig::default(),
+            Duration::ZERO,
+        )
+    }
+
+    fn redis_replica(
+        pool: &RedisPool,
+        replica_id: &str,
+        lease_duration: Duration,
+        persistence: Arc<dyn RoutingTablePersistence + Send + Sync>,
+    ) -> Leadership {
+        Leadership::new(
+            Arc::new(RedisLeaderElection::new(
+                pool,
+                replica_id.to_string(),
+                lease_duration,
+            )),
+            persistence,
+            Arc::new(NoopWorkerExecutors),
+            Arc::new(AlwaysHealthy),
+            0.1,
+            16,
+            LoadBalancingConfig::default(),
+            ZoneAwarenessConfig::default(),
+            Duration::ZERO,
+        )
+    }
+
+    async fn leaders(replicas: &[&Leadership]) -> usize {
+        let mut leaders = 0;
+        for replica in replicas {
+            if replica.is_leader().await {
+                leaders += 1;
+            }
+        }
+        leaders
+    }
+
+    #[test]
+    async fn file_system_leader_election_allows_single_leader() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("routing_table");
+
+        let replica1 = FileSystemLeaderElection::new(&path);
+        let replica2 = FileSystemLeaderElection::new(&path);
+
+        assert!(replica1.try_acquire().await.unwrap());
+        assert!(!replica2.try_acquire().await.unwrap());
+        assert!(replica1.try_acquire().await.unwrap());
+
+        replica1.release().await.unwrap();
+
+        assert!(replica2.try_acquire().await.unwrap());
+        assert!(!replica1.try_acquire().await.unwrap());
+    }
+
+    #[test]
+    async fn follower_takes_over_when_leader_resigns() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("routing_table");
+        let persistence = Arc::new(InMemoryPersistence::new(RoutingTable::new(16)));
+
+        let replica1 = replica(&path, persistence.clone());
+        let replica2 = replica(&path, persistence.clone());
+        let replica3 = replica(&path, persistence.clone());
+
+        for replica in [&replica1, &replica2, &replica3] {
+            replica.run_election().await;
+        }
+
+        assert!(replica1.is_leader().await);
+        assert!(!replica2.is_leader().await);
+        assert!(!replica3.is_leader().await);
+
+        // Followers serve the persisted routing table
+        assert_eq!(replica2.routing_table().await.unwrap().number_of_shards, 16);
+
+        replica1.resign().await;
+        for replica in [&replica2, &replica3, &replica1] {
+            replica.run_election().await;
+        }
+
+        assert!(!replica1.is_leader().await);
+        assert!(replica2.is_leader().await);
+        assert!(!replica3.is_leader().await);
+    }
+
+    #[test]
+    async fn redis_leader_election_allows_single_leader(redis: &TestRedis) {
+        let pool = redis_pool(redis).await;
+        let lease_duration = Duration::from_secs(10);
+
+        let replica1 = RedisLeaderElection::new(&pool, "replica1".to_string(), lease_duration);
+        let replica2 = RedisLeaderElection::new(&pool, "replica2".to_string(), lease_duration);
+
+        assert!(replica1.try_acquire().await.unwrap());
+        assert!(!replica2.try_acquire().await.unwrap());
+        assert!(replica1.try_acquire().await.unwrap());
+
+        // Releasing is only possible by the leader
+        replica2.release().await.unwrap();
+        assert!(!replica2.try_acquire().await.unwrap());
+
+        replica1.release().await.unwrap();
+
+        assert!(replica2.try_acquire().await.unwrap());
+        assert!(!replica1.try_acquire().await.unwrap());
+    }
+
+    #[test]
+    async fn single_follower_takes_over_when_redis_leader_is_killed(redis: &TestRedis) {
+        let pool = redis_pool(redis).await;
+        let lease_duration = Duration::from_millis(500);
+        let persistence = Arc::new(InMemoryPersistence::new(RoutingTable::new(16)));
+
+        let replica1 = redis_replica(&pool, "replica1", lease_duration, persistence.clone());
+        let replica2 = redis_replica(&pool, "replica2", lease_duration, persistence.clone());
+        let replica3 = redis_replica(&pool, "replica3", lease_duration, persistence.clone());
+
+        for replica in [&replica1, &replica2, &replica3] {
+            replica.run_election().await;
+        }
+
+        assert!(replica1.is_leader().await);
+        assert_eq!(leaders(&[&replica1, &replica2, &replica3]).await, 1);
+
+        // The killed leader neither renews nor releases its lease
+        drop(replica1);
+
+        for replica in [&replica2, &replica3] {
+            replica.run_election().await;
+        }
+        assert_eq!(leaders(&[&replica2, &replica3]).await, 0);
+
+        tokio::time::sleep(lease_duration * 2).await;
+
+        for _ in 0..2 {
+            for replica in [&replica2, &replica3] {
+                replica.run_election().await;
+            }
+            assert_eq!(leaders(&[&replica2, &replica3]).await, 1);
+        }
+        assert!(replica2.is_leader().await);
+    }
+
+    #[test]
+    async fn paused_redis_leader_steps_down_after_follower_took_over(redis: &TestRedis) {
+        let pool = redis_pool(redis).await;
+        let lease_duration = Duration::from_millis(500);
+        let persistence = Arc::new(InMemoryPersistence::new(RoutingTable::new(16)));
+
+        let replica1 = redis_replica(&pool, "replica1", lease_duration, persistence.clone());
+        let replica2 = redis_replica(&pool, "replica2", lease_duration, persistence.clone());
+
+        replica1.run_election().await;
+        replica2.run_election().await;
+        assert!(replica1.is_leader().await);
+
+        // The leader misses renewing its lease
+        tokio::time::sleep(lease_duration * 2).await;
+
+        replica2.run_election().await;
+        replica1.run_election().await;
+
+        assert!(!replica1.is_leader().await);
+        assert!(replica2.is_leader().await);
+    }
+
+    #[test]
+    async fn failing_take_over_releases_the_lease() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("routing_table");
+        let persistence = Arc::new(InMemoryPersistence::new(RoutingTable::new(16)));
+
+        let failing_replica = replica(&path, Arc::new(UnreadablePersistence));
+        let other_replica = replica(&path, persistence);
+
+        failing_replica.run_election().await;
+
+        assert!(!failing_replica.is_leader().await);
+
+        // The lease got released, so another replica can take over right away
+        other_replica.run_election().await;
+
+        assert!(other_replica.is_leader().await);
+    }
+
+    #[test]
+    async fn paused_redis_leader_cannot_write_after_follower_took_over(redis: &TestRedis) {
+        let pool = redis_pool(redis).await;
+        let lease_duration = Duration::from_millis(500);
+        let persistence = |replica_id: &str| {
+            Arc::new(
+                RoutingTableRedisPersistence::new(&pool, 16)
+                    .with_leader_fencing(replica_id.to_string()),
+            )
+        };
+        let persistence1 = persistence("replica1");
+        let persistence2 = persistence("replica2");
+
+        let replica1 = redis_replica(&pool, "replica1", lease_duration, persistence1.clone());
+        let replica2 = redis_replica(&pool, "replica2", lease_duration, persistence2.clone());
+
+        replica1.run_election().await;
+        assert!(replica1.is_leader().await);
+        assert!(replica1.lease.is_held());
+        persistence1.write(&RoutingTable::new(16)).await.unwrap();
+
+        // The leader gets paused and misses renewing its lease, so it still has its shard
+        // management when it continues
+        tokio::time::sleep(lease_duration * 2).await;
+        replica2.run_election().await;
+
+        assert!(replica1.is_leader().await);
+        assert!(!replica1.lease.is_held());
+        assert!(matches!(
+            replica1.lease.check(),
+            Err(ShardManagerError::NotLeader)
+        ));
+        assert!(matches!(
+            persistence1.write(&RoutingTable::new(16)).await,
+            Err(ShardManagerError::NotLeader)
+        ));
+
+        assert!(replica2.is_leader().await);
+        persistence2.write(&RoutingTable::new(16)).await.unwrap();
+    }
+
+    #[test]
+    async fn worker_executors_are_only_called_while_the_lease_is_held() {
+        let pod = Pod::new("pod0".to_string(), 9000);
+        let shard_ids = BTreeSet::from([ShardId::new(0)]);
+        let lease = Lease::default();
+        let worker_executors =
+            FencedWorkerExecutorService::new(Arc::new(NoopWorkerExecutors), lease.clone());
+
+        assert!(matches!(
+            worker_executors
+                .assign_shards(&pod, &shard_ids, 16, false)
+                .await,
+            Err(ShardManagerError::NotLeader)
+        ));
+        assert!(matches!(
+            worker_executors
+                .revoke_shards(&pod, &shard_ids, Duration::ZERO)
+                .await,
+            Err(ShardManagerError::NotLeader)
+        ));
+
+        lease.renewed(Some(std::time::Instant::now() + Duration::from_secs(10)));
+        worker_executors
+            .assign_shards(&pod, &shard_ids, 16, false)
+            .await
+            .unwrap();
+
+        // An expired lease counts as lost even without a failed renewal
+        lease.renewed(Some(std::time::Instant::now()));
+        assert!(matches!(
+            worker_executors
+                .revoke_shards(&pod, &shard_ids, Duration::ZERO)
+                .await,
+            Err(ShardManagerError::NotLeader)
+        ));
+    }
+
+    #[test]
+    async fn lease_is_renewed_while_taking_over() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("routing_table");
+
+        let mut routing_table = RoutingTable::new(16);
+        routing_table.add_pod(&Pod::new("pod0".to_string(), 9000));
+        let persistence = Arc::new(InMemoryPersistence::new(routing_table));
+
+        let health_check = Arc::new(GatedHealthCheck {
+            opened: Notify::new(),
+        });
+        let replica = replica_with_health_check(&path, persistence, health_check.clone());
+
+        assert!(replica.renew_lease().await);
+        let take_over = tokio::spawn({
+            let replica = replica.clone();
+            async move { replica.take_over().await }
+        });
+
+        // The initial health check of the take over is still running
+        let renewed = tokio::time::timeout(Duration::from_secs(5), replica.renew_lease())
+            .await
+            .expect("Renewing the lease waited for the take over");
+        assert!(renewed);
+        assert!(!replica.is_leader().await);
+
+        health_check.opened.notify_one();
+        take_over.await.unwrap();
+
+        assert!(replica.is_leader().await);
+    }
+
+    #[test]
+    async fn take_over_is_abandoned_when_lease_is_lost() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("routing_table");
+
+        let mut routing_table = RoutingTable::new(16);
+        routing_table.add_pod(&Pod::new("pod0".to_string(), 9000));
+        let persistence = Arc::new(InMemoryPersistence::new(routing_table));
+        let other_replica = replica(&path, persistence.clone());
+
+        let health_check = Arc::new(GatedHealthCheck {
+            opened: Notify::new(),
+        });
+        let replica = replica_with_health_check(&path, persistence, health_check.clone());
+
+        assert!(replica.renew_lease().await);
+        let take_over = tokio::spawn({
+            let replica = replica.clone();
+            async move { replica.take_over().await }
+        });
+
+        tokio::time::timeout(Duration::from_secs(5), replica.resign())
+            .await
+            .expect("Resigning waited for the take over");
+        other_replica.run_election().await;
+
+        health_check.opened.notify_one();
+        take_over.await.unwrap();
+
+        assert!(!replica.is_leader().await);
+        assert!(other_replica.is_leader().await);
+    }
+
+    #[test]
+    async fn leader_reshards_persisted_routing_table() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("routing_table");
+
+        let pod = Pod::new("pod0".to_string(), 9000);
+        let mut routing_table = RoutingTable::new(4);
+        routing_table.add_pod(&pod);
+        routing_table
+            .shard_assignments
+            .insert(pod.clone(), (0..4).map(ShardId::new).collect());
+        let persistence = Arc::new(InMemoryPersistence::new(routing_table));
+
+        let replica = replica_with_shards(&path, persistence.clone(), 8);
+        replica.run_election().await;
+        assert!(replica.is_leader().await);
+
+        let persisted = persistence.read().await.unwrap();
+        assert_eq!(persisted.number_of_shards, 8);
+        assert_eq!(
+            persisted.get_shards(&pod),
+            Some((0..8).map(ShardId::new).collect())
+        );
+        assert_eq!(replica.routing_table().await.unwrap(), persisted);
+    }
+
+    #[test]
+    async fn leader_drains_and_undrains_pod() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("routing_table");
+
+        let pod0 = Pod::new("pod0".to_string(), 9000);
+        let pod1 = Pod::new("pod1".to_string(), 9001);
+        let mut routing_table = RoutingTable::new(4);
+        routing_table
+            .shard_assignments
+            .insert(pod0.clone(), [0, 1].into_iter().map(ShardId::new).collect());
+        routing_table
+            .shard_assignments
+            .insert(pod1.clone(), [2, 3].into_iter().map(ShardId::new).collect());
+        let persistence = Arc::new(InMemoryPersistence::new(routing_table));
+
+        let replica = replica(&path, persistence.clone());
+        replica.run_election().await;
+        let shard_management = replica.shard_management().await.unwrap();
+
+        let selector = PodSelector {
+            host: "pod0".to_string(),
+            port: 9000,
+            pod_name: None,
+        };
+        let status = shard_management.drain_pod(&selector).await.unwrap();
+        assert_eq!(status, DrainStatus::new(2));
+
+        let shard_count = |pod: Pod| {
+            let shard_management = shard_management.clone();
+            async move {
+                shard_management
+                    .current_snapshot()
+                    .await
+                    .get_shards(&pod)
+                    .unwrap_or_default()
+                    .len()
+            }
+        };
+
+        for _ in 0..100 {
+            if shard_management.drain_pod(&selector).await.unwrap().drained {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(shard_count(pod0.clone()).await, 0);
+        assert_eq!(shard_count(pod1.clone()).await, 4);
+
+        shard_management.undrain_pod(&selector).await.unwrap();
+        for _ in 0..100 {
+            if shard_count(pod0.clone()).await > 0 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let (pod0_shards, pod1_shards) = (shard_count(pod0).await, shard_count(pod1).await);
+        assert!(pod0_shards > 0);
+        assert_eq!(pod0_shards + pod1_shards, 4);
+
+        let unknown = PodSelector {
+            host: "pod2".to_string(),
+            port: 9002,
+            pod_name: None,
+        };
+        assert!(matches!(
+            shard_management.drain_pod(&unknown).await,
+            Err(ShardManagerError::UnknownPod(_))
+        ));
+    }
+
+    #[test]
+    async fn leader_applies_audited_overrides() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("routing_table");
+
+        let pod0 = Pod::new("pod0".to_string(), 9000);
+        let pod1 = Pod::new("pod1".to_string(), 9001);
+        let mut routing_table = RoutingTable::new(4);
+        routing_table
+            .shard_assignments
+            .insert(pod0.clone(), [0, 1].into_iter().map(ShardId::new).collect());
+        routing_table
+            .shard_assignments
+            .insert(pod1.clone(), [2, 3].into_iter().map(ShardId::new).collect());
+        let persistence = Arc::new(InMemoryPersistence::new(routing_table));
+
+        let replica = replica(&path, persistence.clone());
+        replica.run_election().await;
+        let shard_management = replica.shard_management().await.unwrap();
+
+        let pod1_selector = PodSelector {
+            host: "pod1".to_string(),
+            port: 9001,
+            pod_name: None,
+        };
+        let action =
+            |action: AdminAction| AuditLogEntry::new(action, Some("operator".to_string()), None);
+
+        shard_management
+            .apply_admin_action(action(AdminAction::PinShard {
+                shard_id: ShardId::new(0),
+                pod: pod1_selector.clone(),
+            }))
+            .await
+            .unwrap();
+        for _ in 0..100 {
+            if shard_management.pending_rebalance().await.is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert!(shard_management
+            .current_snapshot()
+            .await
+            .get_shards(&pod1)
+            .unwrap()
+            .contains(&ShardId::new(0)));
+
+        // While frozen, draining does not move any shard
+        shard_management
+            .apply_admin_action(action(AdminAction::FreezeRebalancing))
+            .await
+            .unwrap();
+        shard_management.drain_pod(&pod1_selector).await.unwrap();
+        assert!(shard_management.pending_rebalance().await.is_empty());
+
+        assert!(matches!(
+            shard_management
+                .apply_admin_action(action(AdminAction::PinShard {
+                    shard_id: ShardId::new(4),
+                    pod: pod1_selector,
+                }))
+                .await,
+            Err(ShardManagerError::UnknownShard(_))
+        ));
+
+        let persisted = persistence.read_overrides().await.unwrap();
+        assert!(persisted.rebalancing_frozen);
+        assert_eq!(persisted.pinned_shards.len(), 1);
+        assert_eq!(
+            persisted
+                .audit_log
+                .iter()
+                .map(|entry| entry.action.to_string())
+                .collect::<Vec<_>>(),
+            vec!["pin shard <0> to pod1:9001", "freeze rebalancing"]
+        );
+        assert_eq!(persisted, shard_management.overrides().await);
+    }
+}
@@ -14,15 +14,27 @@
 
 mod error;
 mod healthcheck;
+mod http_api;
+mod leader_election;
+mod load_balancing;
+mod metrics;
 mod model;
 mod persistence;
 mod rebalancing;
+mod resharding;
 mod shard_management;
 pub mod shard_manager_config;
 mod worker_executor;
+mod zones;
 
 use crate::error::ShardManagerTraceErrorKind;
-use crate::healthcheck::{get_unhealthy_pods, GrpcHealthCheck, HealthCheck};
+use crate::healthcheck::{
+    get_unhealthy_pods, get_unhealthy_pods_with_shard_loads, GrpcHealthCheck, HealthCheck,
+};
+use crate::leader_election::{
+    FileSystemLeaderElection, LeaderElection, Leadership, NoLeaderElection, RedisLeaderElection,
+};
+use crate::load_balancing::LoadBalancingStatus;
 use crate::persistence::RoutingTableFileSystemPersistence;
 use crate::shard_manager_config::{HealthCheckK8sConfig, HealthCheckMode, PersistenceConfig};
 use error::ShardManagerError;
@@ -32,9 +44,10 @@ use golem_api_grpc::proto::golem::shardmanager::v1::shard_manager_service_server
     ShardManagerService, ShardManagerServiceServer,
 };
 use golem_common::recorded_grpc_api_request;
-use model::{Pod, RoutingTable};
+use model::{AuditLogEntry, DrainStatus, Pod, PodSelector, RoutingTable, ShardOverrides};
 use persistence::{RoutingTablePersistence, RoutingTableRedisPersistence};
 use prometheus::Registry;
+use rebalancing::Rebalance;
 use shard_management::ShardManagement;
 use shard_manager_config::ShardManagerConfig;
 use std::env;
@@ -50,6 +63,7 @@ use tonic_tracing_opentelemetry::middleware;
 use tonic_tracing_opentelemetry::middleware::filters;
 use tracing::Instrument;
 use tracing::{debug, info, warn};
+use uuid::Uuid;
 use worker_executor::{WorkerExecutorService, WorkerExecutorServiceDefault};
 
 #[cfg(test)]
@@ -61,9 +75,10 @@ pub struct RunDetails {
 }
 
 pub struct ShardManagerServiceImpl {
-    shard_management: ShardManagement,
+    leadership: Leadership,
     shard_manager_config: Arc<ShardManagerConfig>,
     health_check: Arc<dyn HealthCheck + Send + Sync>,
+    worker_executor_service: Arc<dyn WorkerExecutorService + Send + Sync>,
 }
 
 impl ShardManagerServiceImpl {
@@ -72,69 +87,214 @@ impl ShardManagerServiceImpl {
         worker_executor_service: Arc<dyn WorkerExecutorService + Send + Sync>,
         shard_manager_config: Arc<ShardManagerConfig>,
         health_check: Arc<dyn HealthCheck + Send + Sync>,
+        leader_election: Arc<dyn LeaderElection + Send + Sync>,
     ) -> Result<ShardManagerServiceImpl, ShardManagerError> {
-        let shard_management = ShardManagement::new(
-            persistence_service.clone(),
-            worker_executor_service,
+        let leadership = Leadership::new(
+            leader_election,
+            persistence_service,
+            worker_executor_service.clone(),
             health_check.clone(),
             shard_manager_config.rebalance_threshold,
-        )
-        .await?;
+            shard_manager_config.number_of_shards,
+            shard_manager_config.load_balancing.clone(),
+            shard_manager_config.zone_awareness.clone(),
+            shard_manager_config
+                .worker_executors
+                .drain_suspension_timeout,
+        );
+
+        // Taking over right away if possible, so a single replica is ready as soon as it starts
+        leadership.run_election().await;
 
         let shard_manager_service = ShardManagerServiceImpl {
-            shard_management,
+            leadership,
             shard_manager_config,
             health_check,
+            worker_executor_service,
         };
 
+        shard_manager_service.start_leader_election();
         shard_manager_service.start_health_check();
 
         Ok(shard_manager_service)
     }
 
-    async fn get_routing_table_internal(&self) -> RoutingTable {
-        let routing_table = self.shard_management.current_snapshot().await;
+    async fn get_routing_table_internal(&self) -> Result<RoutingTable, ShardManagerError> {
+        let routing_table = self.leadership.routing_table().await?;
         debug!("Providing routing table: {}", routing_table);
-        routing_table
+        Ok(routing_table)
+    }
+
+    async fn get_load_balancing_status_internal(
+        &self,
+    ) -> Result<LoadBalancingStatus, ShardManagerError> {
+        // Only the leader makes load balancing decisions
+        let shard_management = self
+            .leadership
+            .shard_management()
+            .await
+            .ok_or(ShardManagerError::NotLeader)?;
+        Ok(shard_management.load_balancing_status().await)
+    }
+
+    async fn drain_internal(
+        &self,
+        selector: PodSelector,
+    ) -> Result<DrainStatus, ShardManagerError> {
+        debug!("Received request to drain pod: {}", selector);
+
+        let shard_management = self
+            .leadership
+            .shard_management()
+            .await
+            .ok_or(ShardManagerError::NotLeader)?;
+        shard_management.drain_pod(&selector).await
+    }
+
+    async fn undrain_internal(&self, selector: PodSelector) -> Result<(), ShardManagerError> {
+        debug!("Received request to undrain pod: {}", selector);
+
+        let shard_management = self
+            .leadership
+            .shard_management()
+            .await
+            .ok_or(ShardManagerError::NotLeader)?;
+        shard_management.undrain_pod(&selector).await
+    }
+
+    async fn get_overrides_internal(&self) -> Result<ShardOverrides, ShardManagerError> {
+        let shard_management = self
+            .leadership
+            .shard_management()
+            .await
+            .ok_or(ShardManagerError::NotLeader)?;
+        Ok(shard_management.overrides().await)
+    }
+
+    async fn get_pending_rebalance_internal(&self) -> Result<Rebalance, ShardManagerError> {
+        let shard_management = self
+            .leadership
+            .shard_management()
+            .await
+            .ok_or(ShardManagerError::NotLeader)?;
+        Ok(shard_management.pending_rebalance().await)
+    }
+
+    async fn apply_admin_action_internal(
+        &self,
+        entry: AuditLogEntry,
+    ) -> Result<ShardOverrides, ShardManagerError> {
+        debug!("Received admin action: {}", entry.action);
+
+        let shard_management = self
+            .leadership
+            .shard_management()
+            .await
+            .ok_or(ShardManagerError::NotLeader)?;
+        shard_management.apply_admin_action(entry).await
     }
 
     async fn register_internal(
         &self,
         source_ip: Option<SocketAddr>,
         request: golem::shardmanager::v1::RegisterRequest,
-    ) -> Result<(), ShardManagerError> {
+    ) -> Result<usize, ShardManagerError> {
         let source_ip = source_ip.ok_or(ShardManagerError::NoSourceIpForPod)?.ip();
 
-        let pod = Pod::from_register_request(source_ip, request)?;
+        let mut pod = Pod::from_register_request(source_ip, request)?;
         debug!("Received request to register pod: {}", pod);
-        self.shard_management.register_pod(pod).await;
-        Ok(())
+
+        if self.shard_manager_config.zone_awareness.enabled && pod.zone.is_none() {
+            pod.zone = self.health_check.zone(&pod).await;
+            debug!("Discovered zone of pod {}: {:?}", pod, pod.zone);
+        }
+
+        // Followers reject registrations, executors keep retrying until they reach the leader
+        let shard_management = self
+            .leadership
+            .shard_management()
+            .await
+            .ok_or(ShardManagerError::NotLeader)?;
+        shard_management.register_pod(pod).await;
+
+        // The persisted routing table may still use the previous number of shards if resharding was rejected
+        Ok(shard_management.current_snapshot().await.number_of_shards)
+    }
+
+    fn start_leader_election(&self) {
+        if !self.shard_manager_config.leader_election.enabled {
+            return;
+        }
+
+        let renew_interval = self.shard_manager_config.leader_election.renew_interval;
+        let leadership = self.leadership.clone();
+
+        tokio::spawn(
+            async move {
+                loop {
+                    tokio::time::sleep(renew_interval).await;
+                    // Taking over can take longer than the lease, so it runs in its own task
+                    // and does not hold back renewing the lease
+                    if leadership.renew_lease().await && !leadership.is_leader().await {
+                        let leadership = leadership.clone();
+                        tokio::spawn(async move { leadership.take_over().await }.in_current_span());
+                    }
+                }
+            }
+            .in_current_span(),
+        );
     }
 
     fn start_health_check(&self) {
         let delay = self.shard_manager_config.health_check.delay;
-        let shard_management = self.shard_management.clone();
+        let leadership = self.leadership.clone();
         let health_check = self.health_check.clone();
+        let worker_executor_service = self
+            .shard_manager_config
+            .load_balancing
+            .enabled
+            .then(|| self.worker_executor_service.clone());
 
         tokio::spawn(
             async move {
                 loop {
                     tokio::time::sleep(delay).await;
-                    Self::health_check(shard_management.clone(), health_check.clone()).await
+                    if let Some(shard_management) = leadership.shard_management().await {
+                        Self::health_check(
+                            shard_management,
+                            health_check.clone(),
+                            worker_executor_service.clone(),
+                        )
+                        .await
+                    }
                 }
             }
             .in_current_span(),
         );
     }
 
+    /// Checks the health of all registered pods. If load balancing is enabled, the shard loads of
+    /// the healthy pods are collected by the same health check.
     async fn health_check(
         shard_management: ShardManagement,
         health_check: Arc<dyn HealthCheck + Send + Sync>,
+        worker_executor_service: Option<Arc<dyn WorkerExecutorService + Send + Sync>>,
     ) {
         debug!("Scheduled to conduct health check");
         let routing_table = shard_management.current_snapshot().await;
         debug!("Checking health of registered pods...");
-        let failed_pods = get_unhealthy_pods(health_check, &routing_table.get_pods()).await;
+        let pods = routing_table.get_pods();
+        let (failed_pods, shard_loads) = match &worker_executor_service {
+            Some(worker_executor_service) => {
+                get_unhealthy_pods_with_shard_loads(
+                    health_check,
+                    worker_executor_service.clone(),
+                    &pods,
+                )
+                .await
+            }
+            None => (get_unhealthy_pods(health_check, &pods).await, None),
+        };
         if failed_pods.is_empty() {
             debug!("All registered pods are healthy")
         } else {
@@ -142,12 +302,20 @@ impl ShardManagerServiceImpl {
                 "The following pods were found to be unhealthy: {:?}",
                 failed_pods
             );
-            for failed_pod in failed_pods {
-                shard_management.unregister_pod(failed_pod).await;
+            for failed_pod in &failed_pods {
+                shard_management.unregister_pod(failed_pod.clone()).await;
             }
         }
 
         debug!("Finished checking health of registered pods");
+
+        // The shards of the unhealthy pods are reassigned first, balancing the load in a later round
+        if worker_executor_service.is_some() && failed_pods.is_empty() {
+            match shard_loads {
+                Some(shard_loads) => shard_management.update_shard_loads(shard_loads).await,
+                None => warn!("Failed to get shard loads, skipping load balancing"),
+            }
+        }
     }
 }
 
@@ -164,17 +332,136 @@ impl ShardManagerService for ShardManagerServiceImpl {
             .instrument(record.span.clone())
             .await;
 
+        let result = match response {
+            Ok(routing_table) => record.succeed(
+                golem::shardmanager::v1::get_routing_table_response::Result::Success(
+                    routing_table.into(),
+                ),
+            ),
+            Err(error) => {
+                let error: golem::shardmanager::v1::ShardManagerError = error.into();
+                record.fail(
+                    golem::shardmanager::v1::get_routing_table_response::Result::Failure(
+                        error.clone(),
+                    ),
+                    &ShardManagerTraceErrorKind(&error),
+                )
+            }
+        };
+
         Ok(Response::new(
             golem::shardmanager::v1::GetRoutingTableResponse {
-                result: Some(
-                    golem::shardmanager::v1::get_routing_table_response::Result::Success(
-                        response.into(),
-                    ),
+                result: Some(result),
+            },
+        ))
+    }
+
+    async fn get_load_balancing_status(
+        &self,
+        _request: tonic::Request<golem::shardmanager::v1::GetLoadBalancingStatusRequest>,
+    ) -> Result<Response<golem::shardmanager::v1::GetLoadBalancingStatusResponse>, tonic::Status>
+    {
+        let record = recorded_grpc_api_request!("get_load_balancing_status",);
+
+        let response = self
+            .get_load_balancing_status_internal()
+            .instrument(record.span.clone())
+            .await;
+
+        let result = match response {
+            Ok(status) => record.succeed(
+                golem::shardmanager::v1::get_load_balancing_status_response::Result::Success(
+                    status.into(),
                 ),
+            ),
+            Err(error) => {
+                let error: golem::shardmanager::v1::ShardManagerError = error.into();
+                record.fail(
+                    golem::shardmanager::v1::get_load_balancing_status_response::Result::Failure(
+                        error.clone(),
+                    ),
+                    &ShardManagerTraceErrorKind(&error),
+                )
+            }
+        };
+
+        Ok(Response::new(
+            golem::shardmanager::v1::GetLoadBalancingStatusResponse {
+                result: Some(result),
             },
         ))
     }
 
+    async fn drain(
+        &self,
+        request: tonic::Request<golem::shardmanager::v1::DrainRequest>,
+    ) -> Result<Response<golem::shardmanager::v1::DrainResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let record = recorded_grpc_api_request!(
+            "drain",
+            host = &request.host,
+            port = &request.port.to_string(),
+            pod_name = request.pod_name.as_deref(),
+        );
+
+        let response = self
+            .drain_internal(request.into())
+            .instrument(record.span.clone())
+            .await;
+
+        let result = match response {
+            Ok(status) => record.succeed(golem::shardmanager::v1::drain_response::Result::Success(
+                status.into(),
+            )),
+            Err(error) => {
+                let error: golem::shardmanager::v1::ShardManagerError = error.into();
+                record.fail(
+                    golem::shardmanager::v1::drain_response::Result::Failure(error.clone()),
+                    &ShardManagerTraceErrorKind(&error),
+                )
+            }
+        };
+
+        Ok(Response::new(golem::shardmanager::v1::DrainResponse {
+            result: Some(result),
+        }))
+    }
+
+    async fn undrain(
+        &self,
+        request: tonic::Request<golem::shardmanager::v1::UndrainRequest>,
+    ) -> Result<Response<golem::shardmanager::v1::UndrainResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let record = recorded_grpc_api_request!(
+            "undrain",
+            host = &request.host,
+            port = &request.port.to_string(),
+            pod_name = request.pod_name.as_deref(),
+        );
+
+        let response = self
+            .undrain_internal(request.into())
+            .instrument(record.span.clone())
+            .await;
+
+        let result = match response {
+            Ok(()) => record.succeed(golem::shardmanager::v1::undrain_response::Result::Success(
+                golem::common::Empty {},
+            )),
+            Err(error) => {
+                let error: golem::shardmanager::v1::ShardManagerError = error.into();
+                record.fail(
+                    golem::shardmanager::v1::undrain_response::Result::Failure(error.clone()),
+                    &ShardManagerTraceErrorKind(&error),
+                )
+            }
+        };
+
+        Ok(Response::new(golem::shardmanager::v1::UndrainResponse {
+            result: Some(result),
+        }))
+    }
+
     async fn register(
         &self,
         request: tonic::Request<golem::shardmanager::v1::RegisterRequest>,
@@ -194,11 +481,13 @@ impl ShardManagerService for ShardManagerServiceImpl {
             .await;
 
         let result = match response {
-            Ok(_) => record.succeed(golem::shardmanager::v1::register_response::Result::Success(
-                golem::shardmanager::v1::RegisterSuccess {
-                    number_of_shards: self.shard_manager_config.number_of_shards as u32,
-                },
-            )),
+            Ok(number_of_shards) => {
+                record.succeed(golem::shardmanager::v1::register_response::Result::Success(
+                    golem::shardmanager::v1::RegisterSuccess {
+                        number_of_shards: number_of_shards as u32,
+                    },
+                ))
+            }
             Err(error) => {
                 let error: golem::shardmanager::v1::ShardManagerError = error.into();
                 record.fail(
@@ -230,33 +519,48 @@ pub async fn run(
         .register_encoded_file_descriptor_set(proto::FILE_DESCRIPTOR_SET)
         .build_v1()?;
 
-    let http_port = golem_service_base::observability::start_health_and_metrics_server(
-        SocketAddrV4::new(Ipv4Addr::new(0, 0, 0, 0), shard_manager_config.http_port),
-        registry,
-        "shard manager is running",
-        join_set,
-    )
-    .await?;
-
     let shard_manager_config = Arc::new(shard_manager_config.clone());
 
-    let persistence_service: Arc<dyn RoutingTablePersistence + Send + Sync> =
-        match &shard_manager_config.persistence {
-            PersistenceConfig::Redis(redis) => {
-                let pool = golem_common::redis::RedisPool::configured(redis).await?;
-                Arc::new(RoutingTableRedisPersistence::new(
-                    &pool,
-                    shard_manager_config.number_of_shards,
-                ))
-            }
-            PersistenceConfig::FileSystem(fs) => Arc::new(
-                RoutingTableFileSystemPersistence::new(
-                    &fs.path,
-                    shard_manager_config.number_of_shards,
+    let replica_id = Uuid::new_v4().to_string();
+    let leader_election_enabled = shard_manager_config.leader_election.enabled;
+    let lease_duration = shard_manager_config.leader_election.lease_duration;
+
+    let (persistence_service, leader_election): (
+        Arc<dyn RoutingTablePersistence + Send + Sync>,
+        Arc<dyn LeaderElection + Send + Sync>,
+    ) = match &shard_manager_config.persistence {
+        PersistenceConfig::Redis(redis) => {
+            let pool = golem_common::redis::RedisPool::configured(redis).await?;
+            let persistence =
+                RoutingTableRedisPersistence::new(&pool, shard_manager_config.number_of_shards);
+            if leader_election_enabled {
+                (
+                    Arc::new(persistence.with_leader_fencing(replica_id.clone())),
+                    Arc::new(RedisLeaderElection::new(&pool, replica_id, lease_duration)),
                 )
-                .await?,
-            ),
-        };
+            } else {
+                (Arc::new(persistence), Arc::new(NoLeaderElection))
+            }
+        }
+        PersistenceConfig::FileSystem(fs) => {
+            let leader_election: Arc<dyn LeaderElection + Send + Sync> = if leader_election_enabled
+            {
+                Arc::new(FileSystemLeaderElection::new(&fs.path))
+            } else {
+                Arc::new(NoLeaderElection)
+            };
+            (
+                Arc::new(
+                    RoutingTableFileSystemPersistence::new(
+                        &fs.path,
+                        shard_manager_config.number_of_shards,
+                    )
+                    .await?,
+                ),
+                leader_election,
+            )
+        }
+    };
     let worker_executors = Arc::new(WorkerExecutorServiceDefault::new(
         shard_manager_config.worker_executors.clone(),
     ));
@@ -285,10 +589,21 @@ pub async fn run(
         worker_executors,
         shard_manager_config.clone(),
         health_check,
+        leader_election,
+    )
+    .await?;
+    let shard_manager = Arc::new(shard_manager);
+
+    let http_port = golem_service_base::observability::start_health_and_metrics_server_with_routes(
+        SocketAddrV4::new(Ipv4Addr::new(0, 0, 0, 0), shard_manager_config.http_port),
+        registry,
+        "shard manager is running",
+        http_api::routes(shard_manager.clone()),
+        join_set,
     )
     .await?;
 
-    let service = ShardManagerServiceServer::new(shard_manager);
+    let service = ShardManagerServiceServer::from_arc(shard_manager);
 
     let shard_manager_port_str =
         env::var("GOLEM_SHARD_MANAGER_PORT").unwrap_or(shard_manager_config.grpc_port.to_string());
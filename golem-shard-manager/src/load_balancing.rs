@@ -0,0 +1,424 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::model::{Assignments, Pod, RoutingTable, ShardLoad, Unassignments};
+use crate::rebalancing::Rebalance;
+use crate::shard_manager_config::LoadBalancingConfig;
+use crate::zones::routing_tables_by_zone;
+use golem_api_grpc::proto::golem;
+use golem_common::model::ShardId;
+use std::collections::{BTreeMap, BTreeSet};
+use tracing::trace;
+
+/// The load of a pod, summed over its assigned shards
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PodLoad {
+    pub shard_count: usize,
+    /// Weighted load, see `LoadBalancingConfig`
+    pub load: f64,
+    pub total: ShardLoad,
+}
+
+/// A shard moved between pods by the load-aware rebalancing
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShardMove {
+    pub shard_id: ShardId,
+    pub source: Pod,
+    pub target: Pod,
+    pub load: f64,
+}
+
+/// The decisions of the last load-aware rebalancing round
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LoadBalancingStatus {
+    /// The pod loads before applying the moves
+    pub pod_loads: BTreeMap<Pod, PodLoad>,
+    /// The difference between the most and least loaded pods, relative to the average pod load
+    pub imbalance: f64,
+    /// The imbalance expected after applying the moves
+    pub expected_imbalance: f64,
+    pub moves: Vec<ShardMove>,
+}
+
+impl From<LoadBalancingStatus> for golem::shardmanager::v1::LoadBalancingStatus {
+    fn from(value: LoadBalancingStatus) -> Self {
+        Self {
+            pod_loads: value
+                .pod_loads
+                .into_iter()
+                .map(|(pod, pod_load)| golem::shardmanager::v1::PodLoad {
+                    pod: Some(pod.into()),
+                    shard_count: pod_load.shard_count as u32,
+                    load: pod_load.load,
+                    active_workers: pod_load.total.active_workers,
+                    memory: pod_load.total.memory,
+                    consumed_fuel: pod_load.total.consumed_fuel,
+                })
+                .collect(),
+            imbalance: value.imbalance,
+            expected_imbalance: value.expected_imbalance,
+            moves: value
+                .moves
+                .into_iter()
+                .map(|shard_move| golem::shardmanager::v1::ShardMove {
+                    shard_id: Some(shard_move.shard_id.into()),
+                    source: Some(shard_move.source.into()),
+                    target: Some(shard_move.target.into()),
+                    load: shard_move.load,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Constructs a rebalance plan moving shards from the most loaded pods to the least loaded ones.
+///
+/// Each round moves at most `max_moves_per_round` shards, choosing the shard that halves the load
+/// difference between the two pods most closely. It stops when the imbalance gets below the
+/// configured `imbalance_threshold`, or when no move would reduce it. The shard count of a pod is
+/// kept within the bounds of the count based rebalancing's `count_threshold`, so the two
+/// strategies never undo each other's moves.
+pub fn plan_load_rebalance(
+    routing_table: &RoutingTable,
+    shard_loads: &BTreeMap<ShardId, ShardLoad>,
+    count_threshold: f64,
+    config: &LoadBalancingConfig,
+) -> (Rebalance, LoadBalancingStatus) {
+    let weighted_loads = weighted_loads(shard_loads, config);
+    let weighted_load = |shard_id: &ShardId| weighted_loads.get(shard_id).copied().unwrap_or(0.0);
+
+    let mut entries: Vec<(Pod, BTreeSet<ShardId>, f64)> = routing_table
+        .shard_assignments
+        .iter()
+        .map(|(pod, shard_ids)| {
+            let load: f64 = shard_ids.iter().map(weighted_load).sum();
+            (pod.clone(), shard_ids.clone(), load)
+        })
+        .collect();
+
+    let pod_loads = entries
+        .iter()
+        .map(|(pod, shard_ids, load)| {
+            let mut total = ShardLoad::default();
+            for shard_id in shard_ids {
+                if let Some(shard_load) = shard_loads.get(shard_id) {
+                    total.add(shard_load);
+                }
+            }
+            (
+                pod.clone(),
+                PodLoad {
+                    shard_count: shard_ids.len(),
+                    load: *load,
+                    total,
+                },
+            )
+        })
+        .collect();
+
+    let mut status = LoadBalancingStatus {
+        pod_loads,
+        imbalance: imbalance(&entries),
+        expected_imbalance: imbalance(&entries),
+        moves: Vec::new(),
+    };
+
+    let pod_count = entries.len();
+    if pod_count < 2 {
+        return (Rebalance::empty(), status);
+    }
+
+    let optimal_count = routing_table.number_of_shards / pod_count;
+    let upper_threshold = (optimal_count as f64 * (1.0 + count_threshold)).ceil() as usize;
+    let lower_threshold = (optimal_count as f64 * (1.0 - count_threshold)).floor() as usize;
+
+    let mut assignments = Assignments::new();
+    let mut unassignments = Unassignments::new();
+    let mut moved_shards = BTreeSet::new();
+
+    for _ in 0..config.max_moves_per_round {
+        if imbalance(&entries) <= config.imbalance_threshold {
+            trace!("Pod loads are balanced");
+            break;
+        }
+
+        let source_idx = (0..pod_count)
+            .filter(|idx| entries[*idx].1.len() > lower_threshold)
+            .max_by(|a, b| entries[*a].2.total_cmp(&entries[*b].2));
+        let target_idx = (0..pod_count)
+            .filter(|idx| entries[*idx].1.len() < upper_threshold)
+            .min_by(|a, b| entries[*a].2.total_cmp(&entries[*b].2));
+
+        let (source_idx, target_idx) = match (source_idx, target_idx) {
+            (Some(source_idx), Some(target_idx)) if source_idx != target_idx => {
+                (source_idx, target_idx)
+            }
+            _ => {
+                trace!("No pods left to move shards between");
+                break;
+            }
+        };
+
+        // Any shard lighter than the difference reduces it, the best one halves it
+        let difference = entries[source_idx].2 - entries[target_idx].2;
+        let candidate = entries[source_idx]
+            .1
+            .iter()
+            .filter(|shard_id| !moved_shards.contains(*shard_id))
+            .map(|shard_id| (*shard_id, weighted_load(shard_id)))
+            .filter(|(_, load)| *load > 0.0 && *load < difference)
+            .min_by(|(_, a), (_, b)| {
+                (a - difference / 2.0)
+                    .abs()
+                    .total_cmp(&(b - difference / 2.0).abs())
+            });
+
+        let Some((shard_id, load)) = candidate else {
+            trace!("No shard move would reduce the imbalance");
+            break;
+        };
+
+        trace!(
+            "Moving shard {} with load {} from {} to {}",
+            shard_id,
+            load,
+            entries[source_idx].0,
+            entries[target_idx].0
+        );
+
+        entries[source_idx].1.remove(&shard_id);
+        entries[source_idx].2 -= load;
+        entries[target_idx].1.insert(shard_id);
+        entries[target_idx].2 += load;
+        moved_shards.insert(shard_id);
+
+        assignments.assign(entries[target_idx].0.clone(), shard_id);
+        unassignments.unassign(entries[source_idx].0.clone(), shard_id);
+        status.moves.push(ShardMove {
+            shard_id,
+            source: entries[source_idx].0.clone(),
+            target: entries[target_idx].0.clone(),
+            load,
+        });
+    }
+
+    status.expected_imbalance = imbalance(&entries);
+
+    (Rebalance::new(assignments, unassignments), status)
+}
+
+/// Plans the load-aware rebalancing separately for each zone, so shards never move between
+/// zones (see `plan_load_rebalance`). The reported imbalance is the highest among the zones.
+pub fn plan_load_rebalance_within_zones(
+    routing_table: &RoutingTable,
+    shard_loads: &BTreeMap<ShardId, ShardLoad>,
+    count_threshold: f64,
+    config: &LoadBalancingConfig,
+) -> (Rebalance, LoadBalancingStatus) {
+    let mut rebalance = Rebalance::empty();
+    let mut status = LoadBalancingStatus::default();
+    for zone_routing_table in routing_tables_by_zone(routing_table).values() {
+        let (zone_rebalance, zone_status) =
+            plan_load_rebalance(zone_routing_table, shard_loads, count_threshold, config);
+        rebalance.extend(zone_rebalance);
+        status.pod_loads.extend(zone_status.pod_loads);
+        status.imbalance = status.imbalance.max(zone_status.imbalance);
+        status.expected_imbalance = status
+            .expected_imbalance
+            .max(zone_status.expected_imbalance);
+        status.moves.extend(zone_status.moves);
+    }
+    (rebalance, status)
+}
+
+/// Calculates the weighted load of each shard, normalizing each kind of load by its total
+/// so they can be combined regardless of their units
+fn weighted_loads(
+    shard_loads: &BTreeMap<ShardId, ShardLoad>,
+    config: &LoadBalancingConfig,
+) -> BTreeMap<ShardId, f64> {
+    let mut total = ShardLoad::default();
+    for shard_load in shard_loads.values() {
+        total.add(shard_load);
+    }
+
+    let share = |value: u64, total: u64, weight: f64| {
+        if total == 0 {
+            0.0
+        } else {
+            weight * value as f64 / total as f64
+        }
+    };
+
+    shard_loads
+        .iter()
+        .map(|(shard_id, shard_load)| {
+            let load = share(
+                shard_load.active_workers,
+                total.active_workers,
+                config.active_workers_weight,
+            ) + share(shard_load.memory, total.memory, config.memory_weight)
+                + share(
+                    shard_load.consumed_fuel,
+                    total.consumed_fuel,
+                    config.consumed_fuel_weight,
+                );
+            (*shard_id, load)
+        })
+        .collect()
+}
+
+fn imbalance(entries: &[(Pod, BTreeSet<ShardId>, f64)]) -> f64 {
+    if entries.is_empty() {
+        return 0.0;
+    }
+
+    let total: f64 = entries.iter().map(|(_, _, load)| load).sum();
+    let mean = total / entries.len() as f64;
+    if mean <= 0.0 {
+        return 0.0;
+    }
+
+    let max = entries
+        .iter()
+        .map(|(_, _, load)| *load)
+        .fold(f64::MIN, f64::max);
+    let min = entries
+        .iter()
+        .map(|(_, _, load)| *load)
+        .fold(f64::MAX, f64::min);
+    (max - min) / mean
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use golem_common::model::ShardId;
+    use std::collections::{BTreeMap, BTreeSet};
+
+    use crate::load_balancing::plan_load_rebalance;
+    use crate::model::{Pod, RoutingTable, ShardLoad};
+    use crate::shard_manager_config::LoadBalancingConfig;
+    use crate::zones::routing_tables_by_zone;
+
+    fn pod(idx: usize) -> Pod {
+        Pod::new(format!("pod{idx}"), (9000 + idx) as u16)
+    }
+
+    fn routing_table(number_of_shards: usize, assignments: Vec<(usize, Vec<i64>)>) -> RoutingTable {
+        let mut routing_table = RoutingTable::new(number_of_shards);
+        for (pod_idx, shards) in assignments {
+            routing_table
+                .shard_assignments
+                .insert(pod(pod_idx), shards.into_iter().map(ShardId::new).collect());
+        }
+        routing_table
+    }
+
+    fn active_workers(loads: Vec<(i64, u64)>) -> BTreeMap<ShardId, ShardLoad> {
+        loads
+            .into_iter()
+            .map(|(shard_id, active_workers)| {
+                (
+                    ShardId::new(shard_id),
+                    ShardLoad {
+                        active_workers,
+                        ..ShardLoad::default()
+                    },
+                )
+            })
+            .collect()
+    }
+
+    fn config(max_moves_per_round: usize) -> LoadBalancingConfig {
+        LoadBalancingConfig {
+            enabled: true,
+            imbalance_threshold: 0.2,
+            max_moves_per_round,
+            ..LoadBalancingConfig::default()
+        }
+    }
+
+    #[test]
+    fn balanced_loads_are_not_moved() {
+        let table = routing_table(4, vec![(0, vec![0, 1]), (1, vec![2, 3])]);
+        let loads = active_workers(vec![(0, 10), (1, 10), (2, 12), (3, 9)]);
+
+        let (rebalance, status) = plan_load_rebalance(&table, &loads, 0.5, &config(4));
+
+        assert!(rebalance.is_empty());
+        assert!(status.moves.is_empty());
+        assert!(status.imbalance < 0.2);
+    }
+
+    #[test]
+    fn moves_shards_from_the_most_loaded_pod() {
+        let table = routing_table(8, vec![(0, vec![0, 1, 2, 3]), (1, vec![4, 5, 6, 7])]);
+        let loads = active_workers(vec![
+            (0, 40),
+            (1, 30),
+            (2, 20),
+            (3, 10),
+            (4, 1),
+            (5, 1),
+            (6, 1),
+            (7, 1),
+        ]);
+
+        let (rebalance, status) = plan_load_rebalance(&table, &loads, 0.5, &config(4));
+
+        // The heaviest shard first, then a lighter one to even out the difference
+        let moved_shards: Vec<_> = status.moves.iter().map(|m| m.shard_id).collect();
+        assert_eq!(moved_shards, vec![ShardId::new(0), ShardId::new(3)]);
+        assert!(status
+            .moves
+            .iter()
+            .all(|m| m.source == pod(0) && m.target == pod(1)));
+        assert!(status.expected_imbalance < 0.2);
+        assert_eq!(
+            rebalance.get_assignments().assignments.get(&pod(1)),
+            Some(&BTreeSet::from([ShardId::new(0), ShardId::new(3)]))
+        );
+        assert_eq!(
+            rebalance.get_unassignments().unassignments.get(&pod(0)),
+            Some(&BTreeSet::from([ShardId::new(0), ShardId::new(3)]))
+        );
+    }
+
+    #[test]
+    fn limits_moves_per_round() {
+        let table = routing_table(
+            12,
+            vec![(0, vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9]), (1, vec![10, 11])],
+        );
+        let loads = active_workers((0..12).map(|shard_id| (shard_id, 10)).collect());
+
+        let (_, status) = plan_load_rebalance(&table, &loads, 1.0, &config(2));
+
+        assert_eq!(status.moves.len(), 2);
+        assert!(status.expected_imbalance > 0.2);
+    }
+
+    #[test]
+    fn keeps_shard_counts_within_the_count_threshold() {
+        let table = routing_table(4, vec![(0, vec![0, 1]), (1, vec![2, 3])]);
+        let loads = active_workers(vec![(0, 100), (1, 100), (2, 1), (3, 1)]);
+
+        let (rebalance, status) = plan_load_rebalance(&table, &loads, 0.0, &config(4));
+
+        assert!(rebalance.is_empty());
+        assert!(status.moves.is_empty());
+    }
+}
@@ -0,0 +1,48 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::load_balancing::LoadBalancingStatus;
+use lazy_static::lazy_static;
+use prometheus::*;
+
+lazy_static! {
+    static ref POD_LOAD: GaugeVec = register_gauge_vec!(
+        "shard_manager_pod_load",
+        "Weighted load of the pods used by the load-aware rebalancing",
+        &["pod"]
+    )
+    .unwrap();
+    static ref LOAD_IMBALANCE: Gauge = register_gauge!(
+        "shard_manager_load_imbalance",
+        "Difference between the most and least loaded pods, relative to the average pod load"
+    )
+    .unwrap();
+    static ref LOAD_REBALANCE_MOVES_TOTAL: IntCounter = register_int_counter!(
+        "shard_manager_load_rebalance_moves_total",
+        "Number of shards moved by the load-aware rebalancing"
+    )
+    .unwrap();
+}
+
+pub fn record_load_balancing(status: &LoadBalancingStatus) {
+    // Resetting first to drop the pods which are no longer part of the routing table
+    POD_LOAD.reset();
+    for (pod, pod_load) in &status.pod_loads {
+        POD_LOAD
+            .with_label_values(&[pod.to_string()])
+            .set(pod_load.load);
+    }
+    LOAD_IMBALANCE.set(status.imbalance);
+    LOAD_REBALANCE_MOVES_TOTAL.inc_by(status.moves.len() as u64);
+}
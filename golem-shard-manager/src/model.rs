@@ -17,7 +17,7 @@ use crate::rebalancing::Rebalance;
 use core::cmp::Ordering;
 use desert_rust::BinaryCodec;
 use golem_api_grpc::proto::golem;
-use golem_common::model::ShardId;
+use golem_common::model::{ShardId, Timestamp};
 use http::Uri;
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
@@ -32,12 +32,14 @@ use tracing::{error, warn};
 #[derive(
     Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd, Serialize, Deserialize, BinaryCodec,
 )]
-#[desert(evolution())]
+#[desert(evolution(FieldAdded("zone", None)))]
 pub struct Pod {
     host: String,
     ip: IpAddr,
     port: u16,
     pub pod_name: Option<String>,
+    /// The availability zone of the pod, used by the zone-aware shard placement
+    pub zone: Option<String>,
 }
 
 impl Pod {
@@ -48,6 +50,15 @@ impl Pod {
             port,
             pod_name: None,
             ip: IpAddr::V4(std::net::Ipv4Addr::LOCALHOST),
+            zone: None,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn with_zone(self, zone: &str) -> Self {
+        Self {
+            zone: Some(zone.to_string()),
+            ..self
         }
     }
 
@@ -77,6 +88,7 @@ impl Pod {
             port: request.port as u16,
             pod_name: request.pod_name,
             ip: source_ip,
+            zone: request.zone,
         };
 
         match pod.address() {
@@ -106,12 +118,63 @@ impl Pod {
     }
 }
 
+/// Identifies a registered pod by its pod name if given, otherwise by the host and port it
+/// registered with
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, BinaryCodec)]
+#[desert(evolution())]
+pub struct PodSelector {
+    #[serde(default)]
+    pub host: String,
+    #[serde(default)]
+    pub port: u16,
+    pub pod_name: Option<String>,
+}
+
+impl PodSelector {
+    pub fn matches(&self, pod: &Pod) -> bool {
+        match &self.pod_name {
+            Some(pod_name) => pod.pod_name.as_ref() == Some(pod_name),
+            None => pod.host == self.host && pod.port == self.port,
+        }
+    }
+}
+
+impl From<golem::shardmanager::v1::DrainRequest> for PodSelector {
+    fn from(value: golem::shardmanager::v1::DrainRequest) -> Self {
+        Self {
+            host: value.host,
+            port: value.port as u16,
+            pod_name: value.pod_name,
+        }
+    }
+}
+
+impl From<golem::shardmanager::v1::UndrainRequest> for PodSelector {
+    fn from(value: golem::shardmanager::v1::UndrainRequest) -> Self {
+        Self {
+            host: value.host,
+            port: value.port as u16,
+            pod_name: value.pod_name,
+        }
+    }
+}
+
+impl Display for PodSelector {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match &self.pod_name {
+            Some(name) => write!(f, "{name}"),
+            None => write!(f, "{}:{}", self.host, self.port),
+        }
+    }
+}
+
 impl From<Pod> for golem::shardmanager::Pod {
     fn from(value: Pod) -> golem::shardmanager::Pod {
         golem::shardmanager::Pod {
             host: value.ip.to_string(),
             port: value.port as u32,
             pod_name: value.pod_name,
+            zone: value.zone,
         }
     }
 }
@@ -364,6 +427,147 @@ impl Display for Unassignments {
     }
 }
 
+/// The progress of draining a pod
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct DrainStatus {
+    pub drained: bool,
+    pub remaining_shards: usize,
+}
+
+impl DrainStatus {
+    pub fn new(remaining_shards: usize) -> Self {
+        Self {
+            drained: remaining_shards == 0,
+            remaining_shards,
+        }
+    }
+}
+
+impl From<DrainStatus> for golem::shardmanager::v1::DrainStatus {
+    fn from(value: DrainStatus) -> Self {
+        Self {
+            drained: value.drained,
+            remaining_shards: value.remaining_shards as u32,
+        }
+    }
+}
+
+/// Manual overrides of the shard placement made through the admin API, persisted next to the
+/// routing table
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, BinaryCodec)]
+#[desert(evolution())]
+pub struct ShardOverrides {
+    /// Shards assigned to the selected pod whenever it is available, regardless of balancing
+    pub pinned_shards: Vec<(ShardId, PodSelector)>,
+    /// While frozen, unassigned shards still get assigned but no shard is moved between pods
+    pub rebalancing_frozen: bool,
+    /// The last `MAX_AUDIT_LOG_ENTRIES` admin actions, oldest first
+    pub audit_log: Vec<AuditLogEntry>,
+}
+
+impl ShardOverrides {
+    pub const MAX_AUDIT_LOG_ENTRIES: usize = 1000;
+
+    pub fn pinned_pod(&self, shard_id: &ShardId) -> Option<&PodSelector> {
+        self.pinned_shards
+            .iter()
+            .find(|(pinned_shard_id, _)| pinned_shard_id == shard_id)
+            .map(|(_, selector)| selector)
+    }
+
+    /// Applies an admin action and records it in the audit log
+    pub fn apply(&mut self, entry: AuditLogEntry) {
+        match &entry.action {
+            AdminAction::PinShard { shard_id, pod } => {
+                self.pinned_shards
+                    .retain(|(pinned_shard_id, _)| pinned_shard_id != shard_id);
+                self.pinned_shards.push((*shard_id, pod.clone()));
+            }
+            AdminAction::UnpinShard { shard_id } => {
+                self.pinned_shards
+                    .retain(|(pinned_shard_id, _)| pinned_shard_id != shard_id);
+            }
+            AdminAction::FreezeRebalancing => self.rebalancing_frozen = true,
+            AdminAction::UnfreezeRebalancing => self.rebalancing_frozen = false,
+            AdminAction::TriggerRebalance => {}
+        }
+
+        self.audit_log.push(entry);
+        if self.audit_log.len() > Self::MAX_AUDIT_LOG_ENTRIES {
+            let excess = self.audit_log.len() - Self::MAX_AUDIT_LOG_ENTRIES;
+            self.audit_log.drain(..excess);
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, BinaryCodec)]
+#[desert(evolution())]
+pub struct AuditLogEntry {
+    pub timestamp: Timestamp,
+    pub action: AdminAction,
+    pub requested_by: Option<String>,
+    pub reason: Option<String>,
+}
+
+impl AuditLogEntry {
+    pub fn new(action: AdminAction, requested_by: Option<String>, reason: Option<String>) -> Self {
+        Self {
+            timestamp: Timestamp::now_utc(),
+            action,
+            requested_by,
+            reason,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, BinaryCodec)]
+#[desert(evolution())]
+pub enum AdminAction {
+    PinShard { shard_id: ShardId, pod: PodSelector },
+    UnpinShard { shard_id: ShardId },
+    FreezeRebalancing,
+    UnfreezeRebalancing,
+    TriggerRebalance,
+}
+
+impl Display for AdminAction {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            AdminAction::PinShard { shard_id, pod } => write!(f, "pin shard {shard_id} to {pod}"),
+            AdminAction::UnpinShard { shard_id } => write!(f, "unpin shard {shard_id}"),
+            AdminAction::FreezeRebalancing => write!(f, "freeze rebalancing"),
+            AdminAction::UnfreezeRebalancing => write!(f, "unfreeze rebalancing"),
+            AdminAction::TriggerRebalance => write!(f, "trigger rebalance"),
+        }
+    }
+}
+
+/// The load of a shard, as reported by the worker executor it is assigned to
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ShardLoad {
+    pub active_workers: u64,
+    pub memory: u64,
+    pub consumed_fuel: u64,
+}
+
+impl ShardLoad {
+    pub fn add(&mut self, other: &ShardLoad) {
+        self.active_workers = self.active_workers.saturating_add(other.active_workers);
+        self.memory = self.memory.saturating_add(other.memory);
+        self.consumed_fuel = self.consumed_fuel.saturating_add(other.consumed_fuel);
+    }
+}
+
+impl From<golem::workerexecutor::v1::ShardLoad> for ShardLoad {
+    fn from(value: golem::workerexecutor::v1::ShardLoad) -> Self {
+        Self {
+            active_workers: value.active_workers,
+            memory: value.memory,
+            consumed_fuel: value.consumed_fuel,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize, BinaryCodec)]
 #[desert(evolution())]
 pub struct ShardManagerState {
@@ -13,9 +13,11 @@
 // limitations under the License.
 
 use crate::error::ShardManagerError;
-use crate::model::{RoutingTable, ShardManagerState};
+use crate::leader_election::RedisLeaderElection;
+use crate::model::{RoutingTable, ShardManagerState, ShardOverrides};
 use async_trait::async_trait;
 use bytes::Bytes;
+use fred::types::Value;
 use golem_common::redis::RedisPool;
 use golem_common::serialization::{deserialize, serialize};
 use std::path::{Path, PathBuf};
@@ -24,11 +26,16 @@ use std::path::{Path, PathBuf};
 pub trait RoutingTablePersistence {
     async fn write(&self, routing_table: &RoutingTable) -> Result<(), ShardManagerError>;
     async fn read(&self) -> Result<RoutingTable, ShardManagerError>;
+    async fn write_overrides(&self, overrides: &ShardOverrides) -> Result<(), ShardManagerError>;
+    async fn read_overrides(&self) -> Result<ShardOverrides, ShardManagerError>;
 }
 
 pub struct RoutingTableRedisPersistence {
     pool: RedisPool,
     number_of_shards: usize,
+    /// The id of this replica when the leader election is enabled, only writing while
+    /// it holds the leader key
+    replica_id: Option<String>,
 }
 
 #[async_trait]
@@ -41,11 +48,7 @@ impl RoutingTablePersistence for RoutingTableRedisPersistence {
             .serialize(&shard_manager_state)
             .map_err(ShardManagerError::SerializationError)?;
 
-        self.pool
-            .with("persistence", "write")
-            .set(key, value, None, None, false)
-            .await
-            .map_err(ShardManagerError::RedisError)
+        self.set(key, value, "write").await
     }
 
     async fn read(&self) -> Result<RoutingTable, ShardManagerError> {
@@ -69,27 +72,113 @@ impl RoutingTablePersistence for RoutingTableRedisPersistence {
             None => Ok(RoutingTable::new(self.number_of_shards)),
         }
     }
+
+    async fn write_overrides(&self, overrides: &ShardOverrides) -> Result<(), ShardManagerError> {
+        let key = "shard:shard_manager_overrides";
+        let value = self
+            .pool
+            .serialize(overrides)
+            .map_err(ShardManagerError::SerializationError)?;
+
+        self.set(key, value, "write_overrides").await
+    }
+
+    async fn read_overrides(&self) -> Result<ShardOverrides, ShardManagerError> {
+        let key = "shard:shard_manager_overrides";
+
+        let value: Option<Bytes> = self
+            .pool
+            .with("persistence", "read_overrides")
+            .get(key)
+            .await
+            .map_err(ShardManagerError::RedisError)?;
+
+        match value {
+            Some(value) => self
+                .pool
+                .deserialize(&value)
+                .map_err(ShardManagerError::SerializationError),
+            None => Ok(ShardOverrides::default()),
+        }
+    }
 }
 
 impl RoutingTableRedisPersistence {
+    // Sets the key only if the given replica holds the leader key, in the same atomic step
+    const FENCED_SET_SCRIPT: &'static str = r#"
+        if redis.call('get', KEYS[2]) == ARGV[2] then
+            redis.call('set', KEYS[1], ARGV[1])
+            return 1
+        else
+            return 0
+        end
+    "#;
+
     pub fn new(pool: &RedisPool, number_of_shards: usize) -> Self {
         Self {
             pool: pool.clone(),
             number_of_shards,
+            replica_id: None,
+        }
+    }
+
+    /// Rejects the writes unless the replica is the leader elected by `RedisLeaderElection`,
+    /// so a replica that lost its leadership without noticing it cannot overwrite the
+    /// routing table of the new leader
+    pub fn with_leader_fencing(mut self, replica_id: String) -> Self {
+        self.replica_id = Some(replica_id);
+        self
+    }
+
+    async fn set(
+        &self,
+        key: &str,
+        value: Vec<u8>,
+        operation: &'static str,
+    ) -> Result<(), ShardManagerError> {
+        match &self.replica_id {
+            Some(replica_id) => {
+                let written: i64 = self
+                    .pool
+                    .with("persistence", operation)
+                    .eval(
+                        Self::FENCED_SET_SCRIPT,
+                        vec![key, RedisLeaderElection::KEY],
+                        vec![Value::Bytes(value.into()), Value::from(replica_id.clone())],
+                    )
+                    .await
+                    .map_err(ShardManagerError::RedisError)?;
+
+                if written == 1 {
+                    Ok(())
+                } else {
+                    Err(ShardManagerError::NotLeader)
+                }
+            }
+            None => self
+                .pool
+                .with("persistence", operation)
+                .set(key, value, None, None, false)
+                .await
+                .map_err(ShardManagerError::RedisError),
         }
     }
 }
 
 pub struct RoutingTableFileSystemPersistence {
     path: PathBuf,
+    overrides_path: PathBuf,
     number_of_shards: usize,
 }
 
 impl RoutingTableFileSystemPersistence {
     pub async fn new(path: &Path, number_of_shards: usize) -> std::io::Result<Self> {
         tokio::fs::create_dir_all(path.parent().unwrap()).await?;
+        let mut overrides_path = path.as_os_str().to_owned();
+        overrides_path.push(".overrides");
         Ok(Self {
             path: path.to_path_buf(),
+            overrides_path: PathBuf::from(overrides_path),
             number_of_shards,
         })
     }
@@ -115,4 +204,19 @@ impl RoutingTablePersistence for RoutingTableFileSystemPersistence {
             Ok(RoutingTable::new(self.number_of_shards))
         }
     }
+
+    async fn write_overrides(&self, overrides: &ShardOverrides) -> Result<(), ShardManagerError> {
+        let encoded = serialize(overrides).map_err(ShardManagerError::SerializationError)?;
+        tokio::fs::write(&self.overrides_path, encoded).await?;
+        Ok(())
+    }
+
+    async fn read_overrides(&self) -> Result<ShardOverrides, ShardManagerError> {
+        if tokio::fs::try_exists(&self.overrides_path).await? {
+            let bytes = tokio::fs::read(&self.overrides_path).await?;
+            deserialize(&bytes).map_err(ShardManagerError::SerializationError)
+        } else {
+            Ok(ShardOverrides::default())
+        }
+    }
 }
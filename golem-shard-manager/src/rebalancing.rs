@@ -245,6 +245,68 @@ impl Rebalance {
             .or_default()
             .append(&mut shard_ids);
     }
+
+    pub fn add_unassignments(&mut self, pod: &Pod, shard_ids: BTreeSet<ShardId>) {
+        for shard_id in shard_ids {
+            self.unassignments.unassign(pod.clone(), shard_id);
+        }
+    }
+
+    /// Adds the assignments and unassignments of another plan, which must not touch the same shards
+    pub fn extend(&mut self, other: Rebalance) {
+        for (pod, shard_ids) in other.assignments.assignments {
+            for shard_id in shard_ids {
+                self.assignments.assign(pod.clone(), shard_id);
+            }
+        }
+        for (pod, shard_ids) in other.unassignments.unassignments {
+            self.add_unassignments(&pod, shard_ids);
+        }
+    }
+
+    /// Drops the planned moves of every shard currently assigned to a pod, keeping only the
+    /// assignments of the unassigned shards
+    pub fn retain_unassigned_shards(&mut self, routing_table: &RoutingTable) {
+        let unassigned_shards = routing_table.get_unassigned_shards();
+        for shard_ids in self.assignments.assignments.values_mut() {
+            shard_ids.retain(|shard_id| unassigned_shards.contains(shard_id));
+        }
+        self.unassignments.unassignments.clear();
+        self.remove_empty_entries();
+    }
+
+    /// Changes the plan so that the shard ends up on the given pod, moving it from the pod it
+    /// is currently assigned to in the routing table if needed
+    pub fn pin_shard(&mut self, routing_table: &RoutingTable, shard_id: ShardId, pod: &Pod) {
+        for shard_ids in self.assignments.assignments.values_mut() {
+            shard_ids.remove(&shard_id);
+        }
+        for shard_ids in self.unassignments.unassignments.values_mut() {
+            shard_ids.remove(&shard_id);
+        }
+
+        let current_pod = routing_table
+            .shard_assignments
+            .iter()
+            .find(|(_, shard_ids)| shard_ids.contains(&shard_id))
+            .map(|(current_pod, _)| current_pod);
+        if current_pod != Some(pod) {
+            if let Some(current_pod) = current_pod {
+                self.unassignments.unassign(current_pod.clone(), shard_id);
+            }
+            self.assignments.assign(pod.clone(), shard_id);
+        }
+        self.remove_empty_entries();
+    }
+
+    fn remove_empty_entries(&mut self) {
+        self.assignments
+            .assignments
+            .retain(|_, shard_ids| !shard_ids.is_empty());
+        self.unassignments
+            .unassignments
+            .retain(|_, shard_ids| !shard_ids.is_empty());
+    }
 }
 
 impl Display for Rebalance {
@@ -730,4 +792,47 @@ mod tests {
 
         assert_eq!(rebalance.unassignments.unassignments.len(), 0);
     }
+
+    #[test]
+    #[traced_test]
+    fn frozen_rebalance_only_assigns_unassigned_shards() {
+        let routing_table = new_routing_table(TestConfig {
+            number_of_shards: 6,
+            number_of_pods: 2,
+            initial_assignments: vec![(0, vec![0, 1, 2, 3])],
+        });
+
+        let mut rebalance = Rebalance::from_routing_table(&routing_table, 0.0);
+        rebalance.retain_unassigned_shards(&routing_table);
+
+        assert_assignments(
+            &rebalance,
+            vec![
+                //
+                (0, vec![]),
+                (1, vec![4, 5]),
+            ],
+        );
+        assert!(rebalance.get_unassignments().is_empty());
+    }
+
+    #[test]
+    #[traced_test]
+    fn pinned_shard_is_moved_to_its_pod() {
+        let routing_table = new_routing_table(TestConfig {
+            number_of_shards: 4,
+            number_of_pods: 2,
+            initial_assignments: vec![(0, vec![0, 1]), (1, vec![2, 3])],
+        });
+
+        let mut rebalance = Rebalance::from_routing_table(&routing_table, 0.0);
+        assert!(rebalance.is_empty());
+
+        rebalance.pin_shard(&routing_table, ShardId::new(0), &pod(1));
+        assert_assignments(&rebalance, vec![(1, vec![0])]);
+        assert_unassignments(&rebalance, vec![(0, vec![0])]);
+
+        rebalance.pin_shard(&routing_table, ShardId::new(0), &pod(0));
+        assert!(rebalance.is_empty());
+    }
 }
@@ -0,0 +1,159 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::ShardManagerError;
+use crate::model::RoutingTable;
+use golem_api_grpc::proto::golem;
+use golem_common::model::ShardId;
+use std::collections::BTreeSet;
+
+/// Returns the shards a shard of a table with `number_of_shards` shards is split into when
+/// the table grows to `new_number_of_shards` shards.
+///
+/// Workers are mapped to shards by `hash % number_of_shards`, so when `new_number_of_shards` is
+/// `k * number_of_shards`, every worker of shard `s` ends up in one of `s + i * number_of_shards`
+/// for `i` in `0..k`, and no other worker does.
+pub fn split_shard(
+    shard_id: &ShardId,
+    number_of_shards: usize,
+    new_number_of_shards: usize,
+) -> BTreeSet<ShardId> {
+    let factor = new_number_of_shards / number_of_shards;
+    let idx = golem::shardmanager::ShardId::from(*shard_id).value;
+    (0..factor)
+        .map(|i| ShardId::new(idx + (i * number_of_shards) as i64))
+        .collect()
+}
+
+/// Splits every shard of the routing table into a deterministic set of new shards, keeping
+/// them on the pod the original shard was assigned to.
+///
+/// As the split shards own exactly the workers of the original shard, no worker changes its pod
+/// and the old and the new routing table route every worker the same way. This makes it
+/// possible to roll out the new table without revoking any shards.
+pub fn split_shards(
+    routing_table: &RoutingTable,
+    new_number_of_shards: usize,
+) -> Result<RoutingTable, ShardManagerError> {
+    let current = routing_table.number_of_shards;
+    if current == 0 || new_number_of_shards < current || new_number_of_shards % current != 0 {
+        return Err(ShardManagerError::InvalidNumberOfShards {
+            current,
+            requested: new_number_of_shards,
+        });
+    }
+
+    let mut result = RoutingTable::new(new_number_of_shards);
+    for (pod, shard_ids) in &routing_table.shard_assignments {
+        let split = shard_ids
+            .iter()
+            .flat_map(|shard_id| split_shard(shard_id, current, new_number_of_shards))
+            .collect();
+        result.shard_assignments.insert(pod.clone(), split);
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use golem_common::model::{ComponentId, ShardId, WorkerId};
+    use std::collections::BTreeSet;
+
+    use crate::error::ShardManagerError;
+    use crate::model::{Pod, RoutingTable};
+    use crate::resharding::{split_shard, split_shards};
+
+    fn pod(idx: usize) -> Pod {
+        Pod::new(format!("pod{idx}"), (9000 + idx) as u16)
+    }
+
+    fn shard_ids(ids: Vec<i64>) -> BTreeSet<ShardId> {
+        ids.into_iter().map(ShardId::new).collect()
+    }
+
+    fn routing_table(number_of_shards: usize, assignments: Vec<(usize, Vec<i64>)>) -> RoutingTable {
+        let mut routing_table = RoutingTable::new(number_of_shards);
+        for (pod_idx, shards) in assignments {
+            routing_table
+                .shard_assignments
+                .insert(pod(pod_idx), shard_ids(shards));
+        }
+        routing_table
+    }
+
+    #[test]
+    fn split_shard_is_deterministic() {
+        assert_eq!(
+            split_shard(&ShardId::new(3), 4, 12),
+            shard_ids(vec![3, 7, 11])
+        );
+        assert_eq!(split_shard(&ShardId::new(0), 4, 4), shard_ids(vec![0]));
+    }
+
+    #[test]
+    fn split_keeps_shards_on_their_pods() {
+        let table = routing_table(4, vec![(0, vec![0, 1]), (1, vec![2, 3]), (2, vec![])]);
+        let split = split_shards(&table, 8).unwrap();
+
+        assert_eq!(split.number_of_shards, 8);
+        assert_eq!(split.get_shards(&pod(0)), Some(shard_ids(vec![0, 1, 4, 5])));
+        assert_eq!(split.get_shards(&pod(1)), Some(shard_ids(vec![2, 3, 6, 7])));
+        assert_eq!(split.get_shards(&pod(2)), Some(BTreeSet::new()));
+        assert!(split.get_unassigned_shards().is_empty());
+    }
+
+    #[test]
+    fn split_preserves_worker_placement() {
+        let table = routing_table(5, vec![(0, vec![0, 2, 4]), (1, vec![1, 3])]);
+        let split = split_shards(&table, 15).unwrap();
+
+        let pod_of = |table: &RoutingTable, worker_id: &WorkerId| {
+            let shard_id = ShardId::from_worker_id(worker_id, table.number_of_shards);
+            table
+                .shard_assignments
+                .iter()
+                .find(|(_, shard_ids)| shard_ids.contains(&shard_id))
+                .map(|(pod, _)| pod.clone())
+        };
+
+        for idx in 0..1000 {
+            let worker_id = WorkerId {
+                component_id: ComponentId::new_v4(),
+                worker_name: format!("worker-{idx}"),
+            };
+            assert_eq!(pod_of(&table, &worker_id), pod_of(&split, &worker_id));
+        }
+    }
+
+    #[test]
+    fn split_rejects_invalid_number_of_shards() {
+        let table = routing_table(4, vec![(0, vec![0, 1, 2, 3])]);
+        assert!(matches!(
+            split_shards(&table, 6),
+            Err(ShardManagerError::InvalidNumberOfShards {
+                current: 4,
+                requested: 6
+            })
+        ));
+        assert!(matches!(
+            split_shards(&table, 2),
+            Err(ShardManagerError::InvalidNumberOfShards {
+                current: 4,
+                requested: 2
+            })
+        ));
+    }
+}
@@ -14,17 +14,31 @@
 
 use crate::error::ShardManagerError;
 use crate::healthcheck::{get_unhealthy_pods, HealthCheck};
-use crate::model::{Pod, RoutingTable};
+use crate::leader_election::{FencedRoutingTablePersistence, FencedWorkerExecutorService, Lease};
+use crate::load_balancing::{
+    plan_load_rebalance, plan_load_rebalance_within_zones, LoadBalancingStatus,
+};
+use crate::metrics::record_load_balancing;
+use crate::model::{
+    AdminAction, Assignments, AuditLogEntry, DrainStatus, Pod, PodSelector, RoutingTable,
+    ShardLoad, ShardOverrides,
+};
 use crate::persistence::RoutingTablePersistence;
 use crate::rebalancing::Rebalance;
+use crate::resharding::split_shards;
+use crate::shard_manager_config::{LoadBalancingConfig, ZoneAwarenessConfig};
 use crate::worker_executor::{assign_shards, revoke_shards, WorkerExecutorService};
+use crate::zones::plan_zone_rebalance;
 use async_rwlock::RwLock;
+use golem_api_grpc::proto::golem;
+use golem_common::model::ShardId;
 use itertools::Itertools;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::{Mutex, Notify};
 use tokio::task::JoinHandle;
-use tracing::{debug, info, warn, Instrument};
+use tracing::{debug, error, info, warn, Instrument};
 
 #[derive(Clone)]
 pub struct ShardManagement {
@@ -33,18 +47,44 @@ pub struct ShardManagement {
     #[allow(dead_code)]
     worker_handle: Arc<WorkerHandle>, // Just kept here for abort on dropping
     updates: Arc<Mutex<ShardManagementChanges>>,
+    load_balancing_status: Arc<RwLock<LoadBalancingStatus>>,
+    /// The total consumed fuel in the previous shard load reports of the pods
+    reported_fuel: Arc<Mutex<BTreeMap<(Pod, ShardId), u64>>>,
+    overrides: Arc<RwLock<ShardOverrides>>,
+    persistence_service: Arc<dyn RoutingTablePersistence + Send + Sync>,
+    threshold: f64,
+    zone_awareness: ZoneAwarenessConfig,
 }
 
 impl ShardManagement {
-    /// Initializes the shard management with an initial routing table and optionally
-    /// a pending rebalance, both read from the persistence service.
+    /// Initializes the shard management with an initial routing table and the manual overrides,
+    /// both read from the persistence service.
+    ///
+    /// If the configured `number_of_shards` differs from the persisted routing table's, the
+    /// table is resharded online by splitting each shard (see `resharding::split_shards`).
+    ///
+    /// The routing table is only written and the shards are only assigned and revoked while
+    /// the `lease` is held.
+    #[allow(clippy::too_many_arguments)]
     pub async fn new(
         persistence_service: Arc<dyn RoutingTablePersistence + Send + Sync>,
         worker_executors: Arc<dyn WorkerExecutorService + Send + Sync>,
         health_check: Arc<dyn HealthCheck + Send + Sync>,
         threshold: f64,
+        number_of_shards: usize,
+        load_balancing: LoadBalancingConfig,
+        zone_awareness: ZoneAwarenessConfig,
+        drain_suspension_timeout: Duration,
+        lease: Lease,
     ) -> Result<Self, ShardManagerError> {
-        let routing_table = persistence_service.read().await.unwrap();
+        let persistence_service: Arc<dyn RoutingTablePersistence + Send + Sync> = Arc::new(
+            FencedRoutingTablePersistence::new(persistence_service, lease.clone()),
+        );
+        let worker_executors: Arc<dyn WorkerExecutorService + Send + Sync> =
+            Arc::new(FencedWorkerExecutorService::new(worker_executors, lease));
+
+        let mut routing_table = persistence_service.read().await?;
+        let overrides = persistence_service.read_overrides().await?;
 
         info!("Initial healthcheck started");
 
@@ -54,28 +94,55 @@ impl ShardManagement {
 
         info!("Initial healthcheck finished");
 
+        if routing_table.number_of_shards != number_of_shards {
+            match split_shards(&routing_table, number_of_shards) {
+                Ok(resharded_routing_table) => {
+                    info!(number_of_shards, "Resharding routing table");
+                    persistence_service.write(&resharded_routing_table).await?;
+                    routing_table = resharded_routing_table;
+                }
+                Err(err) => {
+                    error!(
+                        error = %err,
+                        "Resharding failed, keeping the persisted number of shards",
+                    );
+                }
+            }
+        }
+
         let change = Arc::new(Notify::new());
         // NOTE: We consider all healthy pods as new pods to trigger full assigment, given they might be lagging:
-        //       this can happen with interleaved shard-manager and worker restarts
+        //       this can happen with interleaved shard-manager and worker restarts, and after resharding
         let updates = Arc::new(Mutex::new(ShardManagementChanges::new(
             pods,
             unhealthy_pods,
         )));
         let routing_table = Arc::new(RwLock::new(routing_table));
+        let load_balancing_status = Arc::new(RwLock::new(LoadBalancingStatus::default()));
+        let overrides = Arc::new(RwLock::new(overrides));
 
         let worker_handle = {
             let change = change.clone();
             let updates = updates.clone();
             let routing_table = routing_table.clone();
+            let load_balancing_status = load_balancing_status.clone();
+            let overrides = overrides.clone();
+            let persistence_service = persistence_service.clone();
+            let zone_awareness = zone_awareness.clone();
 
             Arc::new(WorkerHandle::new(tokio::spawn(async move {
                 Self::worker(
                     routing_table,
                     change,
                     updates,
+                    overrides,
                     persistence_service,
                     worker_executors,
                     threshold,
+                    load_balancing,
+                    load_balancing_status,
+                    zone_awareness,
+                    drain_suspension_timeout,
                 )
                 .in_current_span()
                 .await
@@ -89,6 +156,12 @@ impl ShardManagement {
             change,
             worker_handle,
             updates,
+            load_balancing_status,
+            reported_fuel: Arc::new(Mutex::new(BTreeMap::new())),
+            overrides,
+            persistence_service,
+            threshold,
+            zone_awareness,
         })
     }
 
@@ -106,27 +179,233 @@ impl ShardManagement {
         self.change.notify_one();
     }
 
+    /// Marks the selected pod as draining, so its shards get reassigned to the other pods and it
+    /// gets no new shards until it gets undrained or registers again.
+    ///
+    /// Draining is idempotent, the returned status tells whether the pod still holds any shards.
+    pub async fn drain_pod(
+        &self,
+        selector: &PodSelector,
+    ) -> Result<DrainStatus, ShardManagerError> {
+        let (pod, shard_ids) = self.find_pod(selector).await?;
+        let status = DrainStatus::new(shard_ids.len());
+
+        let newly_draining = self.updates.lock().await.drain_pod(pod.clone());
+        if newly_draining {
+            info!(pod=%pod, remaining_shards = status.remaining_shards, "Draining pod");
+        }
+        // Notifying on every request retries the revocations that failed in earlier rounds
+        if newly_draining || !status.drained {
+            self.change.notify_one();
+        }
+
+        Ok(status)
+    }
+
+    /// Lets a previously drained pod receive shards again
+    pub async fn undrain_pod(&self, selector: &PodSelector) -> Result<(), ShardManagerError> {
+        let (pod, _) = self.find_pod(selector).await?;
+        if self.updates.lock().await.undrain_pod(&pod) {
+            info!(pod=%pod, "Undraining pod");
+            self.change.notify_one();
+        }
+        Ok(())
+    }
+
+    async fn find_pod(
+        &self,
+        selector: &PodSelector,
+    ) -> Result<(Pod, BTreeSet<ShardId>), ShardManagerError> {
+        self.routing_table
+            .read()
+            .await
+            .shard_assignments
+            .iter()
+            .find(|(pod, _)| selector.matches(pod))
+            .map(|(pod, shard_ids)| (pod.clone(), shard_ids.clone()))
+            .ok_or_else(|| ShardManagerError::UnknownPod(selector.to_string()))
+    }
+
+    /// Provides the latest shard loads reported by the pods, to be used by the
+    /// load-aware rebalancing
+    pub async fn update_shard_loads(
+        &self,
+        pod_shard_loads: BTreeMap<Pod, BTreeMap<ShardId, ShardLoad>>,
+    ) {
+        let shard_loads = {
+            let mut reported_fuel = self.reported_fuel.lock().await;
+            shard_loads_since_last_report(&mut reported_fuel, pod_shard_loads)
+        };
+        debug!(shard_count = shard_loads.len(), "Updating shard loads");
+        self.updates.lock().await.set_shard_loads(shard_loads);
+        self.change.notify_one();
+    }
+
     /// Gets the current snapshot of the routing table
     pub async fn current_snapshot(&self) -> RoutingTable {
         self.routing_table.read().await.clone()
     }
 
+    /// Gets the decisions of the last load-aware rebalancing round
+    pub async fn load_balancing_status(&self) -> LoadBalancingStatus {
+        self.load_balancing_status.read().await.clone()
+    }
+
+    /// Gets the manual overrides of the shard placement, and the audit log of the admin actions
+    pub async fn overrides(&self) -> ShardOverrides {
+        self.overrides.read().await.clone()
+    }
+
+    /// Calculates the rebalance plan the next round would execute based on the current routing
+    /// table. Load-aware moves are not included, as they depend on the next reported shard loads.
+    pub async fn pending_rebalance(&self) -> Rebalance {
+        let routing_table = self.routing_table.read().await.clone();
+        let draining_pods = self.updates.lock().await.draining_pods();
+        let overrides = self.overrides.read().await.clone();
+        let (rebalance, _, _) = Self::plan_rebalance(
+            &routing_table,
+            draining_pods,
+            &overrides,
+            self.threshold,
+            &self.zone_awareness,
+        );
+        rebalance
+    }
+
+    /// Applies an admin action to the overrides, persisting and recording it in the audit log,
+    /// then triggers a rebalance so the change takes effect right away
+    pub async fn apply_admin_action(
+        &self,
+        entry: AuditLogEntry,
+    ) -> Result<ShardOverrides, ShardManagerError> {
+        if let AdminAction::PinShard { shard_id, pod } = &entry.action {
+            let number_of_shards = self.routing_table.read().await.number_of_shards;
+            let value = golem::shardmanager::ShardId::from(*shard_id).value;
+            if value < 0 || value >= number_of_shards as i64 {
+                return Err(ShardManagerError::UnknownShard(*shard_id));
+            }
+            self.find_pod(pod).await?;
+        }
+
+        let overrides = {
+            let mut overrides = self.overrides.write().await;
+            let mut updated_overrides = overrides.clone();
+            info!(
+                action = %entry.action,
+                requested_by = entry.requested_by.as_deref().unwrap_or("unknown"),
+                reason = entry.reason.as_deref().unwrap_or(""),
+                "Admin action",
+            );
+            updated_overrides.apply(entry);
+            self.persistence_service
+                .write_overrides(&updated_overrides)
+                .await?;
+            *overrides = updated_overrides.clone();
+            updated_overrides
+        };
+
+        self.change.notify_one();
+        Ok(overrides)
+    }
+
+    /// Plans the count or zone based rebalance of the routing table, moving the shards off the
+    /// draining pods and applying the overrides.
+    ///
+    /// Returns the plan, the pods actually being drained and the routing table of the pods
+    /// available for new shards.
+    fn plan_rebalance(
+        routing_table: &RoutingTable,
+        mut draining_pods: HashSet<Pod>,
+        overrides: &ShardOverrides,
+        threshold: f64,
+        zone_awareness: &ZoneAwarenessConfig,
+    ) -> (Rebalance, HashSet<Pod>, RoutingTable) {
+        // The draining pods are left out of balancing, so their shards are considered
+        // unassigned and get assigned to the remaining pods
+        draining_pods.retain(|pod| routing_table.has_pod(pod));
+        if draining_pods.len() == routing_table.get_pod_count() && !draining_pods.is_empty() {
+            warn!("All pods are draining, keeping their shards until another pod joins");
+            draining_pods.clear();
+        }
+        let mut available_routing_table = routing_table.clone();
+        for pod in &draining_pods {
+            available_routing_table.remove_pod(pod);
+        }
+
+        let mut rebalance = if zone_awareness.enabled {
+            plan_zone_rebalance(&available_routing_table, threshold, zone_awareness)
+        } else {
+            Rebalance::from_routing_table(&available_routing_table, threshold)
+        };
+        for pod in &draining_pods {
+            let shard_ids = routing_table.get_shards(pod).unwrap_or_default();
+            rebalance.add_unassignments(pod, shard_ids);
+        }
+
+        if overrides.rebalancing_frozen {
+            rebalance.retain_unassigned_shards(routing_table);
+        }
+        Self::pin_shards(
+            &mut rebalance,
+            routing_table,
+            &available_routing_table,
+            overrides,
+        );
+
+        (rebalance, draining_pods, available_routing_table)
+    }
+
+    /// Places the pinned shards on their pods, if the pod is available
+    fn pin_shards(
+        rebalance: &mut Rebalance,
+        routing_table: &RoutingTable,
+        available_routing_table: &RoutingTable,
+        overrides: &ShardOverrides,
+    ) {
+        for (shard_id, selector) in &overrides.pinned_shards {
+            match available_routing_table
+                .get_pods()
+                .into_iter()
+                .find(|pod| selector.matches(pod))
+            {
+                Some(pod) => rebalance.pin_shard(routing_table, *shard_id, &pod),
+                None => debug!(
+                    shard_id = %shard_id,
+                    pod = %selector,
+                    "Pinned pod is not available, balancing the shard",
+                ),
+            }
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
     async fn worker(
         routing_table: Arc<RwLock<RoutingTable>>,
         change: Arc<Notify>,
         updates: Arc<Mutex<ShardManagementChanges>>,
+        overrides: Arc<RwLock<ShardOverrides>>,
         persistence_service: Arc<dyn RoutingTablePersistence + Send + Sync>,
         worker_executors: Arc<dyn WorkerExecutorService + Send + Sync>,
         threshold: f64,
+        load_balancing: LoadBalancingConfig,
+        load_balancing_status: Arc<RwLock<LoadBalancingStatus>>,
+        zone_awareness: ZoneAwarenessConfig,
+        drain_suspension_timeout: Duration,
     ) {
         loop {
             debug!("Shard management loop awaiting changes");
             change.notified().await;
 
-            let (new_pods, removed_pods) = updates.lock().await.reset();
+            let (new_pods, removed_pods, shard_loads, draining_pods) = {
+                let mut updates = updates.lock().await;
+                let (new_pods, removed_pods, shard_loads) = updates.reset();
+                (new_pods, removed_pods, shard_loads, updates.draining_pods())
+            };
+            let overrides = overrides.read().await.clone();
             debug!(
                 new_pods = new_pods.iter().join(", "),
                 removed_pods = removed_pods.iter().join(", "),
+                draining_pods = draining_pods.iter().join(", "),
                 "Shard management loop woken up",
             );
 
@@ -134,7 +413,7 @@ impl ShardManagement {
             //   - the rebalance plan is calculated,
             //   - new and removed pods are added to the routing table and got persisted,
             // but the rebalance plan is NOT applied yet. The lock is then release for apply.
-            let mut rebalance = {
+            let (full_assignments, mut rebalance, number_of_shards, draining_pods) = {
                 let mut current_routing_table = routing_table.write().await;
 
                 for pod in removed_pods {
@@ -154,47 +433,178 @@ impl ShardManagement {
                         info!(pod= %pod, "Pod added");
                     }
                 }
-                let mut rebalance =
-                    Rebalance::from_routing_table(&current_routing_table, threshold);
+                let (mut rebalance, draining_pods, available_routing_table) = Self::plan_rebalance(
+                    &current_routing_table,
+                    draining_pods,
+                    &overrides,
+                    threshold,
+                    &zone_awareness,
+                );
 
+                let mut full_assignments = Assignments::new();
                 for pod in send_full_assignment {
-                    let assignments = current_routing_table.get_shards(&pod).unwrap_or_default();
-                    rebalance.add_assignments(&pod, assignments);
+                    // Also sent without any shards, so the pod switches to the current number of shards
+                    let shard_ids = current_routing_table.get_shards(&pod).unwrap_or_default();
+                    full_assignments.assignments.insert(pod, shard_ids);
                 }
 
-                persistence_service
-                    .write(&current_routing_table)
+                // Balancing the load only when the shard counts are already balanced and
+                // there are no pending assignments, so each round starts from a stable state
+                if let Some(shard_loads) = shard_loads {
+                    if rebalance.is_empty()
+                        && full_assignments.is_empty()
+                        && !overrides.rebalancing_frozen
+                    {
+                        let plan = if zone_awareness.enabled {
+                            plan_load_rebalance_within_zones
+                        } else {
+                            plan_load_rebalance
+                        };
+                        let (load_rebalance, status) = plan(
+                            &available_routing_table,
+                            &shard_loads,
+                            threshold,
+                            &load_balancing,
+                        );
+                        if !status.moves.is_empty() {
+                            info!(
+                                imbalance = status.imbalance,
+                                expected_imbalance = status.expected_imbalance,
+                                moves = status.moves.len(),
+                                "Rebalancing shards by load",
+                            );
+                        }
+                        record_load_balancing(&status);
+                        *load_balancing_status.write().await = status;
+                        rebalance = load_rebalance;
+                        Self::pin_shards(
+                            &mut rebalance,
+                            &current_routing_table,
+                            &available_routing_table,
+                            &overrides,
+                        );
+                    }
+                }
+
+                if !Self::persist(&*persistence_service, &current_routing_table, "pod changes")
                     .await
-                    .expect("Failed to persist routing table after pod changes");
+                {
+                    break;
+                }
 
-                rebalance
+                (
+                    full_assignments,
+                    rebalance,
+                    current_routing_table.number_of_shards,
+                    draining_pods,
+                )
             };
 
             debug!(rebalance=%rebalance, "Applying rebalance plan");
-            Self::execute_rebalance(worker_executors.clone(), &mut rebalance).await;
+            let lagging_pods = Self::execute_rebalance(
+                worker_executors.clone(),
+                &full_assignments,
+                &mut rebalance,
+                number_of_shards,
+                &draining_pods,
+                drain_suspension_timeout,
+            )
+            .await;
+            if !lagging_pods.is_empty() {
+                // Retried in the next round, without triggering one right away
+                let mut updates = updates.lock().await;
+                for pod in lagging_pods {
+                    updates.retry_full_assignment(pod);
+                }
+            }
 
             routing_table.write().await.rebalance(rebalance);
-            persistence_service
-                .write(&routing_table.read().await.clone())
-                .await
-                .expect("Failed to persist routing table after rebalance");
+            let current_routing_table = routing_table.read().await.clone();
+            if !Self::persist(&*persistence_service, &current_routing_table, "rebalance").await {
+                break;
+            }
         }
     }
 
+    /// Persists the routing table, returning false if the shard management has to stop
+    /// because this replica is not the leader anymore
+    async fn persist(
+        persistence_service: &(dyn RoutingTablePersistence + Send + Sync),
+        routing_table: &RoutingTable,
+        after: &str,
+    ) -> bool {
+        match persistence_service.write(routing_table).await {
+            Ok(()) => true,
+            Err(ShardManagerError::NotLeader) => {
+                warn!("Lost leadership, stopping the shard management loop");
+                false
+            }
+            Err(err) => panic!("Failed to persist routing table after {after}: {err}"),
+        }
+    }
+
+    /// Sends the full assignments first, so that pods still using a previous number of shards
+    /// switch to the current one before they get any revocation or incremental assignment, then
+    /// revokes and assigns the shards of the rebalance plan.
+    ///
+    /// The pods failing to accept their full assignment are left out of the plan, and returned
+    /// so they can get it again in the next round.
     async fn execute_rebalance(
         worker_executors: Arc<dyn WorkerExecutorService + Send + Sync>,
+        full_assignments: &Assignments,
         rebalance: &mut Rebalance,
-    ) {
+        number_of_shards: usize,
+        draining_pods: &HashSet<Pod>,
+        drain_suspension_timeout: Duration,
+    ) -> HashSet<Pod> {
         info!("Beginning rebalance...");
 
+        if !full_assignments.is_empty() {
+            info!(
+                assignments = %full_assignments,
+                "Executing full shard assignments",
+            );
+        }
+        let failed_full_assignments = assign_shards(
+            worker_executors.clone(),
+            full_assignments,
+            number_of_shards,
+            true,
+        )
+        .await;
+        let lagging_pods: HashSet<Pod> = failed_full_assignments
+            .into_iter()
+            .map(|(pod, _)| pod)
+            .collect();
+        if !lagging_pods.is_empty() {
+            let lagging_shards = rebalance
+                .get_assignments()
+                .assignments
+                .iter()
+                .chain(rebalance.get_unassignments().unassignments.iter())
+                .filter(|(pod, _)| lagging_pods.contains(*pod))
+                .flat_map(|(_, shard_ids)| shard_ids.clone())
+                .collect();
+            rebalance.remove_shards(&lagging_shards);
+            warn!(
+                lagging_pods = lagging_pods.iter().join(", "),
+                "Some pods could not get their full assignment and have been removed from rebalance"
+            );
+        }
+
         if !rebalance.get_unassignments().is_empty() {
             info!(
                 unassignments = %rebalance.get_unassignments(),
                 "Executing shard unassignments",
             );
         }
-        let failed_unassignments =
-            revoke_shards(worker_executors.clone(), rebalance.get_unassignments()).await;
+        let failed_unassignments = revoke_shards(
+            worker_executors.clone(),
+            rebalance.get_unassignments(),
+            draining_pods,
+            drain_suspension_timeout,
+        )
+        .await;
         let failed_shards = failed_unassignments
             .iter()
             .flat_map(|(_, shard_ids)| shard_ids.clone())
@@ -213,14 +623,53 @@ impl ShardManagement {
                 "Executing shard assignments",
             );
         }
-        assign_shards(worker_executors.clone(), rebalance.get_assignments()).await;
+        assign_shards(
+            worker_executors.clone(),
+            rebalance.get_assignments(),
+            number_of_shards,
+            false,
+        )
+        .await;
+
+        lagging_pods
+    }
+}
+
+/// Sums up the shard loads reported by the pods.
+///
+/// The pods report the total fuel consumed by the active workers of a shard, so it is replaced by
+/// the difference to the pod's previous report of the same shard. There is no difference for the
+/// first report of a shard on a pod, and a smaller total means some of its workers got unloaded,
+/// in which case the new total is used as the difference.
+fn shard_loads_since_last_report(
+    reported_fuel: &mut BTreeMap<(Pod, ShardId), u64>,
+    pod_shard_loads: BTreeMap<Pod, BTreeMap<ShardId, ShardLoad>>,
+) -> BTreeMap<ShardId, ShardLoad> {
+    let mut current_fuel = BTreeMap::new();
+    let mut shard_loads: BTreeMap<ShardId, ShardLoad> = BTreeMap::new();
+    for (pod, pod_shard_loads) in pod_shard_loads {
+        for (shard_id, mut shard_load) in pod_shard_loads {
+            let total_fuel = shard_load.consumed_fuel;
+            shard_load.consumed_fuel = match reported_fuel.get(&(pod.clone(), shard_id)) {
+                Some(previous_fuel) if *previous_fuel <= total_fuel => total_fuel - previous_fuel,
+                Some(_) => total_fuel,
+                None => 0,
+            };
+            current_fuel.insert((pod.clone(), shard_id), total_fuel);
+            shard_loads.entry(shard_id).or_default().add(&shard_load);
+        }
     }
+    *reported_fuel = current_fuel;
+    shard_loads
 }
 
 #[derive(Debug)]
 struct ShardManagementChanges {
     new_pods: HashSet<Pod>,
     removed_pods: HashSet<Pod>,
+    shard_loads: Option<BTreeMap<ShardId, ShardLoad>>,
+    /// Not reset, pods keep draining until they get undrained, removed or register again
+    draining_pods: HashSet<Pod>,
 }
 
 impl ShardManagementChanges {
@@ -228,25 +677,59 @@ impl ShardManagementChanges {
         ShardManagementChanges {
             new_pods,
             removed_pods,
+            shard_loads: None,
+            draining_pods: HashSet::new(),
         }
     }
 
     pub fn add_new_pod(&mut self, pod: Pod) {
+        // A draining pod registering again has been restarted, and can receive shards
+        self.draining_pods.remove(&pod);
         self.removed_pods.remove(&pod);
         self.new_pods.insert(pod);
     }
 
     pub fn remove_pod(&mut self, pod: Pod) {
+        self.draining_pods.remove(&pod);
         self.new_pods.remove(&pod);
         self.removed_pods.insert(pod);
     }
 
-    pub fn reset(&mut self) -> (HashSet<Pod>, HashSet<Pod>) {
+    /// Sends the full assignment to the pod again in the next round, unless it got removed
+    pub fn retry_full_assignment(&mut self, pod: Pod) {
+        if !self.removed_pods.contains(&pod) {
+            self.new_pods.insert(pod);
+        }
+    }
+
+    pub fn drain_pod(&mut self, pod: Pod) -> bool {
+        self.draining_pods.insert(pod)
+    }
+
+    pub fn undrain_pod(&mut self, pod: &Pod) -> bool {
+        self.draining_pods.remove(pod)
+    }
+
+    pub fn draining_pods(&self) -> HashSet<Pod> {
+        self.draining_pods.clone()
+    }
+
+    pub fn set_shard_loads(&mut self, shard_loads: BTreeMap<ShardId, ShardLoad>) {
+        self.shard_loads = Some(shard_loads);
+    }
+
+    pub fn reset(
+        &mut self,
+    ) -> (
+        HashSet<Pod>,
+        HashSet<Pod>,
+        Option<BTreeMap<ShardId, ShardLoad>>,
+    ) {
         let new = self.new_pods.clone();
         let removed = self.removed_pods.clone();
         self.new_pods.clear();
         self.removed_pods.clear();
-        (new, removed)
+        (new, removed, self.shard_loads.take())
     }
 }
 
@@ -263,3 +746,357 @@ impl Drop for WorkerHandle {
         self.0.abort();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use async_trait::async_trait;
+    use golem_common::model::ShardId;
+    use std::collections::{BTreeMap, BTreeSet, HashSet};
+    use std::future::Future;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::sync::Mutex;
+
+    use crate::error::{HealthCheckError, ShardManagerError};
+    use crate::healthcheck::HealthCheck;
+    use crate::leader_election::Lease;
+    use crate::model::{Pod, RoutingTable, ShardLoad, ShardOverrides};
+    use crate::persistence::RoutingTablePersistence;
+    use crate::shard_management::{shard_loads_since_last_report, ShardManagement};
+    use crate::shard_manager_config::{LoadBalancingConfig, ZoneAwarenessConfig};
+    use crate::worker_executor::WorkerExecutorService;
+
+    fn pod(idx: usize) -> Pod {
+        Pod::new(format!("pod{idx}"), (9000 + idx) as u16)
+    }
+
+    fn shard_ids(ids: Vec<i64>) -> BTreeSet<ShardId> {
+        ids.into_iter().map(ShardId::new).collect()
+    }
+
+    fn routing_table(number_of_shards: usize, assignments: Vec<(usize, Vec<i64>)>) -> RoutingTable {
+        let mut routing_table = RoutingTable::new(number_of_shards);
+        for (pod_idx, shards) in assignments {
+            routing_table
+                .shard_assignments
+                .insert(pod(pod_idx), shard_ids(shards));
+        }
+        routing_table
+    }
+
+    struct InMemoryPersistence {
+        routing_table: Mutex<RoutingTable>,
+    }
+
+    #[async_trait]
+    impl RoutingTablePersistence for InMemoryPersistence {
+        async fn write(&self, routing_table: &RoutingTable) -> Result<(), ShardManagerError> {
+            *self.routing_table.lock().await = routing_table.clone();
+            Ok(())
+        }
+
+        async fn read(&self) -> Result<RoutingTable, ShardManagerError> {
+            Ok(self.routing_table.lock().await.clone())
+        }
+
+        async fn write_overrides(
+            &self,
+            _overrides: &ShardOverrides,
+        ) -> Result<(), ShardManagerError> {
+            Ok(())
+        }
+
+        async fn read_overrides(&self) -> Result<ShardOverrides, ShardManagerError> {
+            Ok(ShardOverrides::default())
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Call {
+        Assign {
+            pod: Pod,
+            shard_ids: BTreeSet<ShardId>,
+            number_of_shards: usize,
+            full_assignment: bool,
+        },
+        Revoke {
+            pod: Pod,
+            shard_ids: BTreeSet<ShardId>,
+        },
+    }
+
+    impl Call {
+        fn pod(&self) -> &Pod {
+            match self {
+                Call::Assign { pod, .. } => pod,
+                Call::Revoke { pod, .. } => pod,
+            }
+        }
+    }
+
+    // Records the successful calls, failing every call to the lagging pods
+    #[derive(Default)]
+    struct RecordingWorkerExecutors {
+        calls: Mutex<Vec<Call>>,
+        lagging_pods: Mutex<HashSet<Pod>>,
+    }
+
+    impl RecordingWorkerExecutors {
+        async fn record(&self, call: Call) -> Result<(), ShardManagerError> {
+            if self.lagging_pods.lock().await.contains(call.pod()) {
+                Err(ShardManagerError::Timeout)
+            } else {
+                self.calls.lock().await.push(call);
+                Ok(())
+            }
+        }
+
+        async fn calls(&self) -> Vec<Call> {
+            self.calls.lock().await.clone()
+        }
+    }
+
+    #[async_trait]
+    impl WorkerExecutorService for RecordingWorkerExecutors {
+        async fn assign_shards(
+            &self,
+            pod: &Pod,
+            shard_ids: &BTreeSet<ShardId>,
+            number_of_shards: usize,
+            full_assignment: bool,
+        ) -> Result<(), ShardManagerError> {
+            self.record(Call::Assign {
+                pod: pod.clone(),
+                shard_ids: shard_ids.clone(),
+                number_of_shards,
+                full_assignment,
+            })
+            .await
+        }
+
+        async fn get_shard_loads(
+            &self,
+            _pod: &Pod,
+        ) -> Result<BTreeMap<ShardId, ShardLoad>, ShardManagerError> {
+            Ok(BTreeMap::new())
+        }
+
+        async fn health_check(&self, _pod: &Pod) -> Result<(), HealthCheckError> {
+            Ok(())
+        }
+
+        async fn revoke_shards(
+            &self,
+            pod: &Pod,
+            shard_ids: &BTreeSet<ShardId>,
+            _suspension_timeout: Duration,
+        ) -> Result<(), ShardManagerError> {
+            self.record(Call::Revoke {
+                pod: pod.clone(),
+                shard_ids: shard_ids.clone(),
+            })
+            .await
+        }
+    }
+
+    struct AlwaysHealthy;
+
+    #[async_trait]
+    impl HealthCheck for AlwaysHealthy {
+        async fn health_check(&self, _pod: &Pod) -> bool {
+            true
+        }
+    }
+
+    async fn shard_management(
+        routing_table: RoutingTable,
+        worker_executors: Arc<RecordingWorkerExecutors>,
+        number_of_shards: usize,
+    ) -> ShardManagement {
+        ShardManagement::new(
+            Arc::new(InMemoryPersistence {
+                routing_table: Mutex::new(routing_table),
+            }),
+            worker_executors,
+            Arc::new(AlwaysHealthy),
+            0.1,
+            number_of_shards,
+            LoadBalancingConfig::default(),
+            ZoneAwarenessConfig::default(),
+            Duration::ZERO,
+            Lease::held(),
+        )
+        .await
+        .unwrap()
+    }
+
+    async fn wait_until<F: Future<Output = bool>>(condition: impl Fn() -> F) {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while !condition().await {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("Condition not reached in time")
+    }
+
+    fn full_assignment(pod_idx: usize, shards: Vec<i64>, number_of_shards: usize) -> Call {
+        Call::Assign {
+            pod: pod(pod_idx),
+            shard_ids: shard_ids(shards),
+            number_of_shards,
+            full_assignment: true,
+        }
+    }
+
+    #[test]
+    async fn resharding_sends_full_assignments_before_revocations() {
+        let worker_executors = Arc::new(RecordingWorkerExecutors::default());
+        let shard_management = shard_management(
+            routing_table(4, vec![(0, vec![0, 1, 2, 3]), (1, vec![])]),
+            worker_executors.clone(),
+            8,
+        )
+        .await;
+
+        wait_until(|| async {
+            let snapshot = shard_management.current_snapshot().await;
+            !snapshot.get_shards(&pod(1)).unwrap_or_default().is_empty()
+        })
+        .await;
+
+        let calls = worker_executors.calls().await;
+        assert!(calls.contains(&full_assignment(0, (0..8).collect(), 8)));
+        assert!(calls.contains(&full_assignment(1, vec![], 8)));
+
+        let first_non_full = calls
+            .iter()
+            .position(|call| {
+                !matches!(
+                    call,
+                    Call::Assign {
+                        full_assignment: true,
+                        ..
+                    }
+                )
+            })
+            .unwrap();
+        assert_eq!(first_non_full, 2);
+        assert!(matches!(calls[first_non_full], Call::Revoke { .. }));
+        for call in &calls[first_non_full..] {
+            if let Call::Assign {
+                number_of_shards,
+                full_assignment,
+                ..
+            } = call
+            {
+                assert!(!full_assignment);
+                assert_eq!(*number_of_shards, 8);
+            }
+        }
+    }
+
+    #[test]
+    async fn lagging_pod_keeps_its_shards_until_it_gets_the_full_assignment() {
+        let worker_executors = Arc::new(RecordingWorkerExecutors::default());
+        worker_executors.lagging_pods.lock().await.insert(pod(1));
+
+        let shard_management = shard_management(
+            routing_table(4, vec![(0, vec![0, 1]), (1, vec![2, 3])]),
+            worker_executors.clone(),
+            8,
+        )
+        .await;
+        shard_management.register_pod(pod(2)).await;
+
+        wait_until(|| async {
+            let snapshot = shard_management.current_snapshot().await;
+            !snapshot.get_shards(&pod(2)).unwrap_or_default().is_empty()
+        })
+        .await;
+
+        // The lagging pod still uses 4 shards, so it must not get any revocation or
+        // incremental assignment in terms of 8 shards, and its shards are not moved
+        let snapshot = shard_management.current_snapshot().await;
+        assert_eq!(
+            snapshot.get_shards(&pod(1)),
+            Some(shard_ids(vec![2, 3, 6, 7]))
+        );
+        let calls = worker_executors.calls().await;
+        assert!(calls.contains(&full_assignment(0, vec![0, 1, 4, 5], 8)));
+        assert!(!calls.iter().any(|call| call.pod() == &pod(1)));
+        let pod1_shards = snapshot.get_shards(&pod(1)).unwrap();
+        assert!(!calls.iter().any(|call| match call {
+            Call::Assign { shard_ids, .. } => !shard_ids.is_disjoint(&pod1_shards),
+            Call::Revoke { .. } => false,
+        }));
+
+        // Once reachable again, the lagging pod gets its full assignment before it is rebalanced
+        worker_executors.lagging_pods.lock().await.clear();
+        shard_management.update_shard_loads(BTreeMap::new()).await;
+
+        wait_until(|| async {
+            let snapshot = shard_management.current_snapshot().await;
+            snapshot.get_shards(&pod(1)).unwrap_or_default().len() < 4
+        })
+        .await;
+
+        let calls = worker_executors.calls().await;
+        let pod1_calls = calls
+            .iter()
+            .filter(|call| call.pod() == &pod(1))
+            .collect::<Vec<_>>();
+        assert_eq!(pod1_calls[0], &full_assignment(1, vec![2, 3, 6, 7], 8));
+        assert!(pod1_calls[1..]
+            .iter()
+            .any(|call| matches!(call, Call::Revoke { .. })));
+    }
+
+    #[test]
+    fn consumed_fuel_is_the_difference_of_the_reported_totals() {
+        let load = |active_workers: u64, consumed_fuel: u64| ShardLoad {
+            active_workers,
+            memory: 0,
+            consumed_fuel,
+        };
+        let report = |loads: Vec<(usize, i64, ShardLoad)>| {
+            let mut pod_shard_loads: BTreeMap<Pod, BTreeMap<ShardId, ShardLoad>> = BTreeMap::new();
+            for (pod_idx, shard_id, shard_load) in loads {
+                pod_shard_loads
+                    .entry(pod(pod_idx))
+                    .or_default()
+                    .insert(ShardId::new(shard_id), shard_load);
+            }
+            pod_shard_loads
+        };
+        let mut reported_fuel = BTreeMap::new();
+
+        // Nothing to compare the first reports to
+        let shard_loads = shard_loads_since_last_report(
+            &mut reported_fuel,
+            report(vec![(0, 0, load(2, 100)), (1, 1, load(1, 50))]),
+        );
+        assert_eq!(shard_loads[&ShardId::new(0)], load(2, 0));
+        assert_eq!(shard_loads[&ShardId::new(1)], load(1, 0));
+
+        // Shard 1 lost a worker, shard 2 moved from pod 0 to pod 1
+        let shard_loads = shard_loads_since_last_report(
+            &mut reported_fuel,
+            report(vec![
+                (0, 0, load(2, 130)),
+                (1, 1, load(1, 20)),
+                (1, 2, load(1, 10)),
+            ]),
+        );
+        assert_eq!(shard_loads[&ShardId::new(0)], load(2, 30));
+        assert_eq!(shard_loads[&ShardId::new(1)], load(1, 20));
+        assert_eq!(shard_loads[&ShardId::new(2)], load(1, 0));
+
+        // Only the fuel consumed since the previous report is counted
+        let shard_loads =
+            shard_loads_since_last_report(&mut reported_fuel, report(vec![(0, 0, load(2, 200))]));
+        assert_eq!(shard_loads[&ShardId::new(0)], load(2, 70));
+    }
+}
@@ -33,6 +33,9 @@ pub struct ShardManagerConfig {
     pub grpc_port: u16,
     pub number_of_shards: usize,
     pub rebalance_threshold: f64,
+    pub leader_election: LeaderElectionConfig,
+    pub load_balancing: LoadBalancingConfig,
+    pub zone_awareness: ZoneAwarenessConfig,
 }
 
 impl SafeDisplay for ShardManagerConfig {
@@ -66,6 +69,24 @@ impl SafeDisplay for ShardManagerConfig {
             "rebalance threshold: {}",
             self.rebalance_threshold
         );
+        let _ = writeln!(&mut result, "leader election:");
+        let _ = writeln!(
+            &mut result,
+            "{}",
+            self.leader_election.to_safe_string_indented()
+        );
+        let _ = writeln!(&mut result, "load balancing:");
+        let _ = writeln!(
+            &mut result,
+            "{}",
+            self.load_balancing.to_safe_string_indented()
+        );
+        let _ = writeln!(&mut result, "zone awareness:");
+        let _ = writeln!(
+            &mut result,
+            "{}",
+            self.zone_awareness.to_safe_string_indented()
+        );
         result
     }
 }
@@ -81,6 +102,9 @@ impl Default for ShardManagerConfig {
             grpc_port: 9002,
             number_of_shards: 1024,
             rebalance_threshold: 0.1,
+            leader_election: LeaderElectionConfig::default(),
+            load_balancing: LoadBalancingConfig::default(),
+            zone_awareness: ZoneAwarenessConfig::default(),
         }
     }
 }
@@ -107,6 +131,10 @@ impl HasConfigExamples<ShardManagerConfig> for ShardManagerConfig {
 pub struct WorkerExecutorServiceConfig {
     #[serde(with = "humantime_serde")]
     pub assign_shards_timeout: Duration,
+    /// How long a draining pod waits for running invocations to reach a suspension point
+    /// before interrupting them
+    #[serde(with = "humantime_serde")]
+    pub drain_suspension_timeout: Duration,
     #[serde(with = "humantime_serde")]
     pub health_check_timeout: Duration,
     #[serde(with = "humantime_serde")]
@@ -124,6 +152,11 @@ impl SafeDisplay for WorkerExecutorServiceConfig {
             "assign shards timeout: {:?}",
             self.assign_shards_timeout
         );
+        let _ = writeln!(
+            &mut result,
+            "drain suspension timeout: {:?}",
+            self.drain_suspension_timeout
+        );
         let _ = writeln!(
             &mut result,
             "health check timeout: {:?}",
@@ -145,6 +178,7 @@ impl Default for WorkerExecutorServiceConfig {
     fn default() -> Self {
         Self {
             assign_shards_timeout: Duration::from_secs(5),
+            drain_suspension_timeout: Duration::from_secs(30),
             health_check_timeout: Duration::from_secs(2),
             revoke_shards_timeout: Duration::from_secs(5),
             retries: RetryConfig::max_attempts_5(),
@@ -153,6 +187,134 @@ impl Default for WorkerExecutorServiceConfig {
     }
 }
 
+/// Running multiple replicas of the shard manager requires leader election, using the
+/// configured persistence backend. The leader has to renew its lease more often than the
+/// lease expires, otherwise another replica takes over.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LeaderElectionConfig {
+    pub enabled: bool,
+    #[serde(with = "humantime_serde")]
+    pub lease_duration: Duration,
+    #[serde(with = "humantime_serde")]
+    pub renew_interval: Duration,
+}
+
+impl SafeDisplay for LeaderElectionConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        let _ = writeln!(&mut result, "enabled: {}", self.enabled);
+        let _ = writeln!(&mut result, "lease duration: {:?}", self.lease_duration);
+        let _ = writeln!(&mut result, "renew interval: {:?}", self.renew_interval);
+        result
+    }
+}
+
+impl Default for LeaderElectionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            lease_duration: Duration::from_secs(15),
+            renew_interval: Duration::from_secs(5),
+        }
+    }
+}
+
+/// Load-aware rebalancing moves shards between pods based on the shard loads reported by the
+/// worker executors on each health check. The load of a shard is the weighted sum of its share
+/// of all the active workers, memory and consumed fuel.
+///
+/// Moves are only made while the difference between the most and least loaded pods exceeds
+/// `imbalance_threshold` (relative to the average pod load), and never take a pod's shard count
+/// out of the range allowed by `rebalance_threshold`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LoadBalancingConfig {
+    pub enabled: bool,
+    pub imbalance_threshold: f64,
+    pub max_moves_per_round: usize,
+    pub active_workers_weight: f64,
+    pub memory_weight: f64,
+    pub consumed_fuel_weight: f64,
+}
+
+impl SafeDisplay for LoadBalancingConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        let _ = writeln!(&mut result, "enabled: {}", self.enabled);
+        let _ = writeln!(
+            &mut result,
+            "imbalance threshold: {}",
+            self.imbalance_threshold
+        );
+        let _ = writeln!(
+            &mut result,
+            "max moves per round: {}",
+            self.max_moves_per_round
+        );
+        let _ = writeln!(
+            &mut result,
+            "active workers weight: {}",
+            self.active_workers_weight
+        );
+        let _ = writeln!(&mut result, "memory weight: {}", self.memory_weight);
+        let _ = writeln!(
+            &mut result,
+            "consumed fuel weight: {}",
+            self.consumed_fuel_weight
+        );
+        result
+    }
+}
+
+impl Default for LoadBalancingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            imbalance_threshold: 0.2,
+            max_moves_per_round: 4,
+            active_workers_weight: 1.0,
+            memory_weight: 1.0,
+            consumed_fuel_weight: 1.0,
+        }
+    }
+}
+
+/// Zone-aware placement spreads the shards evenly across the availability zones of the pods, so
+/// that losing a zone moves a predictable share of the shards. Within a zone the shards are
+/// balanced between its pods as usual, and load-aware rebalancing only moves shards inside zones.
+///
+/// Pods report their zone on registration, or it gets discovered from the node labels when using
+/// the Kubernetes health check. Pods without a known zone are grouped into a zone of their own.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ZoneAwarenessConfig {
+    pub enabled: bool,
+    pub affinity: Vec<ZoneAffinityConfig>,
+}
+
+impl SafeDisplay for ZoneAwarenessConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        let _ = writeln!(&mut result, "enabled: {}", self.enabled);
+        let _ = writeln!(&mut result, "affinity:");
+        for rule in &self.affinity {
+            let _ = writeln!(
+                &mut result,
+                "  shards {}..={}: {}",
+                rule.first_shard, rule.last_shard, rule.zone
+            );
+        }
+        result
+    }
+}
+
+/// Places the shards from `first_shard` to `last_shard` (inclusive) in the given zone, as long
+/// as the zone has any pods
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ZoneAffinityConfig {
+    pub zone: String,
+    pub first_shard: i64,
+    pub last_shard: i64,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct HealthCheckConfig {
     #[serde(with = "humantime_serde")]
@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use crate::error::{HealthCheckError, ShardManagerError};
-use crate::model::{pod_shard_assignments_to_string, Assignments, Pod, Unassignments};
+use crate::model::{pod_shard_assignments_to_string, Assignments, Pod, ShardLoad, Unassignments};
 use crate::shard_manager_config::WorkerExecutorServiceConfig;
 use async_trait::async_trait;
 use golem_api_grpc::proto::golem;
@@ -22,8 +22,9 @@ use golem_common::client::{GrpcClientConfig, MultiTargetGrpcClient};
 use golem_common::model::ShardId;
 use golem_common::retries::with_retriable_errors;
 use golem_service_base::error::worker_executor::WorkerExecutorError;
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::time::error::Elapsed;
 use tokio::time::timeout;
 use tonic::codec::CompressionEncoding;
@@ -37,33 +38,58 @@ use tracing::info;
 
 #[async_trait]
 pub trait WorkerExecutorService {
+    /// Assigns shards to a pod. A `full_assignment` replaces the pod's current assignment,
+    /// possibly switching it to `number_of_shards`, otherwise the shards are added to it.
     async fn assign_shards(
         &self,
         pod: &Pod,
         shard_ids: &BTreeSet<ShardId>,
+        number_of_shards: usize,
+        full_assignment: bool,
     ) -> Result<(), ShardManagerError>;
 
+    async fn get_shard_loads(
+        &self,
+        pod: &Pod,
+    ) -> Result<BTreeMap<ShardId, ShardLoad>, ShardManagerError>;
+
     async fn health_check(&self, pod: &Pod) -> Result<(), HealthCheckError>;
 
+    /// Revokes shards from a pod. With a non-zero `suspension_timeout` the pod waits for the
+    /// running invocations of the affected workers to reach a suspension point before
+    /// interrupting them.
     async fn revoke_shards(
         &self,
         pod: &Pod,
         shard_ids: &BTreeSet<ShardId>,
+        suspension_timeout: Duration,
     ) -> Result<(), ShardManagerError>;
 }
 
-/// Sends revoke requests to all worker executors based on an `Unassignments` plan
+/// Sends revoke requests to all worker executors based on an `Unassignments` plan.
+///
+/// Shards revoked from draining pods are revoked gracefully, using `suspension_timeout`.
 pub async fn revoke_shards(
     worker_executors: Arc<dyn WorkerExecutorService + Send + Sync>,
     unassignments: &Unassignments,
+    draining_pods: &HashSet<Pod>,
+    suspension_timeout: Duration,
 ) -> Vec<(Pod, BTreeSet<ShardId>)> {
     let futures: Vec<_> = unassignments
         .unassignments
         .iter()
         .map(|(pod, shard_ids)| {
             let worker_executors = worker_executors.clone();
+            let suspension_timeout = if draining_pods.contains(pod) {
+                suspension_timeout
+            } else {
+                Duration::ZERO
+            };
             Box::pin(async move {
-                match worker_executors.revoke_shards(pod, shard_ids).await {
+                match worker_executors
+                    .revoke_shards(pod, shard_ids, suspension_timeout)
+                    .await
+                {
                     Ok(_) => None,
                     Err(_) => Some((pod.clone(), shard_ids.clone())),
                 }
@@ -77,10 +103,14 @@ pub async fn revoke_shards(
         .collect()
 }
 
-/// Sends assign requests to all worker executors based on an `Assignments` plan
+/// Sends assign requests to all worker executors based on an `Assignments` plan.
+///
+/// With `full_assignment` the plan holds every shard of the pods, replacing their current ones.
 pub async fn assign_shards(
     worker_executors: Arc<dyn WorkerExecutorService + Send + Sync>,
     assignments: &Assignments,
+    number_of_shards: usize,
+    full_assignment: bool,
 ) -> Vec<(Pod, BTreeSet<ShardId>)> {
     let futures: Vec<_> = assignments
         .assignments
@@ -88,7 +118,10 @@ pub async fn assign_shards(
         .map(|(pod, shard_ids)| {
             let worker_executors = worker_executors.clone();
             Box::pin(async move {
-                match worker_executors.assign_shards(pod, shard_ids).await {
+                match worker_executors
+                    .assign_shards(pod, shard_ids, number_of_shards, full_assignment)
+                    .await
+                {
                     Ok(_) => None,
                     Err(_) => Some((pod.clone(), shard_ids.clone())),
                 }
@@ -113,10 +146,12 @@ impl WorkerExecutorService for WorkerExecutorServiceDefault {
         &self,
         pod: &Pod,
         shard_ids: &BTreeSet<ShardId>,
+        number_of_shards: usize,
+        full_assignment: bool,
     ) -> Result<(), ShardManagerError> {
         info!(
             assigned_shards = pod_shard_assignments_to_string(pod, shard_ids.iter()),
-            "Assigning shards",
+            number_of_shards, full_assignment, "Assigning shards",
         );
 
         with_retriable_errors(
@@ -125,7 +160,29 @@ impl WorkerExecutorService for WorkerExecutorServiceDefault {
             Some(format!("{pod}")),
             &self.config.retries,
             &(pod, shard_ids),
-            |(pod, shard_ids)| Box::pin(self.assign_shards_internal(pod, shard_ids)),
+            |(pod, shard_ids)| {
+                Box::pin(self.assign_shards_internal(
+                    pod,
+                    shard_ids,
+                    number_of_shards,
+                    full_assignment,
+                ))
+            },
+        )
+        .await
+    }
+
+    async fn get_shard_loads(
+        &self,
+        pod: &Pod,
+    ) -> Result<BTreeMap<ShardId, ShardLoad>, ShardManagerError> {
+        with_retriable_errors(
+            "worker_executor",
+            "get_shard_loads",
+            Some(format!("{pod}")),
+            &self.config.retries,
+            pod,
+            |pod| Box::pin(self.get_shard_loads_internal(pod)),
         )
         .await
     }
@@ -160,9 +217,11 @@ impl WorkerExecutorService for WorkerExecutorServiceDefault {
         &self,
         pod: &Pod,
         shard_ids: &BTreeSet<ShardId>,
+        suspension_timeout: Duration,
     ) -> Result<(), ShardManagerError> {
         info!(
             revoked_shards = pod_shard_assignments_to_string(pod, shard_ids.iter()),
+            suspension_timeout = ?suspension_timeout,
             "Revoking shards",
         );
 
@@ -172,7 +231,9 @@ impl WorkerExecutorService for WorkerExecutorServiceDefault {
             Some(format!("{pod}")),
             &self.config.retries,
             &(pod, shard_ids),
-            |(pod, shard_ids)| Box::pin(self.revoke_shards_internal(pod, shard_ids)),
+            |(pod, shard_ids)| {
+                Box::pin(self.revoke_shards_internal(pod, shard_ids, suspension_timeout))
+            },
         )
         .await
     }
@@ -199,6 +260,8 @@ impl WorkerExecutorServiceDefault {
         &self,
         pod: &Pod,
         shard_ids: &BTreeSet<ShardId>,
+        number_of_shards: usize,
+        full_assignment: bool,
     ) -> Result<(), ShardManagerError> {
         let assign_shards_request = golem::workerexecutor::v1::AssignShardsRequest {
             shard_ids: shard_ids
@@ -206,6 +269,8 @@ impl WorkerExecutorServiceDefault {
                 .into_iter()
                 .map(|shard_id| shard_id.into())
                 .collect(),
+            number_of_shards: Some(number_of_shards as u32),
+            full_assignment,
         };
 
         let assign_shards_response = timeout(
@@ -237,10 +302,56 @@ impl WorkerExecutorServiceDefault {
         }
     }
 
+    async fn get_shard_loads_internal(
+        &self,
+        pod: &Pod,
+    ) -> Result<BTreeMap<ShardId, ShardLoad>, ShardManagerError> {
+        // Shard loads are collected together with the health checks, sharing their timeout
+        let get_shard_loads_response = timeout(
+            self.config.health_check_timeout,
+            self.client
+                .call("get_shard_loads", pod.uri(), move |client| {
+                    Box::pin(
+                        client.get_shard_loads(golem::workerexecutor::v1::GetShardLoadsRequest {}),
+                    )
+                }),
+        )
+        .await
+        .map_err(|_: Elapsed| ShardManagerError::Timeout)?
+        .map_err(ShardManagerError::GrpcError)?;
+
+        match get_shard_loads_response.into_inner() {
+            golem::workerexecutor::v1::GetShardLoadsResponse {
+                result:
+                    Some(golem::workerexecutor::v1::get_shard_loads_response::Result::Success(success)),
+            } => Ok(success
+                .shard_loads
+                .into_iter()
+                .filter_map(|shard_load| {
+                    shard_load
+                        .shard_id
+                        .map(|shard_id| (shard_id.into(), shard_load.into()))
+                })
+                .collect()),
+            golem::workerexecutor::v1::GetShardLoadsResponse {
+                result:
+                    Some(golem::workerexecutor::v1::get_shard_loads_response::Result::Failure(failure)),
+            } => Err(ShardManagerError::WorkerExecutionError(
+                failure
+                    .try_into()
+                    .unwrap_or_else(WorkerExecutorError::unknown),
+            )),
+            golem::workerexecutor::v1::GetShardLoadsResponse { result: None } => {
+                Err(ShardManagerError::NoResult)
+            }
+        }
+    }
+
     async fn revoke_shards_internal(
         &self,
         pod: &Pod,
         shard_ids: &BTreeSet<ShardId>,
+        suspension_timeout: Duration,
     ) -> Result<(), ShardManagerError> {
         let revoke_shards_request = golem::workerexecutor::v1::RevokeShardsRequest {
             shard_ids: shard_ids
@@ -248,10 +359,12 @@ impl WorkerExecutorServiceDefault {
                 .into_iter()
                 .map(|shard_id| shard_id.into())
                 .collect(),
+            suspension_timeout_millis: suspension_timeout.as_millis() as u64,
         };
 
+        // The pod may spend the whole suspension timeout waiting before it revokes the shards
         let revoke_shards_response = timeout(
-            self.config.revoke_shards_timeout,
+            self.config.revoke_shards_timeout + suspension_timeout,
             self.client.call("revoke_shards", pod.uri(), move |client| {
                 let revoke_shards_request = revoke_shards_request.clone();
                 Box::pin(client.revoke_shards(revoke_shards_request))
@@ -0,0 +1,346 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::model::{Assignments, Pod, RoutingTable, Unassignments};
+use crate::rebalancing::Rebalance;
+use crate::shard_manager_config::ZoneAwarenessConfig;
+use golem_api_grpc::proto::golem;
+use golem_common::model::ShardId;
+use std::collections::{BTreeMap, BTreeSet};
+use tracing::{trace, warn};
+
+/// The zone of a pod, pods without a known zone are grouped together under `None`
+pub type Zone = Option<String>;
+
+/// Constructs a rebalance plan spreading the shards evenly across the zones of the pods, and
+/// balancing them between the pods of each zone.
+///
+/// Shards matching an affinity rule are placed in the rule's zone if it has any pods, the rest
+/// are split into equal shares for each zone. Shards only move between zones if their zone holds
+/// more than its share, so the placement is stable while the set of zones does not change.
+/// Within a zone, the shard counts of the pods are kept between the bounds defined by
+/// `threshold`, the same way as `Rebalance::from_routing_table` does.
+pub fn plan_zone_rebalance(
+    routing_table: &RoutingTable,
+    threshold: f64,
+    config: &ZoneAwarenessConfig,
+) -> Rebalance {
+    let pods_by_zone = pods_by_zone(routing_table);
+    if pods_by_zone.is_empty() {
+        return Rebalance::empty();
+    }
+
+    let zone_shards = zone_shards(routing_table, &pods_by_zone, config);
+
+    let mut assignments = Assignments::new();
+    let mut unassignments = Unassignments::new();
+    for (zone, pods) in &pods_by_zone {
+        let shard_ids = zone_shards.get(zone).cloned().unwrap_or_default();
+        for (pod, target) in place_in_zone(routing_table, pods, shard_ids, threshold) {
+            let current = routing_table.get_shards(&pod).unwrap_or_default();
+            for shard_id in target.difference(&current) {
+                assignments.assign(pod.clone(), *shard_id);
+            }
+            for shard_id in current.difference(&target) {
+                unassignments.unassign(pod.clone(), *shard_id);
+            }
+        }
+    }
+
+    Rebalance::new(assignments, unassignments)
+}
+
+/// Splits the routing table into one routing table per zone, each having only the shards
+/// currently assigned to the pods of the zone
+pub fn routing_tables_by_zone(routing_table: &RoutingTable) -> BTreeMap<Zone, RoutingTable> {
+    let mut result: BTreeMap<Zone, RoutingTable> = BTreeMap::new();
+    for (pod, shard_ids) in &routing_table.shard_assignments {
+        let zone_routing_table = result
+            .entry(pod.zone.clone())
+            .or_insert_with(|| RoutingTable::new(0));
+        zone_routing_table.number_of_shards += shard_ids.len();
+        zone_routing_table
+            .shard_assignments
+            .insert(pod.clone(), shard_ids.clone());
+    }
+    result
+}
+
+fn pods_by_zone(routing_table: &RoutingTable) -> BTreeMap<Zone, Vec<Pod>> {
+    let mut result: BTreeMap<Zone, Vec<Pod>> = BTreeMap::new();
+    for pod in routing_table.shard_assignments.keys() {
+        result
+            .entry(pod.zone.clone())
+            .or_default()
+            .push(pod.clone());
+    }
+    result
+}
+
+/// Decides which shards each zone should hold
+fn zone_shards(
+    routing_table: &RoutingTable,
+    pods_by_zone: &BTreeMap<Zone, Vec<Pod>>,
+    config: &ZoneAwarenessConfig,
+) -> BTreeMap<Zone, BTreeSet<ShardId>> {
+    let mut current_zones: BTreeMap<ShardId, Zone> = BTreeMap::new();
+    for (pod, shard_ids) in &routing_table.shard_assignments {
+        for shard_id in shard_ids {
+            current_zones.insert(*shard_id, pod.zone.clone());
+        }
+    }
+
+    let mut result: BTreeMap<Zone, BTreeSet<ShardId>> = pods_by_zone
+        .keys()
+        .map(|zone| (zone.clone(), BTreeSet::new()))
+        .collect();
+    let mut free_shards = Vec::new();
+
+    for idx in 0..routing_table.number_of_shards {
+        let shard_id = ShardId::new(idx as i64);
+        let value = golem::shardmanager::ShardId::from(shard_id).value;
+        let affinity = config
+            .affinity
+            .iter()
+            .find(|rule| rule.first_shard <= value && value <= rule.last_shard);
+        match affinity {
+            Some(rule) if pods_by_zone.contains_key(&Some(rule.zone.clone())) => {
+                result
+                    .entry(Some(rule.zone.clone()))
+                    .or_default()
+                    .insert(shard_id);
+            }
+            Some(rule) => {
+                trace!(
+                    "Zone {} of the affinity rule of shard {} has no pods",
+                    rule.zone,
+                    shard_id
+                );
+                free_shards.push(shard_id);
+            }
+            None => free_shards.push(shard_id),
+        }
+    }
+
+    // Each zone gets an equal share of the shards without affinity, the first zones getting
+    // one more if they cannot be split evenly
+    let zone_count = pods_by_zone.len();
+    let shares: BTreeMap<Zone, usize> = pods_by_zone
+        .keys()
+        .enumerate()
+        .map(|(idx, zone)| {
+            let extra = if idx < free_shards.len() % zone_count {
+                1
+            } else {
+                0
+            };
+            (zone.clone(), free_shards.len() / zone_count + extra)
+        })
+        .collect();
+    let mut counts: BTreeMap<Zone, usize> = BTreeMap::new();
+
+    // Shards stay in their current zone while it does not hold more than its share
+    let mut moving_shards = Vec::new();
+    for shard_id in free_shards {
+        match current_zones.get(&shard_id) {
+            Some(zone) if counts.get(zone).copied().unwrap_or(0) < shares[zone] => {
+                *counts.entry(zone.clone()).or_default() += 1;
+                result.entry(zone.clone()).or_default().insert(shard_id);
+            }
+            _ => moving_shards.push(shard_id),
+        }
+    }
+
+    for shard_id in moving_shards {
+        match shares
+            .iter()
+            .find(|(zone, share)| counts.get(*zone).copied().unwrap_or(0) < **share)
+        {
+            Some((zone, _)) => {
+                *counts.entry(zone.clone()).or_default() += 1;
+                result.entry(zone.clone()).or_default().insert(shard_id);
+            }
+            None => warn!("No zone left to place shard {}", shard_id),
+        }
+    }
+
+    result
+}
+
+/// Distributes the shards of a zone between its pods, keeping the current assignments where the
+/// pod's shard count allows it
+fn place_in_zone(
+    routing_table: &RoutingTable,
+    pods: &[Pod],
+    shard_ids: BTreeSet<ShardId>,
+    threshold: f64,
+) -> Vec<(Pod, BTreeSet<ShardId>)> {
+    let shard_count = shard_ids.len();
+    let optimal_count = shard_count / pods.len();
+    let upper_threshold = ((optimal_count as f64 * (1.0 + threshold)).ceil() as usize)
+        .max(shard_count.div_ceil(pods.len()));
+    let lower_threshold = (optimal_count as f64 * (1.0 - threshold)).floor() as usize;
+
+    let mut remaining = shard_ids;
+    let mut entries: Vec<(Pod, BTreeSet<ShardId>)> = pods
+        .iter()
+        .map(|pod| {
+            let kept: BTreeSet<ShardId> = routing_table
+                .get_shards(pod)
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|shard_id| remaining.contains(shard_id))
+                .take(upper_threshold)
+                .collect();
+            remaining.retain(|shard_id| !kept.contains(shard_id));
+            (pod.clone(), kept)
+        })
+        .collect();
+
+    // Shards moved into the zone, unassigned ones, and the ones over the upper threshold
+    for shard_id in remaining {
+        let (_, target) = entries
+            .iter_mut()
+            .min_by_key(|(_, shard_ids)| shard_ids.len())
+            .expect("zones have at least one pod");
+        target.insert(shard_id);
+    }
+
+    // Moving shards to the pods under the lower threshold from the ones having the most
+    loop {
+        let target_idx = (0..entries.len()).min_by_key(|idx| entries[*idx].1.len());
+        let source_idx = (0..entries.len()).max_by_key(|idx| entries[*idx].1.len());
+        match (target_idx, source_idx) {
+            (Some(target_idx), Some(source_idx))
+                if entries[target_idx].1.len() < lower_threshold
+                    && entries[source_idx].1.len() > lower_threshold =>
+            {
+                let shard_id = *entries[source_idx].1.iter().next().unwrap();
+                entries[source_idx].1.remove(&shard_id);
+                entries[target_idx].1.insert(shard_id);
+            }
+            _ => break,
+        }
+    }
+
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use golem_common::model::ShardId;
+    use std::collections::BTreeSet;
+
+    use crate::model::{Pod, RoutingTable};
+    use crate::shard_manager_config::{ZoneAffinityConfig, ZoneAwarenessConfig};
+    use crate::zones::{plan_zone_rebalance, routing_tables_by_zone};
+
+    fn pod(idx: usize, zone: &str) -> Pod {
+        Pod::new(format!("pod{idx}"), (9000 + idx) as u16).with_zone(zone)
+    }
+
+    fn zone_counts(routing_table: &RoutingTable) -> Vec<usize> {
+        routing_tables_by_zone(routing_table)
+            .values()
+            .map(|zone_routing_table| zone_routing_table.number_of_shards)
+            .collect()
+    }
+
+    fn apply(routing_table: &mut RoutingTable, config: &ZoneAwarenessConfig) {
+        let rebalance = plan_zone_rebalance(routing_table, 0.1, config);
+        routing_table.rebalance(rebalance);
+    }
+
+    #[test]
+    fn spreads_shards_evenly_across_zones() {
+        let mut routing_table = RoutingTable::new(90);
+        for (idx, zone) in ["a", "a", "a", "a", "b", "c"].into_iter().enumerate() {
+            routing_table.add_pod(&pod(idx, zone));
+        }
+
+        apply(&mut routing_table, &ZoneAwarenessConfig::default());
+
+        assert!(routing_table.get_unassigned_shards().is_empty());
+        assert_eq!(zone_counts(&routing_table), vec![30, 30, 30]);
+        for idx in 0..4 {
+            let count = routing_table.get_shards(&pod(idx, "a")).unwrap().len();
+            assert!((7..=8).contains(&count));
+        }
+    }
+
+    #[test]
+    fn zone_outage_moves_only_its_shards() {
+        let mut routing_table = RoutingTable::new(90);
+        for (idx, zone) in ["a", "a", "b", "b", "c", "c"].into_iter().enumerate() {
+            routing_table.add_pod(&pod(idx, zone));
+        }
+        apply(&mut routing_table, &ZoneAwarenessConfig::default());
+        let before = routing_table.clone();
+
+        routing_table.remove_pod(&pod(4, "c"));
+        routing_table.remove_pod(&pod(5, "c"));
+        apply(&mut routing_table, &ZoneAwarenessConfig::default());
+
+        assert!(routing_table.get_unassigned_shards().is_empty());
+        assert_eq!(zone_counts(&routing_table), vec![45, 45]);
+        for idx in 0..4 {
+            let pod = pod(idx, if idx < 2 { "a" } else { "b" });
+            let kept = before.get_shards(&pod).unwrap();
+            assert!(routing_table.get_shards(&pod).unwrap().is_superset(&kept));
+        }
+    }
+
+    #[test]
+    fn respects_zone_affinity() {
+        let config = ZoneAwarenessConfig {
+            enabled: true,
+            affinity: vec![
+                ZoneAffinityConfig {
+                    zone: "b".to_string(),
+                    first_shard: 0,
+                    last_shard: 9,
+                },
+                ZoneAffinityConfig {
+                    zone: "missing".to_string(),
+                    first_shard: 10,
+                    last_shard: 19,
+                },
+            ],
+        };
+        let mut routing_table = RoutingTable::new(30);
+        routing_table.add_pod(&pod(0, "a"));
+        routing_table.add_pod(&pod(1, "b"));
+
+        apply(&mut routing_table, &config);
+
+        let zone_b = routing_table.get_shards(&pod(1, "b")).unwrap();
+        assert!(zone_b.is_superset(&(0..10).map(ShardId::new).collect::<BTreeSet<_>>()));
+        assert_eq!(zone_b.len(), 20);
+        assert_eq!(routing_table.get_shards(&pod(0, "a")).unwrap().len(), 10);
+    }
+
+    #[test]
+    fn balanced_placement_is_stable() {
+        let mut routing_table = RoutingTable::new(64);
+        for (idx, zone) in ["a", "b", "b", "c"].into_iter().enumerate() {
+            routing_table.add_pod(&pod(idx, zone));
+        }
+        apply(&mut routing_table, &ZoneAwarenessConfig::default());
+
+        let rebalance = plan_zone_rebalance(&routing_table, 0.1, &ZoneAwarenessConfig::default());
+        assert!(rebalance.is_empty());
+    }
+}
@@ -19,7 +19,7 @@ use std::time::Duration;
 
 use async_trait::async_trait;
 use golem_api_grpc::proto::golem::componentcompilation::v1::{
-    component_compilation_response, ComponentCompilationRequest,
+    component_compilation_response, ComponentCompilationPriority, ComponentCompilationRequest,
 };
 use tonic::codec::CompressionEncoding;
 use tonic::transport::Channel;
@@ -55,6 +55,9 @@ pub trait ComponentCompilationService: Send + Sync {
                 component_version,
                 component_service_port: None,
                 project_id: Some(project_id.into()),
+                compilation_options: None,
+                priority: ComponentCompilationPriority::Interactive as i32,
+                reusable_version: None,
             })
             .await
             .expect("Failed to enqueue component compilation")
@@ -187,6 +187,7 @@ impl FileSystemComponentService {
                 root_package_name: raw_component_metadata.root_package_name,
                 root_package_version: raw_component_metadata.root_package_version,
                 agent_types: vec![],
+                compilation_options: None,
             }),
             account_id: Some(self.account_id.clone().into()),
             project_id: Some(project_id.into()),
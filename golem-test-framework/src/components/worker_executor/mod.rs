@@ -86,6 +86,7 @@ async fn env_vars(
     redis: Arc<dyn Redis + Send + Sync + 'static>,
     cloud_service: &Arc<dyn CloudService>,
     verbosity: Level,
+    pooling_allocator: bool,
     otlp: bool,
 ) -> HashMap<String, String> {
     EnvVarBuilder::golem_service(verbosity)
@@ -173,6 +174,14 @@ async fn env_vars(
         )
         .with_str("GOLEM__RESOURCE_LIMITS__TYPE", "Disabled")
         .with_str("GOLEM__LIMITS__FUEL_TO_BORROW", "100000")
+        .with_str(
+            "GOLEM__ENGINE__INSTANCE_ALLOCATION__TYPE",
+            if pooling_allocator {
+                "Pooling"
+            } else {
+                "OnDemand"
+            },
+        )
         .with_str("GOLEM__PROJECT_SERVICE__TYPE", "Grpc")
         .with(
             "GOLEM__PROJECT_SERVICE__CONFIG__HOST",
@@ -45,6 +45,7 @@ pub struct SpawnedWorkerExecutor {
     err_level: Level,
     client: Option<WorkerExecutorClient<Channel>>,
     cloud_service: Arc<dyn CloudService>,
+    pooling_allocator: bool,
     otlp: bool,
 }
 
@@ -63,6 +64,7 @@ impl SpawnedWorkerExecutor {
         err_level: Level,
         shared_client: bool,
         cloud_service: Arc<dyn CloudService>,
+        pooling_allocator: bool,
         otlp: bool,
     ) -> Self {
         info!("Starting golem-worker-executor process");
@@ -84,6 +86,7 @@ impl SpawnedWorkerExecutor {
             out_level,
             err_level,
             &cloud_service,
+            pooling_allocator,
             otlp,
         )
         .await;
@@ -112,6 +115,7 @@ impl SpawnedWorkerExecutor {
                 None
             },
             cloud_service,
+            pooling_allocator,
             otlp,
         }
     }
@@ -129,6 +133,7 @@ impl SpawnedWorkerExecutor {
         out_level: Level,
         err_level: Level,
         cloud_service: &Arc<dyn CloudService>,
+        pooling_allocator: bool,
         otlp: bool,
     ) -> (Child, ChildProcessLogger) {
         let mut child = Command::new(executable)
@@ -143,6 +148,7 @@ impl SpawnedWorkerExecutor {
                     redis,
                     cloud_service,
                     verbosity,
+                    pooling_allocator,
                     otlp,
                 )
                 .await,
@@ -215,6 +221,7 @@ impl WorkerExecutor for SpawnedWorkerExecutor {
             self.out_level,
             self.err_level,
             &self.cloud_service,
+            self.pooling_allocator,
             self.otlp,
         )
         .await;
@@ -49,6 +49,7 @@ impl SpawnedWorkerExecutorCluster {
         err_level: Level,
         shared_client: bool,
         cloud_service: Arc<dyn CloudService>,
+        pooling_allocator: bool,
         otlp: bool,
     ) -> Arc<dyn WorkerExecutor> {
         Arc::new(
@@ -66,6 +67,7 @@ impl SpawnedWorkerExecutorCluster {
                 err_level,
                 shared_client,
                 cloud_service,
+                pooling_allocator,
                 otlp,
             )
             .await,
@@ -87,6 +89,7 @@ impl SpawnedWorkerExecutorCluster {
         err_level: Level,
         shared_client: bool,
         cloud_service: Arc<dyn CloudService>,
+        pooling_allocator: bool,
         otlp: bool,
     ) -> Self {
         info!("Starting a cluster of golem-worker-executors of size {size}");
@@ -111,6 +114,7 @@ impl SpawnedWorkerExecutorCluster {
                     err_level,
                     shared_client,
                     cloud_service.clone(),
+                    pooling_allocator,
                     otlp,
                 )
                 .in_current_span(),
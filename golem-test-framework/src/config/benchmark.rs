@@ -275,6 +275,7 @@ impl BenchmarkTestDependencies {
         cloud_service_grpc_port: u16,
         mute_child: bool,
         component_directory: &str,
+        pooling_allocator: bool,
         otlp: bool,
     ) -> Self {
         let workspace_root = Path::new(workspace_root).canonicalize().unwrap();
@@ -423,6 +424,7 @@ impl BenchmarkTestDependencies {
                 Level::ERROR,
                 true,
                 cloud_service.clone(),
+                pooling_allocator,
                 otlp,
             )
             .await,
@@ -452,6 +454,29 @@ impl BenchmarkTestDependencies {
         cluster_size: usize,
         compilation_cache_disabled: bool,
         otlp: bool,
+    ) -> Self {
+        Self::new_with_pooling_allocator(
+            mode,
+            verbosity,
+            cluster_size,
+            compilation_cache_disabled,
+            false,
+            otlp,
+        )
+        .await
+    }
+
+    /// Creates the test dependencies, with the spawned worker executors using the pooling
+    /// instance allocator if `pooling_allocator` is set.
+    ///
+    /// Provided worker executors are used with their own engine configuration.
+    pub async fn new_with_pooling_allocator(
+        mode: &TestMode,
+        verbosity: Level,
+        cluster_size: usize,
+        compilation_cache_disabled: bool,
+        pooling_allocator: bool,
+        otlp: bool,
     ) -> Self {
         match mode {
             TestMode::Provided {
@@ -621,6 +646,7 @@ impl BenchmarkTestDependencies {
                     *cloud_service_grpc_port,
                     *mute_child,
                     component_directory,
+                    pooling_allocator,
                     otlp,
                 )
                 .await
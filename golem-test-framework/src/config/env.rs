@@ -364,6 +364,7 @@ impl EnvBasedTestDependencies {
                 config.shared_client,
                 cloud_service,
                 false,
+                false,
             )
             .await,
         )
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use crate::durable_host::DurableWorkerCtx;
+use crate::engines::ComponentEngines;
 use crate::preview2::{golem_agent, golem_api_1_x, golem_durability};
 use crate::services::active_workers::ActiveWorkers;
 use crate::services::agent_types::AgentTypesService;
@@ -59,7 +60,10 @@ struct ServerBootstrap {}
 #[async_trait]
 impl Bootstrap<Context> for ServerBootstrap {
     fn create_active_workers(&self, golem_config: &GolemConfig) -> Arc<ActiveWorkers<Context>> {
-        Arc::new(ActiveWorkers::<Context>::new(&golem_config.memory))
+        Arc::new(ActiveWorkers::<Context>::new(
+            &golem_config.memory,
+            &golem_config.engine,
+        ))
     }
 
     fn create_plugins(
@@ -90,8 +94,7 @@ impl Bootstrap<Context> for ServerBootstrap {
     async fn create_services(
         &self,
         active_workers: Arc<ActiveWorkers<Context>>,
-        engine: Arc<Engine>,
-        linker: Arc<Linker<Context>>,
+        engines: Arc<ComponentEngines<Context>>,
         runtime: Handle,
         component_service: Arc<dyn ComponentService>,
         shard_manager_service: Arc<dyn ShardManagerService>,
@@ -125,8 +128,7 @@ impl Bootstrap<Context> for ServerBootstrap {
                 shard_service.clone(),
             )),
             active_workers.clone(),
-            engine.clone(),
-            linker.clone(),
+            engines.clone(),
             runtime.clone(),
             component_service.clone(),
             shard_manager_service.clone(),
@@ -159,8 +161,7 @@ impl Bootstrap<Context> for ServerBootstrap {
                 shard_service.clone(),
             )),
             active_workers.clone(),
-            engine.clone(),
-            linker.clone(),
+            engines.clone(),
             runtime.clone(),
             component_service.clone(),
             worker_fork.clone(),
@@ -190,8 +191,7 @@ impl Bootstrap<Context> for ServerBootstrap {
         Ok(All::new(
             active_workers,
             agent_type_service,
-            engine,
-            linker,
+            engines,
             runtime.clone(),
             component_service,
             shard_manager_service,
@@ -46,7 +46,7 @@ use crate::services::agent_types::AgentTypesService;
 use crate::services::blob_store::BlobStoreService;
 use crate::services::component::ComponentService;
 use crate::services::file_loader::{FileLoader, FileUseToken};
-use crate::services::golem_config::GolemConfig;
+use crate::services::golem_config::{ComponentDiskCacheConfig, GolemConfig};
 use crate::services::key_value::KeyValueService;
 use crate::services::oplog::{CommitLevel, Oplog, OplogOps, OplogService};
 use crate::services::plugins::Plugins;
@@ -80,7 +80,7 @@ use chrono::{DateTime, Utc};
 pub use durability::*;
 use futures::future::try_join_all;
 use futures::TryFutureExt;
-use futures::TryStreamExt;
+use futures::{StreamExt, TryStreamExt};
 use golem_common::model::agent::{AgentId, AgentMode};
 use golem_common::model::invocation_context::{
     AttributeValue, InvocationContextSpan, InvocationContextStack, SpanId,
@@ -2195,6 +2195,7 @@ impl<Ctx: WorkerCtx + DurableWorkerCtxView<Ctx>> ExternalOperations<Ctx> for Dur
 
         debug!("Recovering running workers: {:?}", workers);
 
+        let mut workers_to_recover = Vec::new();
         for worker in workers {
             let owned_worker_id = worker.initial_worker_metadata.owned_worker_id();
             let created_by = worker.initial_worker_metadata.created_by.clone();
@@ -2211,28 +2212,96 @@ impl<Ctx: WorkerCtx + DurableWorkerCtxView<Ctx>> ExternalOperations<Ctx> for Dur
                 | WorkerStatus::Idle
                 | WorkerStatus::Retrying
                 | WorkerStatus::Interrupted => {
-                    let _ = Worker::get_or_create_running(
-                        this,
-                        &created_by,
-                        &owned_worker_id,
-                        None,
-                        None,
-                        None,
-                        None,
-                        None,
-                        &InvocationContextStack::fresh(),
-                    )
-                    .await?;
+                    workers_to_recover.push((
+                        created_by,
+                        owned_worker_id,
+                        latest_worker_status.component_version,
+                    ));
                 }
                 _ => {}
             }
         }
 
+        warm_up_component_cache(
+            this,
+            workers_to_recover
+                .iter()
+                .map(|(_, owned_worker_id, component_version)| {
+                    (owned_worker_id.component_id(), *component_version)
+                })
+                .collect(),
+        )
+        .await;
+
+        for (created_by, owned_worker_id, _) in workers_to_recover {
+            let _ = Worker::get_or_create_running(
+                this,
+                &created_by,
+                &owned_worker_id,
+                None,
+                None,
+                None,
+                None,
+                None,
+                &InvocationContextStack::fresh(),
+            )
+            .await?;
+        }
+
         info!("Finished recovering workers");
         Ok(())
     }
 }
 
+/// Loads the components of the workers about to be recovered in parallel when the on-disk
+/// compiled component cache is enabled, so the recovery does not wait for downloading or compiling
+/// them one by one.
+async fn warm_up_component_cache<Ctx: WorkerCtx, T: HasAll<Ctx> + Send + Sync>(
+    this: &T,
+    components: HashSet<(ComponentId, ComponentVersion)>,
+) {
+    let config = this.config();
+    let ComponentDiskCacheConfig::Enabled(disk_cache_config) = &config.component_cache.disk else {
+        return;
+    };
+
+    info!(
+        "Warming up the component cache with {} components",
+        components.len()
+    );
+
+    let engines = this.engines();
+    let component_service = this.component_service();
+    futures::stream::iter(components)
+        .for_each_concurrent(
+            disk_cache_config.warm_up_concurrency.max(1),
+            |(component_id, component_version)| {
+                let engines = engines.clone();
+                let component_service = component_service.clone();
+                async move {
+                    let result = match component_service
+                        .get_metadata(&component_id, Some(component_version))
+                        .await
+                    {
+                        Ok(metadata) => {
+                            let engine = engines.engine(metadata.metadata.compilation_options());
+                            component_service
+                                .get(&engine, &component_id, component_version)
+                                .await
+                        }
+                        Err(err) => Err(err),
+                    };
+                    if let Err(err) = result {
+                        warn!(
+                            "Failed to warm up the component cache with {component_id}/{component_version}: {err}"
+                        );
+                    }
+                }
+            },
+        )
+        .await;
+}
+
 #[async_trait]
 impl<Ctx: WorkerCtx + DurableWorkerCtxView<Ctx>> FileSystemReading for DurableWorkerCtx<Ctx> {
     async fn get_file_system_node(
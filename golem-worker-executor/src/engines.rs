@@ -0,0 +1,140 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use golem_common::model::component_metadata::{
+    CompilationInterruption, CompilationOptimizationLevel, ComponentCompilationOptions,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use wasmtime::component::Linker;
+use wasmtime::{Config, Engine, InstanceAllocationStrategy, OptLevel};
+
+/// The part of the compilation options of a component which affects the generated native code.
+///
+/// Compiled components can only be loaded by an engine with the same settings, so each profile
+/// has its own engine, both in the compilation service and in the worker executor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EngineProfile {
+    pub optimization_level: CompilationOptimizationLevel,
+    pub interruption: CompilationInterruption,
+}
+
+impl EngineProfile {
+    pub fn all() -> Vec<EngineProfile> {
+        let mut result = Vec::new();
+        for optimization_level in [
+            CompilationOptimizationLevel::Speed,
+            CompilationOptimizationLevel::None,
+            CompilationOptimizationLevel::SpeedAndSize,
+        ] {
+            for interruption in [
+                CompilationInterruption::Fuel,
+                CompilationInterruption::Epoch,
+            ] {
+                result.push(EngineProfile {
+                    optimization_level,
+                    interruption,
+                });
+            }
+        }
+        result
+    }
+
+    /// Applies the profile on top of the base wasmtime configuration. Epoch based interruption is
+    /// always enabled, as the executor relies on it for interrupting and suspending workers.
+    pub fn configure(&self, config: &mut Config) {
+        config.cranelift_opt_level(match self.optimization_level {
+            CompilationOptimizationLevel::Speed => OptLevel::Speed,
+            CompilationOptimizationLevel::None => OptLevel::None,
+            CompilationOptimizationLevel::SpeedAndSize => OptLevel::SpeedAndSize,
+        });
+        config.epoch_interruption(true);
+        config.consume_fuel(self.interruption == CompilationInterruption::Fuel);
+    }
+}
+
+impl Default for EngineProfile {
+    fn default() -> Self {
+        Self::from(&ComponentCompilationOptions::default())
+    }
+}
+
+impl From<&ComponentCompilationOptions> for EngineProfile {
+    fn from(value: &ComponentCompilationOptions) -> Self {
+        Self {
+            optimization_level: value.optimization_level,
+            interruption: value.interruption,
+        }
+    }
+}
+
+/// The wasmtime engines and linkers of every engine profile
+pub struct ComponentEngines<Ctx> {
+    engines: HashMap<EngineProfile, (Arc<Engine>, Arc<Linker<Ctx>>)>,
+}
+
+impl<Ctx> ComponentEngines<Ctx> {
+    /// Creates the engine and the linker of every profile with the given functions.
+    ///
+    /// Only the engine of the default profile keeps the instance allocation strategy of the
+    /// created configuration, the others allocate their instances on demand. Every engine would
+    /// reserve its own pool otherwise, multiplying the memory reserved by the pooling allocator.
+    pub fn new(
+        create_config: impl Fn() -> Config,
+        create_linker: impl Fn(&Engine) -> anyhow::Result<Linker<Ctx>>,
+    ) -> anyhow::Result<Self> {
+        let mut engines = HashMap::new();
+        for profile in EngineProfile::all() {
+            let mut config = create_config();
+            profile.configure(&mut config);
+            if profile != EngineProfile::default() {
+                config.allocation_strategy(InstanceAllocationStrategy::OnDemand);
+            }
+            let engine = Engine::new(&config)?;
+            let linker = create_linker(&engine)?;
+            engines.insert(profile, (Arc::new(engine), Arc::new(linker)));
+        }
+        Ok(Self { engines })
+    }
+
+    /// The engine for components compiled with the given options
+    pub fn engine(&self, options: &ComponentCompilationOptions) -> Arc<Engine> {
+        self.get(&EngineProfile::from(options)).0.clone()
+    }
+
+    /// The linker for components compiled with the given options
+    pub fn linker(&self, options: &ComponentCompilationOptions) -> Arc<Linker<Ctx>> {
+        self.get(&EngineProfile::from(options)).1.clone()
+    }
+
+    pub fn default_engine(&self) -> Arc<Engine> {
+        self.get(&EngineProfile::default()).0.clone()
+    }
+
+    pub fn default_linker(&self) -> Arc<Linker<Ctx>> {
+        self.get(&EngineProfile::default()).1.clone()
+    }
+
+    pub fn increment_epoch(&self) {
+        for (engine, _) in self.engines.values() {
+            engine.increment_epoch();
+        }
+    }
+
+    fn get(&self, profile: &EngineProfile) -> &(Arc<Engine>, Arc<Linker<Ctx>>) {
+        self.engines
+            .get(profile)
+            .expect("Every engine profile has an engine")
+    }
+}
@@ -71,10 +71,12 @@ use golem_wasm::ValueAndType;
 use std::cmp::min;
 use std::collections::HashMap;
 use std::fmt::{Debug, Display, Formatter};
+use std::future::Future;
 use std::marker::PhantomData;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
 use tokio;
 use tokio::sync::broadcast::error::RecvError;
 use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
@@ -142,6 +144,47 @@ pub fn is_grpc_retriable<E>(error: &GrpcError<E>) -> bool {
     }
 }
 
+/// How often revoked workers are checked for reaching a suspension point
+const SUSPENSION_POINT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Interrupts the revoked workers. With a non-zero `suspension_timeout` the running invocations
+/// first get a chance to reach a suspension point, so the workers don't have to replay them after
+/// being moved to another executor. The workers still running at the deadline are interrupted
+/// anyway.
+async fn interrupt_at_suspension_points<W, S, SFut, I, IFut>(
+    workers: Vec<(WorkerId, W)>,
+    suspension_timeout: Duration,
+    is_at_suspension_point: S,
+    interrupt: I,
+) where
+    W: Clone,
+    S: Fn(W) -> SFut,
+    SFut: Future<Output = bool>,
+    I: Fn(W) -> IFut,
+    IFut: Future<Output = ()>,
+{
+    if !suspension_timeout.is_zero() {
+        let deadline = Instant::now() + suspension_timeout;
+        for (worker_id, worker) in &workers {
+            while !is_at_suspension_point(worker.clone()).await {
+                if Instant::now() >= deadline {
+                    warn!(
+                        worker_id = worker_id.to_string(),
+                        "Worker did not reach a suspension point in {:?}, interrupting it",
+                        suspension_timeout
+                    );
+                    break;
+                }
+                tokio::time::sleep(SUSPENSION_POINT_POLL_INTERVAL).await;
+            }
+        }
+    }
+
+    for (_, worker) in workers {
+        interrupt(worker).await;
+    }
+}
+
 /// This is the implementation of the Worker Executor gRPC API
 pub struct WorkerExecutorImpl<
     Ctx: WorkerCtx,
@@ -850,23 +893,39 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
         request: golem::workerexecutor::v1::RevokeShardsRequest,
     ) -> Result<(), WorkerExecutorError> {
         let proto_shard_ids = request.shard_ids;
+        let suspension_timeout = Duration::from_millis(request.suspension_timeout_millis);
 
         let shard_ids = proto_shard_ids.into_iter().map(ShardId::from).collect();
 
         self.shard_service().revoke_shards(&shard_ids)?;
+        self.stop_unassigned_workers(suspension_timeout).await;
+
+        Ok(())
+    }
 
-        for (worker_id, worker_details) in self.active_workers().snapshot().await {
-            if self.shard_service().check_worker(&worker_id).is_err() {
-                if let Some(mut await_interrupted) = worker_details
-                    .set_interrupting(InterruptKind::Restart)
-                    .await
+    /// Interrupts the active workers that no longer belong to the shards assigned to this executor
+    async fn stop_unassigned_workers(&self, suspension_timeout: Duration) {
+        let revoked_workers = self
+            .active_workers()
+            .snapshot()
+            .await
+            .into_iter()
+            .filter(|(worker_id, _)| self.shard_service().check_worker(worker_id).is_err())
+            .collect::<Vec<_>>();
+
+        interrupt_at_suspension_points(
+            revoked_workers,
+            suspension_timeout,
+            |worker: Arc<Worker<Ctx>>| async move { worker.is_at_suspension_point().await },
+            |worker: Arc<Worker<Ctx>>| async move {
+                if let Some(mut await_interrupted) =
+                    worker.set_interrupting(InterruptKind::Restart).await
                 {
                     await_interrupted.recv().await.unwrap();
                 }
-            }
-        }
-
-        Ok(())
+            },
+        )
+        .await;
     }
 
     async fn assign_shards_internal(
@@ -877,12 +936,79 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
 
         let shard_ids = proto_shard_ids.into_iter().map(ShardId::from).collect();
 
-        self.shard_service().assign_shards(&shard_ids)?;
+        let current_number_of_shards = self
+            .shard_service()
+            .try_get_current_assignment()
+            .map(|assignment| assignment.number_of_shards);
+
+        if request.full_assignment {
+            // Replacing the whole assignment, possibly with a different number of shards. When
+            // resharding, every worker stays on this executor, only the shard ids they belong to change.
+            let number_of_shards = request
+                .number_of_shards
+                .map(|number_of_shards| number_of_shards as usize)
+                .or(current_number_of_shards)
+                .ok_or_else(|| {
+                    WorkerExecutorError::invalid_request(
+                        "Full shard assignment without the number of shards",
+                    )
+                })?;
+            self.shard_service().reshard(number_of_shards, &shard_ids)?;
+            self.stop_unassigned_workers(Duration::ZERO).await;
+        } else {
+            // An incremental assignment in terms of another number of shards would leave this
+            // executor with a mix of both, so it has to wait for its full assignment
+            if let (Some(number_of_shards), Some(current_number_of_shards)) =
+                (request.number_of_shards, current_number_of_shards)
+            {
+                if number_of_shards as usize != current_number_of_shards {
+                    return Err(WorkerExecutorError::invalid_request(format!(
+                        "Incremental shard assignment for {number_of_shards} shards, but the executor uses {current_number_of_shards}"
+                    )));
+                }
+            }
+            self.shard_service().assign_shards(&shard_ids)?;
+        }
         Ctx::on_shard_assignment_changed(self).await?;
 
         Ok(())
     }
 
+    async fn get_shard_loads_internal(
+        &self,
+    ) -> Result<Vec<golem::workerexecutor::v1::ShardLoad>, WorkerExecutorError> {
+        let number_of_shards = self.shard_service().current_assignment()?.number_of_shards;
+
+        let mut shard_loads: HashMap<ShardId, golem::workerexecutor::v1::ShardLoad> =
+            HashMap::new();
+        for (worker_id, worker) in self.active_workers().snapshot().await {
+            let shard_id = ShardId::from_worker_id(&worker_id, number_of_shards);
+            let memory = match worker.memory_requirement().await {
+                Ok(memory) => memory,
+                Err(err) => {
+                    // A single worker failing must not prevent reporting the other loads
+                    warn!(
+                        worker_id = worker_id.to_string(),
+                        "Failed to get memory requirement, skipping worker from shard loads: {err}"
+                    );
+                    continue;
+                }
+            };
+
+            let shard_load = shard_loads.entry(shard_id).or_insert_with(|| {
+                golem::workerexecutor::v1::ShardLoad {
+                    shard_id: Some(shard_id.into()),
+                    ..Default::default()
+                }
+            });
+            shard_load.active_workers += 1;
+            shard_load.memory += memory;
+            shard_load.consumed_fuel += worker.consumed_fuel();
+        }
+
+        Ok(shard_loads.into_values().collect())
+    }
+
     async fn get_worker_metadata_internal(
         &self,
         request: golem::workerexecutor::v1::GetWorkerMetadataRequest,
@@ -2189,6 +2315,41 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
         }
     }
 
+    async fn get_shard_loads(
+        &self,
+        _request: Request<golem::workerexecutor::v1::GetShardLoadsRequest>,
+    ) -> Result<Response<golem::workerexecutor::v1::GetShardLoadsResponse>, Status> {
+        let record = recorded_grpc_api_request!("get_shard_loads",);
+
+        match self
+            .get_shard_loads_internal()
+            .instrument(record.span.clone())
+            .await
+        {
+            Ok(shard_loads) => record.succeed(Ok(Response::new(
+                golem::workerexecutor::v1::GetShardLoadsResponse {
+                    result: Some(
+                        golem::workerexecutor::v1::get_shard_loads_response::Result::Success(
+                            golem::workerexecutor::v1::GetShardLoadsSuccess { shard_loads },
+                        ),
+                    ),
+                },
+            ))),
+            Err(err) => record.fail(
+                Ok(Response::new(
+                    golem::workerexecutor::v1::GetShardLoadsResponse {
+                        result: Some(
+                            golem::workerexecutor::v1::get_shard_loads_response::Result::Failure(
+                                err.clone().into(),
+                            ),
+                        ),
+                    },
+                )),
+                &err,
+            ),
+        }
+    }
+
     async fn get_worker_metadata(
         &self,
         request: Request<golem::workerexecutor::v1::GetWorkerMetadataRequest>,
@@ -2785,3 +2946,125 @@ fn extract_account_id<T>(
         .ok_or(WorkerExecutorError::invalid_request("account_id not found"))?;
     Ok(account_id.clone().into())
 }
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use crate::grpc::{interrupt_at_suspension_points, SUSPENSION_POINT_POLL_INTERVAL};
+    use golem_common::model::{ComponentId, WorkerId};
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::{Duration, Instant};
+
+    #[derive(Clone)]
+    struct TestWorker {
+        at_suspension_point: Arc<AtomicBool>,
+        // Whether the worker was at a suspension point when it got interrupted, and when
+        interrupted: Arc<Mutex<Option<(bool, Instant)>>>,
+    }
+
+    impl TestWorker {
+        fn new() -> Self {
+            Self {
+                at_suspension_point: Arc::new(AtomicBool::new(false)),
+                interrupted: Arc::new(Mutex::new(None)),
+            }
+        }
+
+        fn interrupted(&self) -> Option<(bool, Instant)> {
+            *self.interrupted.lock().unwrap()
+        }
+    }
+
+    fn worker_id(name: &str) -> WorkerId {
+        WorkerId {
+            component_id: ComponentId::new_v4(),
+            worker_name: name.to_string(),
+        }
+    }
+
+    async fn interrupt(workers: &[(WorkerId, TestWorker)], suspension_timeout: Duration) {
+        interrupt_at_suspension_points(
+            workers.to_vec(),
+            suspension_timeout,
+            |worker: TestWorker| async move { worker.at_suspension_point.load(Ordering::SeqCst) },
+            |worker: TestWorker| async move {
+                let at_suspension_point = worker.at_suspension_point.load(Ordering::SeqCst);
+                *worker.interrupted.lock().unwrap() = Some((at_suspension_point, Instant::now()));
+            },
+        )
+        .await
+    }
+
+    #[test]
+    async fn revoked_worker_is_interrupted_after_reaching_a_suspension_point() {
+        let worker = TestWorker::new();
+        let workers = vec![(worker_id("worker"), worker.clone())];
+        let started = Instant::now();
+
+        tokio::spawn({
+            let at_suspension_point = worker.at_suspension_point.clone();
+            async move {
+                tokio::time::sleep(Duration::from_millis(300)).await;
+                at_suspension_point.store(true, Ordering::SeqCst);
+            }
+        });
+
+        interrupt(&workers, Duration::from_secs(10)).await;
+
+        let (at_suspension_point, interrupted_at) = worker.interrupted().unwrap();
+        assert!(at_suspension_point);
+        assert!(interrupted_at - started >= Duration::from_millis(300));
+        assert!(interrupted_at - started < Duration::from_secs(10));
+    }
+
+    #[test]
+    async fn revoked_worker_is_interrupted_when_the_suspension_timeout_passes() {
+        let busy_worker = TestWorker::new();
+        let idle_worker = TestWorker::new();
+        idle_worker
+            .at_suspension_point
+            .store(true, Ordering::SeqCst);
+        let workers = vec![
+            (worker_id("busy"), busy_worker.clone()),
+            (worker_id("idle"), idle_worker.clone()),
+        ];
+        let suspension_timeout = Duration::from_millis(300);
+        let started = Instant::now();
+
+        interrupt(&workers, suspension_timeout).await;
+
+        let (at_suspension_point, interrupted_at) = busy_worker.interrupted().unwrap();
+        assert!(!at_suspension_point);
+        assert!(interrupted_at - started >= suspension_timeout);
+        assert!(interrupted_at - started < suspension_timeout + SUSPENSION_POINT_POLL_INTERVAL * 5);
+        assert!(idle_worker.interrupted().is_some());
+    }
+
+    #[test]
+    async fn revoked_workers_are_interrupted_right_away_without_suspension_timeout() {
+        let worker = TestWorker::new();
+        let workers = vec![(worker_id("worker"), worker.clone())];
+        let checks = Arc::new(AtomicUsize::new(0));
+
+        interrupt_at_suspension_points(
+            workers,
+            Duration::ZERO,
+            |_: TestWorker| {
+                let checks = checks.clone();
+                async move {
+                    checks.fetch_add(1, Ordering::SeqCst);
+                    false
+                }
+            },
+            |worker: TestWorker| async move {
+                *worker.interrupted.lock().unwrap() = Some((false, Instant::now()));
+            },
+        )
+        .await;
+
+        assert!(worker.interrupted().is_some());
+        assert_eq!(checks.load(Ordering::SeqCst), 0);
+    }
+}
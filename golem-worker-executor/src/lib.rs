@@ -15,6 +15,7 @@
 pub mod bootstrap;
 pub mod config;
 pub mod durable_host;
+pub mod engines;
 pub mod grpc;
 pub mod metrics;
 pub mod model;
@@ -30,6 +31,7 @@ pub mod workerctx;
 test_r::enable!();
 
 use self::services::promise::LazyPromiseService;
+use crate::engines::ComponentEngines;
 use crate::grpc::WorkerExecutorImpl;
 use crate::services::active_workers::ActiveWorkers;
 use crate::services::agent_types::AgentTypesService;
@@ -37,7 +39,8 @@ use crate::services::blob_store::{BlobStoreService, DefaultBlobStoreService};
 use crate::services::component::ComponentService;
 use crate::services::events::Events;
 use crate::services::golem_config::{
-    EngineConfig, GolemConfig, IndexedStorageConfig, KeyValueStorageConfig,
+    EngineConfig, GolemConfig, IndexedStorageConfig, InstanceAllocationConfig,
+    KeyValueStorageConfig,
 };
 use crate::services::key_value::{DefaultKeyValueService, KeyValueService};
 use crate::services::oplog::plugin::{
@@ -60,7 +63,7 @@ use crate::services::worker_enumeration::{
     RunningWorkerEnumerationServiceDefault, WorkerEnumerationService,
 };
 use crate::services::worker_proxy::{RemoteWorkerProxy, WorkerProxy};
-use crate::services::{rdbms, shard_manager, All, HasConfig};
+use crate::services::{rdbms, shard_manager, All, HasConfig, HasShardManagerService};
 use crate::storage::indexed::redis::RedisIndexedStorage;
 use crate::storage::indexed::sqlite::SqliteIndexedStorage;
 use crate::storage::indexed::IndexedStorage;
@@ -70,6 +73,7 @@ use crate::storage::keyvalue::KeyValueStorage;
 use crate::workerctx::WorkerCtx;
 use anyhow::anyhow;
 use async_trait::async_trait;
+use gethostname::gethostname;
 use golem_api_grpc::proto;
 use golem_api_grpc::proto::golem::workerexecutor::v1::worker_executor_server::WorkerExecutorServer;
 use golem_common::redis::RedisPool;
@@ -94,10 +98,12 @@ use tonic::codec::CompressionEncoding;
 use tonic::transport::Server;
 use tonic_tracing_opentelemetry::middleware;
 use tonic_tracing_opentelemetry::middleware::filters;
-use tracing::{info, Instrument};
+use tracing::{info, warn, Instrument};
 use uuid::Uuid;
 use wasmtime::component::Linker;
-use wasmtime::{Config, Engine, WasmBacktraceDetails};
+use wasmtime::{
+    Config, Engine, InstanceAllocationStrategy, PoolingAllocationConfig, WasmBacktraceDetails,
+};
 
 pub struct RunDetails {
     pub http_port: u16,
@@ -190,8 +196,7 @@ pub trait Bootstrap<Ctx: WorkerCtx> {
     async fn create_services(
         &self,
         active_workers: Arc<ActiveWorkers<Ctx>>,
-        engine: Arc<Engine>,
-        linker: Arc<Linker<Ctx>>,
+        engines: Arc<ComponentEngines<Ctx>>,
         runtime: Handle,
         component_service: Arc<dyn ComponentService>,
         shard_manager_service: Arc<dyn ShardManagerService>,
@@ -234,6 +239,28 @@ pub trait Bootstrap<Ctx: WorkerCtx> {
             ));
         }
 
+        if let InstanceAllocationConfig::Pooling(pooling_config) =
+            &engine_config.instance_allocation
+        {
+            let mut pooling = PoolingAllocationConfig::default();
+            pooling
+                .total_component_instances(pooling_config.total_component_instances)
+                .total_core_instances(pooling_config.total_core_instances)
+                .total_memories(pooling_config.total_memories)
+                .total_tables(pooling_config.total_tables)
+                .total_stacks(pooling_config.total_stacks)
+                .max_memory_size(pooling_config.max_memory_size)
+                .table_elements(pooling_config.table_elements)
+                .max_core_instances_per_component(pooling_config.max_core_instances_per_component)
+                .max_memories_per_component(pooling_config.max_memories_per_component)
+                .max_tables_per_component(pooling_config.max_tables_per_component)
+                .max_unused_warm_slots(pooling_config.max_unused_warm_slots)
+                .linear_memory_keep_resident(pooling_config.linear_memory_keep_resident);
+
+            config.allocation_strategy(InstanceAllocationStrategy::Pooling(pooling));
+            config.memory_init_cow(pooling_config.memory_init_cow);
+        }
+
         config
     }
 
@@ -260,6 +287,25 @@ pub trait Bootstrap<Ctx: WorkerCtx> {
             ISizeFormatter::new(system_memory, BINARY),
             ISizeFormatter::new(worker_memory, BINARY)
         );
+        if let InstanceAllocationConfig::Pooling(pooling) = &golem_config.engine.instance_allocation
+        {
+            info!(
+                "Using pooling instance allocator for the default engine profile with {} instance slots, maximum linear memory per worker: {}, total linear memory of the pool: {}",
+                pooling.total_component_instances,
+                ISizeFormatter::new(pooling.max_memory_size as u64, BINARY),
+                ISizeFormatter::new(pooling.total_memory_size(), BINARY)
+            );
+            if pooling.max_memory_size > worker_memory {
+                warn!(
+                    "The pooling allocator's maximum memory size is larger than the total memory available for workers"
+                );
+            }
+            if pooling.total_memory_size() > worker_memory as u64 {
+                warn!(
+                    "The pooling allocator's total linear memory is larger than the total memory available for workers"
+                );
+            }
+        }
 
         let lazy_worker_activator = Arc::new(LazyWorkerActivator::new());
 
@@ -271,6 +317,8 @@ pub trait Bootstrap<Ctx: WorkerCtx> {
         )
         .await?;
 
+        let shard_manager_service = worker_executor_impl.shard_manager_service();
+
         let grpc_port = self
             .run_grpc_server(worker_executor_impl, lazy_worker_activator, join_set)
             .await?;
@@ -283,6 +331,17 @@ pub trait Bootstrap<Ctx: WorkerCtx> {
         )
         .await?;
 
+        if let Some(admin_http_addr) = golem_config.admin_http_addr()? {
+            shard_manager::start_admin_http_server(
+                admin_http_addr,
+                shard_manager_service,
+                gethostname().to_string_lossy().to_string(),
+                grpc_port,
+                join_set,
+            )
+            .await?;
+        }
+
         Ok(RunDetails {
             http_port,
             grpc_port,
@@ -470,20 +529,19 @@ pub async fn create_worker_executor_impl<Ctx: WorkerCtx, A: Bootstrap<Ctx> + ?Si
 
     let shard_manager_service = shard_manager::configured(&golem_config.shard_manager_service);
 
-    let config = bootstrap.create_wasmtime_config(&golem_config.engine);
-    let engine = Arc::new(Engine::new(&config)?);
-    let linker = bootstrap.create_wasmtime_linker(&engine)?;
+    let engines = Arc::new(ComponentEngines::new(
+        || bootstrap.create_wasmtime_config(&golem_config.engine),
+        |engine| bootstrap.create_wasmtime_linker(engine),
+    )?);
 
-    let engine_ref: Arc<Engine> = engine.clone();
+    let engines_ref = engines.clone();
 
     let epoch_interval = golem_config.limits.epoch_interval;
     let epoch_thread = std::thread::spawn(move || loop {
         std::thread::sleep(epoch_interval);
-        engine_ref.increment_epoch();
+        engines_ref.increment_epoch();
     });
 
-    let linker = Arc::new(linker);
-
     let key_value_service = Arc::new(DefaultKeyValueService::new(key_value_storage.clone()));
 
     let blob_store_service = Arc::new(DefaultBlobStoreService::new(blob_storage.clone()));
@@ -549,8 +607,7 @@ pub async fn create_worker_executor_impl<Ctx: WorkerCtx, A: Bootstrap<Ctx> + ?Si
     let all = bootstrap
         .create_services(
             active_workers,
-            engine,
-            linker,
+            engines,
             runtime.clone(),
             component_service,
             shard_manager_service,
@@ -163,6 +163,60 @@ pub mod sharding {
     }
 }
 
+pub mod instance_pool {
+    use lazy_static::lazy_static;
+    use prometheus::*;
+
+    lazy_static! {
+        static ref INSTANCE_POOL_SLOTS_TOTAL: IntGauge = register_int_gauge!(
+            "instance_pool_slots_total",
+            "Number of worker instance slots of the pooling instance allocator"
+        )
+        .unwrap();
+        static ref INSTANCE_POOL_SLOTS_IN_USE: IntGauge = register_int_gauge!(
+            "instance_pool_slots_in_use",
+            "Number of instance slots currently used by running workers"
+        )
+        .unwrap();
+        static ref INSTANCE_POOL_EXHAUSTED_TOTAL: Counter = register_counter!(
+            "instance_pool_exhausted_total",
+            "Number of times a worker could not get an instance slot immediately"
+        )
+        .unwrap();
+    }
+
+    pub fn record_instance_pool_size(size: u32) {
+        INSTANCE_POOL_SLOTS_TOTAL.set(size as i64);
+    }
+
+    pub fn record_instance_slot_acquired() {
+        INSTANCE_POOL_SLOTS_IN_USE.inc();
+    }
+
+    pub fn record_instance_slot_released() {
+        INSTANCE_POOL_SLOTS_IN_USE.dec();
+    }
+
+    pub fn record_instance_pool_exhausted() {
+        INSTANCE_POOL_EXHAUSTED_TOTAL.inc();
+    }
+
+    #[cfg(test)]
+    pub fn instance_pool_slots_total() -> i64 {
+        INSTANCE_POOL_SLOTS_TOTAL.get()
+    }
+
+    #[cfg(test)]
+    pub fn instance_pool_slots_in_use() -> i64 {
+        INSTANCE_POOL_SLOTS_IN_USE.get()
+    }
+
+    #[cfg(test)]
+    pub fn instance_pool_exhausted_total() -> f64 {
+        INSTANCE_POOL_EXHAUSTED_TOTAL.get()
+    }
+}
+
 pub mod wasm {
     use std::time::Duration;
 
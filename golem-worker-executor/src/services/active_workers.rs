@@ -19,7 +19,11 @@ use tokio::sync::{Mutex, OwnedSemaphorePermit, Semaphore, TryAcquireError};
 
 use tracing::{debug, Instrument};
 
-use crate::services::golem_config::MemoryConfig;
+use crate::metrics::instance_pool::{
+    record_instance_pool_exhausted, record_instance_pool_size, record_instance_slot_acquired,
+    record_instance_slot_released,
+};
+use crate::services::golem_config::{EngineConfig, MemoryConfig};
 use crate::services::HasAll;
 use crate::worker::Worker;
 use crate::workerctx::WorkerCtx;
@@ -32,13 +36,55 @@ use golem_service_base::error::worker_executor::WorkerExecutorError;
 pub struct ActiveWorkers<Ctx: WorkerCtx> {
     workers: Cache<WorkerId, (), Arc<Worker<Ctx>>, WorkerExecutorError>,
     worker_memory: Arc<Semaphore>,
+    /// Free instance slots of the pooling instance allocator, if it is enabled
+    instance_slots: Option<Arc<Semaphore>>,
     priority_allocation_lock: Arc<Mutex<()>>,
     acquire_retry_delay: Duration,
 }
 
+/// Permits held by a running worker: its estimated memory, and when the pooling instance
+/// allocator is used, one of the pool's instance slots
+#[derive(Debug)]
+pub struct WorkerPermit {
+    memory: OwnedSemaphorePermit,
+    _instance_slot: Option<InstanceSlot>,
+}
+
+impl WorkerPermit {
+    pub fn merge_memory(&mut self, extra_memory: OwnedSemaphorePermit) {
+        self.memory.merge(extra_memory);
+    }
+}
+
+#[derive(Debug)]
+struct InstanceSlot {
+    _permit: OwnedSemaphorePermit,
+}
+
+impl InstanceSlot {
+    fn new(permit: OwnedSemaphorePermit) -> Self {
+        record_instance_slot_acquired();
+        Self { _permit: permit }
+    }
+}
+
+impl Drop for InstanceSlot {
+    fn drop(&mut self) {
+        record_instance_slot_released();
+    }
+}
+
 impl<Ctx: WorkerCtx> ActiveWorkers<Ctx> {
-    pub fn new(memory_config: &MemoryConfig) -> Self {
+    pub fn new(memory_config: &MemoryConfig, engine_config: &EngineConfig) -> Self {
         let worker_memory_size = memory_config.worker_memory();
+        let instance_slots =
+            engine_config
+                .instance_allocation
+                .max_instances()
+                .map(|max_instances| {
+                    record_instance_pool_size(max_instances);
+                    Arc::new(Semaphore::new(max_instances as usize))
+                });
         Self {
             workers: Cache::new(
                 None,
@@ -47,6 +93,7 @@ impl<Ctx: WorkerCtx> ActiveWorkers<Ctx> {
                 "active_workers",
             ),
             worker_memory: Arc::new(Semaphore::new(worker_memory_size)),
+            instance_slots,
             acquire_retry_delay: memory_config.acquire_retry_delay,
             priority_allocation_lock: Arc::new(Mutex::new(())),
         }
@@ -109,7 +156,50 @@ impl<Ctx: WorkerCtx> ActiveWorkers<Ctx> {
         self.workers.iter().await
     }
 
-    pub async fn acquire(&self, memory: u64) -> OwnedSemaphorePermit {
+    /// Acquires everything needed to start a worker: an instance slot if the pooling allocator
+    /// is used, and the worker's estimated memory
+    pub async fn acquire(&self, memory: u64) -> WorkerPermit {
+        let instance_slot = self.acquire_instance_slot().await;
+        let memory = self.acquire_memory(memory).await;
+        WorkerPermit {
+            memory,
+            _instance_slot: instance_slot,
+        }
+    }
+
+    async fn acquire_instance_slot(&self) -> Option<InstanceSlot> {
+        let instance_slots = self.instance_slots.as_ref()?;
+
+        let mut exhausted = false;
+        loop {
+            match instance_slots.clone().try_acquire_owned() {
+                Ok(permit) => {
+                    debug!(
+                        "Acquired instance slot, available: {}",
+                        instance_slots.available_permits()
+                    );
+                    break Some(InstanceSlot::new(permit));
+                }
+                Err(TryAcquireError::Closed) => panic!("instance slot semaphore has been closed"),
+                Err(TryAcquireError::NoPermits) => {
+                    if !exhausted {
+                        record_instance_pool_exhausted();
+                        exhausted = true;
+                    }
+                    debug!("No free instance slots, trying to free one up");
+                    if self.try_free_up_instance_slot().await {
+                        debug!("Freed up an instance slot, retrying");
+                        continue;
+                    } else {
+                        debug!("Could not free up an instance slot, retrying after some time");
+                        tokio::time::sleep(self.acquire_retry_delay).await;
+                    }
+                }
+            }
+        }
+    }
+
+    async fn acquire_memory(&self, memory: u64) -> OwnedSemaphorePermit {
         let mem32: u32 = memory
             .try_into()
             .expect("requested memory size is too large");
@@ -194,30 +284,13 @@ impl<Ctx: WorkerCtx> ActiveWorkers<Ctx> {
         let needed = memory.saturating_sub(current_avail as u64);
 
         if needed > 0 {
-            let mut possibilities = Vec::new();
-
-            debug!("Collecting possibilities");
-            // Collecting the workers which are currently idle but loaded into memory
-            let pairs = self.workers.iter().await;
-            for (worker_id, worker) in pairs {
-                if worker.is_currently_idle_but_running().await {
-                    if let Ok(mem) = worker.memory_requirement().await {
-                        let last_changed = worker.last_execution_state_change();
-                        possibilities.push((worker_id, worker, mem, last_changed));
-                    }
-                }
-            }
-
-            // Sorting them by last time they changed their status - newest first
-            possibilities
-                .sort_by_key(|(_worker_id, _worker, _mem, last_changed)| last_changed.to_millis());
-            possibilities.reverse();
+            let mut possibilities = self.idle_workers().await;
 
             let mut freed = 0;
 
             // Dropping the oldest ones until we have enough memory available - rechecking the idle status before
             while freed < needed && !possibilities.is_empty() {
-                let (worker_id, worker, mem, _) = possibilities.pop().unwrap();
+                let (worker_id, worker, mem) = possibilities.pop().unwrap();
 
                 debug!("Trying to stop {worker_id} to free up memory");
                 if worker.stop_if_idle().await {
@@ -236,4 +309,225 @@ impl<Ctx: WorkerCtx> ActiveWorkers<Ctx> {
             true
         }
     }
+
+    async fn try_free_up_instance_slot(&self) -> bool {
+        let mut possibilities = self.idle_workers().await;
+
+        // Stopping the oldest idle worker - rechecking the idle status before
+        while let Some((worker_id, worker, _)) = possibilities.pop() {
+            debug!("Trying to stop {worker_id} to free up an instance slot");
+            if worker.stop_if_idle().await {
+                debug!("Stopped {worker_id} to free up an instance slot");
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Collects the workers which are currently idle but loaded into memory, with their memory
+    /// requirement. The result is ordered by the last time they changed their status, newest first.
+    async fn idle_workers(&self) -> Vec<(WorkerId, Arc<Worker<Ctx>>, u64)> {
+        let mut possibilities = Vec::new();
+
+        debug!("Collecting possibilities");
+        let pairs = self.workers.iter().await;
+        for (worker_id, worker) in pairs {
+            if worker.is_currently_idle_but_running().await {
+                if let Ok(mem) = worker.memory_requirement().await {
+                    let last_changed = worker.last_execution_state_change();
+                    possibilities.push((worker_id, worker, mem, last_changed));
+                }
+            }
+        }
+
+        possibilities
+            .sort_by_key(|(_worker_id, _worker, _mem, last_changed)| last_changed.to_millis());
+        possibilities.reverse();
+
+        possibilities
+            .into_iter()
+            .map(|(worker_id, worker, mem, _)| (worker_id, worker, mem))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+#[test_r::sequential]
+mod tests {
+    use test_r::test;
+
+    use crate::metrics::instance_pool::{
+        instance_pool_exhausted_total, instance_pool_slots_in_use, instance_pool_slots_total,
+    };
+    use crate::services::active_workers::{ActiveWorkers, WorkerPermit};
+    use crate::services::golem_config::{
+        EngineConfig, InstanceAllocationConfig, InstanceAllocationPoolingConfig, MemoryConfig,
+    };
+    use crate::workerctx::default::Context;
+    use golem_service_base::error::worker_executor::WorkerExecutorError;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    fn memory_config(system_memory: u64) -> MemoryConfig {
+        MemoryConfig {
+            system_memory_override: Some(system_memory),
+            worker_memory_ratio: 0.5,
+            acquire_retry_delay: Duration::from_millis(10),
+            ..MemoryConfig::default()
+        }
+    }
+
+    fn pooling_engine_config(total_component_instances: u32) -> EngineConfig {
+        EngineConfig {
+            enable_fs_cache: false,
+            instance_allocation: InstanceAllocationConfig::Pooling(
+                InstanceAllocationPoolingConfig {
+                    total_component_instances,
+                    ..InstanceAllocationPoolingConfig::default()
+                },
+            ),
+        }
+    }
+
+    fn available_slots(active_workers: &ActiveWorkers<Context>) -> usize {
+        active_workers
+            .instance_slots
+            .as_ref()
+            .expect("pooling allocator should have instance slots")
+            .available_permits()
+    }
+
+    async fn failing_instantiation(_permit: WorkerPermit) -> Result<(), WorkerExecutorError> {
+        Err(WorkerExecutorError::unknown("instantiation failed"))
+    }
+
+    #[test]
+    async fn on_demand_allocation_has_no_instance_slots() {
+        let active_workers = ActiveWorkers::<Context>::new(
+            &memory_config(1000),
+            &EngineConfig {
+                enable_fs_cache: false,
+                instance_allocation: InstanceAllocationConfig::default(),
+            },
+        );
+
+        let permit = active_workers.acquire(100).await;
+
+        assert!(active_workers.instance_slots.is_none());
+        assert!(permit._instance_slot.is_none());
+        assert_eq!(active_workers.worker_memory.available_permits(), 400);
+    }
+
+    #[test]
+    async fn acquire_takes_an_instance_slot_and_dropping_the_permit_releases_it() {
+        let active_workers =
+            ActiveWorkers::<Context>::new(&memory_config(1000), &pooling_engine_config(2));
+        let in_use_before = instance_pool_slots_in_use();
+
+        assert_eq!(instance_pool_slots_total(), 2);
+        assert_eq!(available_slots(&active_workers), 2);
+
+        let first = active_workers.acquire(100).await;
+        let second = active_workers.acquire(100).await;
+
+        assert_eq!(available_slots(&active_workers), 0);
+        assert_eq!(instance_pool_slots_in_use(), in_use_before + 2);
+
+        drop(first);
+
+        assert_eq!(available_slots(&active_workers), 1);
+        assert_eq!(instance_pool_slots_in_use(), in_use_before + 1);
+
+        drop(second);
+
+        assert_eq!(available_slots(&active_workers), 2);
+        assert_eq!(instance_pool_slots_in_use(), in_use_before);
+    }
+
+    #[test]
+    async fn failed_instantiation_releases_the_instance_slot_and_memory() {
+        let active_workers =
+            ActiveWorkers::<Context>::new(&memory_config(1000), &pooling_engine_config(1));
+        let in_use_before = instance_pool_slots_in_use();
+
+        let permit = active_workers.acquire(300).await;
+        assert_eq!(available_slots(&active_workers), 0);
+        assert_eq!(active_workers.worker_memory.available_permits(), 200);
+
+        let result = failing_instantiation(permit).await;
+
+        assert!(result.is_err());
+        assert_eq!(available_slots(&active_workers), 1);
+        assert_eq!(active_workers.worker_memory.available_permits(), 500);
+        assert_eq!(instance_pool_slots_in_use(), in_use_before);
+
+        // The released slot can be used by the next worker
+        let _permit = active_workers.acquire(300).await;
+        assert_eq!(available_slots(&active_workers), 0);
+        assert_eq!(instance_pool_slots_in_use(), in_use_before + 1);
+    }
+
+    #[test]
+    async fn instance_slots_and_memory_are_accounted_separately() {
+        let active_workers =
+            ActiveWorkers::<Context>::new(&memory_config(1000), &pooling_engine_config(2));
+
+        assert_eq!(active_workers.worker_memory.available_permits(), 500);
+
+        let mut permit = active_workers.acquire(400).await;
+
+        assert_eq!(active_workers.worker_memory.available_permits(), 100);
+        assert_eq!(available_slots(&active_workers), 1);
+        assert!(active_workers.try_acquire(200).await.is_none());
+
+        // Growing a running worker takes more memory but no extra instance slot
+        let extra = active_workers.try_acquire(100).await.unwrap();
+        permit.merge_memory(extra);
+
+        assert_eq!(active_workers.worker_memory.available_permits(), 0);
+        assert_eq!(available_slots(&active_workers), 1);
+
+        drop(permit);
+
+        assert_eq!(active_workers.worker_memory.available_permits(), 500);
+        assert_eq!(available_slots(&active_workers), 2);
+
+        // Using up the instance slots leaves the remaining memory available
+        let _first = active_workers.acquire(100).await;
+        let _second = active_workers.acquire(100).await;
+
+        assert_eq!(available_slots(&active_workers), 0);
+        assert_eq!(active_workers.worker_memory.available_permits(), 300);
+    }
+
+    #[test]
+    async fn exhausted_pool_waits_for_a_released_slot() {
+        let active_workers = Arc::new(ActiveWorkers::<Context>::new(
+            &memory_config(1000),
+            &pooling_engine_config(1),
+        ));
+        let exhausted_before = instance_pool_exhausted_total();
+        let in_use_before = instance_pool_slots_in_use();
+
+        let first = active_workers.acquire(100).await;
+
+        let active_workers_clone = active_workers.clone();
+        let waiting = tokio::spawn(async move { active_workers_clone.acquire(100).await });
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(!waiting.is_finished());
+        assert_eq!(instance_pool_exhausted_total(), exhausted_before + 1.0);
+        assert_eq!(instance_pool_slots_in_use(), in_use_before + 1);
+
+        drop(first);
+
+        let second = waiting.await.unwrap();
+        assert_eq!(available_slots(&active_workers), 0);
+        assert_eq!(instance_pool_slots_in_use(), in_use_before + 1);
+        // Retrying while waiting for the slot counts as a single exhaustion
+        assert_eq!(instance_pool_exhausted_total(), exhausted_before + 1.0);
+
+        drop(second);
+        assert_eq!(instance_pool_slots_in_use(), in_use_before);
+    }
 }
@@ -12,16 +12,23 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::services::golem_config::CompiledComponentServiceConfig;
+use crate::services::golem_config::{
+    CompiledComponentServiceConfig, ComponentDiskCacheConfig, ComponentDiskCacheEnabledConfig,
+};
 use crate::Engine;
 use async_trait::async_trait;
+use golem_common::model::component_metadata::ComponentCompilationOptions;
 use golem_common::model::{ComponentId, ProjectId};
 use golem_service_base::error::worker_executor::WorkerExecutorError;
 use golem_service_base::storage::blob::{BlobStorage, BlobStorageNamespace};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+use tokio::task::spawn_blocking;
 use tokio::time::Instant;
-use tracing::{debug, info_span};
+use tracing::{debug, info, info_span, warn};
+use uuid::Uuid;
 use wasmtime::component::Component;
 
 /// Service for storing compiled native binaries of WebAssembly components
@@ -32,6 +39,7 @@ pub trait CompiledComponentService: Send + Sync {
         project_id: &ProjectId,
         component_id: &ComponentId,
         component_version: u64,
+        compilation_options: &ComponentCompilationOptions,
         engine: &Engine,
     ) -> Result<Option<Component>, WorkerExecutorError>;
     async fn put(
@@ -39,6 +47,7 @@ pub trait CompiledComponentService: Send + Sync {
         project_id: &ProjectId,
         component_id: &ComponentId,
         component_version: u64,
+        compilation_options: &ComponentCompilationOptions,
         component: &Component,
     ) -> Result<(), WorkerExecutorError>;
 }
@@ -52,8 +61,19 @@ impl DefaultCompiledComponentService {
         Self { blob_storage }
     }
 
-    fn key(component_id: &ComponentId, component_version: u64) -> PathBuf {
-        Path::new(&component_id.to_string()).join(format!("{component_version}.cwasm"))
+    /// Components compiled with a non-default optimization level or interruption get the
+    /// fingerprint of the options in their key, so the keys of the components compiled with the
+    /// defaults do not change.
+    fn key(
+        component_id: &ComponentId,
+        component_version: u64,
+        compilation_options: &ComponentCompilationOptions,
+    ) -> PathBuf {
+        let file_name = match compilation_options.fingerprint() {
+            Some(fingerprint) => format!("{component_version}-{fingerprint}.cwasm"),
+            None => format!("{component_version}.cwasm"),
+        };
+        Path::new(&component_id.to_string()).join(file_name)
     }
 }
 
@@ -64,6 +84,7 @@ impl CompiledComponentService for DefaultCompiledComponentService {
         project_id: &ProjectId,
         component_id: &ComponentId,
         component_version: u64,
+        compilation_options: &ComponentCompilationOptions,
         engine: &Engine,
     ) -> Result<Option<Component>, WorkerExecutorError> {
         match self
@@ -74,7 +95,7 @@ impl CompiledComponentService for DefaultCompiledComponentService {
                 BlobStorageNamespace::CompilationCache {
                     project_id: project_id.clone(),
                 },
-                &Self::key(component_id, component_version),
+                &Self::key(component_id, component_version, compilation_options),
             )
             .await
         {
@@ -120,6 +141,7 @@ impl CompiledComponentService for DefaultCompiledComponentService {
         project_id: &ProjectId,
         component_id: &ComponentId,
         component_version: u64,
+        compilation_options: &ComponentCompilationOptions,
         component: &Component,
     ) -> Result<(), WorkerExecutorError> {
         let bytes = component
@@ -132,7 +154,7 @@ impl CompiledComponentService for DefaultCompiledComponentService {
                 BlobStorageNamespace::CompilationCache {
                     project_id: project_id.clone(),
                 },
-                &Self::key(component_id, component_version),
+                &Self::key(component_id, component_version, compilation_options),
                 bytes,
             )
             .await
@@ -160,6 +182,284 @@ pub fn configured(
     }
 }
 
+/// Wraps a compiled component service with an executor-local on-disk cache, so compiled
+/// components are only downloaded (or compiled) once per executor. The least recently used
+/// entries are evicted when the total size of the cache exceeds its limit.
+pub fn with_disk_cache(
+    compiled_component_service: Arc<dyn CompiledComponentService>,
+    config: &ComponentDiskCacheConfig,
+) -> Arc<dyn CompiledComponentService> {
+    match config {
+        ComponentDiskCacheConfig::Enabled(config) => Arc::new(
+            DiskCachedCompiledComponentService::new(compiled_component_service, config),
+        ),
+        ComponentDiskCacheConfig::Disabled(_) => compiled_component_service,
+    }
+}
+
+pub struct DiskCachedCompiledComponentService {
+    inner: Arc<dyn CompiledComponentService>,
+    root: PathBuf,
+    index: Mutex<DiskCacheIndex>,
+}
+
+impl DiskCachedCompiledComponentService {
+    pub fn new(
+        inner: Arc<dyn CompiledComponentService>,
+        config: &ComponentDiskCacheEnabledConfig,
+    ) -> Self {
+        let mut index = DiskCacheIndex::new(config.max_size);
+        let mut entries = Vec::new();
+        if let Err(err) = Self::scan(&config.root, &mut entries) {
+            warn!(
+                "Failed to scan the compiled component cache at {:?}: {err}",
+                config.root
+            );
+        }
+        entries.sort_by_key(|(_, _, modified)| *modified);
+        for (path, size, _) in entries {
+            Self::remove_files(index.insert(path, size));
+        }
+        info!(
+            "Using compiled component cache at {:?} with {} entries ({} bytes)",
+            config.root,
+            index.entries.len(),
+            index.total_size
+        );
+
+        Self {
+            inner,
+            root: config.root.clone(),
+            index: Mutex::new(index),
+        }
+    }
+
+    /// Collects the cached compiled components left by a previous run of the executor, and
+    /// deletes the temporary files of interrupted writes
+    fn scan(dir: &Path, entries: &mut Vec<(PathBuf, u64, SystemTime)>) -> std::io::Result<()> {
+        if !dir.exists() {
+            return Ok(());
+        }
+        for entry in std::fs::read_dir(dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            let metadata = entry.metadata()?;
+            if metadata.is_dir() {
+                Self::scan(&path, entries)?;
+            } else if path.extension().is_some_and(|ext| ext == "cwasm") {
+                let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+                entries.push((path, metadata.len(), modified));
+            } else if path.extension().is_some_and(|ext| ext == "tmp") {
+                let _ = std::fs::remove_file(&path);
+            }
+        }
+        Ok(())
+    }
+
+    fn path(
+        &self,
+        project_id: &ProjectId,
+        component_id: &ComponentId,
+        component_version: u64,
+        compilation_options: &ComponentCompilationOptions,
+    ) -> PathBuf {
+        self.root
+            .join(project_id.to_string())
+            .join(DefaultCompiledComponentService::key(
+                component_id,
+                component_version,
+                compilation_options,
+            ))
+    }
+
+    async fn load(engine: &Engine, path: &Path) -> anyhow::Result<Component> {
+        let engine = engine.clone();
+        let path = path.to_path_buf();
+        spawn_blocking(move || {
+            let span = info_span!("Loading cached precompiled WASM component");
+            let _enter = span.enter();
+            unsafe { Component::deserialize_file(&engine, &path) }
+        })
+        .await?
+    }
+
+    async fn store(&self, path: &Path, component: &Component) -> anyhow::Result<()> {
+        let bytes = component.serialize()?;
+        let size = bytes.len() as u64;
+
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        // Writing to a temporary file first, so concurrent readers never see partial entries
+        let temp_path = path.with_extension(format!("{}.tmp", Uuid::new_v4()));
+        tokio::fs::write(&temp_path, bytes).await?;
+        if let Err(err) = tokio::fs::rename(&temp_path, path).await {
+            let _ = tokio::fs::remove_file(&temp_path).await;
+            return Err(err.into());
+        }
+
+        let evicted = self.index.lock().unwrap().insert(path.to_path_buf(), size);
+        Self::remove_files(evicted);
+        Ok(())
+    }
+
+    fn remove_files(paths: Vec<PathBuf>) {
+        for path in paths {
+            debug!("Evicting compiled component {:?} from the cache", path);
+            let _ = std::fs::remove_file(&path);
+        }
+    }
+}
+
+#[async_trait]
+impl CompiledComponentService for DiskCachedCompiledComponentService {
+    async fn get(
+        &self,
+        project_id: &ProjectId,
+        component_id: &ComponentId,
+        component_version: u64,
+        compilation_options: &ComponentCompilationOptions,
+        engine: &Engine,
+    ) -> Result<Option<Component>, WorkerExecutorError> {
+        let path = self.path(
+            project_id,
+            component_id,
+            component_version,
+            compilation_options,
+        );
+
+        if self.index.lock().unwrap().touch(&path) {
+            match Self::load(engine, &path).await {
+                Ok(component) => return Ok(Some(component)),
+                Err(err) => {
+                    warn!("Failed to load cached compiled component {:?}: {err}", path);
+                    self.index.lock().unwrap().remove(&path);
+                    let _ = tokio::fs::remove_file(&path).await;
+                }
+            }
+        }
+
+        let component = self
+            .inner
+            .get(
+                project_id,
+                component_id,
+                component_version,
+                compilation_options,
+                engine,
+            )
+            .await?;
+        if let Some(component) = &component {
+            if let Err(err) = self.store(&path, component).await {
+                warn!("Failed to cache compiled component {:?}: {err}", path);
+            }
+        }
+        Ok(component)
+    }
+
+    async fn put(
+        &self,
+        project_id: &ProjectId,
+        component_id: &ComponentId,
+        component_version: u64,
+        compilation_options: &ComponentCompilationOptions,
+        component: &Component,
+    ) -> Result<(), WorkerExecutorError> {
+        let path = self.path(
+            project_id,
+            component_id,
+            component_version,
+            compilation_options,
+        );
+        if let Err(err) = self.store(&path, component).await {
+            warn!("Failed to cache compiled component {:?}: {err}", path);
+        }
+        self.inner
+            .put(
+                project_id,
+                component_id,
+                component_version,
+                compilation_options,
+                component,
+            )
+            .await
+    }
+}
+
+/// Sizes and recency of the entries of the on-disk compiled component cache
+struct DiskCacheIndex {
+    max_size: u64,
+    total_size: u64,
+    last_access: u64,
+    entries: HashMap<PathBuf, DiskCacheEntry>,
+}
+
+struct DiskCacheEntry {
+    size: u64,
+    last_access: u64,
+}
+
+impl DiskCacheIndex {
+    fn new(max_size: u64) -> Self {
+        Self {
+            max_size,
+            total_size: 0,
+            last_access: 0,
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Marks the entry as most recently used, returns false if it is not cached
+    fn touch(&mut self, path: &Path) -> bool {
+        self.last_access += 1;
+        match self.entries.get_mut(path) {
+            Some(entry) => {
+                entry.last_access = self.last_access;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Adds a new entry and returns the paths of the evicted least recently used entries.
+    /// An entry larger than the limit is evicted immediately.
+    fn insert(&mut self, path: PathBuf, size: u64) -> Vec<PathBuf> {
+        self.last_access += 1;
+        let previous = self.entries.insert(
+            path,
+            DiskCacheEntry {
+                size,
+                last_access: self.last_access,
+            },
+        );
+        if let Some(previous) = previous {
+            self.total_size -= previous.size;
+        }
+        self.total_size += size;
+
+        let mut evicted = Vec::new();
+        while self.total_size > self.max_size {
+            let Some(path) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_access)
+                .map(|(path, _)| path.clone())
+            else {
+                break;
+            };
+            self.remove(&path);
+            evicted.push(path);
+        }
+        evicted
+    }
+
+    fn remove(&mut self, path: &Path) {
+        if let Some(entry) = self.entries.remove(path) {
+            self.total_size -= entry.size;
+        }
+    }
+}
+
 pub struct CompiledComponentServiceDisabled {}
 
 impl Default for CompiledComponentServiceDisabled {
@@ -181,6 +481,7 @@ impl CompiledComponentService for CompiledComponentServiceDisabled {
         _project_id: &ProjectId,
         _component_id: &ComponentId,
         _component_version: u64,
+        _compilation_options: &ComponentCompilationOptions,
         _engine: &Engine,
     ) -> Result<Option<Component>, WorkerExecutorError> {
         Ok(None)
@@ -191,8 +492,47 @@ impl CompiledComponentService for CompiledComponentServiceDisabled {
         _project_id: &ProjectId,
         _component_id: &ComponentId,
         _component_version: u64,
+        _compilation_options: &ComponentCompilationOptions,
         _component: &Component,
     ) -> Result<(), WorkerExecutorError> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::DiskCacheIndex;
+    use std::path::PathBuf;
+    use test_r::test;
+
+    #[test]
+    fn disk_cache_index_evicts_least_recently_used_entries() {
+        let mut index = DiskCacheIndex::new(100);
+        assert!(index.insert(PathBuf::from("a"), 40).is_empty());
+        assert!(index.insert(PathBuf::from("b"), 40).is_empty());
+        assert!(index.touch(&PathBuf::from("a")));
+
+        let evicted = index.insert(PathBuf::from("c"), 40);
+        assert_eq!(evicted, vec![PathBuf::from("b")]);
+        assert_eq!(index.total_size, 80);
+        assert!(!index.touch(&PathBuf::from("b")));
+    }
+
+    #[test]
+    fn disk_cache_index_does_not_keep_entries_larger_than_the_limit() {
+        let mut index = DiskCacheIndex::new(100);
+        assert!(index.insert(PathBuf::from("a"), 40).is_empty());
+
+        let evicted = index.insert(PathBuf::from("b"), 120);
+        assert_eq!(evicted, vec![PathBuf::from("a"), PathBuf::from("b")]);
+        assert_eq!(index.total_size, 0);
+    }
+
+    #[test]
+    fn disk_cache_index_replaces_existing_entries() {
+        let mut index = DiskCacheIndex::new(100);
+        assert!(index.insert(PathBuf::from("a"), 40).is_empty());
+        assert!(index.insert(PathBuf::from("a"), 60).is_empty());
+        assert_eq!(index.total_size, 60);
+    }
+}
@@ -68,8 +68,10 @@ pub fn configured(
     plugin_observations: Arc<dyn PluginsObservations>,
     project_service: Arc<dyn ProjectService>,
 ) -> Arc<dyn ComponentService> {
-    let compiled_component_service =
-        super::compiled_component::configured(compiled_config, blob_storage);
+    let compiled_component_service = super::compiled_component::with_disk_cache(
+        super::compiled_component::configured(compiled_config, blob_storage),
+        &cache_config.disk,
+    );
     match config {
         ComponentServiceConfig::Grpc(config) => {
             info!("Using component API at {}", config.url());
@@ -135,6 +137,7 @@ mod filesystem {
     use golem_common::cache::Cache;
     use golem_common::cache::SimpleCache;
     use golem_common::model::component::ComponentOwner;
+    use golem_common::model::component_metadata::ComponentCompilationOptions;
     use golem_common::model::{ComponentId, ComponentVersion, ProjectId};
     use golem_service_base::error::worker_executor::WorkerExecutorError;
     use golem_service_base::testing::LocalFileSystemComponentMetadata;
@@ -254,6 +257,7 @@ mod filesystem {
             project_id: &ProjectId,
             component_id: &ComponentId,
             component_version: ComponentVersion,
+            compilation_options: &ComponentCompilationOptions,
         ) -> Result<Component, WorkerExecutorError> {
             let key = ComponentKey {
                 component_id: component_id.clone(),
@@ -263,12 +267,19 @@ mod filesystem {
             let engine = engine.clone();
             let compiled_component_service = self.compiled_component_service.clone();
             let path = wasm_path.to_path_buf();
+            let compilation_options = compilation_options.clone();
 
             self.component_cache
                 .get_or_insert_simple(&key.clone(), || {
                     Box::pin(async move {
                         let result = compiled_component_service
-                            .get(project_id, &component_id, component_version, &engine)
+                            .get(
+                                project_id,
+                                &component_id,
+                                component_version,
+                                &compilation_options,
+                                &engine,
+                            )
                             .await;
 
                         let component = match result {
@@ -313,7 +324,13 @@ mod filesystem {
                                 );
 
                                 let result = compiled_component_service
-                                    .put(project_id, &component_id, component_version, &component)
+                                    .put(
+                                        project_id,
+                                        &component_id,
+                                        component_version,
+                                        &compilation_options,
+                                        &component,
+                                    )
                                     .await;
 
                                 match result {
@@ -413,18 +430,20 @@ mod filesystem {
             };
 
             let wasm_path = self.root.join(metadata.wasm_filename.clone());
+            let metadata: golem_service_base::model::Component = metadata.into();
 
             let component = self
                 .get_component_from_path(
                     &wasm_path,
                     engine,
-                    &metadata.project_id,
+                    &metadata.owner.project_id,
                     component_id,
                     component_version,
+                    metadata.metadata.compilation_options(),
                 )
                 .await?;
 
-            Ok((component, metadata.into()))
+            Ok((component, metadata))
         }
 
         async fn get_metadata(
@@ -681,6 +700,7 @@ mod grpc {
                 .get_metadata(component_id, Some(component_version))
                 .await?;
             let project_id_clone = metadata.owner.project_id.clone();
+            let compilation_options = metadata.metadata.compilation_options().clone();
 
             let component = self
                 .component_cache
@@ -691,6 +711,7 @@ mod grpc {
                                 &project_id_clone,
                                 &component_id_clone,
                                 component_version,
+                                &compilation_options,
                                 &engine,
                             )
                             .await;
@@ -747,6 +768,7 @@ mod grpc {
                                         &project_id_clone,
                                         &component_id_clone,
                                         component_version,
+                                        &compilation_options,
                                         &component,
                                     )
                                     .await;
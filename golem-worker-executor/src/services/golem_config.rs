@@ -60,6 +60,10 @@ pub struct GolemConfig {
     pub port: u16,
     pub http_address: String,
     pub http_port: u16,
+    /// Address of the admin HTTP server serving the drain endpoints
+    pub admin_http_address: String,
+    /// Port of the admin HTTP server, which is only started if it is set
+    pub admin_http_port: Option<u16>,
 }
 
 impl SafeDisplay for GolemConfig {
@@ -173,6 +177,12 @@ impl SafeDisplay for GolemConfig {
         let _ = writeln!(&mut result, "gRPC port: {}", self.port);
         let _ = writeln!(&mut result, "HTTP address: {}", self.http_address);
         let _ = writeln!(&mut result, "HTTP port: {}", self.http_port);
+        let _ = writeln!(
+            &mut result,
+            "admin HTTP address: {}",
+            self.admin_http_address
+        );
+        let _ = writeln!(&mut result, "admin HTTP port: {:?}", self.admin_http_port);
 
         result
     }
@@ -186,6 +196,9 @@ pub struct Limits {
     pub event_broadcast_capacity: usize,
     pub event_history_size: usize,
     pub fuel_to_borrow: i64,
+    /// Fuel charged every time a component compiled with epoch based interruption reaches its
+    /// epoch deadline, as such components do not consume fuel
+    pub epoch_fuel: i64,
     #[serde(with = "humantime_serde")]
     pub epoch_interval: Duration,
     pub epoch_ticks: u64,
@@ -222,6 +235,7 @@ impl SafeDisplay for Limits {
             self.event_history_size
         );
         let _ = writeln!(&mut result, "fuel to borrow: {}", self.fuel_to_borrow);
+        let _ = writeln!(&mut result, "epoch fuel: {}", self.epoch_fuel);
         let _ = writeln!(
             &mut result,
             "epoch interval: {}",
@@ -348,6 +362,11 @@ pub struct ShardManagerServiceGrpcConfig {
     pub host: String,
     pub port: u16,
     pub retries: RetryConfig,
+    /// How long draining this executor waits for the shard manager to reassign all its shards
+    #[serde(with = "humantime_serde")]
+    pub drain_timeout: Duration,
+    #[serde(with = "humantime_serde")]
+    pub drain_poll_interval: Duration,
 }
 
 impl SafeDisplay for ShardManagerServiceGrpcConfig {
@@ -355,6 +374,12 @@ impl SafeDisplay for ShardManagerServiceGrpcConfig {
         let mut result = String::new();
         let _ = writeln!(&mut result, "host: {}", self.host);
         let _ = writeln!(&mut result, "port: {}", self.port);
+        let _ = writeln!(&mut result, "drain timeout: {:?}", self.drain_timeout);
+        let _ = writeln!(
+            &mut result,
+            "drain poll interval: {:?}",
+            self.drain_poll_interval
+        );
         let _ = writeln!(&mut result, "retries:",);
         let _ = writeln!(&mut result, "{}", self.retries.to_safe_string_indented());
         result
@@ -410,6 +435,19 @@ impl GolemConfig {
         ))
     }
 
+    pub fn admin_http_addr(&self) -> anyhow::Result<Option<SocketAddrV4>> {
+        self.admin_http_port
+            .map(|admin_http_port| {
+                Ok(SocketAddrV4::new(
+                    self.admin_http_address
+                        .parse::<Ipv4Addr>()
+                        .context("admin_http_address configuration")?,
+                    admin_http_port,
+                ))
+            })
+            .transpose()
+    }
+
     pub fn add_port_to_tracing_file_name_if_enabled(&mut self) {
         if self.tracing_file_name_with_port {
             if let Some(file_name) = &self.tracing.file_name {
@@ -828,6 +866,7 @@ pub struct ComponentCacheConfig {
     pub max_resolved_component_capacity: usize,
     #[serde(with = "humantime_serde")]
     pub time_to_idle: Duration,
+    pub disk: ComponentDiskCacheConfig,
 }
 
 impl SafeDisplay for ComponentCacheConfig {
@@ -845,10 +884,64 @@ impl SafeDisplay for ComponentCacheConfig {
             self.max_resolved_component_capacity
         );
         let _ = writeln!(&mut result, "time to idle: {:?}", self.time_to_idle);
+        let _ = writeln!(&mut result, "disk:");
+        let _ = writeln!(&mut result, "{}", self.disk.to_safe_string_indented());
         result
     }
 }
 
+/// Executor-local on-disk LRU cache of compiled components, in front of the compiled component
+/// service. When enabled, the cache is also warmed with the components of the running workers of
+/// newly assigned shards, before these workers get recovered.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", content = "config")]
+pub enum ComponentDiskCacheConfig {
+    Enabled(ComponentDiskCacheEnabledConfig),
+    Disabled(ComponentDiskCacheDisabledConfig),
+}
+
+impl SafeDisplay for ComponentDiskCacheConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        match self {
+            ComponentDiskCacheConfig::Enabled(enabled) => {
+                let _ = writeln!(&mut result, "enabled:");
+                let _ = writeln!(&mut result, "{}", enabled.to_safe_string_indented());
+            }
+            ComponentDiskCacheConfig::Disabled(_) => {
+                let _ = writeln!(&mut result, "disabled");
+            }
+        }
+        result
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ComponentDiskCacheEnabledConfig {
+    pub root: PathBuf,
+    /// Maximum total size of the cached compiled components in bytes
+    pub max_size: u64,
+    /// Maximum number of components loaded in parallel when warming the cache on shard assignment
+    pub warm_up_concurrency: usize,
+}
+
+impl SafeDisplay for ComponentDiskCacheEnabledConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        let _ = writeln!(&mut result, "root: {:?}", self.root);
+        let _ = writeln!(&mut result, "max size: {}", self.max_size);
+        let _ = writeln!(
+            &mut result,
+            "warm up concurrency: {}",
+            self.warm_up_concurrency
+        );
+        result
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ComponentDiskCacheDisabledConfig {}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(tag = "type", content = "config")]
 pub enum ProjectServiceConfig {
@@ -1076,12 +1169,165 @@ impl SafeDisplay for ComponentServiceGrpcConfig {
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 pub struct EngineConfig {
     pub enable_fs_cache: bool,
+    pub instance_allocation: InstanceAllocationConfig,
 }
 
 impl SafeDisplay for EngineConfig {
     fn to_safe_string(&self) -> String {
         let mut result = String::new();
         let _ = writeln!(&mut result, "enable fs cache: {}", self.enable_fs_cache);
+        let _ = writeln!(&mut result, "instance allocation:");
+        let _ = writeln!(
+            &mut result,
+            "{}",
+            self.instance_allocation.to_safe_string_indented()
+        );
+        result
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", content = "config")]
+pub enum InstanceAllocationConfig {
+    OnDemand(InstanceAllocationOnDemandConfig),
+    Pooling(InstanceAllocationPoolingConfig),
+}
+
+impl InstanceAllocationConfig {
+    /// Maximum number of workers that can be instantiated at the same time, if limited by the allocator
+    pub fn max_instances(&self) -> Option<u32> {
+        match self {
+            InstanceAllocationConfig::OnDemand(_) => None,
+            InstanceAllocationConfig::Pooling(pooling) => Some(pooling.total_component_instances),
+        }
+    }
+}
+
+impl Default for InstanceAllocationConfig {
+    fn default() -> Self {
+        Self::OnDemand(InstanceAllocationOnDemandConfig {})
+    }
+}
+
+impl SafeDisplay for InstanceAllocationConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        match self {
+            InstanceAllocationConfig::OnDemand(inner) => {
+                let _ = writeln!(&mut result, "on-demand:");
+                let _ = writeln!(&mut result, "{}", inner.to_safe_string_indented());
+            }
+            InstanceAllocationConfig::Pooling(inner) => {
+                let _ = writeln!(&mut result, "pooling:");
+                let _ = writeln!(&mut result, "{}", inner.to_safe_string_indented());
+            }
+        }
+        result
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InstanceAllocationOnDemandConfig {}
+
+impl SafeDisplay for InstanceAllocationOnDemandConfig {
+    fn to_safe_string(&self) -> String {
+        "".to_string()
+    }
+}
+
+/// Configuration of wasmtime's pooling instance allocator.
+///
+/// The pool is only used by the engine of the default engine profile, components compiled with
+/// other options are instantiated on demand. The number of concurrently instantiated workers is
+/// still limited to `total_component_instances`, whichever engine they use.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InstanceAllocationPoolingConfig {
+    pub total_component_instances: u32,
+    pub total_core_instances: u32,
+    pub total_memories: u32,
+    pub total_tables: u32,
+    pub total_stacks: u32,
+    pub max_memory_size: usize,
+    pub table_elements: usize,
+    pub max_core_instances_per_component: u32,
+    pub max_memories_per_component: u32,
+    pub max_tables_per_component: u32,
+    pub max_unused_warm_slots: u32,
+    pub linear_memory_keep_resident: usize,
+    pub memory_init_cow: bool,
+}
+
+impl InstanceAllocationPoolingConfig {
+    /// The total size of the linear memories the pool can hold
+    pub fn total_memory_size(&self) -> u64 {
+        self.total_memories as u64 * self.max_memory_size as u64
+    }
+}
+
+impl Default for InstanceAllocationPoolingConfig {
+    fn default() -> Self {
+        Self {
+            total_component_instances: 1000,
+            total_core_instances: 10000,
+            total_memories: 1000,
+            total_tables: 10000,
+            total_stacks: 1000,
+            max_memory_size: 512 * 1024 * 1024,
+            table_elements: 20000,
+            max_core_instances_per_component: 50,
+            max_memories_per_component: 10,
+            max_tables_per_component: 50,
+            max_unused_warm_slots: 100,
+            linear_memory_keep_resident: 0,
+            memory_init_cow: true,
+        }
+    }
+}
+
+impl SafeDisplay for InstanceAllocationPoolingConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        let _ = writeln!(
+            &mut result,
+            "total component instances: {}",
+            self.total_component_instances
+        );
+        let _ = writeln!(
+            &mut result,
+            "total core instances: {}",
+            self.total_core_instances
+        );
+        let _ = writeln!(&mut result, "total memories: {}", self.total_memories);
+        let _ = writeln!(&mut result, "total tables: {}", self.total_tables);
+        let _ = writeln!(&mut result, "total stacks: {}", self.total_stacks);
+        let _ = writeln!(&mut result, "max memory size: {}", self.max_memory_size);
+        let _ = writeln!(&mut result, "table elements: {}", self.table_elements);
+        let _ = writeln!(
+            &mut result,
+            "max core instances per component: {}",
+            self.max_core_instances_per_component
+        );
+        let _ = writeln!(
+            &mut result,
+            "max memories per component: {}",
+            self.max_memories_per_component
+        );
+        let _ = writeln!(
+            &mut result,
+            "max tables per component: {}",
+            self.max_tables_per_component
+        );
+        let _ = writeln!(
+            &mut result,
+            "max unused warm slots: {}",
+            self.max_unused_warm_slots
+        );
+        let _ = writeln!(
+            &mut result,
+            "linear memory keep resident: {}",
+            self.linear_memory_keep_resident
+        );
+        let _ = writeln!(&mut result, "memory init cow: {}", self.memory_init_cow);
         result
     }
 }
@@ -1116,6 +1362,8 @@ impl Default for GolemConfig {
             port: 9000,
             http_address: "0.0.0.0".to_string(),
             http_port: 8082,
+            admin_http_address: "127.0.0.1".to_string(),
+            admin_http_port: None,
         }
     }
 }
@@ -1150,6 +1398,18 @@ impl HasConfigExamples<GolemConfig> for GolemConfig {
                     ..Self::default()
                 },
             ),
+            (
+                "with pooling instance allocator",
+                Self {
+                    engine: EngineConfig {
+                        instance_allocation: InstanceAllocationConfig::Pooling(
+                            InstanceAllocationPoolingConfig::default(),
+                        ),
+                        ..EngineConfig::default()
+                    },
+                    ..Self::default()
+                },
+            ),
         ]
     }
 }
@@ -1163,6 +1423,7 @@ impl Default for Limits {
             event_broadcast_capacity: 1024,
             event_history_size: 128,
             fuel_to_borrow: 10000,
+            epoch_fuel: 10000000,
             epoch_interval: Duration::from_millis(10),
             epoch_ticks: 1,
             max_oplog_query_pages_size: 100,
@@ -1217,6 +1478,8 @@ impl Default for ShardManagerServiceGrpcConfig {
             host: "localhost".to_string(),
             port: 9002,
             retries: RetryConfig::default(),
+            drain_timeout: Duration::from_secs(120),
+            drain_poll_interval: Duration::from_secs(1),
         }
     }
 }
@@ -1344,6 +1607,23 @@ impl Default for ComponentCacheConfig {
             max_metadata_capacity: 16384,
             max_resolved_component_capacity: 1024,
             time_to_idle: Duration::from_secs(12 * 60 * 60),
+            disk: ComponentDiskCacheConfig::default(),
+        }
+    }
+}
+
+impl Default for ComponentDiskCacheConfig {
+    fn default() -> Self {
+        Self::Disabled(ComponentDiskCacheDisabledConfig {})
+    }
+}
+
+impl Default for ComponentDiskCacheEnabledConfig {
+    fn default() -> Self {
+        Self {
+            root: PathBuf::from("../data/compiled_component_cache"),
+            max_size: 10 * 1024 * 1024 * 1024,
+            warm_up_concurrency: 8,
         }
     }
 }
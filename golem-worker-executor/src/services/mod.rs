@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::engines::ComponentEngines;
 use crate::services::worker_activator::WorkerActivator;
 use std::sync::Arc;
 
@@ -111,8 +112,7 @@ pub trait HasPromiseService {
 }
 
 pub trait HasWasmtimeEngine<Ctx> {
-    fn engine(&self) -> Arc<wasmtime::Engine>;
-    fn linker(&self) -> Arc<wasmtime::component::Linker<Ctx>>;
+    fn engines(&self) -> Arc<ComponentEngines<Ctx>>;
     fn runtime(&self) -> Handle;
 }
 
@@ -258,8 +258,7 @@ impl<
 pub struct All<Ctx: WorkerCtx> {
     active_workers: Arc<active_workers::ActiveWorkers<Ctx>>,
     agent_types: Arc<dyn agent_types::AgentTypesService>,
-    engine: Arc<wasmtime::Engine>,
-    linker: Arc<wasmtime::component::Linker<Ctx>>,
+    engines: Arc<ComponentEngines<Ctx>>,
     runtime: Handle,
     component_service: Arc<dyn component::ComponentService>,
     shard_manager_service: Arc<dyn shard_manager::ShardManagerService>,
@@ -293,8 +292,7 @@ impl<Ctx: WorkerCtx> Clone for All<Ctx> {
         Self {
             active_workers: self.active_workers.clone(),
             agent_types: self.agent_types.clone(),
-            engine: self.engine.clone(),
-            linker: self.linker.clone(),
+            engines: self.engines.clone(),
             runtime: self.runtime.clone(),
             component_service: self.component_service.clone(),
             shard_manager_service: self.shard_manager_service.clone(),
@@ -329,8 +327,7 @@ impl<Ctx: WorkerCtx> All<Ctx> {
     pub fn new(
         active_workers: Arc<active_workers::ActiveWorkers<Ctx>>,
         agent_types: Arc<dyn agent_types::AgentTypesService>,
-        engine: Arc<wasmtime::Engine>,
-        linker: Arc<wasmtime::component::Linker<Ctx>>,
+        engines: Arc<ComponentEngines<Ctx>>,
         runtime: Handle,
         component_service: Arc<dyn component::ComponentService>,
         shard_manager_service: Arc<dyn shard_manager::ShardManagerService>,
@@ -362,8 +359,7 @@ impl<Ctx: WorkerCtx> All<Ctx> {
         Self {
             active_workers,
             agent_types,
-            engine,
-            linker,
+            engines,
             runtime,
             component_service,
             shard_manager_service,
@@ -396,8 +392,7 @@ impl<Ctx: WorkerCtx> All<Ctx> {
         All::new(
             this.active_workers(),
             this.agent_types(),
-            this.engine(),
-            this.linker(),
+            this.engines(),
             this.runtime(),
             this.component_service(),
             this.shard_manager_service(),
@@ -510,12 +505,8 @@ impl<Ctx: WorkerCtx, T: UsesAllDeps<Ctx = Ctx>> HasPromiseService for T {
 }
 
 impl<Ctx: WorkerCtx, T: UsesAllDeps<Ctx = Ctx>> HasWasmtimeEngine<Ctx> for T {
-    fn engine(&self) -> Arc<wasmtime::Engine> {
-        self.all().engine.clone()
-    }
-
-    fn linker(&self) -> Arc<wasmtime::component::Linker<Ctx>> {
-        self.all().linker.clone()
+    fn engines(&self) -> Arc<ComponentEngines<Ctx>> {
+        self.all().engines.clone()
     }
 
     fn runtime(&self) -> Handle {
@@ -17,6 +17,7 @@ use std::fmt::{Display, Formatter};
 use std::sync::Arc;
 
 use super::file_loader::FileLoader;
+use crate::engines::ComponentEngines;
 use crate::services::events::Events;
 use crate::services::oplog::plugin::OplogProcessorPlugin;
 use crate::services::plugins::Plugins;
@@ -326,8 +327,7 @@ impl Rpc for RemoteInvocationRpc {
 pub struct DirectWorkerInvocationRpc<Ctx: WorkerCtx> {
     remote_rpc: Arc<RemoteInvocationRpc>,
     active_workers: Arc<active_workers::ActiveWorkers<Ctx>>,
-    engine: Arc<wasmtime::Engine>,
-    linker: Arc<wasmtime::component::Linker<Ctx>>,
+    engines: Arc<ComponentEngines<Ctx>>,
     runtime: Handle,
     component_service: Arc<dyn component::ComponentService>,
     shard_manager_service: Arc<dyn shard_manager::ShardManagerService>,
@@ -360,8 +360,7 @@ impl<Ctx: WorkerCtx> Clone for DirectWorkerInvocationRpc<Ctx> {
         Self {
             remote_rpc: self.remote_rpc.clone(),
             active_workers: self.active_workers.clone(),
-            engine: self.engine.clone(),
-            linker: self.linker.clone(),
+            engines: self.engines.clone(),
             runtime: self.runtime.clone(),
             component_service: self.component_service.clone(),
             shard_manager_service: self.shard_manager_service.clone(),
@@ -447,12 +446,8 @@ impl<Ctx: WorkerCtx> HasPromiseService for DirectWorkerInvocationRpc<Ctx> {
 }
 
 impl<Ctx: WorkerCtx> HasWasmtimeEngine<Ctx> for DirectWorkerInvocationRpc<Ctx> {
-    fn engine(&self) -> Arc<wasmtime::Engine> {
-        self.engine.clone()
-    }
-
-    fn linker(&self) -> Arc<wasmtime::component::Linker<Ctx>> {
-        self.linker.clone()
+    fn engines(&self) -> Arc<ComponentEngines<Ctx>> {
+        self.engines.clone()
     }
 
     fn runtime(&self) -> Handle {
@@ -568,8 +563,7 @@ impl<Ctx: WorkerCtx> DirectWorkerInvocationRpc<Ctx> {
     pub fn new(
         remote_rpc: Arc<RemoteInvocationRpc>,
         active_workers: Arc<active_workers::ActiveWorkers<Ctx>>,
-        engine: Arc<wasmtime::Engine>,
-        linker: Arc<wasmtime::component::Linker<Ctx>>,
+        engines: Arc<ComponentEngines<Ctx>>,
         runtime: Handle,
         component_service: Arc<dyn component::ComponentService>,
         worker_fork: Arc<dyn worker_fork::WorkerForkService>,
@@ -600,8 +594,7 @@ impl<Ctx: WorkerCtx> DirectWorkerInvocationRpc<Ctx> {
         Self {
             remote_rpc,
             active_workers,
-            engine,
-            linker,
+            engines,
             runtime,
             component_service,
             shard_manager_service,
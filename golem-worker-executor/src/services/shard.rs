@@ -28,6 +28,11 @@ pub trait ShardService: Send + Sync {
     fn assign_shards(&self, shard_ids: &HashSet<ShardId>) -> Result<(), WorkerExecutorError>;
     fn check_worker(&self, worker_id: &WorkerId) -> Result<(), WorkerExecutorError>;
     fn register(&self, number_of_shards: usize, shard_ids: &HashSet<ShardId>);
+    fn reshard(
+        &self,
+        number_of_shards: usize,
+        shard_ids: &HashSet<ShardId>,
+    ) -> Result<(), WorkerExecutorError>;
     fn revoke_shards(&self, shard_ids: &HashSet<ShardId>) -> Result<(), WorkerExecutorError>;
     fn current_assignment(&self) -> Result<ShardAssignment, WorkerExecutorError>;
     fn try_get_current_assignment(&self) -> Option<ShardAssignment>;
@@ -127,6 +132,29 @@ impl ShardService for ShardServiceDefault {
         })
     }
 
+    fn reshard(
+        &self,
+        number_of_shards: usize,
+        shard_ids: &HashSet<ShardId>,
+    ) -> Result<(), WorkerExecutorError> {
+        self.with_write_shard_assignment(|shard_assignment| match shard_assignment {
+            Some(shard_assignment) => {
+                debug!(
+                    number_of_shards_current = shard_assignment.number_of_shards,
+                    number_of_shards,
+                    shard_ids_current = shard_assignment.shard_ids.iter().join(", "),
+                    shard_ids_new = shard_ids.iter().join(", "),
+                    "ShardService.reshard"
+                );
+                shard_assignment.reshard(number_of_shards, shard_ids);
+                let assigned_shard_count = shard_assignment.shard_ids.len();
+                record_assigned_shard_count(assigned_shard_count);
+                Ok(())
+            }
+            None => Err(sharding_not_ready_error()),
+        })
+    }
+
     fn revoke_shards(&self, shard_ids: &HashSet<ShardId>) -> Result<(), WorkerExecutorError> {
         self.with_write_shard_assignment(|shard_assignment| match shard_assignment {
             Some(shard_assignment) => {
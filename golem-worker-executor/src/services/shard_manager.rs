@@ -14,17 +14,29 @@
 
 use crate::services::golem_config::{ShardManagerServiceConfig, ShardManagerServiceGrpcConfig};
 use async_trait::async_trait;
+use axum::response::IntoResponse;
+use axum::routing::post;
+use axum::{Json, Router};
 use golem_api_grpc::proto::golem::shardmanager;
 use golem_api_grpc::proto::golem::shardmanager::v1::shard_manager_service_client::ShardManagerServiceClient;
 use golem_common::client::{GrpcClient, GrpcClientConfig};
 use golem_common::model::{ShardAssignment, ShardId};
 use golem_common::retries::with_retries;
 use golem_service_base::error::worker_executor::WorkerExecutorError;
+use http::StatusCode;
+use serde::Serialize;
 use std::collections::HashSet;
+use std::future::Future;
+use std::net::SocketAddrV4;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::task::JoinSet;
+use tokio::time::Instant;
 use tonic::codec::CompressionEncoding;
 use tonic::transport::Channel;
 use tonic_tracing_opentelemetry::middleware::client::OtelGrpcService;
+use tracing::{info, Instrument};
 
 /// Service providing access to the shard manager service
 #[async_trait]
@@ -34,6 +46,88 @@ pub trait ShardManagerService: Send + Sync {
         host: String,
         port: u16,
     ) -> Result<ShardAssignment, WorkerExecutorError>;
+
+    /// Asks the shard manager to reassign all shards of this executor to other executors, and
+    /// waits until it holds no shards or the drain timeout expires
+    async fn drain(&self, host: String, port: u16) -> Result<DrainStatus, WorkerExecutorError>;
+
+    /// Lets the shard manager assign shards to this executor again
+    async fn undrain(&self, host: String, port: u16) -> Result<(), WorkerExecutorError>;
+}
+
+/// The progress of draining an executor
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+pub struct DrainStatus {
+    pub drained: bool,
+    pub remaining_shards: usize,
+}
+
+/// Starts the admin HTTP server serving the drain endpoints. It is separate from the health and
+/// metrics server, so emptying the executor is only possible through the admin address.
+pub async fn start_admin_http_server(
+    addr: SocketAddrV4,
+    shard_manager_service: Arc<dyn ShardManagerService>,
+    host: String,
+    port: u16,
+    join_set: &mut JoinSet<Result<(), anyhow::Error>>,
+) -> Result<u16, anyhow::Error> {
+    let app = drain_routes(shard_manager_service, host, port);
+
+    let listener = TcpListener::bind(addr).await?;
+    let local_addr = listener.local_addr()?;
+
+    join_set.spawn(
+        async move {
+            axum::serve(listener, app).await?;
+            Ok(())
+        }
+        .in_current_span(),
+    );
+
+    info!("Admin server started on ports: http: {local_addr}");
+
+    Ok(local_addr.port())
+}
+
+/// HTTP endpoints to drain and undrain this executor, for example from a pre-stop hook before
+/// rolling the executor. Draining responds with `503 Service Unavailable` if the executor still
+/// holds shards when the drain timeout expires.
+fn drain_routes(
+    shard_manager_service: Arc<dyn ShardManagerService>,
+    host: String,
+    port: u16,
+) -> Router {
+    let drain = {
+        let shard_manager_service = shard_manager_service.clone();
+        let host = host.clone();
+        move || {
+            let shard_manager_service = shard_manager_service.clone();
+            let host = host.clone();
+            async move {
+                match shard_manager_service.drain(host, port).await {
+                    Ok(status) if status.drained => (StatusCode::OK, Json(status)).into_response(),
+                    Ok(status) => (StatusCode::SERVICE_UNAVAILABLE, Json(status)).into_response(),
+                    Err(err) => {
+                        (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response()
+                    }
+                }
+            }
+        }
+    };
+    let undrain = move || {
+        let shard_manager_service = shard_manager_service.clone();
+        let host = host.clone();
+        async move {
+            match shard_manager_service.undrain(host, port).await {
+                Ok(()) => StatusCode::NO_CONTENT.into_response(),
+                Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response(),
+            }
+        }
+    };
+
+    Router::new()
+        .route("/drain", post(drain))
+        .route("/undrain", post(undrain))
 }
 
 pub fn configured(config: &ShardManagerServiceConfig) -> Arc<dyn ShardManagerService> {
@@ -71,6 +165,66 @@ impl ShardManagerServiceGrpc {
         );
         Self { config, client }
     }
+
+    async fn request_drain(
+        &self,
+        host: &str,
+        port: u16,
+        pod_name: &Option<String>,
+    ) -> Result<DrainStatus, WorkerExecutorError> {
+        with_retries(
+            "shard_manager",
+            "drain",
+            Some(format!("{pod_name:?}")),
+            &self.config.retries,
+            &(host.to_string(), port, pod_name.clone()),
+            |(host, port, pod_name)| {
+                let client = self.client.clone();
+                Box::pin(async move {
+                    let response = client
+                        .call("drain", move |client| {
+                            Box::pin(client.drain(shardmanager::v1::DrainRequest {
+                                host: host.clone(),
+                                port: *port as i32,
+                                pod_name: pod_name.clone(),
+                                zone: zone.clone(),
+                            }))
+                        })
+                        .await
+                        .map_err(|err| {
+                            WorkerExecutorError::unknown(format!(
+                                "Draining with shard manager failed with {err}"
+                            ))
+                        })?;
+                    match response.into_inner() {
+                        shardmanager::v1::DrainResponse {
+                            result:
+                                Some(shardmanager::v1::drain_response::Result::Success(
+                                    shardmanager::v1::DrainStatus {
+                                        drained,
+                                        remaining_shards,
+                                    },
+                                )),
+                        } => Ok(DrainStatus {
+                            drained,
+                            remaining_shards: remaining_shards as usize,
+                        }),
+                        shardmanager::v1::DrainResponse {
+                            result:
+                                Some(shardmanager::v1::drain_response::Result::Failure(failure)),
+                        } => Err(WorkerExecutorError::unknown(format!(
+                            "Draining with shard manager failed with shard manager error {failure:?}"
+                        ))),
+                        shardmanager::v1::DrainResponse { .. } => Err(WorkerExecutorError::unknown(
+                            "Draining with shard manager failed with unknown error",
+                        )),
+                    }
+                })
+            },
+            |_| true,
+        )
+        .await
+    }
 }
 
 #[async_trait]
@@ -81,6 +235,7 @@ impl ShardManagerService for ShardManagerServiceGrpc {
         port: u16,
     ) -> Result<ShardAssignment, WorkerExecutorError> {
         let pod_name = std::env::var_os("POD_NAME").map(|s| s.to_string_lossy().to_string());
+        let zone = std::env::var_os("POD_ZONE").map(|s| s.to_string_lossy().to_string());
         with_retries(
             "shard_manager",
             "register",
@@ -90,6 +245,7 @@ impl ShardManagerService for ShardManagerServiceGrpc {
             |(host, port)| {
                 let client = self.client.clone();
                 let pod_name = pod_name.clone();
+                let zone = zone.clone();
                 Box::pin(async move {
                     let response = client
                         .call("register", move |client| {
@@ -131,6 +287,95 @@ impl ShardManagerService for ShardManagerServiceGrpc {
         )
         .await
     }
+
+    async fn drain(&self, host: String, port: u16) -> Result<DrainStatus, WorkerExecutorError> {
+        let pod_name = std::env::var_os("POD_NAME").map(|s| s.to_string_lossy().to_string());
+
+        poll_drain(
+            self.config.drain_timeout,
+            self.config.drain_poll_interval,
+            || self.request_drain(&host, port, &pod_name),
+        )
+        .await
+    }
+
+    async fn undrain(&self, host: String, port: u16) -> Result<(), WorkerExecutorError> {
+        let pod_name = std::env::var_os("POD_NAME").map(|s| s.to_string_lossy().to_string());
+        with_retries(
+            "shard_manager",
+            "undrain",
+            Some(format!("{pod_name:?}")),
+            &self.config.retries,
+            &(host, port),
+            |(host, port)| {
+                let client = self.client.clone();
+                let pod_name = pod_name.clone();
+                Box::pin(async move {
+                    let response = client
+                        .call("undrain", move |client| {
+                            Box::pin(client.undrain(shardmanager::v1::UndrainRequest {
+                                host: host.clone(),
+                                port: *port as i32,
+                                pod_name: pod_name.clone(),
+                            }))
+                        })
+                        .await
+                        .map_err(|err| {
+                            WorkerExecutorError::unknown(format!(
+                                "Undraining with shard manager failed with {err}"
+                            ))
+                        })?;
+                    match response.into_inner() {
+                        shardmanager::v1::UndrainResponse {
+                            result: Some(shardmanager::v1::undrain_response::Result::Success(_)),
+                        } => Ok(()),
+                        shardmanager::v1::UndrainResponse {
+                            result:
+                                Some(shardmanager::v1::undrain_response::Result::Failure(failure)),
+                        } => Err(WorkerExecutorError::unknown(format!(
+                            "Undraining with shard manager failed with shard manager error {failure:?}"
+                        ))),
+                        shardmanager::v1::UndrainResponse { .. } => Err(WorkerExecutorError::unknown(
+                            "Undraining with shard manager failed with unknown error",
+                        )),
+                    }
+                })
+            },
+            |_| true,
+        )
+        .await
+    }
+}
+
+/// Requests draining until no shards are left or the next poll would be after `drain_timeout`.
+///
+/// Draining is idempotent on the shard manager side, repeating it until no shards are left
+/// also restarts the drain if another shard manager replica took over in the meantime.
+async fn poll_drain<F, Fut>(
+    drain_timeout: Duration,
+    drain_poll_interval: Duration,
+    request_drain: F,
+) -> Result<DrainStatus, WorkerExecutorError>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<DrainStatus, WorkerExecutorError>>,
+{
+    let deadline = Instant::now() + drain_timeout;
+    loop {
+        let status = request_drain().await?;
+        if status.drained {
+            info!("Worker executor drained");
+            return Ok(status);
+        }
+        if Instant::now() + drain_poll_interval > deadline {
+            return Ok(status);
+        }
+        info!(
+            remaining_shards = status.remaining_shards,
+            "Waiting for worker executor to be drained"
+        );
+        tokio::time::sleep(drain_poll_interval).await;
+    }
 }
 
 pub struct ShardManagerServiceSingleShard {}
@@ -159,4 +404,81 @@ impl ShardManagerService for ShardManagerServiceSingleShard {
             HashSet::from_iter(vec![ShardId::new(0)]),
         ))
     }
+
+    async fn drain(&self, _host: String, _port: u16) -> Result<DrainStatus, WorkerExecutorError> {
+        Err(WorkerExecutorError::invalid_request(
+            "Draining is not supported with a single shard",
+        ))
+    }
+
+    async fn undrain(&self, _host: String, _port: u16) -> Result<(), WorkerExecutorError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use crate::services::shard_manager::{poll_drain, DrainStatus};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    async fn drain_is_polled_until_no_shards_are_left() {
+        let requests = AtomicUsize::new(0);
+
+        let status = poll_drain(Duration::from_secs(10), Duration::from_millis(10), || {
+            let remaining_shards = 3 - requests.fetch_add(1, Ordering::SeqCst);
+            async move {
+                Ok(DrainStatus {
+                    drained: remaining_shards == 0,
+                    remaining_shards,
+                })
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(
+            status,
+            DrainStatus {
+                drained: true,
+                remaining_shards: 0
+            }
+        );
+        assert_eq!(requests.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    async fn drain_stops_at_the_drain_timeout() {
+        let drain_timeout = Duration::from_millis(300);
+        let drain_poll_interval = Duration::from_millis(50);
+        let requests = AtomicUsize::new(0);
+        let started = Instant::now();
+
+        let status = poll_drain(drain_timeout, drain_poll_interval, || {
+            requests.fetch_add(1, Ordering::SeqCst);
+            async {
+                Ok(DrainStatus {
+                    drained: false,
+                    remaining_shards: 2,
+                })
+            }
+        })
+        .await
+        .unwrap();
+
+        let elapsed = started.elapsed();
+        assert_eq!(
+            status,
+            DrainStatus {
+                drained: false,
+                remaining_shards: 2
+            }
+        );
+        assert!(elapsed <= drain_timeout + drain_poll_interval);
+        assert!(elapsed >= drain_timeout - drain_poll_interval);
+        assert!(requests.load(Ordering::SeqCst) <= 7);
+    }
 }
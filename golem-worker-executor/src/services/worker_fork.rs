@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use super::file_loader::FileLoader;
+use crate::engines::ComponentEngines;
 use crate::metrics::workers::record_worker_call;
 use crate::model::ExecutionStatus;
 use crate::services::events::Events;
@@ -76,8 +77,7 @@ pub struct DefaultWorkerFork<Ctx: WorkerCtx> {
     pub rpc: Arc<dyn Rpc>,
     pub active_workers: Arc<active_workers::ActiveWorkers<Ctx>>,
     pub agent_types: Arc<dyn agent_types::AgentTypesService>,
-    pub engine: Arc<wasmtime::Engine>,
-    pub linker: Arc<wasmtime::component::Linker<Ctx>>,
+    pub engines: Arc<ComponentEngines<Ctx>>,
     pub runtime: Handle,
     pub component_service: Arc<dyn component::ComponentService>,
     pub shard_manager_service: Arc<dyn shard_manager::ShardManagerService>,
@@ -161,12 +161,8 @@ impl<Ctx: WorkerCtx> HasPromiseService for DefaultWorkerFork<Ctx> {
 }
 
 impl<Ctx: WorkerCtx> HasWasmtimeEngine<Ctx> for DefaultWorkerFork<Ctx> {
-    fn engine(&self) -> Arc<wasmtime::Engine> {
-        self.engine.clone()
-    }
-
-    fn linker(&self) -> Arc<wasmtime::component::Linker<Ctx>> {
-        self.linker.clone()
+    fn engines(&self) -> Arc<ComponentEngines<Ctx>> {
+        self.engines.clone()
     }
 
     fn runtime(&self) -> Handle {
@@ -282,8 +278,7 @@ impl<Ctx: WorkerCtx> Clone for DefaultWorkerFork<Ctx> {
             rpc: self.rpc.clone(),
             active_workers: self.active_workers.clone(),
             agent_types: self.agent_types.clone(),
-            engine: self.engine.clone(),
-            linker: self.linker.clone(),
+            engines: self.engines.clone(),
             runtime: self.runtime.clone(),
             component_service: self.component_service.clone(),
             shard_manager_service: self.shard_manager_service.clone(),
@@ -316,8 +311,7 @@ impl<Ctx: WorkerCtx> DefaultWorkerFork<Ctx> {
     pub fn new(
         rpc: Arc<dyn Rpc>,
         active_workers: Arc<active_workers::ActiveWorkers<Ctx>>,
-        engine: Arc<wasmtime::Engine>,
-        linker: Arc<wasmtime::component::Linker<Ctx>>,
+        engines: Arc<ComponentEngines<Ctx>>,
         runtime: Handle,
         component_service: Arc<dyn component::ComponentService>,
         shard_manager_service: Arc<dyn shard_manager::ShardManagerService>,
@@ -349,8 +343,7 @@ impl<Ctx: WorkerCtx> DefaultWorkerFork<Ctx> {
             rpc,
             active_workers,
             agent_types,
-            engine,
-            linker,
+            engines,
             runtime,
             component_service,
             shard_manager_service,
@@ -520,7 +520,7 @@ async fn drop_resource<Ctx: WorkerCtx>(
 
         let result = resource.resource_drop_async(&mut store).await;
 
-        let current_fuel_level = store.get_fuel().unwrap_or(0);
+        let current_fuel_level = store.get_fuel().unwrap_or(i64::MAX as u64);
         let consumed_fuel = store
             .data_mut()
             .return_fuel(current_fuel_level as i64)
@@ -589,7 +589,9 @@ async fn finish_invocation_and_get_fuel_consumption<Ctx: WorkerCtx>(
     raw_function_name: &str,
     idempotency_key: Option<IdempotencyKey>,
 ) -> Result<i64, WorkerExecutorError> {
-    let current_fuel_level = store.get_fuel().unwrap_or(0);
+    // Components compiled with epoch based interruption do not consume fuel, the fuel charged
+    // for their epoch deadlines is accounted by the worker context
+    let current_fuel_level = store.get_fuel().unwrap_or(i64::MAX as u64);
     let consumed_fuel_for_call = store
         .data_mut()
         .return_fuel(current_fuel_level as i64)
@@ -474,6 +474,7 @@ impl<Ctx: WorkerCtx> Invocation<'_, Ctx> {
                 output,
                 consumed_fuel,
             }) => {
+                self.parent.add_consumed_fuel(consumed_fuel);
                 self.exported_function_invocation_finished(
                     full_function_name,
                     &function_input,
@@ -21,6 +21,7 @@ use self::status::{
 };
 use crate::durable_host::recover_stderr_logs;
 use crate::model::{ExecutionStatus, LookupResult, ReadFileResult, TrapType, WorkerConfig};
+use crate::services::active_workers::WorkerPermit;
 use crate::services::events::{Event, EventsSubscription};
 use crate::services::oplog::{CommitLevel, Oplog, OplogOps};
 use crate::services::worker::GetWorkerMetadataResult;
@@ -39,6 +40,7 @@ use crate::workerctx::WorkerCtx;
 use anyhow::anyhow;
 use futures::channel::oneshot;
 use golem_common::model::agent::{AgentId, AgentMode};
+use golem_common::model::component_metadata::CompilationInterruption;
 use golem_common::model::invocation_context::InvocationContextStack;
 use golem_common::model::oplog::{OplogEntry, OplogIndex, UpdateDescription};
 use golem_common::model::regions::OplogRegion;
@@ -57,7 +59,7 @@ use golem_service_base::error::worker_executor::{
 use golem_wasm::analysis::AnalysedFunctionResult;
 use golem_wasm::{IntoValue, Value, ValueAndType};
 use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::broadcast::error::RecvError;
@@ -101,6 +103,8 @@ pub struct Worker<Ctx: WorkerCtx> {
     execution_status: Arc<std::sync::RwLock<ExecutionStatus>>,
     update_state_lock: tokio::sync::Mutex<()>,
     worker_estimate_coefficient: f64,
+    // Total fuel consumed since the worker was loaded, for the shard load reports
+    consumed_fuel: AtomicU64,
 
     // IMPORTANT: Every external operation must acquire the instance lock, even briefly, to confirm the worker isn’t deleting.
     instance: Arc<tokio::sync::Mutex<WorkerInstance>>,
@@ -288,6 +292,7 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
             oom_retry_config: deps.config().memory.oom_retry_config.clone(),
             update_state_lock: tokio::sync::Mutex::new(()),
             last_known_status_detached: AtomicBool::new(false),
+            consumed_fuel: AtomicU64::new(0),
         };
 
         // just some sanity checking
@@ -322,6 +327,20 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
         &self.oom_retry_config
     }
 
+    /// Returns the total fuel consumed by the worker's invocations since it was loaded, used for
+    /// reporting shard loads to the shard manager.
+    ///
+    /// Never reset, so a report lost on the way does not lose the consumed fuel; the shard
+    /// manager takes the difference of the consecutive reports.
+    pub fn consumed_fuel(&self) -> u64 {
+        self.consumed_fuel.load(Ordering::Acquire)
+    }
+
+    pub(crate) fn add_consumed_fuel(&self, consumed_fuel: i64) {
+        self.consumed_fuel
+            .fetch_add(consumed_fuel.max(0) as u64, Ordering::AcqRel);
+    }
+
     pub async fn start_if_needed(this: Arc<Worker<Ctx>>) -> Result<bool, WorkerExecutorError> {
         Self::start_if_needed_internal(this, 0).await
     }
@@ -768,6 +787,18 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
         }
     }
 
+    /// Returns true if the worker is not in the middle of executing an invocation, so interrupting
+    /// it does not lose any progress made since its last suspension point.
+    pub async fn is_at_suspension_point(&self) -> bool {
+        match &*self.instance.lock().await {
+            WorkerInstance::Running(running) => running.waiting_for_command.load(Ordering::Acquire),
+            WorkerInstance::WaitingForPermit(_)
+            | WorkerInstance::Unloaded
+            | WorkerInstance::Stopping(_)
+            | WorkerInstance::Deleting => true,
+        }
+    }
+
     /// Gets the timestamp of the last time the execution status changed
     pub fn last_execution_state_change(&self) -> Timestamp {
         self.execution_status.read().unwrap().timestamp()
@@ -1683,7 +1714,7 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
 
     async fn start_waiting_worker(
         this: Arc<Worker<Ctx>>,
-        permit: OwnedSemaphorePermit,
+        permit: WorkerPermit,
         oom_retry_count: u32,
         start_attempt: Uuid,
     ) {
@@ -1806,7 +1837,7 @@ struct RunningWorker {
     handle: Option<JoinHandle<()>>,
     sender: UnboundedSender<WorkerCommand>,
     queue: Arc<tokio::sync::RwLock<VecDeque<QueuedWorkerInvocation>>>,
-    permit: OwnedSemaphorePermit,
+    permit: WorkerPermit,
     waiting_for_command: Arc<AtomicBool>,
 }
 
@@ -1815,7 +1846,7 @@ impl RunningWorker {
         owned_worker_id: OwnedWorkerId,
         queue: Arc<tokio::sync::RwLock<VecDeque<QueuedWorkerInvocation>>>,
         parent: Arc<Worker<Ctx>>,
-        permit: OwnedSemaphorePermit,
+        permit: WorkerPermit,
         oom_retry_count: u32,
     ) -> Self {
         let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
@@ -1867,7 +1898,7 @@ impl RunningWorker {
     }
 
     pub fn merge_extra_permits(&mut self, extra_permit: OwnedSemaphorePermit) {
-        self.permit.merge(extra_permit);
+        self.permit.merge_memory(extra_permit);
     }
 
     pub fn stop(mut self) -> JoinHandle<()> {
@@ -1915,11 +1946,23 @@ impl RunningWorker {
                 },
             );
 
-            match parent
-                .component_service()
-                .get(&parent.engine(), &component_id, component_version)
+            let component_service = parent.component_service();
+            let result = match component_service
+                .get_metadata(&component_id, Some(component_version))
                 .await
             {
+                Ok(metadata) => {
+                    let engine = parent
+                        .engines()
+                        .engine(metadata.metadata.compilation_options());
+                    component_service
+                        .get(&engine, &component_id, component_version)
+                        .await
+                }
+                Err(error) => Err(error),
+            };
+
+            match result {
                 Ok((component, component_metadata)) => {
                     Ok((pending_update, component, component_metadata))
                 }
@@ -2016,12 +2059,22 @@ impl RunningWorker {
         )
         .await?;
 
-        let engine = parent.engine();
+        let compilation_options = component_metadata.metadata.compilation_options();
+        let engine = parent.engines().engine(compilation_options);
         let mut store = Store::new(&engine, context);
         store.set_epoch_deadline(parent.config().limits.epoch_ticks);
         let worker_id_clone = worker_metadata.worker_id.clone();
+        let epoch_fuel = match compilation_options.interruption {
+            CompilationInterruption::Fuel => None,
+            CompilationInterruption::Epoch => Some(parent.config().limits.epoch_fuel),
+        };
         store.epoch_deadline_callback(move |mut store| {
-            let current_level = store.get_fuel().unwrap_or(0);
+            // Components compiled with epoch based interruption do not consume fuel, they get
+            // charged for every epoch deadline they reach instead
+            if let Some(epoch_fuel) = epoch_fuel {
+                store.data_mut().charge_fuel(epoch_fuel);
+            }
+            let current_level = store.get_fuel().unwrap_or(i64::MAX as u64);
             if store.data().is_out_of_fuel(current_level as i64) {
                 debug!("{worker_id_clone} ran out of fuel, borrowing more");
                 store.data_mut().borrow_fuel_sync();
@@ -2033,12 +2086,14 @@ impl RunningWorker {
             }
         });
 
-        store.set_fuel(i64::MAX as u64)?;
+        if compilation_options.interruption == CompilationInterruption::Fuel {
+            store.set_fuel(i64::MAX as u64)?;
+        }
         store.data_mut().borrow_fuel().await?; // Borrowing fuel for initialization and also to make sure account is in cache
 
         store.limiter_async(|ctx| ctx.resource_limiter());
 
-        let mut linker = (*parent.linker()).clone(); // fresh linker
+        let mut linker = (*parent.engines().linker(compilation_options)).clone(); // fresh linker
         store
             .data_mut()
             .link(&engine, &mut linker, &component, &component_metadata)?;
@@ -49,6 +49,7 @@ use anyhow::{anyhow, Error};
 use async_trait::async_trait;
 use golem_common::base_model::{OplogIndex, ProjectId};
 use golem_common::model::agent::{AgentId, AgentMode};
+use golem_common::model::component_metadata::ComponentPoolingLimits;
 use golem_common::model::invocation_context::{
     self, AttributeValue, InvocationContextStack, SpanId,
 };
@@ -72,7 +73,7 @@ use std::future::Future;
 use std::sync::{Arc, Weak};
 use tracing::debug;
 use wasmtime::component::{Component, Instance, Linker, Resource, ResourceAny};
-use wasmtime::{AsContextMut, Engine, ResourceLimiterAsync};
+use wasmtime::{AsContextMut, Engine, ResourceLimiterAsync, DEFAULT_INSTANCE_LIMIT};
 use wasmtime_wasi::p2::WasiView;
 use wasmtime_wasi_http::WasiHttpView;
 
@@ -83,6 +84,7 @@ pub struct Context {
     resource_limits: Arc<dyn ResourceLimits>,
     last_fuel_level: i64,
     min_fuel_level: i64,
+    charged_fuel: i64,
 }
 
 impl Context {
@@ -99,6 +101,7 @@ impl Context {
             resource_limits,
             last_fuel_level: i64::MAX,
             min_fuel_level: i64::MAX,
+            charged_fuel: 0,
         }
     }
 
@@ -107,6 +110,16 @@ impl Context {
             .get_max_memory(&self.project_owner_account_id)
             .await
     }
+
+    /// The limits configured in the compilation options of the component, if any
+    fn pooling_limits(&self) -> Option<&ComponentPoolingLimits> {
+        self.durable_ctx
+            .component_metadata()
+            .metadata
+            .compilation_options()
+            .pooling_limits
+            .as_ref()
+    }
 }
 
 impl DurableWorkerCtxView<Context> for Context {
@@ -122,7 +135,7 @@ impl DurableWorkerCtxView<Context> for Context {
 #[async_trait]
 impl FuelManagement for Context {
     fn is_out_of_fuel(&self, current_level: i64) -> bool {
-        current_level < self.min_fuel_level
+        current_level - self.charged_fuel < self.min_fuel_level
     }
 
     async fn borrow_fuel(&mut self) -> Result<(), WorkerExecutorError> {
@@ -156,6 +169,7 @@ impl FuelManagement for Context {
     }
 
     async fn return_fuel(&mut self, current_level: i64) -> Result<i64, WorkerExecutorError> {
+        let current_level = current_level - self.charged_fuel;
         let unused = current_level - self.min_fuel_level;
         if unused > 0 {
             debug!("current_level: {current_level}");
@@ -177,6 +191,10 @@ impl FuelManagement for Context {
         );
         Ok(consumed)
     }
+
+    fn charge_fuel(&mut self, amount: i64) {
+        self.charged_fuel += amount;
+    }
 }
 
 #[async_trait]
@@ -265,7 +283,10 @@ impl ResourceLimiterAsync for Context {
         desired: usize,
         maximum: Option<usize>,
     ) -> anyhow::Result<bool> {
-        let limit = self.get_max_memory().await?;
+        let mut limit = self.get_max_memory().await?;
+        if let Some(pooling_limits) = self.pooling_limits() {
+            limit = limit.min(pooling_limits.max_memory_size as usize);
+        }
         debug!(
             "memory_growing: current={}, desired={}, maximum={:?}, account limit={}",
             current, desired, maximum, limit
@@ -297,7 +318,16 @@ impl ResourceLimiterAsync for Context {
             "table_growing: current={}, desired={}, maximum={:?}",
             current, desired, maximum
         );
-        Ok(true)
+        match self.pooling_limits() {
+            Some(pooling_limits) => Ok(desired <= pooling_limits.table_elements as usize),
+            None => Ok(true),
+        }
+    }
+
+    fn instances(&self) -> usize {
+        self.pooling_limits()
+            .map(|pooling_limits| pooling_limits.max_core_instances as usize)
+            .unwrap_or(DEFAULT_INSTANCE_LIMIT)
     }
 }
 
@@ -220,6 +220,11 @@ pub trait FuelManagement {
     /// Returns the remaining fuel that was previously borrowed. The remaining amount can be calculated
     /// by the current fuel level and some internal state of the worker context.
     async fn return_fuel(&mut self, current_level: i64) -> Result<i64, WorkerExecutorError>;
+
+    /// Charges fuel for executing components compiled with epoch based interruption, which do not
+    /// consume fuel. The charged amount is deducted from the fuel levels passed to
+    /// `is_out_of_fuel` and `return_fuel`.
+    fn charge_fuel(&mut self, amount: i64);
 }
 
 /// The invocation management interface of a worker context is responsible for connecting
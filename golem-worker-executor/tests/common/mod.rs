@@ -40,6 +40,7 @@ use golem_wasm::{HostWasmRpc, RpcError, Uri, Value, ValueAndType, WitValue};
 use golem_worker_executor::durable_host::{
     DurableWorkerCtx, DurableWorkerCtxView, PublicDurableWorkerState,
 };
+use golem_worker_executor::engines::ComponentEngines;
 use golem_worker_executor::model::{
     CurrentResourceLimits, ExecutionStatus, LastError, ReadFileResult, TrapType, WorkerConfig,
 };
@@ -298,6 +299,7 @@ pub async fn start_customized(
         agent_types_service: AgentTypesServiceConfig::Local(AgentTypesServiceLocalConfig {}),
         engine: EngineConfig {
             enable_fs_cache: true,
+            ..Default::default()
         },
         ..Default::default()
     };
@@ -392,6 +394,8 @@ impl FuelManagement for TestWorkerCtx {
     async fn return_fuel(&mut self, _current_level: i64) -> Result<i64, WorkerExecutorError> {
         Ok(0)
     }
+
+    fn charge_fuel(&mut self, _amount: i64) {}
 }
 
 #[async_trait]
@@ -928,7 +932,10 @@ impl Bootstrap<TestWorkerCtx> for ServerBootstrap {
         &self,
         golem_config: &GolemConfig,
     ) -> Arc<ActiveWorkers<TestWorkerCtx>> {
-        Arc::new(ActiveWorkers::<TestWorkerCtx>::new(&golem_config.memory))
+        Arc::new(ActiveWorkers::<TestWorkerCtx>::new(
+            &golem_config.memory,
+            &golem_config.engine,
+        ))
     }
 
     fn create_plugins(
@@ -960,8 +967,7 @@ impl Bootstrap<TestWorkerCtx> for ServerBootstrap {
     async fn create_services(
         &self,
         active_workers: Arc<ActiveWorkers<TestWorkerCtx>>,
-        engine: Arc<Engine>,
-        linker: Arc<Linker<TestWorkerCtx>>,
+        engines: Arc<ComponentEngines<TestWorkerCtx>>,
         runtime: Handle,
         component_service: Arc<dyn ComponentService>,
         shard_manager_service: Arc<dyn ShardManagerService>,
@@ -997,8 +1003,7 @@ impl Bootstrap<TestWorkerCtx> for ServerBootstrap {
                 shard_service.clone(),
             )),
             active_workers.clone(),
-            engine.clone(),
-            linker.clone(),
+            engines.clone(),
             runtime.clone(),
             component_service.clone(),
             shard_manager_service.clone(),
@@ -1031,8 +1036,7 @@ impl Bootstrap<TestWorkerCtx> for ServerBootstrap {
                 shard_service.clone(),
             )),
             active_workers.clone(),
-            engine.clone(),
-            linker.clone(),
+            engines.clone(),
             runtime.clone(),
             component_service.clone(),
             worker_fork.clone(),
@@ -1061,8 +1065,7 @@ impl Bootstrap<TestWorkerCtx> for ServerBootstrap {
         Ok(All::new(
             active_workers,
             agent_types_service,
-            engine,
-            linker,
+            engines,
             runtime,
             component_service,
             shard_manager_service,
@@ -0,0 +1,3 @@
+ALTER TABLE api_deployments ADD COLUMN traffic_weight integer;
+ALTER TABLE api_deployments ADD COLUMN traffic_header_name text;
+ALTER TABLE api_deployments ADD COLUMN traffic_header_value text;
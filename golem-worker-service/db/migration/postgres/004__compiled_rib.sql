@@ -0,0 +1,8 @@
+CREATE TABLE compiled_rib
+(
+    key              text      NOT NULL,
+    compiler_version integer   NOT NULL,
+    data             bytea     NOT NULL,
+    created_at       timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (key)
+);
@@ -0,0 +1,8 @@
+CREATE TABLE compiled_rib
+(
+    key              text    NOT NULL,
+    compiler_version integer NOT NULL,
+    data             blob    NOT NULL,
+    created_at       timestamp without time zone DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    PRIMARY KEY (key)
+);
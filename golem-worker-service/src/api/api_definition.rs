@@ -16,6 +16,7 @@ use super::dto::HttpApiDefinitionRequest;
 use super::dto::HttpApiDefinitionResponseData;
 use crate::api::common::ApiEndpointError;
 use crate::gateway_api_definition::http::api_oas_convert::OpenApiHttpApiDefinitionResponse;
+use crate::gateway_api_definition::http::api_proto_convert::ProtoHttpApiDefinitionResponse;
 use crate::gateway_api_definition::http::HttpApiDefinitionRequest as CoreHttpApiDefinitionRequest;
 use crate::gateway_api_definition::http::OpenApiHttpApiDefinition;
 use crate::gateway_api_definition::{ApiDefinitionId, ApiVersion};
@@ -503,4 +504,63 @@ impl ApiDefinitionApi {
 
         Ok(Json(response))
     }
+
+    /// Export the gRPC services of an API definition
+    ///
+    /// Exports the gRPC routes of an API definition by its API definition ID and version as a
+    /// .proto file, describing the services and messages gRPC clients can use to call them.
+    #[oai(
+        path = "/:project_id/:id/:version/export-proto",
+        method = "get",
+        operation_id = "export_definition_proto"
+    )]
+    async fn export_proto(
+        &self,
+        project_id: Path<ProjectId>,
+        id: Path<ApiDefinitionId>,
+        version: Path<ApiVersion>,
+        token: GolemSecurityScheme,
+    ) -> Result<Json<ProtoHttpApiDefinitionResponse>, ApiEndpointError> {
+        let record = recorded_http_api_request!(
+            "export_definition_proto",
+            api_definition_id = id.0.to_string(),
+            version = version.0.to_string(),
+            project_id = project_id.0.to_string()
+        );
+
+        let response = self
+            .export_proto_internal(project_id.0, id.0, version.0, token)
+            .instrument(record.span.clone())
+            .await;
+        record.result(response)
+    }
+
+    async fn export_proto_internal(
+        &self,
+        project_id: ProjectId,
+        api_definition_id: ApiDefinitionId,
+        api_version: ApiVersion,
+        token: GolemSecurityScheme,
+    ) -> Result<Json<ProtoHttpApiDefinitionResponse>, ApiEndpointError> {
+        let auth_ctx = AuthCtx::new(token.secret());
+        let namespace = self
+            .auth_service
+            .authorize_project_action(&project_id, ProjectAction::ExportApiDefinition, &auth_ctx)
+            .await?;
+
+        let data = self
+            .definition_service
+            .get(&api_definition_id, &api_version, &namespace, &auth_ctx)
+            .await?;
+
+        let compiled_definition = data.ok_or(ApiEndpointError::not_found(safe(format!(
+            "Can't find api definition with id {api_definition_id}, and version {api_version} in project {project_id}"
+        ))))?;
+
+        let response =
+            ProtoHttpApiDefinitionResponse::from_compiled_http_api_definition(&compiled_definition)
+                .map_err(|e| ApiEndpointError::bad_request(safe(e)))?;
+
+        Ok(Json(response))
+    }
 }
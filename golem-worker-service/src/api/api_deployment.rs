@@ -31,6 +31,7 @@ use golem_service_base::model::auth::GolemSecurityScheme;
 use poem_openapi::param::{Path, Query};
 use poem_openapi::payload::Json;
 use poem_openapi::*;
+use std::collections::HashMap;
 use std::sync::Arc;
 use tracing::Instrument;
 
@@ -102,10 +103,27 @@ impl ApiDeploymentApi {
             })
             .collect::<Vec<ApiDefinitionIdWithVersion>>();
 
+        let traffic_splits = payload
+            .api_definitions
+            .iter()
+            .filter_map(|k| {
+                k.traffic_split.clone().map(|traffic_split| {
+                    (
+                        ApiDefinitionIdWithVersion {
+                            id: k.id.clone(),
+                            version: k.version.clone(),
+                        },
+                        traffic_split,
+                    )
+                })
+            })
+            .collect::<HashMap<_, _>>();
+
         let api_deployment = gateway_api_deployment::ApiDeploymentRequest {
             namespace: namespace.clone(),
             api_definition_keys: api_definition_infos.clone(),
             site: payload.site.clone(),
+            traffic_splits,
         };
 
         self.deployment_service
@@ -237,6 +237,7 @@ impl From<ApiDeploymentError> for ApiEndpointError {
             ApiDeploymentError::ApiDeploymentNotFound(_, _) => ApiEndpointError::not_found(value),
             ApiDeploymentError::ApiDeploymentConflict(_) => ApiEndpointError::already_exists(value),
             ApiDeploymentError::ApiDefinitionsConflict(_) => ApiEndpointError::bad_request(value),
+            ApiDeploymentError::InvalidTrafficSplit(_) => ApiEndpointError::bad_request(value),
             ApiDeploymentError::InternalRepoError(_) => ApiEndpointError::internal(value),
             ApiDeploymentError::InternalConversionError { .. } => ApiEndpointError::internal(value),
             ApiDeploymentError::ComponentConstraintCreateError(_) => {
@@ -29,6 +29,7 @@ use crate::gateway_rib_interpreter::DefaultRibInterpreter;
 use crate::gateway_security::DefaultIdentityProvider;
 use futures::FutureExt;
 use poem::{Endpoint, Request, Response};
+use rib::RibExecutionLimits;
 
 pub struct CustomHttpRequestApi {
     pub gateway_http_input_executor: Arc<dyn GatewayHttpInputExecutor>,
@@ -41,6 +42,7 @@ impl CustomHttpRequestApi {
         file_server_binding_handler: Arc<dyn FileServerBindingHandler>,
         http_handler_binding_handler: Arc<dyn HttpHandlerBindingHandler>,
         gateway_session_store: Arc<dyn GatewaySession>,
+        rib_execution_limits: RibExecutionLimits,
     ) -> Self {
         let evaluator = Arc::new(DefaultRibInterpreter::from_worker_request_executor(
             worker_request_executor_service.clone(),
@@ -58,6 +60,7 @@ impl CustomHttpRequestApi {
             api_definition_lookup_service,
             gateway_session_store,
             identity_provider: Arc::new(DefaultIdentityProvider),
+            rib_execution_limits,
         });
 
         Self {
@@ -16,13 +16,15 @@ use crate::gateway_api_definition::http::{
     AllPathPatterns, CompiledHttpApiDefinition, CompiledRoute, MethodPattern, RouteRequest,
 };
 use crate::gateway_api_definition::{ApiDefinitionId, ApiVersion};
-use crate::gateway_api_deployment::ApiSite;
+use crate::gateway_api_deployment::{ApiSite, TrafficSplit};
 use crate::gateway_binding::{
     FileServerBinding, FileServerBindingCompiled, GatewayBinding, GatewayBindingCompiled,
-    HttpHandlerBinding, HttpHandlerBindingCompiled, StaticBinding, SwaggerUiBinding, WorkerBinding,
-    WorkerBindingCompiled,
+    HttpHandlerBinding, HttpHandlerBindingCompiled, IngressProtocolType, ResponseStreamType,
+    StaticBinding, SwaggerUiBinding, WorkerBinding, WorkerBindingCompiled,
+};
+use crate::gateway_middleware::{
+    CorsPreflightExpr, HttpCors, HttpMiddleware, HttpMiddlewares, HttpTransformation,
 };
-use crate::gateway_middleware::{CorsPreflightExpr, HttpCors, HttpMiddleware, HttpMiddlewares};
 use crate::gateway_security::{
     Provider, SecurityScheme, SecuritySchemeIdentifier, SecuritySchemeReference,
     SecuritySchemeWithProviderMetadata,
@@ -33,8 +35,9 @@ use golem_common::model::GatewayBindingType;
 use golem_service_base::model::ComponentName;
 use openidconnect::{ClientId, ClientSecret, RedirectUrl, Scope};
 use poem_openapi::*;
-use rib::{RibInputTypeInfo, RibOutputTypeInfo};
+use rib::{RibExecutionLimits, RibInputTypeInfo, RibOutputTypeInfo};
 use serde::{Deserialize, Serialize};
+use std::ops::Deref;
 use std::result::Result;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
@@ -60,6 +63,7 @@ pub struct ApiDeployment {
 pub struct ApiDefinitionInfo {
     pub id: ApiDefinitionId,
     pub version: ApiVersion,
+    pub traffic_split: Option<TrafficSplit>,
 }
 
 // Mostly this data structures that represents the actual incoming request
@@ -75,6 +79,7 @@ pub struct HttpApiDefinitionRequest {
     pub routes: Vec<RouteRequestData>,
     #[serde(default)]
     pub draft: bool,
+    pub rib_execution_limits: Option<RibExecutionLimitsData>,
 }
 
 impl HttpApiDefinitionRequest {
@@ -104,11 +109,46 @@ impl HttpApiDefinitionRequest {
                 version: self.version,
                 routes,
                 draft: self.draft,
+                rib_execution_limits: self.rib_execution_limits.map(RibExecutionLimits::from),
             },
         )
     }
 }
 
+// Limits of the Rib scripts of an API definition. Limits that are not set
+// fall back to the ones configured for the worker service.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct RibExecutionLimitsData {
+    pub max_instructions: Option<u64>,
+    pub max_stack_depth: Option<u64>,
+    pub max_list_size: Option<u64>,
+    pub max_worker_calls: Option<u64>,
+}
+
+impl From<RibExecutionLimitsData> for RibExecutionLimits {
+    fn from(value: RibExecutionLimitsData) -> Self {
+        RibExecutionLimits {
+            max_instructions: value.max_instructions,
+            max_stack_depth: value.max_stack_depth.map(|value| value as usize),
+            max_list_size: value.max_list_size.map(|value| value as usize),
+            max_worker_calls: value.max_worker_calls,
+        }
+    }
+}
+
+impl From<RibExecutionLimits> for RibExecutionLimitsData {
+    fn from(value: RibExecutionLimits) -> Self {
+        RibExecutionLimitsData {
+            max_instructions: value.max_instructions,
+            max_stack_depth: value.max_stack_depth.map(|value| value as u64),
+            max_list_size: value.max_list_size.map(|value| value as u64),
+            max_worker_calls: value.max_worker_calls,
+        }
+    }
+}
+
 // Mostly this data structures that represents the actual incoming request
 // exist due to the presence of complicated Expr data type in gateway_api_definition::ApiDefinition.
 // Consider them to be otherwise same
@@ -194,6 +234,7 @@ pub struct HttpApiDefinitionResponseData {
     #[serde(default)]
     pub draft: bool,
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub rib_execution_limits: Option<RibExecutionLimitsData>,
 }
 
 impl HttpApiDefinitionResponseData {
@@ -219,6 +260,7 @@ impl HttpApiDefinitionResponseData {
             routes,
             draft: value.draft,
             created_at: Some(value.created_at),
+            rib_execution_limits: value.rib_execution_limits.map(RibExecutionLimitsData::from),
         })
     }
 }
@@ -231,6 +273,7 @@ pub struct RouteRequestData {
     pub path: String,
     pub binding: GatewayBindingData,
     pub security: Option<String>,
+    pub transformation: Option<HttpTransformation>,
 }
 
 impl RouteRequestData {
@@ -250,6 +293,7 @@ impl RouteRequestData {
             path,
             binding,
             security,
+            transformation: self.transformation,
         })
     }
 }
@@ -262,6 +306,7 @@ pub struct RouteResponseData {
     pub path: String,
     pub security: Option<String>,
     pub binding: GatewayBindingResponseData,
+    pub transformation: Option<HttpTransformation>,
 }
 
 impl RouteResponseData {
@@ -271,6 +316,10 @@ impl RouteResponseData {
     ) -> Result<Self, String> {
         let method = value.method;
         let path = value.path.to_string();
+        let transformation = value
+            .middlewares
+            .as_ref()
+            .and_then(|middlewares| middlewares.get_transformation_middleware());
         let security = value.middlewares.and_then(|middlewares| {
             middlewares
                 .get_http_authentication_middleware()
@@ -292,6 +341,7 @@ impl RouteResponseData {
                 conversion_ctx,
             )
             .await?,
+            transformation,
         })
     }
 }
@@ -338,6 +388,10 @@ pub struct GatewayBindingData {
     pub response: Option<String>,
     // For binding type - worker/default
     pub invocation_context: Option<String>,
+    // For binding type - worker/default
+    pub response_stream: Option<ResponseStreamType>,
+    // For binding type - worker/default
+    pub ingress_protocol: Option<IngressProtocolType>,
 }
 
 impl GatewayBindingData {
@@ -378,6 +432,18 @@ impl GatewayBindingData {
                 };
 
                 if v == Some(GatewayBindingType::FileServer) {
+                    if self.response_stream.is_some() {
+                        return Err(
+                            "Response stream is only supported for default bindings".to_string()
+                        );
+                    }
+
+                    if self.ingress_protocol.is_some() {
+                        return Err(
+                            "Ingress protocol is only supported for default bindings".to_string()
+                        );
+                    }
+
                     let file_server_worker_binding = FileServerBinding {
                         component_id: VersionedComponentId {
                             component_id: component_view.id,
@@ -393,6 +459,12 @@ impl GatewayBindingData {
                         file_server_worker_binding,
                     )))
                 } else {
+                    if self.response_stream.is_some() && self.ingress_protocol.is_some() {
+                        return Err(
+                            "Response stream is not supported for gRPC bindings".to_string()
+                        );
+                    }
+
                     let default_worker_binding = WorkerBinding {
                         component_id: VersionedComponentId {
                             component_id: component_view.id,
@@ -401,6 +473,8 @@ impl GatewayBindingData {
                         idempotency_key,
                         response_mapping: response,
                         invocation_context,
+                        response_stream: self.response_stream,
+                        ingress_protocol: self.ingress_protocol,
                     };
 
                     Ok(GatewayBinding::Default(Box::new(default_worker_binding)))
@@ -470,12 +544,14 @@ impl GatewayBindingData {
 pub struct MiddlewareData {
     pub cors: Option<HttpCors>,
     pub auth: Option<SecuritySchemeReferenceData>,
+    pub transformation: Option<HttpTransformation>,
 }
 
 impl From<HttpMiddlewares> for MiddlewareData {
     fn from(value: HttpMiddlewares) -> Self {
         let mut cors = None;
         let mut auth = None;
+        let mut transformation = None;
 
         for i in value.0.iter() {
             match i {
@@ -486,10 +562,17 @@ impl From<HttpMiddlewares> for MiddlewareData {
                     );
                     auth = Some(security_scheme_reference)
                 }
+                HttpMiddleware::Transform(transformation0) => {
+                    transformation = Some(transformation0.deref().clone())
+                }
             }
         }
 
-        MiddlewareData { cors, auth }
+        MiddlewareData {
+            cors,
+            auth,
+            transformation,
+        }
     }
 }
 
@@ -549,6 +632,8 @@ pub struct GatewayBindingResponseData {
     pub idempotency_key_input: Option<RibInputTypeInfo>, // If bindingType is Default or FilerServer
     pub cors_preflight: Option<HttpCors>, // If bindingType is CorsPreflight (internally, a static binding)
     pub response_mapping_output: Option<RibOutputTypeInfo>, // If bindingType is Default or FileServer
+    pub response_stream: Option<ResponseStreamType>,        // If bindingType is Default
+    pub ingress_protocol: Option<IngressProtocolType>,      // If bindingType is Default
 }
 
 impl GatewayBindingResponseData {
@@ -605,6 +690,8 @@ impl GatewayBindingResponseData {
                     idempotency_key_input: None,
                     cors_preflight: static_binding.get_cors_preflight(),
                     response_mapping_output: None,
+                    response_stream: None,
+                    ingress_protocol: None,
                 })
             }
             GatewayBindingCompiled::SwaggerUi(_) => Ok(GatewayBindingResponseData {
@@ -619,6 +706,8 @@ impl GatewayBindingResponseData {
                 idempotency_key_input: None,
                 cors_preflight: None,
                 response_mapping_output: None,
+                response_stream: None,
+                ingress_protocol: None,
             }),
         }
     }
@@ -663,6 +752,8 @@ impl GatewayBindingResponseData {
                 .map(|idempotency_key_compiled| idempotency_key_compiled.rib_input),
             cors_preflight: None,
             response_mapping_output: worker_binding.response_compiled.rib_output,
+            response_stream: None,
+            ingress_protocol: None,
         })
     }
 
@@ -703,6 +794,8 @@ impl GatewayBindingResponseData {
                 .map(|idempotency_key_compiled| idempotency_key_compiled.rib_input),
             cors_preflight: None,
             response_mapping_output: worker_binding.response_compiled.rib_output,
+            response_stream: worker_binding.response_stream,
+            ingress_protocol: worker_binding.ingress_protocol,
         })
     }
 
@@ -739,6 +832,8 @@ impl GatewayBindingResponseData {
                 .map(|idempotency_key_compiled| idempotency_key_compiled.rib_input),
             cors_preflight: None,
             response_mapping_output: None,
+            response_stream: None,
+            ingress_protocol: None,
         })
     }
 }
@@ -749,6 +844,7 @@ impl From<crate::gateway_api_deployment::ApiDeployment> for ApiDeployment {
             .api_definition_keys
             .into_iter()
             .map(|key| ApiDefinitionInfo {
+                traffic_split: value.traffic_splits.get(&key).cloned(),
                 id: key.id,
                 version: key.version,
             })
@@ -77,5 +77,6 @@ pub fn custom_http_request_api(services: &Services) -> CustomHttpRequestApi {
         services.file_server_binding_handler.clone(),
         services.http_handler_binding_handler.clone(),
         services.gateway_session_store.clone(),
+        services.rib_execution_limits.clone(),
     )
 }
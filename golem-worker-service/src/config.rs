@@ -23,6 +23,7 @@ use golem_service_base::clients::RemoteServiceConfig;
 use golem_service_base::config::BlobStorageConfig;
 use golem_service_base::service::routing_table::RoutingTableConfig;
 use http::Uri;
+use rib::RibExecutionLimits;
 use serde::{Deserialize, Serialize};
 use std::fmt::{Debug, Write};
 use std::path::PathBuf;
@@ -48,6 +49,7 @@ pub struct WorkerServiceConfig {
     pub domain_records: DomainRecordsConfig,
     pub cloud_service: RemoteServiceConfig,
     pub cors_origin_regex: String,
+    pub rib_execution_limits: RibExecutionLimitsConfig,
 }
 
 impl WorkerServiceConfig {
@@ -121,6 +123,12 @@ impl SafeDisplay for WorkerServiceConfig {
             self.cloud_service.to_safe_string_indented()
         );
         let _ = writeln!(&mut result, "CORS origin regex: {}", self.cors_origin_regex);
+        let _ = writeln!(&mut result, "Rib execution limits:");
+        let _ = writeln!(
+            &mut result,
+            "{}",
+            self.rib_execution_limits.to_safe_string_indented()
+        );
 
         result
     }
@@ -154,6 +162,7 @@ impl Default for WorkerServiceConfig {
             domain_records: DomainRecordsConfig::default(),
             cloud_service: RemoteServiceConfig::default(),
             cors_origin_regex: "https://*.golem.cloud".to_string(),
+            rib_execution_limits: RibExecutionLimitsConfig::default(),
         }
     }
 }
@@ -369,6 +378,49 @@ fn domain_match(domain: &str, domain_cfg: &str) -> bool {
     }
 }
 
+// Limits applied to the evaluation of Rib scripts by the API gateway,
+// so that a single route cannot use the resources of the worker service without bounds.
+// API definitions can set their own limits, these are used for the ones they do not set.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RibExecutionLimitsConfig {
+    pub max_instructions: u64,
+    pub max_stack_depth: usize,
+    pub max_list_size: usize,
+    pub max_worker_calls: u64,
+}
+
+impl RibExecutionLimitsConfig {
+    pub fn to_rib_execution_limits(&self) -> RibExecutionLimits {
+        RibExecutionLimits::default()
+            .with_max_instructions(self.max_instructions)
+            .with_max_stack_depth(self.max_stack_depth)
+            .with_max_list_size(self.max_list_size)
+            .with_max_worker_calls(self.max_worker_calls)
+    }
+}
+
+impl SafeDisplay for RibExecutionLimitsConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        let _ = writeln!(&mut result, "max instructions: {}", self.max_instructions);
+        let _ = writeln!(&mut result, "max stack depth: {}", self.max_stack_depth);
+        let _ = writeln!(&mut result, "max list size: {}", self.max_list_size);
+        let _ = writeln!(&mut result, "max worker calls: {}", self.max_worker_calls);
+        result
+    }
+}
+
+impl Default for RibExecutionLimitsConfig {
+    fn default() -> Self {
+        Self {
+            max_instructions: 10_000_000,
+            max_stack_depth: 256,
+            max_list_size: 100_000,
+            max_worker_calls: 1000,
+        }
+    }
+}
+
 const CONFIG_FILE_NAME: &str = "config/worker-service.toml";
 
 pub fn make_worker_service_config_loader() -> ConfigLoader<WorkerServiceConfig> {
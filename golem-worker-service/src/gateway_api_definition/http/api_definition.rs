@@ -12,7 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::gateway_api_definition::http::path_pattern_parser::parse_path_pattern;
+use crate::gateway_api_definition::http::path_pattern_parser::{
+    parse_path_pattern, path_pattern_error_message,
+};
 use crate::gateway_api_definition::http::{
     HttpApiDefinitionRequest, OpenApiHttpApiDefinition, RouteRequest,
 };
@@ -27,7 +29,9 @@ use crate::gateway_binding::{HttpHandlerBindingCompiled, WorkerBindingCompiled};
 use crate::gateway_middleware::{
     HttpAuthenticationMiddleware, HttpCors, HttpMiddleware, HttpMiddlewares,
 };
-use crate::gateway_rib_compiler::ComponentDependencyWithAgentInfo;
+use crate::gateway_rib_compiler::{
+    CompiledRibCache, ComponentDependencyWithAgentInfo, PathParamTypes,
+};
 use crate::gateway_security::SecuritySchemeReference;
 use crate::service::gateway::api_definition::ApiDefinitionError;
 use crate::service::gateway::api_definition_validator::ValidationErrors;
@@ -38,9 +42,9 @@ use golem_common::model::auth::Namespace;
 use golem_common::model::component::VersionedComponentId;
 use golem_common::model::component_metadata::ComponentMetadata;
 use golem_service_base::model::Component;
-use golem_wasm::analysis::AnalysedType;
+use golem_wasm::analysis::{analysed_type, AnalysedType};
 use poem_openapi::Enum;
-use rib::{ComponentDependencyKey, RibCompilationError, RibInputTypeInfo};
+use rib::{ComponentDependencyKey, RibCompilationError, RibExecutionLimits, RibInputTypeInfo};
 use serde::de::Error;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value;
@@ -58,6 +62,7 @@ pub struct HttpApiDefinition {
     pub routes: Vec<Route>,
     pub draft: bool,
     pub created_at: chrono::DateTime<chrono::Utc>,
+    pub rib_execution_limits: Option<RibExecutionLimits>,
 }
 
 impl HttpApiDefinition {
@@ -122,6 +127,11 @@ impl HttpApiDefinition {
                 http_middlewares.push(HttpMiddleware::authenticate_request(security_scheme));
             }
 
+            // Transformations apply to the authenticated request only
+            if let Some(transformation) = &route.transformation {
+                http_middlewares.push(HttpMiddleware::transform(transformation.clone()));
+            }
+
             routes.push(Route {
                 method: route.method,
                 path: route.path,
@@ -141,6 +151,7 @@ impl HttpApiDefinition {
             routes,
             draft: request.draft,
             created_at,
+            rib_execution_limits: request.rib_execution_limits,
         };
 
         transform_http_api_definition(&mut http_api_definition).map_err(|error| {
@@ -160,6 +171,7 @@ impl From<HttpApiDefinition> for HttpApiDefinitionRequest {
             version: value.version(),
             routes: value.routes.into_iter().map(RouteRequest::from).collect(),
             draft: value.draft,
+            rib_execution_limits: value.rib_execution_limits,
         }
     }
 }
@@ -185,6 +197,7 @@ impl From<CompiledHttpApiDefinition> for HttpApiDefinition {
                 .collect(),
             draft: compiled_http_api_definition.draft,
             created_at: compiled_http_api_definition.created_at,
+            rib_execution_limits: compiled_http_api_definition.rib_execution_limits,
         }
     }
 }
@@ -202,6 +215,7 @@ pub struct CompiledHttpApiDefinition {
     pub draft: bool,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub namespace: Namespace,
+    pub rib_execution_limits: Option<RibExecutionLimits>,
 }
 
 impl CompiledHttpApiDefinition {
@@ -228,6 +242,7 @@ impl CompiledHttpApiDefinition {
             draft: self.draft,
             created_at: self.created_at,
             namespace: self.namespace.clone(),
+            rib_execution_limits: self.rib_execution_limits.clone(),
         }
     }
 
@@ -236,11 +251,13 @@ impl CompiledHttpApiDefinition {
         metadata_dictionary: &ComponentMetadataDictionary,
         namespace: &Namespace,
         conversion_context: &BoxConversionContext<'_>,
+        compiled_rib_cache: &CompiledRibCache,
     ) -> Result<Self, RouteCompilationErrors> {
         let mut compiled_routes = vec![];
 
         for route in &http_api_definition.routes {
-            let compiled_route = CompiledRoute::from_route(route, metadata_dictionary)?;
+            let compiled_route =
+                CompiledRoute::from_route(route, metadata_dictionary, compiled_rib_cache)?;
             compiled_routes.push(compiled_route);
         }
 
@@ -251,6 +268,7 @@ impl CompiledHttpApiDefinition {
             draft: http_api_definition.draft,
             created_at: http_api_definition.created_at,
             namespace: namespace.clone(),
+            rib_execution_limits: http_api_definition.rib_execution_limits.clone(),
         };
         // Update SwaggerUI routes with actual OpenAPI spec
         result.update_swagger_ui_openapi_specs(conversion_context);
@@ -435,6 +453,80 @@ pub struct LiteralInfo(pub String);
 #[desert(evolution())]
 pub struct VarInfo {
     pub key_name: String,
+    pub constraint: Option<PathParamConstraint>,
+}
+
+/// Restricts the path segments matched by a path variable, written as `{name:constraint}`.
+/// Typed constraints also determine the type of the variable in Rib, which is a string otherwise.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, BinaryCodec)]
+#[desert(evolution())]
+pub enum PathParamConstraint {
+    Int,
+    U32,
+    U64,
+    S32,
+    S64,
+    Bool,
+    Uuid,
+    Regex(String),
+}
+
+impl PathParamConstraint {
+    /// The type of the path variable in Rib, if it is not a string.
+    pub fn rib_type(&self) -> Option<AnalysedType> {
+        match self {
+            PathParamConstraint::Int | PathParamConstraint::S64 => Some(analysed_type::s64()),
+            PathParamConstraint::U32 => Some(analysed_type::u32()),
+            PathParamConstraint::U64 => Some(analysed_type::u64()),
+            PathParamConstraint::S32 => Some(analysed_type::s32()),
+            PathParamConstraint::Bool => Some(analysed_type::bool()),
+            PathParamConstraint::Uuid | PathParamConstraint::Regex(_) => None,
+        }
+    }
+}
+
+impl Display for PathParamConstraint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PathParamConstraint::Int => write!(f, "int"),
+            PathParamConstraint::U32 => write!(f, "u32"),
+            PathParamConstraint::U64 => write!(f, "u64"),
+            PathParamConstraint::S32 => write!(f, "s32"),
+            PathParamConstraint::S64 => write!(f, "s64"),
+            PathParamConstraint::Bool => write!(f, "bool"),
+            PathParamConstraint::Uuid => write!(f, "uuid"),
+            PathParamConstraint::Regex(regex) => write!(f, "regex({regex})"),
+        }
+    }
+}
+
+impl FromStr for PathParamConstraint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "int" => Ok(PathParamConstraint::Int),
+            "u32" => Ok(PathParamConstraint::U32),
+            "u64" => Ok(PathParamConstraint::U64),
+            "s32" => Ok(PathParamConstraint::S32),
+            "s64" => Ok(PathParamConstraint::S64),
+            "bool" => Ok(PathParamConstraint::Bool),
+            "uuid" => Ok(PathParamConstraint::Uuid),
+            other => match other
+                .strip_prefix("regex(")
+                .and_then(|rest| rest.strip_suffix(')'))
+            {
+                Some(regex) => {
+                    regex::Regex::new(regex)
+                        .map_err(|err| format!("Invalid regex in path parameter: {err}"))?;
+                    Ok(PathParamConstraint::Regex(regex.to_string()))
+                }
+                None => Err(format!(
+                    "Unknown path parameter constraint: {other}. Expected one of int, u32, u64, s32, s64, bool, uuid or regex(<pattern>)"
+                )),
+            },
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, BinaryCodec)]
@@ -488,7 +580,7 @@ impl FromStr for AllPathPatterns {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         parse_path_pattern(s)
-            .map_err(|err| err.to_string())
+            .map_err(path_pattern_error_message)
             .and_then(|(leftover, result)| {
                 if !leftover.is_empty() {
                     Err("Failed to parse path".to_string())
@@ -544,12 +636,24 @@ impl PathPattern {
     pub fn var(value: impl Into<String>) -> PathPattern {
         PathPattern::Var(VarInfo {
             key_name: value.into(),
+            constraint: None,
+        })
+    }
+
+    pub fn constrained_var(
+        value: impl Into<String>,
+        constraint: PathParamConstraint,
+    ) -> PathPattern {
+        PathPattern::Var(VarInfo {
+            key_name: value.into(),
+            constraint: Some(constraint),
         })
     }
 
     pub fn catch_all_var(value: impl Into<String>) -> PathPattern {
         PathPattern::CatchAllVar(VarInfo {
             key_name: value.into(),
+            constraint: None,
         })
     }
 }
@@ -558,6 +662,10 @@ impl Display for PathPattern {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             PathPattern::Literal(info) => write!(f, "{}", info.0),
+            PathPattern::Var(VarInfo {
+                key_name,
+                constraint: Some(constraint),
+            }) => write!(f, "{{{key_name}:{constraint}}}"),
             PathPattern::Var(info) => write!(f, "{{{}}}", info.key_name),
             PathPattern::CatchAllVar(info) => write!(f, "{{+{}}}", info.key_name),
         }
@@ -715,6 +823,7 @@ impl CompiledRoute {
     pub fn from_route(
         route: &Route,
         metadata_dictionary: &ComponentMetadataDictionary,
+        compiled_rib_cache: &CompiledRibCache,
     ) -> Result<CompiledRoute, RouteCompilationErrors> {
         let query_params = route.path.query_params.as_ref();
         let path_params = route
@@ -727,6 +836,7 @@ impl CompiledRoute {
                 _ => None,
             })
             .collect::<Vec<_>>();
+        let path_param_types = PathParamTypes::from_path(&route.path);
 
         match &route.binding {
             GatewayBinding::Default(worker_binding) => {
@@ -745,6 +855,8 @@ impl CompiledRoute {
                 let binding = WorkerBindingCompiled::from_raw_worker_binding(
                     worker_binding,
                     &component_dependency,
+                    &path_param_types,
+                    compiled_rib_cache,
                 )
                 .map_err(RouteCompilationErrors::RibError)?;
 
@@ -782,6 +894,8 @@ impl CompiledRoute {
                 let binding = FileServerBindingCompiled::from_raw_file_server_worker_binding(
                     worker_binding,
                     &component_dependency_with_agent_types,
+                    &path_param_types,
+                    compiled_rib_cache,
                 )
                 .map_err(RouteCompilationErrors::RibError)?;
 
@@ -803,9 +917,12 @@ impl CompiledRoute {
             }
 
             GatewayBinding::HttpHandler(http_handler_binding) => {
-                let binding =
-                    HttpHandlerBindingCompiled::from_raw_http_handler_binding(http_handler_binding)
-                        .map_err(RouteCompilationErrors::RibError)?;
+                let binding = HttpHandlerBindingCompiled::from_raw_http_handler_binding(
+                    http_handler_binding,
+                    &path_param_types,
+                    compiled_rib_cache,
+                )
+                .map_err(RouteCompilationErrors::RibError)?;
 
                 Self::validate_rib_scripts(
                     query_params,
@@ -15,7 +15,9 @@
 use crate::gateway_api_definition::http::{AllPathPatterns, MethodPattern, Route};
 use crate::gateway_api_definition::{ApiDefinitionId, ApiVersion};
 use crate::gateway_binding::GatewayBinding;
+use crate::gateway_middleware::HttpTransformation;
 use crate::gateway_security::SecuritySchemeReference;
+use rib::RibExecutionLimits;
 
 // HttpApiDefinitionRequest corresponds to the user facing http api definition.
 // It has security at the global level, which is following OpenAPI style of defining security at the root level.
@@ -26,6 +28,9 @@ pub struct HttpApiDefinitionRequest {
     pub version: ApiVersion,
     pub routes: Vec<RouteRequest>,
     pub draft: bool,
+    // Limits of the Rib scripts of this definition. Limits that are not set
+    // fall back to the ones configured for the worker service.
+    pub rib_execution_limits: Option<RibExecutionLimits>,
 }
 
 // In a RouteRequest, security is defined at the outer level
@@ -38,6 +43,7 @@ pub struct RouteRequest {
     pub path: AllPathPatterns,
     pub binding: GatewayBinding,
     pub security: Option<SecuritySchemeReference>,
+    pub transformation: Option<HttpTransformation>,
 }
 
 impl From<Route> for RouteRequest {
@@ -47,12 +53,18 @@ impl From<Route> for RouteRequest {
             .clone()
             .and_then(|x| x.get_http_authentication_middleware());
 
+        let transformation = value
+            .middlewares
+            .clone()
+            .and_then(|x| x.get_transformation_middleware());
+
         RouteRequest {
             method: value.method,
             path: value.path,
             binding: value.binding,
             security: security_middleware
                 .map(|x| SecuritySchemeReference::from(x.security_scheme_with_metadata)),
+            transformation,
         }
     }
 }
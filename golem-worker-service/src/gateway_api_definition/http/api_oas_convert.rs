@@ -18,8 +18,10 @@ use crate::gateway_api_definition::http::{
 };
 use crate::gateway_api_definition::{ApiDefinitionId, ApiVersion};
 use crate::gateway_binding::{
-    GatewayBindingCompiled, ResponseMappingCompiled, StaticBinding, WorkerNameCompiled,
+    GatewayBindingCompiled, IngressProtocolType, ResponseMappingCompiled, ResponseStreamType,
+    StaticBinding, WorkerNameCompiled,
 };
+use crate::gateway_execution::grpc_response::GRPC_CONTENT_TYPE;
 use crate::gateway_middleware::{CorsPreflightExpr, HttpCors};
 use crate::service::gateway::BoxConversionContext;
 use golem_common::model::component::VersionedComponentId;
@@ -34,6 +36,7 @@ use serde::{Deserialize, Serialize};
 const GOLEM_API_DEFINITION_ID_EXTENSION: &str = "x-golem-api-definition-id";
 const GOLEM_API_DEFINITION_VERSION: &str = "x-golem-api-definition-version";
 const GOLEM_API_GATEWAY_BINDING: &str = "x-golem-api-gateway-binding";
+const GOLEM_API_GATEWAY_TRANSFORMATION: &str = "x-golem-api-gateway-transformation";
 
 // OpenApiHttpApiDefinitionResponse is a wrapper id, version and open api schema as yaml string
 // OpenApiHttpApiDefinition struct is defined using crate openapiv3 as OPENAPI+GOLEMEXTENSIONS
@@ -193,6 +196,7 @@ async fn create_operation(
     add_request_body(&mut operation, route);
     add_responses(&mut operation, route);
     add_binding_info(&mut operation, route, conversion_ctx).await?;
+    add_transformation(&mut operation, route)?;
     add_security(&mut operation, route, security_schemes);
 
     Ok(operation)
@@ -418,6 +422,11 @@ fn create_header_parameter(
 fn add_request_body(operation: &mut openapiv3::Operation, route: &CompiledRoute) {
     // Only add request body if we have a binding that contains response_mapping
     match &route.binding {
+        GatewayBindingCompiled::Worker(worker_binding)
+            if worker_binding.ingress_protocol == Some(IngressProtocolType::Grpc) =>
+        {
+            // gRPC request messages are not JSON, they are described by the exported .proto file
+        }
         GatewayBindingCompiled::Worker(worker_binding) => {
             if let Some(request_body) =
                 create_request_body(route, &worker_binding.response_compiled.rib_input)
@@ -449,6 +458,14 @@ fn determine_response_schema_and_content_type(
     route: &CompiledRoute,
 ) -> (Option<openapiv3::Schema>, String) {
     if let GatewayBindingCompiled::Worker(worker_binding) = &route.binding {
+        if worker_binding.response_stream == Some(ResponseStreamType::Sse) {
+            // Events are plain text frames, the result is sent as the data of the last event
+            return (None, "text/event-stream".to_string());
+        }
+        if worker_binding.ingress_protocol == Some(IngressProtocolType::Grpc) {
+            // gRPC response messages are described by the exported .proto file
+            return (None, GRPC_CONTENT_TYPE.to_string());
+        }
         if let Some(output_info) = &worker_binding.response_compiled.rib_output {
             if let AnalysedType::Record(record) = &output_info.analysed_type {
                 let (headers_opt, body_opt, status_opt) = extract_response_fields(&record.fields);
@@ -573,6 +590,26 @@ async fn add_binding_info(
     Ok(())
 }
 
+// Helper function: Adds transformation to the operation
+fn add_transformation(
+    operation: &mut openapiv3::Operation,
+    route: &CompiledRoute,
+) -> Result<(), String> {
+    if let Some(transformation) = route
+        .middlewares
+        .as_ref()
+        .and_then(|middlewares| middlewares.get_transformation_middleware())
+    {
+        let transformation = serde_json::to_value(transformation).map_err(|err| {
+            format!("Failed to serialize {GOLEM_API_GATEWAY_TRANSFORMATION}: {err}")
+        })?;
+        operation
+            .extensions
+            .insert(GOLEM_API_GATEWAY_TRANSFORMATION.to_string(), transformation);
+    }
+    Ok(())
+}
+
 // Helper function: Adds security to the operation
 fn add_security(
     operation: &mut openapiv3::Operation,
@@ -804,6 +841,23 @@ async fn create_binding_info(
         );
     }
 
+    if let GatewayBindingCompiled::Worker(worker_binding) = &route.binding {
+        if let Some(response_stream) = &worker_binding.response_stream {
+            binding_info.insert(
+                "response-stream".to_string(),
+                serde_json::to_value(response_stream)
+                    .map_err(|e| format!("Failed to serialize response stream: {e}"))?,
+            );
+        }
+        if let Some(ingress_protocol) = &worker_binding.ingress_protocol {
+            binding_info.insert(
+                "ingress-protocol".to_string(),
+                serde_json::to_value(ingress_protocol)
+                    .map_err(|e| format!("Failed to serialize ingress protocol: {e}"))?,
+            );
+        }
+    }
+
     // Add CORS preflight response for Cors Binding
     if let Some(cors) = data.cors_preflight {
         let cors_expr = CorsPreflightExpr::from_cors(cors);
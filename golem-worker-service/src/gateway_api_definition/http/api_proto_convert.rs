@@ -0,0 +1,568 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::gateway_api_definition::http::{
+    AllPathPatterns, CompiledHttpApiDefinition, MethodPattern, PathPattern,
+};
+use crate::gateway_api_definition::{ApiDefinitionId, ApiVersion};
+use crate::gateway_binding::{GatewayBindingCompiled, IngressProtocolType, WorkerBindingCompiled};
+use crate::gateway_execution::grpc_codec::{is_message, needs_wrapper};
+use golem_wasm::analysis::analysed_type::record;
+use golem_wasm::analysis::AnalysedType;
+use heck::{ToShoutySnakeCase, ToSnakeCase, ToUpperCamelCase};
+use rib::{RibInputTypeInfo, RibOutputTypeInfo};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+
+const EMPTY_MESSAGE: &str = "Empty";
+
+// ProtoHttpApiDefinitionResponse is a wrapper of id, version and the .proto file of the gRPC routes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, poem_openapi::Object)]
+pub struct ProtoHttpApiDefinitionResponse {
+    pub id: ApiDefinitionId,
+    pub version: ApiVersion,
+    pub proto: String,
+}
+
+impl ProtoHttpApiDefinitionResponse {
+    pub fn from_compiled_http_api_definition(
+        definition: &CompiledHttpApiDefinition,
+    ) -> Result<Self, String> {
+        Ok(ProtoHttpApiDefinitionResponse {
+            id: definition.id.clone(),
+            version: definition.version.clone(),
+            proto: to_proto_file(definition)?,
+        })
+    }
+}
+
+// A route with `grpc` ingress protocol is a method of a gRPC service.
+// Its path is `/<package>.<service>/<method>`, which is what gRPC clients call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GrpcMethod {
+    pub package: Option<String>,
+    pub service: String,
+    pub method: String,
+}
+
+impl GrpcMethod {
+    pub fn from_route(method: &MethodPattern, path: &AllPathPatterns) -> Result<Self, String> {
+        if method != &MethodPattern::Post {
+            return Err("gRPC routes must use the POST method".to_string());
+        }
+
+        match (path.path_patterns.as_slice(), path.query_params.is_empty()) {
+            ([PathPattern::Literal(service), PathPattern::Literal(method)], true) => {
+                let (package, service) = match service.0.rsplit_once('.') {
+                    Some((package, service)) => (Some(package.to_string()), service.to_string()),
+                    None => (None, service.0.clone()),
+                };
+
+                let is_valid = package
+                    .iter()
+                    .flat_map(|package| package.split('.'))
+                    .chain([service.as_str(), method.0.as_str()])
+                    .all(is_identifier);
+
+                if is_valid {
+                    Ok(GrpcMethod {
+                        package,
+                        service,
+                        method: method.0.clone(),
+                    })
+                } else {
+                    Err(format!("Invalid gRPC method path: {path}"))
+                }
+            }
+            _ => Err(format!(
+                "gRPC routes must have a path of `/<package>.<service>/<method>`, found: {path}"
+            )),
+        }
+    }
+}
+
+fn is_identifier(value: &str) -> bool {
+    let mut chars = value.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+// The message of a gRPC request is the `request.body` of the response mapping
+pub fn grpc_request_type(rib_input: &RibInputTypeInfo) -> Option<AnalysedType> {
+    match rib_input.get("request") {
+        Some(AnalysedType::Record(request)) => request
+            .fields
+            .iter()
+            .find(|field| field.name == "body")
+            .map(|field| field.typ.clone()),
+        _ => None,
+    }
+}
+
+// The message of a gRPC response is the `body` of the response mapping if it has one,
+// similar to http responses. Status and headers are not part of the message.
+pub fn grpc_response_type(rib_output: Option<&RibOutputTypeInfo>) -> Option<AnalysedType> {
+    let output = &rib_output?.analysed_type;
+
+    match output {
+        AnalysedType::Record(record)
+            if record
+                .fields
+                .iter()
+                .any(|field| ["status", "headers", "body"].contains(&field.name.as_str())) =>
+        {
+            record
+                .fields
+                .iter()
+                .find(|field| field.name == "body")
+                .map(|field| field.typ.clone())
+        }
+        output => Some(output.clone()),
+    }
+}
+
+pub fn grpc_routes(
+    definition: &CompiledHttpApiDefinition,
+) -> Result<Vec<(GrpcMethod, &WorkerBindingCompiled)>, String> {
+    let mut result = vec![];
+
+    for route in &definition.routes {
+        if let GatewayBindingCompiled::Worker(worker_binding) = &route.binding {
+            if worker_binding.ingress_protocol == Some(IngressProtocolType::Grpc) {
+                result.push((
+                    GrpcMethod::from_route(&route.method, &route.path)?,
+                    worker_binding.as_ref(),
+                ));
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+// Renders a .proto file describing the gRPC routes of an API definition, so that
+// clients can generate their stubs. All gRPC routes of a definition share the same package.
+pub fn to_proto_file(definition: &CompiledHttpApiDefinition) -> Result<String, String> {
+    let routes = grpc_routes(definition)?;
+
+    if routes.is_empty() {
+        return Err(format!(
+            "API definition {} has no gRPC routes",
+            definition.id.0
+        ));
+    }
+
+    let package = routes[0].0.package.clone();
+
+    if let Some((method, _)) = routes.iter().find(|(method, _)| method.package != package) {
+        return Err(format!(
+            "All gRPC routes must be in the same package, found {} and {}",
+            package.as_deref().unwrap_or("<none>"),
+            method.package.as_deref().unwrap_or("<none>")
+        ));
+    }
+
+    let mut schema = ProtoSchema::default();
+    let mut services: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+    for (method, worker_binding) in routes {
+        let request = schema.rpc_message(
+            &format!("{}Request", method.method),
+            grpc_request_type(&worker_binding.response_compiled.rib_input),
+        )?;
+        let response = schema.rpc_message(
+            &format!("{}Response", method.method),
+            grpc_response_type(worker_binding.response_compiled.rib_output.as_ref()),
+        )?;
+
+        services.entry(method.service).or_default().push(format!(
+            "  rpc {}({request}) returns ({response});",
+            method.method
+        ));
+    }
+
+    let mut proto = String::from("syntax = \"proto3\";\n\n");
+
+    if let Some(package) = package {
+        let _ = writeln!(proto, "package {package};\n");
+    }
+
+    for (service, rpcs) in services {
+        let _ = writeln!(proto, "service {service} {{\n{}\n}}\n", rpcs.join("\n"));
+    }
+
+    proto.push_str(&schema.definitions.join("\n"));
+
+    Ok(proto)
+}
+
+enum Reserved {
+    New(String),
+    Existing(String),
+}
+
+#[derive(Default)]
+struct ProtoSchema {
+    definitions: Vec<String>,
+    names: HashMap<String, AnalysedType>,
+    empty: Option<String>,
+}
+
+impl ProtoSchema {
+    fn rpc_message(&mut self, name: &str, typ: Option<AnalysedType>) -> Result<String, String> {
+        match typ {
+            Some(typ) if is_message(&typ) => self.message(name, &typ),
+            Some(typ) => self.wrapper(name, &typ),
+            None => Ok(self.empty()),
+        }
+    }
+
+    // Unit cases and missing request or response bodies are all sent as the same empty message
+    fn empty(&mut self) -> String {
+        if let Some(name) = &self.empty {
+            return name.clone();
+        }
+
+        let name = match self.reserve(EMPTY_MESSAGE, &record(vec![])) {
+            Reserved::New(name) | Reserved::Existing(name) => name,
+        };
+
+        self.definitions.push(format!("message {name} {{}}\n"));
+        self.empty = Some(name.clone());
+
+        name
+    }
+
+    // Named types are defined once and reused, anonymous ones are named after where they are used
+    fn reserve(&mut self, hint: &str, typ: &AnalysedType) -> Reserved {
+        let base = typ.name().unwrap_or(hint).to_upper_camel_case();
+
+        let mut name = base.clone();
+        let mut suffix = 1;
+
+        loop {
+            match self.names.get(&name) {
+                Some(existing) if existing == typ && typ.name().is_some() => {
+                    return Reserved::Existing(name);
+                }
+                Some(_) => {
+                    suffix += 1;
+                    name = format!("{base}{suffix}");
+                }
+                None => {
+                    self.names.insert(name.clone(), typ.clone());
+                    return Reserved::New(name);
+                }
+            }
+        }
+    }
+
+    fn message(&mut self, hint: &str, typ: &AnalysedType) -> Result<String, String> {
+        let name = match self.reserve(hint, typ) {
+            Reserved::New(name) => name,
+            Reserved::Existing(name) => return Ok(name),
+        };
+
+        let mut body = vec![];
+
+        match typ {
+            AnalysedType::Record(record) => {
+                for (index, field) in record.fields.iter().enumerate() {
+                    body.push(self.field(&name, &field.name, index + 1, &field.typ)?);
+                }
+            }
+            AnalysedType::Tuple(tuple) => {
+                for (index, item) in tuple.items.iter().enumerate() {
+                    body.push(self.field(&name, &format!("item_{index}"), index + 1, item)?);
+                }
+            }
+            AnalysedType::Flags(flags) => {
+                for (index, flag) in flags.names.iter().enumerate() {
+                    body.push(format!("  bool {} = {};", flag.to_snake_case(), index + 1));
+                }
+            }
+            AnalysedType::Variant(variant) => {
+                let mut cases = vec![];
+                for (index, case) in variant.cases.iter().enumerate() {
+                    cases.push(self.oneof_case(&name, &case.name, index + 1, case.typ.as_ref())?);
+                }
+                body.push(format!("  oneof value {{\n{}\n  }}", cases.join("\n")));
+            }
+            AnalysedType::Result(result) => {
+                let cases = vec![
+                    self.oneof_case(&name, "ok", 1, result.ok.as_deref())?,
+                    self.oneof_case(&name, "err", 2, result.err.as_deref())?,
+                ];
+                body.push(format!("  oneof value {{\n{}\n  }}", cases.join("\n")));
+            }
+            _ => return Err(format!("{typ:?} is not a message type")),
+        }
+
+        self.definitions
+            .push(format!("message {name} {{\n{}\n}}\n", body.join("\n")));
+
+        Ok(name)
+    }
+
+    fn wrapper(&mut self, hint: &str, typ: &AnalysedType) -> Result<String, String> {
+        let name = match self.reserve(hint, typ) {
+            Reserved::New(name) => name,
+            Reserved::Existing(name) => return Ok(name),
+        };
+
+        let field = self.field(&name, "value", 1, typ)?;
+
+        self.definitions
+            .push(format!("message {name} {{\n{field}\n}}\n"));
+
+        Ok(name)
+    }
+
+    fn field(
+        &mut self,
+        parent: &str,
+        name: &str,
+        number: usize,
+        typ: &AnalysedType,
+    ) -> Result<String, String> {
+        let hint = format!("{parent}_{name}");
+
+        let (modifier, type_name) = match typ {
+            AnalysedType::List(list) => ("repeated ", self.element_type(&hint, &list.inner)?),
+            AnalysedType::Option(option) => ("optional ", self.element_type(&hint, &option.inner)?),
+            typ => ("", self.type_name(&hint, typ)?),
+        };
+
+        Ok(format!(
+            "  {modifier}{type_name} {} = {number};",
+            name.to_snake_case()
+        ))
+    }
+
+    fn oneof_case(
+        &mut self,
+        parent: &str,
+        name: &str,
+        number: usize,
+        typ: Option<&AnalysedType>,
+    ) -> Result<String, String> {
+        let type_name = match typ {
+            Some(typ) => self.element_type(&format!("{parent}_{name}"), typ)?,
+            None => self.empty(),
+        };
+
+        Ok(format!(
+            "    {type_name} {} = {number};",
+            name.to_snake_case()
+        ))
+    }
+
+    // Lists and options can't be nested directly in a repeated, optional or oneof field
+    fn element_type(&mut self, hint: &str, typ: &AnalysedType) -> Result<String, String> {
+        if needs_wrapper(typ) {
+            self.wrapper(hint, typ)
+        } else {
+            self.type_name(hint, typ)
+        }
+    }
+
+    fn type_name(&mut self, hint: &str, typ: &AnalysedType) -> Result<String, String> {
+        let name = match typ {
+            AnalysedType::Bool(_) => "bool",
+            AnalysedType::U8(_) | AnalysedType::U16(_) | AnalysedType::U32(_) => "uint32",
+            AnalysedType::U64(_) => "uint64",
+            AnalysedType::S8(_) | AnalysedType::S16(_) | AnalysedType::S32(_) => "int32",
+            AnalysedType::S64(_) => "int64",
+            AnalysedType::F32(_) => "float",
+            AnalysedType::F64(_) => "double",
+            AnalysedType::Chr(_) => "uint32",
+            AnalysedType::Str(_) => "string",
+            AnalysedType::Enum(enum_type) => {
+                let name = match self.reserve(hint, typ) {
+                    Reserved::New(name) => name,
+                    Reserved::Existing(name) => return Ok(name),
+                };
+
+                // Enum values are scoped to the package in protobuf, so they are prefixed
+                let prefix = name.to_shouty_snake_case();
+                let values = enum_type
+                    .cases
+                    .iter()
+                    .enumerate()
+                    .map(|(index, case)| {
+                        format!("  {prefix}_{} = {index};", case.to_shouty_snake_case())
+                    })
+                    .collect::<Vec<_>>();
+
+                self.definitions
+                    .push(format!("enum {name} {{\n{}\n}}\n", values.join("\n")));
+
+                return Ok(name);
+            }
+            AnalysedType::Handle(_) => {
+                return Err("Resources are not supported in gRPC messages".to_string())
+            }
+            typ if is_message(typ) => return self.message(hint, typ),
+            typ => return self.wrapper(hint, typ),
+        };
+
+        Ok(name.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{to_proto_file, GrpcMethod};
+    use crate::gateway_api_definition::http::{
+        AllPathPatterns, CompiledHttpApiDefinition, CompiledRoute, MethodPattern,
+    };
+    use crate::gateway_api_definition::{ApiDefinitionId, ApiVersion};
+    use crate::gateway_binding::{
+        GatewayBindingCompiled, IngressProtocolType, ResponseMappingCompiled, WorkerBindingCompiled,
+    };
+    use golem_common::model::auth::Namespace;
+    use golem_common::model::component::VersionedComponentId;
+    use golem_common::model::{AccountId, ComponentId, ProjectId};
+    use golem_wasm::analysis::analysed_type::{
+        case, field, list, option, r#enum, record, s32, str, u64, unit_case, variant,
+    };
+    use rib::{RibByteCode, RibInputTypeInfo, RibOutputTypeInfo};
+    use std::collections::HashMap;
+    use test_r::test;
+
+    #[test]
+    fn method_is_parsed_from_path() {
+        let path = AllPathPatterns::parse("/shop.v1.Cart/AddItem").unwrap();
+
+        assert_eq!(
+            GrpcMethod::from_route(&MethodPattern::Post, &path),
+            Ok(GrpcMethod {
+                package: Some("shop.v1".to_string()),
+                service: "Cart".to_string(),
+                method: "AddItem".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn invalid_method_paths_are_rejected() {
+        let post = MethodPattern::Post;
+
+        let parse = |path: &str| AllPathPatterns::parse(path).unwrap();
+
+        assert!(GrpcMethod::from_route(&MethodPattern::Get, &parse("/Cart/AddItem")).is_err());
+        assert!(GrpcMethod::from_route(&post, &parse("/Cart/{method}")).is_err());
+        assert!(GrpcMethod::from_route(&post, &parse("/shop/Cart/AddItem")).is_err());
+        assert!(GrpcMethod::from_route(&post, &parse("/shop-v1.Cart/AddItem")).is_err());
+    }
+
+    #[test]
+    fn proto_file_is_rendered_from_rib_types() {
+        let request = record(vec![field(
+            "body",
+            record(vec![
+                field("product-id", str()),
+                field("quantity", s32()),
+                field("tags", list(list(str()))),
+            ]),
+        )]);
+        let response = record(vec![
+            field("status", u64()),
+            field(
+                "body",
+                variant(vec![
+                    unit_case("out-of-stock"),
+                    case("added", option(r#enum(&["new", "merged"]))),
+                ]),
+            ),
+        ]);
+
+        let definition = CompiledHttpApiDefinition {
+            id: ApiDefinitionId("shop".to_string()),
+            version: ApiVersion("0.0.1".to_string()),
+            routes: vec![CompiledRoute {
+                method: MethodPattern::Post,
+                path: AllPathPatterns::parse("/shop.v1.Cart/AddItem").unwrap(),
+                binding: GatewayBindingCompiled::Worker(Box::new(WorkerBindingCompiled {
+                    component_id: VersionedComponentId {
+                        component_id: ComponentId::new_v4(),
+                        version: 0,
+                    },
+                    idempotency_key_compiled: None,
+                    response_compiled: ResponseMappingCompiled {
+                        response_mapping_expr: rib::from_string("1").unwrap(),
+                        response_mapping_compiled: RibByteCode::default(),
+                        rib_input: RibInputTypeInfo {
+                            types: HashMap::from_iter(vec![("request".to_string(), request)]),
+                        },
+                        worker_calls: None,
+                        rib_output: Some(RibOutputTypeInfo {
+                            analysed_type: response,
+                        }),
+                    },
+                    invocation_context_compiled: None,
+                    response_stream: None,
+                    ingress_protocol: Some(IngressProtocolType::Grpc),
+                })),
+                middlewares: None,
+            }],
+            draft: true,
+            created_at: chrono::Utc::now(),
+            namespace: Namespace::new(ProjectId::new_v4(), AccountId::generate()),
+            rib_execution_limits: None,
+        };
+
+        let expected = r#"syntax = "proto3";
+
+package shop.v1;
+
+service Cart {
+  rpc AddItem(AddItemRequest) returns (AddItemResponse);
+}
+
+message AddItemRequestTags {
+  repeated string value = 1;
+}
+
+message AddItemRequest {
+  string product_id = 1;
+  int32 quantity = 2;
+  repeated AddItemRequestTags tags = 3;
+}
+
+message Empty {}
+
+enum AddItemResponseAddedValue {
+  ADD_ITEM_RESPONSE_ADDED_VALUE_NEW = 0;
+  ADD_ITEM_RESPONSE_ADDED_VALUE_MERGED = 1;
+}
+
+message AddItemResponseAdded {
+  optional AddItemResponseAddedValue value = 1;
+}
+
+message AddItemResponse {
+  oneof value {
+    Empty out_of_stock = 1;
+    AddItemResponseAdded added = 2;
+  }
+}
+"#;
+
+        assert_eq!(to_proto_file(&definition).unwrap(), expected);
+    }
+}
@@ -20,6 +20,7 @@ pub use oas_api_definition::*;
 mod api_definition;
 mod api_definition_request;
 pub mod api_oas_convert;
+pub mod api_proto_convert;
 pub mod oas_api_definition;
 mod path_pattern_parser;
 mod place_holder_parser;
@@ -52,6 +52,7 @@ impl OpenApiHttpApiDefinition {
             version: api_definition_version,
             routes,
             draft: true,
+            rib_execution_limits: None,
         })
     }
 }
@@ -131,10 +132,10 @@ mod internal {
     use crate::gateway_api_definition::http::{AllPathPatterns, MethodPattern, RouteRequest};
 
     use crate::gateway_binding::{
-        GatewayBinding, HttpHandlerBinding, ResponseMapping, StaticBinding, SwaggerUiBinding,
-        WorkerBinding,
+        GatewayBinding, HttpHandlerBinding, IngressProtocolType, ResponseMapping,
+        ResponseStreamType, StaticBinding, SwaggerUiBinding, WorkerBinding,
     };
-    use crate::gateway_middleware::{CorsPreflightExpr, HttpCors};
+    use crate::gateway_middleware::{CorsPreflightExpr, HttpCors, HttpTransformation};
     use crate::gateway_security::{SecuritySchemeIdentifier, SecuritySchemeReference};
     use crate::service::gateway::BoxConversionContext;
     use golem_common::model::component::VersionedComponentId;
@@ -152,6 +153,8 @@ mod internal {
 
     pub(super) const GOLEM_API_GATEWAY_BINDING: &str = "x-golem-api-gateway-binding";
 
+    pub(super) const GOLEM_API_GATEWAY_TRANSFORMATION: &str = "x-golem-api-gateway-transformation";
+
     pub(super) fn get_root_extension_str(
         open_api: &OpenAPI,
         key_name: &str,
@@ -234,6 +237,8 @@ mod internal {
             security_scheme_identifier: SecuritySchemeIdentifier::new(x),
         });
 
+        let transformation = get_transformation(method_operation)?;
+
         let worker_gateway_info_optional = method_operation
             .extensions
             // TO keep backward compatibility with the old extension
@@ -253,6 +258,7 @@ mod internal {
                             path: path_pattern.clone(),
                             binding: GatewayBinding::static_binding(binding),
                             security,
+                            transformation,
                         })
                     }
 
@@ -264,6 +270,7 @@ mod internal {
                             method,
                             binding: GatewayBinding::Default(Box::new(binding)),
                             security,
+                            transformation,
                         })
                     }
                     (GatewayBindingType::FileServer, _) => {
@@ -274,6 +281,7 @@ mod internal {
                             method,
                             binding: GatewayBinding::Default(Box::new(binding)),
                             security,
+                            transformation,
                         })
                     }
                     (GatewayBindingType::HttpHandler, _) => {
@@ -284,6 +292,7 @@ mod internal {
                             method,
                             binding: GatewayBinding::HttpHandler(Box::new(binding)),
                             security,
+                            transformation,
                         })
                     }
                     (GatewayBindingType::SwaggerUi, _) => {
@@ -292,6 +301,7 @@ mod internal {
                             method,
                             binding: GatewayBinding::SwaggerUi(SwaggerUiBinding::default()),
                             security,
+                            transformation,
                         })
                     }
                     (GatewayBindingType::CorsPreflight, method) => {
@@ -309,6 +319,7 @@ mod internal {
                         method,
                         binding: GatewayBinding::static_binding(binding),
                         security,
+                        transformation,
                     })
                 } else {
                     Err(format!(
@@ -319,6 +330,20 @@ mod internal {
         }
     }
 
+    pub(super) fn get_transformation(
+        method_operation: &Operation,
+    ) -> Result<Option<HttpTransformation>, String> {
+        method_operation
+            .extensions
+            .get(GOLEM_API_GATEWAY_TRANSFORMATION)
+            .map(|transformation| {
+                serde_json::from_value(transformation.clone()).map_err(|err| {
+                    format!("Invalid {GOLEM_API_GATEWAY_TRANSFORMATION} extension: {err}")
+                })
+            })
+            .transpose()
+    }
+
     pub(super) async fn get_worker_binding(
         gateway_binding_value: &Value,
         ctx: &BoxConversionContext<'_>,
@@ -336,6 +361,8 @@ mod internal {
             idempotency_key: get_idempotency_key(gateway_binding_value)?,
             response_mapping: get_response_mapping(gateway_binding_value)?,
             invocation_context: get_invocation_context(gateway_binding_value)?,
+            response_stream: get_response_stream(gateway_binding_value)?,
+            ingress_protocol: get_ingress_protocol(gateway_binding_value)?,
         };
 
         Ok(binding)
@@ -485,6 +512,26 @@ mod internal {
         }
     }
 
+    pub(super) fn get_response_stream(
+        gateway_binding_value: &Value,
+    ) -> Result<Option<ResponseStreamType>, String> {
+        gateway_binding_value
+            .get("response-stream")
+            .map(|value| serde_json::from_value(value.clone()))
+            .transpose()
+            .map_err(|err| format!("Invalid schema for response-stream. {err}"))
+    }
+
+    pub(super) fn get_ingress_protocol(
+        gateway_binding_value: &Value,
+    ) -> Result<Option<IngressProtocolType>, String> {
+        gateway_binding_value
+            .get("ingress-protocol")
+            .map(|value| serde_json::from_value(value.clone()))
+            .transpose()
+            .map_err(|err| format!("Invalid schema for ingress-protocol. {err}"))
+    }
+
     pub(super) fn get_path_pattern(path: &str) -> Result<AllPathPatterns, String> {
         AllPathPatterns::parse(path)
     }
@@ -615,6 +662,7 @@ mod tests {
                 HttpCors::default(),
             )),
             security: None,
+            transformation: None,
         }
     }
 
@@ -626,6 +674,7 @@ mod tests {
             method: MethodPattern::Options,
             binding: GatewayBinding::static_binding(StaticBinding::from_http_cors(cors_preflight)),
             security: None,
+            transformation: None,
         }
     }
 
@@ -22,8 +22,9 @@ use nom::sequence::{delimited, preceded, terminated, tuple};
 use nom::{IResult, Parser};
 
 use crate::gateway_api_definition::http::{
-    place_holder_parser, AllPathPatterns, PathPattern, QueryInfo,
+    place_holder_parser, AllPathPatterns, PathParamConstraint, PathPattern, QueryInfo,
 };
+use std::str::FromStr;
 
 pub fn parse_path_pattern(input: &str) -> IResult<&str, AllPathPatterns> {
     let (input, (path, query)) =
@@ -56,15 +57,38 @@ fn path_parser(input: &str) -> IResult<&str, Vec<PathPattern>> {
     let indexed_patterns = patterns
         .into_iter()
         .map(|pattern| match pattern {
-            ParsedPattern::Literal(literal) => PathPattern::literal(literal.trim()),
-            ParsedPattern::Var(var) => PathPattern::var(var.trim()),
-            ParsedPattern::CatchAllVar(var) => PathPattern::catch_all_var(var.trim()),
+            ParsedPattern::Literal(literal) => Ok(PathPattern::literal(literal.trim())),
+            ParsedPattern::Var(var) => match var.split_once(':') {
+                Some((var, constraint)) => PathParamConstraint::from_str(constraint)
+                    .map(|constraint| PathPattern::constrained_var(var.trim(), constraint))
+                    .map_err(|_| {
+                        nom::Err::Failure(nom::error::Error::new(
+                            constraint,
+                            nom::error::ErrorKind::Verify,
+                        ))
+                    }),
+                None => Ok(PathPattern::var(var.trim())),
+            },
+            ParsedPattern::CatchAllVar(var) => Ok(PathPattern::catch_all_var(var.trim())),
         })
-        .collect();
+        .collect::<Result<Vec<_>, _>>()?;
 
     Ok((input, indexed_patterns))
 }
 
+/// Turns a failure of the path pattern parser into an error message, pointing out
+/// invalid path parameter constraints.
+pub fn path_pattern_error_message(err: nom::Err<nom::error::Error<&str>>) -> String {
+    match err {
+        nom::Err::Failure(error) if error.code == nom::error::ErrorKind::Verify => {
+            PathParamConstraint::from_str(error.input)
+                .err()
+                .unwrap_or_else(|| nom::Err::Failure(error).to_string())
+        }
+        err => err.to_string(),
+    }
+}
+
 fn query_parser(input: &str) -> IResult<&str, Vec<QueryInfo>> {
     separated_list0(char('&'), query_param_parser)(input)
 }
@@ -100,6 +124,13 @@ fn catch_all_path_var_inner_parser(
     input: &str,
 ) -> Result<ParsedPattern<'_>, nom::Err<nom::error::Error<&str>>> {
     let (i, _) = char('+')(input)?;
+    // Constraints are only supported on variables matching a single segment
+    if i.contains(':') {
+        return Err(nom::Err::Error(nom::error::Error::new(
+            i,
+            nom::error::ErrorKind::Verify,
+        )));
+    }
     Ok(ParsedPattern::CatchAllVar(i))
 }
 
@@ -126,7 +157,7 @@ fn literal_parser(input: &str) -> IResult<&str, ParsedPattern<'_>> {
 mod tests {
     use crate::gateway_api_definition::http::path_pattern_parser::parse_path_pattern;
     use crate::gateway_api_definition::http::{
-        AllPathPatterns, LiteralInfo, PathPattern, QueryInfo,
+        AllPathPatterns, LiteralInfo, PathParamConstraint, PathPattern, QueryInfo,
     };
     use test_r::test;
 
@@ -188,6 +219,44 @@ mod tests {
         assert!(AllPathPatterns::parse("/api/{foo}/{+others}/{bar}").is_err());
     }
 
+    #[test]
+    fn test_parse_constrained_vars() {
+        let result =
+            AllPathPatterns::parse("/users/{id:u64}/orders/{order:uuid}/{code:regex([a-z]{3})}")
+                .unwrap();
+
+        assert_eq!(
+            result,
+            AllPathPatterns {
+                path_patterns: vec![
+                    PathPattern::literal("users"),
+                    PathPattern::constrained_var("id", PathParamConstraint::U64),
+                    PathPattern::literal("orders"),
+                    PathPattern::constrained_var("order", PathParamConstraint::Uuid),
+                    PathPattern::constrained_var(
+                        "code",
+                        PathParamConstraint::Regex("[a-z]{3}".to_string())
+                    ),
+                ],
+                query_params: vec![]
+            },
+        );
+
+        assert_eq!(
+            result.to_string(),
+            "/users/{id:u64}/orders/{order:uuid}/{code:regex([a-z]{3})}"
+        );
+
+        assert_eq!(
+            AllPathPatterns::parse("/users/{id:u46}"),
+            Err("Unknown path parameter constraint: u46. Expected one of int, u32, u64, s32, s64, bool, uuid or regex(<pattern>)".to_string())
+        );
+        assert!(AllPathPatterns::parse("/users/{id:regex([a-z)}").is_err());
+
+        // Constraints are not allowed on catch-all variables
+        assert!(AllPathPatterns::parse("/files/{+path:int}").is_err());
+    }
+
     #[test]
     fn test_parse_root_only() {
         assert_eq!(
@@ -141,6 +141,8 @@ mod tests {
             idempotency_key: None,
             response_mapping: ResponseMapping(Expr::literal("")),
             invocation_context: None,
+            response_stream: None,
+            ingress_protocol: None,
         };
 
         Route {
@@ -160,6 +162,8 @@ mod tests {
             idempotency_key: None,
             response_mapping: ResponseMapping(Expr::literal("")),
             invocation_context: None,
+            response_stream: None,
+            ingress_protocol: None,
         };
 
         Route {
@@ -199,6 +203,7 @@ mod tests {
             version: ApiVersion::new("v1"),
             draft: false,
             created_at: chrono::Utc::now(),
+            rib_execution_limits: None,
         };
 
         cors_transform(&mut api_definition).unwrap();
@@ -233,6 +238,7 @@ mod tests {
             version: ApiVersion::new("v1"),
             draft: false,
             created_at: chrono::Utc::now(),
+            rib_execution_limits: None,
         };
 
         let result = cors_transform(&mut api_definition).map_err(|x| match x {
@@ -263,6 +269,7 @@ mod tests {
             version: ApiVersion::new("v1"),
             draft: false,
             created_at: chrono::Utc::now(),
+            rib_execution_limits: None,
         };
 
         let expected = api_definition.clone();
@@ -16,21 +16,62 @@ use crate::service::gateway::api_definition::ApiDefinitionIdWithVersion;
 use golem_common::model::auth::Namespace;
 use poem_openapi::{NewType, Object};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fmt::Display;
 
-#[derive(Eq, Hash, PartialEq, Clone, Debug, serde::Deserialize)]
+#[derive(Eq, PartialEq, Clone, Debug, serde::Deserialize)]
 pub struct ApiDeploymentRequest {
     pub namespace: Namespace,
     pub api_definition_keys: Vec<ApiDefinitionIdWithVersion>,
     pub site: ApiSite,
+    // Only the definitions present in this map have their traffic split (re)configured,
+    // the rest keep their existing configuration if they are already deployed
+    pub traffic_splits: HashMap<ApiDefinitionIdWithVersion, TrafficSplit>,
 }
 
-#[derive(Eq, Hash, PartialEq, Clone, Debug, serde::Deserialize)]
+#[derive(Eq, PartialEq, Clone, Debug, serde::Deserialize)]
 pub struct ApiDeployment {
     pub namespace: Namespace,
     pub api_definition_keys: Vec<ApiDefinitionIdWithVersion>,
     pub site: ApiSite,
     pub created_at: chrono::DateTime<chrono::Utc>,
+    pub traffic_splits: HashMap<ApiDefinitionIdWithVersion, TrafficSplit>,
+}
+
+// Multiple versions of the same API definition can be deployed to a site
+// if their traffic is split. A request having the header of a version is always routed
+// to that version, the rest of the requests are distributed based on the weights,
+// which have to add up to 100 across the versions of the definition.
+#[derive(Debug, Eq, Clone, Hash, PartialEq, Serialize, Deserialize, Object)]
+#[oai(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase")]
+pub struct TrafficSplit {
+    pub weight: Option<u32>,
+    pub header: Option<TrafficSplitHeader>,
+}
+
+#[derive(Debug, Eq, Clone, Hash, PartialEq, Serialize, Deserialize, Object)]
+#[oai(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase")]
+pub struct TrafficSplitHeader {
+    pub name: String,
+    pub value: String,
+}
+
+impl TrafficSplit {
+    pub fn weight(&self) -> u32 {
+        self.weight.unwrap_or(0)
+    }
+
+    pub fn matches_headers(&self, headers: &http::HeaderMap) -> bool {
+        match &self.header {
+            Some(header) => headers
+                .get(header.name.as_str())
+                .and_then(|value| value.to_str().ok())
+                .is_some_and(|value| value == header.value),
+            None => false,
+        }
+    }
 }
 
 #[derive(Debug, Eq, Clone, Hash, PartialEq, Serialize, Deserialize, Object)]
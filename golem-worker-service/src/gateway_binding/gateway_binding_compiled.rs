@@ -16,8 +16,12 @@ use crate::gateway_binding::{
     FileServerBinding, FileServerBindingCompiled, GatewayBinding, IdempotencyKeyCompiled,
     ResponseMappingCompiled, WorkerBinding, WorkerBindingCompiled, WorkerNameCompiled,
 };
-use crate::gateway_binding::{InvocationContextCompiled, StaticBinding};
+use crate::gateway_binding::{
+    IngressProtocolType, InvocationContextCompiled, ResponseStreamType, StaticBinding,
+};
 use golem_api_grpc::proto::golem::apidefinition::GatewayBindingType as ProtoGatewayBindingType;
+use golem_api_grpc::proto::golem::apidefinition::IngressProtocolType as ProtoIngressProtocolType;
+use golem_api_grpc::proto::golem::apidefinition::ResponseStreamType as ProtoResponseStreamType;
 use golem_common::model::GatewayBindingType;
 use rib::RibOutputTypeInfo;
 
@@ -109,6 +113,38 @@ impl From<GatewayBindingCompiled> for GatewayBinding {
     }
 }
 
+impl From<ResponseStreamType> for ProtoResponseStreamType {
+    fn from(value: ResponseStreamType) -> Self {
+        match value {
+            ResponseStreamType::Sse => ProtoResponseStreamType::Sse,
+        }
+    }
+}
+
+impl From<ProtoResponseStreamType> for ResponseStreamType {
+    fn from(value: ProtoResponseStreamType) -> Self {
+        match value {
+            ProtoResponseStreamType::Sse => ResponseStreamType::Sse,
+        }
+    }
+}
+
+impl From<IngressProtocolType> for ProtoIngressProtocolType {
+    fn from(value: IngressProtocolType) -> Self {
+        match value {
+            IngressProtocolType::Grpc => ProtoIngressProtocolType::Grpc,
+        }
+    }
+}
+
+impl From<ProtoIngressProtocolType> for IngressProtocolType {
+    fn from(value: ProtoIngressProtocolType) -> Self {
+        match value {
+            ProtoIngressProtocolType::Grpc => IngressProtocolType::Grpc,
+        }
+    }
+}
+
 impl TryFrom<GatewayBindingCompiled>
     for golem_api_grpc::proto::golem::apidefinition::CompiledGatewayBinding
 {
@@ -166,6 +202,8 @@ impl TryFrom<GatewayBindingCompiled>
                         compiled_invocation_context_expr: None,
                         invocation_context_rib_input: None,
                         openapi_spec_json: None,
+                        response_stream: None,
+                        ingress_protocol: None,
                     },
                 )
             }
@@ -190,6 +228,8 @@ impl TryFrom<GatewayBindingCompiled>
                     compiled_invocation_context_expr: None,
                     invocation_context_rib_input: None,
                     openapi_spec_json: swagger_binding.openapi_spec_json.clone(),
+                    response_stream: None,
+                    ingress_protocol: None,
                 },
             ),
         }
@@ -301,12 +341,32 @@ impl TryFrom<golem_api_grpc::proto::golem::apidefinition::CompiledGatewayBinding
                     .unwrap_or(ProtoGatewayBindingType::Default.into());
 
                 if binding_type == 0 {
+                    let response_stream = value
+                        .response_stream
+                        .map(|response_stream| {
+                            ProtoResponseStreamType::try_from(response_stream)
+                                .map(ResponseStreamType::from)
+                                .map_err(|e| format!("Failed to convert response stream: {e}"))
+                        })
+                        .transpose()?;
+
+                    let ingress_protocol = value
+                        .ingress_protocol
+                        .map(|ingress_protocol| {
+                            ProtoIngressProtocolType::try_from(ingress_protocol)
+                                .map(IngressProtocolType::from)
+                                .map_err(|e| format!("Failed to convert ingress protocol: {e}"))
+                        })
+                        .transpose()?;
+
                     Ok(GatewayBindingCompiled::Worker(Box::new(
                         WorkerBindingCompiled {
                             component_id,
                             idempotency_key_compiled,
                             response_compiled,
                             invocation_context_compiled,
+                            response_stream,
+                            ingress_protocol,
                         },
                     )))
                 } else {
@@ -482,6 +542,8 @@ mod internal {
                 compiled_invocation_context_expr,
                 invocation_context_rib_input,
                 openapi_spec_json: None,
+                response_stream: None,
+                ingress_protocol: None,
             },
         )
     }
@@ -562,6 +624,15 @@ mod internal {
                 compiled_invocation_context_expr,
                 invocation_context_rib_input,
                 openapi_spec_json: None,
+                response_stream: worker_binding.response_stream.map(|response_stream| {
+                    golem_api_grpc::proto::golem::apidefinition::ResponseStreamType::from(
+                        response_stream,
+                    )
+                    .into()
+                }),
+                ingress_protocol: worker_binding.ingress_protocol.map(|ingress_protocol| {
+                    ProtoIngressProtocolType::from(ingress_protocol).into()
+                }),
             },
         )
     }
@@ -620,6 +691,8 @@ mod internal {
                 compiled_invocation_context_expr: None,
                 invocation_context_rib_input: None,
                 openapi_spec_json: None,
+                response_stream: None,
+                ingress_protocol: None,
             },
         )
     }
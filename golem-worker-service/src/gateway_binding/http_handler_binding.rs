@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use super::{IdempotencyKeyCompiled, WorkerNameCompiled};
+use crate::gateway_rib_compiler::{CompiledRibCache, PathParamTypes};
 use golem_common::model::component::VersionedComponentId;
 use rib::{Expr, RibCompilationError};
 
@@ -33,16 +34,26 @@ pub struct HttpHandlerBindingCompiled {
 impl HttpHandlerBindingCompiled {
     pub fn from_raw_http_handler_binding(
         http_handler_binding: &HttpHandlerBinding,
+        path_param_types: &PathParamTypes,
+        compiled_rib_cache: &CompiledRibCache,
     ) -> Result<Self, RibCompilationError> {
         let worker_name_compiled: Option<WorkerNameCompiled> = http_handler_binding
             .worker_name
             .clone()
-            .map(|worker_name_expr| WorkerNameCompiled::from_worker_name(&worker_name_expr))
+            .map(|worker_name_expr| {
+                WorkerNameCompiled::from_worker_name(
+                    &worker_name_expr,
+                    path_param_types,
+                    compiled_rib_cache,
+                )
+            })
             .transpose()?;
 
         let idempotency_key_compiled = match &http_handler_binding.idempotency_key {
             Some(idempotency_key) => Some(IdempotencyKeyCompiled::from_idempotency_key(
                 idempotency_key,
+                path_param_types,
+                compiled_rib_cache,
             )?),
             None => None,
         };
@@ -20,9 +20,8 @@ mod worker_binding;
 pub use self::http_handler_binding::*;
 pub use self::worker_binding::*;
 pub(crate) use crate::gateway_execution::gateway_binding_resolver::*;
-use crate::gateway_rib_compiler::WorkerServiceRibCompiler;
 use crate::gateway_rib_compiler::{
-    ComponentDependencyWithAgentInfo, DefaultWorkerServiceRibCompiler,
+    CompiledRibCache, ComponentDependencyWithAgentInfo, PathParamTypes,
 };
 pub use gateway_binding_compiled::SwaggerUiBinding;
 pub(crate) use gateway_binding_compiled::*;
@@ -97,8 +96,13 @@ pub struct WorkerNameCompiled {
 }
 
 impl WorkerNameCompiled {
-    pub fn from_worker_name(worker_name: &Expr) -> Result<Self, RibCompilationError> {
-        let compiled_worker_name = DefaultWorkerServiceRibCompiler::compile(worker_name, &[])?;
+    pub fn from_worker_name(
+        worker_name: &Expr,
+        path_param_types: &PathParamTypes,
+        compiled_rib_cache: &CompiledRibCache,
+    ) -> Result<Self, RibCompilationError> {
+        let compiled_worker_name =
+            compiled_rib_cache.compile(worker_name, &[], path_param_types)?;
 
         Ok(WorkerNameCompiled {
             worker_name: worker_name.clone(),
@@ -116,9 +120,13 @@ pub struct IdempotencyKeyCompiled {
 }
 
 impl IdempotencyKeyCompiled {
-    pub fn from_idempotency_key(idempotency_key: &Expr) -> Result<Self, RibCompilationError> {
+    pub fn from_idempotency_key(
+        idempotency_key: &Expr,
+        path_param_types: &PathParamTypes,
+        compiled_rib_cache: &CompiledRibCache,
+    ) -> Result<Self, RibCompilationError> {
         let idempotency_key_compiled =
-            DefaultWorkerServiceRibCompiler::compile(idempotency_key, &[])?;
+            compiled_rib_cache.compile(idempotency_key, &[], path_param_types)?;
 
         Ok(IdempotencyKeyCompiled {
             idempotency_key: idempotency_key.clone(),
@@ -139,9 +147,11 @@ impl InvocationContextCompiled {
     pub fn from_invocation_context(
         invocation_context: &Expr,
         exports: &[ComponentDependencyWithAgentInfo],
+        path_param_types: &PathParamTypes,
+        compiled_rib_cache: &CompiledRibCache,
     ) -> Result<Self, RibCompilationError> {
         let invocation_context_compiled =
-            DefaultWorkerServiceRibCompiler::compile(invocation_context, exports)?;
+            compiled_rib_cache.compile(invocation_context, exports, path_param_types)?;
 
         Ok(InvocationContextCompiled {
             invocation_context: invocation_context.clone(),
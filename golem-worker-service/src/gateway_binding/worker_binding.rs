@@ -13,9 +13,8 @@
 // limitations under the License.
 
 use super::{IdempotencyKeyCompiled, InvocationContextCompiled, WorkerNameCompiled};
-use crate::gateway_rib_compiler::WorkerServiceRibCompiler;
 use crate::gateway_rib_compiler::{
-    ComponentDependencyWithAgentInfo, DefaultWorkerServiceRibCompiler,
+    CompiledRibCache, ComponentDependencyWithAgentInfo, PathParamTypes,
 };
 use golem_common::model::component::VersionedComponentId;
 use rib::{
@@ -46,27 +45,41 @@ impl FileServerBindingCompiled {
     pub fn from_raw_file_server_worker_binding(
         gateway_worker_binding: &FileServerBinding,
         component_dependency: &[ComponentDependencyWithAgentInfo],
+        path_param_types: &PathParamTypes,
+        compiled_rib_cache: &CompiledRibCache,
     ) -> Result<Self, RibCompilationError> {
         let worker_name_compiled: Option<WorkerNameCompiled> = gateway_worker_binding
             .worker_name
             .clone()
-            .map(|worker_name_expr| WorkerNameCompiled::from_worker_name(&worker_name_expr))
+            .map(|worker_name_expr| {
+                WorkerNameCompiled::from_worker_name(
+                    &worker_name_expr,
+                    path_param_types,
+                    compiled_rib_cache,
+                )
+            })
             .transpose()?;
 
         let idempotency_key_compiled = match &gateway_worker_binding.idempotency_key {
             Some(idempotency_key) => Some(IdempotencyKeyCompiled::from_idempotency_key(
                 idempotency_key,
+                path_param_types,
+                compiled_rib_cache,
             )?),
             None => None,
         };
         let response_compiled = ResponseMappingCompiled::from_response_mapping(
             &gateway_worker_binding.response_mapping,
             component_dependency,
+            path_param_types,
+            compiled_rib_cache,
         )?;
         let invocation_context_compiled = match &gateway_worker_binding.invocation_context {
             Some(invocation_context) => Some(InvocationContextCompiled::from_invocation_context(
                 invocation_context,
                 component_dependency,
+                path_param_types,
+                compiled_rib_cache,
             )?),
             None => None,
         };
@@ -109,6 +122,8 @@ pub struct WorkerBinding {
     pub idempotency_key: Option<Expr>,
     pub response_mapping: ResponseMapping,
     pub invocation_context: Option<Expr>,
+    pub response_stream: Option<ResponseStreamType>,
+    pub ingress_protocol: Option<IngressProtocolType>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -117,27 +132,59 @@ pub struct WorkerBindingCompiled {
     pub idempotency_key_compiled: Option<IdempotencyKeyCompiled>,
     pub response_compiled: ResponseMappingCompiled,
     pub invocation_context_compiled: Option<InvocationContextCompiled>,
+    pub response_stream: Option<ResponseStreamType>,
+    pub ingress_protocol: Option<IngressProtocolType>,
+}
+
+// Streaming is opt-in per route. Without it, the result of the response mapping
+// is turned into a single http response once the Rib script finishes.
+// With `sse`, the gateway opens a Server-Sent Events stream, forwards the output of
+// the invoked workers while the script is running, and ends the stream with the result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, poem_openapi::Enum)]
+#[serde(rename_all = "kebab-case")]
+#[oai(rename_all = "kebab-case")]
+pub enum ResponseStreamType {
+    Sse,
+}
+
+// Routes accept http requests with json bodies by default. With `grpc`, the route is
+// a unary method of a gRPC service instead, with a path of `/<package>.<service>/<method>`.
+// The request message is decoded as `request.body`, and the `body` of the response mapping
+// is sent back as the response message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, poem_openapi::Enum)]
+#[serde(rename_all = "kebab-case")]
+#[oai(rename_all = "kebab-case")]
+pub enum IngressProtocolType {
+    Grpc,
 }
 
 impl WorkerBindingCompiled {
     pub fn from_raw_worker_binding(
         gateway_worker_binding: &WorkerBinding,
         component_dependency: &[ComponentDependencyWithAgentInfo],
+        path_param_types: &PathParamTypes,
+        compiled_rib_cache: &CompiledRibCache,
     ) -> Result<Self, RibCompilationError> {
         let idempotency_key_compiled = match &gateway_worker_binding.idempotency_key {
             Some(idempotency_key) => Some(IdempotencyKeyCompiled::from_idempotency_key(
                 idempotency_key,
+                path_param_types,
+                compiled_rib_cache,
             )?),
             None => None,
         };
         let response_compiled = ResponseMappingCompiled::from_response_mapping(
             &gateway_worker_binding.response_mapping,
             component_dependency,
+            path_param_types,
+            compiled_rib_cache,
         )?;
         let invocation_context_compiled = match &gateway_worker_binding.invocation_context {
             Some(invocation_context) => Some(InvocationContextCompiled::from_invocation_context(
                 invocation_context,
                 component_dependency,
+                path_param_types,
+                compiled_rib_cache,
             )?),
             None => None,
         };
@@ -147,6 +194,8 @@ impl WorkerBindingCompiled {
             idempotency_key_compiled,
             response_compiled,
             invocation_context_compiled,
+            response_stream: gateway_worker_binding.response_stream,
+            ingress_protocol: gateway_worker_binding.ingress_protocol,
         })
     }
 }
@@ -166,6 +215,8 @@ impl From<WorkerBindingCompiled> for WorkerBinding {
             invocation_context: worker_binding
                 .invocation_context_compiled
                 .map(|compiled| compiled.invocation_context),
+            response_stream: worker_binding.response_stream,
+            ingress_protocol: worker_binding.ingress_protocol,
         }
     }
 }
@@ -188,9 +239,14 @@ impl ResponseMappingCompiled {
     pub fn from_response_mapping(
         response_mapping: &ResponseMapping,
         component_dependency: &[ComponentDependencyWithAgentInfo],
+        path_param_types: &PathParamTypes,
+        compiled_rib_cache: &CompiledRibCache,
     ) -> Result<Self, RibCompilationError> {
-        let response_compiled =
-            DefaultWorkerServiceRibCompiler::compile(&response_mapping.0, component_dependency)?;
+        let response_compiled = compiled_rib_cache.compile(
+            &response_mapping.0,
+            component_dependency,
+            path_param_types,
+        )?;
 
         Ok(ResponseMappingCompiled {
             response_mapping_expr: response_mapping.0.clone(),
@@ -12,10 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use crate::gateway_api_definition::http::CompiledHttpApiDefinition;
-use crate::gateway_api_deployment::ApiSiteString;
+use crate::gateway_api_deployment::{ApiSiteString, TrafficSplit};
+use crate::service::gateway::api_definition::ApiDefinitionIdWithVersion;
 use crate::service::gateway::api_deployment::{ApiDeploymentError, ApiDeploymentService};
 use async_trait::async_trait;
 use golem_common::SafeDisplay;
@@ -31,6 +33,14 @@ pub trait HttpApiDefinitionsLookup: Send + Sync {
         &self,
         host: &ApiSiteString,
     ) -> Result<Vec<CompiledHttpApiDefinition>, ApiDefinitionLookupError>;
+
+    // Only relevant if multiple versions of an API definition are deployed to the site
+    async fn get_traffic_splits(
+        &self,
+        _host: &ApiSiteString,
+    ) -> Result<HashMap<ApiDefinitionIdWithVersion, TrafficSplit>, ApiDefinitionLookupError> {
+        Ok(HashMap::new())
+    }
 }
 
 pub enum ApiDefinitionLookupError {
@@ -79,4 +89,17 @@ impl HttpApiDefinitionsLookup for DefaultHttpApiDefinitionLookup {
 
         Ok(http_api_defs)
     }
+
+    async fn get_traffic_splits(
+        &self,
+        host: &ApiSiteString,
+    ) -> Result<HashMap<ApiDefinitionIdWithVersion, TrafficSplit>, ApiDefinitionLookupError> {
+        self.deployment_service
+            .get_all_traffic_splits_by_site(host)
+            .await
+            .map_err(|err| {
+                error!("Failed to lookup traffic splits: {}", err);
+                ApiDefinitionLookupError::ApiDeploymentError(err)
+            })
+    }
 }
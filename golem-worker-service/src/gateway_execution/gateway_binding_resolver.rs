@@ -16,30 +16,62 @@ use crate::gateway_api_definition::http::CompiledHttpApiDefinition;
 use crate::gateway_execution::router::RouterPattern;
 use crate::gateway_request::http_request::router;
 use crate::gateway_request::http_request::router::RouteEntry;
+use crate::service::gateway::api_definition::ApiDefinitionIdWithVersion;
 use golem_common::model::auth::Namespace;
+use rib::RibExecutionLimits;
 
 pub struct ResolvedRouteEntry {
     pub path_segments: Vec<String>,
     pub route_entry: RouteEntry<Namespace>,
+    pub api_definition: ApiDefinitionIdWithVersion,
+    pub rib_execution_limits: Option<RibExecutionLimits>,
 }
 
 pub async fn resolve_gateway_binding(
     compiled_api_definitions: Vec<CompiledHttpApiDefinition>,
     request: &poem::Request,
 ) -> Option<ResolvedRouteEntry> {
+    // The API definition of the route is kept along with the namespace,
+    // to be able to tell which version of a split API definition serves the request,
+    // and to run the Rib scripts of the route with the limits of its definition
     let compiled_routes = compiled_api_definitions
         .iter()
-        .flat_map(|x| x.routes.iter().map(|y| (x.namespace.clone(), y.clone())))
+        .flat_map(|x| {
+            x.routes.iter().map(|y| {
+                let api_definition = ApiDefinitionIdWithVersion {
+                    id: x.id.clone(),
+                    version: x.version.clone(),
+                };
+                (
+                    (
+                        x.namespace.clone(),
+                        api_definition,
+                        x.rib_execution_limits.clone(),
+                    ),
+                    y.clone(),
+                )
+            })
+        })
         .collect::<Vec<_>>();
 
     let router = router::build(compiled_routes);
 
     let path_segments: Vec<&str> = RouterPattern::split(request.uri().path()).collect();
 
-    let route_entry = router.check_path(request.method(), &path_segments)?;
+    let route_entry = router.check_path(request.method(), &path_segments)?.clone();
+
+    let (namespace, api_definition, rib_execution_limits) = route_entry.namespace;
 
     Some(ResolvedRouteEntry {
         path_segments: path_segments.into_iter().map(|s| s.to_string()).collect(),
-        route_entry: route_entry.clone(),
+        route_entry: RouteEntry {
+            path_params: route_entry.path_params,
+            query_params: route_entry.query_params,
+            namespace,
+            binding: route_entry.binding,
+            middlewares: route_entry.middlewares,
+        },
+        api_definition,
+        rib_execution_limits,
     })
 }
@@ -14,18 +14,23 @@
 
 use super::auth_call_back_binding_handler::AuthorisationSuccess;
 use super::file_server_binding_handler::{FileServerBindingError, FileServerBindingSuccess};
+use super::grpc_response::{grpc_response_from_rib_result, GrpcError, GRPC_CONTENT_TYPE};
 use super::http_handler_binding_handler::{HttpHandlerBindingHandler, HttpHandlerBindingResult};
 use super::request::{
     authority_from_request, split_resolved_route_entry, RichRequest, SplitResolvedRouteEntryResult,
 };
+use super::sse_response::{sse_response, GatewayStreamEvent, SSE_EVENT_BUFFER_SIZE};
 use super::swagger_binding_handler::SwaggerBindingHandler;
 use super::to_response::GatewayHttpResult;
+use super::traffic_split::TrafficSplitSelection;
 use super::WorkerDetails;
+use crate::gateway_api_definition::http::api_proto_convert::grpc_request_type;
 use crate::gateway_api_deployment::ApiSiteString;
 use crate::gateway_binding::{
     resolve_gateway_binding, FileServerBindingCompiled, GatewayBindingCompiled,
-    HttpHandlerBindingCompiled, IdempotencyKeyCompiled, InvocationContextCompiled,
-    ResponseMappingCompiled, StaticBinding, WorkerBindingCompiled, WorkerNameCompiled,
+    HttpHandlerBindingCompiled, IdempotencyKeyCompiled, IngressProtocolType,
+    InvocationContextCompiled, ResolvedRouteEntry, ResponseMappingCompiled, ResponseStreamType,
+    StaticBinding, WorkerBindingCompiled, WorkerNameCompiled,
 };
 use crate::gateway_execution::api_definition_lookup::{
     ApiDefinitionLookupError, HttpApiDefinitionsLookup,
@@ -55,11 +60,12 @@ use golem_wasm::json::ValueAndTypeJsonExtensions;
 use golem_wasm::{IntoValue, IntoValueAndType, ValueAndType};
 use http::StatusCode;
 use poem::Body;
-use rib::{RibInput, RibInputTypeInfo, RibResult, TypeName};
+use rib::{RibExecutionLimits, RibInput, RibInputTypeInfo, RibResult, TypeName};
 use std::collections::HashMap;
 use std::str::FromStr;
 use std::sync::Arc;
-use tracing::error;
+use std::time::Instant;
+use tracing::{error, Instrument};
 use uuid::Uuid;
 
 #[async_trait]
@@ -76,6 +82,8 @@ pub struct DefaultGatewayInputExecutor {
     pub api_definition_lookup_service: Arc<dyn HttpApiDefinitionsLookup>,
     pub gateway_session_store: GatewaySessionStore,
     pub identity_provider: Arc<dyn IdentityProvider>,
+    // Limits of the Rib scripts of API definitions, used where the definition does not set its own
+    pub rib_execution_limits: RibExecutionLimits,
 }
 
 impl DefaultGatewayInputExecutor {
@@ -88,6 +96,7 @@ impl DefaultGatewayInputExecutor {
         api_definition_lookup_service: Arc<dyn HttpApiDefinitionsLookup>,
         gateway_session_store: GatewaySessionStore,
         identity_provider: Arc<dyn IdentityProvider>,
+        rib_execution_limits: RibExecutionLimits,
     ) -> Self {
         Self {
             evaluator,
@@ -98,6 +107,7 @@ impl DefaultGatewayInputExecutor {
             api_definition_lookup_service,
             gateway_session_store,
             identity_provider,
+            rib_execution_limits,
         }
     }
 
@@ -106,12 +116,14 @@ impl DefaultGatewayInputExecutor {
         namespace: Namespace,
         request: &mut RichRequest,
         binding: WorkerBindingCompiled,
+        rib_execution_limits: &RibExecutionLimits,
     ) -> GatewayHttpResult<RibResult> {
         let WorkerBindingCompiled {
             response_compiled,
             component_id,
             idempotency_key_compiled,
             invocation_context_compiled,
+            ..
         } = binding;
 
         let worker_detail = self
@@ -121,11 +133,127 @@ impl DefaultGatewayInputExecutor {
                 idempotency_key_compiled,
                 component_id,
                 invocation_context_compiled,
+                rib_execution_limits,
             )
             .await?;
 
-        self.execute_response_mapping_script(namespace, response_compiled, request, worker_detail)
+        self.execute_response_mapping_script(
+            namespace,
+            response_compiled,
+            request,
+            worker_detail,
+            rib_execution_limits,
+        )
+        .await
+    }
+
+    // Errors in resolving the inputs of the script are reported as regular http errors.
+    // Once the script starts running, the response is an event stream that ends with
+    // the result of the script, or its error.
+    async fn handle_worker_binding_streaming(
+        &self,
+        namespace: Namespace,
+        request: &mut RichRequest,
+        binding: WorkerBindingCompiled,
+        rib_execution_limits: &RibExecutionLimits,
+    ) -> GatewayHttpResult<poem::Response> {
+        let WorkerBindingCompiled {
+            response_compiled,
+            component_id,
+            idempotency_key_compiled,
+            invocation_context_compiled,
+            ..
+        } = binding;
+
+        let WorkerDetails {
+            invocation_context,
+            idempotency_key,
+            ..
+        } = self
+            .get_worker_details(
+                request,
+                None,
+                idempotency_key_compiled,
+                component_id,
+                invocation_context_compiled,
+                rib_execution_limits,
+            )
+            .await?;
+
+        let ResponseMappingCompiled {
+            response_mapping_compiled,
+            rib_input,
+            ..
+        } = response_compiled;
+
+        let rib_input = resolve_rib_input(request, &rib_input).await?;
+
+        let (sender, receiver) = tokio::sync::mpsc::channel(SSE_EVENT_BUFFER_SIZE);
+        let evaluator = self.evaluator.clone();
+        let rib_execution_limits = rib_execution_limits.clone();
+
+        tokio::spawn(
+            async move {
+                let result = evaluator
+                    .evaluate_streaming(
+                        idempotency_key,
+                        invocation_context,
+                        response_mapping_compiled,
+                        rib_input,
+                        namespace,
+                        rib_execution_limits,
+                        sender.clone(),
+                    )
+                    .await;
+
+                let _ = sender
+                    .send(GatewayStreamEvent::from_rib_result(result))
+                    .await;
+            }
+            .in_current_span(),
+        );
+
+        Ok(sse_response(receiver))
+    }
+
+    // gRPC calls are answered with http status 200 even if they fail, the outcome is
+    // reported with the gRPC status. Gateway errors keep their message and are mapped
+    // from their http status.
+    async fn handle_worker_binding_grpc(
+        &self,
+        namespace: Namespace,
+        request: &mut RichRequest,
+        binding: WorkerBindingCompiled,
+        rib_execution_limits: &RibExecutionLimits,
+    ) -> Result<poem::Response, GrpcError> {
+        let is_grpc_request = request
+            .headers()
+            .get(http::header::CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok())
+            .is_some_and(|content_type| content_type.starts_with(GRPC_CONTENT_TYPE));
+
+        if !is_grpc_request {
+            return Ok(poem::Response::builder()
+                .status(StatusCode::UNSUPPORTED_MEDIA_TYPE)
+                .body(Body::from_string(format!(
+                    "Expected content type {GRPC_CONTENT_TYPE}"
+                ))));
+        }
+
+        if let Some(request_type) = grpc_request_type(&binding.response_compiled.rib_input) {
+            request.take_grpc_request_body(&request_type).await?;
+        }
+
+        match self
+            .handle_worker_binding(namespace, request, binding, rib_execution_limits)
             .await
+        {
+            Ok(result) => grpc_response_from_rib_result(result),
+            Err(err) => {
+                let response = err.to_response(request, &self.gateway_session_store).await;
+                Err(GrpcError::from_http_response(response).await)
+            }
+        }
     }
 
     async fn handle_http_handler_binding(
@@ -133,6 +261,7 @@ impl DefaultGatewayInputExecutor {
         namespace: &Namespace,
         request: &mut RichRequest,
         binding: HttpHandlerBindingCompiled,
+        rib_execution_limits: &RibExecutionLimits,
     ) -> GatewayHttpResult<HttpHandlerBindingResult> {
         let HttpHandlerBindingCompiled {
             component_id,
@@ -148,6 +277,7 @@ impl DefaultGatewayInputExecutor {
                 idempotency_key_compiled,
                 component_id,
                 None,
+                rib_execution_limits,
             )
             .await?;
 
@@ -174,6 +304,7 @@ impl DefaultGatewayInputExecutor {
         namespace: Namespace,
         request: &mut RichRequest,
         binding: FileServerBindingCompiled,
+        rib_execution_limits: &RibExecutionLimits,
     ) -> GatewayHttpResult<FileServerBindingSuccess> {
         let FileServerBindingCompiled {
             component_id: version_component_id,
@@ -192,6 +323,7 @@ impl DefaultGatewayInputExecutor {
                 idempotency_key_compiled,
                 version_component_id,
                 None,
+                rib_execution_limits,
             )
             .await?;
 
@@ -211,6 +343,7 @@ impl DefaultGatewayInputExecutor {
                 response_compiled,
                 request,
                 worker_detail,
+                rib_execution_limits,
             )
             .await?;
 
@@ -245,6 +378,7 @@ impl DefaultGatewayInputExecutor {
         &self,
         script: WorkerNameCompiled,
         request: &mut RichRequest,
+        rib_execution_limits: &RibExecutionLimits,
     ) -> GatewayHttpResult<String> {
         let WorkerNameCompiled {
             compiled_worker_name,
@@ -254,14 +388,19 @@ impl DefaultGatewayInputExecutor {
 
         let rib_input: RibInput = resolve_rib_input(request, &rib_input_type_info).await?;
 
-        let result = rib::interpret_pure(compiled_worker_name, rib_input, None)
-            .await
-            .map_err(|err| GatewayHttpError::RibInterpretPureError(err.to_string()))?
-            .get_literal()
-            .ok_or(GatewayHttpError::BadRequest(
-                "Worker name is not a Rib expression that resolves to String".to_string(),
-            ))?
-            .as_string();
+        let result = rib::interpret_pure_with_limits(
+            compiled_worker_name,
+            rib_input,
+            None,
+            rib_execution_limits.clone(),
+        )
+        .await
+        .map_err(GatewayHttpError::from_pure_rib_error)?
+        .get_literal()
+        .ok_or(GatewayHttpError::BadRequest(
+            "Worker name is not a Rib expression that resolves to String".to_string(),
+        ))?
+        .as_string();
 
         Ok(result)
     }
@@ -270,6 +409,7 @@ impl DefaultGatewayInputExecutor {
         &self,
         script: IdempotencyKeyCompiled,
         request: &mut RichRequest,
+        rib_execution_limits: &RibExecutionLimits,
     ) -> GatewayHttpResult<IdempotencyKey> {
         let IdempotencyKeyCompiled {
             compiled_idempotency_key,
@@ -279,14 +419,19 @@ impl DefaultGatewayInputExecutor {
 
         let rib_input: RibInput = resolve_rib_input(request, &rib_input).await?;
 
-        let value = rib::interpret_pure(compiled_idempotency_key, rib_input, None)
-            .await
-            .map_err(|err| GatewayHttpError::RibInterpretPureError(err.to_string()))?
-            .get_literal()
-            .ok_or(GatewayHttpError::BadRequest(
-                "Idempotency key is not a Rib expression that resolves to String".to_string(),
-            ))?
-            .as_string();
+        let value = rib::interpret_pure_with_limits(
+            compiled_idempotency_key,
+            rib_input,
+            None,
+            rib_execution_limits.clone(),
+        )
+        .await
+        .map_err(GatewayHttpError::from_pure_rib_error)?
+        .get_literal()
+        .ok_or(GatewayHttpError::BadRequest(
+            "Idempotency key is not a Rib expression that resolves to String".to_string(),
+        ))?
+        .as_string();
 
         Ok(IdempotencyKey::new(value))
     }
@@ -295,6 +440,7 @@ impl DefaultGatewayInputExecutor {
         &self,
         script: InvocationContextCompiled,
         request: &mut RichRequest,
+        rib_execution_limits: &RibExecutionLimits,
     ) -> GatewayHttpResult<(Option<TraceId>, HashMap<String, ValueAndType>)> {
         let InvocationContextCompiled {
             compiled_invocation_context,
@@ -304,13 +450,18 @@ impl DefaultGatewayInputExecutor {
 
         let rib_input: RibInput = resolve_rib_input(request, &rib_input).await?;
 
-        let value = rib::interpret_pure(compiled_invocation_context, rib_input, None)
-            .await
-            .map_err(|err| GatewayHttpError::RibInterpretPureError(err.to_string()))?
-            .get_record()
-            .ok_or(GatewayHttpError::BadRequest(
-                "Invocation context must be a Rib expression that resolves to record".to_string(),
-            ))?;
+        let value = rib::interpret_pure_with_limits(
+            compiled_invocation_context,
+            rib_input,
+            None,
+            rib_execution_limits.clone(),
+        )
+        .await
+        .map_err(GatewayHttpError::from_pure_rib_error)?
+        .get_record()
+        .ok_or(GatewayHttpError::BadRequest(
+            "Invocation context must be a Rib expression that resolves to record".to_string(),
+        ))?;
         let record: HashMap<String, ValueAndType> = HashMap::from_iter(value);
 
         let trace_id = record
@@ -361,10 +512,15 @@ impl DefaultGatewayInputExecutor {
         idempotency_key_compiled: Option<IdempotencyKeyCompiled>,
         component_id: VersionedComponentId,
         invocation_context_compiled: Option<InvocationContextCompiled>,
+        rib_execution_limits: &RibExecutionLimits,
     ) -> GatewayHttpResult<WorkerDetails> {
         let worker_name = if let Some(worker_name_compiled) = worker_name_compiled {
             let result = self
-                .evaluate_worker_name_rib_script(worker_name_compiled, request)
+                .evaluate_worker_name_rib_script(
+                    worker_name_compiled,
+                    request,
+                    rib_execution_limits,
+                )
                 .await?;
             Some(result)
         } else {
@@ -376,7 +532,11 @@ impl DefaultGatewayInputExecutor {
         // If neither is available, the worker-executor will later generate an idempotency key.
         let idempotency_key = if let Some(idempotency_key_compiled) = idempotency_key_compiled {
             let result = self
-                .evaluate_idempotency_key_rib_script(idempotency_key_compiled, request)
+                .evaluate_idempotency_key_rib_script(
+                    idempotency_key_compiled,
+                    request,
+                    rib_execution_limits,
+                )
                 .await?;
             Some(result)
         } else {
@@ -396,7 +556,11 @@ impl DefaultGatewayInputExecutor {
             let trace_context_headers = TraceContextHeaders::parse(request.underlying.headers());
 
             let (user_defined_trace_id, user_defined_span) = self
-                .evaluate_invocation_context_rib_script(invocation_context_compiled, request)
+                .evaluate_invocation_context_rib_script(
+                    invocation_context_compiled,
+                    request,
+                    rib_execution_limits,
+                )
                 .await?;
 
             match (trace_context_headers, &user_defined_trace_id) {
@@ -457,6 +621,7 @@ impl DefaultGatewayInputExecutor {
         compiled_response_mapping: ResponseMappingCompiled,
         request: &mut RichRequest,
         worker_detail: WorkerDetails,
+        rib_execution_limits: &RibExecutionLimits,
     ) -> GatewayHttpResult<RibResult> {
         let WorkerDetails {
             invocation_context,
@@ -479,6 +644,7 @@ impl DefaultGatewayInputExecutor {
                 response_mapping_compiled,
                 rib_input,
                 namespace,
+                rib_execution_limits.clone(),
             )
             .await
             .map_err(GatewayHttpError::EvaluationError)
@@ -492,7 +658,7 @@ impl DefaultGatewayInputExecutor {
         if let Some(middlewares) = middlewares {
             let input_middleware_result = middlewares
                 .process_middleware_in(
-                    &request,
+                    &mut request,
                     &self.gateway_session_store,
                     &self.identity_provider,
                 )
@@ -532,6 +698,7 @@ impl DefaultGatewayInputExecutor {
                         MiddlewareError::InternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
                         MiddlewareError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
                         MiddlewareError::CorsError(_) => StatusCode::FORBIDDEN,
+                        MiddlewareError::RequestBodyTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
                     });
                     Err(response)?
                 }
@@ -573,8 +740,35 @@ impl GatewayHttpInputExecutor for DefaultGatewayInputExecutor {
             }
         };
 
+        let traffic_splits = match self
+            .api_definition_lookup_service
+            .get_traffic_splits(&ApiSiteString(authority.clone()))
+            .await
+        {
+            Ok(traffic_splits) => traffic_splits,
+            Err(api_defs_lookup_error) => {
+                error!(
+                    "API request host: {} - error: {}",
+                    authority,
+                    api_defs_lookup_error.to_safe_string()
+                );
+
+                return api_defs_lookup_error
+                    .to_response_from_safe_display(get_status_code_from_api_lookup_error);
+            }
+        };
+
+        let TrafficSplitSelection {
+            api_definitions,
+            variants,
+        } = TrafficSplitSelection::select(
+            possible_api_definitions,
+            &traffic_splits,
+            request.headers(),
+        );
+
         let resolved_route_entry = if let Some(resolved_route_entry) =
-            resolve_gateway_binding(possible_api_definitions, &request).await
+            resolve_gateway_binding(api_definitions, &request).await
         {
             resolved_route_entry
         } else {
@@ -583,13 +777,44 @@ impl GatewayHttpInputExecutor for DefaultGatewayInputExecutor {
                 .body(Body::from_string("Route not found".to_string()));
         };
 
+        let traffic_split_variant = variants.get(&resolved_route_entry.api_definition);
+
+        if let Some(variant) = traffic_split_variant {
+            variant.record_request();
+        }
+
+        let start = Instant::now();
+
+        let response = self
+            .execute_resolved_route(authority, request, resolved_route_entry)
+            .await;
+
+        if let Some(variant) = traffic_split_variant {
+            variant.record_response(&response, start.elapsed());
+        }
+
+        response
+    }
+}
+
+impl DefaultGatewayInputExecutor {
+    async fn execute_resolved_route(
+        &self,
+        authority: String,
+        request: poem::Request,
+        resolved_route_entry: ResolvedRouteEntry,
+    ) -> poem::Response {
         let SplitResolvedRouteEntryResult {
             namespace,
             binding,
             middlewares,
+            rib_execution_limits,
             rich_request,
         } = split_resolved_route_entry(request, resolved_route_entry);
 
+        let rib_execution_limits =
+            rib_execution_limits_or_default(rib_execution_limits, &self.rib_execution_limits);
+
         let mut rich_request = match self
             .maybe_apply_middlewares_in(rich_request, &middlewares)
             .await
@@ -601,7 +826,7 @@ impl GatewayHttpInputExecutor for DefaultGatewayInputExecutor {
             }
         };
 
-        match binding {
+        let response = match binding {
             GatewayBindingCompiled::Static(StaticBinding::HttpCorsPreflight(cors_preflight)) => {
                 cors_preflight
                     .clone()
@@ -622,16 +847,52 @@ impl GatewayHttpInputExecutor for DefaultGatewayInputExecutor {
                     .await
             }
 
+            GatewayBindingCompiled::Worker(resolved_worker_binding)
+                if resolved_worker_binding.response_stream == Some(ResponseStreamType::Sse) =>
+            {
+                match self
+                    .handle_worker_binding_streaming(
+                        namespace,
+                        &mut rich_request,
+                        *resolved_worker_binding,
+                        &rib_execution_limits,
+                    )
+                    .await
+                {
+                    Ok(response) => response,
+                    Err(err) => {
+                        err.to_response(&rich_request, &self.gateway_session_store)
+                            .await
+                    }
+                }
+            }
+
+            GatewayBindingCompiled::Worker(resolved_worker_binding)
+                if resolved_worker_binding.ingress_protocol == Some(IngressProtocolType::Grpc) =>
+            {
+                self.handle_worker_binding_grpc(
+                    namespace,
+                    &mut rich_request,
+                    *resolved_worker_binding,
+                    &rib_execution_limits,
+                )
+                .await
+                .unwrap_or_else(|err| err.into_response())
+            }
+
             GatewayBindingCompiled::Worker(resolved_worker_binding) => {
                 let result = self
-                    .handle_worker_binding(namespace, &mut rich_request, *resolved_worker_binding)
+                    .handle_worker_binding(
+                        namespace,
+                        &mut rich_request,
+                        *resolved_worker_binding,
+                        &rib_execution_limits,
+                    )
                     .await;
 
-                let response = result
+                result
                     .to_response(&rich_request, &self.gateway_session_store)
-                    .await;
-
-                maybe_apply_middlewares_out(response, &middlewares).await
+                    .await
             }
 
             GatewayBindingCompiled::HttpHandler(http_handler_binding) => {
@@ -640,14 +901,13 @@ impl GatewayHttpInputExecutor for DefaultGatewayInputExecutor {
                         &namespace,
                         &mut rich_request,
                         *http_handler_binding,
+                        &rib_execution_limits,
                     )
                     .await;
 
-                let response = result
+                result
                     .to_response(&rich_request, &self.gateway_session_store)
-                    .await;
-
-                maybe_apply_middlewares_out(response, &middlewares).await
+                    .await
             }
 
             GatewayBindingCompiled::FileServer(resolved_file_server_binding) => {
@@ -656,14 +916,13 @@ impl GatewayHttpInputExecutor for DefaultGatewayInputExecutor {
                         namespace,
                         &mut rich_request,
                         *resolved_file_server_binding,
+                        &rib_execution_limits,
                     )
                     .await;
 
-                let response = result
+                result
                     .to_response(&rich_request, &self.gateway_session_store)
-                    .await;
-
-                maybe_apply_middlewares_out(response, &middlewares).await
+                    .await
             }
 
             GatewayBindingCompiled::SwaggerUi(swagger_binding) => {
@@ -672,13 +931,30 @@ impl GatewayHttpInputExecutor for DefaultGatewayInputExecutor {
                     .handle_swagger_binding_request(&authority, &swagger_binding)
                     .await;
 
-                let response = result
+                result
                     .to_response(&rich_request, &self.gateway_session_store)
-                    .await;
-
-                maybe_apply_middlewares_out(response, &middlewares).await
+                    .await
             }
-        }
+        };
+
+        maybe_apply_middlewares_out(response, &rich_request, &middlewares).await
+    }
+}
+
+// The limits an API definition sets for its Rib scripts, with the ones it does not set taken
+// from the limits of the worker service
+fn rib_execution_limits_or_default(
+    rib_execution_limits: Option<RibExecutionLimits>,
+    default: &RibExecutionLimits,
+) -> RibExecutionLimits {
+    match rib_execution_limits {
+        Some(limits) => RibExecutionLimits {
+            max_instructions: limits.max_instructions.or(default.max_instructions),
+            max_stack_depth: limits.max_stack_depth.or(default.max_stack_depth),
+            max_list_size: limits.max_list_size.or(default.max_list_size),
+            max_worker_calls: limits.max_worker_calls.or(default.max_worker_calls),
+        },
+        None => default.clone(),
     }
 }
 
@@ -856,10 +1132,13 @@ async fn resolve_rib_input(
 
 async fn maybe_apply_middlewares_out(
     mut response: poem::Response,
+    rich_request: &RichRequest,
     middlewares: &Option<HttpMiddlewares>,
 ) -> poem::Response {
     if let Some(middlewares) = middlewares {
-        let result = middlewares.process_middleware_out(&mut response).await;
+        let result = middlewares
+            .process_middleware_out(rich_request, &mut response)
+            .await;
         match result {
             Ok(_) => response,
             Err(err) => {
@@ -899,6 +1178,7 @@ fn get_status_code_from_api_lookup_error(error: &ApiDefinitionLookupError) -> St
                 }
 
                 ApiDeploymentError::ApiDefinitionsConflict(_) => StatusCode::INTERNAL_SERVER_ERROR,
+                ApiDeploymentError::InvalidTrafficSplit(_) => StatusCode::INTERNAL_SERVER_ERROR,
                 ApiDeploymentError::InternalRepoError(_) => StatusCode::INTERNAL_SERVER_ERROR,
                 ApiDeploymentError::InternalConversionError { .. } => {
                     StatusCode::INTERNAL_SERVER_ERROR
@@ -12,17 +12,25 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::gateway_execution::sse_response::{GatewayStreamEvent, GatewayStreamEventSender};
 use crate::gateway_execution::GatewayResolvedWorkerRequest;
 use crate::service::component::ComponentService;
 use crate::service::worker::WorkerService;
 use async_trait::async_trait;
+use futures::{Stream, StreamExt};
+use golem_api_grpc::proto::golem::worker::log_event::Event as LogEventKind;
+use golem_api_grpc::proto::golem::worker::LogEvent;
 use golem_common::model::auth::{AuthCtx, TokenSecret};
-use golem_common::model::WorkerId;
+use golem_common::model::{IdempotencyKey, WorkerId};
 use golem_common::SafeDisplay;
 use golem_wasm::ValueAndType;
 use std::collections::BTreeMap;
 use std::fmt::Display;
+use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+use tonic::Status;
 use tracing::debug;
 
 #[async_trait]
@@ -31,6 +39,17 @@ pub trait GatewayWorkerRequestExecutor: Send + Sync {
         &self,
         resolved_worker_request: GatewayResolvedWorkerRequest,
     ) -> Result<WorkerResponse, WorkerRequestExecutorError>;
+
+    // Same as `execute`, but forwards the output the invocation produces on the
+    // worker to `events` while it is running. Executors that cannot observe
+    // the worker simply execute the request.
+    async fn execute_streaming(
+        &self,
+        resolved_worker_request: GatewayResolvedWorkerRequest,
+        _events: GatewayStreamEventSender,
+    ) -> Result<WorkerResponse, WorkerRequestExecutorError> {
+        self.execute(resolved_worker_request).await
+    }
 }
 
 // The result of a worker execution from worker-bridge,
@@ -82,12 +101,11 @@ impl GatewayWorkerRequestExecutorDefault {
     }
 }
 
-#[async_trait]
-impl GatewayWorkerRequestExecutor for GatewayWorkerRequestExecutorDefault {
-    async fn execute(
+impl GatewayWorkerRequestExecutorDefault {
+    async fn resolve_worker_id(
         &self,
-        resolved_worker_request: GatewayResolvedWorkerRequest,
-    ) -> Result<WorkerResponse, WorkerRequestExecutorError> {
+        resolved_worker_request: &GatewayResolvedWorkerRequest,
+    ) -> Result<WorkerId, WorkerRequestExecutorError> {
         let component = self
             .component_service
             .get_by_version(
@@ -101,9 +119,17 @@ impl GatewayWorkerRequestExecutor for GatewayWorkerRequestExecutorDefault {
         let worker_id = WorkerId::from_component_metadata_and_worker_id(
             component.versioned_component_id.component_id.clone(),
             &component.metadata,
-            resolved_worker_request.worker_name,
+            resolved_worker_request.worker_name.clone(),
         )?;
 
+        Ok(worker_id)
+    }
+
+    async fn invoke(
+        &self,
+        worker_id: WorkerId,
+        resolved_worker_request: GatewayResolvedWorkerRequest,
+    ) -> Result<WorkerResponse, WorkerRequestExecutorError> {
         debug!(
             component_id = resolved_worker_request.component_id.to_string(),
             function_name = resolved_worker_request.function_name,
@@ -135,3 +161,353 @@ impl GatewayWorkerRequestExecutor for GatewayWorkerRequestExecutorDefault {
         })
     }
 }
+
+#[async_trait]
+impl GatewayWorkerRequestExecutor for GatewayWorkerRequestExecutorDefault {
+    async fn execute(
+        &self,
+        resolved_worker_request: GatewayResolvedWorkerRequest,
+    ) -> Result<WorkerResponse, WorkerRequestExecutorError> {
+        let worker_id = self.resolve_worker_id(&resolved_worker_request).await?;
+
+        self.invoke(worker_id, resolved_worker_request).await
+    }
+
+    async fn execute_streaming(
+        &self,
+        mut resolved_worker_request: GatewayResolvedWorkerRequest,
+        events: GatewayStreamEventSender,
+    ) -> Result<WorkerResponse, WorkerRequestExecutorError> {
+        let worker_id = self.resolve_worker_id(&resolved_worker_request).await?;
+
+        // The idempotency key identifies the invocation in the worker's event stream, which
+        // contains the output of all the invocations of the worker
+        let idempotency_key = resolved_worker_request
+            .idempotency_key
+            .get_or_insert_with(IdempotencyKey::fresh)
+            .clone();
+
+        let worker_service = self.worker_service.clone();
+        let connected_worker_id = worker_id.clone();
+        let namespace = resolved_worker_request.namespace.clone();
+
+        let connect = move || {
+            let worker_service = worker_service.clone();
+            let worker_id = connected_worker_id.clone();
+            let namespace = namespace.clone();
+            async move {
+                worker_service
+                    .connect(&worker_id, namespace)
+                    .await
+                    .map_err(|err| err.to_safe_string())
+            }
+        };
+
+        // Connecting before the invocation, so no output of an existing worker is missed.
+        // It fails if the worker does not exist yet, in which case it is retried while the
+        // invocation that creates the worker is running.
+        let first_attempt = connect().await;
+        let invocation_finished = CancellationToken::new();
+        let forwarder = tokio::spawn(connect_and_forward_worker_events(
+            first_attempt,
+            connect,
+            idempotency_key,
+            events,
+            invocation_finished.clone(),
+        ));
+
+        let result = self.invoke(worker_id, resolved_worker_request).await;
+
+        invocation_finished.cancel();
+        let _ = forwarder.await;
+
+        result
+    }
+}
+
+const CONNECT_RETRY_DELAY: Duration = Duration::from_millis(100);
+
+// Forwards the invocation's output until it has finished, retrying to connect
+// to the worker if the first attempt has failed.
+async fn connect_and_forward_worker_events<Connect, ConnectResult, WorkerStream>(
+    first_attempt: Result<WorkerStream, String>,
+    mut connect: Connect,
+    idempotency_key: IdempotencyKey,
+    events: GatewayStreamEventSender,
+    invocation_finished: CancellationToken,
+) where
+    Connect: FnMut() -> ConnectResult,
+    ConnectResult: Future<Output = Result<WorkerStream, String>>,
+    WorkerStream: Stream<Item = Result<LogEvent, Status>> + Unpin,
+{
+    let mut attempt = first_attempt;
+    let worker_stream = loop {
+        match attempt {
+            Ok(worker_stream) => break worker_stream,
+            Err(err) => {
+                debug!("Failed to connect to worker, retrying: {err}");
+            }
+        }
+
+        tokio::select! {
+            biased;
+            _ = invocation_finished.cancelled() => return,
+            _ = tokio::time::sleep(CONNECT_RETRY_DELAY) => {}
+        }
+
+        attempt = connect().await;
+    };
+
+    forward_worker_events(worker_stream, idempotency_key, events, invocation_finished).await
+}
+
+// Forwards worker output until the invocation has finished. Events that are
+// already received are preferred over the cancellation, so that the output
+// written right before returning is not lost.
+//
+// The worker's event stream contains the output of every invocation of the worker,
+// including the ones made by other callers, so only the events between the start and
+// the end of the invocation with the given idempotency key are forwarded.
+async fn forward_worker_events<WorkerStream>(
+    mut worker_stream: WorkerStream,
+    idempotency_key: IdempotencyKey,
+    events: GatewayStreamEventSender,
+    invocation_finished: CancellationToken,
+) where
+    WorkerStream: Stream<Item = Result<LogEvent, Status>> + Unpin,
+{
+    let is_this_invocation =
+        |key: &Option<golem_api_grpc::proto::golem::worker::IdempotencyKey>| {
+            key.as_ref().map(|key| key.value.as_str()) == Some(idempotency_key.value.as_str())
+        };
+    let mut invocation_running = false;
+
+    loop {
+        tokio::select! {
+            biased;
+            message = worker_stream.next() => {
+                match message {
+                    Some(Ok(log_event)) => {
+                        match &log_event.event {
+                            Some(LogEventKind::InvocationStarted(started)) => {
+                                if is_this_invocation(&started.idempotency_key) {
+                                    invocation_running = true;
+                                }
+                                continue;
+                            }
+                            Some(LogEventKind::InvocationFinished(finished)) => {
+                                if is_this_invocation(&finished.idempotency_key) {
+                                    invocation_running = false;
+                                }
+                                continue;
+                            }
+                            _ if !invocation_running => continue,
+                            _ => {}
+                        }
+
+                        if let Some(event) = GatewayStreamEvent::from_log_event(log_event) {
+                            if events.send(event).await.is_err() {
+                                // The client has disconnected
+                                break;
+                            }
+                        }
+                    }
+                    Some(Err(status)) => {
+                        debug!("Worker event stream failed: {status}");
+                        break;
+                    }
+                    None => break,
+                }
+            }
+            _ = invocation_finished.cancelled() => {
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{connect_and_forward_worker_events, forward_worker_events};
+    use crate::gateway_execution::sse_response::GatewayStreamEvent;
+    use golem_api_grpc::proto::golem::worker::log_event::Event as LogEventKind;
+    use golem_api_grpc::proto::golem::worker::{
+        InvocationFinished, InvocationStarted, LogEvent, StdOutLog,
+    };
+    use golem_common::model::IdempotencyKey;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use test_r::test;
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::UnboundedReceiverStream;
+    use tokio_util::sync::CancellationToken;
+    use tonic::Status;
+
+    fn stdout(message: &str) -> Result<LogEvent, Status> {
+        Ok(LogEvent {
+            event: Some(LogEventKind::Stdout(StdOutLog {
+                message: message.to_string(),
+                timestamp: None,
+            })),
+        })
+    }
+
+    fn invocation_started(idempotency_key: &IdempotencyKey) -> Result<LogEvent, Status> {
+        Ok(LogEvent {
+            event: Some(LogEventKind::InvocationStarted(InvocationStarted {
+                timestamp: None,
+                function: "f".to_string(),
+                idempotency_key: Some(idempotency_key.clone().into()),
+            })),
+        })
+    }
+
+    fn invocation_finished(idempotency_key: &IdempotencyKey) -> Result<LogEvent, Status> {
+        Ok(LogEvent {
+            event: Some(LogEventKind::InvocationFinished(InvocationFinished {
+                timestamp: None,
+                function: "f".to_string(),
+                idempotency_key: Some(idempotency_key.clone().into()),
+            })),
+        })
+    }
+
+    #[test]
+    async fn output_of_new_worker_is_streamed() {
+        let idempotency_key = IdempotencyKey::fresh();
+        let (events_tx, mut events_rx) = mpsc::channel(16);
+        let (worker_tx, worker_rx) = mpsc::unbounded_channel();
+        let worker_rx = Arc::new(std::sync::Mutex::new(Some(worker_rx)));
+
+        // The worker only exists once the invocation has created it
+        let worker_created = Arc::new(AtomicBool::new(false));
+        let connect_attempts = Arc::new(AtomicUsize::new(0));
+
+        let invocation_finished = CancellationToken::new();
+        let forwarder = tokio::spawn(connect_and_forward_worker_events(
+            Err("worker not found".to_string()),
+            {
+                let worker_created = worker_created.clone();
+                let connect_attempts = connect_attempts.clone();
+                move || {
+                    let worker_created = worker_created.clone();
+                    let connect_attempts = connect_attempts.clone();
+                    let worker_rx = worker_rx.clone();
+                    async move {
+                        connect_attempts.fetch_add(1, Ordering::SeqCst);
+                        if worker_created.load(Ordering::SeqCst) {
+                            let receiver = worker_rx.lock().unwrap().take().unwrap();
+                            Ok(UnboundedReceiverStream::new(receiver))
+                        } else {
+                            Err("worker not found".to_string())
+                        }
+                    }
+                }
+            },
+            idempotency_key.clone(),
+            events_tx,
+            invocation_finished.clone(),
+        ));
+
+        // Invocation starts and creates the worker, which writes to its stdout
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        worker_created.store(true, Ordering::SeqCst);
+        worker_tx
+            .send(invocation_started(&idempotency_key))
+            .unwrap();
+        worker_tx.send(stdout("hello")).unwrap();
+        worker_tx.send(stdout("world")).unwrap();
+
+        assert_eq!(
+            events_rx.recv().await,
+            Some(GatewayStreamEvent::StdOut("hello".to_string()))
+        );
+        assert_eq!(
+            events_rx.recv().await,
+            Some(GatewayStreamEvent::StdOut("world".to_string()))
+        );
+
+        invocation_finished.cancel();
+        forwarder.await.unwrap();
+
+        assert!(connect_attempts.load(Ordering::SeqCst) >= 1);
+    }
+
+    #[test]
+    async fn connect_is_not_retried_after_invocation_finished() {
+        let (events_tx, mut events_rx) = mpsc::channel(16);
+        let connect_attempts = Arc::new(AtomicUsize::new(0));
+
+        let invocation_finished = CancellationToken::new();
+        invocation_finished.cancel();
+
+        connect_and_forward_worker_events(
+            Err::<UnboundedReceiverStream<Result<LogEvent, Status>>, _>(
+                "worker not found".to_string(),
+            ),
+            {
+                let connect_attempts = connect_attempts.clone();
+                move || {
+                    let connect_attempts = connect_attempts.clone();
+                    async move {
+                        connect_attempts.fetch_add(1, Ordering::SeqCst);
+                        Err("worker not found".to_string())
+                    }
+                }
+            },
+            IdempotencyKey::fresh(),
+            events_tx,
+            invocation_finished,
+        )
+        .await;
+
+        assert_eq!(connect_attempts.load(Ordering::SeqCst), 0);
+        assert_eq!(events_rx.recv().await, None);
+    }
+
+    #[test]
+    async fn only_output_of_this_invocation_is_streamed() {
+        let (events_tx, mut events_rx) = mpsc::channel(16);
+        let (worker_tx, worker_rx) = mpsc::unbounded_channel();
+        let this_invocation = IdempotencyKey::fresh();
+        let other_invocation = IdempotencyKey::fresh();
+
+        // Invocations of another caller run on the same worker before and after this one
+        worker_tx
+            .send(invocation_started(&other_invocation))
+            .unwrap();
+        worker_tx.send(stdout("other before")).unwrap();
+        worker_tx
+            .send(invocation_finished(&other_invocation))
+            .unwrap();
+        worker_tx
+            .send(invocation_started(&this_invocation))
+            .unwrap();
+        worker_tx.send(stdout("this")).unwrap();
+        worker_tx
+            .send(invocation_finished(&this_invocation))
+            .unwrap();
+        worker_tx
+            .send(invocation_started(&other_invocation))
+            .unwrap();
+        worker_tx.send(stdout("other after")).unwrap();
+        worker_tx
+            .send(invocation_finished(&other_invocation))
+            .unwrap();
+        drop(worker_tx);
+
+        forward_worker_events(
+            UnboundedReceiverStream::new(worker_rx),
+            this_invocation,
+            events_tx,
+            CancellationToken::new(),
+        )
+        .await;
+
+        assert_eq!(
+            events_rx.recv().await,
+            Some(GatewayStreamEvent::StdOut("this".to_string()))
+        );
+        assert_eq!(events_rx.recv().await, None);
+    }
+}
@@ -0,0 +1,628 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Protobuf wire format of values, driven by their analysed types.
+//
+// The messages follow the schema rendered by `api_proto_convert`:
+//   - records, tuples and flags are messages with one field per item, numbered from 1
+//   - variants and results are messages with a single `oneof`
+//   - lists are repeated fields, options are fields with presence
+//   - lists and options that cannot be expressed as a field directly (such as a list of lists,
+//     or a list in a oneof) are wrapped in a message with a single `value = 1` field
+//   - any other top level type is wrapped the same way, as gRPC methods exchange messages
+
+use bytes::{Buf, BufMut, Bytes};
+use golem_wasm::analysis::AnalysedType;
+use golem_wasm::Value;
+use prost::encoding::{decode_key, decode_varint, encode_key, encode_varint, WireType};
+use std::collections::HashMap;
+
+const WRAPPED_VALUE_FIELD: u32 = 1;
+
+pub fn decode_message(bytes: &[u8], typ: &AnalysedType) -> Result<Value, String> {
+    if is_message(typ) {
+        decode_message_body(bytes, typ)
+    } else {
+        decode_wrapped(bytes, typ)
+    }
+}
+
+pub fn encode_message(value: &Value, typ: &AnalysedType) -> Result<Vec<u8>, String> {
+    let mut buf = Vec::new();
+
+    if is_message(typ) {
+        encode_message_body(value, typ, &mut buf)?;
+    } else {
+        encode_field(WRAPPED_VALUE_FIELD, value, typ, &mut buf)?;
+    }
+
+    Ok(buf)
+}
+
+// Types that are represented by a message of their own
+pub fn is_message(typ: &AnalysedType) -> bool {
+    matches!(
+        typ,
+        AnalysedType::Record(_)
+            | AnalysedType::Tuple(_)
+            | AnalysedType::Flags(_)
+            | AnalysedType::Variant(_)
+            | AnalysedType::Result(_)
+    )
+}
+
+// Lists and options need a wrapper message when they are not the type of a plain field
+pub fn needs_wrapper(typ: &AnalysedType) -> bool {
+    matches!(typ, AnalysedType::List(_) | AnalysedType::Option(_))
+}
+
+fn is_packable(typ: &AnalysedType) -> bool {
+    matches!(
+        typ,
+        AnalysedType::Bool(_)
+            | AnalysedType::U8(_)
+            | AnalysedType::U16(_)
+            | AnalysedType::U32(_)
+            | AnalysedType::U64(_)
+            | AnalysedType::S8(_)
+            | AnalysedType::S16(_)
+            | AnalysedType::S32(_)
+            | AnalysedType::S64(_)
+            | AnalysedType::F32(_)
+            | AnalysedType::F64(_)
+            | AnalysedType::Chr(_)
+            | AnalysedType::Enum(_)
+    )
+}
+
+#[derive(Debug, Clone)]
+enum RawField {
+    Varint(u64),
+    Fixed64(u64),
+    Fixed32(u32),
+    LengthDelimited(Bytes),
+}
+
+fn read_fields(bytes: &[u8]) -> Result<HashMap<u32, Vec<RawField>>, String> {
+    let mut buf = Bytes::copy_from_slice(bytes);
+    let mut fields: HashMap<u32, Vec<RawField>> = HashMap::new();
+
+    while buf.has_remaining() {
+        let (number, wire_type) = decode_key(&mut buf).map_err(|err| err.to_string())?;
+
+        let field = match wire_type {
+            WireType::Varint => {
+                RawField::Varint(decode_varint(&mut buf).map_err(|err| err.to_string())?)
+            }
+            WireType::SixtyFourBit => {
+                if buf.remaining() < 8 {
+                    return Err("Unexpected end of message".to_string());
+                }
+                RawField::Fixed64(buf.get_u64_le())
+            }
+            WireType::ThirtyTwoBit => {
+                if buf.remaining() < 4 {
+                    return Err("Unexpected end of message".to_string());
+                }
+                RawField::Fixed32(buf.get_u32_le())
+            }
+            WireType::LengthDelimited => {
+                let length = decode_varint(&mut buf).map_err(|err| err.to_string())? as usize;
+                if buf.remaining() < length {
+                    return Err("Unexpected end of message".to_string());
+                }
+                RawField::LengthDelimited(buf.split_to(length))
+            }
+            WireType::StartGroup | WireType::EndGroup => {
+                return Err(format!("Unsupported group in field {number}"));
+            }
+        };
+
+        fields.entry(number).or_default().push(field);
+    }
+
+    Ok(fields)
+}
+
+fn decode_message_body(bytes: &[u8], typ: &AnalysedType) -> Result<Value, String> {
+    let fields = read_fields(bytes)?;
+    let get = |number: usize| -> &[RawField] {
+        fields
+            .get(&(number as u32))
+            .map(|values| values.as_slice())
+            .unwrap_or(&[])
+    };
+
+    match typ {
+        AnalysedType::Record(record) => {
+            let mut values = Vec::with_capacity(record.fields.len());
+            for (index, field) in record.fields.iter().enumerate() {
+                let value = decode_field(get(index + 1), &field.typ)
+                    .map_err(|err| format!("Invalid field `{}`: {err}", field.name))?;
+                values.push(value);
+            }
+            Ok(Value::Record(values))
+        }
+        AnalysedType::Tuple(tuple) => {
+            let mut values = Vec::with_capacity(tuple.items.len());
+            for (index, item) in tuple.items.iter().enumerate() {
+                let value = decode_field(get(index + 1), item)
+                    .map_err(|err| format!("Invalid tuple item {index}: {err}"))?;
+                values.push(value);
+            }
+            Ok(Value::Tuple(values))
+        }
+        AnalysedType::Flags(flags) => {
+            let mut values = Vec::with_capacity(flags.names.len());
+            for (index, name) in flags.names.iter().enumerate() {
+                let value = match get(index + 1).last() {
+                    Some(RawField::Varint(v)) => *v != 0,
+                    Some(_) => return Err(format!("Invalid flag `{name}`: unexpected wire type")),
+                    None => false,
+                };
+                values.push(value);
+            }
+            Ok(Value::Flags(values))
+        }
+        AnalysedType::Variant(variant) => {
+            // Well-behaved clients send a single case of a oneof
+            let selected = last_present_case(&fields, variant.cases.len())
+                .ok_or("Missing variant case".to_string())?;
+            let case = &variant.cases[selected];
+            let case_value = match &case.typ {
+                Some(case_type) => Some(Box::new(
+                    decode_single(get(selected + 1).last().unwrap(), case_type)
+                        .map_err(|err| format!("Invalid case `{}`: {err}", case.name))?,
+                )),
+                None => None,
+            };
+            Ok(Value::Variant {
+                case_idx: selected as u32,
+                case_value,
+            })
+        }
+        AnalysedType::Result(result) => {
+            let selected =
+                last_present_case(&fields, 2).ok_or("Missing result case".to_string())?;
+            let case_type = if selected == 0 {
+                &result.ok
+            } else {
+                &result.err
+            };
+            let case_value = match case_type {
+                Some(case_type) => Some(Box::new(decode_single(
+                    get(selected + 1).last().unwrap(),
+                    case_type,
+                )?)),
+                None => None,
+            };
+            if selected == 0 {
+                Ok(Value::Result(Ok(case_value)))
+            } else {
+                Ok(Value::Result(Err(case_value)))
+            }
+        }
+        _ => Err(format!("{typ:?} is not a message type")),
+    }
+}
+
+// Index of the case of a oneof, if there are more, the one with the highest field number
+fn last_present_case(fields: &HashMap<u32, Vec<RawField>>, case_count: usize) -> Option<usize> {
+    (0..case_count)
+        .rev()
+        .find(|index| fields.contains_key(&(*index as u32 + 1)))
+}
+
+fn decode_wrapped(bytes: &[u8], typ: &AnalysedType) -> Result<Value, String> {
+    let fields = read_fields(bytes)?;
+    let raw = fields
+        .get(&WRAPPED_VALUE_FIELD)
+        .map(|values| values.as_slice())
+        .unwrap_or(&[]);
+
+    decode_field(raw, typ)
+}
+
+fn decode_field(raw: &[RawField], typ: &AnalysedType) -> Result<Value, String> {
+    match typ {
+        AnalysedType::List(list) => {
+            let mut values = vec![];
+            for field in raw {
+                match field {
+                    RawField::LengthDelimited(bytes) if is_packable(&list.inner) => {
+                        let mut packed = bytes.clone();
+                        while packed.has_remaining() {
+                            let element = read_packed_element(&mut packed, &list.inner)?;
+                            values.push(decode_single(&element, &list.inner)?);
+                        }
+                    }
+                    field => values.push(decode_single(field, &list.inner)?),
+                }
+            }
+            Ok(Value::List(values))
+        }
+        AnalysedType::Option(option) => match raw.last() {
+            Some(field) => Ok(Value::Option(Some(Box::new(decode_single(
+                field,
+                &option.inner,
+            )?)))),
+            None => Ok(Value::Option(None)),
+        },
+        _ => match raw.last() {
+            Some(field) => decode_single(field, typ),
+            None => default_value(typ),
+        },
+    }
+}
+
+fn read_packed_element(buf: &mut Bytes, typ: &AnalysedType) -> Result<RawField, String> {
+    match typ {
+        AnalysedType::F32(_) if buf.remaining() >= 4 => Ok(RawField::Fixed32(buf.get_u32_le())),
+        AnalysedType::F64(_) if buf.remaining() >= 8 => Ok(RawField::Fixed64(buf.get_u64_le())),
+        AnalysedType::F32(_) | AnalysedType::F64(_) => {
+            Err("Unexpected end of packed field".to_string())
+        }
+        _ => decode_varint(buf)
+            .map(RawField::Varint)
+            .map_err(|err| err.to_string()),
+    }
+}
+
+fn decode_single(raw: &RawField, typ: &AnalysedType) -> Result<Value, String> {
+    match (typ, raw) {
+        (AnalysedType::Bool(_), RawField::Varint(v)) => Ok(Value::Bool(*v != 0)),
+        (AnalysedType::U8(_), RawField::Varint(v)) => narrow(*v).map(Value::U8),
+        (AnalysedType::U16(_), RawField::Varint(v)) => narrow(*v).map(Value::U16),
+        (AnalysedType::U32(_), RawField::Varint(v)) => narrow(*v).map(Value::U32),
+        (AnalysedType::U64(_), RawField::Varint(v)) => Ok(Value::U64(*v)),
+        (AnalysedType::S8(_), RawField::Varint(v)) => narrow(*v as i64).map(Value::S8),
+        (AnalysedType::S16(_), RawField::Varint(v)) => narrow(*v as i64).map(Value::S16),
+        (AnalysedType::S32(_), RawField::Varint(v)) => narrow(*v as i64).map(Value::S32),
+        (AnalysedType::S64(_), RawField::Varint(v)) => Ok(Value::S64(*v as i64)),
+        (AnalysedType::F32(_), RawField::Fixed32(v)) => Ok(Value::F32(f32::from_bits(*v))),
+        (AnalysedType::F64(_), RawField::Fixed64(v)) => Ok(Value::F64(f64::from_bits(*v))),
+        (AnalysedType::Chr(_), RawField::Varint(v)) => narrow::<_, u32>(*v)
+            .and_then(|v| char::from_u32(v).ok_or(format!("Invalid char {v}")))
+            .map(Value::Char),
+        (AnalysedType::Str(_), RawField::LengthDelimited(bytes)) => {
+            String::from_utf8(bytes.to_vec())
+                .map(Value::String)
+                .map_err(|err| err.to_string())
+        }
+        (AnalysedType::Enum(enum_type), RawField::Varint(v)) => {
+            let index = narrow::<_, u32>(*v)?;
+            if (index as usize) < enum_type.cases.len() {
+                Ok(Value::Enum(index))
+            } else {
+                Err(format!("Unknown enum value {index}"))
+            }
+        }
+        (typ, RawField::LengthDelimited(bytes)) if needs_wrapper(typ) => decode_wrapped(bytes, typ),
+        (typ, RawField::LengthDelimited(bytes)) if is_message(typ) => {
+            decode_message_body(bytes, typ)
+        }
+        (AnalysedType::Handle(_), _) => Err("Resources are not supported in messages".to_string()),
+        _ => Err("Unexpected wire type".to_string()),
+    }
+}
+
+fn narrow<T, U: TryFrom<T>>(value: T) -> Result<U, String>
+where
+    T: Copy + std::fmt::Display,
+{
+    U::try_from(value).map_err(|_| format!("Value {value} is out of range"))
+}
+
+// Proto3 does not send fields with default values
+fn default_value(typ: &AnalysedType) -> Result<Value, String> {
+    match typ {
+        AnalysedType::Bool(_) => Ok(Value::Bool(false)),
+        AnalysedType::U8(_) => Ok(Value::U8(0)),
+        AnalysedType::U16(_) => Ok(Value::U16(0)),
+        AnalysedType::U32(_) => Ok(Value::U32(0)),
+        AnalysedType::U64(_) => Ok(Value::U64(0)),
+        AnalysedType::S8(_) => Ok(Value::S8(0)),
+        AnalysedType::S16(_) => Ok(Value::S16(0)),
+        AnalysedType::S32(_) => Ok(Value::S32(0)),
+        AnalysedType::S64(_) => Ok(Value::S64(0)),
+        AnalysedType::F32(_) => Ok(Value::F32(0.0)),
+        AnalysedType::F64(_) => Ok(Value::F64(0.0)),
+        AnalysedType::Chr(_) => Ok(Value::Char('\0')),
+        AnalysedType::Str(_) => Ok(Value::String(String::new())),
+        AnalysedType::Enum(_) => Ok(Value::Enum(0)),
+        AnalysedType::List(_) => Ok(Value::List(vec![])),
+        AnalysedType::Option(_) => Ok(Value::Option(None)),
+        typ if is_message(typ) => decode_message_body(&[], typ),
+        _ => Err("Resources are not supported in messages".to_string()),
+    }
+}
+
+fn encode_message_body(value: &Value, typ: &AnalysedType, buf: &mut Vec<u8>) -> Result<(), String> {
+    match (typ, value) {
+        (AnalysedType::Record(record), Value::Record(values))
+            if record.fields.len() == values.len() =>
+        {
+            for (index, (field, value)) in record.fields.iter().zip(values).enumerate() {
+                encode_field(index as u32 + 1, value, &field.typ, buf)?;
+            }
+            Ok(())
+        }
+        (AnalysedType::Tuple(tuple), Value::Tuple(values)) if tuple.items.len() == values.len() => {
+            for (index, (item, value)) in tuple.items.iter().zip(values).enumerate() {
+                encode_field(index as u32 + 1, value, item, buf)?;
+            }
+            Ok(())
+        }
+        (AnalysedType::Flags(flags), Value::Flags(values)) if flags.names.len() == values.len() => {
+            for (index, value) in values.iter().enumerate() {
+                encode_key(index as u32 + 1, WireType::Varint, buf);
+                encode_varint(*value as u64, buf);
+            }
+            Ok(())
+        }
+        (
+            AnalysedType::Variant(variant),
+            Value::Variant {
+                case_idx,
+                case_value,
+            },
+        ) => {
+            let case = variant
+                .cases
+                .get(*case_idx as usize)
+                .ok_or(format!("Unknown variant case {case_idx}"))?;
+            encode_case(*case_idx + 1, case_value.as_deref(), case.typ.as_ref(), buf)
+        }
+        (AnalysedType::Result(result), Value::Result(Ok(value))) => {
+            encode_case(1, value.as_deref(), result.ok.as_deref(), buf)
+        }
+        (AnalysedType::Result(result), Value::Result(Err(value))) => {
+            encode_case(2, value.as_deref(), result.err.as_deref(), buf)
+        }
+        _ => Err(format!("Value does not match the type {typ:?}")),
+    }
+}
+
+// Cases without a type are sent as an empty message, so that the selected case is still visible
+fn encode_case(
+    number: u32,
+    value: Option<&Value>,
+    typ: Option<&AnalysedType>,
+    buf: &mut Vec<u8>,
+) -> Result<(), String> {
+    match (value, typ) {
+        (Some(value), Some(typ)) => encode_single(number, value, typ, buf),
+        (None, None) => {
+            encode_key(number, WireType::LengthDelimited, buf);
+            encode_varint(0, buf);
+            Ok(())
+        }
+        _ => Err("Value does not match the case type".to_string()),
+    }
+}
+
+fn encode_field(
+    number: u32,
+    value: &Value,
+    typ: &AnalysedType,
+    buf: &mut Vec<u8>,
+) -> Result<(), String> {
+    match (typ, value) {
+        (AnalysedType::List(list), Value::List(values)) => {
+            if is_packable(&list.inner) {
+                if !values.is_empty() {
+                    let mut packed = Vec::new();
+                    for value in values {
+                        encode_scalar(value, &list.inner, &mut packed)?;
+                    }
+                    encode_key(number, WireType::LengthDelimited, buf);
+                    encode_varint(packed.len() as u64, buf);
+                    buf.put_slice(&packed);
+                }
+            } else {
+                for value in values {
+                    encode_single(number, value, &list.inner, buf)?;
+                }
+            }
+            Ok(())
+        }
+        (AnalysedType::Option(option), Value::Option(value)) => match value {
+            Some(value) => encode_single(number, value, &option.inner, buf),
+            None => Ok(()),
+        },
+        _ => encode_single(number, value, typ, buf),
+    }
+}
+
+fn encode_single(
+    number: u32,
+    value: &Value,
+    typ: &AnalysedType,
+    buf: &mut Vec<u8>,
+) -> Result<(), String> {
+    if is_packable(typ) {
+        encode_key(number, wire_type(typ), buf);
+        return encode_scalar(value, typ, buf);
+    }
+
+    let mut nested = Vec::new();
+
+    match (typ, value) {
+        (AnalysedType::Str(_), Value::String(value)) => nested.put_slice(value.as_bytes()),
+        (typ, value) if needs_wrapper(typ) => {
+            encode_field(WRAPPED_VALUE_FIELD, value, typ, &mut nested)?
+        }
+        (typ, value) if is_message(typ) => encode_message_body(value, typ, &mut nested)?,
+        (AnalysedType::Handle(_), _) => {
+            return Err("Resources are not supported in messages".to_string())
+        }
+        _ => return Err(format!("Value does not match the type {typ:?}")),
+    }
+
+    encode_key(number, WireType::LengthDelimited, buf);
+    encode_varint(nested.len() as u64, buf);
+    buf.put_slice(&nested);
+    Ok(())
+}
+
+fn wire_type(typ: &AnalysedType) -> WireType {
+    match typ {
+        AnalysedType::F32(_) => WireType::ThirtyTwoBit,
+        AnalysedType::F64(_) => WireType::SixtyFourBit,
+        _ => WireType::Varint,
+    }
+}
+
+fn encode_scalar(value: &Value, typ: &AnalysedType, buf: &mut Vec<u8>) -> Result<(), String> {
+    match (typ, value) {
+        (AnalysedType::Bool(_), Value::Bool(v)) => encode_varint(*v as u64, buf),
+        (AnalysedType::U8(_), Value::U8(v)) => encode_varint(*v as u64, buf),
+        (AnalysedType::U16(_), Value::U16(v)) => encode_varint(*v as u64, buf),
+        (AnalysedType::U32(_), Value::U32(v)) => encode_varint(*v as u64, buf),
+        (AnalysedType::U64(_), Value::U64(v)) => encode_varint(*v, buf),
+        // Negative int32 values are sign extended to 64 bits on the wire
+        (AnalysedType::S8(_), Value::S8(v)) => encode_varint(*v as i64 as u64, buf),
+        (AnalysedType::S16(_), Value::S16(v)) => encode_varint(*v as i64 as u64, buf),
+        (AnalysedType::S32(_), Value::S32(v)) => encode_varint(*v as i64 as u64, buf),
+        (AnalysedType::S64(_), Value::S64(v)) => encode_varint(*v as u64, buf),
+        (AnalysedType::F32(_), Value::F32(v)) => buf.put_u32_le(v.to_bits()),
+        (AnalysedType::F64(_), Value::F64(v)) => buf.put_u64_le(v.to_bits()),
+        (AnalysedType::Chr(_), Value::Char(v)) => encode_varint(*v as u64, buf),
+        (AnalysedType::Enum(_), Value::Enum(v)) => encode_varint(*v as u64, buf),
+        _ => return Err(format!("Value does not match the type {typ:?}")),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_message, encode_message};
+    use golem_wasm::analysis::analysed_type::{
+        case, f64, field, list, option, r#enum, record, s32, str, u64, u8, unit_case, variant,
+    };
+    use golem_wasm::Value;
+    use test_r::test;
+
+    #[test]
+    fn record_round_trip() {
+        let typ = record(vec![
+            field("name", str()),
+            field("quantity", s32()),
+            field("price", f64()),
+            field("tags", list(str())),
+            field("discount", option(u64())),
+            field("status", r#enum(&["open", "closed"])),
+        ]);
+
+        let value = Value::Record(vec![
+            Value::String("apple".to_string()),
+            Value::S32(-3),
+            Value::F64(1.5),
+            Value::List(vec![
+                Value::String("fruit".to_string()),
+                Value::String("red".to_string()),
+            ]),
+            Value::Option(Some(Box::new(Value::U64(10)))),
+            Value::Enum(1),
+        ]);
+
+        let bytes = encode_message(&value, &typ).unwrap();
+
+        assert_eq!(decode_message(&bytes, &typ).unwrap(), value);
+    }
+
+    #[test]
+    fn encoding_matches_protobuf() {
+        // message Item { string name = 1; int32 quantity = 2; repeated uint64 ids = 3; }
+        let typ = record(vec![
+            field("name", str()),
+            field("quantity", s32()),
+            field("ids", list(u64())),
+        ]);
+
+        let value = Value::Record(vec![
+            Value::String("ab".to_string()),
+            Value::S32(150),
+            Value::List(vec![Value::U64(1), Value::U64(2)]),
+        ]);
+
+        assert_eq!(
+            encode_message(&value, &typ).unwrap(),
+            vec![0x0a, 0x02, b'a', b'b', 0x10, 0x96, 0x01, 0x1a, 0x02, 0x01, 0x02]
+        );
+    }
+
+    #[test]
+    fn missing_fields_are_defaults() {
+        let typ = record(vec![
+            field("name", str()),
+            field("quantity", s32()),
+            field("tags", list(str())),
+            field("discount", option(u64())),
+        ]);
+
+        assert_eq!(
+            decode_message(&[], &typ).unwrap(),
+            Value::Record(vec![
+                Value::String(String::new()),
+                Value::S32(0),
+                Value::List(vec![]),
+                Value::Option(None),
+            ])
+        );
+    }
+
+    #[test]
+    fn variant_round_trip() {
+        let typ = variant(vec![unit_case("empty"), case("items", list(list(str())))]);
+
+        let empty = Value::Variant {
+            case_idx: 0,
+            case_value: None,
+        };
+        let items = Value::Variant {
+            case_idx: 1,
+            case_value: Some(Box::new(Value::List(vec![Value::List(vec![
+                Value::String("a".to_string()),
+            ])]))),
+        };
+
+        for value in [empty, items] {
+            let bytes = encode_message(&value, &typ).unwrap();
+            assert_eq!(decode_message(&bytes, &typ).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn non_message_types_are_wrapped() {
+        let bytes = encode_message(&Value::String("hello".to_string()), &str()).unwrap();
+
+        assert_eq!(bytes, vec![0x0a, 0x05, b'h', b'e', b'l', b'l', b'o']);
+        assert_eq!(
+            decode_message(&bytes, &str()).unwrap(),
+            Value::String("hello".to_string())
+        );
+    }
+
+    #[test]
+    fn out_of_range_values_are_rejected() {
+        let wide = record(vec![field("small", u64())]);
+        let narrow = record(vec![field("small", u8())]);
+
+        let bytes = encode_message(&Value::Record(vec![Value::U64(300)]), &wide).unwrap();
+
+        assert!(decode_message(&bytes, &narrow).is_err());
+    }
+}
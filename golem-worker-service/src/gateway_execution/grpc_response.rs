@@ -0,0 +1,292 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::grpc_codec::encode_message;
+use crate::getter::get_status_code_or_ok;
+use bytes::{BufMut, Bytes, BytesMut};
+use golem_common::widen_infallible;
+use golem_wasm::analysis::AnalysedType;
+use golem_wasm::json::ValueAndTypeJsonExtensions;
+use golem_wasm::{Value, ValueAndType};
+use http::{HeaderMap, HeaderValue, StatusCode};
+use http_body_util::combinators::BoxBody;
+use http_body_util::BodyExt;
+use rib::RibResult;
+
+pub const GRPC_CONTENT_TYPE: &str = "application/grpc";
+
+const FRAME_HEADER_SIZE: usize = 5;
+
+// Status codes of the gRPC protocol, sent in the `grpc-status` trailer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GrpcStatus {
+    Ok = 0,
+    Cancelled = 1,
+    Unknown = 2,
+    InvalidArgument = 3,
+    DeadlineExceeded = 4,
+    NotFound = 5,
+    AlreadyExists = 6,
+    PermissionDenied = 7,
+    ResourceExhausted = 8,
+    FailedPrecondition = 9,
+    Aborted = 10,
+    OutOfRange = 11,
+    Unimplemented = 12,
+    Internal = 13,
+    Unavailable = 14,
+    DataLoss = 15,
+    Unauthenticated = 16,
+}
+
+impl GrpcStatus {
+    // Response mappings and gateway errors use http status codes, these are
+    // translated to the closest gRPC status
+    pub fn from_http_status(status: StatusCode) -> Self {
+        match status.as_u16() {
+            200..=299 => GrpcStatus::Ok,
+            400 => GrpcStatus::InvalidArgument,
+            401 => GrpcStatus::Unauthenticated,
+            403 => GrpcStatus::PermissionDenied,
+            404 => GrpcStatus::NotFound,
+            409 => GrpcStatus::AlreadyExists,
+            412 => GrpcStatus::FailedPrecondition,
+            413 => GrpcStatus::ResourceExhausted,
+            429 => GrpcStatus::ResourceExhausted,
+            499 => GrpcStatus::Cancelled,
+            501 => GrpcStatus::Unimplemented,
+            503 => GrpcStatus::Unavailable,
+            504 => GrpcStatus::DeadlineExceeded,
+            500..=599 => GrpcStatus::Internal,
+            _ => GrpcStatus::Unknown,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GrpcError {
+    pub status: GrpcStatus,
+    pub message: String,
+}
+
+impl GrpcError {
+    pub fn new(status: GrpcStatus, message: impl Into<String>) -> Self {
+        GrpcError {
+            status,
+            message: message.into(),
+        }
+    }
+
+    // Errors of the gateway are rendered as http responses, only their status and
+    // message is kept
+    pub async fn from_http_response(response: poem::Response) -> Self {
+        let status = response.status();
+        let message = response.into_body().into_string().await.unwrap_or_default();
+
+        GrpcError::new(GrpcStatus::from_http_status(status), message)
+    }
+
+    // The error is sent in a Trailers-Only response, as no message precedes it
+    pub fn into_response(self) -> poem::Response {
+        poem::Response::builder()
+            .status(StatusCode::OK)
+            .content_type(GRPC_CONTENT_TYPE)
+            .header("grpc-status", self.status as i32)
+            .header("grpc-message", percent_encode_message(&self.message))
+            .finish()
+    }
+}
+
+// Only unary calls are supported, so the request body holds exactly one message
+pub fn decode_grpc_frame(body: &[u8]) -> Result<&[u8], GrpcError> {
+    if body.len() < FRAME_HEADER_SIZE {
+        return Err(GrpcError::new(
+            GrpcStatus::InvalidArgument,
+            "Missing gRPC message",
+        ));
+    }
+
+    if body[0] != 0 {
+        return Err(GrpcError::new(
+            GrpcStatus::Unimplemented,
+            "Compressed gRPC messages are not supported",
+        ));
+    }
+
+    let length = u32::from_be_bytes([body[1], body[2], body[3], body[4]]) as usize;
+
+    if body.len() != FRAME_HEADER_SIZE + length {
+        return Err(GrpcError::new(
+            GrpcStatus::InvalidArgument,
+            "Streaming gRPC requests are not supported",
+        ));
+    }
+
+    Ok(&body[FRAME_HEADER_SIZE..])
+}
+
+pub fn encode_grpc_frame(message: &[u8]) -> Bytes {
+    let mut frame = BytesMut::with_capacity(FRAME_HEADER_SIZE + message.len());
+    frame.put_u8(0);
+    frame.put_u32(message.len() as u32);
+    frame.put_slice(message);
+    frame.freeze()
+}
+
+// The response message is the `body` of the response mapping, the same way as for http
+// responses. A non-successful `status` fails the call instead, with the body as the message.
+pub fn grpc_response_from_rib_result(result: RibResult) -> Result<poem::Response, GrpcError> {
+    let result = match result {
+        RibResult::Val(result) => result,
+        RibResult::Unit => return Ok(grpc_message_response(vec![])),
+    };
+
+    let status =
+        get_status_code_or_ok(&result).map_err(|err| GrpcError::new(GrpcStatus::Internal, err))?;
+
+    let body = response_body(result);
+
+    let grpc_status = GrpcStatus::from_http_status(status);
+
+    if grpc_status != GrpcStatus::Ok {
+        let message = body
+            .map(|body| match body.value {
+                Value::String(message) => message,
+                _ => body
+                    .to_json_value()
+                    .map(|json| json.to_string())
+                    .unwrap_or_default(),
+            })
+            .unwrap_or_else(|| status.to_string());
+
+        return Err(GrpcError::new(grpc_status, message));
+    }
+
+    let message = match body {
+        Some(body) => encode_message(&body.value, &body.typ).map_err(|err| {
+            GrpcError::new(
+                GrpcStatus::Internal,
+                format!("Failed to encode response: {err}"),
+            )
+        })?,
+        None => vec![],
+    };
+
+    Ok(grpc_message_response(message))
+}
+
+fn response_body(result: ValueAndType) -> Option<ValueAndType> {
+    match (result.value, result.typ) {
+        (Value::Record(values), AnalysedType::Record(record))
+            if record
+                .fields
+                .iter()
+                .any(|field| ["status", "headers", "body"].contains(&field.name.as_str())) =>
+        {
+            record
+                .fields
+                .into_iter()
+                .zip(values)
+                .find(|(field, _)| field.name == "body")
+                .map(|(field, value)| ValueAndType::new(value, field.typ))
+        }
+        (value, typ) => Some(ValueAndType::new(value, typ)),
+    }
+}
+
+fn grpc_message_response(message: Vec<u8>) -> poem::Response {
+    let mut trailers = HeaderMap::new();
+    trailers.insert("grpc-status", HeaderValue::from(GrpcStatus::Ok as i32));
+
+    let body = http_body_util::Full::new(encode_grpc_frame(&message))
+        .with_trailers(async { Some(Ok(trailers)) });
+
+    let boxed: BoxBody<Bytes, std::io::Error> =
+        BoxBody::new(body.map_err(widen_infallible::<std::io::Error>));
+
+    poem::Response::builder()
+        .status(StatusCode::OK)
+        .content_type(GRPC_CONTENT_TYPE)
+        .body(boxed)
+}
+
+// Characters outside of printable ASCII and `%` are percent encoded in `grpc-message`
+fn percent_encode_message(message: &str) -> String {
+    let mut result = String::with_capacity(message.len());
+
+    for byte in message.bytes() {
+        if (0x20..=0x7e).contains(&byte) && byte != b'%' {
+            result.push(byte as char);
+        } else {
+            result.push_str(&format!("%{byte:02X}"));
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        decode_grpc_frame, encode_grpc_frame, grpc_response_from_rib_result,
+        percent_encode_message, GrpcStatus,
+    };
+    use golem_wasm::analysis::analysed_type::{field, record, str, u64};
+    use golem_wasm::{Value, ValueAndType};
+    use rib::RibResult;
+    use test_r::test;
+
+    #[test]
+    fn frame_round_trip() {
+        let frame = encode_grpc_frame(&[1, 2, 3]);
+
+        assert_eq!(frame.as_ref(), &[0, 0, 0, 0, 3, 1, 2, 3]);
+        assert_eq!(decode_grpc_frame(&frame).unwrap(), &[1, 2, 3]);
+    }
+
+    #[test]
+    fn compressed_frames_are_rejected() {
+        let error = decode_grpc_frame(&[1, 0, 0, 0, 0]).unwrap_err();
+
+        assert_eq!(error.status, GrpcStatus::Unimplemented);
+    }
+
+    #[test]
+    fn multiple_messages_are_rejected() {
+        let error = decode_grpc_frame(&[0, 0, 0, 0, 0, 0, 0, 0, 0, 0]).unwrap_err();
+
+        assert_eq!(error.status, GrpcStatus::InvalidArgument);
+    }
+
+    #[test]
+    fn error_status_fails_the_call() {
+        let result = ValueAndType::new(
+            Value::Record(vec![
+                Value::U64(404),
+                Value::String("no such cart".to_string()),
+            ]),
+            record(vec![field("status", u64()), field("body", str())]),
+        );
+
+        let error = grpc_response_from_rib_result(RibResult::Val(result)).unwrap_err();
+
+        assert_eq!(error.status, GrpcStatus::NotFound);
+        assert_eq!(error.message, "no such cart");
+    }
+
+    #[test]
+    fn messages_are_percent_encoded() {
+        assert_eq!(percent_encode_message("100% ok\n"), "100%25 ok%0A");
+    }
+}
@@ -19,13 +19,17 @@ pub mod gateway_binding_resolver;
 pub mod gateway_http_input_executor;
 pub mod gateway_session;
 mod gateway_worker_request_executor;
+pub mod grpc_codec;
+pub mod grpc_response;
 mod http_content_type_mapper;
 pub mod http_handler_binding_handler;
 pub mod request;
 pub mod router;
+pub mod sse_response;
 pub mod swagger_binding_handler;
 pub mod to_response;
 pub mod to_response_failure;
+pub mod traffic_split;
 pub use gateway_worker_request_executor::*;
 
 use golem_common::model::auth::Namespace;
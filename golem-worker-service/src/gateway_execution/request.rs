@@ -13,6 +13,8 @@
 // limitations under the License.
 
 use super::gateway_session::{DataKey, GatewaySessionStore, SessionId};
+use super::grpc_codec::decode_message;
+use super::grpc_response::{decode_grpc_frame, GrpcError, GrpcStatus};
 use crate::gateway_api_definition::http::QueryInfo;
 use crate::gateway_binding::{GatewayBindingCompiled, ResolvedRouteEntry};
 use crate::gateway_middleware::HttpMiddlewares;
@@ -20,7 +22,11 @@ use crate::gateway_request::http_request::router::PathParamExtractor;
 use bytes::Bytes;
 use golem_common::model::auth::Namespace;
 use golem_common::SafeDisplay;
+use golem_wasm::analysis::AnalysedType;
+use golem_wasm::json::ValueAndTypeJsonExtensions;
+use golem_wasm::ValueAndType;
 use http::HeaderMap;
+use rib::RibExecutionLimits;
 use serde_json::Value;
 use std::collections::HashMap;
 use uuid::Uuid;
@@ -31,6 +37,8 @@ const COOKIE_HEADER_NAMES: [&str; 2] = ["cookie", "Cookie"];
 pub struct RichRequest {
     pub underlying: poem::Request,
     pub request_id: Uuid,
+    /// Request id sent by the client, kept as-is even when it is not a UUID
+    pub client_request_id: Option<String>,
     path_segments: Vec<String>,
     path_param_extractors: Vec<PathParamExtractor>,
     auth_data: Option<Value>,
@@ -42,6 +50,7 @@ impl RichRequest {
         RichRequest {
             underlying,
             request_id: Uuid::new_v4(),
+            client_request_id: None,
             path_segments: vec![],
             path_param_extractors: vec![],
             auth_data: None,
@@ -116,6 +125,37 @@ impl RichRequest {
         Ok(self.cached_request_body())
     }
 
+    /// Consumes the body of a gRPC request, and caches the decoded message as the request body,
+    /// so that Rib scripts can refer to it as `request.body` the same way as for http requests.
+    pub async fn take_grpc_request_body(&mut self, typ: &AnalysedType) -> Result<(), GrpcError> {
+        let body = self
+            .underlying
+            .take_body()
+            .into_bytes()
+            .await
+            .map_err(|err| {
+                GrpcError::new(
+                    GrpcStatus::Internal,
+                    format!("Failed reading request body: {err}"),
+                )
+            })?;
+
+        let message = decode_grpc_frame(&body)?;
+
+        let value = decode_message(message, typ).map_err(|err| {
+            GrpcError::new(
+                GrpcStatus::InvalidArgument,
+                format!("Invalid request message: {err}"),
+            )
+        })?;
+
+        self.cached_request_body = ValueAndType::new(value, typ.clone())
+            .to_json_value()
+            .map_err(|err| GrpcError::new(GrpcStatus::Internal, err))?;
+
+        Ok(())
+    }
+
     /// consumes the body of the underlying request
     pub async fn as_wasi_http_input(
         &mut self,
@@ -210,6 +250,7 @@ pub struct SplitResolvedRouteEntryResult {
     pub namespace: Namespace,
     pub binding: GatewayBindingCompiled,
     pub middlewares: Option<HttpMiddlewares>,
+    pub rib_execution_limits: Option<RibExecutionLimits>,
     pub rich_request: RichRequest,
 }
 
@@ -222,10 +263,12 @@ pub fn split_resolved_route_entry(
     let namespace = entry.route_entry.namespace;
     let binding = entry.route_entry.binding;
     let middlewares = entry.route_entry.middlewares;
+    let rib_execution_limits = entry.rib_execution_limits;
 
     let rich_request = RichRequest {
         underlying: request,
         request_id: Uuid::new_v4(),
+        client_request_id: None,
         path_segments: entry.path_segments,
         path_param_extractors: entry.route_entry.path_params,
         auth_data: None,
@@ -236,6 +279,7 @@ pub fn split_resolved_route_entry(
         namespace,
         binding,
         middlewares,
+        rib_execution_limits,
         rich_request,
     }
 }
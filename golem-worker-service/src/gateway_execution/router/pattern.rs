@@ -12,9 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::gateway_api_definition::http::{PathParamConstraint, PathPattern};
+use regex::Regex;
+use std::str::FromStr;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum RouterPattern {
     Literal(LiteralPattern),
+    Constrained(ConstraintPattern),
     Variable,
     CatchAll,
 }
@@ -22,6 +27,67 @@ pub enum RouterPattern {
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct LiteralPattern(pub String);
 
+/// A variable segment which only matches the segments satisfying its constraint.
+#[derive(Debug, Clone)]
+pub struct ConstraintPattern {
+    constraint: PathParamConstraint,
+    regex: Option<Regex>,
+}
+
+impl ConstraintPattern {
+    pub fn new(constraint: PathParamConstraint) -> Self {
+        // `int` is an alias of `s64`, so that both conflict with each other
+        let constraint = match constraint {
+            PathParamConstraint::Int => PathParamConstraint::S64,
+            other => other,
+        };
+
+        let regex = match &constraint {
+            PathParamConstraint::Regex(regex) => Regex::new(&format!("^(?:{regex})$")).ok(),
+            _ => None,
+        };
+
+        Self { constraint, regex }
+    }
+
+    pub fn matches(&self, segment: &str) -> bool {
+        match &self.constraint {
+            PathParamConstraint::Int | PathParamConstraint::S64 => segment.parse::<i64>().is_ok(),
+            PathParamConstraint::U32 => segment.parse::<u32>().is_ok(),
+            PathParamConstraint::U64 => segment.parse::<u64>().is_ok(),
+            PathParamConstraint::S32 => segment.parse::<i32>().is_ok(),
+            PathParamConstraint::Bool => segment.parse::<bool>().is_ok(),
+            PathParamConstraint::Uuid => uuid::Uuid::parse_str(segment).is_ok(),
+            PathParamConstraint::Regex(_) => self
+                .regex
+                .as_ref()
+                .is_some_and(|regex| regex.is_match(segment)),
+        }
+    }
+
+    // Constrained siblings are tried in this order, so that the narrower constraints
+    // take precedence, independently of the order the routes were added in.
+    pub(super) fn priority(&self) -> (u8, &str) {
+        match &self.constraint {
+            PathParamConstraint::Bool => (0, ""),
+            PathParamConstraint::U32 => (1, ""),
+            PathParamConstraint::S32 => (2, ""),
+            PathParamConstraint::U64 => (3, ""),
+            PathParamConstraint::Int | PathParamConstraint::S64 => (4, ""),
+            PathParamConstraint::Uuid => (5, ""),
+            PathParamConstraint::Regex(regex) => (6, regex.as_str()),
+        }
+    }
+}
+
+impl PartialEq for ConstraintPattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.constraint == other.constraint
+    }
+}
+
+impl Eq for ConstraintPattern {}
+
 impl RouterPattern {
     pub fn literal(literal: impl Into<String>) -> Self {
         Self::Literal(LiteralPattern(literal.into()))
@@ -39,7 +105,15 @@ impl RouterPattern {
 
     #[inline]
     fn parse_single(s: &str) -> RouterPattern {
-        if s.starts_with(':') || (s.starts_with('{') && s.ends_with('}')) {
+        if s.starts_with('{') && s.ends_with('}') {
+            match s[1..s.len() - 1]
+                .split_once(':')
+                .and_then(|(_, constraint)| PathParamConstraint::from_str(constraint).ok())
+            {
+                Some(constraint) => RouterPattern::Constrained(ConstraintPattern::new(constraint)),
+                None => RouterPattern::Variable,
+            }
+        } else if s.starts_with(':') {
             RouterPattern::Variable
         } else if s == "*" {
             RouterPattern::CatchAll
@@ -49,13 +123,14 @@ impl RouterPattern {
     }
 }
 
-use crate::gateway_api_definition::http::PathPattern;
-
 impl From<PathPattern> for RouterPattern {
     fn from(path: PathPattern) -> Self {
         match path {
             PathPattern::Literal(literal) => RouterPattern::literal(literal.0),
-            PathPattern::Var(_) => RouterPattern::Variable,
+            PathPattern::Var(info) => match info.constraint {
+                Some(constraint) => RouterPattern::Constrained(ConstraintPattern::new(constraint)),
+                None => RouterPattern::Variable,
+            },
             PathPattern::CatchAllVar(_) => RouterPattern::CatchAll,
         }
     }
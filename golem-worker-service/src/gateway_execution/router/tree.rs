@@ -38,6 +38,9 @@ struct Children<T> {
     // Given the paths are perfectly de-duplicated,
     // We can assume that each child has a unique first pattern.
     literal_children: FxHashMap<LiteralPattern, RadixNode<T>>,
+    // Sorted by the priority of the constraints, which decides the match
+    // when a segment satisfies more than one of them.
+    constrained_children: Vec<RadixNode<T>>,
     variable_child: Option<Box<RadixNode<T>>>,
     catch_all_child: Option<Box<RadixNode<T>>>,
 }
@@ -46,6 +49,7 @@ impl<T> Default for Children<T> {
     fn default() -> Self {
         Self {
             literal_children: Default::default(),
+            constrained_children: Vec::new(),
             variable_child: None,
             catch_all_child: None,
         }
@@ -57,12 +61,24 @@ impl<T> Children<T> {
     fn get_child_by_str(&self, input: &str) -> Option<&RadixNode<T>> {
         self.literal_children
             .get(input)
+            .or_else(|| {
+                self.constrained_children
+                    .iter()
+                    .find(|c| match c.pattern.first() {
+                        Some(RouterPattern::Constrained(constraint)) => constraint.matches(input),
+                        _ => false,
+                    })
+            })
             .or_else(|| self.variable_child.as_ref().map(|c| c.as_ref()))
     }
 
     fn get_child(&self, pattern: &RouterPattern) -> Option<&RadixNode<T>> {
         match pattern {
             RouterPattern::Literal(literal_pattern) => self.literal_children.get(literal_pattern),
+            RouterPattern::Constrained(_) => self
+                .constrained_children
+                .iter()
+                .find(|c| c.pattern.first() == Some(pattern)),
             RouterPattern::Variable => self.variable_child.as_ref().map(|c| c.as_ref()),
             RouterPattern::CatchAll => self.catch_all_child.as_ref().map(|c| c.as_ref()),
         }
@@ -73,6 +89,10 @@ impl<T> Children<T> {
             RouterPattern::Literal(literal_pattern) => {
                 self.literal_children.get_mut(literal_pattern)
             }
+            RouterPattern::Constrained(_) => self
+                .constrained_children
+                .iter_mut()
+                .find(|c| c.pattern.first() == Some(pattern)),
             RouterPattern::Variable => self.variable_child.as_mut().map(|c| c.as_mut()),
             RouterPattern::CatchAll => self.catch_all_child.as_mut().map(|c| c.as_mut()),
         }
@@ -85,6 +105,22 @@ impl<T> Children<T> {
                 debug_assert!(inserted.is_none(), "Duplicate static child");
                 let _ = inserted;
             }
+            Some(RouterPattern::Constrained(constraint)) => {
+                debug_assert!(
+                    self.get_child(&RouterPattern::Constrained(constraint.clone()))
+                        .is_none(),
+                    "Duplicate constrained child"
+                );
+
+                let priority = constraint.priority();
+                let index =
+                    self.constrained_children
+                        .partition_point(|c| match c.pattern.first() {
+                            Some(RouterPattern::Constrained(other)) => other.priority() <= priority,
+                            _ => false,
+                        });
+                self.constrained_children.insert(index, node);
+            }
             Some(RouterPattern::Variable) => {
                 debug_assert!(
                     self.variable_child.is_none(),
@@ -332,6 +368,11 @@ impl<T> RadixNode<T> {
                         break;
                     }
                 }
+                RouterPattern::Constrained(constraint) => {
+                    if !constraint.matches(b) {
+                        break;
+                    }
+                }
                 RouterPattern::Variable => {}
                 RouterPattern::CatchAll => {
                     *last_catch_all = Some(self);
@@ -599,6 +640,79 @@ mod test {
         test_three(&root);
     }
 
+    #[test]
+    fn test_constrained_variables() {
+        let mut root = RadixNode::default();
+
+        let path1 = RouterPattern::parse("/users/me");
+        root.insert_path(&path1, 1).unwrap();
+
+        let path2 = RouterPattern::parse("/users/{id:u64}");
+        root.insert_path(&path2, 2).unwrap();
+
+        let path3 = RouterPattern::parse("/users/:name");
+        root.insert_path(&path3, 3).unwrap();
+
+        let path4 = RouterPattern::parse("/users/{id:uuid}/orders");
+        root.insert_path(&path4, 4).unwrap();
+
+        assert_eq!(Some(&1), root.matches_str("/users/me"));
+        assert_eq!(Some(&2), root.matches_str("/users/42"));
+        assert_eq!(Some(&3), root.matches_str("/users/-42"));
+        assert_eq!(Some(&3), root.matches_str("/users/bob"));
+        assert_eq!(
+            Some(&4),
+            root.matches_str("/users/67e55044-10b1-426f-9247-bb680e5fe0c8/orders")
+        );
+        assert_eq!(None, root.matches_str("/users/bob/orders"));
+    }
+
+    #[test]
+    fn test_constrained_variables_priority() {
+        #[track_caller]
+        fn check(root: &RadixNode<i32>) {
+            assert_eq!(Some(&1), root.matches_str("/items/7"));
+            assert_eq!(Some(&2), root.matches_str("/items/99999999999"));
+            assert_eq!(Some(&3), root.matches_str("/items/abc"));
+            assert_eq!(None, root.matches_str("/items/ab"));
+        }
+
+        let paths = [
+            (RouterPattern::parse("/items/{id:u32}"), 1),
+            (RouterPattern::parse(r"/items/{code:regex(\d+)}"), 2),
+            (RouterPattern::parse("/items/{code:regex([a-z]{3})}"), 3),
+        ];
+
+        let mut root = RadixNode::default();
+        for (path, data) in paths.iter() {
+            root.insert_path(path, *data).unwrap();
+        }
+        check(&root);
+
+        let mut root = RadixNode::default();
+        for (path, data) in paths.iter().rev() {
+            root.insert_path(path, *data).unwrap();
+        }
+        check(&root);
+    }
+
+    #[test]
+    fn test_conflict_constrained_variables() {
+        let mut root = RadixNode::default();
+
+        let path1 = RouterPattern::parse("/users/{id:int}");
+        root.insert_path(&path1, 1).unwrap();
+
+        let path2 = RouterPattern::parse("/users/{user_id:s64}");
+        assert!(matches!(
+            root.insert_path(&path2, 2),
+            Err(InsertionError::Conflict)
+        ));
+
+        let path3 = RouterPattern::parse("/users/{user_id:u64}");
+        root.insert_path(&path3, 3).unwrap();
+    }
+
     #[test]
     fn test_catch_all() {
         let mut root = RadixNode::default();
@@ -0,0 +1,219 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::gateway_rib_interpreter::RibRuntimeError;
+use crate::getter::GetterExt;
+use crate::path::Path;
+use futures::StreamExt;
+use golem_api_grpc::proto::golem::worker::log_event::Event as LogEventKind;
+use golem_api_grpc::proto::golem::worker::{Level, LogEvent};
+use golem_common::SafeDisplay;
+use golem_wasm::json::ValueAndTypeJsonExtensions;
+use poem::web::sse::{Event, SSE};
+use poem::IntoResponse;
+use rib::RibResult;
+use serde_json::json;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+
+const SSE_KEEP_ALIVE: Duration = Duration::from_secs(15);
+
+// Bounds the number of events buffered for a slow client. Workers are not
+// slowed down by it, as the worker connection itself drops lagging clients.
+pub const SSE_EVENT_BUFFER_SIZE: usize = 128;
+
+pub type GatewayStreamEventSender = mpsc::Sender<GatewayStreamEvent>;
+pub type GatewayStreamEventReceiver = mpsc::Receiver<GatewayStreamEvent>;
+
+// An event forwarded to the client of a route with `sse` response stream.
+// Output of the invoked workers is forwarded as it arrives, and the stream
+// always ends with exactly one `Result` or `Error` event.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GatewayStreamEvent {
+    StdOut(String),
+    StdErr(String),
+    Log {
+        level: String,
+        context: String,
+        message: String,
+    },
+    Result(serde_json::Value),
+    Error(String),
+}
+
+impl GatewayStreamEvent {
+    // Only the output of the worker is forwarded, invocation lifecycle
+    // events are internal details of the worker connection.
+    pub fn from_log_event(log_event: LogEvent) -> Option<Self> {
+        match log_event.event? {
+            LogEventKind::Stdout(stdout) => Some(GatewayStreamEvent::StdOut(stdout.message)),
+            LogEventKind::Stderr(stderr) => Some(GatewayStreamEvent::StdErr(stderr.message)),
+            LogEventKind::Log(log) => Some(GatewayStreamEvent::Log {
+                level: Level::try_from(log.level)
+                    .map(|level| level.as_str_name().to_lowercase())
+                    .unwrap_or_else(|_| "info".to_string()),
+                context: log.context,
+                message: log.message,
+            }),
+            LogEventKind::InvocationStarted(_)
+            | LogEventKind::InvocationFinished(_)
+            | LogEventKind::ClientLagged(_) => None,
+        }
+    }
+
+    // Similar to a regular response, only the `body` of the response mapping
+    // is sent to the client if there is one. Status and headers are already
+    // sent when the stream is opened.
+    pub fn from_rib_result(result: Result<RibResult, RibRuntimeError>) -> Self {
+        match result {
+            Ok(RibResult::Val(value)) => {
+                let body = value.get_optional(&Path::from_key("body")).unwrap_or(value);
+
+                match body.to_json_value() {
+                    Ok(json) => GatewayStreamEvent::Result(json),
+                    Err(err) => {
+                        GatewayStreamEvent::Error(format!("Failed to encode result: {err}"))
+                    }
+                }
+            }
+            Ok(RibResult::Unit) => GatewayStreamEvent::Result(serde_json::Value::Null),
+            Err(err) => GatewayStreamEvent::Error(err.to_safe_string()),
+        }
+    }
+
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            GatewayStreamEvent::StdOut(_) => "stdout",
+            GatewayStreamEvent::StdErr(_) => "stderr",
+            GatewayStreamEvent::Log { .. } => "log",
+            GatewayStreamEvent::Result(_) => "result",
+            GatewayStreamEvent::Error(_) => "error",
+        }
+    }
+
+    pub fn to_sse_event(&self) -> Event {
+        let data = match self {
+            GatewayStreamEvent::StdOut(message) | GatewayStreamEvent::StdErr(message) => {
+                message.clone()
+            }
+            GatewayStreamEvent::Log {
+                level,
+                context,
+                message,
+            } => json!({
+                "level": level,
+                "context": context,
+                "message": message,
+            })
+            .to_string(),
+            GatewayStreamEvent::Result(value) => value.to_string(),
+            GatewayStreamEvent::Error(error) => error.clone(),
+        };
+
+        Event::message(data).event_type(self.event_type())
+    }
+}
+
+pub fn sse_response(receiver: GatewayStreamEventReceiver) -> poem::Response {
+    let events = ReceiverStream::new(receiver).map(|event| event.to_sse_event());
+
+    SSE::new(events).keep_alive(SSE_KEEP_ALIVE).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GatewayStreamEvent;
+    use golem_api_grpc::proto::golem::worker::log_event::Event as LogEventKind;
+    use golem_api_grpc::proto::golem::worker::{
+        InvocationStarted, Level, Log, LogEvent, StdOutLog,
+    };
+    use golem_wasm::analysis::analysed_type::{field, record, str, u64};
+    use golem_wasm::{Value, ValueAndType};
+    use rib::RibResult;
+    use serde_json::json;
+    use test_r::test;
+
+    #[test]
+    fn stdout_is_forwarded() {
+        let log_event = LogEvent {
+            event: Some(LogEventKind::Stdout(StdOutLog {
+                message: "hello".to_string(),
+                timestamp: None,
+            })),
+        };
+
+        assert_eq!(
+            GatewayStreamEvent::from_log_event(log_event),
+            Some(GatewayStreamEvent::StdOut("hello".to_string()))
+        );
+    }
+
+    #[test]
+    fn log_level_is_lower_case() {
+        let log_event = LogEvent {
+            event: Some(LogEventKind::Log(Log {
+                level: Level::Warn as i32,
+                context: "agent".to_string(),
+                message: "slow".to_string(),
+                timestamp: None,
+            })),
+        };
+
+        assert_eq!(
+            GatewayStreamEvent::from_log_event(log_event),
+            Some(GatewayStreamEvent::Log {
+                level: "warn".to_string(),
+                context: "agent".to_string(),
+                message: "slow".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn invocation_lifecycle_is_not_forwarded() {
+        let log_event = LogEvent {
+            event: Some(LogEventKind::InvocationStarted(InvocationStarted {
+                timestamp: None,
+                function: "golem:it/api.{f}".to_string(),
+                idempotency_key: None,
+            })),
+        };
+
+        assert_eq!(GatewayStreamEvent::from_log_event(log_event), None);
+    }
+
+    #[test]
+    fn result_body_is_extracted() {
+        let value = ValueAndType::new(
+            Value::Record(vec![Value::U64(200), Value::String("done".to_string())]),
+            record(vec![field("status", u64()), field("body", str())]),
+        );
+
+        assert_eq!(
+            GatewayStreamEvent::from_rib_result(Ok(RibResult::Val(value))),
+            GatewayStreamEvent::Result(json!("done"))
+        );
+    }
+
+    #[test]
+    fn result_is_sent_as_json() {
+        let event = GatewayStreamEvent::Result(json!({"answer": 42}));
+
+        assert_eq!(
+            event.to_sse_event().to_string(),
+            "event: result\ndata: {\"answer\":42}\n\n"
+        );
+    }
+}
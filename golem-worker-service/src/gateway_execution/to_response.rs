@@ -29,7 +29,7 @@ use http::header::*;
 use http::StatusCode;
 use poem::Body;
 use poem::IntoResponse;
-use rib::RibResult;
+use rib::{ExecutionLimit, RibResult};
 
 #[async_trait]
 pub trait ToHttpResponse {
@@ -67,6 +67,29 @@ pub enum GatewayHttpError {
     AuthorisationError(AuthorisationError),
 }
 
+impl GatewayHttpError {
+    // Errors of Rib scripts that have no worker calls, such as the ones resolving the worker name.
+    // Exceeding an execution limit is reported the same way as in the response mapping.
+    pub fn from_pure_rib_error(err: rib::RibRuntimeError) -> Self {
+        match err {
+            rib::RibRuntimeError::LimitExceeded(limit) => {
+                GatewayHttpError::EvaluationError(RibRuntimeError::LimitExceeded(limit))
+            }
+            other => GatewayHttpError::RibInterpretPureError(other.to_string()),
+        }
+    }
+}
+
+fn execution_limit_status_code(limit: &ExecutionLimit) -> StatusCode {
+    match limit {
+        ExecutionLimit::Instructions(_) | ExecutionLimit::StackDepth(_) => {
+            StatusCode::LOOP_DETECTED
+        }
+        ExecutionLimit::ListSize(_) => StatusCode::PAYLOAD_TOO_LARGE,
+        ExecutionLimit::WorkerCalls(_) => StatusCode::TOO_MANY_REQUESTS,
+    }
+}
+
 #[async_trait]
 impl ToHttpResponse for GatewayHttpError {
     async fn to_response(
@@ -87,7 +110,10 @@ impl ToHttpResponse for GatewayHttpError {
                     "Failed interpreting pure rib expression: {err}"
                 ))),
             GatewayHttpError::EvaluationError(err) => {
-                err.to_response_from_safe_display(|_| StatusCode::INTERNAL_SERVER_ERROR)
+                err.to_response_from_safe_display(|err| match err {
+                    RibRuntimeError::LimitExceeded(limit) => execution_limit_status_code(limit),
+                    RibRuntimeError::Failed(_) => StatusCode::INTERNAL_SERVER_ERROR,
+                })
             }
             GatewayHttpError::HttpHandlerBindingError(inner) => {
                 inner.to_response(request_details, session_store).await
@@ -337,10 +363,11 @@ mod internal {
         ) -> Result<IntermediateHttpResponse, RibRuntimeError> {
             match evaluation_result {
                 RibResult::Val(rib_result) => {
-                    let status = get_status_code_or_ok(rib_result).map_err(RibRuntimeError)?;
+                    let status =
+                        get_status_code_or_ok(rib_result).map_err(RibRuntimeError::from)?;
 
-                    let headers =
-                        get_response_headers_or_default(rib_result).map_err(RibRuntimeError)?;
+                    let headers = get_response_headers_or_default(rib_result)
+                        .map_err(RibRuntimeError::from)?;
 
                     let body = rib_result
                         .get_optional(&Path::from_key("body"))
@@ -416,13 +443,13 @@ mod test {
         DataKey, DataValue, GatewaySession, GatewaySessionError, SessionId,
     };
     use crate::gateway_execution::request::RichRequest;
-    use crate::gateway_execution::to_response::ToHttpResponse;
+    use crate::gateway_execution::to_response::{GatewayHttpError, ToHttpResponse};
     use golem_wasm::analysis::analysed_type::record;
     use golem_wasm::analysis::NameTypePair;
     use golem_wasm::{IntoValueAndType, Value, ValueAndType};
     use http::header::CONTENT_TYPE;
     use http::StatusCode;
-    use rib::RibResult;
+    use rib::{ExecutionLimit, RibResult};
 
     fn create_record(values: Vec<(String, ValueAndType)>) -> ValueAndType {
         let mut fields = vec![];
@@ -515,6 +542,35 @@ mod test {
         assert_eq!(status, expected_status);
     }
 
+    #[test]
+    async fn test_execution_limit_error_to_response() {
+        let session_store: Arc<dyn GatewaySession + Send + Sync> = Arc::new(TestSessionStore);
+
+        let cases = vec![
+            (ExecutionLimit::Instructions(100), StatusCode::LOOP_DETECTED),
+            (ExecutionLimit::StackDepth(10), StatusCode::LOOP_DETECTED),
+            (ExecutionLimit::ListSize(5), StatusCode::PAYLOAD_TOO_LARGE),
+            (
+                ExecutionLimit::WorkerCalls(2),
+                StatusCode::TOO_MANY_REQUESTS,
+            ),
+        ];
+
+        for (limit, expected_status) in cases {
+            let error = GatewayHttpError::from_pure_rib_error(rib::RibRuntimeError::LimitExceeded(
+                limit.clone(),
+            ));
+
+            let http_response = error.to_response(&test_request(), &session_store).await;
+
+            let (response_parts, body) = http_response.into_parts();
+            let body = body.into_string().await.unwrap();
+
+            assert_eq!(response_parts.status, expected_status);
+            assert_eq!(body, format!("Rib execution limit exceeded: {limit}"));
+        }
+    }
+
     struct TestSessionStore;
 
     #[async_trait]
@@ -0,0 +1,303 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::gateway_api_definition::http::CompiledHttpApiDefinition;
+use crate::gateway_api_definition::ApiDefinitionId;
+use crate::gateway_api_deployment::TrafficSplit;
+use crate::metrics::gateway::{record_traffic_split_request, record_traffic_split_response};
+use crate::service::gateway::api_definition::ApiDefinitionIdWithVersion;
+use http::HeaderMap;
+use std::collections::HashMap;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrafficSplitSelector {
+    Header,
+    Weight,
+}
+
+impl TrafficSplitSelector {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TrafficSplitSelector::Header => "header",
+            TrafficSplitSelector::Weight => "weight",
+        }
+    }
+}
+
+// The version of a split API definition that was selected to serve a request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrafficSplitVariant {
+    pub api_definition: ApiDefinitionIdWithVersion,
+    pub selected_by: TrafficSplitSelector,
+}
+
+impl TrafficSplitVariant {
+    pub fn record_request(&self) {
+        record_traffic_split_request(
+            &self.api_definition.id.0,
+            &self.api_definition.version.0,
+            self.selected_by.as_str(),
+        );
+    }
+
+    pub fn record_response(&self, response: &poem::Response, duration: Duration) {
+        record_traffic_split_response(
+            &self.api_definition.id.0,
+            &self.api_definition.version.0,
+            response.status().as_u16(),
+            duration,
+        );
+    }
+}
+
+pub struct TrafficSplitSelection {
+    pub api_definitions: Vec<CompiledHttpApiDefinition>,
+    pub variants: HashMap<ApiDefinitionIdWithVersion, TrafficSplitVariant>,
+}
+
+impl TrafficSplitSelection {
+    pub fn select(
+        api_definitions: Vec<CompiledHttpApiDefinition>,
+        traffic_splits: &HashMap<ApiDefinitionIdWithVersion, TrafficSplit>,
+        headers: &HeaderMap,
+    ) -> Self {
+        Self::select_with_roll(
+            api_definitions,
+            traffic_splits,
+            headers,
+            fastrand::u32(0..100),
+        )
+    }
+
+    // Only one version of each API definition is kept. The versions of a definition are
+    // checked in order for a matching header first, and if none of them matches, `roll`
+    // (in the range of 0..100) selects the version based on the cumulative weights.
+    pub fn select_with_roll(
+        api_definitions: Vec<CompiledHttpApiDefinition>,
+        traffic_splits: &HashMap<ApiDefinitionIdWithVersion, TrafficSplit>,
+        headers: &HeaderMap,
+        roll: u32,
+    ) -> Self {
+        let mut versions: Vec<(ApiDefinitionId, Vec<CompiledHttpApiDefinition>)> = vec![];
+
+        for api_definition in api_definitions {
+            match versions.iter_mut().find(|(id, _)| *id == api_definition.id) {
+                Some((_, definitions)) => definitions.push(api_definition),
+                None => versions.push((api_definition.id.clone(), vec![api_definition])),
+            }
+        }
+
+        let mut selected = vec![];
+        let mut variants = HashMap::new();
+
+        for (_, mut definitions) in versions {
+            if definitions.len() == 1 {
+                selected.append(&mut definitions);
+                continue;
+            }
+
+            let splits = definitions
+                .iter()
+                .map(|definition| {
+                    traffic_splits
+                        .get(&key(definition))
+                        .cloned()
+                        .unwrap_or(TrafficSplit {
+                            weight: None,
+                            header: None,
+                        })
+                })
+                .collect::<Vec<_>>();
+
+            let selection = splits
+                .iter()
+                .position(|split| split.matches_headers(headers))
+                .map(|index| (index, TrafficSplitSelector::Header))
+                .or_else(|| {
+                    let mut cumulative_weight = 0;
+                    splits
+                        .iter()
+                        .position(|split| {
+                            cumulative_weight += split.weight();
+                            roll < cumulative_weight
+                        })
+                        .map(|index| (index, TrafficSplitSelector::Weight))
+                });
+
+            // Deployments are validated to have weights adding up to 100, this is only
+            // a fallback to keep serving requests if that is violated
+            let (index, selected_by) = selection.unwrap_or((0, TrafficSplitSelector::Weight));
+
+            let definition = definitions.swap_remove(index);
+
+            variants.insert(
+                key(&definition),
+                TrafficSplitVariant {
+                    api_definition: key(&definition),
+                    selected_by,
+                },
+            );
+
+            selected.push(definition);
+        }
+
+        TrafficSplitSelection {
+            api_definitions: selected,
+            variants,
+        }
+    }
+}
+
+fn key(definition: &CompiledHttpApiDefinition) -> ApiDefinitionIdWithVersion {
+    ApiDefinitionIdWithVersion {
+        id: definition.id.clone(),
+        version: definition.version.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TrafficSplitSelection, TrafficSplitSelector};
+    use crate::gateway_api_definition::http::CompiledHttpApiDefinition;
+    use crate::gateway_api_definition::{ApiDefinitionId, ApiVersion};
+    use crate::gateway_api_deployment::{TrafficSplit, TrafficSplitHeader};
+    use crate::service::gateway::api_definition::ApiDefinitionIdWithVersion;
+    use golem_common::model::auth::Namespace;
+    use golem_common::model::{AccountId, ProjectId};
+    use http::{HeaderMap, HeaderValue};
+    use std::collections::HashMap;
+    use test_r::test;
+
+    fn definition(id: &str, version: &str) -> CompiledHttpApiDefinition {
+        CompiledHttpApiDefinition {
+            id: ApiDefinitionId(id.to_string()),
+            version: ApiVersion(version.to_string()),
+            routes: vec![],
+            draft: false,
+            created_at: chrono::Utc::now(),
+            namespace: Namespace::new(ProjectId::new_v4(), AccountId::generate()),
+            rib_execution_limits: None,
+        }
+    }
+
+    fn key(id: &str, version: &str) -> ApiDefinitionIdWithVersion {
+        ApiDefinitionIdWithVersion {
+            id: ApiDefinitionId(id.to_string()),
+            version: ApiVersion(version.to_string()),
+        }
+    }
+
+    fn canary_splits() -> HashMap<ApiDefinitionIdWithVersion, TrafficSplit> {
+        HashMap::from_iter(vec![
+            (
+                key("shop", "0.0.1"),
+                TrafficSplit {
+                    weight: Some(95),
+                    header: None,
+                },
+            ),
+            (
+                key("shop", "0.0.2"),
+                TrafficSplit {
+                    weight: Some(5),
+                    header: Some(TrafficSplitHeader {
+                        name: "x-canary".to_string(),
+                        value: "true".to_string(),
+                    }),
+                },
+            ),
+        ])
+    }
+
+    fn selected_versions(selection: &TrafficSplitSelection) -> Vec<(String, String)> {
+        selection
+            .api_definitions
+            .iter()
+            .map(|def| (def.id.0.clone(), def.version.0.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn single_versions_are_not_split() {
+        let selection = TrafficSplitSelection::select_with_roll(
+            vec![definition("shop", "0.0.1"), definition("admin", "0.0.1")],
+            &canary_splits(),
+            &HeaderMap::new(),
+            99,
+        );
+
+        assert_eq!(selection.api_definitions.len(), 2);
+        assert!(selection.variants.is_empty());
+    }
+
+    #[test]
+    fn versions_are_selected_by_weight() {
+        let definitions = vec![definition("shop", "0.0.1"), definition("shop", "0.0.2")];
+
+        let stable = TrafficSplitSelection::select_with_roll(
+            definitions.clone(),
+            &canary_splits(),
+            &HeaderMap::new(),
+            94,
+        );
+        let canary = TrafficSplitSelection::select_with_roll(
+            definitions,
+            &canary_splits(),
+            &HeaderMap::new(),
+            95,
+        );
+
+        assert_eq!(
+            selected_versions(&stable),
+            vec![("shop".to_string(), "0.0.1".to_string())]
+        );
+        assert_eq!(
+            selected_versions(&canary),
+            vec![("shop".to_string(), "0.0.2".to_string())]
+        );
+        assert_eq!(
+            canary
+                .variants
+                .get(&key("shop", "0.0.2"))
+                .map(|v| v.selected_by),
+            Some(TrafficSplitSelector::Weight)
+        );
+    }
+
+    #[test]
+    fn header_takes_precedence_over_weight() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-canary", HeaderValue::from_static("true"));
+
+        let selection = TrafficSplitSelection::select_with_roll(
+            vec![definition("shop", "0.0.1"), definition("shop", "0.0.2")],
+            &canary_splits(),
+            &headers,
+            0,
+        );
+
+        assert_eq!(
+            selected_versions(&selection),
+            vec![("shop".to_string(), "0.0.2".to_string())]
+        );
+        assert_eq!(
+            selection
+                .variants
+                .get(&key("shop", "0.0.2"))
+                .map(|v| v.selected_by),
+            Some(TrafficSplitSelector::Header)
+        );
+    }
+}
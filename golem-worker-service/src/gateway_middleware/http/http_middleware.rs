@@ -16,6 +16,7 @@ use crate::gateway_middleware::http::authentication::HttpAuthenticationMiddlewar
 use std::ops::Deref;
 
 use crate::gateway_middleware::http::cors::HttpCors;
+use crate::gateway_middleware::http::transformation::HttpTransformation;
 
 use crate::gateway_security::SecuritySchemeWithProviderMetadata;
 
@@ -23,6 +24,7 @@ use crate::gateway_security::SecuritySchemeWithProviderMetadata;
 pub enum HttpMiddleware {
     Cors(HttpCors),
     AuthenticateRequest(Box<HttpAuthenticationMiddleware>), // Middleware to authenticate before feeding the input to the binding executor
+    Transform(Box<HttpTransformation>), // Middleware to transform the request before the binding executor, and the response after it
 }
 
 impl HttpMiddleware {
@@ -30,6 +32,7 @@ impl HttpMiddleware {
         match self {
             HttpMiddleware::Cors(cors) => Some(cors.clone()),
             HttpMiddleware::AuthenticateRequest(_) => None,
+            HttpMiddleware::Transform(_) => None,
         }
     }
 
@@ -39,6 +42,15 @@ impl HttpMiddleware {
                 Some(authentication.deref().clone())
             }
             HttpMiddleware::Cors(_) => None,
+            HttpMiddleware::Transform(_) => None,
+        }
+    }
+
+    pub fn get_transformation(&self) -> Option<HttpTransformation> {
+        match self {
+            HttpMiddleware::Transform(transformation) => Some(transformation.deref().clone()),
+            HttpMiddleware::Cors(_) => None,
+            HttpMiddleware::AuthenticateRequest(_) => None,
         }
     }
 
@@ -52,4 +64,8 @@ impl HttpMiddleware {
     pub fn cors(cors: HttpCors) -> Self {
         HttpMiddleware::Cors(cors)
     }
+
+    pub fn transform(transformation: HttpTransformation) -> Self {
+        HttpMiddleware::Transform(Box::new(transformation))
+    }
 }
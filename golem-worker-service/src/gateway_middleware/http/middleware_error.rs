@@ -20,6 +20,7 @@ use golem_common::SafeDisplay;
 pub enum MiddlewareError {
     Unauthorized(AuthorisationError),
     CorsError(CorsError),
+    RequestBodyTooLarge(u64),
     InternalError(String),
 }
 
@@ -39,6 +40,9 @@ impl SafeDisplay for MiddlewareError {
                 CorsError::MethodNotAllowed => "CORS Error: Method not allowed".to_string(),
                 CorsError::HeadersNotAllowed => "CORS Error: Headers not allowed".to_string(),
             },
+            MiddlewareError::RequestBodyTooLarge(limit) => {
+                format!("Request body exceeds the limit of {limit} bytes")
+            }
             MiddlewareError::InternalError(msg) => {
                 format!("Internal Server Error: {msg}")
             }
@@ -0,0 +1,708 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::gateway_api_definition::http::{AllPathPatterns, PathPattern};
+use crate::gateway_execution::request::RichRequest;
+use crate::gateway_middleware::MiddlewareError;
+use futures::StreamExt;
+use golem_service_base::headers::TraceContextHeaders;
+use http::header::{ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE, VARY};
+use http::{HeaderMap, HeaderName, HeaderValue, Method, StatusCode, Uri};
+use poem::web::{Compress, CompressionAlgo};
+use poem::{Body, IntoResponse};
+use poem_openapi::{Enum, Object};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use uuid::Uuid;
+
+const TRACEPARENT_HEADER: &str = "traceparent";
+
+// Responses of gRPC calls carry trailers, and event streams are flushed per event,
+// compressing them as a whole would break both
+const UNCOMPRESSED_CONTENT_TYPES: [&str; 2] = ["application/grpc", "text/event-stream"];
+
+// Transformations applied by the gateway to the request before it reaches the binding,
+// and to the response the binding produced. These are the same for every binding type,
+// and are meant for the common cases that would otherwise need to be repeated in Rib scripts.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct HttpTransformation {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_headers: Option<HeaderTransformation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_headers: Option<HeaderTransformation>,
+    // Path of the request as seen by the binding, with `{var}` placeholders
+    // filled from the path variables of the route
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path_rewrite: Option<String>,
+    // Header holding the id of the request. An incoming id is kept, otherwise
+    // the id generated by the gateway is added to the request, and both are echoed in the response
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id_header: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub propagate_trace_context: Option<bool>,
+    // Compression algorithms offered for responses, in the order of preference
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compression: Option<Vec<CompressionAlgorithm>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_request_body_size: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct HeaderTransformation {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub set: Option<BTreeMap<String, String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remove: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Enum)]
+#[serde(rename_all = "kebab-case")]
+#[oai(rename_all = "kebab-case")]
+pub enum CompressionAlgorithm {
+    Gzip,
+    Br,
+}
+
+impl CompressionAlgorithm {
+    fn encoding(&self) -> &'static str {
+        match self {
+            CompressionAlgorithm::Gzip => "gzip",
+            CompressionAlgorithm::Br => "br",
+        }
+    }
+
+    fn to_compression_algo(self) -> CompressionAlgo {
+        match self {
+            CompressionAlgorithm::Gzip => CompressionAlgo::GZIP,
+            CompressionAlgorithm::Br => CompressionAlgo::BR,
+        }
+    }
+}
+
+impl HeaderTransformation {
+    fn validate(&self) -> Result<(), String> {
+        for name in self.remove.iter().flatten() {
+            HeaderName::from_str(name).map_err(|_| format!("Invalid header name {name}"))?;
+        }
+
+        for (name, value) in self.set.iter().flatten() {
+            HeaderName::from_str(name).map_err(|_| format!("Invalid header name {name}"))?;
+            HeaderValue::from_str(value).map_err(|_| format!("Invalid value for header {name}"))?;
+        }
+
+        Ok(())
+    }
+
+    fn apply(&self, headers: &mut HeaderMap) -> Result<(), String> {
+        for name in self.remove.iter().flatten() {
+            headers.remove(name.as_str());
+        }
+
+        for (name, value) in self.set.iter().flatten() {
+            let name =
+                HeaderName::from_str(name).map_err(|_| format!("Invalid header name {name}"))?;
+            let value = HeaderValue::from_str(value)
+                .map_err(|_| format!("Invalid value for header {name}"))?;
+
+            headers.insert(name, value);
+        }
+
+        Ok(())
+    }
+}
+
+impl HttpTransformation {
+    // Checked when the API definition is created, so that applying the transformation
+    // can only fail because of the request
+    pub fn validate(&self, path: &AllPathPatterns) -> Result<(), String> {
+        if let Some(request_headers) = &self.request_headers {
+            request_headers.validate()?;
+        }
+
+        if let Some(response_headers) = &self.response_headers {
+            response_headers.validate()?;
+        }
+
+        if let Some(request_id_header) = &self.request_id_header {
+            HeaderName::from_str(request_id_header)
+                .map_err(|_| format!("Invalid request id header {request_id_header}"))?;
+        }
+
+        if let Some(path_rewrite) = &self.path_rewrite {
+            let variables = path
+                .path_patterns
+                .iter()
+                .filter_map(|pattern| match pattern {
+                    PathPattern::Var(info) => Some(info.key_name.as_str()),
+                    PathPattern::CatchAllVar(info) => Some(info.key_name.as_str()),
+                    PathPattern::Literal(_) => None,
+                })
+                .collect::<Vec<_>>();
+
+            if !path_rewrite.starts_with('/') {
+                return Err(format!("Path rewrite {path_rewrite} must start with /"));
+            }
+
+            let mut example_path = path_rewrite.clone();
+
+            for variable in path_rewrite_variables(path_rewrite)? {
+                if !variables.contains(&variable) {
+                    return Err(format!(
+                        "Path rewrite {path_rewrite} refers to unknown path variable {variable}"
+                    ));
+                }
+
+                example_path = example_path.replace(&format!("{{{variable}}}"), variable);
+            }
+
+            Uri::from_str(&example_path)
+                .map_err(|_| format!("Path rewrite {path_rewrite} is not a valid path"))?;
+        }
+
+        if self.max_request_body_size == Some(0) {
+            return Err("Maximum request body size must be greater than 0".to_string());
+        }
+
+        Ok(())
+    }
+
+    pub fn transform_request(&self, request: &mut RichRequest) -> Result<(), MiddlewareError> {
+        if let Some(limit) = self.max_request_body_size {
+            limit_request_body(request, limit)?;
+        }
+
+        if let Some(request_id_header) = &self.request_id_header {
+            let incoming_request_id = request
+                .headers()
+                .get(request_id_header.as_str())
+                .and_then(|value| value.to_str().ok())
+                .map(|value| value.trim())
+                .filter(|value| !value.is_empty())
+                .map(|value| value.to_string());
+
+            match incoming_request_id {
+                Some(client_request_id) => {
+                    if let Ok(request_id) = Uuid::parse_str(&client_request_id) {
+                        request.request_id = request_id;
+                    }
+                    request.client_request_id = Some(client_request_id);
+                }
+                None => {
+                    let name = HeaderName::from_str(request_id_header)
+                        .map_err(|err| MiddlewareError::InternalError(err.to_string()))?;
+                    let value = HeaderValue::from_str(&request.request_id.to_string())
+                        .map_err(|err| MiddlewareError::InternalError(err.to_string()))?;
+
+                    request.underlying.headers_mut().insert(name, value);
+                }
+            }
+        }
+
+        // The invocation context of the request is created from the trace context headers,
+        // so a trace started here is the one the worker invocation becomes part of
+        if self.propagate_trace_context == Some(true)
+            && TraceContextHeaders::parse(request.headers()).is_none()
+        {
+            for (name, value) in TraceContextHeaders::generate().to_raw_headers_map() {
+                let name = HeaderName::from_str(&name)
+                    .map_err(|err| MiddlewareError::InternalError(err.to_string()))?;
+                let value = HeaderValue::from_str(&value)
+                    .map_err(|err| MiddlewareError::InternalError(err.to_string()))?;
+
+                request.underlying.headers_mut().insert(name, value);
+            }
+        }
+
+        if let Some(request_headers) = &self.request_headers {
+            request_headers
+                .apply(request.underlying.headers_mut())
+                .map_err(MiddlewareError::InternalError)?;
+        }
+
+        if let Some(path_rewrite) = &self.path_rewrite {
+            let uri =
+                rewrite_path(path_rewrite, request).map_err(MiddlewareError::InternalError)?;
+            request.underlying.set_uri(uri);
+        }
+
+        Ok(())
+    }
+
+    pub fn transform_response(
+        &self,
+        request: &RichRequest,
+        response: &mut poem::Response,
+    ) -> Result<(), MiddlewareError> {
+        if let Some(response_headers) = &self.response_headers {
+            response_headers
+                .apply(response.headers_mut())
+                .map_err(MiddlewareError::InternalError)?;
+        }
+
+        if let Some(request_id_header) = &self.request_id_header {
+            let name = HeaderName::from_str(request_id_header)
+                .map_err(|err| MiddlewareError::InternalError(err.to_string()))?;
+            let request_id = match &request.client_request_id {
+                Some(client_request_id) => client_request_id.clone(),
+                None => request.request_id.to_string(),
+            };
+            let value = HeaderValue::from_str(&request_id)
+                .map_err(|err| MiddlewareError::InternalError(err.to_string()))?;
+
+            response.headers_mut().insert(name, value);
+        }
+
+        if self.propagate_trace_context == Some(true) {
+            if let Some(traceparent) = request.headers().get(TRACEPARENT_HEADER) {
+                response
+                    .headers_mut()
+                    .insert(TRACEPARENT_HEADER, traceparent.clone());
+            }
+        }
+
+        if let Some(algorithms) = &self.compression {
+            if is_compressible(request, response) {
+                let accept_encoding = request.headers().get(ACCEPT_ENCODING);
+
+                response
+                    .headers_mut()
+                    .append(VARY, HeaderValue::from_static("accept-encoding"));
+
+                if let Some(algorithm) = negotiate_compression(algorithms, accept_encoding) {
+                    let uncompressed = std::mem::take(response);
+                    *response = Compress::new(uncompressed, algorithm.to_compression_algo())
+                        .into_response();
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Requests announcing a larger body are rejected upfront, others are
+// failed while the body is read
+fn limit_request_body(request: &mut RichRequest, limit: u64) -> Result<(), MiddlewareError> {
+    let content_length = request
+        .headers()
+        .get(CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok());
+
+    match content_length {
+        Some(content_length) if content_length > limit => {
+            Err(MiddlewareError::RequestBodyTooLarge(limit))
+        }
+        Some(_) => Ok(()),
+        None => {
+            let body = request.underlying.take_body();
+
+            if body.is_empty() {
+                request.underlying.set_body(body);
+            } else {
+                let mut read = 0u64;
+                let limited = body.into_bytes_stream().map(move |chunk| {
+                    let chunk = chunk?;
+                    read += chunk.len() as u64;
+                    if read > limit {
+                        Err(std::io::Error::other(format!(
+                            "Request body exceeds the limit of {limit} bytes"
+                        )))
+                    } else {
+                        Ok(chunk)
+                    }
+                });
+
+                request
+                    .underlying
+                    .set_body(Body::from_bytes_stream(limited));
+            }
+
+            Ok(())
+        }
+    }
+}
+
+fn is_compressible(request: &RichRequest, response: &poem::Response) -> bool {
+    let is_uncompressed_content_type = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|content_type| {
+            UNCOMPRESSED_CONTENT_TYPES
+                .iter()
+                .any(|uncompressed| content_type.starts_with(uncompressed))
+        });
+
+    request.underlying.method() != Method::HEAD
+        && response.status() != StatusCode::NO_CONTENT
+        && response.status() != StatusCode::NOT_MODIFIED
+        && !response.headers().contains_key(CONTENT_ENCODING)
+        && !is_uncompressed_content_type
+}
+
+// Picks the algorithm with the highest quality in `Accept-Encoding`, preferring
+// the configured order between the ones with equal quality
+fn negotiate_compression(
+    algorithms: &[CompressionAlgorithm],
+    accept_encoding: Option<&HeaderValue>,
+) -> Option<CompressionAlgorithm> {
+    let accept_encoding = accept_encoding?.to_str().ok()?;
+
+    let accepted = accept_encoding
+        .split(',')
+        .filter_map(|item| {
+            let mut parts = item.split(';');
+            let encoding = parts.next()?.trim().to_lowercase();
+            let quality = parts
+                .find_map(|param| param.trim().strip_prefix("q="))
+                .map(|quality| quality.trim().parse::<f32>().unwrap_or(0.0))
+                .unwrap_or(1.0);
+
+            Some((encoding, quality))
+        })
+        .collect::<Vec<_>>();
+
+    let quality_of = |encoding: &str| {
+        accepted
+            .iter()
+            .find(|(accepted, _)| accepted == encoding)
+            .or_else(|| accepted.iter().find(|(accepted, _)| accepted == "*"))
+            .map(|(_, quality)| *quality)
+            .unwrap_or(0.0)
+    };
+
+    let mut result: Option<(CompressionAlgorithm, f32)> = None;
+
+    for algorithm in algorithms {
+        let quality = quality_of(algorithm.encoding());
+
+        if quality > 0.0 && result.is_none_or(|(_, best)| quality > best) {
+            result = Some((*algorithm, quality));
+        }
+    }
+
+    result.map(|(algorithm, _)| algorithm)
+}
+
+fn path_rewrite_variables(path_rewrite: &str) -> Result<Vec<&str>, String> {
+    let mut variables = vec![];
+    let mut rest = path_rewrite;
+
+    while let Some(start) = rest.find('{') {
+        let end = rest[start..].find('}').ok_or(format!(
+            "Unclosed path variable in path rewrite {path_rewrite}"
+        ))?;
+
+        variables.push(&rest[start + 1..start + end]);
+        rest = &rest[start + end + 1..];
+    }
+
+    if rest.contains('}') {
+        return Err(format!(
+            "Unopened path variable in path rewrite {path_rewrite}"
+        ));
+    }
+
+    Ok(variables)
+}
+
+// The query of the original request is kept
+fn rewrite_path(path_rewrite: &str, request: &RichRequest) -> Result<Uri, String> {
+    let path_params = request.path_params();
+    let mut path = path_rewrite.to_string();
+
+    for variable in path_rewrite_variables(path_rewrite)? {
+        let value = path_params
+            .get(variable)
+            .ok_or(format!("Missing path variable {variable}"))?;
+
+        path = path.replace(&format!("{{{variable}}}"), value);
+    }
+
+    let path_and_query = match request.underlying.uri().query() {
+        Some(query) => format!("{path}?{query}"),
+        None => path,
+    };
+
+    let mut parts = request.underlying.uri().clone().into_parts();
+    parts.path_and_query = Some(
+        path_and_query
+            .parse()
+            .map_err(|_| format!("Invalid rewritten path {path_and_query}"))?,
+    );
+
+    Uri::from_parts(parts).map_err(|err| err.to_string())
+}
+
+impl From<HeaderTransformation>
+    for golem_api_grpc::proto::golem::apidefinition::HeaderTransformation
+{
+    fn from(value: HeaderTransformation) -> Self {
+        golem_api_grpc::proto::golem::apidefinition::HeaderTransformation {
+            set: value.set.unwrap_or_default().into_iter().collect(),
+            remove: value.remove.unwrap_or_default(),
+        }
+    }
+}
+
+impl From<golem_api_grpc::proto::golem::apidefinition::HeaderTransformation>
+    for HeaderTransformation
+{
+    fn from(value: golem_api_grpc::proto::golem::apidefinition::HeaderTransformation) -> Self {
+        HeaderTransformation {
+            set: (!value.set.is_empty()).then(|| value.set.into_iter().collect()),
+            remove: (!value.remove.is_empty()).then_some(value.remove),
+        }
+    }
+}
+
+impl From<CompressionAlgorithm>
+    for golem_api_grpc::proto::golem::apidefinition::CompressionAlgorithm
+{
+    fn from(value: CompressionAlgorithm) -> Self {
+        match value {
+            CompressionAlgorithm::Gzip => {
+                golem_api_grpc::proto::golem::apidefinition::CompressionAlgorithm::Gzip
+            }
+            CompressionAlgorithm::Br => {
+                golem_api_grpc::proto::golem::apidefinition::CompressionAlgorithm::Brotli
+            }
+        }
+    }
+}
+
+impl From<golem_api_grpc::proto::golem::apidefinition::CompressionAlgorithm>
+    for CompressionAlgorithm
+{
+    fn from(value: golem_api_grpc::proto::golem::apidefinition::CompressionAlgorithm) -> Self {
+        match value {
+            golem_api_grpc::proto::golem::apidefinition::CompressionAlgorithm::Gzip => {
+                CompressionAlgorithm::Gzip
+            }
+            golem_api_grpc::proto::golem::apidefinition::CompressionAlgorithm::Brotli => {
+                CompressionAlgorithm::Br
+            }
+        }
+    }
+}
+
+impl From<HttpTransformation> for golem_api_grpc::proto::golem::apidefinition::HttpTransformation {
+    fn from(value: HttpTransformation) -> Self {
+        golem_api_grpc::proto::golem::apidefinition::HttpTransformation {
+            request_headers: value.request_headers.map(|headers| headers.into()),
+            response_headers: value.response_headers.map(|headers| headers.into()),
+            path_rewrite: value.path_rewrite,
+            request_id_header: value.request_id_header,
+            propagate_trace_context: value.propagate_trace_context,
+            compression: value
+                .compression
+                .unwrap_or_default()
+                .into_iter()
+                .map(|algorithm| {
+                    golem_api_grpc::proto::golem::apidefinition::CompressionAlgorithm::from(
+                        algorithm,
+                    ) as i32
+                })
+                .collect(),
+            max_request_body_size: value.max_request_body_size,
+        }
+    }
+}
+
+impl TryFrom<golem_api_grpc::proto::golem::apidefinition::HttpTransformation>
+    for HttpTransformation
+{
+    type Error = String;
+
+    fn try_from(
+        value: golem_api_grpc::proto::golem::apidefinition::HttpTransformation,
+    ) -> Result<Self, Self::Error> {
+        let compression = value
+            .compression
+            .into_iter()
+            .map(|algorithm| {
+                golem_api_grpc::proto::golem::apidefinition::CompressionAlgorithm::try_from(
+                    algorithm,
+                )
+                .map(CompressionAlgorithm::from)
+                .map_err(|_| format!("Invalid compression algorithm {algorithm}"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(HttpTransformation {
+            request_headers: value.request_headers.map(|headers| headers.into()),
+            response_headers: value.response_headers.map(|headers| headers.into()),
+            path_rewrite: value.path_rewrite,
+            request_id_header: value.request_id_header,
+            propagate_trace_context: value.propagate_trace_context,
+            compression: (!compression.is_empty()).then_some(compression),
+            max_request_body_size: value.max_request_body_size,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{negotiate_compression, path_rewrite_variables, CompressionAlgorithm};
+    use super::{HeaderTransformation, HttpTransformation};
+    use crate::gateway_api_definition::http::AllPathPatterns;
+    use crate::gateway_execution::request::RichRequest;
+    use http::HeaderValue;
+    use std::collections::BTreeMap;
+    use test_r::test;
+    use uuid::Uuid;
+
+    fn request_id_transformation() -> HttpTransformation {
+        HttpTransformation {
+            request_id_header: Some("x-request-id".to_string()),
+            ..HttpTransformation::default()
+        }
+    }
+
+    fn transform(request_id: Option<&str>) -> (RichRequest, poem::Response) {
+        let mut builder = poem::Request::builder();
+        if let Some(request_id) = request_id {
+            builder = builder.header("x-request-id", request_id);
+        }
+        let mut request = RichRequest::new(builder.finish());
+        let mut response = poem::Response::default();
+
+        let transformation = request_id_transformation();
+        transformation.transform_request(&mut request).unwrap();
+        transformation
+            .transform_response(&request, &mut response)
+            .unwrap();
+
+        (request, response)
+    }
+
+    #[test]
+    fn compression_follows_accepted_quality() {
+        let algorithms = [CompressionAlgorithm::Gzip, CompressionAlgorithm::Br];
+
+        let accept = HeaderValue::from_static("gzip;q=0.5, br");
+        assert_eq!(
+            negotiate_compression(&algorithms, Some(&accept)),
+            Some(CompressionAlgorithm::Br)
+        );
+
+        let accept = HeaderValue::from_static("br, gzip");
+        assert_eq!(
+            negotiate_compression(&algorithms, Some(&accept)),
+            Some(CompressionAlgorithm::Gzip)
+        );
+
+        let accept = HeaderValue::from_static("*;q=0.1, gzip;q=0");
+        assert_eq!(
+            negotiate_compression(&algorithms, Some(&accept)),
+            Some(CompressionAlgorithm::Br)
+        );
+
+        let accept = HeaderValue::from_static("identity");
+        assert_eq!(negotiate_compression(&algorithms, Some(&accept)), None);
+        assert_eq!(negotiate_compression(&algorithms, None), None);
+    }
+
+    #[test]
+    fn path_rewrite_variables_are_extracted() {
+        assert_eq!(
+            path_rewrite_variables("/v2/{user}/items/{rest}").unwrap(),
+            vec!["user", "rest"]
+        );
+        assert!(path_rewrite_variables("/v2/{user").is_err());
+        assert!(path_rewrite_variables("/v2/user}").is_err());
+    }
+
+    #[test]
+    fn validate_checks_path_variables_and_headers() {
+        let path = AllPathPatterns::parse("/api/{user}/{+rest}").unwrap();
+
+        let valid = HttpTransformation {
+            path_rewrite: Some("/{user}/{rest}".to_string()),
+            request_headers: Some(HeaderTransformation {
+                set: Some(BTreeMap::from([(
+                    "x-gateway".to_string(),
+                    "golem".to_string(),
+                )])),
+                remove: Some(vec!["cookie".to_string()]),
+            }),
+            ..HttpTransformation::default()
+        };
+        assert_eq!(valid.validate(&path), Ok(()));
+
+        let unknown_variable = HttpTransformation {
+            path_rewrite: Some("/{account}".to_string()),
+            ..HttpTransformation::default()
+        };
+        assert!(unknown_variable.validate(&path).is_err());
+
+        let invalid_header = HttpTransformation {
+            response_headers: Some(HeaderTransformation {
+                set: None,
+                remove: Some(vec!["not a header".to_string()]),
+            }),
+            ..HttpTransformation::default()
+        };
+        assert!(invalid_header.validate(&path).is_err());
+    }
+
+    #[test]
+    fn non_uuid_request_id_of_client_is_kept() {
+        let (request, response) = transform(Some("client-request-1"));
+
+        assert_eq!(
+            request.headers().get("x-request-id"),
+            Some(&HeaderValue::from_static("client-request-1"))
+        );
+        assert_eq!(
+            response.headers().get("x-request-id"),
+            Some(&HeaderValue::from_static("client-request-1"))
+        );
+    }
+
+    #[test]
+    fn uuid_request_id_of_client_becomes_the_request_id() {
+        let request_id = Uuid::new_v4();
+        let (request, response) = transform(Some(&request_id.to_string()));
+
+        assert_eq!(request.request_id, request_id);
+        assert_eq!(
+            response.headers().get("x-request-id").unwrap(),
+            request_id.to_string().as_str()
+        );
+    }
+
+    #[test]
+    fn missing_or_empty_request_id_is_generated() {
+        for incoming in [None, Some(""), Some("  ")] {
+            let (request, response) = transform(incoming);
+            let generated = request.request_id.to_string();
+
+            assert_eq!(
+                request.headers().get("x-request-id").unwrap(),
+                generated.as_str()
+            );
+            assert_eq!(
+                response.headers().get("x-request-id").unwrap(),
+                generated.as_str()
+            );
+        }
+    }
+}
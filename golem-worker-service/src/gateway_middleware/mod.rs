@@ -16,6 +16,7 @@ use crate::gateway_execution::gateway_session::GatewaySessionStore;
 use crate::gateway_execution::request::RichRequest;
 use crate::gateway_security::{IdentityProvider, SecuritySchemeWithProviderMetadata};
 pub use http::*;
+use std::ops::Deref;
 use std::sync::Arc;
 
 mod http;
@@ -40,13 +41,20 @@ impl HttpMiddlewares {
 
     pub async fn process_middleware_in(
         &self,
-        rich_request: &RichRequest,
+        rich_request: &mut RichRequest,
         session_store: &GatewaySessionStore,
         identity_provider: &Arc<dyn IdentityProvider>,
     ) -> Result<MiddlewareSuccess, MiddlewareError> {
         let mut final_session_id = None;
 
-        for middleware in self.0.iter() {
+        // Transformations only see requests that passed every other middleware,
+        // so they can never change what the authentication is based on
+        let (transformations, others): (Vec<_>, Vec<_>) = self
+            .0
+            .iter()
+            .partition(|middleware| matches!(middleware, HttpMiddleware::Transform(_)));
+
+        for middleware in others.into_iter().chain(transformations) {
             match middleware {
                 HttpMiddleware::Cors(cors) => {
                     cors.apply_cors(rich_request)
@@ -66,6 +74,9 @@ impl HttpMiddlewares {
                         }
                     }
                 }
+                HttpMiddleware::Transform(transformation) => {
+                    transformation.transform_request(rich_request)?;
+                }
             }
         }
 
@@ -76,6 +87,7 @@ impl HttpMiddlewares {
 
     pub async fn process_middleware_out(
         &self,
+        rich_request: &RichRequest,
         response: &mut poem::Response,
     ) -> Result<(), MiddlewareError> {
         for middleware in self.0.iter() {
@@ -84,6 +96,9 @@ impl HttpMiddlewares {
                     cors.add_header_in_response(response);
                 }
                 HttpMiddleware::AuthenticateRequest(_) => {}
+                HttpMiddleware::Transform(transformation) => {
+                    transformation.transform_response(rich_request, response)?;
+                }
             }
         }
 
@@ -101,6 +116,10 @@ impl HttpMiddlewares {
     pub fn get_http_authentication_middleware(&self) -> Option<HttpAuthenticationMiddleware> {
         self.0.iter().find_map(|m| m.get_http_authentication())
     }
+
+    pub fn get_transformation_middleware(&self) -> Option<HttpTransformation> {
+        self.0.iter().find_map(|m| m.get_transformation())
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -115,6 +134,7 @@ impl TryFrom<golem_api_grpc::proto::golem::apidefinition::Middleware> for HttpMi
         value: golem_api_grpc::proto::golem::apidefinition::Middleware,
     ) -> Result<Self, Self::Error> {
         let mut http_middlewares = Vec::new();
+
         if let Some(cors) = value.cors {
             let cors = HttpCors::try_from(cors)?;
             http_middlewares.push(HttpMiddleware::cors(cors));
@@ -125,6 +145,11 @@ impl TryFrom<golem_api_grpc::proto::golem::apidefinition::Middleware> for HttpMi
             http_middlewares.push(HttpMiddleware::authenticate_request(auth))
         }
 
+        if let Some(transformation) = value.transformation {
+            let transformation = HttpTransformation::try_from(transformation)?;
+            http_middlewares.push(HttpMiddleware::transform(transformation));
+        }
+
         Ok(HttpMiddlewares(http_middlewares))
     }
 }
@@ -134,6 +159,7 @@ impl TryFrom<HttpMiddlewares> for golem_api_grpc::proto::golem::apidefinition::M
     fn try_from(value: HttpMiddlewares) -> Result<Self, String> {
         let mut cors = None;
         let mut auth = None;
+        let mut transformation = None;
 
         for http_middleware in value.0.iter() {
             match http_middleware {
@@ -143,12 +169,16 @@ impl TryFrom<HttpMiddlewares> for golem_api_grpc::proto::golem::apidefinition::M
                 HttpMiddleware::AuthenticateRequest(http_request_authentication) => {
                     auth = Some(golem_api_grpc::proto::golem::apidefinition::SecurityWithProviderMetadata::try_from(http_request_authentication.security_scheme_with_metadata.clone())?)
                 }
+                HttpMiddleware::Transform(http_transformation) => {
+                    transformation = Some(golem_api_grpc::proto::golem::apidefinition::HttpTransformation::from(http_transformation.deref().clone()));
+                }
             }
         }
 
         Ok(golem_api_grpc::proto::golem::apidefinition::Middleware {
             cors,
             http_authentication: auth,
+            transformation,
         })
     }
 }
@@ -0,0 +1,180 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::gateway_rib_compiler::{
+    ComponentDependencyWithAgentInfo, DefaultWorkerServiceRibCompiler, PathParamTypes,
+    WorkerServiceRibCompiler,
+};
+use rib::{CompilerOutput, Expr, RibCompilationError};
+use std::collections::HashMap;
+use std::sync::{Mutex, RwLock};
+use tracing::warn;
+
+// Identifies a compiled Rib script by its source, the metadata of the components
+// it depends on, and the types of the path parameters available to it
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CompiledRibKey(pub String);
+
+impl CompiledRibKey {
+    pub fn new(
+        rib: &Expr,
+        component_dependency: &[ComponentDependencyWithAgentInfo],
+        path_param_types: &PathParamTypes,
+    ) -> Result<Self, String> {
+        let mut hasher = blake3::Hasher::new();
+
+        update_with_length(&mut hasher, rib.to_string().as_bytes());
+
+        for dep in component_dependency {
+            let dependency_key = desert_rust::serialize_to_byte_vec(
+                &dep.component_dependency.component_dependency_key,
+            )
+            .map_err(|e| format!("Failed to serialize component dependency key: {e}"))?;
+            let metadata = desert_rust::serialize_to_byte_vec(&dep.component_metadata)
+                .map_err(|e| format!("Failed to serialize component metadata: {e}"))?;
+
+            update_with_length(&mut hasher, &dependency_key);
+            update_with_length(&mut hasher, blake3::hash(&metadata).as_bytes());
+        }
+
+        for (name, typ) in &path_param_types.0 {
+            let typ = desert_rust::serialize_to_byte_vec(typ)
+                .map_err(|e| format!("Failed to serialize type of path parameter {name}: {e}"))?;
+
+            update_with_length(&mut hasher, name.as_bytes());
+            update_with_length(&mut hasher, &typ);
+        }
+
+        Ok(CompiledRibKey(hasher.finalize().to_hex().to_string()))
+    }
+}
+
+// Each part is prefixed with its length, so that different parts never hash the same
+fn update_with_length(hasher: &mut blake3::Hasher, bytes: &[u8]) {
+    hasher.update(&(bytes.len() as u64).to_le_bytes());
+    hasher.update(bytes);
+}
+
+// Compiled Rib scripts shared by the compilation of all API definitions, such that a script
+// is compiled only once for the same source and component metadata.
+// The scripts compiled by the cache are kept aside until taken with `take_new_entries`,
+// to be persisted, and other worker service instances to start with them.
+#[derive(Default)]
+pub struct CompiledRibCache {
+    entries: RwLock<HashMap<CompiledRibKey, CompilerOutput>>,
+    new_entries: Mutex<Vec<CompiledRibKey>>,
+}
+
+impl CompiledRibCache {
+    pub fn new(entries: HashMap<CompiledRibKey, CompilerOutput>) -> Self {
+        CompiledRibCache {
+            entries: RwLock::new(entries),
+            new_entries: Mutex::new(vec![]),
+        }
+    }
+
+    pub fn compile(
+        &self,
+        rib: &Expr,
+        component_dependency: &[ComponentDependencyWithAgentInfo],
+        path_param_types: &PathParamTypes,
+    ) -> Result<CompilerOutput, RibCompilationError> {
+        let key = match CompiledRibKey::new(rib, component_dependency, path_param_types) {
+            Ok(key) => key,
+            Err(err) => {
+                warn!(
+                    rib = rib.to_string(),
+                    "Compiling Rib without the cache: {err}"
+                );
+                return DefaultWorkerServiceRibCompiler::compile(
+                    rib,
+                    component_dependency,
+                    path_param_types,
+                );
+            }
+        };
+
+        if let Some(compiler_output) = self.entries.read().unwrap().get(&key) {
+            return Ok(compiler_output.clone());
+        }
+
+        let compiler_output =
+            DefaultWorkerServiceRibCompiler::compile(rib, component_dependency, path_param_types)?;
+
+        self.entries
+            .write()
+            .unwrap()
+            .insert(key.clone(), compiler_output.clone());
+        self.new_entries.lock().unwrap().push(key);
+
+        Ok(compiler_output)
+    }
+
+    pub fn take_new_entries(&self) -> Vec<(CompiledRibKey, CompilerOutput)> {
+        let new_entries = std::mem::take(&mut *self.new_entries.lock().unwrap());
+        let entries = self.entries.read().unwrap();
+
+        new_entries
+            .into_iter()
+            .filter_map(|key| {
+                entries
+                    .get(&key)
+                    .cloned()
+                    .map(|compiler_output| (key, compiler_output))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CompiledRibCache, CompiledRibKey};
+    use crate::gateway_rib_compiler::PathParamTypes;
+    use golem_wasm::analysis::analysed_type::u64;
+    use rib::Expr;
+    use test_r::test;
+
+    #[test]
+    fn test_compiled_rib_cache_compiles_once() {
+        let cache = CompiledRibCache::default();
+        let rib = Expr::from_text(r#"let x: u64 = 1; "foo-${x}""#).unwrap();
+
+        let first = cache
+            .compile(&rib, &[], &PathParamTypes::default())
+            .unwrap();
+        let second = cache
+            .compile(&rib, &[], &PathParamTypes::default())
+            .unwrap();
+
+        assert_eq!(first.byte_code, second.byte_code);
+        assert_eq!(cache.take_new_entries().len(), 1);
+        assert!(cache.take_new_entries().is_empty());
+    }
+
+    #[test]
+    fn test_compiled_rib_key_depends_on_path_param_types() {
+        let rib = Expr::from_text("request.path.id").unwrap();
+
+        let untyped = CompiledRibKey::new(&rib, &[], &PathParamTypes::default()).unwrap();
+        let typed =
+            CompiledRibKey::new(&rib, &[], &PathParamTypes(vec![("id".to_string(), u64())]))
+                .unwrap();
+
+        assert_eq!(
+            untyped,
+            CompiledRibKey::new(&rib, &[], &PathParamTypes::default()).unwrap()
+        );
+        assert_ne!(untyped, typed);
+    }
+}
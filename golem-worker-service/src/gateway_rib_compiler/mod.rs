@@ -12,12 +12,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod compiled_rib_cache;
+
+pub use compiled_rib_cache::*;
+
+use crate::gateway_api_definition::http::{AllPathPatterns, PathPattern};
 use golem_common::model::component_metadata::ComponentMetadata;
+use golem_wasm::analysis::AnalysedType;
 use golem_wasm::IntoValue;
 use rib::{
     CompilerOutput, ComponentDependency, ComponentDependencyKey, Expr, GlobalVariableTypeSpec,
     InferredType, InterfaceName, Path, RibCompilationError, RibCompiler, RibCompilerConfig,
 };
+use tracing::warn;
 use uuid::Uuid;
 
 // A wrapper over ComponentDependency which is coming from rib-module
@@ -41,12 +48,36 @@ impl ComponentDependencyWithAgentInfo {
     }
 }
 
+// The types of the path parameters of a route, which are not strings in Rib,
+// because of a typed constraint such as `{id:u64}`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PathParamTypes(pub Vec<(String, AnalysedType)>);
+
+impl PathParamTypes {
+    pub fn from_path(path: &AllPathPatterns) -> Self {
+        PathParamTypes(
+            path.path_patterns
+                .iter()
+                .filter_map(|pattern| match pattern {
+                    PathPattern::Var(var_info) => var_info
+                        .constraint
+                        .as_ref()
+                        .and_then(|constraint| constraint.rib_type())
+                        .map(|typ| (var_info.key_name.clone(), typ)),
+                    _ => None,
+                })
+                .collect(),
+        )
+    }
+}
+
 // A wrapper service over original Rib Compiler concerning
 // the details of the worker bridge.
 pub trait WorkerServiceRibCompiler {
     fn compile(
         rib: &Expr,
         component_dependency: &[ComponentDependencyWithAgentInfo],
+        path_param_types: &PathParamTypes,
     ) -> Result<CompilerOutput, RibCompilationError>;
 }
 
@@ -56,6 +87,7 @@ impl WorkerServiceRibCompiler for DefaultWorkerServiceRibCompiler {
     fn compile(
         rib: &Expr,
         component_dependency: &[ComponentDependencyWithAgentInfo],
+        path_param_types: &PathParamTypes,
     ) -> Result<CompilerOutput, RibCompilationError> {
         let mut custom_instance_spec = vec![];
 
@@ -94,7 +126,7 @@ impl WorkerServiceRibCompiler for DefaultWorkerServiceRibCompiler {
             .map(|cd| cd.component_dependency.clone())
             .collect::<Vec<_>>();
 
-        let rib_input_spec = vec![
+        let mut rib_input_spec = vec![
             GlobalVariableTypeSpec::new(
                 "request",
                 Path::from_elems(vec!["path"]),
@@ -125,11 +157,30 @@ impl WorkerServiceRibCompiler for DefaultWorkerServiceRibCompiler {
             ),
         ];
 
+        // Typed path parameters override the string type of `request.path.*`
+        for (name, typ) in path_param_types.0.iter() {
+            rib_input_spec.push(GlobalVariableTypeSpec::exact(
+                "request",
+                Path::from_elems(vec!["path", name.as_str()]),
+                typ.into(),
+            ));
+        }
+
         let compiler_config =
             RibCompilerConfig::new(component_dependency, rib_input_spec, custom_instance_spec);
 
         let compiler = RibCompiler::new(compiler_config);
 
-        compiler.compile(rib.clone())
+        let compiler_output = compiler.compile(rib.clone())?;
+
+        for warning in &compiler_output.lint_warnings {
+            warn!(
+                rib = rib.to_string(),
+                lint = warning.lint.name(),
+                "Rib script has a lint warning: {warning}"
+            );
+        }
+
+        Ok(compiler_output)
     }
 }
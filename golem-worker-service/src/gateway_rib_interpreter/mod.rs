@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::gateway_execution::sse_response::GatewayStreamEventSender;
 use crate::gateway_execution::{GatewayResolvedWorkerRequest, GatewayWorkerRequestExecutor};
 use async_trait::async_trait;
 use golem_common::model::auth::Namespace;
@@ -21,8 +22,9 @@ use golem_common::SafeDisplay;
 use golem_wasm::analysis::AnalysedType;
 use golem_wasm::ValueAndType;
 use rib::{
-    ComponentDependencyKey, EvaluatedFnArgs, EvaluatedFqFn, EvaluatedWorkerName, InstructionId,
-    RibByteCode, RibComponentFunctionInvoke, RibFunctionInvokeResult, RibInput, RibResult,
+    ComponentDependencyKey, EvaluatedFnArgs, EvaluatedFqFn, EvaluatedWorkerName, ExecutionLimit,
+    InstructionId, RibByteCode, RibComponentFunctionInvoke, RibExecutionLimits,
+    RibFunctionInvokeResult, RibInput, RibResult,
 };
 use std::fmt::Display;
 use std::sync::Arc;
@@ -35,7 +37,7 @@ use std::sync::Arc;
 // rib that's devoid of any instantiation of worker or worker function invocation
 #[async_trait]
 pub trait WorkerServiceRibInterpreter: Send + Sync {
-    // Evaluate a Rib byte code against a specific worker.
+    // Evaluate a Rib byte code against a specific worker, within the given limits.
     // RibByteCode may have actual function calls.
     async fn evaluate(
         &self,
@@ -44,27 +46,59 @@ pub trait WorkerServiceRibInterpreter: Send + Sync {
         rib_byte_code: RibByteCode,
         rib_input: RibInput,
         namespace: Namespace,
+        limits: RibExecutionLimits,
+    ) -> Result<RibResult, RibRuntimeError>;
+
+    // Evaluate a Rib byte code, forwarding the output of every worker
+    // invoked by the script to `events` while it runs.
+    async fn evaluate_streaming(
+        &self,
+        idempotency_key: Option<IdempotencyKey>,
+        invocation_context: InvocationContextStack,
+        rib_byte_code: RibByteCode,
+        rib_input: RibInput,
+        namespace: Namespace,
+        limits: RibExecutionLimits,
+        events: GatewayStreamEventSender,
     ) -> Result<RibResult, RibRuntimeError>;
 }
 
 #[derive(Debug, PartialEq)]
-pub struct RibRuntimeError(pub String);
+pub enum RibRuntimeError {
+    // The script went beyond one of the configured `RibExecutionLimits`
+    LimitExceeded(ExecutionLimit),
+    Failed(String),
+}
 
 impl Display for RibRuntimeError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
+        match self {
+            RibRuntimeError::LimitExceeded(limit) => {
+                write!(f, "Rib execution limit exceeded: {limit}")
+            }
+            RibRuntimeError::Failed(err) => write!(f, "{err}"),
+        }
     }
 }
 
 impl SafeDisplay for RibRuntimeError {
     fn to_safe_string(&self) -> String {
-        self.0.clone()
+        self.to_string()
     }
 }
 
 impl From<String> for RibRuntimeError {
     fn from(err: String) -> Self {
-        RibRuntimeError(err)
+        RibRuntimeError::Failed(err)
+    }
+}
+
+impl From<rib::RibRuntimeError> for RibRuntimeError {
+    fn from(err: rib::RibRuntimeError) -> Self {
+        match err {
+            rib::RibRuntimeError::LimitExceeded(limit) => RibRuntimeError::LimitExceeded(limit),
+            other => RibRuntimeError::Failed(other.to_string()),
+        }
     }
 }
 
@@ -86,12 +120,14 @@ impl DefaultRibInterpreter {
         idempotency_key: Option<IdempotencyKey>,
         invocation_context: InvocationContextStack,
         namespace: Namespace,
+        events: Option<GatewayStreamEventSender>,
     ) -> Arc<dyn RibComponentFunctionInvoke + Sync + Send> {
         Arc::new(WorkerServiceRibInvoke {
             idempotency_key,
             invocation_context,
             executor: self.worker_request_executor.clone(),
             namespace,
+            events,
         })
     }
 }
@@ -105,13 +141,33 @@ impl WorkerServiceRibInterpreter for DefaultRibInterpreter {
         expr: RibByteCode,
         rib_input: RibInput,
         namespace: Namespace,
+        limits: RibExecutionLimits,
     ) -> Result<RibResult, RibRuntimeError> {
         let worker_invoke_function =
-            self.rib_invoke(idempotency_key, invocation_context, namespace);
+            self.rib_invoke(idempotency_key, invocation_context, namespace, None);
 
-        let result = rib::interpret(expr, rib_input, worker_invoke_function, None)
-            .await
-            .map_err(|err| RibRuntimeError(err.to_string()))?;
+        let result =
+            rib::interpret_with_limits(expr, rib_input, worker_invoke_function, None, limits)
+                .await?;
+        Ok(result)
+    }
+
+    async fn evaluate_streaming(
+        &self,
+        idempotency_key: Option<IdempotencyKey>,
+        invocation_context: InvocationContextStack,
+        expr: RibByteCode,
+        rib_input: RibInput,
+        namespace: Namespace,
+        limits: RibExecutionLimits,
+        events: GatewayStreamEventSender,
+    ) -> Result<RibResult, RibRuntimeError> {
+        let worker_invoke_function =
+            self.rib_invoke(idempotency_key, invocation_context, namespace, Some(events));
+
+        let result =
+            rib::interpret_with_limits(expr, rib_input, worker_invoke_function, None, limits)
+                .await?;
         Ok(result)
     }
 }
@@ -121,6 +177,7 @@ struct WorkerServiceRibInvoke {
     invocation_context: InvocationContextStack,
     executor: Arc<dyn GatewayWorkerRequestExecutor>,
     namespace: Namespace,
+    events: Option<GatewayStreamEventSender>,
 }
 
 #[async_trait]
@@ -155,7 +212,15 @@ impl RibComponentFunctionInvoke for WorkerServiceRibInvoke {
             namespace,
         };
 
-        let opt_vnt = executor.execute(worker_request).await.map(|v| v.result)?;
-        Ok(opt_vnt)
+        let response = match &self.events {
+            Some(events) => {
+                executor
+                    .execute_streaming(worker_request, events.clone())
+                    .await?
+            }
+            None => executor.execute(worker_request).await?,
+        };
+
+        Ok(response.result)
     }
 }
@@ -21,3 +21,62 @@ pub fn register_all() -> Registry {
 
     default_registry().clone()
 }
+
+pub mod gateway {
+    use std::time::Duration;
+
+    use lazy_static::lazy_static;
+    use prometheus::*;
+
+    use golem_common::metrics::DEFAULT_TIME_BUCKETS;
+
+    lazy_static! {
+        static ref TRAFFIC_SPLIT_REQUESTS_TOTAL: CounterVec = register_counter_vec!(
+            "gateway_traffic_split_requests_total",
+            "Number of requests routed to a version of an API definition with split traffic",
+            &["api_definition_id", "api_definition_version", "selected_by"]
+        )
+        .unwrap();
+        static ref TRAFFIC_SPLIT_RESPONSES_TOTAL: CounterVec = register_counter_vec!(
+            "gateway_traffic_split_responses_total",
+            "Number of responses of a version of an API definition with split traffic",
+            &["api_definition_id", "api_definition_version", "status"]
+        )
+        .unwrap();
+        static ref TRAFFIC_SPLIT_RESPONSE_TIME_SECONDS: HistogramVec = register_histogram_vec!(
+            "gateway_traffic_split_response_time_seconds",
+            "Response time of a version of an API definition with split traffic",
+            &["api_definition_id", "api_definition_version"],
+            DEFAULT_TIME_BUCKETS.to_vec()
+        )
+        .unwrap();
+    }
+
+    pub fn record_traffic_split_request(
+        api_definition_id: &str,
+        api_definition_version: &str,
+        selected_by: &'static str,
+    ) {
+        TRAFFIC_SPLIT_REQUESTS_TOTAL
+            .with_label_values(&[api_definition_id, api_definition_version, selected_by])
+            .inc();
+    }
+
+    pub fn record_traffic_split_response(
+        api_definition_id: &str,
+        api_definition_version: &str,
+        status: u16,
+        duration: Duration,
+    ) {
+        TRAFFIC_SPLIT_RESPONSES_TOTAL
+            .with_label_values(&[
+                api_definition_id,
+                api_definition_version,
+                &status.to_string(),
+            ])
+            .inc();
+        TRAFFIC_SPLIT_RESPONSE_TIME_SECONDS
+            .with_label_values(&[api_definition_id, api_definition_version])
+            .observe(duration.as_secs_f64());
+    }
+}
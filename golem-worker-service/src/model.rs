@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use crate::gateway_api_definition::{ApiDefinitionId, ApiVersion};
-use crate::gateway_api_deployment::ApiSite;
+use crate::gateway_api_deployment::{ApiSite, TrafficSplit};
 use derive_more::FromStr;
 use golem_common::model::oplog::WorkerResourceId;
 use golem_common::model::regions::OplogRegion;
@@ -206,6 +206,7 @@ pub struct ApiDeployment {
 pub struct ApiDefinitionInfo {
     pub id: ApiDefinitionId,
     pub version: ApiVersion,
+    pub traffic_split: Option<TrafficSplit>,
 }
 
 impl From<crate::gateway_api_deployment::ApiDeployment> for ApiDeployment {
@@ -217,6 +218,7 @@ impl From<crate::gateway_api_deployment::ApiDeployment> for ApiDeployment {
                 .map(|k| ApiDefinitionInfo {
                     id: k.id.clone(),
                     version: k.version.clone(),
+                    traffic_split: api_deployment.traffic_splits.get(k).cloned(),
                 })
                 .collect(),
             project_id: api_deployment.namespace.project_id.clone(),
@@ -36,7 +36,10 @@ impl ApiDefinitionRecord {
         definition: CompiledHttpApiDefinition,
         created_at: chrono::DateTime<chrono::Utc>,
     ) -> Result<Self, String> {
-        let data = record_data_serde::serialize(&definition.routes)?;
+        let data = record_data_serde::serialize(
+            &definition.routes,
+            definition.rib_execution_limits.as_ref(),
+        )?;
         Ok(Self {
             namespace: definition.namespace.to_string(),
             id: definition.id.0,
@@ -51,7 +54,7 @@ impl ApiDefinitionRecord {
 impl TryFrom<ApiDefinitionRecord> for CompiledHttpApiDefinition {
     type Error = String;
     fn try_from(value: ApiDefinitionRecord) -> Result<Self, Self::Error> {
-        let routes = record_data_serde::deserialize(&value.data)?;
+        let (routes, rib_execution_limits) = record_data_serde::deserialize(&value.data)?;
 
         let namespace = Namespace::try_from(value.namespace)
             .map_err(|e| format!("Failed to convert namespace: {e}"))?;
@@ -63,6 +66,7 @@ impl TryFrom<ApiDefinitionRecord> for CompiledHttpApiDefinition {
             draft: value.draft,
             created_at: value.created_at,
             namespace,
+            rib_execution_limits,
         })
     }
 }
@@ -70,7 +74,7 @@ impl TryFrom<ApiDefinitionRecord> for CompiledHttpApiDefinition {
 impl TryFrom<ApiDefinitionRecord> for HttpApiDefinition {
     type Error = String;
     fn try_from(value: ApiDefinitionRecord) -> Result<Self, Self::Error> {
-        let routes = record_data_serde::deserialize(&value.data)?;
+        let (routes, rib_execution_limits) = record_data_serde::deserialize(&value.data)?;
 
         let routes = routes
             .into_iter()
@@ -83,6 +87,7 @@ impl TryFrom<ApiDefinitionRecord> for HttpApiDefinition {
             routes,
             draft: value.draft,
             created_at: value.created_at,
+            rib_execution_limits,
         })
     }
 }
@@ -453,20 +458,34 @@ pub mod record_data_serde {
     use bytes::{BufMut, Bytes, BytesMut};
     use golem_api_grpc::proto::golem::apidefinition::{
         CompiledHttpApiDefinition as ProtoCompiledHttpApiDefinition,
-        CompiledHttpRoute as ProtoCompiledRoute,
+        CompiledHttpRoute as ProtoCompiledRoute, RibExecutionLimits as ProtoRibExecutionLimits,
     };
     use prost::Message;
+    use rib::RibExecutionLimits;
 
     pub const SERIALIZATION_VERSION_V1: u8 = 1u8;
 
-    pub fn serialize(value: &[CompiledRoute]) -> Result<Bytes, String> {
+    pub fn serialize(
+        value: &[CompiledRoute],
+        rib_execution_limits: Option<&RibExecutionLimits>,
+    ) -> Result<Bytes, String> {
         let routes: Vec<ProtoCompiledRoute> = value
             .iter()
             .cloned()
             .map(ProtoCompiledRoute::try_from)
             .collect::<Result<Vec<ProtoCompiledRoute>, String>>()?;
 
-        let proto_value: ProtoCompiledHttpApiDefinition = ProtoCompiledHttpApiDefinition { routes };
+        let rib_execution_limits = rib_execution_limits.map(|limits| ProtoRibExecutionLimits {
+            max_instructions: limits.max_instructions,
+            max_stack_depth: limits.max_stack_depth.map(|value| value as u64),
+            max_list_size: limits.max_list_size.map(|value| value as u64),
+            max_worker_calls: limits.max_worker_calls,
+        });
+
+        let proto_value: ProtoCompiledHttpApiDefinition = ProtoCompiledHttpApiDefinition {
+            routes,
+            rib_execution_limits,
+        };
 
         let mut bytes = BytesMut::new();
         bytes.put_u8(SERIALIZATION_VERSION_V1);
@@ -474,7 +493,10 @@ pub mod record_data_serde {
         Ok(bytes.freeze())
     }
 
-    pub fn deserialize(bytes: &[u8]) -> Result<Vec<CompiledRoute>, String> {
+    // Definitions stored before they could have their own limits are read without any
+    pub fn deserialize(
+        bytes: &[u8],
+    ) -> Result<(Vec<CompiledRoute>, Option<RibExecutionLimits>), String> {
         let (version, data) = bytes.split_at(1);
 
         match version[0] {
@@ -488,7 +510,17 @@ pub mod record_data_serde {
                     .map(CompiledRoute::try_from)
                     .collect::<Result<Vec<CompiledRoute>, String>>()?;
 
-                Ok(value)
+                let rib_execution_limits =
+                    proto_value
+                        .rib_execution_limits
+                        .map(|limits| RibExecutionLimits {
+                            max_instructions: limits.max_instructions,
+                            max_stack_depth: limits.max_stack_depth.map(|value| value as usize),
+                            max_list_size: limits.max_list_size.map(|value| value as usize),
+                            max_worker_calls: limits.max_worker_calls,
+                        });
+
+                Ok((value, rib_execution_limits))
             }
             _ => Err("Unsupported serialization version".to_string()),
         }
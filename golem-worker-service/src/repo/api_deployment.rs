@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::gateway_api_deployment::ApiSite;
+use crate::gateway_api_deployment::{ApiSite, TrafficSplit, TrafficSplitHeader};
 use crate::repo::api_definition::ApiDefinitionRecord;
 use crate::service::gateway::api_definition::ApiDefinitionIdWithVersion;
 use async_trait::async_trait;
@@ -30,6 +30,9 @@ pub struct ApiDeploymentRecord {
     pub subdomain: Option<String>,
     pub definition_id: String,
     pub definition_version: String,
+    pub traffic_weight: Option<i32>,
+    pub traffic_header_name: Option<String>,
+    pub traffic_header_value: Option<String>,
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
@@ -38,18 +41,62 @@ impl ApiDeploymentRecord {
         namespace: Namespace,
         site: ApiSite,
         definition_id: ApiDefinitionIdWithVersion,
+        traffic_split: Option<&TrafficSplit>,
         created_at: chrono::DateTime<chrono::Utc>,
     ) -> Self {
-        Self {
+        let mut record = Self {
             namespace: namespace.to_string(),
             site: site.clone().to_string(),
             host: site.host.clone(),
             subdomain: site.subdomain.clone(),
             definition_id: definition_id.id.0,
             definition_version: definition_id.version.0,
+            traffic_weight: None,
+            traffic_header_name: None,
+            traffic_header_value: None,
             created_at,
+        };
+        record.set_traffic_split(traffic_split);
+        record
+    }
+
+    pub fn key(&self) -> ApiDefinitionIdWithVersion {
+        ApiDefinitionIdWithVersion {
+            id: self.definition_id.clone().into(),
+            version: self.definition_version.clone().into(),
+        }
+    }
+
+    pub fn traffic_split(&self) -> Option<TrafficSplit> {
+        let header = match (&self.traffic_header_name, &self.traffic_header_value) {
+            (Some(name), Some(value)) => Some(TrafficSplitHeader {
+                name: name.clone(),
+                value: value.clone(),
+            }),
+            _ => None,
+        };
+
+        if self.traffic_weight.is_none() && header.is_none() {
+            None
+        } else {
+            Some(TrafficSplit {
+                weight: self.traffic_weight.map(|weight| weight as u32),
+                header,
+            })
         }
     }
+
+    pub fn set_traffic_split(&mut self, traffic_split: Option<&TrafficSplit>) {
+        self.traffic_weight = traffic_split
+            .and_then(|split| split.weight)
+            .map(|weight| weight as i32);
+        self.traffic_header_name = traffic_split
+            .and_then(|split| split.header.as_ref())
+            .map(|header| header.name.clone());
+        self.traffic_header_value = traffic_split
+            .and_then(|split| split.header.as_ref())
+            .map(|header| header.value.clone());
+    }
 }
 
 #[async_trait]
@@ -66,6 +113,12 @@ pub trait ApiDeploymentRepo: Send + Sync {
         deployments: Vec<ApiDeploymentRecord>,
     ) -> Result<bool, RepoError>;
 
+    async fn update_traffic_splits(
+        &self,
+        namespace: &str,
+        deployments: Vec<ApiDeploymentRecord>,
+    ) -> Result<(), RepoError>;
+
     async fn get_all(&self, namespace: &str) -> Result<Vec<ApiDeploymentRecord>, RepoError>;
 
     async fn get_by_id(
@@ -98,6 +151,8 @@ pub trait ApiDeploymentRepo: Send + Sync {
         &self,
         site: &str,
     ) -> Result<Vec<ApiDefinitionRecord>, RepoError>;
+
+    async fn get_all_by_site(&self, site: &str) -> Result<Vec<ApiDeploymentRecord>, RepoError>;
 }
 
 pub struct LoggedDeploymentRepo<Repo: ApiDeploymentRepo> {
@@ -136,6 +191,16 @@ impl<Repo: ApiDeploymentRepo + Sync> ApiDeploymentRepo for LoggedDeploymentRepo<
         self.repo.delete(namespace, deployments).await
     }
 
+    async fn update_traffic_splits(
+        &self,
+        namespace: &str,
+        deployments: Vec<ApiDeploymentRecord>,
+    ) -> Result<(), RepoError> {
+        self.repo
+            .update_traffic_splits(namespace, deployments)
+            .await
+    }
+
     async fn get_all(&self, namespace: &str) -> Result<Vec<ApiDeploymentRecord>, RepoError> {
         self.repo
             .get_all(namespace)
@@ -188,6 +253,10 @@ impl<Repo: ApiDeploymentRepo + Sync> ApiDeploymentRepo for LoggedDeploymentRepo<
     ) -> Result<Vec<ApiDefinitionRecord>, RepoError> {
         self.repo.get_all_definitions_by_site(site).await
     }
+
+    async fn get_all_by_site(&self, site: &str) -> Result<Vec<ApiDeploymentRecord>, RepoError> {
+        self.repo.get_all_by_site(site).await
+    }
 }
 
 pub struct DbApiDeploymentRepo<DB: Pool> {
@@ -219,9 +288,9 @@ impl ApiDeploymentRepo for DbApiDeploymentRepo<golem_service_base::db::postgres:
                 let query = sqlx::query(
                     r#"
                       INSERT INTO api_deployments
-                        (namespace, site, host, subdomain, definition_id, definition_version, created_at)
+                        (namespace, site, host, subdomain, definition_id, definition_version, traffic_weight, traffic_header_name, traffic_header_value, created_at)
                       VALUES
-                        ($1, $2, $3, $4, $5, $6, $7)
+                        ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
                        "#,
                 )
                 .bind(namespace)
@@ -230,6 +299,9 @@ impl ApiDeploymentRepo for DbApiDeploymentRepo<golem_service_base::db::postgres:
                 .bind(deployment.subdomain.clone())
                 .bind(deployment.definition_id.clone())
                 .bind(deployment.definition_version.clone())
+                .bind(deployment.traffic_weight)
+                .bind(deployment.traffic_header_name.clone())
+                .bind(deployment.traffic_header_value.clone())
                 .bind(deployment.created_at);
 
                 transaction.execute(query).await?;
@@ -273,6 +345,43 @@ impl ApiDeploymentRepo for DbApiDeploymentRepo<golem_service_base::db::postgres:
             Ok(false)
         }
     }
+
+    async fn update_traffic_splits(
+        &self,
+        namespace: &str,
+        deployments: Vec<ApiDeploymentRecord>,
+    ) -> Result<(), RepoError> {
+        if !deployments.is_empty() {
+            let mut transaction = self
+                .db_pool
+                .with_rw("api_deployment", "update_traffic_splits")
+                .begin()
+                .await?;
+            for deployment in deployments {
+                let query = sqlx::query(
+                    r#"
+                      UPDATE api_deployments
+                      SET traffic_weight = $1, traffic_header_name = $2, traffic_header_value = $3
+                      WHERE namespace = $4 AND site = $5 AND definition_id = $6 AND definition_version = $7
+                       "#,
+                )
+                .bind(deployment.traffic_weight)
+                .bind(deployment.traffic_header_name.clone())
+                .bind(deployment.traffic_header_value.clone())
+                .bind(namespace)
+                .bind(deployment.site.clone())
+                .bind(deployment.definition_id.clone())
+                .bind(deployment.definition_version.clone());
+                transaction.execute(query).await?;
+            }
+            self.db_pool
+                .with_rw("api_deployment", "update_traffic_splits")
+                .commit(transaction)
+                .await?;
+        }
+        Ok(())
+    }
+
     #[when(golem_service_base::db::postgres::PostgresPool -> get_all)]
     async fn get_all_postgres(
         &self,
@@ -280,7 +389,7 @@ impl ApiDeploymentRepo for DbApiDeploymentRepo<golem_service_base::db::postgres:
     ) -> Result<Vec<ApiDeploymentRecord>, RepoError> {
         let query = sqlx::query_as::<_, ApiDeploymentRecord>(
             r#"
-                SELECT namespace, site, host, subdomain, definition_id, definition_version, created_at::timestamptz
+                SELECT namespace, site, host, subdomain, definition_id, definition_version, traffic_weight, traffic_header_name, traffic_header_value, created_at::timestamptz
                 FROM api_deployments
                 WHERE namespace = $1
                 ORDER BY site, host, subdomain, definition_id, definition_version
@@ -297,7 +406,7 @@ impl ApiDeploymentRepo for DbApiDeploymentRepo<golem_service_base::db::postgres:
     async fn get_all_sqlite(&self, namespace: &str) -> Result<Vec<ApiDeploymentRecord>, RepoError> {
         let query = sqlx::query_as::<_, ApiDeploymentRecord>(
             r#"
-                SELECT namespace, site, host, subdomain, definition_id, definition_version, created_at
+                SELECT namespace, site, host, subdomain, definition_id, definition_version, traffic_weight, traffic_header_name, traffic_header_value, created_at
                 FROM api_deployments
                 WHERE namespace = $1
                 ORDER BY site, host, subdomain, definition_id, definition_version
@@ -319,7 +428,7 @@ impl ApiDeploymentRepo for DbApiDeploymentRepo<golem_service_base::db::postgres:
     ) -> Result<Vec<ApiDeploymentRecord>, RepoError> {
         let query = sqlx::query_as::<_, ApiDeploymentRecord>(
             r#"
-                SELECT namespace, site, host, subdomain, definition_id, definition_version, created_at::timestamptz
+                SELECT namespace, site, host, subdomain, definition_id, definition_version, traffic_weight, traffic_header_name, traffic_header_value, created_at::timestamptz
                 FROM api_deployments
                 WHERE namespace = $1 AND definition_id = $2
                 ORDER BY site, host, subdomain, definition_version
@@ -342,7 +451,7 @@ impl ApiDeploymentRepo for DbApiDeploymentRepo<golem_service_base::db::postgres:
     ) -> Result<Vec<ApiDeploymentRecord>, RepoError> {
         let query = sqlx::query_as::<_, ApiDeploymentRecord>(
             r#"
-                SELECT namespace, site, host, subdomain, definition_id, definition_version, created_at
+                SELECT namespace, site, host, subdomain, definition_id, definition_version, traffic_weight, traffic_header_name, traffic_header_value, created_at
                 FROM api_deployments
                 WHERE namespace = $1 AND definition_id = $2
                 ORDER BY site, host, subdomain, definition_version
@@ -366,7 +475,7 @@ impl ApiDeploymentRepo for DbApiDeploymentRepo<golem_service_base::db::postgres:
     ) -> Result<Vec<ApiDeploymentRecord>, RepoError> {
         let query = sqlx::query_as::<_, ApiDeploymentRecord>(
             r#"
-                SELECT namespace, site, host, subdomain, definition_id, definition_version, created_at::timestamptz
+                SELECT namespace, site, host, subdomain, definition_id, definition_version, traffic_weight, traffic_header_name, traffic_header_value, created_at::timestamptz
                 FROM api_deployments
                 WHERE namespace = $1 AND definition_id = $2 AND definition_version = $3
                 ORDER BY site, host, subdomain
@@ -391,7 +500,7 @@ impl ApiDeploymentRepo for DbApiDeploymentRepo<golem_service_base::db::postgres:
     ) -> Result<Vec<ApiDeploymentRecord>, RepoError> {
         let query = sqlx::query_as::<_, ApiDeploymentRecord>(
             r#"
-                SELECT namespace, site, host, subdomain, definition_id, definition_version, created_at
+                SELECT namespace, site, host, subdomain, definition_id, definition_version, traffic_weight, traffic_header_name, traffic_header_value, created_at
                 FROM api_deployments
                 WHERE namespace = $1 AND definition_id = $2 AND definition_version = $3
                 ORDER BY site, host, subdomain
@@ -415,7 +524,7 @@ impl ApiDeploymentRepo for DbApiDeploymentRepo<golem_service_base::db::postgres:
     ) -> Result<Vec<ApiDeploymentRecord>, RepoError> {
         let query = sqlx::query_as::<_, ApiDeploymentRecord>(
             r#"
-                SELECT namespace, site, host, subdomain, definition_id, definition_version, created_at::timestamptz
+                SELECT namespace, site, host, subdomain, definition_id, definition_version, traffic_weight, traffic_header_name, traffic_header_value, created_at::timestamptz
                 FROM api_deployments
                 WHERE namespace = $1 and site = $2
                 ORDER BY namespace, host, subdomain, definition_id, definition_version
@@ -437,7 +546,7 @@ impl ApiDeploymentRepo for DbApiDeploymentRepo<golem_service_base::db::postgres:
     ) -> Result<Vec<ApiDeploymentRecord>, RepoError> {
         let query = sqlx::query_as::<_, ApiDeploymentRecord>(
             r#"
-                SELECT namespace, site, host, subdomain, definition_id, definition_version, created_at
+                SELECT namespace, site, host, subdomain, definition_id, definition_version, traffic_weight, traffic_header_name, traffic_header_value, created_at
                 FROM api_deployments
                 WHERE namespace = $1 and site = $2
                 ORDER BY namespace, host, subdomain, definition_id, definition_version
@@ -542,4 +651,46 @@ impl ApiDeploymentRepo for DbApiDeploymentRepo<golem_service_base::db::postgres:
             .fetch_all(query)
             .await
     }
+
+    #[when(golem_service_base::db::postgres::PostgresPool -> get_all_by_site)]
+    async fn get_all_by_site_postgres(
+        &self,
+        site: &str,
+    ) -> Result<Vec<ApiDeploymentRecord>, RepoError> {
+        let query = sqlx::query_as::<_, ApiDeploymentRecord>(
+            r#"
+                SELECT namespace, site, host, subdomain, definition_id, definition_version, traffic_weight, traffic_header_name, traffic_header_value, created_at::timestamptz
+                FROM api_deployments
+                WHERE site = $1
+                ORDER BY namespace, definition_id, definition_version
+                "#,
+        )
+        .bind(site);
+
+        self.db_pool
+            .with("api_deployment", "get_all_by_site")
+            .fetch_all(query)
+            .await
+    }
+
+    #[when(golem_service_base::db::sqlite::SqlitePool -> get_all_by_site)]
+    async fn get_all_by_site_sqlite(
+        &self,
+        site: &str,
+    ) -> Result<Vec<ApiDeploymentRecord>, RepoError> {
+        let query = sqlx::query_as::<_, ApiDeploymentRecord>(
+            r#"
+                SELECT namespace, site, host, subdomain, definition_id, definition_version, traffic_weight, traffic_header_name, traffic_header_value, created_at
+                FROM api_deployments
+                WHERE site = $1
+                ORDER BY namespace, definition_id, definition_version
+                "#,
+        )
+        .bind(site);
+
+        self.db_pool
+            .with_ro("api_deployment", "get_all_by_site")
+            .fetch_all(query)
+            .await
+    }
 }
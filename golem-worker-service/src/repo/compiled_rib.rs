@@ -0,0 +1,145 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::gateway_rib_compiler::CompiledRibKey;
+use async_trait::async_trait;
+use conditional_trait_gen::{trait_gen, when};
+use golem_service_base::db::Pool;
+use golem_service_base::repo::RepoError;
+use prost::Message;
+use rib::proto::golem::rib::CompilerOutput as ProtoCompilerOutput;
+use rib::{CompilerOutput, RIB_COMPILER_VERSION};
+
+// A compiled Rib script in its protobuf form, along with the version of the compiler
+// that compiled it
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct CompiledRibRecord {
+    pub key: String,
+    pub compiler_version: i32,
+    pub data: Vec<u8>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl CompiledRibRecord {
+    pub fn new(
+        key: &CompiledRibKey,
+        compiler_output: CompilerOutput,
+        created_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Self, String> {
+        let proto_compiler_output = ProtoCompilerOutput::try_from(compiler_output)?;
+
+        Ok(Self {
+            key: key.0.clone(),
+            compiler_version: RIB_COMPILER_VERSION as i32,
+            data: proto_compiler_output.encode_to_vec(),
+            created_at,
+        })
+    }
+
+    pub fn is_current_compiler_version(&self) -> bool {
+        self.compiler_version == RIB_COMPILER_VERSION as i32
+    }
+}
+
+impl TryFrom<CompiledRibRecord> for CompilerOutput {
+    type Error = String;
+
+    fn try_from(value: CompiledRibRecord) -> Result<Self, Self::Error> {
+        let proto_compiler_output = ProtoCompilerOutput::decode(value.data.as_slice())
+            .map_err(|e| format!("Failed to deserialize compiled Rib: {e}"))?;
+
+        CompilerOutput::try_from(proto_compiler_output)
+    }
+}
+
+#[async_trait]
+pub trait CompiledRibRepo: Send + Sync {
+    async fn create_or_update(&self, records: Vec<CompiledRibRecord>) -> Result<(), RepoError>;
+
+    async fn get_all(&self) -> Result<Vec<CompiledRibRecord>, RepoError>;
+}
+
+pub struct DbCompiledRibRepo<DB: Pool> {
+    db_pool: DB,
+}
+
+impl<DB: Pool> DbCompiledRibRepo<DB> {
+    pub fn new(db_pool: DB) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[trait_gen(golem_service_base::db::postgres::PostgresPool -> golem_service_base::db::postgres::PostgresPool, golem_service_base::db::sqlite::SqlitePool
+)]
+#[async_trait]
+impl CompiledRibRepo for DbCompiledRibRepo<golem_service_base::db::postgres::PostgresPool> {
+    async fn create_or_update(&self, records: Vec<CompiledRibRecord>) -> Result<(), RepoError> {
+        if !records.is_empty() {
+            let mut transaction = self
+                .db_pool
+                .with_rw("compiled_rib", "create_or_update")
+                .begin()
+                .await?;
+            for record in records {
+                let query = sqlx::query(
+                    r#"
+                      INSERT INTO compiled_rib
+                        (key, compiler_version, data, created_at)
+                      VALUES
+                        ($1, $2, $3, $4)
+                      ON CONFLICT (key) DO UPDATE
+                      SET compiler_version = $2,
+                          data = $3,
+                          created_at = $4
+                    "#,
+                )
+                .bind(record.key)
+                .bind(record.compiler_version)
+                .bind(record.data)
+                .bind(record.created_at);
+
+                transaction.execute(query).await?;
+            }
+            self.db_pool
+                .with_rw("compiled_rib", "create_or_update")
+                .commit(transaction)
+                .await?;
+        }
+        Ok(())
+    }
+
+    #[when(golem_service_base::db::sqlite::SqlitePool -> get_all)]
+    async fn get_all_sqlite(&self) -> Result<Vec<CompiledRibRecord>, RepoError> {
+        let query = sqlx::query_as::<_, CompiledRibRecord>(
+            "SELECT key, compiler_version, data, created_at FROM compiled_rib",
+        );
+
+        self.db_pool
+            .with_ro("compiled_rib", "get_all")
+            .fetch_all(query)
+            .await
+    }
+
+    #[when(golem_service_base::db::postgres::PostgresPool -> get_all)]
+    async fn get_all_postgres(&self) -> Result<Vec<CompiledRibRecord>, RepoError> {
+        let query = sqlx::query_as::<_, CompiledRibRecord>(
+            "SELECT key, compiler_version, data, created_at::timestamptz FROM compiled_rib",
+        );
+
+        self.db_pool
+            .with_ro("compiled_rib", "get_all")
+            .fetch_all(query)
+            .await
+    }
+}
@@ -16,4 +16,5 @@ pub mod api_certificate;
 pub mod api_definition;
 pub mod api_deployment;
 pub mod api_domain;
+pub mod compiled_rib;
 pub mod security_scheme;
@@ -18,10 +18,12 @@ use crate::gateway_api_definition::http::{
     HttpApiDefinitionRequest, OpenApiHttpApiDefinition, RouteCompilationErrors,
 };
 use crate::gateway_api_definition::{ApiDefinitionId, ApiVersion, HasGolemBindings};
+use crate::gateway_rib_compiler::{CompiledRibCache, CompiledRibKey};
 use crate::gateway_security::IdentityProviderError;
 use crate::repo::api_definition::ApiDefinitionRecord;
 use crate::repo::api_definition::ApiDefinitionRepo;
 use crate::repo::api_deployment::ApiDeploymentRepo;
+use crate::repo::compiled_rib::{CompiledRibRecord, CompiledRibRepo};
 use crate::service::component::{ComponentService, ComponentServiceError};
 use crate::service::gateway::api_definition_validator::{
     ApiDefinitionValidatorService, ValidationErrors,
@@ -37,7 +39,7 @@ use golem_common::model::ComponentId;
 use golem_common::SafeDisplay;
 use golem_service_base::model::{Component, ComponentName};
 use golem_service_base::repo::RepoError;
-use rib::RibCompilationError;
+use rib::{CompilerOutput, RibCompilationError, RIB_COMPILER_VERSION};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
@@ -148,6 +150,9 @@ impl From<RouteCompilationErrors> for ApiDefinitionError {
                 RibCompilationError::ByteCodeGenerationFail(error) => {
                     ApiDefinitionError::RibByteCodeGenerationError(error.to_string())
                 }
+                error @ RibCompilationError::DeniedLints(_) => {
+                    ApiDefinitionError::RibCompilationErrors(error.to_string())
+                }
             },
             RouteCompilationErrors::ValidationError(e) => ApiDefinitionError::ValidationError(e),
             RouteCompilationErrors::MetadataNotFoundError(e) => {
@@ -344,6 +349,8 @@ pub struct ApiDefinitionServiceDefault {
     deployment_repo: Arc<dyn ApiDeploymentRepo>,
     security_scheme_service: Arc<dyn SecuritySchemeService>,
     api_definition_validator: Arc<dyn ApiDefinitionValidatorService<HttpApiDefinition>>,
+    compiled_rib_repo: Arc<dyn CompiledRibRepo>,
+    compiled_rib_cache: Arc<CompiledRibCache>,
     component_name_cache: ComponentByNameCache,
     component_id_cache: ComponentByIdCache,
 }
@@ -357,6 +364,8 @@ impl ApiDefinitionServiceDefault {
         api_definition_validator: Arc<
             dyn ApiDefinitionValidatorService<HttpApiDefinition> + Sync + Send,
         >,
+        compiled_rib_repo: Arc<dyn CompiledRibRepo>,
+        compiled_rib_cache: Arc<CompiledRibCache>,
         config: ApiDefinitionServiceConfig,
     ) -> Self {
         Self {
@@ -365,6 +374,8 @@ impl ApiDefinitionServiceDefault {
             security_scheme_service,
             deployment_repo,
             api_definition_validator,
+            compiled_rib_repo,
+            compiled_rib_cache,
             component_name_cache: Cache::new(
                 Some(config.component_by_name_cache_size),
                 FullCacheEvictionMode::None,
@@ -435,6 +446,73 @@ impl ApiDefinitionServiceDefault {
                 .collect())
         }
     }
+
+    // Failing to persist the compiled Rib scripts does not fail the API definition,
+    // as other worker service instances can still compile them again
+    async fn persist_compiled_ribs(&self) {
+        let created_at = Utc::now();
+
+        let records = self
+            .compiled_rib_cache
+            .take_new_entries()
+            .into_iter()
+            .filter_map(|(key, compiler_output)| {
+                CompiledRibRecord::new(&key, compiler_output, created_at)
+                    .inspect_err(|err| {
+                        error!(error = err, key = key.0, "Failed to serialize compiled Rib")
+                    })
+                    .ok()
+            })
+            .collect::<Vec<_>>();
+
+        if let Err(err) = self.compiled_rib_repo.create_or_update(records).await {
+            error!(error = err.to_string(), "Failed to persist compiled Rib");
+        }
+    }
+}
+
+// Loads the Rib scripts compiled by the current version of the Rib compiler, so that
+// API definitions can be compiled without compiling them again.
+// Scripts compiled by a different version are ignored, and replaced once compiled again.
+pub async fn load_compiled_rib_cache(
+    compiled_rib_repo: &dyn CompiledRibRepo,
+) -> Result<CompiledRibCache, RepoError> {
+    let records = compiled_rib_repo.get_all().await?;
+
+    let mut entries = HashMap::new();
+    let mut outdated = 0;
+
+    for record in records {
+        if !record.is_current_compiler_version() {
+            outdated += 1;
+            continue;
+        }
+
+        let key = CompiledRibKey(record.key.clone());
+
+        match CompilerOutput::try_from(record) {
+            Ok(compiler_output) => {
+                entries.insert(key, compiler_output);
+            }
+            Err(err) => {
+                error!(
+                    error = err,
+                    key = key.0,
+                    "Failed to deserialize compiled Rib"
+                );
+            }
+        }
+    }
+
+    if outdated > 0 {
+        info!(
+            outdated,
+            compiler_version = RIB_COMPILER_VERSION,
+            "Ignoring Rib compiled by a different version of the Rib compiler"
+        );
+    }
+
+    Ok(CompiledRibCache::new(entries))
 }
 
 #[async_trait]
@@ -490,8 +568,11 @@ impl ApiDefinitionService for ApiDefinitionServiceDefault {
             &component_metadata_dictionary,
             namespace,
             &conversion_context,
+            &self.compiled_rib_cache,
         )?;
 
+        self.persist_compiled_ribs().await;
+
         let record = ApiDefinitionRecord::new(compiled_http_api_definition.clone(), created_at)
             .map_err(|e| {
                 ApiDefinitionError::Internal(format!("Failed to create API definition record: {e}"))
@@ -565,8 +646,11 @@ impl ApiDefinitionService for ApiDefinitionServiceDefault {
             &component_metadata_dictionary,
             namespace,
             &conversion_context,
+            &self.compiled_rib_cache,
         )?;
 
+        self.persist_compiled_ribs().await;
+
         let record = ApiDefinitionRecord::new(compiled_http_api_definition.clone(), created_at)
             .map_err(|e| {
                 ApiDefinitionError::Internal(format!("Failed to create API definition record: {e}"))
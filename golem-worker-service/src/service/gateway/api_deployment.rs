@@ -83,6 +83,13 @@ pub trait ApiDeploymentService: Send + Sync {
         site: &ApiSiteString,
     ) -> Result<Vec<CompiledHttpApiDefinition>, ApiDeploymentError>;
 
+    /// Get the traffic splits between the versions of the API definitions deployed in a site,
+    /// regardless of the namespace, to serve the http requests to API gateway
+    async fn get_all_traffic_splits_by_site(
+        &self,
+        site: &ApiSiteString,
+    ) -> Result<HashMap<ApiDefinitionIdWithVersion, TrafficSplit>, ApiDeploymentError>;
+
     async fn delete(
         &self,
         namespace: &Namespace,
@@ -101,6 +108,8 @@ pub enum ApiDeploymentError {
     ApiDeploymentConflict(ApiSiteString),
     #[error("API deployment definitions conflict error: {0}")]
     ApiDefinitionsConflict(String),
+    #[error("Invalid traffic split: {0}")]
+    InvalidTrafficSplit(String),
     #[error("Internal repository error: {0}")]
     InternalRepoError(RepoError),
     #[error("Internal error: failed to convert {what}: {error}")]
@@ -131,6 +140,7 @@ impl SafeDisplay for ApiDeploymentError {
             ApiDeploymentError::ApiDeploymentNotFound(_, _) => self.to_string(),
             ApiDeploymentError::ApiDeploymentConflict(_) => self.to_string(),
             ApiDeploymentError::ApiDefinitionsConflict(_) => self.to_string(),
+            ApiDeploymentError::InvalidTrafficSplit(_) => self.to_string(),
             ApiDeploymentError::InternalRepoError(inner) => inner.to_safe_string(),
             ApiDeploymentError::InternalConversionError { .. } => self.to_string(),
             ApiDeploymentError::ComponentConstraintCreateError(_) => self.to_string(),
@@ -178,6 +188,95 @@ impl ConflictChecker for HttpApiDefinition {
     }
 }
 
+// Routes conflict pairwise, so checking each pair of definitions finds the same conflicts as checking
+// all of them at once, while allowing to skip the pairs of versions of the same definition.
+fn find_conflicts_between_definitions(definitions: &[HttpApiDefinition]) -> Vec<AllPathPatterns> {
+    let mut conflicts = vec![];
+
+    for (index, definition) in definitions.iter().enumerate() {
+        let mut found = HttpApiDefinition::find_conflicts(std::slice::from_ref(definition));
+
+        for other in definitions[index + 1..]
+            .iter()
+            .filter(|other| other.id != definition.id)
+        {
+            found.extend(HttpApiDefinition::find_conflicts(&[
+                definition.clone(),
+                other.clone(),
+            ]));
+        }
+
+        for conflict in found {
+            if !conflicts.contains(&conflict) {
+                conflicts.push(conflict);
+            }
+        }
+    }
+
+    conflicts
+}
+
+// Versions of the same API definition can only be deployed to a site together if each of them
+// has a traffic split, their weights add up to 100 and they do not share the same header.
+fn validate_traffic_splits(
+    deployed: &[(ApiDefinitionIdWithVersion, Option<TrafficSplit>)],
+) -> Result<(), ApiDeploymentError> {
+    let mut versions: HashMap<&ApiDefinitionId, Vec<(&ApiVersion, &Option<TrafficSplit>)>> =
+        HashMap::new();
+
+    for (key, traffic_split) in deployed {
+        if let Some(weight) = traffic_split.as_ref().and_then(|split| split.weight) {
+            if weight > 100 {
+                return Err(ApiDeploymentError::InvalidTrafficSplit(format!(
+                    "weight of {}/{} is {weight}, it has to be between 0 and 100",
+                    key.id, key.version
+                )));
+            }
+        }
+
+        versions
+            .entry(&key.id)
+            .or_default()
+            .push((&key.version, traffic_split));
+    }
+
+    for (id, versions) in versions {
+        if versions.len() < 2 {
+            continue;
+        }
+
+        let mut total_weight = 0;
+        let mut headers = HashSet::new();
+
+        for (version, traffic_split) in versions {
+            let traffic_split = traffic_split.as_ref().ok_or_else(|| {
+                ApiDeploymentError::InvalidTrafficSplit(format!(
+                    "multiple versions of {id} are deployed, but {id}/{version} has no traffic split"
+                ))
+            })?;
+
+            total_weight += traffic_split.weight();
+
+            if let Some(header) = &traffic_split.header {
+                if !headers.insert((header.name.to_lowercase(), header.value.clone())) {
+                    return Err(ApiDeploymentError::InvalidTrafficSplit(format!(
+                        "multiple versions of {id} are selected by the header {}: {}",
+                        header.name, header.value
+                    )));
+                }
+            }
+        }
+
+        if total_weight != 100 {
+            return Err(ApiDeploymentError::InvalidTrafficSplit(format!(
+                "weights of the versions of {id} add up to {total_weight} instead of 100"
+            )));
+        }
+    }
+
+    Ok(())
+}
+
 pub struct ApiDeploymentServiceDefault {
     pub deployment_repo: Arc<dyn ApiDeploymentRepo>,
     pub definition_repo: Arc<dyn ApiDefinitionRepo>,
@@ -238,17 +337,28 @@ impl ApiDeploymentServiceDefault {
     }
 
     /// Checks for conflicts among API definitions.
+    /// Versions of the same API definition only serve a request alternatively (by splitting the traffic),
+    /// so they are not conflicting with each other.
     fn check_for_conflicts(
         &self,
         namespace: &Namespace,
         all_definitions: &[CompiledHttpApiDefinition],
     ) -> Result<(), ApiDeploymentError> {
-        let conflicts = HttpApiDefinition::find_conflicts(
-            &all_definitions
-                .iter()
-                .map(|x| HttpApiDefinition::from((*x).clone()))
-                .collect::<Vec<_>>(),
-        );
+        let definitions = all_definitions
+            .iter()
+            .map(|x| HttpApiDefinition::from((*x).clone()))
+            .collect::<Vec<_>>();
+
+        let has_multiple_versions = definitions
+            .iter()
+            .enumerate()
+            .any(|(index, def)| definitions[..index].iter().any(|other| other.id == def.id));
+
+        let conflicts = if has_multiple_versions {
+            find_conflicts_between_definitions(&definitions)
+        } else {
+            HttpApiDefinition::find_conflicts(&definitions)
+        };
 
         if conflicts.is_empty() {
             Ok(())
@@ -333,6 +443,13 @@ impl ApiDeploymentServiceDefault {
             )
             .await?;
 
+        self.deployment_repo
+            .update_traffic_splits(
+                &deployment_plan.namespace.to_string(),
+                deployment_plan.traffic_splits_to_update.clone(),
+            )
+            .await?;
+
         Ok(())
     }
 
@@ -413,6 +530,8 @@ impl ApiDeploymentService for ApiDeploymentServiceDefault {
         )
         .await?;
 
+        validate_traffic_splits(&new_deployment.traffic_splits_after_deployment())?;
+
         self.finalize_deployment(deployment_request, auth_ctx, new_deployment)
             .await
     }
@@ -529,6 +648,9 @@ impl ApiDeploymentService for ApiDeploymentServiceDefault {
         let mut values: Vec<ApiDeployment> = vec![];
 
         for deployment_record in existing_deployment_records {
+            let api_definition_key = deployment_record.key();
+            let traffic_split = deployment_record.traffic_split();
+
             let site = ApiSite {
                 host: deployment_record.host,
                 subdomain: deployment_record.subdomain,
@@ -540,27 +662,32 @@ impl ApiDeploymentService for ApiDeploymentServiceDefault {
                 },
             )?;
 
-            let api_definition_key = ApiDefinitionIdWithVersion {
-                id: deployment_record.definition_id.into(),
-                version: deployment_record.definition_version.into(),
-            };
-
-            match values
-                .iter_mut()
-                .find(|val| val.site == site && val.namespace == namespace)
+            let index = match values
+                .iter()
+                .position(|val| val.site == site && val.namespace == namespace)
             {
-                Some(val) => {
-                    val.api_definition_keys.push(api_definition_key);
-                }
+                Some(index) => index,
                 None => {
                     values.push(ApiDeployment {
                         site,
                         namespace,
-                        api_definition_keys: vec![api_definition_key],
+                        api_definition_keys: vec![],
                         created_at: deployment_record.created_at,
+                        traffic_splits: HashMap::new(),
                     });
+                    values.len() - 1
                 }
+            };
+
+            let deployment = &mut values[index];
+
+            if let Some(traffic_split) = traffic_split {
+                deployment
+                    .traffic_splits
+                    .insert(api_definition_key.clone(), traffic_split);
             }
+
+            deployment.api_definition_keys.push(api_definition_key);
         }
 
         Ok(values)
@@ -578,10 +705,17 @@ impl ApiDeploymentService for ApiDeploymentServiceDefault {
             .await?;
 
         let mut api_definition_keys: Vec<ApiDefinitionIdWithVersion> = vec![];
+        let mut traffic_splits = HashMap::new();
         let mut site: Option<ApiSite> = None;
         let mut created_at: Option<chrono::DateTime<Utc>> = None;
 
         for deployment_record in existing_deployment_records {
+            let api_definition_key = deployment_record.key();
+
+            if let Some(traffic_split) = deployment_record.traffic_split() {
+                traffic_splits.insert(api_definition_key.clone(), traffic_split);
+            }
+
             // Retrieving the original domain and subdomain from the deployment record
             if site.is_none() {
                 site = Some(ApiSite {
@@ -595,10 +729,7 @@ impl ApiDeploymentService for ApiDeploymentServiceDefault {
                 created_at = Some(deployment_record.created_at);
             }
 
-            api_definition_keys.push(ApiDefinitionIdWithVersion {
-                id: deployment_record.definition_id.into(),
-                version: deployment_record.definition_version.into(),
-            });
+            api_definition_keys.push(api_definition_key);
         }
 
         match (site, created_at) {
@@ -607,6 +738,7 @@ impl ApiDeploymentService for ApiDeploymentServiceDefault {
                 site,
                 api_definition_keys,
                 created_at,
+                traffic_splits,
             })),
             _ => Ok(None),
         }
@@ -659,6 +791,25 @@ impl ApiDeploymentService for ApiDeploymentServiceDefault {
         Ok(values)
     }
 
+    async fn get_all_traffic_splits_by_site(
+        &self,
+        site: &ApiSiteString,
+    ) -> Result<HashMap<ApiDefinitionIdWithVersion, TrafficSplit>, ApiDeploymentError> {
+        let records = self
+            .deployment_repo
+            .get_all_by_site(&site.to_string())
+            .await?;
+
+        Ok(records
+            .into_iter()
+            .filter_map(|record| {
+                record
+                    .traffic_split()
+                    .map(|traffic_split| (record.key(), traffic_split))
+            })
+            .collect())
+    }
+
     async fn delete(
         &self,
         namespace: &Namespace,
@@ -703,6 +854,11 @@ struct ApiDeploymentPlan {
     namespace: Namespace,
     site: ApiSite,
     apis_to_deploy: Vec<CompiledHttpApiDefinition>,
+    traffic_splits: HashMap<ApiDefinitionIdWithVersion, TrafficSplit>,
+    // Already deployed definitions with a changed traffic split
+    traffic_splits_to_update: Vec<ApiDeploymentRecord>,
+    // Already deployed definitions which are not affected by the plan
+    unchanged_deployments: Vec<ApiDeploymentRecord>,
 }
 
 impl ApiDeploymentPlan {
@@ -712,20 +868,31 @@ impl ApiDeploymentPlan {
         definition_repo: &Arc<dyn ApiDefinitionRepo>,
     ) -> Result<ApiDeploymentPlan, ApiDeploymentError> {
         let mut new_definitions_to_deploy = Vec::new();
+        let mut traffic_splits_to_update = Vec::new();
+        let mut unchanged_deployments = Vec::new();
 
-        let existing_deployed_api_def_keys = deployment_repo
+        let existing_deployments = deployment_repo
             .get_by_site(
                 &deployment_request.namespace.to_string(),
                 &deployment_request.site.to_string(),
             )
-            .await?
-            .into_iter()
-            .map(|record| ApiDefinitionIdWithVersion {
-                id: record.definition_id.into(),
-                version: record.definition_version.into(),
-            })
+            .await?;
+
+        let existing_deployed_api_def_keys = existing_deployments
+            .iter()
+            .map(|record| record.key())
             .collect::<HashSet<_>>();
 
+        for mut record in existing_deployments {
+            match deployment_request.traffic_splits.get(&record.key()) {
+                Some(traffic_split) if record.traffic_split().as_ref() != Some(traffic_split) => {
+                    record.set_traffic_split(Some(traffic_split));
+                    traffic_splits_to_update.push(record);
+                }
+                _ => unchanged_deployments.push(record),
+            }
+        }
+
         for api_key_to_deploy in &deployment_request.api_definition_keys {
             if existing_deployed_api_def_keys.contains(api_key_to_deploy) {
                 continue;
@@ -755,9 +922,31 @@ impl ApiDeploymentPlan {
             namespace: deployment_request.namespace.clone(),
             site: deployment_request.site.clone(),
             apis_to_deploy: new_definitions_to_deploy,
+            traffic_splits: deployment_request.traffic_splits.clone(),
+            traffic_splits_to_update,
+            unchanged_deployments,
         })
     }
 
+    // The traffic splits of all the definitions deployed to the site once the plan is applied
+    pub fn traffic_splits_after_deployment(
+        &self,
+    ) -> Vec<(ApiDefinitionIdWithVersion, Option<TrafficSplit>)> {
+        self.unchanged_deployments
+            .iter()
+            .chain(self.traffic_splits_to_update.iter())
+            .map(|record| (record.key(), record.traffic_split()))
+            .chain(self.apis_to_deploy.iter().map(|def| {
+                let key = ApiDefinitionIdWithVersion {
+                    id: def.id.clone(),
+                    version: def.version.clone(),
+                };
+                let traffic_split = self.traffic_splits.get(&key).cloned();
+                (key, traffic_split)
+            }))
+            .collect()
+    }
+
     pub fn remove_existing_deployed_auth_call_backs(
         &self,
         deployed_auth_call_back_routes: &[CompiledAuthCallBackRoute],
@@ -769,7 +958,7 @@ impl ApiDeploymentPlan {
     }
 
     pub fn is_empty(&self) -> bool {
-        self.apis_to_deploy.is_empty()
+        self.apis_to_deploy.is_empty() && self.traffic_splits_to_update.is_empty()
     }
 
     // All the new API definitions (in the plan) to be deployed in this site
@@ -789,13 +978,17 @@ impl ApiDeploymentPlan {
         self.apis_to_deploy
             .iter()
             .map(|def| {
+                let key = ApiDefinitionIdWithVersion {
+                    id: def.id.clone(),
+                    version: def.version.clone(),
+                };
+                let traffic_split = self.traffic_splits.get(&key);
+
                 ApiDeploymentRecord::new(
                     self.namespace.to_string(),
                     self.site.clone(),
-                    ApiDefinitionIdWithVersion {
-                        id: def.id.clone(),
-                        version: def.version.clone(),
-                    },
+                    key,
+                    traffic_split,
                     created_at,
                 )
             })
@@ -882,10 +1075,84 @@ impl ComponentConstraints {
 mod tests {
     use test_r::test;
 
-    use crate::service::gateway::api_deployment::ApiDeploymentError;
+    use crate::gateway_api_definition::{ApiDefinitionId, ApiVersion};
+    use crate::gateway_api_deployment::{TrafficSplit, TrafficSplitHeader};
+    use crate::service::gateway::api_definition::ApiDefinitionIdWithVersion;
+    use crate::service::gateway::api_deployment::{validate_traffic_splits, ApiDeploymentError};
     use golem_common::SafeDisplay;
     use golem_service_base::repo::RepoError;
 
+    fn deployed(
+        version: &str,
+        weight: Option<u32>,
+        header: Option<(&str, &str)>,
+    ) -> (ApiDefinitionIdWithVersion, Option<TrafficSplit>) {
+        let traffic_split = if weight.is_none() && header.is_none() {
+            None
+        } else {
+            Some(TrafficSplit {
+                weight,
+                header: header.map(|(name, value)| TrafficSplitHeader {
+                    name: name.to_string(),
+                    value: value.to_string(),
+                }),
+            })
+        };
+
+        (
+            ApiDefinitionIdWithVersion {
+                id: ApiDefinitionId("shop".to_string()),
+                version: ApiVersion(version.to_string()),
+            },
+            traffic_split,
+        )
+    }
+
+    #[test]
+    pub fn test_traffic_split_of_single_version_is_not_validated() {
+        assert!(validate_traffic_splits(&[deployed("0.0.1", None, None)]).is_ok());
+        assert!(validate_traffic_splits(&[deployed("0.0.1", Some(5), None)]).is_ok());
+    }
+
+    #[test]
+    pub fn test_canary_traffic_split() {
+        let result = validate_traffic_splits(&[
+            deployed("0.0.1", Some(95), None),
+            deployed("0.0.2", Some(5), Some(("x-canary", "true"))),
+        ]);
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    pub fn test_invalid_traffic_splits() {
+        let missing = validate_traffic_splits(&[
+            deployed("0.0.1", None, None),
+            deployed("0.0.2", Some(100), None),
+        ]);
+        let wrong_total = validate_traffic_splits(&[
+            deployed("0.0.1", Some(90), None),
+            deployed("0.0.2", Some(5), None),
+        ]);
+        let same_header = validate_traffic_splits(&[
+            deployed("0.0.1", Some(50), Some(("x-canary", "true"))),
+            deployed("0.0.2", Some(50), Some(("X-Canary", "true"))),
+        ]);
+
+        assert!(matches!(
+            missing,
+            Err(ApiDeploymentError::InvalidTrafficSplit(_))
+        ));
+        assert!(matches!(
+            wrong_total,
+            Err(ApiDeploymentError::InvalidTrafficSplit(_))
+        ));
+        assert!(matches!(
+            same_header,
+            Err(ApiDeploymentError::InvalidTrafficSplit(_))
+        ));
+    }
+
     #[test]
     pub fn test_repo_error_to_service_error() {
         let repo_err = RepoError::Internal("some sql error".to_string());
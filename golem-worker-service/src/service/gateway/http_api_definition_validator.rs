@@ -12,8 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::gateway_api_definition::http::api_proto_convert::GrpcMethod;
 use crate::gateway_api_definition::http::{HttpApiDefinition, MethodPattern, Route};
 use crate::gateway_api_definition::ApiDefinitionId;
+use crate::gateway_binding::{GatewayBinding, IngressProtocolType};
 use crate::gateway_execution::router::{Router, RouterPattern};
 use crate::service::gateway::api_definition_validator::{
     ApiDefinitionValidatorService, ValidationErrors,
@@ -68,7 +70,9 @@ impl ApiDefinitionValidatorService<HttpApiDefinition> for HttpApiDefinitionValid
         api: &HttpApiDefinition,
         _components: &[Component],
     ) -> Result<(), ValidationErrors> {
-        let errors = unique_routes(api.routes.as_slice());
+        let mut errors = unique_routes(api.routes.as_slice());
+        errors.extend(grpc_routes(api.routes.as_slice()));
+        errors.extend(transformations(api.routes.as_slice()));
         let errors_string = errors.iter().map(|x| x.to_string()).collect::<Vec<_>>();
 
         if errors_string.is_empty() {
@@ -121,6 +125,65 @@ fn unique_routes(routes: &[Route]) -> Vec<RouteValidationError> {
     errors
 }
 
+fn grpc_routes(routes: &[Route]) -> Vec<RouteValidationError> {
+    let mut errors = vec![];
+    let mut packages = vec![];
+
+    for route in routes {
+        if let GatewayBinding::Default(worker_binding) = &route.binding {
+            if worker_binding.ingress_protocol == Some(IngressProtocolType::Grpc) {
+                match GrpcMethod::from_route(&route.method, &route.path) {
+                    Ok(method) => {
+                        if !packages.contains(&method.package) {
+                            packages.push(method.package);
+                        }
+                    }
+                    Err(detail) => errors.push(RouteValidationError {
+                        method: route.method.clone(),
+                        path: route.path.to_string(),
+                        component: route.binding.get_component_id(),
+                        detail,
+                    }),
+                }
+            }
+        }
+    }
+
+    // The gRPC services of a definition are exported as a single .proto file
+    if packages.len() > 1 {
+        errors.push(RouteValidationError {
+            method: MethodPattern::Post,
+            path: "/".to_string(),
+            component: None,
+            detail: "All gRPC routes of an API definition must be in the same package".to_string(),
+        });
+    }
+
+    errors
+}
+
+fn transformations(routes: &[Route]) -> Vec<RouteValidationError> {
+    routes
+        .iter()
+        .filter_map(|route| {
+            let transformation = route
+                .middlewares
+                .as_ref()
+                .and_then(|middlewares| middlewares.get_transformation_middleware())?;
+
+            transformation
+                .validate(&route.path)
+                .err()
+                .map(|detail| RouteValidationError {
+                    method: route.method.clone(),
+                    path: route.path.to_string(),
+                    component: route.binding.get_component_id(),
+                    detail,
+                })
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use test_r::test;
@@ -132,25 +195,27 @@ mod tests {
     use golem_common::model::ComponentId;
     use rib::Expr;
 
-    #[test]
-    fn test_unique_routes() {
-        fn make_route(method: MethodPattern, path: &str) -> Route {
-            Route {
-                method,
-                path: crate::gateway_api_definition::http::AllPathPatterns::parse(path).unwrap(),
-                binding: GatewayBinding::Default(Box::new(crate::gateway_binding::WorkerBinding {
-                    component_id: VersionedComponentId {
-                        component_id: ComponentId::new_v4(),
-                        version: 1,
-                    },
-                    idempotency_key: None,
-                    response_mapping: ResponseMapping(Expr::literal("sample")),
-                    invocation_context: None,
-                })),
-                middlewares: None,
-            }
+    fn make_route(method: MethodPattern, path: &str) -> Route {
+        Route {
+            method,
+            path: crate::gateway_api_definition::http::AllPathPatterns::parse(path).unwrap(),
+            binding: GatewayBinding::Default(Box::new(crate::gateway_binding::WorkerBinding {
+                component_id: VersionedComponentId {
+                    component_id: ComponentId::new_v4(),
+                    version: 1,
+                },
+                idempotency_key: None,
+                response_mapping: ResponseMapping(Expr::literal("sample")),
+                invocation_context: None,
+                response_stream: None,
+                ingress_protocol: None,
+            })),
+            middlewares: None,
         }
+    }
 
+    #[test]
+    fn test_unique_routes() {
         let paths = &[
             "/users/{id}/posts/{post_id}",
             "/users/{id}/posts/{post_id}/comments/{comment_id}",
@@ -180,4 +245,28 @@ mod tests {
         assert!(errors.len() == 1);
         assert!(errors[0].detail.contains(paths[0]), "Received: {errors:?}");
     }
+
+    #[test]
+    fn test_unique_routes_with_constraints() {
+        let routes = [
+            make_route(MethodPattern::Get, "/users/me"),
+            make_route(MethodPattern::Get, "/users/{id:u64}"),
+            make_route(MethodPattern::Get, "/users/{id:uuid}"),
+            make_route(MethodPattern::Get, "/users/{name}"),
+        ];
+
+        let errors = unique_routes(&routes);
+        assert!(errors.is_empty(), "Received: {errors:?}");
+
+        let conflict_route = make_route(MethodPattern::Get, "/users/{user_id:u64}");
+
+        let with_conflict = [&routes[..], &[conflict_route]].concat();
+
+        let errors = unique_routes(&with_conflict);
+        assert!(errors.len() == 1);
+        assert!(
+            errors[0].detail.contains("/users/{id:u64}"),
+            "Received: {errors:?}"
+        );
+    }
 }
@@ -43,6 +43,7 @@ use crate::repo::api_certificate::{ApiCertificateRepo, DbApiCertificateRepo};
 use crate::repo::api_definition::{ApiDefinitionRepo, DbApiDefinitionRepo};
 use crate::repo::api_deployment::{ApiDeploymentRepo, DbApiDeploymentRepo};
 use crate::repo::api_domain::{ApiDomainRepo, DbApiDomainRepo};
+use crate::repo::compiled_rib::{CompiledRibRepo, DbCompiledRibRepo};
 use crate::repo::security_scheme::{DbSecuritySchemeRepo, SecuritySchemeRepo};
 use crate::service::api_certificate::{
     AwsCertificateManager, CertificateManager, CertificateService, CertificateServiceDefault,
@@ -57,7 +58,8 @@ use crate::service::api_security::{SecuritySchemeService, SecuritySchemeServiceD
 use crate::service::auth::{AuthService, GrpcAuthService};
 use crate::service::component::{CachedComponentService, ComponentService, RemoteComponentService};
 use crate::service::gateway::api_definition::{
-    ApiDefinitionService, ApiDefinitionServiceConfig, ApiDefinitionServiceDefault,
+    load_compiled_rib_cache, ApiDefinitionService, ApiDefinitionServiceConfig,
+    ApiDefinitionServiceDefault,
 };
 use crate::service::gateway::api_definition_validator::ApiDefinitionValidatorService;
 use crate::service::gateway::api_deployment::{ApiDeploymentService, ApiDeploymentServiceDefault};
@@ -78,6 +80,7 @@ use golem_service_base::db::sqlite::SqlitePool;
 use golem_service_base::service::initial_component_files::InitialComponentFilesService;
 use golem_service_base::service::routing_table::{RoutingTableService, RoutingTableServiceDefault};
 use golem_service_base::storage::blob::BlobStorage;
+use rib::RibExecutionLimits;
 use std::sync::Arc;
 use std::time::Duration;
 use tonic::codec::CompressionEncoding;
@@ -101,6 +104,7 @@ pub struct Services {
     pub http_handler_binding_handler: Arc<dyn HttpHandlerBindingHandler>,
     pub security_scheme_service: Arc<dyn SecuritySchemeService>,
     pub gateway_session_store: Arc<dyn GatewaySession>,
+    pub rib_execution_limits: RibExecutionLimits,
 }
 
 impl Services {
@@ -119,6 +123,7 @@ impl Services {
             api_certificate_repo,
             api_domain_repo,
             security_scheme_repo,
+            compiled_rib_repo,
         ) = match config.db.clone() {
             DbConfig::Postgres(config) => {
                 let db_pool = PostgresPool::configured(&config)
@@ -134,12 +139,15 @@ impl Services {
                     Arc::new(DbApiDomainRepo::new(db_pool.clone()));
                 let security_scheme_repo: Arc<dyn SecuritySchemeRepo> =
                     Arc::new(DbSecuritySchemeRepo::new(db_pool.clone()));
+                let compiled_rib_repo: Arc<dyn CompiledRibRepo> =
+                    Arc::new(DbCompiledRibRepo::new(db_pool.clone()));
                 (
                     api_definition_repo,
                     api_deployment_repo,
                     api_certificate_repo,
                     api_domain_repo,
                     security_scheme_repo,
+                    compiled_rib_repo,
                 )
             }
             DbConfig::Sqlite(config) => {
@@ -156,6 +164,8 @@ impl Services {
                     Arc::new(DbApiDomainRepo::new(db_pool.clone()));
                 let security_scheme_repo: Arc<dyn SecuritySchemeRepo> =
                     Arc::new(DbSecuritySchemeRepo::new(db_pool.clone()));
+                let compiled_rib_repo: Arc<dyn CompiledRibRepo> =
+                    Arc::new(DbCompiledRibRepo::new(db_pool.clone()));
 
                 (
                     api_definition_repo,
@@ -163,6 +173,7 @@ impl Services {
                     api_certificate_repo,
                     api_domain_repo,
                     security_scheme_repo,
+                    compiled_rib_repo,
                 )
             }
         };
@@ -245,6 +256,10 @@ impl Services {
             base_security_scheme_service.clone(),
         ));
 
+        let compiled_rib_cache = load_compiled_rib_cache(compiled_rib_repo.as_ref())
+            .await
+            .map_err(|e| format!("Init error (compiled Rib): {e}"))?;
+
         let definition_service: Arc<dyn ApiDefinitionService> =
             Arc::new(ApiDefinitionServiceDefault::new(
                 component_service.clone(),
@@ -252,6 +267,8 @@ impl Services {
                 api_deployment_repo.clone(),
                 base_security_scheme_service.clone(),
                 api_definition_validator,
+                compiled_rib_repo,
+                Arc::new(compiled_rib_cache),
                 ApiDefinitionServiceConfig::default(),
             ));
 
@@ -411,6 +428,7 @@ impl Services {
             http_handler_binding_handler,
             security_scheme_service,
             gateway_session_store,
+            rib_execution_limits: config.rib_execution_limits.to_rib_execution_limits(),
         })
     }
 }
@@ -30,6 +30,7 @@ use golem_worker_service::gateway_execution::gateway_session::{
 };
 use golem_worker_service::gateway_middleware::HttpCors;
 use golem_worker_service::gateway_request::http_request::ApiInputPath;
+use golem_worker_service::gateway_rib_compiler::CompiledRibCache;
 use golem_worker_service::gateway_security::{Provider, SecurityScheme, SecuritySchemeIdentifier};
 use golem_worker_service::service::gateway::api_definition_validator::ValidationErrors;
 use golem_worker_service::service::gateway::{ComponentView, ConversionContext};
@@ -38,7 +39,7 @@ use http::header::{HOST, LOCATION, ORIGIN};
 use http::{HeaderMap, HeaderValue, Method, StatusCode, Uri};
 use openidconnect::{ClientId, ClientSecret, RedirectUrl, Scope};
 use poem::{Request, Response};
-use rib::RibCompilationError;
+use rib::{RibCompilationError, RibExecutionLimits};
 use serde_json::{Number, Value as JsonValue};
 use std::str::FromStr;
 use std::sync::Arc;
@@ -71,6 +72,7 @@ async fn execute(
         &internal::get_component_metadata(),
         &test_namespace(),
         &(Box::new(TestConversionContext) as Box<dyn ConversionContext>),
+        &CompiledRibCache::default(),
     )
     .expect("Failed to compile API definition");
 
@@ -83,6 +85,7 @@ async fn execute(
         Arc::new(internal::TestApiDefinitionLookup::new(compiled)),
         Arc::clone(session_store),
         Arc::new(test_identity_provider.clone()),
+        RibExecutionLimits::default(),
     );
 
     test_executor.execute_http_request(api_request).await
@@ -153,6 +156,7 @@ async fn test_api_def_with_agent() {
         &internal::get_agent_component_metadata(),
         &test_namespace(),
         &(Box::new(TestConversionContext) as Box<dyn ConversionContext>),
+        &CompiledRibCache::default(),
     );
 
     assert!(result.is_ok());
@@ -223,6 +227,7 @@ async fn gen_agent_type_error_tests(r: &mut DynamicTestRegistration) {
             &internal::get_agent_component_metadata(),
             &test_namespace(),
             &(Box::new(TestConversionContext) as Box<dyn ConversionContext>),
+            &CompiledRibCache::default(),
         );
 
         assert2::assert!(let Err(RouteCompilationErrors::RibError(RibCompilationError::RibTypeError(_))) = result);
@@ -285,6 +290,88 @@ async fn test_api_def_with_resource_1() {
     assert_eq!(message, "USD");
 }
 
+#[test]
+async fn test_api_defs_with_different_rib_execution_limits() {
+    let response_mapping = r#"
+       let worker-instance = instance("shopping-cart");
+       let first = worker-instance.get-cart-contents("a", "b");
+       let response = worker-instance.get-cart-contents("c", "d");
+      response
+    "#;
+
+    // Allows the two worker calls of the script
+    let own_limits = get_api_def_with_rib_execution_limits(
+        "own-limits-api",
+        "/own-limits",
+        response_mapping,
+        "maxWorkerCalls: 2",
+    )
+    .await;
+
+    // Sets a different limit only, so the worker calls are limited by the default
+    let default_limits = get_api_def_with_rib_execution_limits(
+        "default-limits-api",
+        "/default-limits",
+        response_mapping,
+        "maxInstructions: 1000000",
+    )
+    .await;
+
+    let compiled = [own_limits, default_limits]
+        .iter()
+        .map(|api_specification| {
+            CompiledHttpApiDefinition::from_http_api_definition(
+                api_specification,
+                &internal::get_component_metadata(),
+                &test_namespace(),
+                &(Box::new(TestConversionContext) as Box<dyn ConversionContext>),
+                &CompiledRibCache::default(),
+            )
+            .expect("Failed to compile API definition")
+        })
+        .collect::<Vec<_>>();
+
+    let session_store: Arc<dyn GatewaySession + Sync + Send> = internal::get_session_store();
+
+    let test_executor = DefaultGatewayInputExecutor::new(
+        internal::get_test_rib_interpreter(),
+        internal::get_test_file_server_binding_handler(),
+        Arc::new(DefaultAuthCallBack),
+        internal::get_test_http_handler_binding_handler(),
+        internal::get_test_swagger_binding_handler(),
+        Arc::new(internal::TestApiDefinitionLookup::with_definitions(
+            compiled,
+        )),
+        Arc::clone(&session_store),
+        Arc::new(TestIdentityProvider::default()),
+        RibExecutionLimits::default().with_max_worker_calls(1),
+    );
+
+    let own_limits_response = test_executor
+        .execute_http_request(get_gateway_request(
+            "/own-limits",
+            None,
+            &HeaderMap::new(),
+            JsonValue::Null,
+        ))
+        .await;
+
+    let default_limits_response = test_executor
+        .execute_http_request(get_gateway_request(
+            "/default-limits",
+            None,
+            &HeaderMap::new(),
+            JsonValue::Null,
+        ))
+        .await;
+
+    assert_eq!(own_limits_response.status(), StatusCode::OK);
+    assert_eq!(
+        default_limits_response.status(),
+        StatusCode::TOO_MANY_REQUESTS
+    );
+}
+
 #[test]
 async fn test_api_def_with_single_query_param() {
     let api_request =
@@ -436,6 +523,7 @@ async fn test_api_def_with_invalid_path_lookup() {
         &internal::get_component_metadata(),
         &test_namespace(),
         &(Box::new(TestConversionContext) as Box<dyn ConversionContext>),
+        &CompiledRibCache::default(),
     )
     .unwrap_err();
 
@@ -467,6 +555,7 @@ async fn test_api_def_with_invalid_query_and_path_lookup() {
         &internal::get_component_metadata(),
         &test_namespace(),
         &(Box::new(TestConversionContext) as Box<dyn ConversionContext>),
+        &CompiledRibCache::default(),
     )
     .unwrap_err();
 
@@ -489,6 +578,7 @@ async fn test_api_def_with_invalid_query_and_path_lookup() {
         &internal::get_component_metadata(),
         &test_namespace(),
         &(Box::new(TestConversionContext) as Box<dyn ConversionContext>),
+        &CompiledRibCache::default(),
     )
     .unwrap_err();
 
@@ -561,6 +651,7 @@ async fn test_api_def_with_invalid_query_lookup() {
         &internal::get_component_metadata(),
         &test_namespace(),
         &(Box::new(TestConversionContext) as Box<dyn ConversionContext>),
+        &CompiledRibCache::default(),
     )
     .unwrap_err();
 
@@ -1130,6 +1221,121 @@ async fn test_api_def_with_security() {
     assert_eq!(result, expected);
 }
 
+// Transformations of a secured route are applied after authentication, so they can
+// remove the session cookie from the request the binding sees, and a non-UUID request id
+// of the client is echoed back unchanged
+#[test]
+async fn test_api_def_with_security_and_transformation() {
+    let empty_headers = HeaderMap::new();
+    let api_request = get_gateway_request("/foo/1", None, &empty_headers, serde_json::Value::Null);
+
+    let response_mapping = r#"
+      let id: u64 = request.path.user-id;
+      let worker = instance("shopping-cart-${id}");
+      let response = worker.get-cart-contents("a", "b");
+      let email: string = request.auth.email;
+      { body: response, headers: {email: email} }
+    "#;
+
+    let identity_provider = TestIdentityProvider::get_provider_with_valid_id_token();
+
+    let auth_call_back_url =
+        RedirectUrl::new("http://localhost/auth/callback".to_string()).unwrap();
+
+    let api_specification: HttpApiDefinition = get_api_def_with_security_and_transformation(
+        "/foo/{user-id}",
+        response_mapping,
+        &auth_call_back_url,
+        &identity_provider,
+        r#"
+            transformation:
+              requestIdHeader: x-request-id
+              requestHeaders:
+                remove:
+                - cookie
+        "#,
+    )
+    .await;
+
+    let session_store = internal::get_session_store();
+
+    let initial_redirect_response = execute(
+        api_request,
+        &api_specification,
+        &session_store,
+        &identity_provider,
+    )
+    .await;
+
+    let location = initial_redirect_response
+        .headers()
+        .get(LOCATION)
+        .expect("Expecting location")
+        .to_str()
+        .expect("Location should be a string");
+
+    let url = Url::parse(location).expect("Expect the initial redirection to be a full URL");
+
+    let query_components = ApiInputPath::query_components_from_str(url.query().unwrap_or_default());
+
+    let initial_redirect_data = security::get_initial_redirect_data(&query_components);
+
+    let call_back_request_from_identity_provider =
+        security::request_from_identity_provider_to_auth_call_back_endpoint(
+            initial_redirect_data.state.as_str(),
+            "foo_code", // Decided by IdentityProvider
+            initial_redirect_data.scope.as_str(),
+            &auth_call_back_url.to_string(),
+            "localhost",
+        );
+
+    let final_redirect_response = execute(
+        call_back_request_from_identity_provider,
+        &api_specification,
+        &session_store,
+        &identity_provider,
+    )
+    .await;
+
+    let mut api_request =
+        security::create_request_from_redirect(final_redirect_response.headers()).await;
+    api_request
+        .headers_mut()
+        .insert("x-request-id", HeaderValue::from_static("client-request-1"));
+
+    let response = execute(
+        api_request,
+        &api_specification,
+        &session_store,
+        &identity_provider,
+    )
+    .await;
+
+    assert_eq!(
+        response.headers().get("x-request-id"),
+        Some(&HeaderValue::from_static("client-request-1"))
+    );
+
+    let test_response = internal::get_details_from_response(response).await;
+
+    let result = (
+        test_response.function_name,
+        test_response.function_params,
+        test_response.user_email,
+    );
+
+    let expected = (
+        "golem:it/api.{get-cart-contents}".to_string(),
+        JsonValue::Array(vec![
+            JsonValue::String("a".to_string()),
+            JsonValue::String("b".to_string()),
+        ]),
+        Some("bob@example.com".to_string()),
+    );
+
+    assert_eq!(result, expected);
+}
+
 // A test where security is enabled but the signatures don't match
 #[test]
 async fn test_api_def_with_security_with_invalid_signatures() {
@@ -2297,12 +2503,76 @@ async fn get_api_def_with_worker_binding(
     .unwrap()
 }
 
+async fn get_api_def_with_rib_execution_limits(
+    id: &str,
+    path_pattern: &str,
+    rib_expression: &str,
+    rib_execution_limits: &str,
+) -> HttpApiDefinition {
+    let yaml_string = format!(
+        r#"
+          id: {id}
+          version: 0.0.1
+          createdAt: 2024-08-21T07:42:15.696Z
+          ribExecutionLimits:
+            {rib_execution_limits}
+          routes:
+          - method: Get
+            path: {path_pattern}
+            binding:
+              type: wit-worker
+              component:
+                name: test-component
+                version: 0
+              response: '${{{rib_expression}}}'
+
+        "#
+    );
+
+    let http_api_definition_request: api::dto::HttpApiDefinitionRequest =
+        serde_yaml::from_str(yaml_string.as_str()).unwrap();
+
+    let core_request: gateway_api_definition::http::HttpApiDefinitionRequest =
+        http_api_definition_request
+            .into_core(&TestConversionContext.boxed())
+            .await
+            .unwrap();
+
+    let create_at: DateTime<Utc> = "2024-08-21T07:42:15.696Z".parse().unwrap();
+
+    HttpApiDefinition::from_http_api_definition_request(
+        &test_namespace(),
+        core_request,
+        create_at,
+        &security::get_test_security_scheme_service(TestIdentityProvider::default()),
+    )
+    .await
+    .unwrap()
+}
+
 // https://swagger.io/docs/specification/v3_0/authentication/openid-connect-discovery/
 async fn get_api_def_with_security(
     path_pattern: &str,
     rib_expression: &str,
     auth_call_back_url: &RedirectUrl,
     test_identity_provider: &TestIdentityProvider,
+) -> HttpApiDefinition {
+    get_api_def_with_security_and_transformation(
+        path_pattern,
+        rib_expression,
+        auth_call_back_url,
+        test_identity_provider,
+        "",
+    )
+    .await
+}
+
+async fn get_api_def_with_security_and_transformation(
+    path_pattern: &str,
+    rib_expression: &str,
+    auth_call_back_url: &RedirectUrl,
+    test_identity_provider: &TestIdentityProvider,
+    route_transformation: &str,
 ) -> HttpApiDefinition {
     let security_scheme_identifier = SecuritySchemeIdentifier::new("openId1".to_string());
 
@@ -2345,6 +2615,7 @@ async fn get_api_def_with_security(
                 name: test-component
                 version: 0
               response: '${{{rib_expression}}}'
+{route_transformation}
         "#
     );
 
@@ -2631,12 +2902,16 @@ mod internal {
     use uuid::Uuid;
 
     pub struct TestApiDefinitionLookup {
-        pub api_definition: CompiledHttpApiDefinition,
+        pub api_definitions: Vec<CompiledHttpApiDefinition>,
     }
 
     impl TestApiDefinitionLookup {
         pub fn new(api_definition: CompiledHttpApiDefinition) -> Self {
-            Self { api_definition }
+            Self::with_definitions(vec![api_definition])
+        }
+
+        pub fn with_definitions(api_definitions: Vec<CompiledHttpApiDefinition>) -> Self {
+            Self { api_definitions }
         }
     }
 
@@ -2646,7 +2921,7 @@ mod internal {
             &self,
             _input: &ApiSiteString,
         ) -> Result<Vec<CompiledHttpApiDefinition>, ApiDefinitionLookupError> {
-            Ok(vec![self.api_definition.clone()])
+            Ok(self.api_definitions.clone())
         }
     }
 
@@ -4096,3 +4371,81 @@ async fn test_api_def_with_request_id() {
 
     assert!(uuid::Uuid::from_str(&response_body).is_ok());
 }
+
+#[test]
+async fn test_api_def_with_sse_response_stream() {
+    let response_mapping = r#"
+       let worker = instance("shopping-cart");
+       let response = worker.get-cart-contents("a", "b");
+       response
+    "#;
+
+    let api_specification: HttpApiDefinition =
+        get_api_def_with_sse_worker_binding("/foo", response_mapping).await;
+
+    let session_store: Arc<dyn GatewaySession + Send + Sync> = internal::get_session_store();
+
+    let api_request = get_gateway_request("/foo", None, &HeaderMap::new(), JsonValue::Null);
+
+    let mut response = execute(
+        api_request,
+        &api_specification,
+        &session_store,
+        &TestIdentityProvider::default(),
+    )
+    .await;
+
+    assert_eq!(
+        response.headers().get(http::header::CONTENT_TYPE),
+        Some(&HeaderValue::from_static("text/event-stream"))
+    );
+
+    let response_body = response.take_body().into_string().await.unwrap();
+
+    assert!(response_body.starts_with("event: result\ndata: {"));
+    assert!(response_body.contains("golem:it/api.{get-cart-contents}"));
+    assert!(response_body.ends_with("}\n\n"));
+}
+
+async fn get_api_def_with_sse_worker_binding(
+    path_pattern: &str,
+    rib_expression: &str,
+) -> HttpApiDefinition {
+    let yaml_string = format!(
+        r#"
+          id: users-api
+          version: 0.0.1
+          createdAt: 2024-08-21T07:42:15.696Z
+          routes:
+          - method: Get
+            path: {path_pattern}
+            binding:
+              component:
+                name: test-component
+                version: 0
+              responseStream: sse
+              response: '${{{rib_expression}}}'
+
+        "#
+    );
+
+    let http_api_definition_request: api::dto::HttpApiDefinitionRequest =
+        serde_yaml::from_str(yaml_string.as_str()).unwrap();
+
+    let core_request: gateway_api_definition::http::HttpApiDefinitionRequest =
+        http_api_definition_request
+            .into_core(&TestConversionContext.boxed())
+            .await
+            .unwrap();
+
+    let create_at: DateTime<Utc> = "2024-08-21T07:42:15.696Z".parse().unwrap();
+
+    HttpApiDefinition::from_http_api_definition_request(
+        &test_namespace(),
+        core_request,
+        create_at,
+        &security::get_test_security_scheme_service(TestIdentityProvider::default()),
+    )
+    .await
+    .unwrap()
+}
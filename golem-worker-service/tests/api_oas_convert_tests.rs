@@ -137,6 +137,7 @@ async fn test_simple_conversion() {
         draft: false,
         created_at: Utc::now(),
         namespace: test_namespace(),
+        rib_execution_limits: None,
     };
 
     // Create dummy conversion context
@@ -218,6 +219,7 @@ async fn test_cors_preflight_response_formatting() {
         draft: false,
         created_at: Utc::now(),
         namespace: test_namespace(),
+        rib_execution_limits: None,
     };
 
     // Create dummy conversion context
@@ -333,6 +335,7 @@ async fn test_other_binding_types() {
         draft: false,
         created_at: Utc::now(),
         namespace: test_namespace(),
+        rib_execution_limits: None,
     };
 
     // Create dummy conversion context
@@ -445,6 +448,8 @@ async fn test_security_conversion() {
                 rib_output: None,
             },
             invocation_context_compiled: None,
+            response_stream: None,
+            ingress_protocol: None,
         }
     };
 
@@ -529,6 +534,7 @@ async fn test_security_conversion() {
         draft: false,
         created_at: Utc::now(),
         namespace: test_namespace(),
+        rib_execution_limits: None,
     };
 
     // Create dummy conversion context
@@ -655,6 +661,8 @@ async fn test_multi_component_binding() {
         idempotency_key_compiled: None,
         response_compiled: response_compiled_shopping_cart,
         invocation_context_compiled: None,
+        response_stream: None,
+        ingress_protocol: None,
     };
     let route_shopping_cart = CompiledRoute {
         method: MethodPattern::Post,
@@ -733,6 +741,8 @@ async fn test_multi_component_binding() {
         idempotency_key_compiled: None,
         response_compiled: response_compiled_delay_echo,
         invocation_context_compiled: None,
+        response_stream: None,
+        ingress_protocol: None,
     };
     let route_delay_echo = CompiledRoute {
         method: MethodPattern::Post,
@@ -749,6 +759,7 @@ async fn test_multi_component_binding() {
         draft: false,
         created_at: Utc::now(),
         namespace: test_namespace(),
+        rib_execution_limits: None,
     };
 
     // Create dummy conversion context
@@ -915,6 +926,8 @@ async fn test_basic_types_and_record_conversion() {
             rib_output: Some(create_output(AnalysedType::U64(TypeU64))),
         },
         invocation_context_compiled: None,
+        response_stream: None,
+        ingress_protocol: None,
     };
 
     routes.push(CompiledRoute {
@@ -939,6 +952,8 @@ async fn test_basic_types_and_record_conversion() {
             rib_output: Some(create_output(AnalysedType::Bool(TypeBool))),
         },
         invocation_context_compiled: None,
+        response_stream: None,
+        ingress_protocol: None,
     };
 
     routes.push(CompiledRoute {
@@ -982,6 +997,8 @@ async fn test_basic_types_and_record_conversion() {
             rib_output: Some(create_output(record_type)),
         },
         invocation_context_compiled: None,
+        response_stream: None,
+        ingress_protocol: None,
     };
 
     routes.push(CompiledRoute {
@@ -1012,6 +1029,8 @@ async fn test_basic_types_and_record_conversion() {
             rib_output: Some(create_output(enum_type)),
         },
         invocation_context_compiled: None,
+        response_stream: None,
+        ingress_protocol: None,
     };
 
     routes.push(CompiledRoute {
@@ -1029,6 +1048,7 @@ async fn test_basic_types_and_record_conversion() {
         draft: false,
         created_at: Utc::now(),
         namespace: test_namespace(),
+        rib_execution_limits: None,
     };
 
     // Create dummy conversion context
@@ -1273,6 +1293,8 @@ async fn test_complete_todo_structure_with_optional_and_oneof() {
             rib_output: Some(response_output),
         },
         invocation_context_compiled: None,
+        response_stream: None,
+        ingress_protocol: None,
     };
 
     // Create route
@@ -1291,6 +1313,7 @@ async fn test_complete_todo_structure_with_optional_and_oneof() {
         draft: false,
         created_at: Utc::now(),
         namespace: test_namespace(),
+        rib_execution_limits: None,
     };
 
     // Create dummy conversion context
@@ -1439,6 +1462,8 @@ async fn test_variant_output_structure() {
             rib_output: Some(response_output),
         },
         invocation_context_compiled: None,
+        response_stream: None,
+        ingress_protocol: None,
     };
 
     // Create route
@@ -1457,6 +1482,7 @@ async fn test_variant_output_structure() {
         draft: false,
         created_at: Utc::now(),
         namespace: test_namespace(),
+        rib_execution_limits: None,
     };
 
     // Create dummy conversion context
@@ -1624,7 +1650,9 @@ async fn test_oas_conversion_full_structure_shopping_cart() {
             worker_calls: None,
             rib_output: Some(response_output)
         },
-        invocation_context_compiled: None
+        invocation_context_compiled: None,
+        response_stream: None,
+        ingress_protocol: None,
     };
 
     let worker_route = CompiledRoute {
@@ -1661,6 +1689,7 @@ async fn test_oas_conversion_full_structure_shopping_cart() {
         draft: false,
         created_at: Utc::now(),
         namespace: test_namespace(),
+        rib_execution_limits: None,
     };
 
     // Create dummy conversion context
@@ -1915,6 +1944,8 @@ async fn test_path_query_header_parameter_combinations() {
                 }),
             },
             invocation_context_compiled: None,
+            response_stream: None,
+            ingress_protocol: None,
         })),
         middlewares: None,
     };
@@ -1956,6 +1987,8 @@ async fn test_path_query_header_parameter_combinations() {
                 }),
             },
             invocation_context_compiled: None,
+            response_stream: None,
+            ingress_protocol: None,
         })),
         middlewares: None,
     };
@@ -1969,6 +2002,7 @@ async fn test_path_query_header_parameter_combinations() {
         draft: false,
         created_at: Utc::now(),
         namespace: test_namespace(),
+        rib_execution_limits: None,
     };
 
     // Create dummy conversion context
@@ -2191,6 +2225,8 @@ async fn test_comprehensive_analysed_type_coverage() {
                 }),
             },
             invocation_context_compiled: None,
+            response_stream: None,
+            ingress_protocol: None,
         }
     };
 
@@ -2598,6 +2634,7 @@ async fn test_comprehensive_analysed_type_coverage() {
         draft: false,
         created_at: Utc::now(),
         namespace: test_namespace(),
+        rib_execution_limits: None,
     };
 
     // Create dummy conversion context
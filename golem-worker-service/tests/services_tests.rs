@@ -40,6 +40,7 @@ use golem_worker_service::gateway_execution::gateway_session::{
     DataKey, DataValue, GatewaySession, GatewaySessionError, RedisGatewaySession,
     RedisGatewaySessionExpiration, SessionId, SqliteGatewaySession, SqliteGatewaySessionExpiration,
 };
+use golem_worker_service::gateway_rib_compiler::CompiledRibCache;
 use golem_worker_service::gateway_security::{
     AuthorizationUrl, DefaultIdentityProvider, GolemIdentityProviderMetadata, IdentityProvider,
     IdentityProviderError, OpenIdClient, Provider, SecurityScheme, SecuritySchemeIdentifier,
@@ -47,6 +48,7 @@ use golem_worker_service::gateway_security::{
 use golem_worker_service::model::{ApiDomain, Certificate, CertificateRequest, DomainRequest};
 use golem_worker_service::repo::api_certificate::{ApiCertificateRepo, DbApiCertificateRepo};
 use golem_worker_service::repo::api_domain::{ApiDomainRepo, DbApiDomainRepo};
+use golem_worker_service::repo::compiled_rib::{CompiledRibRepo, DbCompiledRibRepo};
 use golem_worker_service::repo::security_scheme::{DbSecuritySchemeRepo, SecuritySchemeRepo};
 use golem_worker_service::repo::{api_definition, api_deployment};
 use golem_worker_service::service::api_certificate::{
@@ -629,12 +631,16 @@ pub async fn test_with_postgres_db() {
 
     let api_domain_repo: Arc<dyn ApiDomainRepo> = Arc::new(DbApiDomainRepo::new(db_pool.clone()));
 
+    let compiled_rib_repo: Arc<dyn CompiledRibRepo> =
+        Arc::new(DbCompiledRibRepo::new(db_pool.clone()));
+
     test_services(
         api_definition_repo,
         api_deployment_repo,
         security_scheme_repo,
         api_certificate_repo,
         api_domain_repo,
+        compiled_rib_repo,
     )
     .await;
 }
@@ -817,12 +823,16 @@ pub async fn test_with_sqlite_db() {
 
     let api_domain_repo: Arc<dyn ApiDomainRepo> = Arc::new(DbApiDomainRepo::new(db_pool.clone()));
 
+    let compiled_rib_repo: Arc<dyn CompiledRibRepo> =
+        Arc::new(DbCompiledRibRepo::new(db_pool.clone()));
+
     test_services(
         api_definition_repo,
         api_deployment_repo,
         security_scheme_repo,
         api_certificate_repo,
         api_domain_repo,
+        compiled_rib_repo,
     )
     .await;
 }
@@ -833,6 +843,7 @@ async fn test_services(
     security_scheme_repo: Arc<dyn SecuritySchemeRepo>,
     api_certificate_repo: Arc<dyn ApiCertificateRepo>,
     api_domain_repo: Arc<dyn ApiDomainRepo>,
+    compiled_rib_repo: Arc<dyn CompiledRibRepo>,
 ) {
     let component_service: Arc<dyn ComponentService> = Arc::new(TestComponentService {});
 
@@ -851,6 +862,8 @@ async fn test_services(
             api_deployment_repo.clone(),
             security_scheme_service.clone(),
             api_definition_validator_service.clone(),
+            compiled_rib_repo.clone(),
+            Arc::new(CompiledRibCache::default()),
             ApiDefinitionServiceConfig::default(),
         ));
 
@@ -1360,6 +1373,7 @@ fn get_api_deployment(
             host: host.to_string(),
             subdomain: subdomain.map(|s| s.to_string()),
         },
+        traffic_splits: HashMap::new(),
     }
 }
 
@@ -67,6 +67,22 @@ async fn main() {
             >(mode, verbosity, item, primary_only, otlp))
         }),
     );
+    benchmarks_by_name.insert(
+        "instantiation-on-demand",
+        Box::new(|mode, verbosity, item, primary_only, otlp| {
+            Box::pin(run_benchmark::<
+                integration_tests::benchmarks::instantiation::InstantiationOnDemand,
+            >(mode, verbosity, item, primary_only, otlp))
+        }),
+    );
+    benchmarks_by_name.insert(
+        "instantiation-pooling",
+        Box::new(|mode, verbosity, item, primary_only, otlp| {
+            Box::pin(run_benchmark::<
+                integration_tests::benchmarks::instantiation::InstantiationPooling,
+            >(mode, verbosity, item, primary_only, otlp))
+        }),
+    );
     benchmarks_by_name.insert(
         "latency-small",
         Box::new(|mode, verbosity, item, primary_only, otlp| {
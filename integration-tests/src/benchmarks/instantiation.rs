@@ -0,0 +1,287 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::benchmarks::{delete_workers, invoke_and_await, InvokeResult};
+use async_trait::async_trait;
+use futures_concurrency::future::Join;
+use golem_common::model::{ComponentId, WorkerId};
+use golem_test_framework::benchmark::{Benchmark, BenchmarkRecorder, RunConfig};
+use golem_test_framework::config::benchmark::TestMode;
+use golem_test_framework::config::{BenchmarkTestDependencies, TestDependencies};
+use golem_test_framework::dsl::TestDsl;
+use golem_wasm::IntoValueAndType;
+use indoc::indoc;
+use tracing::{info, Level};
+
+const FUNCTION_NAME: &str = "benchmark:direct-rust-exports/benchmark-direct-rust-api.{echo}";
+
+pub struct InstantiationOnDemand {
+    config: RunConfig,
+}
+
+pub struct InstantiationPooling {
+    config: RunConfig,
+}
+
+#[async_trait]
+impl Benchmark for InstantiationOnDemand {
+    type BenchmarkContext = InstantiationBenchmark;
+    type IterationContext = IterationContext;
+
+    fn name() -> &'static str {
+        "instantiation-on-demand"
+    }
+
+    fn description() -> &'static str {
+        indoc! {
+            "Creates `size` new workers in parallel, invokes them once and deletes them, repeated `length` times.
+            The worker executors use the default on-demand instance allocator. Compare with `instantiation-pooling`.
+            "
+        }
+    }
+
+    async fn create_benchmark_context(
+        mode: &TestMode,
+        verbosity: Level,
+        cluster_size: usize,
+        disable_compilation_cache: bool,
+        otlp: bool,
+    ) -> Self::BenchmarkContext {
+        InstantiationBenchmark::new(
+            mode,
+            verbosity,
+            cluster_size,
+            disable_compilation_cache,
+            false,
+            otlp,
+        )
+        .await
+    }
+
+    async fn cleanup(benchmark_context: Self::BenchmarkContext) {
+        benchmark_context.cleanup().await
+    }
+
+    async fn create(_mode: &TestMode, config: RunConfig) -> Self {
+        Self { config }
+    }
+
+    async fn setup_iteration(
+        &self,
+        benchmark_context: &Self::BenchmarkContext,
+    ) -> Self::IterationContext {
+        benchmark_context.setup_iteration().await
+    }
+
+    async fn warmup(
+        &self,
+        benchmark_context: &Self::BenchmarkContext,
+        context: &Self::IterationContext,
+    ) {
+        benchmark_context.warmup(context).await
+    }
+
+    async fn run(
+        &self,
+        benchmark_context: &Self::BenchmarkContext,
+        context: &Self::IterationContext,
+        recorder: BenchmarkRecorder,
+    ) {
+        benchmark_context.run(&self.config, context, recorder).await
+    }
+
+    async fn cleanup_iteration(
+        &self,
+        _benchmark_context: &Self::BenchmarkContext,
+        _context: Self::IterationContext,
+    ) {
+    }
+}
+
+#[async_trait]
+impl Benchmark for InstantiationPooling {
+    type BenchmarkContext = InstantiationBenchmark;
+    type IterationContext = IterationContext;
+
+    fn name() -> &'static str {
+        "instantiation-pooling"
+    }
+
+    fn description() -> &'static str {
+        indoc! {
+            "Creates `size` new workers in parallel, invokes them once and deletes them, repeated `length` times.
+            The worker executors use the pooling instance allocator with copy-on-write memory initialization.
+            Provided worker executors are used with their own configuration. Compare with `instantiation-on-demand`.
+            "
+        }
+    }
+
+    async fn create_benchmark_context(
+        mode: &TestMode,
+        verbosity: Level,
+        cluster_size: usize,
+        disable_compilation_cache: bool,
+        otlp: bool,
+    ) -> Self::BenchmarkContext {
+        InstantiationBenchmark::new(
+            mode,
+            verbosity,
+            cluster_size,
+            disable_compilation_cache,
+            true,
+            otlp,
+        )
+        .await
+    }
+
+    async fn cleanup(benchmark_context: Self::BenchmarkContext) {
+        benchmark_context.cleanup().await
+    }
+
+    async fn create(_mode: &TestMode, config: RunConfig) -> Self {
+        Self { config }
+    }
+
+    async fn setup_iteration(
+        &self,
+        benchmark_context: &Self::BenchmarkContext,
+    ) -> Self::IterationContext {
+        benchmark_context.setup_iteration().await
+    }
+
+    async fn warmup(
+        &self,
+        benchmark_context: &Self::BenchmarkContext,
+        context: &Self::IterationContext,
+    ) {
+        benchmark_context.warmup(context).await
+    }
+
+    async fn run(
+        &self,
+        benchmark_context: &Self::BenchmarkContext,
+        context: &Self::IterationContext,
+        recorder: BenchmarkRecorder,
+    ) {
+        benchmark_context.run(&self.config, context, recorder).await
+    }
+
+    async fn cleanup_iteration(
+        &self,
+        _benchmark_context: &Self::BenchmarkContext,
+        _context: Self::IterationContext,
+    ) {
+    }
+}
+
+pub struct IterationContext {
+    component_id: ComponentId,
+}
+
+pub struct InstantiationBenchmark {
+    deps: BenchmarkTestDependencies,
+}
+
+impl InstantiationBenchmark {
+    pub async fn new(
+        mode: &TestMode,
+        verbosity: Level,
+        cluster_size: usize,
+        disable_compilation_cache: bool,
+        pooling_allocator: bool,
+        otlp: bool,
+    ) -> Self {
+        Self {
+            deps: BenchmarkTestDependencies::new_with_pooling_allocator(
+                mode,
+                verbosity,
+                cluster_size,
+                disable_compilation_cache,
+                pooling_allocator,
+                otlp,
+            )
+            .await,
+        }
+    }
+
+    pub async fn cleanup(&self) {
+        self.deps.kill_all().await
+    }
+
+    pub async fn setup_iteration(&self) -> IterationContext {
+        info!("Registering component");
+        let component_id = self
+            .deps
+            .admin()
+            .await
+            .component("benchmark_direct_rust")
+            .name("benchmark:direct-rust")
+            .store()
+            .await;
+
+        IterationContext { component_id }
+    }
+
+    pub async fn warmup(&self, context: &IterationContext) {
+        // Making sure the component is compiled and cached before measuring the instantiations
+        let worker_ids = Self::worker_ids(context, "warmup", 1);
+        let _ = self.invoke_all(&worker_ids).await;
+        delete_workers(&self.deps, &worker_ids).await;
+    }
+
+    pub async fn run(
+        &self,
+        config: &RunConfig,
+        context: &IterationContext,
+        recorder: BenchmarkRecorder,
+    ) {
+        for round in 0..config.length {
+            let worker_ids = Self::worker_ids(context, &round.to_string(), config.size);
+
+            let results = self.invoke_all(&worker_ids).await;
+            for (idx, result) in results.iter().enumerate() {
+                result.record(&recorder, "", &format!("{round}-{idx}"));
+            }
+
+            delete_workers(&self.deps, &worker_ids).await;
+        }
+    }
+
+    async fn invoke_all(&self, worker_ids: &[WorkerId]) -> Vec<InvokeResult> {
+        let result_futures = worker_ids
+            .iter()
+            .map(move |worker_id| async move {
+                let deps_clone = self.deps.clone();
+                invoke_and_await(
+                    &deps_clone,
+                    worker_id,
+                    FUNCTION_NAME,
+                    vec!["benchmark".into_value_and_type()],
+                )
+                .await
+            })
+            .collect::<Vec<_>>();
+
+        result_futures.join().await
+    }
+
+    fn worker_ids(context: &IterationContext, prefix: &str, count: usize) -> Vec<WorkerId> {
+        (0..count)
+            .map(|n| WorkerId {
+                component_id: context.component_id.clone(),
+                worker_name: format!("benchmark-agent(\"test-{prefix}-{n}\")"),
+            })
+            .collect()
+    }
+}
@@ -25,6 +25,7 @@ use tracing::{info, warn};
 
 pub mod cold_start_unknown;
 pub mod durability_overhead;
+pub mod instantiation;
 pub mod latency;
 pub mod sleep;
 pub mod throughput;
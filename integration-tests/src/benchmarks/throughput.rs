@@ -626,8 +626,11 @@ impl ThroughputBenchmark {
                         idempotency_key: None,
                         binding_type: Some(GatewayBindingType::Default),
                         invocation_context: None,
+                        response_stream: None,
+                        ingress_protocol: None,
                     },
                     security: None,
+                    transformation: None,
                 },
                 RouteRequestData {
                     method: MethodPattern::Post,
@@ -649,8 +652,11 @@ impl ThroughputBenchmark {
                         idempotency_key: None,
                         binding_type: Some(GatewayBindingType::Default),
                         invocation_context: None,
+                        response_stream: None,
+                        ingress_protocol: None,
                     },
                     security: None,
+                    transformation: None,
                 },
                 RouteRequestData {
                     method: MethodPattern::Post,
@@ -673,10 +679,14 @@ impl ThroughputBenchmark {
                         idempotency_key: None,
                         binding_type: Some(GatewayBindingType::Default),
                         invocation_context: None,
+                        response_stream: None,
+                        ingress_protocol: None,
                     },
                     security: None,
+                    transformation: None,
                 },
             ],
+            rib_execution_limits: None,
         };
 
         let admin = self.deps.admin().await;
@@ -692,6 +702,7 @@ impl ThroughputBenchmark {
             api_definitions: vec![ApiDefinitionInfo {
                 id: api_definition_id.clone(),
                 version: "1".to_string(),
+                traffic_split: None,
             }],
             site: ApiSite {
                 host: format!(
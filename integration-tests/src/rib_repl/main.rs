@@ -26,6 +26,7 @@ async fn main() {
         }),
         prompt: None,
         command_registry: None,
+        lint_config: None,
     })
     .await
     .expect("Failed to bootstrap REPL");
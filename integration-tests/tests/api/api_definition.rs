@@ -70,9 +70,13 @@ async fn create_and_get_api_definition(deps: &EnvBasedTestDependencies) {
                 idempotency_key: None,
                 binding_type: Some(GatewayBindingType::Default),
                 invocation_context: None,
+                response_stream: None,
+                ingress_protocol: None,
             },
             security: None,
+            transformation: None,
         }],
+        rib_execution_limits: None,
     };
 
     let project = admin.default_project().await;
@@ -145,9 +149,13 @@ async fn get_api_definition_versions(deps: &EnvBasedTestDependencies) {
                 idempotency_key: None,
                 binding_type: Some(GatewayBindingType::Default),
                 invocation_context: None,
+                response_stream: None,
+                ingress_protocol: None,
             },
             security: None,
+            transformation: None,
         }],
+        rib_execution_limits: None,
     };
 
     let request_2 = HttpApiDefinitionRequest {
@@ -183,8 +191,11 @@ async fn get_api_definition_versions(deps: &EnvBasedTestDependencies) {
                     idempotency_key: None,
                     binding_type: Some(GatewayBindingType::Default),
                     invocation_context: None,
+                    response_stream: None,
+                    ingress_protocol: None,
                 },
                 security: None,
+                transformation: None,
             },
             RouteRequestData {
                 method: MethodPattern::Patch,
@@ -213,10 +224,14 @@ async fn get_api_definition_versions(deps: &EnvBasedTestDependencies) {
                     idempotency_key: None,
                     binding_type: Some(GatewayBindingType::Default),
                     invocation_context: None,
+                    response_stream: None,
+                    ingress_protocol: None,
                 },
                 security: None,
+                transformation: None,
             },
         ],
+        rib_execution_limits: None,
     };
 
     let project = admin.default_project().await;
@@ -337,9 +352,13 @@ async fn get_api_definition_all_versions(deps: &EnvBasedTestDependencies) {
                 idempotency_key: None,
                 binding_type: Some(GatewayBindingType::Default),
                 invocation_context: None,
+                response_stream: None,
+                ingress_protocol: None,
             },
             security: None,
+            transformation: None,
         }],
+        rib_execution_limits: None,
     };
 
     let request_1_2 = HttpApiDefinitionRequest {
@@ -379,9 +398,13 @@ async fn get_api_definition_all_versions(deps: &EnvBasedTestDependencies) {
                 idempotency_key: None,
                 binding_type: Some(GatewayBindingType::Default),
                 invocation_context: None,
+                response_stream: None,
+                ingress_protocol: None,
             },
             security: None,
+            transformation: None,
         }],
+        rib_execution_limits: None,
     };
 
     let request_2_2 = HttpApiDefinitionRequest {
@@ -796,9 +819,13 @@ async fn test_export_openapi_spec_simple(deps: &EnvBasedTestDependencies) {
                 idempotency_key: None,
                 binding_type: Some(GatewayBindingType::Default),
                 invocation_context: None,
+                response_stream: None,
+                ingress_protocol: None,
             },
             security: None,
+            transformation: None,
         }],
+        rib_execution_limits: None,
     };
 
     // Create the API definition
@@ -890,9 +917,13 @@ async fn test_roundtrip_api_definition(deps: &EnvBasedTestDependencies) {
                 idempotency_key: None,
                 binding_type: Some(GatewayBindingType::Default),
                 invocation_context: None,
+                response_stream: None,
+                ingress_protocol: None,
             },
             security: None,
+            transformation: None,
         }],
+        rib_execution_limits: None,
     };
 
     // 2. Create the API definition
@@ -76,10 +76,12 @@ async fn create_and_get_api_deployment(deps: &EnvBasedTestDependencies) {
             ApiDefinitionInfo {
                 id: api_definition_1.id.clone(),
                 version: api_definition_1.version.clone(),
+                traffic_split: None,
             },
             ApiDefinitionInfo {
                 id: api_definition_2.id.clone(),
                 version: api_definition_2.version.clone(),
+                traffic_split: None,
             },
         ],
         site: ApiSite {
@@ -121,10 +123,12 @@ async fn create_and_get_api_deployment(deps: &EnvBasedTestDependencies) {
             ApiDefinitionInfo {
                 id: api_definition_2.id.clone(),
                 version: api_definition_2.version.clone(),
+                traffic_split: None,
             },
             ApiDefinitionInfo {
                 id: api_definition_3.id.clone(),
                 version: api_definition_3.version.clone(),
+                traffic_split: None,
             },
         ],
         site: ApiSite {
@@ -140,14 +144,17 @@ async fn create_and_get_api_deployment(deps: &EnvBasedTestDependencies) {
             ApiDefinitionInfo {
                 id: api_definition_1.id.clone(),
                 version: api_definition_1.version.clone(),
+                traffic_split: None,
             },
             ApiDefinitionInfo {
                 id: api_definition_2.id.clone(),
                 version: api_definition_2.version.clone(),
+                traffic_split: None,
             },
             ApiDefinitionInfo {
                 id: api_definition_3.id.clone(),
                 version: api_definition_3.version.clone(),
+                traffic_split: None,
             },
         ],
         site: ApiSite {
@@ -225,6 +232,7 @@ async fn create_api_deployment_and_update_component(deps: &EnvBasedTestDependenc
         api_definitions: vec![ApiDefinitionInfo {
             id: api_definition_1.id.clone(),
             version: api_definition_1.version.clone(),
+            traffic_split: None,
         }],
         site: ApiSite {
             host: "localhost".to_string(),
@@ -317,6 +325,7 @@ async fn create_multiple_api_deployments_and_update_component_1(deps: &EnvBasedT
         api_definitions: vec![ApiDefinitionInfo {
             id: api_definition.id.clone(),
             version: api_definition.version.clone(),
+            traffic_split: None,
         }],
         site: ApiSite {
             host: "domain1".to_string(),
@@ -329,6 +338,7 @@ async fn create_multiple_api_deployments_and_update_component_1(deps: &EnvBasedT
         api_definitions: vec![ApiDefinitionInfo {
             id: api_definition.id.clone(),
             version: api_definition.version.clone(),
+            traffic_split: None,
         }],
         site: ApiSite {
             host: "domain2".to_string(),
@@ -460,6 +470,7 @@ async fn create_multiple_api_deployments_and_update_component_2(deps: &EnvBasedT
         api_definitions: vec![ApiDefinitionInfo {
             id: api_definition1.id.clone(),
             version: api_definition1.version.clone(),
+            traffic_split: None,
         }],
         site: ApiSite {
             host: "domain3".to_string(),
@@ -472,6 +483,7 @@ async fn create_multiple_api_deployments_and_update_component_2(deps: &EnvBasedT
         api_definitions: vec![ApiDefinitionInfo {
             id: api_definition2.id.clone(),
             version: api_definition2.version.clone(),
+            traffic_split: None,
         }],
         site: ApiSite {
             host: "domain4".to_string(),
@@ -577,6 +589,7 @@ async fn get_all_api_deployments(deps: &EnvBasedTestDependencies) {
                 api_definitions: vec![ApiDefinitionInfo {
                     id: api_definition_1.id.clone(),
                     version: api_definition_1.version.clone(),
+                    traffic_split: None,
                 }],
                 site: ApiSite {
                     host: "domain".to_string(),
@@ -595,6 +608,7 @@ async fn get_all_api_deployments(deps: &EnvBasedTestDependencies) {
                 api_definitions: vec![ApiDefinitionInfo {
                     id: api_definition_1.id.clone(),
                     version: api_definition_1.version.clone(),
+                    traffic_split: None,
                 }],
                 site: ApiSite {
                     host: "domain".to_string(),
@@ -613,6 +627,7 @@ async fn get_all_api_deployments(deps: &EnvBasedTestDependencies) {
                 api_definitions: vec![ApiDefinitionInfo {
                     id: api_definition_2.id.clone(),
                     version: api_definition_2.version.clone(),
+                    traffic_split: None,
                 }],
                 site: ApiSite {
                     host: "other-domain".to_string(),
@@ -711,9 +726,13 @@ async fn create_api_definition_without_worker_calls(
                         idempotency_key: None,
                         binding_type: Some(GatewayBindingType::Default),
                         invocation_context: None,
+                        response_stream: None,
+                        ingress_protocol: None,
                     },
                     security: None,
+                    transformation: None,
                 }],
+                rib_execution_limits: None,
             },
         )
         .await
@@ -763,9 +782,13 @@ async fn create_api_definition(
                         idempotency_key: None,
                         binding_type: Some(GatewayBindingType::Default),
                         invocation_context: None,
+                        response_stream: None,
+                        ingress_protocol: None,
                     },
                     security: None,
+                    transformation: None,
                 }],
+                rib_execution_limits: None,
             },
         )
         .await
@@ -815,10 +838,12 @@ async fn undeploy_api_test(deps: &EnvBasedTestDependencies) {
                     ApiDefinitionInfo {
                         id: api_definition_1.id.clone(),
                         version: api_definition_1.version.clone(),
+                        traffic_split: None,
                     },
                     ApiDefinitionInfo {
                         id: api_definition_2.id.clone(),
                         version: api_definition_2.version.clone(),
+                        traffic_split: None,
                     },
                 ],
                 site: ApiSite {
@@ -841,12 +866,14 @@ async fn undeploy_api_test(deps: &EnvBasedTestDependencies) {
         .any(|d| d.api_definitions.contains(&ApiDefinitionInfo {
             id: api_definition_1.id.clone(),
             version: api_definition_1.version.clone(),
+            traffic_split: None,
         })));
     check!(deployments
         .iter()
         .any(|d| d.api_definitions.contains(&ApiDefinitionInfo {
             id: api_definition_2.id.clone(),
             version: api_definition_2.version.clone(),
+            traffic_split: None,
         })));
 
     // Undeploy API 1
@@ -872,6 +899,7 @@ async fn undeploy_api_test(deps: &EnvBasedTestDependencies) {
         .any(|d| d.api_definitions.contains(&ApiDefinitionInfo {
             id: api_definition_1.id.clone(),
             version: api_definition_1.version.clone(),
+            traffic_split: None,
         })));
 
     // Verify that API 2 is still in the deployments
@@ -880,6 +908,7 @@ async fn undeploy_api_test(deps: &EnvBasedTestDependencies) {
         .any(|d| d.api_definitions.contains(&ApiDefinitionInfo {
             id: api_definition_2.id.clone(),
             version: api_definition_2.version.clone(),
+            traffic_split: None,
         })));
 
     // Test undeploying from a non-existent API
@@ -941,6 +970,7 @@ async fn undeploy_component_constraint_test(deps: &EnvBasedTestDependencies) {
         api_definitions: vec![ApiDefinitionInfo {
             id: api_definition_1.id.clone(),
             version: api_definition_1.version.clone(),
+            traffic_split: None,
         }],
         site: ApiSite {
             host: "localhost".to_string(),
@@ -1051,9 +1081,13 @@ async fn create_and_invoke_api_deployment_with_agent(
                         idempotency_key: None,
                         binding_type: Some(GatewayBindingType::Default),
                         invocation_context: None,
+                        response_stream: None,
+                        ingress_protocol: None,
                     },
                     security: None,
+                    transformation: None,
                 }],
+                rib_execution_limits: None,
             },
         )
         .await?;
@@ -1067,6 +1101,7 @@ async fn create_and_invoke_api_deployment_with_agent(
             api_definitions: vec![ApiDefinitionInfo {
                 id: api_definition.id.clone(),
                 version: api_definition.version.clone(),
+                traffic_split: None,
             }],
             site: ApiSite {
                 host: format!("127.0.0.1:{custom_request_port}"),
@@ -1151,9 +1186,13 @@ async fn rib_api_mapping_correctly_encodes_strings(
                         idempotency_key: None,
                         binding_type: Some(GatewayBindingType::Default),
                         invocation_context: None,
+                        response_stream: None,
+                        ingress_protocol: None,
                     },
                     security: None,
+                    transformation: None,
                 }],
+                rib_execution_limits: None,
             },
         )
         .await?;
@@ -1167,6 +1206,7 @@ async fn rib_api_mapping_correctly_encodes_strings(
             api_definitions: vec![ApiDefinitionInfo {
                 id: api_definition.id.clone(),
                 version: api_definition.version.clone(),
+                traffic_split: None,
             }],
             site: ApiSite {
                 host: format!("127.0.0.1:{custom_request_port}"),
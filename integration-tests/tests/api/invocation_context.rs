@@ -150,9 +150,13 @@ async fn invocation_context_test(deps: &EnvBasedTestDependencies) {
                     "#
                     .to_string(),
                 ),
+                response_stream: None,
+                ingress_protocol: None,
             },
             security: None,
+            transformation: None,
         }],
+        rib_execution_limits: None,
     };
 
     let project_id = admin.default_project().await;
@@ -168,6 +172,7 @@ async fn invocation_context_test(deps: &EnvBasedTestDependencies) {
         api_definitions: vec![ApiDefinitionInfo {
             id: api_definition_id,
             version: "1".to_string(),
+            traffic_split: None,
         }],
         site: ApiSite {
             host: format!(
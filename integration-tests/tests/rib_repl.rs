@@ -71,6 +71,7 @@ async fn test_repl_invoking_functions(deps: &EnvBasedTestDependencies, worker_na
         }),
         prompt: None,
         command_registry: None,
+        lint_config: None,
     })
     .await
     .expect("Failed to bootstrap REPL");
@@ -189,6 +190,7 @@ async fn test_repl_invoking_resource_methods(
         }),
         prompt: None,
         command_registry: None,
+        lint_config: None,
     })
     .await
     .expect("Failed to bootstrap REPL");